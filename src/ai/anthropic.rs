@@ -0,0 +1,191 @@
+//! Native Anthropic Messages API provider
+//!
+//! Calls `POST /v1/messages` directly, so Claude works without the `claude`
+//! CLI installed. Replies are streamed as server-sent events and API errors
+//! are mapped to actionable messages.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AnthropicConfig, ClaudeModel};
+use crate::http::AI_AGENT;
+
+const MESSAGES_URL: &str = "https://api.anthropic.com/v1/messages";
+const API_VERSION: &str = "2023-06-01";
+const MAX_TOKENS: u32 = 4096;
+
+#[derive(Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    max_tokens: u32,
+    messages: Vec<Message<'a>>,
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    delta: Option<TextDelta>,
+    error: Option<ErrorBody>,
+}
+
+#[derive(Deserialize)]
+struct TextDelta {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+/// Send a single-turn prompt and stream Claude's reply
+pub fn complete(
+    config: &AnthropicConfig,
+    model: ClaudeModel,
+    prompt: &str,
+    on_chunk: &mut dyn FnMut(&str) -> bool,
+) -> Result<String> {
+    let Some(api_key) = config.resolve_api_key() else {
+        bail!(
+            "No Anthropic API key found. Set ANTHROPIC_API_KEY, ai.anthropic.api_key, \
+             or store it in the OS keyring (service 'hoards', key 'anthropic')"
+        );
+    };
+
+    let body = MessagesRequest {
+        model: model.api_id(),
+        max_tokens: MAX_TOKENS,
+        messages: vec![Message {
+            role: "user",
+            content: prompt,
+        }],
+        stream: true,
+    };
+
+    let mut response = AI_AGENT
+        .post(MESSAGES_URL)
+        .header("x-api-key", &api_key)
+        .header("anthropic-version", API_VERSION)
+        .send_json(&body)
+        .context("Failed to reach the Anthropic API")?;
+    let status = response.status().as_u16();
+
+    if !(200..300).contains(&status) {
+        let text = response.body_mut().read_to_string().unwrap_or_default();
+        bail!("{}", map_error(status, &text, model));
+    }
+
+    super::stream_lines(
+        response.into_body(),
+        |line| parse_stream_line(line, model),
+        on_chunk,
+    )
+}
+
+/// Extract the text delta from one server-sent event line
+///
+/// Errors can arrive mid-stream (e.g. overload), so `error` events are
+/// mapped the same way as HTTP errors.
+fn parse_stream_line(line: &str, model: ClaudeModel) -> Result<Option<String>> {
+    let Some(data) = line.strip_prefix("data:") else {
+        return Ok(None);
+    };
+
+    let event: StreamEvent =
+        serde_json::from_str(data.trim()).context("Failed to parse Anthropic response")?;
+    match event.kind.as_str() {
+        "content_block_delta" => Ok(event.delta.and_then(|delta| delta.text)),
+        "error" => {
+            let error = event.error.map(|e| (e.kind, e.message)).unwrap_or_default();
+            let body = serde_json::json!({ "error": { "type": error.0, "message": error.1 } });
+            bail!("{}", map_error(0, &body.to_string(), model))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Turn an API error into an actionable message
+fn map_error(status: u16, body: &str, model: ClaudeModel) -> String {
+    let (kind, message) = match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(err) => (err.error.kind, err.error.message),
+        Err(_) => (String::new(), body.chars().take(200).collect()),
+    };
+
+    match (status, kind.as_str()) {
+        (401, _) | (_, "authentication_error") => {
+            "Anthropic API key was rejected (check ANTHROPIC_API_KEY)".to_string()
+        }
+        (403, _) | (_, "permission_error") => {
+            format!("Anthropic API key lacks permission: {}", message)
+        }
+        (404, _) | (_, "not_found_error") => format!(
+            "Model '{}' not available to this API key: {}",
+            model.api_id(),
+            message
+        ),
+        (429, _) | (_, "rate_limit_error") => {
+            "Rate limited by the Anthropic API; try again later".to_string()
+        }
+        (529, _) | (_, "overloaded_error") => {
+            "Anthropic API is overloaded; try again shortly".to_string()
+        }
+        _ => format!("Anthropic API error ({}): {}", status, message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_line() {
+        let model = ClaudeModel::Sonnet;
+        let delta = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#;
+        assert_eq!(
+            parse_stream_line(delta, model).unwrap().as_deref(),
+            Some("Hello")
+        );
+
+        let ping = r#"data: {"type":"ping"}"#;
+        assert_eq!(parse_stream_line(ping, model).unwrap(), None);
+        assert_eq!(
+            parse_stream_line("event: content_block_delta", model).unwrap(),
+            None
+        );
+
+        let error =
+            r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        let err = parse_stream_line(error, model).unwrap_err();
+        assert!(err.to_string().contains("overloaded"));
+    }
+
+    #[test]
+    fn test_map_error() {
+        let auth = r#"{"type":"error","error":{"type":"authentication_error","message":"invalid x-api-key"}}"#;
+        assert!(map_error(401, auth, ClaudeModel::Sonnet).contains("rejected"));
+
+        let overloaded =
+            r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert!(map_error(529, overloaded, ClaudeModel::Sonnet).contains("overloaded"));
+
+        let missing = r#"{"type":"error","error":{"type":"not_found_error","message":"model: x"}}"#;
+        assert!(map_error(404, missing, ClaudeModel::Opus).contains("claude-opus"));
+
+        assert!(map_error(500, "Internal error", ClaudeModel::Haiku).contains("(500)"));
+    }
+}
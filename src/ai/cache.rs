@@ -0,0 +1,153 @@
+//! Keyed AI response cache with a time-to-live per feature
+//!
+//! Responses are stored in the `ai_cache` table under `<prefix><key>`, where
+//! the key is either something stable (a tool name for cheatsheets) or a hash
+//! of the full prompt, so re-running a command with the same input skips the
+//! provider. Each feature has a default TTL that can be overridden with
+//! `ai.cache_ttl_days` in the config (0 disables caching for that feature).
+
+use anyhow::Result;
+
+use super::embeddings::content_hash;
+use crate::config::HoardConfig;
+use crate::db::Database;
+
+/// A feature whose AI responses are cached
+#[derive(Debug, Clone, Copy)]
+pub struct CacheFeature {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Key prefix in the `ai_cache` table
+    pub prefix: &'static str,
+    pub default_ttl_days: u64,
+}
+
+pub const DESCRIBE: CacheFeature = CacheFeature {
+    name: "describe",
+    description: "Tool descriptions (ai enrich --describe)",
+    prefix: "ai:describe:",
+    default_ttl_days: 30,
+};
+
+pub const CATEGORIZE: CacheFeature = CacheFeature {
+    name: "categorize",
+    description: "Tool categories (ai enrich --categorize)",
+    prefix: "ai:categorize:",
+    default_ttl_days: 30,
+};
+
+pub const DISCOVERY: CacheFeature = CacheFeature {
+    name: "discovery",
+    description: "Tool recommendations (ai discover)",
+    prefix: "ai:discovery:",
+    default_ttl_days: 7,
+};
+
+pub const CHEATSHEET: CacheFeature = CacheFeature {
+    name: "cheatsheet",
+    description: "Tool and bundle cheatsheets (ai cheatsheet)",
+    prefix: "cheatsheet:",
+    default_ttl_days: 90,
+};
+
+pub const UPDATES_SUMMARY: CacheFeature = CacheFeature {
+    name: "updates_summary",
+    description: "Release-note digests (ai updates-summary)",
+    prefix: "updates-summary:",
+    default_ttl_days: 90,
+};
+
+/// All cached features
+pub const FEATURES: &[CacheFeature] =
+    &[DESCRIBE, CATEGORIZE, DISCOVERY, CHEATSHEET, UPDATES_SUMMARY];
+
+/// Find a cached feature by name
+pub fn find(name: &str) -> Option<&'static CacheFeature> {
+    FEATURES.iter().find(|f| f.name == name)
+}
+
+impl CacheFeature {
+    /// Configured TTL in days (0 means caching is disabled)
+    pub fn ttl_days(&self, config: &HoardConfig) -> u64 {
+        config
+            .ai
+            .cache_ttl_days
+            .get(self.name)
+            .copied()
+            .unwrap_or(self.default_ttl_days)
+    }
+
+    /// Cache key for a prompt
+    pub fn prompt_key(&self, prompt: &str) -> String {
+        format!("{}{}", self.prefix, content_hash(prompt))
+    }
+
+    /// Look up an entry that hasn't expired
+    pub fn get(&self, db: &Database, key: &str) -> Option<String> {
+        let ttl = self.ttl_days(&HoardConfig::load().unwrap_or_default());
+        if ttl == 0 {
+            return None;
+        }
+        db.get_ai_cache_fresh(key, Some(chrono::Duration::days(ttl as i64)))
+            .ok()
+            .flatten()
+    }
+
+    /// Store an entry (skipped when caching is disabled for the feature)
+    pub fn set(&self, db: &Database, key: &str, content: &str) -> Result<()> {
+        if self.ttl_days(&HoardConfig::load().unwrap_or_default()) > 0 {
+            db.set_ai_cache(key, content)?;
+        }
+        Ok(())
+    }
+
+    /// Delete every cached entry of this feature
+    pub fn clear(&self, db: &Database) -> Result<usize> {
+        db.clear_ai_cache(Some(self.prefix))
+    }
+
+    /// Invoke the AI unless the same prompt has a fresh cached response
+    ///
+    /// Only responses that `parse` accepts are cached. Returns the parsed
+    /// value and whether it came from the cache.
+    pub fn invoke<T>(
+        &self,
+        db: &Database,
+        prompt: &str,
+        parse: impl Fn(&str) -> Result<T>,
+    ) -> Result<(T, bool)> {
+        let key = self.prompt_key(prompt);
+        if let Some(parsed) = self.get(db, &key).and_then(|cached| parse(&cached).ok()) {
+            return Ok((parsed, true));
+        }
+
+        let response = super::invoke_ai(self.name, prompt)?;
+        let parsed = parse(&response)?;
+        self.set(db, &key, &response)?;
+        Ok((parsed, false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_override() {
+        let mut config = HoardConfig::default();
+        assert_eq!(DISCOVERY.ttl_days(&config), 7);
+        config.ai.cache_ttl_days.insert("discovery".to_string(), 0);
+        assert_eq!(DISCOVERY.ttl_days(&config), 0);
+        assert_eq!(DESCRIBE.ttl_days(&config), 30);
+    }
+
+    #[test]
+    fn test_prompt_keys_are_prefixed_and_stable() {
+        let key = DESCRIBE.prompt_key("describe rg");
+        assert!(key.starts_with("ai:describe:"));
+        assert_eq!(key, DESCRIBE.prompt_key("describe rg"));
+        assert_ne!(key, DESCRIBE.prompt_key("describe fd"));
+        assert!(find("cheatsheet").is_some());
+        assert!(find("nope").is_none());
+    }
+}
@@ -0,0 +1,253 @@
+//! Release-note digests for pending updates
+//!
+//! Release notes between the installed and the latest version are fetched
+//! from the tool's forge and summarized by the AI into one prioritized entry per tool.
+//! Entries are cached per (tool, installed, latest) version pair, so later
+//! runs only send updates that appeared since.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+use crate::forge::RepoRef;
+use crate::updates::Update;
+
+/// Release notes kept per tool in the prompt
+const MAX_NOTES_CHARS: usize = 4000;
+
+/// An update waiting to be summarized
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingUpdate {
+    pub tool: String,
+    pub source: String,
+    pub current: String,
+    pub latest: String,
+    /// Source repository, if known
+    pub repo: Option<RepoRef>,
+}
+
+impl PendingUpdate {
+    fn cache_key(&self) -> String {
+        format!(
+            "{}{}:{}:{}",
+            super::cache::UPDATES_SUMMARY.prefix,
+            self.tool,
+            self.current,
+            self.latest
+        )
+    }
+}
+
+/// How urgently an update should be applied, most urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase", from = "String")]
+pub enum UpdatePriority {
+    Security,
+    Breaking,
+    Recommended,
+    Optional,
+    Skip,
+}
+
+/// Unknown priorities from the AI fall back to `Optional`
+impl From<String> for UpdatePriority {
+    fn from(value: String) -> Self {
+        match value.to_lowercase().as_str() {
+            "security" => UpdatePriority::Security,
+            "breaking" => UpdatePriority::Breaking,
+            "recommended" => UpdatePriority::Recommended,
+            "skip" => UpdatePriority::Skip,
+            _ => UpdatePriority::Optional,
+        }
+    }
+}
+
+impl UpdatePriority {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UpdatePriority::Security => "security",
+            UpdatePriority::Breaking => "breaking",
+            UpdatePriority::Recommended => "recommended",
+            UpdatePriority::Optional => "optional",
+            UpdatePriority::Skip => "skip",
+        }
+    }
+}
+
+/// AI summary of one pending update
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateNote {
+    pub tool: String,
+    pub priority: UpdatePriority,
+    pub summary: String,
+}
+
+#[derive(Deserialize)]
+struct SummaryResponse {
+    updates: Vec<UpdateNote>,
+}
+
+/// Attach known source repositories to available updates
+pub fn pending_updates<'a>(
+    db: &Database,
+    updates: impl IntoIterator<Item = &'a Update>,
+) -> Result<Vec<PendingUpdate>> {
+    updates
+        .into_iter()
+        .map(|update| {
+            let repo = db.get_github_info(&update.name)?.map(|gh| gh.repo_ref());
+            Ok(PendingUpdate {
+                tool: update.name.clone(),
+                source: update.source.clone(),
+                current: update.current.clone(),
+                latest: update.latest.clone(),
+                repo,
+            })
+        })
+        .collect()
+}
+
+/// Split updates into cached notes and updates that still need the AI
+pub fn cached_notes(
+    db: &Database,
+    pending: Vec<PendingUpdate>,
+) -> (Vec<UpdateNote>, Vec<PendingUpdate>) {
+    let mut notes = Vec::new();
+    let mut uncached = Vec::new();
+    for update in pending {
+        let cached = super::cache::UPDATES_SUMMARY
+            .get(db, &update.cache_key())
+            .and_then(|json| serde_json::from_str::<UpdateNote>(&json).ok());
+        match cached {
+            Some(note) => notes.push(note),
+            None => uncached.push(update),
+        }
+    }
+    (notes, uncached)
+}
+
+/// Cache fresh notes under their update's version pair
+pub fn cache_notes(db: &Database, pending: &[PendingUpdate], notes: &[UpdateNote]) -> Result<()> {
+    for note in notes {
+        if let Some(update) = pending.iter().find(|u| u.tool == note.tool) {
+            super::cache::UPDATES_SUMMARY.set(
+                db,
+                &update.cache_key(),
+                &serde_json::to_string(note)?,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Build the summary prompt, fetching release notes for each update
+pub fn summary_prompt(pending: &[PendingUpdate]) -> String {
+    let sections: Vec<String> = pending
+        .iter()
+        .map(|update| {
+            let notes = update
+                .repo
+                .as_ref()
+                .and_then(|repo| repo.release_notes(&update.current, &update.latest).ok())
+                .map(|releases| {
+                    releases
+                        .into_iter()
+                        .map(|r| format!("### {}\n{}", r.tag_name, r.body.unwrap_or_default()))
+                        .collect::<Vec<_>>()
+                        .join("\n\n")
+                })
+                .filter(|notes| !notes.trim().is_empty())
+                .unwrap_or_else(|| "(no release notes found)".to_string());
+            format!(
+                "## {} ({}) {} -> {}\n{}",
+                update.tool,
+                update.source,
+                update.current,
+                update.latest,
+                truncate_notes(&notes)
+            )
+        })
+        .collect();
+
+    super::prompts::UPDATES_SUMMARY.render(&[("UPDATES", &sections.join("\n\n"))])
+}
+
+fn truncate_notes(notes: &str) -> String {
+    match notes.char_indices().nth(MAX_NOTES_CHARS) {
+        Some((end, _)) => format!("{}\n[release notes truncated]", &notes[..end]),
+        None => notes.to_string(),
+    }
+}
+
+/// Parse the AI digest
+pub fn parse_summary(response: &str) -> Result<Vec<UpdateNote>> {
+    let json_str = super::extract_json_object(response)?;
+    let parsed: SummaryResponse =
+        serde_json::from_str(&json_str).context("Failed to parse updates summary")?;
+    Ok(parsed.updates)
+}
+
+/// Order notes by priority, then tool name
+pub fn sort_notes(notes: &mut [UpdateNote]) {
+    notes.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.tool.cmp(&b.tool)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(tool: &str, current: &str, latest: &str) -> PendingUpdate {
+        PendingUpdate {
+            tool: tool.to_string(),
+            source: "cargo".to_string(),
+            current: current.to_string(),
+            latest: latest.to_string(),
+            repo: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_and_sort_summary() {
+        let response = r#"Sure:
+{"updates": [
+  {"tool": "fd", "priority": "skip", "summary": "Docs only"},
+  {"tool": "bat", "priority": "breaking", "summary": "--style defaults changed"},
+  {"tool": "jq", "priority": "security", "summary": "Fixes CVE-2024-1234"},
+  {"tool": "rg", "priority": "whatever", "summary": "Minor fixes"}
+]}"#;
+        let mut notes = parse_summary(response).unwrap();
+        assert_eq!(notes[3].priority, UpdatePriority::Optional);
+
+        sort_notes(&mut notes);
+        let order: Vec<&str> = notes.iter().map(|n| n.tool.as_str()).collect();
+        assert_eq!(order, vec!["jq", "bat", "rg", "fd"]);
+    }
+
+    #[test]
+    fn test_notes_cached_per_version_pair() {
+        let db = Database::open_in_memory().unwrap();
+        let bat = pending("bat", "0.23.0", "0.24.0");
+        let note = UpdateNote {
+            tool: "bat".to_string(),
+            priority: UpdatePriority::Recommended,
+            summary: "Faster paging".to_string(),
+        };
+        cache_notes(&db, std::slice::from_ref(&bat), std::slice::from_ref(&note)).unwrap();
+
+        let (notes, uncached) = cached_notes(&db, vec![bat, pending("fd", "9.0.0", "10.0.0")]);
+        assert_eq!(notes, vec![note]);
+        assert_eq!(uncached.len(), 1);
+
+        // A newer release is a different version pair
+        let (notes, uncached) = cached_notes(&db, vec![pending("bat", "0.23.0", "0.25.0")]);
+        assert!(notes.is_empty());
+        assert_eq!(uncached.len(), 1);
+    }
+
+    #[test]
+    fn test_truncate_notes() {
+        let long = "é".repeat(MAX_NOTES_CHARS + 10);
+        assert!(truncate_notes(&long).ends_with("[release notes truncated]"));
+        assert_eq!(truncate_notes("short"), "short");
+    }
+}
@@ -0,0 +1,362 @@
+//! Embeddings for similar-tool search
+//!
+//! Each tool's name, description, category, labels, GitHub metadata and the
+//! start of its cached README are embedded once and stored in the database;
+//! similarity is the cosine between
+//! stored vectors. The default `local` backend is an offline hashed
+//! bag-of-words model, while `openai` and `ollama` call the provider's
+//! embedding endpoint for semantic matches.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AiConfig, EmbeddingProvider};
+use crate::db::{Database, GitHubInfo};
+use crate::http::AI_AGENT;
+use crate::models::Tool;
+
+/// Dimensions of the local hashed embedding
+const LOCAL_DIMENSIONS: usize = 256;
+
+/// Texts sent per provider request
+const BATCH_SIZE: usize = 64;
+
+/// Characters of a cached README added to a tool's text
+const README_CHARS: usize = 2000;
+
+/// Words too common in tool descriptions to say anything about similarity
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "for", "in", "is", "of", "on", "the", "to", "with", "your", "you", "that",
+    "tool", "tools", "cli", "command", "line",
+];
+
+#[derive(Serialize)]
+struct EmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAiEmbedding {
+    embedding: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Build the text embedded for a tool
+///
+/// Only the first [`README_CHARS`] characters of `readme` are used; the
+/// opening of a README says what the tool is for.
+pub fn tool_document(
+    tool: &Tool,
+    labels: &[String],
+    github: Option<&GitHubInfo>,
+    readme: Option<&str>,
+) -> String {
+    let mut parts = vec![tool.name.clone()];
+    if let Some(binary) = tool.binary_name.as_ref().filter(|b| **b != tool.name) {
+        parts.push(binary.clone());
+    }
+    parts.extend(tool.description.clone());
+    parts.extend(tool.category.clone());
+    if !labels.is_empty() {
+        parts.push(labels.join(" "));
+    }
+    if let Some(gh) = github {
+        parts.extend(gh.description.clone());
+        parts.extend(gh.language.clone());
+    }
+    if let Some(readme) = readme.map(str::trim).filter(|r| !r.is_empty()) {
+        parts.push(readme.chars().take(README_CHARS).collect());
+    }
+    parts.join("\n")
+}
+
+/// Stable FNV-1a hash used to detect changed tool text
+pub fn content_hash(text: &str) -> String {
+    format!("{:016x}", fnv1a(text.as_bytes()))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Offline embedding: hashed word and character-trigram counts
+///
+/// Trigrams let related spellings meet (`json`/`jsonl`, `grep`/`ripgrep`).
+pub fn local_embed(text: &str) -> Vec<f32> {
+    let mut vector = vec![0f32; LOCAL_DIMENSIONS];
+    let lowered = text.to_lowercase();
+    let words = lowered
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 1 && !STOPWORDS.contains(w));
+
+    let mut add = |feature: &str, weight: f32| {
+        let hash = fnv1a(feature.as_bytes());
+        let index = (hash % LOCAL_DIMENSIONS as u64) as usize;
+        let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+        vector[index] += sign * weight;
+    };
+
+    for word in words {
+        add(word, 1.0);
+        let chars: Vec<char> = format!("^{}$", word).chars().collect();
+        for trigram in chars.windows(3) {
+            add(&trigram.iter().collect::<String>(), 0.5);
+        }
+    }
+
+    normalize(&mut vector);
+    vector
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+/// Cosine similarity between two vectors (0 when sizes differ)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+/// Embed a batch of texts with the configured backend
+pub fn embed(config: &AiConfig, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let model = config.embeddings.model_name();
+    let vectors = match config.embeddings.provider {
+        EmbeddingProvider::Local => texts.iter().map(|t| local_embed(t)).collect(),
+        EmbeddingProvider::OpenAi => {
+            let url = format!(
+                "{}/embeddings",
+                config.openai.base_url.trim_end_matches('/')
+            );
+            let mut request = AI_AGENT.post(&url);
+            if let Some(key) = config.openai.resolve_api_key() {
+                request = request.header("Authorization", format!("Bearer {}", key));
+            }
+            let mut response = request
+                .send_json(&EmbedRequest {
+                    model,
+                    input: texts,
+                })
+                .with_context(|| format!("Failed to reach {}", url))?;
+            if !response.status().is_success() {
+                let body = response.body_mut().read_to_string().unwrap_or_default();
+                bail!(
+                    "Embedding API error ({}): {}",
+                    response.status().as_u16(),
+                    body.chars().take(200).collect::<String>()
+                );
+            }
+            let parsed: OpenAiEmbedResponse = response
+                .body_mut()
+                .read_json()
+                .context("Failed to parse embedding response")?;
+            parsed.data.into_iter().map(|d| d.embedding).collect()
+        }
+        EmbeddingProvider::Ollama => {
+            let url = format!("{}/api/embed", config.ollama.host.trim_end_matches('/'));
+            let mut response = AI_AGENT
+                .post(&url)
+                .send_json(&EmbedRequest {
+                    model,
+                    input: texts,
+                })
+                .with_context(|| format!("Failed to reach Ollama at {}", config.ollama.host))?;
+            if !response.status().is_success() {
+                let body = response.body_mut().read_to_string().unwrap_or_default();
+                bail!(
+                    "Ollama embedding error ({}): {} (try 'ollama pull {}')",
+                    response.status().as_u16(),
+                    body.trim(),
+                    model
+                );
+            }
+            let parsed: OllamaEmbedResponse = response
+                .body_mut()
+                .read_json()
+                .context("Failed to parse Ollama embedding response")?;
+            parsed.embeddings
+        }
+    };
+
+    if vectors.len() != texts.len() {
+        bail!(
+            "Embedding provider returned {} vectors for {} texts",
+            vectors.len(),
+            texts.len()
+        );
+    }
+    Ok(vectors)
+}
+
+/// Embed tools whose text changed since they were last indexed
+///
+/// Returns the number of tools (re-)embedded. `on_progress` receives
+/// (done, total) after each batch.
+pub fn update_index(
+    db: &Database,
+    config: &AiConfig,
+    on_progress: &mut dyn FnMut(usize, usize),
+) -> Result<usize> {
+    let model_id = config.embeddings.model_id();
+    let hashes = db.get_embedding_hashes(&model_id)?;
+    let labels = db.get_all_tool_labels()?;
+    let github: std::collections::HashMap<String, GitHubInfo> =
+        db.get_all_github_info()?.into_iter().collect();
+
+    let mut stale: Vec<(String, String, String)> = Vec::new();
+    for tool in db.get_all_tools()? {
+        let tool_labels = labels.get(&tool.name).map(Vec::as_slice).unwrap_or(&[]);
+        let gh = github.get(&tool.name);
+        let readme = match gh {
+            Some(gh) => db.get_cached_readme(&gh.host, &gh.repo_owner, &gh.repo_name)?,
+            None => None,
+        };
+        let document = tool_document(
+            &tool,
+            tool_labels,
+            gh,
+            readme.as_ref().map(|r| r.content.as_str()),
+        );
+        let hash = content_hash(&document);
+        if hashes.get(&tool.name) != Some(&hash) {
+            stale.push((tool.name, document, hash));
+        }
+    }
+
+    let total = stale.len();
+    let mut done = 0;
+    for batch in stale.chunks(BATCH_SIZE) {
+        let texts: Vec<String> = batch.iter().map(|(_, doc, _)| doc.clone()).collect();
+        let vectors = embed(config, &texts)?;
+        for ((name, _, hash), vector) in batch.iter().zip(vectors) {
+            db.set_embedding(name, &model_id, hash, &vector)?;
+        }
+        done += batch.len();
+        on_progress(done, total);
+    }
+
+    Ok(total)
+}
+
+/// Rank indexed tools by similarity to `tool_name`
+///
+/// Only stored vectors are used (no provider calls), compared against tools
+/// embedded with the same model. Returns `None` if the tool isn't indexed.
+pub fn similar_tools(
+    db: &Database,
+    tool_name: &str,
+    limit: usize,
+) -> Result<Option<Vec<(String, f32)>>> {
+    let Some((model, target)) = db.get_embedding(tool_name)? else {
+        return Ok(None);
+    };
+
+    let mut scored: Vec<(String, f32)> = db
+        .get_embeddings(&model)?
+        .into_iter()
+        .filter(|(name, _)| name != tool_name)
+        .map(|(name, vector)| {
+            let score = cosine_similarity(&target, &vector);
+            (name, score)
+        })
+        .filter(|(_, score)| *score > 0.0)
+        .collect();
+
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(limit);
+    Ok(Some(scored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_embed_ranks_related_text_higher() {
+        let rg = local_embed("ripgrep\nFast regex search, recursive grep replacement\nsearch");
+        let ag = local_embed("the_silver_searcher\nCode search similar to ack and grep\nsearch");
+        let htop = local_embed("htop\nInteractive process viewer\nsystem");
+
+        assert!(cosine_similarity(&rg, &ag) > cosine_similarity(&rg, &htop));
+        assert!((cosine_similarity(&rg, &rg) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_cosine_similarity_edge_cases() {
+        assert_eq!(cosine_similarity(&[], &[]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_tool_document_includes_readme() {
+        let tool = Tool::new("ripgrep").with_description("Fast grep");
+        let labels = vec!["search".to_string()];
+        assert_eq!(
+            tool_document(&tool, &labels, None, Some("  \n")),
+            "ripgrep\nFast grep\nsearch"
+        );
+
+        let readme = format!(
+            "# ripgrep\n\nRecursively searches directories{}",
+            "é".repeat(3000)
+        );
+        let document = tool_document(&tool, &labels, None, Some(&readme));
+        assert!(document.starts_with("ripgrep\nFast grep\nsearch\n# ripgrep\n"));
+        assert!(document.contains("Recursively searches directories"));
+        let readme_part = document.split_once("search\n").unwrap().1;
+        assert_eq!(readme_part.chars().count(), README_CHARS);
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash("ripgrep"), content_hash("ripgrep"));
+        assert_ne!(content_hash("ripgrep"), content_hash("ripgrep "));
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn test_index_and_similar_tools() {
+        let db = Database::open_in_memory().unwrap();
+        for (name, desc) in [
+            ("ripgrep", "Fast regex search, recursive grep"),
+            ("ugrep", "Ultra fast grep with regex search"),
+            ("htop", "Interactive process viewer"),
+        ] {
+            db.insert_tool(&Tool::new(name).with_description(desc))
+                .unwrap();
+        }
+
+        let config = AiConfig::default();
+        assert_eq!(update_index(&db, &config, &mut |_, _| {}).unwrap(), 3);
+        // Unchanged tools are not re-embedded
+        assert_eq!(update_index(&db, &config, &mut |_, _| {}).unwrap(), 0);
+
+        let similar = similar_tools(&db, "ripgrep", 5).unwrap().unwrap();
+        assert_eq!(similar[0].0, "ugrep");
+        assert!(similar_tools(&db, "missing", 5).unwrap().is_none());
+    }
+}
@@ -1,18 +1,27 @@
 //! AI provider integration for smart features
 //!
 //! Provides functions to invoke configured AI CLI tools (claude, gemini, codex, opencode)
-//! and parse their responses for categorization, description generation, and bundle suggestions.
+//! or a native HTTP API (OpenAI-compatible, Ollama, Anthropic), and parse their responses for categorization, description generation, and bundle suggestions.
 //!
-//! Prompts are loaded from `~/.config/hoards/prompts/` and can be customized by the user.
-//! If a prompt file is missing, embedded defaults are used.
+//! Prompts are loaded from `~/.config/hoards/prompts/` and can be customized by the user
+//! (see [`prompts`]). If a prompt file is missing, embedded defaults are used.
 
 use crate::config::{AiProvider, HoardConfig};
 use crate::models::{Bundle, Tool};
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
 use std::process::Command;
 
+mod anthropic;
+pub mod cache;
+pub mod changelog;
+pub mod embeddings;
+pub mod ollama;
+mod openai;
+pub mod prompts;
+
+pub use prompts::prompts_dir;
+
 // ==================== Embedded default prompts ====================
 
 const DEFAULT_CATEGORIZE_PROMPT: &str = r#"You are helping categorize CLI tools. Here are the existing categories: {{CATEGORIES}}
@@ -31,6 +40,9 @@ Example: {"ripgrep": "Fast regex search tool, replacement for grep", "bat": "Cat
 
 Tools needing descriptions:
 {{TOOLS}}
+
+What their repositories say about them, where known:
+{{DESCRIPTION}}
 "#;
 
 const DEFAULT_SUGGEST_BUNDLE_PROMPT: &str = r#"Analyze this user's CLI tools and suggest {{COUNT}} logical bundles based on their ACTUAL USAGE PATTERNS.
@@ -75,7 +87,8 @@ README content:
 {{README}}
 "#;
 
-const DEFAULT_CHEATSHEET_PROMPT: &str = r#"Create a concise CLI cheatsheet for the tool "{{TOOL_NAME}}" based on its --help output.
+const DEFAULT_CHEATSHEET_PROMPT: &str = r#"Create a concise CLI cheatsheet for the tool "{{TOOL}}" based on its --help output.
+What it does: {{DESCRIPTION}}
 
 Guidelines:
 1. Group commands by category (BASIC USAGE, FILE FILTERING, OUTPUT, etc.)
@@ -165,9 +178,18 @@ Respond with JSON:
 }
 "#;
 
-const DEFAULT_ANALYZE_PROMPT: &str = r#"Analyze this CLI usage data and provide a brief personalized insight.
+const DEFAULT_ANALYZE_PROMPT: &str = r#"Audit this user's command-line workflow and recommend concrete improvements.
+
+Most used commands (from shell history):
+{{TOP_COMMANDS}}
 
-Traditional tool usage (from shell history):
+Frequent full command lines:
+{{FREQUENT_LINES}}
+
+Commands often used together:
+{{CO_OCCURRING}}
+
+Traditional tool usage where a modern replacement is installed:
 {{TRADITIONAL_USAGE}}
 
 Modern replacement tools installed:
@@ -176,12 +198,23 @@ Modern replacement tools installed:
 Unused installed tools with high potential:
 {{UNUSED_TOOLS}}
 
-Provide a brief (2-3 sentence) personalized insight about:
-1. The user's apparent workflow patterns
-2. Which specific unused tools would benefit them most based on their usage
+Existing bundles:
+{{EXISTING_BUNDLES}}
+
+Recommend:
+1. Aliases for long command lines that are typed often
+2. Tools to replace with better alternatives
+3. Bundles grouping tools that are used together (not duplicating existing bundles)
+4. Labels that describe what tools are used for
+
+Be concrete and only recommend what the data supports. Use empty lists when nothing fits.
 
 Respond with JSON:
-{"insight": "Your personalized analysis here"}
+{"insight": "2-3 sentence summary of the workflow and the biggest win",
+ "aliases": [{"name": "gs", "command": "git status", "reason": "typed 300 times"}],
+ "replacements": [{"replace": "find", "with": "fd", "reason": "why"}],
+ "bundles": [{"name": "search", "description": "short description", "tools": ["rg", "fzf"], "reasoning": "why"}],
+ "labels": [{"tool": "rg", "labels": ["search"]}]}
 "#;
 
 const DEFAULT_MIGRATE_PROMPT: &str = r#"For each tool being migrated between package sources, provide a brief benefit description (5-10 words) explaining why the newer version is better.
@@ -195,6 +228,77 @@ Respond with JSON:
 {"benefits": {"tool_name": "brief benefit description", ...}}
 "#;
 
+const DEFAULT_EXPLAIN_ERROR_PROMPT: &str = r#"Explain this error from the hoards CLI tool manager in plain language.
+
+Error:
+{{LOG}}
+
+In 3-6 short lines, say what most likely caused it and the concrete steps or commands to fix it.
+Respond in plain text, no markdown headings.
+"#;
+
+const DEFAULT_UPDATES_SUMMARY_PROMPT: &str = r#"Summarize these pending CLI tool updates for a user deciding what to upgrade.
+
+For each tool you get the installed version, the new version, and the release notes in between:
+
+{{UPDATES}}
+
+For every tool, pick one priority:
+- "security": fixes a vulnerability, upgrade soon
+- "breaking": changes flags, output, config or defaults the user may rely on
+- "recommended": notable fixes or features worth having
+- "optional": minor changes
+- "skip": nothing relevant (or no release notes, and nothing suggests otherwise)
+
+Write one short sentence per tool naming the concrete change (e.g. "--json output renamed keys", "fixes CVE-2024-1234").
+
+Respond with JSON only:
+{"updates": [{"tool": "name", "priority": "breaking", "summary": "One sentence"}]}
+"#;
+
+const DEFAULT_DEDUPE_PROMPT: &str = r#"Find groups of tools in this collection that serve the same purpose (e.g. exa/eza/lsd, fd/find, bat/cat).
+
+Tools (name: description [category] - times used):
+{{TOOLS}}
+
+For each group of 2 or more overlapping tools:
+- "keep": the one to keep, preferring well-maintained tools the user actually uses
+- "tools": every tool in the group, including the one to keep
+- "reason": one sentence on why the others are redundant
+
+Only group tools whose main purpose really overlaps, and only use names from the list.
+
+Respond with JSON only:
+{"groups": [{"purpose": "ls replacement", "tools": ["exa", "eza", "lsd"], "keep": "eza", "reason": "exa is unmaintained; eza is its fork"}]}
+"#;
+
+const DEFAULT_ACTION_PLAN_PROMPT: &str = r#"You turn a user's request about their CLI tools into a plan for the hoards tool manager.
+
+User request: {{REQUEST}}
+
+Installed tools: {{INSTALLED_TOOLS}}
+
+Existing bundles: {{BUNDLES}}
+
+Available actions:
+- {"action": "install", "tool": "<package name>", "source": "cargo|pip|npm|apt|brew|snap|flatpak", "description": "<short description>"}
+- {"action": "create_bundle", "bundle": "<name>", "description": "<short description>"}
+- {"action": "add_to_bundle", "bundle": "<name>", "tools": ["<tool>", ...]}
+- {"action": "remove_from_bundle", "bundle": "<name>", "tools": ["<tool>", ...]}
+- {"action": "label", "tool": "<tool>", "labels": ["<label>", ...]}
+- {"action": "favorite", "tool": "<tool>"}
+
+Guidelines:
+1. Use only the actions above, in the order they should run
+2. Pick one concrete, well-maintained tool when the user describes a need ("a fast json viewer")
+3. Use the exact package name for the chosen source
+4. Do not install tools that are already installed
+5. Only create a bundle if it does not exist yet
+
+Respond with JSON only:
+{"summary": "One sentence describing the plan", "steps": [ ... ]}
+"#;
+
 // ==================== Modern tool replacements ====================
 
 /// A mapping from a traditional Unix tool to its modern replacement
@@ -342,12 +446,64 @@ pub struct UnderutilizedTool {
     pub stars: Option<u64>,
 }
 
+/// Usage data sent to the AI for a workflow audit
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext {
+    pub top_commands: Vec<(String, i64)>,
+    pub frequent_lines: Vec<(String, i64)>,
+    pub co_occurring: Vec<((String, String), i64)>,
+    pub traditional_usage: Vec<(String, i64)>,
+    pub modern_tools: Vec<String>,
+    pub unused_tools: Vec<String>,
+    pub existing_bundles: Vec<String>,
+}
+
+/// A shell alias recommended by the audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AliasSuggestion {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// A tool the audit suggests replacing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplacementSuggestion {
+    pub replace: String,
+    pub with: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Labels the audit suggests for a tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelSuggestion {
+    pub tool: String,
+    pub labels: Vec<String>,
+}
+
+/// AI workflow audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowAudit {
+    pub insight: String,
+    #[serde(default)]
+    pub aliases: Vec<AliasSuggestion>,
+    #[serde(default)]
+    pub replacements: Vec<ReplacementSuggestion>,
+    #[serde(default)]
+    pub bundles: Vec<BundleSuggestion>,
+    #[serde(default)]
+    pub labels: Vec<LabelSuggestion>,
+}
+
 /// Result of usage analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
     pub tips: Vec<AnalyzeTip>,
     pub underutilized: Vec<UnderutilizedTool>,
     pub ai_insight: Option<String>,
+    pub audit: Option<WorkflowAudit>,
 }
 
 /// A tool that can be migrated to a different source
@@ -369,39 +525,78 @@ pub struct MigrationResult {
     pub ai_summary: Option<String>,
 }
 
-// ==================== Prompt loading ====================
+// ==================== AI invocation ====================
+
+/// Error message used when a streaming request is cancelled by the caller
+pub const CANCELLED_MESSAGE: &str = "AI request cancelled";
 
-/// Get the prompts directory path
-pub fn prompts_dir() -> Result<PathBuf> {
-    let config_dir = dirs::config_dir()
-        .context("Could not determine config directory")?
-        .join("hoards")
-        .join("prompts");
-    Ok(config_dir)
+/// Invoke the configured AI provider with a prompt
+///
+/// `feature` names the caller (usually the prompt name) for usage accounting.
+pub fn invoke_ai(feature: &str, prompt: &str) -> Result<String> {
+    invoke_ai_streaming(feature, prompt, &mut |_| true)
 }
 
-/// Load a prompt template from file, falling back to embedded default
-fn load_prompt(name: &str, default: &str) -> String {
-    let path = match prompts_dir() {
-        Ok(dir) => dir.join(format!("{}.txt", name)),
-        Err(_) => return default.to_string(),
-    };
+/// Invoke the configured AI provider, reporting output as it arrives
+///
+/// `on_chunk` receives each piece of text as the provider produces it and
+/// returns `false` to cancel the request (the provider process or connection
+/// is dropped and [`CANCELLED_MESSAGE`] is returned as the error). The full
+/// response is returned on success. Every call is recorded in the `ai_calls`
+/// table under `feature` (see `hoards ai usage`).
+pub fn invoke_ai_streaming(
+    feature: &str,
+    prompt: &str,
+    on_chunk: &mut dyn FnMut(&str) -> bool,
+) -> Result<String> {
+    let config = HoardConfig::load()?;
+    if config.ai.provider == AiProvider::None {
+        bail!("No AI provider configured. Run 'hoards ai set <provider>' first.");
+    }
 
-    match std::fs::read_to_string(&path) {
-        Ok(content) => content,
-        Err(_) => default.to_string(),
+    let started = std::time::Instant::now();
+    let result = complete(&config, prompt, on_chunk);
+    let call = crate::db::AiCall {
+        feature: feature.to_string(),
+        provider: config.ai.provider.to_string(),
+        model: config.ai.model_name(),
+        prompt_tokens: estimate_tokens(prompt),
+        response_tokens: result.as_deref().map(estimate_tokens).unwrap_or(0),
+        duration_ms: started.elapsed().as_millis() as i64,
+        success: result.is_ok(),
+    };
+    // Accounting must never break the feature itself
+    if let Ok(db) = crate::db::Database::open() {
+        let _ = db.record_ai_call(&call);
     }
+    result
 }
 
-// ==================== AI invocation ====================
+/// Rough token count (about four characters per token)
+///
+/// Providers tokenize differently and CLI providers don't report usage, so
+/// this is only meant for comparing features against each other.
+pub fn estimate_tokens(text: &str) -> i64 {
+    text.chars().count().div_ceil(4) as i64
+}
 
-/// Invoke the configured AI provider with a prompt
-pub fn invoke_ai(prompt: &str) -> Result<String> {
-    let config = HoardConfig::load()?;
+/// Send a prompt to the configured provider
+fn complete(
+    config: &HoardConfig,
+    prompt: &str,
+    on_chunk: &mut dyn FnMut(&str) -> bool,
+) -> Result<String> {
     let provider = &config.ai.provider;
 
-    if *provider == AiProvider::None {
-        bail!("No AI provider configured. Run 'hoards ai set <provider>' first.");
+    // Native HTTP providers
+    match provider {
+        AiProvider::OpenAi => return openai::complete(&config.ai.openai, prompt, on_chunk),
+        AiProvider::Ollama => return ollama::complete(&config.ai.ollama, prompt, on_chunk),
+        AiProvider::Anthropic => {
+            let model = config.ai.claude_model.unwrap_or_default();
+            return anthropic::complete(&config.ai.anthropic, model, prompt, on_chunk);
+        }
+        _ => {}
     }
 
     let cmd_name = provider
@@ -416,46 +611,122 @@ pub fn invoke_ai(prompt: &str) -> Result<String> {
     }
 
     // Build the command based on provider
-    let output = match provider {
+    let mut command = Command::new(cmd_name);
+    match provider {
         AiProvider::Claude => {
             // claude -p "prompt" for non-interactive mode
-            Command::new(cmd_name)
-                .arg("-p")
-                .arg(prompt)
-                .output()
-                .context("Failed to execute claude")?
-        }
-        AiProvider::Gemini => {
-            // gemini "prompt"
-            Command::new(cmd_name)
-                .arg(prompt)
-                .output()
-                .context("Failed to execute gemini")?
+            if let Some(model) = config.ai.claude_model {
+                command.args(["--model", model.cli_alias()]);
+            }
+            command.arg("-p").arg(prompt);
         }
         AiProvider::Codex => {
             // codex -q "prompt" for quiet mode
-            Command::new(cmd_name)
-                .arg("-q")
-                .arg(prompt)
-                .output()
-                .context("Failed to execute codex")?
+            command.arg("-q").arg(prompt);
         }
-        AiProvider::Opencode => {
-            // opencode "prompt"
-            Command::new(cmd_name)
-                .arg(prompt)
-                .output()
-                .context("Failed to execute opencode")?
+        AiProvider::Gemini | AiProvider::Opencode => {
+            // gemini "prompt" / opencode "prompt"
+            command.arg(prompt);
         }
-        AiProvider::None => unreachable!(),
-    };
+        AiProvider::OpenAi | AiProvider::Ollama | AiProvider::Anthropic | AiProvider::None => {
+            unreachable!()
+        }
+    }
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
+    stream_command(command, cmd_name, on_chunk)
+}
+
+/// Run an AI CLI, forwarding stdout to `on_chunk` as it is produced
+fn stream_command(
+    mut command: Command,
+    cmd_name: &str,
+    on_chunk: &mut dyn FnMut(&str) -> bool,
+) -> Result<String> {
+    use std::io::Read;
+    use std::process::Stdio;
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute {}", cmd_name))?;
+
+    let mut stdout = child.stdout.take().context("Failed to capture AI output")?;
+    // Drain stderr alongside stdout: a CLI blocked on a full stderr pipe
+    // would never finish its output
+    let mut stderr = child.stderr.take().context("Failed to capture AI errors")?;
+    let stderr = std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        let _ = stderr.read_to_end(&mut bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    });
+    let mut response = String::new();
+    let mut pending = Vec::new(); // bytes of an incomplete UTF-8 sequence
+    let mut buf = [0u8; 1024];
+
+    loop {
+        let n = stdout.read(&mut buf).context("Failed to read AI output")?;
+        if n == 0 {
+            break;
+        }
+        pending.extend_from_slice(&buf[..n]);
+        let valid = match std::str::from_utf8(&pending) {
+            Ok(text) => text.len(),
+            Err(e) => e.valid_up_to(),
+        };
+        let chunk = String::from_utf8_lossy(&pending[..valid]).to_string();
+        pending.drain(..valid);
+
+        if !chunk.is_empty() {
+            response.push_str(&chunk);
+            if !on_chunk(&chunk) {
+                let _ = child.kill();
+                let _ = child.wait();
+                bail!(CANCELLED_MESSAGE);
+            }
+        }
+    }
+
+    let status = child.wait().context("Failed to wait for AI command")?;
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
         bail!("AI command failed: {}", stderr);
     }
 
-    let response = String::from_utf8_lossy(&output.stdout).to_string();
+    Ok(response.trim().to_string())
+}
+
+/// Read a streamed HTTP body line by line
+///
+/// `parse_line` turns one line of the provider's stream format into an
+/// optional text delta; each delta is appended to the response and passed to
+/// `on_chunk`.
+fn stream_lines(
+    body: ureq::Body,
+    mut parse_line: impl FnMut(&str) -> Result<Option<String>>,
+    on_chunk: &mut dyn FnMut(&str) -> bool,
+) -> Result<String> {
+    use std::io::BufRead;
+
+    let reader = std::io::BufReader::new(body.into_reader());
+    let mut response = String::new();
+
+    for line in reader.lines() {
+        let line = line.context("Failed to read AI response stream")?;
+        if let Some(delta) = parse_line(&line)?
+            && !delta.is_empty()
+        {
+            response.push_str(&delta);
+            if !on_chunk(&delta) {
+                bail!(CANCELLED_MESSAGE);
+            }
+        }
+    }
+
+    if response.trim().is_empty() {
+        bail!("AI response contained no text");
+    }
     Ok(response.trim().to_string())
 }
 
@@ -480,10 +751,10 @@ pub fn categorize_prompt(tools: &[Tool], existing_categories: &[String]) -> Stri
         existing_categories.join(", ")
     };
 
-    let template = load_prompt("categorize", DEFAULT_CATEGORIZE_PROMPT);
-    template
-        .replace("{{CATEGORIES}}", &categories)
-        .replace("{{TOOLS}}", &tool_list.join("\n"))
+    prompts::CATEGORIZE.render(&[
+        ("CATEGORIES", &categories),
+        ("TOOLS", &tool_list.join("\n")),
+    ])
 }
 
 /// Parse categorization response from AI
@@ -501,11 +772,24 @@ pub fn parse_categorize_response(
 // ==================== Describe ====================
 
 /// Generate a prompt for describing tools
-pub fn describe_prompt(tools: &[Tool]) -> String {
+///
+/// `hints` maps tool names to their repository's description, if known.
+pub fn describe_prompt(
+    tools: &[Tool],
+    hints: &std::collections::HashMap<String, String>,
+) -> String {
     let tool_list: Vec<String> = tools.iter().map(|t| format!("- {}", t.name)).collect();
+    let known: Vec<String> = tools
+        .iter()
+        .filter_map(|t| Some(format!("- {}: {}", t.name, hints.get(&t.name)?)))
+        .collect();
+    let known = if known.is_empty() {
+        "(none)".to_string()
+    } else {
+        known.join("\n")
+    };
 
-    let template = load_prompt("describe", DEFAULT_DESCRIBE_PROMPT);
-    template.replace("{{TOOLS}}", &tool_list.join("\n"))
+    prompts::DESCRIBE.render(&[("TOOLS", &tool_list.join("\n")), ("DESCRIPTION", &known)])
 }
 
 /// Parse description response from AI
@@ -523,7 +807,7 @@ pub fn parse_describe_response(
 // ==================== Suggest Bundle ====================
 
 /// Bundle suggestion from AI
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BundleSuggestion {
     pub name: String,
     pub description: String,
@@ -578,11 +862,11 @@ pub fn suggest_bundle_prompt(
             .join("\n")
     };
 
-    let template = load_prompt("suggest-bundle", DEFAULT_SUGGEST_BUNDLE_PROMPT);
-    template
-        .replace("{{COUNT}}", &count.to_string())
-        .replace("{{EXISTING_BUNDLES}}", &bundles_str)
-        .replace("{{TOOLS}}", &tool_list.join("\n"))
+    prompts::SUGGEST_BUNDLE.render(&[
+        ("COUNT", &count.to_string()),
+        ("EXISTING_BUNDLES", &bundles_str),
+        ("TOOLS", &tool_list.join("\n")),
+    ])
 }
 
 /// Parse bundle suggestion response from AI
@@ -675,61 +959,6 @@ pub fn parse_github_url(url: &str) -> Result<(String, String)> {
     bail!("Invalid GitHub URL format: {}", url)
 }
 
-/// Fetch README content from GitHub using gh CLI
-pub fn fetch_readme(owner: &str, repo: &str) -> Result<String> {
-    let output = Command::new("gh")
-        .args(["api", &format!("repos/{}/{}/readme", owner, repo)])
-        .output()
-        .context("Failed to run gh api")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("Failed to fetch README: {}", stderr);
-    }
-
-    #[derive(serde::Deserialize)]
-    struct ReadmeResponse {
-        content: String,
-        encoding: String,
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let readme: ReadmeResponse =
-        serde_json::from_str(&stdout).context("Failed to parse README response")?;
-
-    if readme.encoding != "base64" {
-        bail!("Unexpected README encoding: {}", readme.encoding);
-    }
-
-    // Decode base64 content
-    use base64::{Engine as _, engine::general_purpose};
-    let decoded = general_purpose::STANDARD
-        .decode(readme.content.replace('\n', ""))
-        .context("Failed to decode README content")?;
-
-    String::from_utf8(decoded).context("README is not valid UTF-8")
-}
-
-/// Fetch the latest commit SHA for a repo (used for cache versioning)
-pub fn fetch_repo_version(owner: &str, repo: &str) -> Result<String> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{}/{}/commits/HEAD", owner, repo),
-            "--jq",
-            ".sha",
-        ])
-        .output()
-        .context("Failed to run gh api")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("Failed to fetch repo version: {}", stderr);
-    }
-
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-}
-
 /// Generate extraction prompt
 pub fn extract_prompt(readme: &str) -> String {
     // Truncate README if too long (keep first ~8000 chars to leave room for prompt)
@@ -739,8 +968,7 @@ pub fn extract_prompt(readme: &str) -> String {
         readme.to_string()
     };
 
-    let template = load_prompt("extract", DEFAULT_EXTRACT_PROMPT);
-    template.replace("{{README}}", &readme_truncated)
+    prompts::EXTRACT.render(&[("README", &readme_truncated)])
 }
 
 /// Parse extraction response from AI
@@ -839,9 +1067,7 @@ pub fn get_tool_version(binary: &str) -> Option<String> {
 }
 
 /// Generate a cheatsheet prompt from --help output
-pub fn cheatsheet_prompt(tool_name: &str, help_output: &str) -> String {
-    let template = load_prompt("cheatsheet", DEFAULT_CHEATSHEET_PROMPT);
-
+pub fn cheatsheet_prompt(tool_name: &str, description: Option<&str>, help_output: &str) -> String {
     // Truncate help output if too long (keep first 4000 chars)
     let truncated_help = if help_output.len() > 4000 {
         format!("{}...\n[truncated]", &help_output[..4000])
@@ -849,9 +1075,13 @@ pub fn cheatsheet_prompt(tool_name: &str, help_output: &str) -> String {
         help_output.to_string()
     };
 
-    template
-        .replace("{{TOOL_NAME}}", tool_name)
-        .replace("{{HELP_OUTPUT}}", &truncated_help)
+    // TOOL_NAME is still filled in for templates written before {{TOOL}}
+    prompts::CHEATSHEET.render(&[
+        ("TOOL", tool_name),
+        ("TOOL_NAME", tool_name),
+        ("DESCRIPTION", description.unwrap_or("(no description)")),
+        ("HELP_OUTPUT", &truncated_help),
+    ])
 }
 
 /// Generate a bundle cheatsheet prompt from multiple tools' --help outputs
@@ -859,8 +1089,6 @@ pub fn bundle_cheatsheet_prompt(
     bundle_name: &str,
     tools_help: &[(String, String)], // (tool_name, help_output)
 ) -> String {
-    let template = load_prompt("bundle_cheatsheet", DEFAULT_BUNDLE_CHEATSHEET_PROMPT);
-
     let tool_list = tools_help
         .iter()
         .map(|(name, _)| name.as_str())
@@ -885,25 +1113,163 @@ pub fn bundle_cheatsheet_prompt(
         combined_help
     };
 
-    template
-        .replace("{{BUNDLE_NAME}}", bundle_name)
-        .replace("{{TOOL_LIST}}", &tool_list)
-        .replace("{{HELP_OUTPUTS}}", &final_help)
+    prompts::BUNDLE_CHEATSHEET.render(&[
+        ("BUNDLE_NAME", bundle_name),
+        ("TOOL_LIST", &tool_list),
+        ("HELP_OUTPUTS", &final_help),
+    ])
 }
 
 /// Generate a discovery prompt from user query and context
 pub fn discovery_prompt(query: &str, installed_tools: &[String]) -> String {
-    let template = load_prompt("discovery", DEFAULT_DISCOVERY_PROMPT);
-
     let installed_list = if installed_tools.is_empty() {
         "None".to_string()
     } else {
         installed_tools.join(", ")
     };
 
-    template
-        .replace("{{QUERY}}", query)
-        .replace("{{INSTALLED_TOOLS}}", &installed_list)
+    prompts::DISCOVERY.render(&[("QUERY", query), ("INSTALLED_TOOLS", &installed_list)])
+}
+
+/// Generate a prompt asking the AI to explain an error message
+pub fn explain_error_prompt(error: &str) -> String {
+    prompts::EXPLAIN_ERROR.render(&[("LOG", error)])
+}
+
+// ==================== Duplicate detection ====================
+
+/// Tools the AI found to overlap in purpose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolGroup {
+    pub purpose: String,
+    pub tools: Vec<String>,
+    pub keep: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl ToolGroup {
+    /// Tools in the group other than the one to keep
+    pub fn redundant(&self) -> impl Iterator<Item = &String> {
+        self.tools.iter().filter(|t| **t != self.keep)
+    }
+}
+
+/// Generate a prompt asking the AI to group overlapping tools
+pub fn dedupe_prompt(tools: &[Tool], usage: &std::collections::HashMap<String, i64>) -> String {
+    let tool_list = tools
+        .iter()
+        .map(|t| {
+            format!(
+                "- {}: {} [{}] - {}",
+                t.name,
+                t.description.as_deref().unwrap_or("no description"),
+                t.category.as_deref().unwrap_or("uncategorized"),
+                usage.get(&t.name).copied().unwrap_or(0)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    prompts::DEDUPE.render(&[("TOOLS", &tool_list)])
+}
+
+/// Parse duplicate groups, dropping tools that aren't in `known`
+///
+/// Groups left with fewer than two known tools, or whose `keep` is not one
+/// of them, are discarded.
+pub fn parse_dedupe_response(response: &str, known: &[String]) -> Result<Vec<ToolGroup>> {
+    #[derive(Deserialize)]
+    struct DedupeResponse {
+        groups: Vec<ToolGroup>,
+    }
+
+    let json_str = extract_json_object(response)?;
+    let parsed: DedupeResponse =
+        serde_json::from_str(&json_str).context("Failed to parse dedupe response")?;
+
+    Ok(parsed
+        .groups
+        .into_iter()
+        .filter_map(|mut group| {
+            group.tools.retain(|t| known.contains(t));
+            group.tools.dedup();
+            (group.tools.len() >= 2 && group.tools.contains(&group.keep)).then_some(group)
+        })
+        .collect())
+}
+
+// ==================== Action plans ====================
+
+/// One step of a plan produced by `ai do`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum PlanStep {
+    Install {
+        tool: String,
+        source: String,
+        #[serde(default)]
+        description: Option<String>,
+    },
+    CreateBundle {
+        bundle: String,
+        #[serde(default)]
+        description: Option<String>,
+    },
+    AddToBundle {
+        bundle: String,
+        tools: Vec<String>,
+    },
+    RemoveFromBundle {
+        bundle: String,
+        tools: Vec<String>,
+    },
+    Label {
+        tool: String,
+        labels: Vec<String>,
+    },
+    Favorite {
+        tool: String,
+    },
+}
+
+/// Structured plan for a natural-language request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionPlan {
+    pub summary: String,
+    pub steps: Vec<PlanStep>,
+}
+
+/// Generate a prompt turning a request into an action plan
+pub fn action_plan_prompt(request: &str, installed_tools: &[String], bundles: &[Bundle]) -> String {
+    let installed = if installed_tools.is_empty() {
+        "None".to_string()
+    } else {
+        installed_tools.join(", ")
+    };
+    let bundle_list = if bundles.is_empty() {
+        "None".to_string()
+    } else {
+        bundles
+            .iter()
+            .map(|b| format!("{} ({})", b.name, b.tools.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+
+    prompts::ACTION_PLAN.render(&[
+        ("REQUEST", request),
+        ("INSTALLED_TOOLS", &installed),
+        ("BUNDLES", &bundle_list),
+    ])
+}
+
+/// Parse an action plan response from AI
+pub fn parse_action_plan(response: &str) -> Result<ActionPlan> {
+    let json_str = extract_json_object(response)?;
+    let plan: ActionPlan =
+        serde_json::from_str(&json_str).context("Failed to parse action plan")?;
+    Ok(plan)
 }
 
 /// Parse discovery response from AI
@@ -914,60 +1280,73 @@ pub fn parse_discovery_response(response: &str) -> Result<DiscoveryResponse> {
     Ok(discovery)
 }
 
-/// Generate an analyze prompt from usage data
-pub fn analyze_prompt(
-    traditional_usage: &[(String, i64)],
-    modern_tools: &[String],
-    unused_tools: &[String],
-) -> String {
-    let template = load_prompt("analyze", DEFAULT_ANALYZE_PROMPT);
-
-    let traditional_str = if traditional_usage.is_empty() {
-        "None detected".to_string()
-    } else {
-        traditional_usage
+/// Generate a workflow audit prompt from usage data
+pub fn analyze_prompt(context: &AuditContext) -> String {
+    fn counted(items: &[(String, i64)]) -> String {
+        if items.is_empty() {
+            return "None detected".to_string();
+        }
+        items
             .iter()
-            .map(|(cmd, count)| format!("{} ({}x)", cmd, count))
+            .map(|(item, count)| format!("{} ({}x)", item, count))
             .collect::<Vec<_>>()
             .join(", ")
-    };
+    }
 
-    let modern_str = if modern_tools.is_empty() {
-        "None".to_string()
+    fn listed(items: &[String]) -> String {
+        if items.is_empty() {
+            "None".to_string()
+        } else {
+            items.join(", ")
+        }
+    }
+
+    let frequent_lines = if context.frequent_lines.is_empty() {
+        "None detected".to_string()
     } else {
-        modern_tools.join(", ")
+        context
+            .frequent_lines
+            .iter()
+            .map(|(line, count)| format!("- {} ({}x)", line, count))
+            .collect::<Vec<_>>()
+            .join("\n")
     };
 
-    let unused_str = if unused_tools.is_empty() {
-        "None".to_string()
+    let co_occurring = if context.co_occurring.is_empty() {
+        "None detected".to_string()
     } else {
-        unused_tools.join(", ")
+        context
+            .co_occurring
+            .iter()
+            .map(|((a, b), count)| format!("{} + {} ({}x)", a, b, count))
+            .collect::<Vec<_>>()
+            .join(", ")
     };
 
-    template
-        .replace("{{TRADITIONAL_USAGE}}", &traditional_str)
-        .replace("{{MODERN_TOOLS}}", &modern_str)
-        .replace("{{UNUSED_TOOLS}}", &unused_str)
+    prompts::ANALYZE.render(&[
+        ("TOP_COMMANDS", &counted(&context.top_commands)),
+        ("FREQUENT_LINES", &frequent_lines),
+        ("CO_OCCURRING", &co_occurring),
+        ("TRADITIONAL_USAGE", &counted(&context.traditional_usage)),
+        ("MODERN_TOOLS", &listed(&context.modern_tools)),
+        ("UNUSED_TOOLS", &listed(&context.unused_tools)),
+        ("EXISTING_BUNDLES", &listed(&context.existing_bundles)),
+    ])
 }
 
-/// Parse analyze insight response from AI
-pub fn parse_analyze_response(response: &str) -> Result<String> {
+/// Parse a workflow audit response from AI
+///
+/// Older custom prompts that only return `{"insight": ...}` still parse,
+/// with no recommendations.
+pub fn parse_analyze_response(response: &str) -> Result<WorkflowAudit> {
     let json_str = extract_json_object(response)?;
-
-    #[derive(serde::Deserialize)]
-    struct AnalyzeInsight {
-        insight: String,
-    }
-
-    let insight: AnalyzeInsight =
+    let audit: WorkflowAudit =
         serde_json::from_str(&json_str).context("Failed to parse analyze response")?;
-    Ok(insight.insight)
+    Ok(audit)
 }
 
 /// Build prompt for migration benefit descriptions
 pub fn migrate_prompt(tools: &[(String, String, String, String, String)]) -> String {
-    let prompt_template = load_prompt("migrate", DEFAULT_MIGRATE_PROMPT);
-
     let tools_str = tools
         .iter()
         .map(|(name, from_source, from_ver, to_source, to_ver)| {
@@ -979,7 +1358,7 @@ pub fn migrate_prompt(tools: &[(String, String, String, String, String)]) -> Str
         .collect::<Vec<_>>()
         .join("\n");
 
-    prompt_template.replace("{{TOOLS}}", &tools_str)
+    prompts::MIGRATE.render(&[("TOOLS", &tools_str)])
 }
 
 /// Parse migration benefits response from AI
@@ -1100,6 +1479,34 @@ pub fn format_cheatsheet(cheatsheet: &Cheatsheet) -> String {
     output.join("\n")
 }
 
+/// Render a cheatsheet as a markdown document
+pub fn cheatsheet_markdown(cheatsheet: &Cheatsheet, description: Option<&str>) -> String {
+    let escape = |text: &str| text.replace('|', "\\|");
+
+    let mut output = vec![format!("# {}", cheatsheet.title), String::new()];
+    if let Some(desc) = description {
+        output.push(format!("> {}", desc));
+        output.push(String::new());
+    }
+
+    for section in &cheatsheet.sections {
+        output.push(format!("## {}", section.name));
+        output.push(String::new());
+        output.push("| Command | Description |".to_string());
+        output.push("|---------|-------------|".to_string());
+        for cmd in &section.commands {
+            output.push(format!(
+                "| `{}` | {} |",
+                escape(&cmd.cmd).replace('`', "'"),
+                escape(&cmd.desc)
+            ));
+        }
+        output.push(String::new());
+    }
+
+    output.join("\n")
+}
+
 // ==================== JSON extraction helpers ====================
 
 /// Extract a JSON object from a response that might contain extra text
@@ -1138,6 +1545,22 @@ fn extract_json_array(response: &str) -> Result<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_stream_command_drains_stderr() {
+        // Far more than a pipe buffer of errors, and nothing on stdout
+        let mut command = Command::new("cat");
+        command.args((0..4000).map(|i| format!("/nonexistent/hoards-test-{}", i)));
+
+        let mut chunks = 0;
+        let err = stream_command(command, "cat", &mut |_| {
+            chunks += 1;
+            true
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("hoards-test-3999"));
+        assert_eq!(chunks, 0);
+    }
+
     #[test]
     fn test_extract_json_object() {
         let response = r#"Here's the categorization:
@@ -1162,9 +1585,91 @@ Done!"#;
         assert!(DEFAULT_CATEGORIZE_PROMPT.contains("{{CATEGORIES}}"));
         assert!(DEFAULT_CATEGORIZE_PROMPT.contains("{{TOOLS}}"));
         assert!(DEFAULT_DESCRIBE_PROMPT.contains("{{TOOLS}}"));
+        assert!(DEFAULT_DESCRIBE_PROMPT.contains("{{DESCRIPTION}}"));
+        assert!(DEFAULT_CHEATSHEET_PROMPT.contains("{{DESCRIPTION}}"));
         assert!(DEFAULT_SUGGEST_BUNDLE_PROMPT.contains("{{COUNT}}"));
         assert!(DEFAULT_SUGGEST_BUNDLE_PROMPT.contains("{{TOOLS}}"));
         assert!(DEFAULT_EXTRACT_PROMPT.contains("{{README}}"));
+        assert!(DEFAULT_EXPLAIN_ERROR_PROMPT.contains("{{LOG}}"));
+        assert!(DEFAULT_ACTION_PLAN_PROMPT.contains("{{REQUEST}}"));
+    }
+
+    #[test]
+    fn test_cheatsheet_markdown() {
+        let cheatsheet = Cheatsheet {
+            title: "ripgrep (rg)".to_string(),
+            sections: vec![CheatsheetSection {
+                name: "Basics".to_string(),
+                commands: vec![CheatsheetCommand {
+                    cmd: "rg 'a|b'".to_string(),
+                    desc: "Match a or b".to_string(),
+                }],
+            }],
+        };
+
+        let markdown = cheatsheet_markdown(&cheatsheet, Some("Fast grep"));
+        assert!(markdown.starts_with("# ripgrep (rg)\n\n> Fast grep"));
+        assert!(markdown.contains("## Basics"));
+        assert!(markdown.contains("| `rg 'a\\|b'` | Match a or b |"));
+    }
+
+    #[test]
+    fn test_parse_dedupe_response() {
+        let known: Vec<String> = ["exa", "eza", "fd", "bat"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let response = r#"{"groups": [
+            {"purpose": "ls", "tools": ["exa", "eza", "lsd"], "keep": "eza", "reason": "fork"},
+            {"purpose": "find", "tools": ["fd", "find"], "keep": "fd"},
+            {"purpose": "cat", "tools": ["bat", "exa"], "keep": "ccat"}
+        ]}"#;
+
+        let groups = parse_dedupe_response(response, &known).unwrap();
+        // lsd is unknown; the find group has one known tool; ccat isn't in its group
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].tools, vec!["exa", "eza"]);
+        assert_eq!(groups[0].redundant().collect::<Vec<_>>(), vec!["exa"]);
+    }
+
+    #[test]
+    fn test_parse_analyze_response() {
+        let response = r#"{"insight": "Heavy git user", "aliases": [{"name": "gs", "command": "git status", "reason": "typed often"}],
+            "bundles": [{"name": "search", "description": "Search tools", "tools": ["rg", "fzf"]}],
+            "labels": [{"tool": "rg", "labels": ["search"]}]}"#;
+        let audit = parse_analyze_response(response).unwrap();
+        assert_eq!(audit.aliases[0].command, "git status");
+        assert_eq!(audit.bundles[0].tools, vec!["rg", "fzf"]);
+        assert!(audit.replacements.is_empty());
+
+        // Insight-only responses from older prompt templates
+        let legacy = parse_analyze_response(r#"{"insight": "Try fd"}"#).unwrap();
+        assert_eq!(legacy.insight, "Try fd");
+        assert!(legacy.aliases.is_empty() && legacy.labels.is_empty());
+    }
+
+    #[test]
+    fn test_parse_action_plan() {
+        let response = r#"Here is the plan:
+{"summary": "Install jless and add it to data", "steps": [
+  {"action": "install", "tool": "jless", "source": "cargo", "description": "JSON viewer"},
+  {"action": "add_to_bundle", "bundle": "data", "tools": ["jless"]},
+  {"action": "favorite", "tool": "jless"}
+]}"#;
+        let plan = parse_action_plan(response).unwrap();
+        assert_eq!(plan.steps.len(), 3);
+        assert_eq!(
+            plan.steps[0],
+            PlanStep::Install {
+                tool: "jless".to_string(),
+                source: "cargo".to_string(),
+                description: Some("JSON viewer".to_string()),
+            }
+        );
+        assert!(matches!(&plan.steps[1], PlanStep::AddToBundle { bundle, .. } if bundle == "data"));
+
+        let unknown = r#"{"summary": "x", "steps": [{"action": "rm_rf", "tool": "/"}]}"#;
+        assert!(parse_action_plan(unknown).is_err());
     }
 
     #[test]
@@ -0,0 +1,158 @@
+//! Ollama local-model provider
+//!
+//! Talks to a local Ollama server (`/api/chat`), so AI features work fully
+//! offline with no API key or per-request cost. Replies are streamed as
+//! newline-delimited JSON.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::config::OllamaConfig;
+use crate::http::{AI_AGENT, HTTP_AGENT};
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    message: Option<ResponseMessage>,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    models: Vec<ModelInfo>,
+}
+
+#[derive(Deserialize)]
+struct ModelInfo {
+    name: String,
+}
+
+/// Join the server host and an API path
+fn endpoint(host: &str, path: &str) -> String {
+    format!("{}{}", host.trim_end_matches('/'), path)
+}
+
+/// Send a single-turn prompt and stream the model's reply
+pub fn complete(
+    config: &OllamaConfig,
+    prompt: &str,
+    on_chunk: &mut dyn FnMut(&str) -> bool,
+) -> Result<String> {
+    let url = endpoint(&config.host, "/api/chat");
+    let body = ChatRequest {
+        model: &config.model,
+        messages: vec![ChatMessage {
+            role: "user",
+            content: prompt,
+        }],
+        stream: true,
+    };
+
+    let mut response = AI_AGENT.post(&url).send_json(&body).with_context(|| {
+        format!(
+            "Failed to reach Ollama at {} (is 'ollama serve' running?)",
+            config.host
+        )
+    })?;
+    let status = response.status();
+
+    if !status.is_success() {
+        let text = response.body_mut().read_to_string().unwrap_or_default();
+        let message = serde_json::from_str::<ErrorResponse>(&text)
+            .map(|e| e.error)
+            .unwrap_or(text);
+        bail!("Ollama error ({}): {}", status.as_u16(), message);
+    }
+
+    super::stream_lines(response.into_body(), parse_stream_line, on_chunk)
+}
+
+/// Extract the message content from one streamed JSON line
+fn parse_stream_line(line: &str) -> Result<Option<String>> {
+    if line.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let chunk: ChatResponse =
+        serde_json::from_str(line).context("Failed to parse Ollama response")?;
+    if let Some(error) = chunk.error {
+        bail!("Ollama error: {}", error);
+    }
+    Ok(chunk.message.map(|m| m.content))
+}
+
+/// List the models available on the Ollama server (connectivity check)
+pub fn list_models(config: &OllamaConfig) -> Result<Vec<String>> {
+    let url = endpoint(&config.host, "/api/tags");
+    let mut response = HTTP_AGENT
+        .get(&url)
+        .call()
+        .with_context(|| format!("Ollama not reachable at {}", config.host))?;
+    let tags: TagsResponse = response
+        .body_mut()
+        .read_json()
+        .context("Failed to parse Ollama model list")?;
+    Ok(tags.models.into_iter().map(|m| m.name).collect())
+}
+
+/// Check whether a model is pulled (`llama3.2` matches `llama3.2:latest`)
+pub fn has_model(models: &[String], model: &str) -> bool {
+    models
+        .iter()
+        .any(|name| name == model || name.strip_suffix(":latest") == Some(model))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        assert_eq!(
+            endpoint("http://localhost:11434/", "/api/chat"),
+            "http://localhost:11434/api/chat"
+        );
+    }
+
+    #[test]
+    fn test_has_model() {
+        let models = vec!["llama3.2:latest".to_string(), "qwen2.5:7b".to_string()];
+        assert!(has_model(&models, "llama3.2"));
+        assert!(has_model(&models, "llama3.2:latest"));
+        assert!(has_model(&models, "qwen2.5:7b"));
+        assert!(!has_model(&models, "qwen2.5"));
+    }
+
+    #[test]
+    fn test_parse_stream_line() {
+        let line =
+            r#"{"model":"llama3.2","message":{"role":"assistant","content":"OK"},"done":false}"#;
+        assert_eq!(parse_stream_line(line).unwrap().as_deref(), Some("OK"));
+        assert_eq!(parse_stream_line("").unwrap(), None);
+
+        let error = r#"{"error":"model 'nope' not found"}"#;
+        assert!(parse_stream_line(error).is_err());
+    }
+}
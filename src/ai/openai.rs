@@ -0,0 +1,158 @@
+//! OpenAI-compatible chat completions provider
+//!
+//! Talks to any endpoint implementing `POST {base_url}/chat/completions`,
+//! so AI features work without installing a vendor CLI. Responses are
+//! streamed as server-sent events.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::config::OpenAiConfig;
+use crate::http::AI_AGENT;
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct StreamChunk {
+    #[serde(default)]
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: Delta,
+}
+
+#[derive(Deserialize)]
+struct Delta {
+    content: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+}
+
+/// Build the chat completions URL from a base URL
+fn completions_url(base_url: &str) -> String {
+    format!("{}/chat/completions", base_url.trim_end_matches('/'))
+}
+
+/// Send a single-turn prompt and stream the assistant's reply
+pub fn complete(
+    config: &OpenAiConfig,
+    prompt: &str,
+    on_chunk: &mut dyn FnMut(&str) -> bool,
+) -> Result<String> {
+    let url = completions_url(&config.base_url);
+    let body = ChatRequest {
+        model: &config.model,
+        messages: vec![ChatMessage {
+            role: "user",
+            content: prompt,
+        }],
+        stream: true,
+    };
+
+    let mut request = AI_AGENT.post(&url);
+    if let Some(key) = config.resolve_api_key() {
+        request = request.header("Authorization", format!("Bearer {}", key));
+    }
+
+    let mut response = request
+        .send_json(&body)
+        .with_context(|| format!("Failed to reach {}", url))?;
+    let status = response.status();
+
+    if !status.is_success() {
+        let text = response.body_mut().read_to_string().unwrap_or_default();
+        bail!(
+            "AI API error ({}): {}",
+            status.as_u16(),
+            error_message(&text)
+        );
+    }
+
+    super::stream_lines(response.into_body(), parse_stream_line, on_chunk)
+}
+
+/// Extract the text delta from one server-sent event line
+fn parse_stream_line(line: &str) -> Result<Option<String>> {
+    let Some(data) = line.strip_prefix("data:") else {
+        return Ok(None);
+    };
+    let data = data.trim();
+    if data.is_empty() || data == "[DONE]" {
+        return Ok(None);
+    }
+
+    let chunk: StreamChunk = serde_json::from_str(data).context("Failed to parse AI response")?;
+    Ok(chunk
+        .choices
+        .into_iter()
+        .next()
+        .and_then(|choice| choice.delta.content))
+}
+
+/// Extract a readable message from an error response body
+fn error_message(body: &str) -> String {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(err) => err.error.message,
+        Err(_) => body.chars().take(200).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions_url() {
+        assert_eq!(
+            completions_url("https://api.openai.com/v1"),
+            "https://api.openai.com/v1/chat/completions"
+        );
+        assert_eq!(
+            completions_url("http://localhost:1234/v1/"),
+            "http://localhost:1234/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_parse_stream_line() {
+        let line = r#"data: {"id":"1","choices":[{"index":0,"delta":{"content":"Hel"}}]}"#;
+        assert_eq!(parse_stream_line(line).unwrap().as_deref(), Some("Hel"));
+
+        let role_only = r#"data: {"choices":[{"index":0,"delta":{"role":"assistant"}}]}"#;
+        assert_eq!(parse_stream_line(role_only).unwrap(), None);
+
+        assert_eq!(parse_stream_line("data: [DONE]").unwrap(), None);
+        assert_eq!(parse_stream_line(": keep-alive").unwrap(), None);
+        assert_eq!(parse_stream_line("").unwrap(), None);
+        assert!(parse_stream_line("data: not json").is_err());
+    }
+
+    #[test]
+    fn test_error_message() {
+        let body =
+            r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error"}}"#;
+        assert_eq!(error_message(body), "Incorrect API key provided");
+        assert_eq!(error_message("Bad Gateway"), "Bad Gateway");
+    }
+}
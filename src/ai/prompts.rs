@@ -0,0 +1,272 @@
+//! User-customizable AI prompt templates
+//!
+//! Every prompt hoards sends can be overridden by a file named
+//! `<name>.txt` in `~/.config/hoards/prompts/`. Variables are written as
+//! `{{NAME}}` or `{name}` and substituted in a single pass, so text inside a
+//! substituted value is never expanded again.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// A prompt with its embedded default and the variables it accepts
+#[derive(Debug, Clone, Copy)]
+pub struct PromptTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: &'static str,
+    pub variables: &'static [&'static str],
+}
+
+pub const CATEGORIZE: PromptTemplate = PromptTemplate {
+    name: "categorize",
+    description: "Assign categories to tools (ai enrich --categorize)",
+    default: super::DEFAULT_CATEGORIZE_PROMPT,
+    variables: &["CATEGORIES", "TOOLS"],
+};
+
+pub const DESCRIBE: PromptTemplate = PromptTemplate {
+    name: "describe",
+    description: "Write short tool descriptions (ai enrich --describe)",
+    default: super::DEFAULT_DESCRIBE_PROMPT,
+    variables: &["TOOLS", "DESCRIPTION"],
+};
+
+pub const SUGGEST_BUNDLE: PromptTemplate = PromptTemplate {
+    name: "suggest-bundle",
+    description: "Suggest bundles from usage (ai suggest-bundle)",
+    default: super::DEFAULT_SUGGEST_BUNDLE_PROMPT,
+    variables: &["COUNT", "EXISTING_BUNDLES", "TOOLS"],
+};
+
+pub const EXTRACT: PromptTemplate = PromptTemplate {
+    name: "extract",
+    description: "Extract tool info from a README (ai extract)",
+    default: super::DEFAULT_EXTRACT_PROMPT,
+    variables: &["README"],
+};
+
+pub const CHEATSHEET: PromptTemplate = PromptTemplate {
+    name: "cheatsheet",
+    description: "Tool cheatsheet from --help output (ai cheatsheet)",
+    default: super::DEFAULT_CHEATSHEET_PROMPT,
+    variables: &["TOOL", "DESCRIPTION", "HELP_OUTPUT"],
+};
+
+pub const BUNDLE_CHEATSHEET: PromptTemplate = PromptTemplate {
+    name: "bundle_cheatsheet",
+    description: "Workflow cheatsheet for a bundle (ai cheatsheet --bundle)",
+    default: super::DEFAULT_BUNDLE_CHEATSHEET_PROMPT,
+    variables: &["BUNDLE_NAME", "TOOL_LIST", "HELP_OUTPUTS"],
+};
+
+pub const DISCOVERY: PromptTemplate = PromptTemplate {
+    name: "discovery",
+    description: "Tool recommendations for a query (ai discover)",
+    default: super::DEFAULT_DISCOVERY_PROMPT,
+    variables: &["QUERY", "INSTALLED_TOOLS"],
+};
+
+pub const ANALYZE: PromptTemplate = PromptTemplate {
+    name: "analyze",
+    description: "Workflow audit from usage data (ai analyze)",
+    default: super::DEFAULT_ANALYZE_PROMPT,
+    variables: &[
+        "TOP_COMMANDS",
+        "FREQUENT_LINES",
+        "CO_OCCURRING",
+        "TRADITIONAL_USAGE",
+        "MODERN_TOOLS",
+        "UNUSED_TOOLS",
+        "EXISTING_BUNDLES",
+    ],
+};
+
+pub const MIGRATE: PromptTemplate = PromptTemplate {
+    name: "migrate",
+    description: "Migration benefits (ai migrate)",
+    default: super::DEFAULT_MIGRATE_PROMPT,
+    variables: &["TOOLS"],
+};
+
+pub const EXPLAIN_ERROR: PromptTemplate = PromptTemplate {
+    name: "explain_error",
+    description: "Error analysis (TUI :explain)",
+    default: super::DEFAULT_EXPLAIN_ERROR_PROMPT,
+    variables: &["LOG"],
+};
+
+pub const DEDUPE: PromptTemplate = PromptTemplate {
+    name: "dedupe",
+    description: "Group tools with overlapping purpose (ai dedupe)",
+    default: super::DEFAULT_DEDUPE_PROMPT,
+    variables: &["TOOLS"],
+};
+
+pub const UPDATES_SUMMARY: PromptTemplate = PromptTemplate {
+    name: "updates_summary",
+    description: "Digest of release notes for pending updates (ai updates-summary)",
+    default: super::DEFAULT_UPDATES_SUMMARY_PROMPT,
+    variables: &["UPDATES"],
+};
+
+pub const ACTION_PLAN: PromptTemplate = PromptTemplate {
+    name: "action_plan",
+    description: "Plan for a natural-language request (ai do)",
+    default: super::DEFAULT_ACTION_PLAN_PROMPT,
+    variables: &["REQUEST", "INSTALLED_TOOLS", "BUNDLES"],
+};
+
+/// All customizable prompts
+pub const TEMPLATES: &[PromptTemplate] = &[
+    CATEGORIZE,
+    DESCRIBE,
+    SUGGEST_BUNDLE,
+    EXTRACT,
+    CHEATSHEET,
+    BUNDLE_CHEATSHEET,
+    DISCOVERY,
+    ANALYZE,
+    MIGRATE,
+    EXPLAIN_ERROR,
+    UPDATES_SUMMARY,
+    DEDUPE,
+    ACTION_PLAN,
+];
+
+/// Get the prompts directory path
+pub fn prompts_dir() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .context("Could not determine config directory")?
+        .join("hoards")
+        .join("prompts");
+    Ok(config_dir)
+}
+
+/// Find a prompt template by name
+pub fn find(name: &str) -> Option<&'static PromptTemplate> {
+    TEMPLATES.iter().find(|t| t.name == name)
+}
+
+impl PromptTemplate {
+    /// Path of the user override file
+    pub fn path(&self) -> Result<PathBuf> {
+        Ok(prompts_dir()?.join(format!("{}.txt", self.name)))
+    }
+
+    /// Load the user's template, falling back to the embedded default
+    pub fn load(&self) -> String {
+        self.path()
+            .and_then(|path| Ok(std::fs::read_to_string(path)?))
+            .unwrap_or_else(|_| self.default.to_string())
+    }
+
+    /// Check whether the user has overridden this prompt
+    pub fn is_customized(&self) -> bool {
+        self.path().map(|path| path.exists()).unwrap_or(false)
+    }
+
+    /// Load the template and substitute its variables
+    pub fn render(&self, vars: &[(&str, &str)]) -> String {
+        substitute(&self.load(), vars)
+    }
+
+    /// Variables not referenced by `text` in either form
+    pub fn missing_variables(&self, text: &str) -> Vec<&'static str> {
+        self.variables
+            .iter()
+            .filter(|var| {
+                !text.contains(&format!("{{{{{}}}}}", var))
+                    && !text.contains(&format!("{{{}}}", var.to_lowercase()))
+            })
+            .copied()
+            .collect()
+    }
+}
+
+/// Replace `{{NAME}}` and `{name}` placeholders in one pass
+fn substitute(template: &str, vars: &[(&str, &str)]) -> String {
+    let patterns: Vec<(String, String, &str)> = vars
+        .iter()
+        .map(|(name, value)| {
+            (
+                format!("{{{{{}}}}}", name),
+                format!("{{{}}}", name.to_lowercase()),
+                *value,
+            )
+        })
+        .collect();
+
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let matched = patterns.iter().find_map(|(long, short, value)| {
+            [long, short]
+                .into_iter()
+                .find(|pattern| rest.starts_with(pattern.as_str()))
+                .map(|pattern| (pattern.len(), *value))
+        });
+        match matched {
+            Some((len, value)) => {
+                output.push_str(value);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_substitute_both_forms() {
+        let text = substitute(
+            "Explain {tool}: {{TOOL}} uses {{HELP_OUTPUT}}",
+            &[("TOOL", "rg"), ("HELP_OUTPUT", "--help")],
+        );
+        assert_eq!(text, "Explain rg: rg uses --help");
+    }
+
+    #[test]
+    fn test_substitute_leaves_json_and_values_alone() {
+        let text = substitute(
+            r#"Example: {"rg": "search"} for {log}"#,
+            &[("LOG", "value with {log} inside")],
+        );
+        assert_eq!(
+            text,
+            r#"Example: {"rg": "search"} for value with {log} inside"#
+        );
+    }
+
+    #[test]
+    fn test_defaults_reference_their_variables() {
+        for template in TEMPLATES {
+            assert!(
+                template.missing_variables(template.default).is_empty(),
+                "{} default is missing {:?}",
+                template.name,
+                template.missing_variables(template.default)
+            );
+        }
+        assert_eq!(
+            CHEATSHEET.missing_variables("{tool} {description}"),
+            vec!["HELP_OUTPUT"]
+        );
+    }
+
+    #[test]
+    fn test_find_template() {
+        assert_eq!(find("discovery").unwrap().name, "discovery");
+        assert!(find("nope").is_none());
+    }
+}
@@ -0,0 +1,221 @@
+//! User-extensible known-tools catalog
+//!
+//! Besides the compiled-in [`KNOWN_TOOLS`](crate::scanner::KNOWN_TOOLS),
+//! tools can be defined in TOML files under `~/.config/hoards/known_tools.d/`,
+//! so teams can ship catalogs of their internal tools:
+//!
+//! ```toml
+//! [[tool]]
+//! name = "acme-deploy"
+//! binary = "deploy"
+//! category = "dev"
+//! description = "Deploy services to the ACME cluster"
+//!
+//! [tool.install]
+//! cargo = "cargo install --git https://git.acme.dev/acme-deploy"
+//! brew = "brew install acme/tap/acme-deploy"
+//! ```
+//!
+//! Catalog tools are scanned for and suggested like built-in ones, and
+//! replace a built-in tool with the same name.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{validate_binary_name, validate_package_name};
+use crate::config::HoardConfig;
+use crate::models::InstallSource;
+use crate::scanner::KnownTool;
+
+/// Source picked for a tool with several install commands and no `source`
+const SOURCE_PREFERENCE: &[&str] = &[
+    "cargo", "brew", "pip", "npm", "github", "apt", "snap", "flatpak", "manual",
+];
+
+/// A catalog file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    tool: Vec<CatalogTool>,
+}
+
+/// A tool definition in a catalog file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogTool {
+    pub name: String,
+    /// Binary to look for on PATH (default: the name)
+    pub binary: Option<String>,
+    /// Category (default: cli)
+    pub category: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    /// Source to track the tool under (default: picked from `install`)
+    pub source: Option<String>,
+    /// Install command per source
+    #[serde(default)]
+    pub install: BTreeMap<String, String>,
+}
+
+impl CatalogTool {
+    /// The source the tool is tracked under
+    pub fn source(&self) -> &str {
+        if let Some(source) = &self.source {
+            return source;
+        }
+        SOURCE_PREFERENCE
+            .iter()
+            .copied()
+            .find(|s| self.install.contains_key(*s))
+            .or_else(|| self.install.keys().next().map(String::as_str))
+            .unwrap_or("manual")
+    }
+
+    /// Check the names are safe to run and look up
+    fn validate(&self) -> Result<()> {
+        validate_package_name(&self.name)?;
+        if let Some(binary) = &self.binary {
+            validate_binary_name(binary)?;
+        }
+        if InstallSource::from(self.source()) == InstallSource::Unknown {
+            anyhow::bail!("Unknown source '{}'", self.source());
+        }
+        Ok(())
+    }
+
+    /// The tool as a known tool
+    ///
+    /// The catalog is loaded once per run and lives until exit, so its
+    /// strings are leaked to fit the `'static` built-in entries.
+    fn into_known_tool(self) -> KnownTool {
+        let leak = |s: String| -> &'static str { Box::leak(s.into_boxed_str()) };
+        let source = self.source().to_string();
+        let install_cmd = self.install.get(&source).cloned().unwrap_or_default();
+        KnownTool {
+            binary: leak(self.binary.unwrap_or_else(|| self.name.clone())),
+            name: leak(self.name),
+            description: leak(self.description),
+            category: leak(self.category.unwrap_or_else(|| "cli".to_string())),
+            source: InstallSource::from(source.as_str()),
+            install_cmd: leak(install_cmd),
+        }
+    }
+}
+
+/// Directory holding user catalog files
+pub fn catalog_dir() -> Result<PathBuf> {
+    HoardConfig::config_dir().map(|d| d.join("known_tools.d"))
+}
+
+/// Parse and validate the tools of a catalog file
+pub fn parse_catalog(content: &str) -> Result<Vec<CatalogTool>> {
+    let file: CatalogFile = toml::from_str(content)?;
+    for tool in &file.tool {
+        tool.validate()
+            .with_context(|| format!("Invalid tool '{}'", tool.name))?;
+    }
+    Ok(file.tool)
+}
+
+/// Tools from every `*.toml` file in `dir`, in file name order
+///
+/// Files that don't parse are skipped with a warning, so one broken catalog
+/// doesn't stop scans.
+pub fn load_catalog_dir(dir: &Path) -> Vec<CatalogTool> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    let mut tools = Vec::new();
+    for path in paths {
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| parse_catalog(&content));
+        match parsed {
+            Ok(file_tools) => {
+                tracing::debug!("{} tool(s) from {}", file_tools.len(), path.display());
+                tools.extend(file_tools);
+            }
+            Err(e) => tracing::warn!("Skipping tool catalog {}: {:#}", path.display(), e),
+        }
+    }
+    tools
+}
+
+/// Known tools from the user catalog; later files override earlier ones
+pub fn load_user_catalog() -> Vec<KnownTool> {
+    let Ok(dir) = catalog_dir() else {
+        return Vec::new();
+    };
+    let mut tools: Vec<CatalogTool> = Vec::new();
+    for tool in load_catalog_dir(&dir) {
+        tools.retain(|t| t.name != tool.name);
+        tools.push(tool);
+    }
+    tools
+        .into_iter()
+        .map(CatalogTool::into_known_tool)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_catalog() {
+        let tools = parse_catalog(
+            r#"
+[[tool]]
+name = "acme-deploy"
+binary = "deploy"
+description = "Deploy services"
+
+[tool.install]
+npm = "npm install -g @acme/deploy"
+cargo = "cargo install --git https://git.acme.dev/acme-deploy"
+
+[[tool]]
+name = "acme-vpn"
+source = "apt"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].source(), "cargo");
+        let kt = tools[0].clone().into_known_tool();
+        assert_eq!(kt.binary, "deploy");
+        assert_eq!(kt.category, "cli");
+        assert_eq!(kt.source, InstallSource::Cargo);
+        assert!(kt.install_cmd.starts_with("cargo install --git"));
+
+        let kt = tools[1].clone().into_known_tool();
+        assert_eq!((kt.binary, kt.source), ("acme-vpn", InstallSource::Apt));
+        assert_eq!(kt.install_cmd, "");
+
+        assert!(parse_catalog("[[tool]]\nname = \"x; rm -rf /\"").is_err());
+        assert!(parse_catalog("[[tool]]\nname = \"x\"\nsource = \"zypper\"").is_err());
+        assert!(parse_catalog("[[tool]]\nname = \"x\"\ncolour = \"red\"").is_err());
+    }
+
+    #[test]
+    fn test_load_catalog_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.toml"), "[[tool]]\nname = \"one\"").unwrap();
+        std::fs::write(dir.path().join("b.toml"), "not toml [").unwrap();
+        std::fs::write(dir.path().join("c.txt"), "[[tool]]\nname = \"two\"").unwrap();
+
+        let tools = load_catalog_dir(dir.path());
+        let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["one"]);
+    }
+}
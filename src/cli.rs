@@ -1,12 +1,32 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
+use std::collections::BTreeMap;
 
 #[derive(Parser)]
 #[command(name = "hoards")]
 #[command(about = "AI-powered CLI tool manager with usage analytics and multi-source tracking")]
 #[command(version)]
-#[command(after_help = "Use 'hoards <command> --help' for more information about a command.")]
+#[command(
+    after_help = "Use 'hoards <command> --help' for more information about a command.\n\
+                  Use 'hoards help exit-codes' for the exit codes scripts can rely on."
+)]
 pub struct Cli {
+    /// Use plain ASCII icons and high-contrast colors (accessibility mode)
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Output as JSON for programmatic use
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Show more diagnostics (-v for info, -vv for debug)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only show errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -18,9 +38,18 @@ pub enum Commands {
     // CORE COMMANDS
     // ============================================
     /// Add a tool to the database
+    #[command(after_help = "Examples:
+  hoards add ripgrep -s cargo -b rg             # One tool
+  hoards add --from-file tools.txt              # One name (or name:source) per line
+  brew leaves | hoards add --from-file - -s brew")]
     Add {
         /// Tool name
-        name: String,
+        #[arg(required_unless_present = "from_file", conflicts_with = "from_file")]
+        name: Option<String>,
+
+        /// Add every tool listed in a file ('-' for stdin)
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["description", "install_cmd", "binary", "installed"])]
+        from_file: Option<String>,
 
         /// Description of the tool
         #[arg(short, long)]
@@ -53,6 +82,82 @@ pub enum Commands {
         name: String,
     },
 
+    /// Show where a tool's binary comes from
+    ///
+    /// Prints the binary PATH resolves to, the recorded source and version,
+    /// whether the binary agrees with them, and other copies it shadows.
+    #[command(after_help = "Examples:
+  hoards which ripgrep              # By tool name
+  hoards which rg                   # By binary name")]
+    Which {
+        /// Tool or binary name
+        name: String,
+    },
+
+    /// Pick a tool with a fuzzy selector and print its name
+    ///
+    /// The selector is drawn on stderr, so the name can be piped on.
+    #[command(after_help = "Examples:
+  hoards pick | xargs hoards show           # Show the picked tool
+  hoards pick --installed | xargs hoards upgrade
+  $(hoards pick --category search) --help   # Run it")]
+    Pick {
+        /// Only installed tools
+        #[arg(short, long)]
+        installed: bool,
+
+        /// Only tools in this category
+        #[arg(short, long)]
+        category: Option<String>,
+    },
+
+    /// Search notes, descriptions, labels, cheatsheets and AI extractions
+    ///
+    /// Matches are case-insensitive and shown line by line, with the field
+    /// they were found in.
+    #[command(after_help = "Examples:
+  hoards grep wireguard             # Every field
+  hoards grep -f notes vpn          # Only your notes
+  hoards grep -f cheatsheet -f labels json")]
+    Grep {
+        /// Text to look for
+        pattern: String,
+
+        /// Only search these fields (repeatable)
+        #[arg(short, long, value_parser = ["description", "notes", "labels", "cheatsheet", "extraction"])]
+        field: Vec<String>,
+    },
+
+    /// Open a tool's homepage, repository or registry page in the browser
+    ///
+    /// Without a flag the homepage is opened, falling back to the repository
+    /// and then the package registry.
+    #[command(after_help = "Examples:
+  hoards open ripgrep               # Homepage, repo or registry page
+  hoards open ripgrep --repo        # Source repository
+  hoards open ripgrep --registry    # crates.io, PyPI, npm, ... page
+  hoards open ripgrep --print       # Print the URL instead")]
+    Open {
+        /// Tool name
+        name: String,
+
+        /// Open the source repository
+        #[arg(long, group = "page")]
+        repo: bool,
+
+        /// Open the documentation (homepage, or the repository README)
+        #[arg(long, group = "page")]
+        docs: bool,
+
+        /// Open the package registry page
+        #[arg(long, group = "page")]
+        registry: bool,
+
+        /// Print the URL instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
+
     /// Remove a tool from the database
     Remove {
         /// Tool name
@@ -69,6 +174,24 @@ pub enum Commands {
         name: String,
     },
 
+    /// Add a timestamped note to a tool, or show its notes
+    #[command(after_help = "Examples:
+  hoards note wireguard-tools \"wg-quick needs resolvconf on this box\"
+  hoards note ripgrep               # Show the notes
+  hoards note ripgrep --edit        # Edit them in $EDITOR")]
+    Note {
+        /// Tool name
+        name: String,
+
+        /// Note text (words are joined with spaces)
+        #[arg(conflicts_with = "edit")]
+        text: Vec<String>,
+
+        /// Edit all notes in $VISUAL/$EDITOR
+        #[arg(short, long)]
+        edit: bool,
+    },
+
     // ============================================
     // SYNC - Unified sync command
     // ============================================
@@ -79,6 +202,7 @@ pub enum Commands {
     #[command(after_help = "Examples:
   hoard sync                 # Check installation status
   hoard sync --scan          # Also discover new tools
+  hoard sync --full          # Scan, rescanning unchanged sources too
   hoard sync --github        # Also fetch GitHub data
   hoard sync --usage         # Also scan shell history
   hoard sync --all           # Do everything")]
@@ -91,6 +215,11 @@ pub enum Commands {
         #[arg(long)]
         scan: bool,
 
+        /// Rescan every source, even those unchanged since the last scan
+        /// (implies --scan)
+        #[arg(long)]
+        full: bool,
+
         /// Also sync GitHub data (stars, topics, descriptions)
         #[arg(long)]
         github: bool,
@@ -111,8 +240,9 @@ pub enum Commands {
         #[arg(long)]
         limit: Option<usize>,
 
-        /// Delay between GitHub API calls in ms (default: 2000)
-        #[arg(long, default_value = "2000")]
+        /// Extra pause between tools during GitHub sync, in ms
+        /// (requests are already paced by GitHub's rate-limit headers)
+        #[arg(long, default_value = "0")]
         delay: u64,
     },
 
@@ -158,8 +288,9 @@ pub enum Commands {
     /// Performs routine maintenance:
     /// 1. Sync installation status
     /// 2. Check for available updates
-    /// 3. Scan shell history for usage
-    /// 4. Show any health issues
+    /// 3. Check watched tools for new releases
+    /// 4. Scan shell history for usage
+    /// 5. Show any health issues
     Maintain {
         /// Run non-interactively
         #[arg(long)]
@@ -194,15 +325,45 @@ pub enum Commands {
     ///
     /// A full-featured terminal interface for browsing and managing tools.
     /// Supports vim-style navigation (j/k), tabs, search, and more.
-    Tui,
+    #[command(after_help = "Examples:
+  hoards tui --tab updates          # Open on the Updates tab
+  hoards tui --query ripgrep        # Pre-fill the search filter
+  hoards tui --filter cargo         # Only show cargo tools
+  hoards tui --bundle devtools      # Open a bundle in the Bundles tab")]
+    Tui {
+        /// Tab to open on launch
+        #[arg(long, value_parser = ["installed", "available", "updates", "bundles", "discover"])]
+        tab: Option<String>,
+
+        /// Pre-fill the search filter
+        #[arg(long)]
+        query: Option<String>,
+
+        /// Filter tools by source (cargo, apt, pip, npm, brew, ...)
+        #[arg(short, long)]
+        filter: Option<String>,
+
+        /// Open the Bundles tab with this bundle selected
+        #[arg(short, long, conflicts_with = "tab")]
+        bundle: Option<String>,
+    },
 
     // ============================================
     // INSTALL/UNINSTALL/UPGRADE
     // ============================================
     /// Install a tool
+    #[command(after_help = "Examples:
+  hoards install ripgrep                        # One tool
+  hoards install --from-file tools.txt          # One name (or name:source) per line
+  cat tools.txt | hoards install - --force      # From stdin")]
     Install {
-        /// Tool name to install
-        name: String,
+        /// Tool name to install ('-' reads a list from stdin)
+        #[arg(required_unless_present = "from_file", conflicts_with = "from_file")]
+        name: Option<String>,
+
+        /// Install every tool listed in a file ('-' for stdin)
+        #[arg(long, value_name = "FILE", conflicts_with = "version")]
+        from_file: Option<String>,
 
         /// Installation source (cargo, pip, npm, apt, brew, snap)
         #[arg(short, long)]
@@ -215,6 +376,37 @@ pub enum Commands {
         /// Skip confirmation
         #[arg(short, long)]
         force: bool,
+
+        /// Install a pinned tool at another version than its pin
+        #[arg(long)]
+        override_pin: bool,
+    },
+
+    /// Run a tool, installing it first if it is missing
+    ///
+    /// Like npx or pipx run, for any source. A missing tool shows the
+    /// install plan and asks first; pinned tools install their pinned
+    /// version. The run counts as a usage event.
+    #[command(after_help = "Examples:
+  hoards run tokei                  # Run a tracked tool
+  hoards run rg -- -i todo src      # Arguments after the tool go to it
+  hoards run -s cargo hyperfine     # Install an untracked tool from cargo
+  hoards run -y bat README.md       # Install without asking")]
+    Run {
+        /// Install source for a tool that isn't tracked yet
+        #[arg(short, long)]
+        source: Option<String>,
+
+        /// Install without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Tool or binary name
+        name: String,
+
+        /// Arguments passed to the tool
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
     },
 
     /// Uninstall a tool
@@ -244,11 +436,35 @@ pub enum Commands {
         #[arg(short = 'V', long)]
         version: Option<String>,
 
-        /// Skip confirmation
+        /// Skip confirmation, and upgrade a pinned tool anyway
         #[arg(short, long)]
         force: bool,
     },
 
+    /// Pin a tool so install and upgrade leave it alone, or list pins
+    ///
+    /// A pinned tool is only upgraded, or installed at another version,
+    /// with --force. Without --version the tool is held at whatever is
+    /// installed.
+    #[command(after_help = "Examples:
+  hoards pin node --version 20.11.0   # Keep node at 20.11.0
+  hoards pin ripgrep                  # Hold ripgrep where it is
+  hoards pin                          # List pinned tools")]
+    Pin {
+        /// Tool name (lists pins when omitted)
+        name: Option<String>,
+
+        /// Version to pin at
+        #[arg(short = 'V', long, requires = "name")]
+        version: Option<String>,
+    },
+
+    /// Remove a tool's pin
+    Unpin {
+        /// Tool name
+        name: String,
+    },
+
     /// Check for available updates
     Updates {
         /// Filter by source (cargo, pip, npm, apt, brew)
@@ -268,6 +484,25 @@ pub enum Commands {
         all_versions: bool,
     },
 
+    /// Watch a tool's upstream releases
+    ///
+    /// `hoards watch <tool>` subscribes to the releases of the tool's
+    /// repository (GitHub, Codeberg, ...). New releases are reported by
+    /// `hoards maintain` and `hoards watch check`, including tools hoards
+    /// can't upgrade itself, such as manual installs.
+    #[command(args_conflicts_with_subcommands = true)]
+    Watch {
+        /// Tool to watch (lists watched tools when omitted)
+        tool: Option<String>,
+
+        /// Repository URL, if the tool isn't linked to one yet
+        #[arg(long)]
+        repo: Option<String>,
+
+        #[command(subcommand)]
+        command: Option<WatchCommands>,
+    },
+
     // ============================================
     // BUNDLES & CONFIG
     // ============================================
@@ -279,31 +514,58 @@ pub enum Commands {
     #[command(subcommand)]
     Config(ConfigCommands),
 
+    /// Manage shell aliases for tools (e.g. cat=bat)
+    #[command(subcommand)]
+    Alias(AliasCommands),
+
     // ============================================
     // IMPORT/EXPORT
     // ============================================
     /// Export tools database to a file
     Export {
-        /// Output file path (supports .json or .toml)
+        /// Output file path
         #[arg(short, long)]
         output: Option<String>,
 
-        /// Export format (json or toml)
-        #[arg(short, long, default_value = "json")]
+        /// Export format (brewfile: Homebrew tools for `brew bundle`;
+        /// home-manager: a Nix module with home.packages and programs.*;
+        /// dockerfile/devcontainer: install steps for a container image;
+        /// topgrade: a topgrade.toml for the package managers in use)
+        #[arg(short, long, default_value = "json", value_parser = ["json", "yaml", "toml", "brewfile", "home-manager", "dockerfile", "devcontainer", "topgrade"])]
         format: String,
 
         /// Only export installed tools
         #[arg(short, long)]
         installed: bool,
+
+        /// Only export the tools of a bundle
+        #[arg(short, long)]
+        bundle: Option<String>,
+
+        /// Print the JSON Schema of the export file instead
+        #[arg(long, conflicts_with_all = ["format", "installed", "bundle"])]
+        schema: bool,
     },
 
     /// Import tools from a file
     Import {
-        /// Input file path (.json or .toml)
-        file: String,
+        /// Input file path (.json, .yaml, .toml, a Brewfile, .tool-versions
+        /// or mise.toml)
+        #[arg(required_unless_present = "db", conflicts_with = "db")]
+        file: Option<String>,
+
+        /// Merge another machine's hoards.db instead: tools, bundles,
+        /// labels, usage and GitHub caches
+        #[arg(long, value_name = "PATH")]
+        db: Option<String>,
+
+        /// Which side wins for entries on both machines (with --db)
+        /// [default: prefer-local]
+        #[arg(long, value_parser = ["prefer-local", "prefer-remote", "newest"], requires = "db", conflicts_with = "file")]
+        conflict: Option<String>,
 
         /// Skip tools that already exist
-        #[arg(short, long)]
+        #[arg(short, long, conflicts_with = "db")]
         skip_existing: bool,
 
         /// Only show what would be imported (dry run)
@@ -311,6 +573,57 @@ pub enum Commands {
         dry_run: bool,
     },
 
+    /// Compare installed tools with another machine's export or hoards.db
+    #[command(after_help = "Examples:
+  hoards compare laptop.json                      # Tools only on one side, version and source differences
+  hoards compare ~/backup/hoards.db               # Another machine's database
+  hoards compare laptop.json --install-missing    # Pick tools only the other machine has and install them")]
+    Compare {
+        /// Export file (.json, .yaml, .toml, a Brewfile, .tool-versions or
+        /// mise.toml) or hoards.db of the other machine
+        #[arg(value_name = "FILE")]
+        other: String,
+
+        /// Install tools the other machine has and this one doesn't
+        #[arg(long)]
+        install_missing: bool,
+
+        /// Install all of them without asking (with --install-missing)
+        #[arg(short, long, requires = "install_missing")]
+        force: bool,
+    },
+
+    // ============================================
+    // SERVE - REST API daemon
+    // ============================================
+    /// Serve a JSON API over HTTP for editors, dashboards and other machines
+    ///
+    /// Every request needs an `Authorization: Bearer <token>` header. Syncs
+    /// and installs are queued and run one at a time; poll /api/jobs for
+    /// their progress.
+    #[command(after_help = "Examples:
+  hoards serve                                    # Listen on 127.0.0.1:7868 with a generated token
+  hoards serve --listen 0.0.0.0:7868 --token s3cr3t # Reachable from other machines
+  HOARDS_API_TOKEN=s3cr3t hoards serve            # Token from the environment
+
+Endpoints:
+  GET  /api/tools[?installed=1&category=C&label=L&libraries=1]
+  GET  /api/tools/search?q=QUERY
+  GET  /api/tools/NAME
+  POST /api/sync      {\"scan\": true}
+  POST /api/install   {\"name\": \"fd\", \"source\": \"cargo\", \"version\": null, \"force\": false}
+  GET  /api/jobs, /api/jobs/ID
+  GET  /api/insights/stats, /usage, /unused, /health")]
+    Serve {
+        /// Address to listen on
+        #[arg(short, long, value_name = "ADDR", default_value = "127.0.0.1:7868")]
+        listen: String,
+
+        /// API token [default: $HOARDS_API_TOKEN, else a generated one]
+        #[arg(long)]
+        token: Option<String>,
+    },
+
     // ============================================
     // GITHUB (power user commands)
     // ============================================
@@ -343,6 +656,10 @@ pub enum Commands {
         #[arg(short = 'L', long)]
         label: Option<String>,
 
+        /// Include pip/npm library packages that install no commands
+        #[arg(long)]
+        libraries: bool,
+
         /// Output format (table, json)
         #[arg(short, long, default_value = "table")]
         format: String,
@@ -361,6 +678,10 @@ pub enum Commands {
         /// Only show what would be added (dry run)
         #[arg(short, long)]
         dry_run: bool,
+
+        /// Rescan every source, even those unchanged since the last scan
+        #[arg(long)]
+        full: bool,
     },
 
     /// Fetch missing descriptions (use 'sync --descriptions' instead)
@@ -442,6 +763,10 @@ pub enum DiscoverCommands {
         #[arg(short = 'L', long)]
         label: Option<String>,
 
+        /// Include pip/npm library packages that install no commands
+        #[arg(long)]
+        libraries: bool,
+
         /// Output format (table, json)
         #[arg(short, long, default_value = "table")]
         format: String,
@@ -482,9 +807,49 @@ pub enum DiscoverCommands {
     },
 
     /// Find tools similar to one you already use
+    ///
+    /// Ranks tools by embedding similarity of their names, descriptions,
+    /// categories and labels. The index is updated for changed tools on
+    /// each run (backend: ai.embeddings in the config).
     Similar {
         /// Tool name to find similar tools for
         tool: String,
+
+        /// Number of similar tools to show
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Rebuild the embedding index from scratch
+        #[arg(long)]
+        reindex: bool,
+    },
+
+    /// Find tools on GitHub by repository topic (e.g. terminal, tui, devops)
+    ///
+    /// Lists tracked tools with the topic, then the most starred GitHub
+    /// repositories tagged with it, marking those already in your hoard.
+    Topics {
+        /// Topic to search for
+        topic: String,
+
+        /// Maximum GitHub results
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Find the CLI tools a project refers to and install the missing ones
+    #[command(
+        after_help = "Reads Cargo.toml build dependencies, package.json scripts, pyproject.toml,
+Makefiles, justfiles, .tool-versions and CI workflows for tools the project
+runs (jq, shellcheck, protoc, ...), then offers to install the missing ones."
+    )]
+    Project {
+        /// Project directory (default: current directory)
+        path: Option<String>,
+
+        /// Install missing tools without asking
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Show trending tools by GitHub stars
@@ -514,16 +879,29 @@ pub enum InsightsCommands {
         /// Number of top tools to show
         #[arg(short, long, default_value = "20")]
         limit: usize,
+
+        /// Only usage recorded inside this project (hook mode)
+        #[arg(long, value_name = "PATH", conflicts_with = "tool")]
+        project: Option<String>,
     },
 
     /// Find installed tools you never use
     Unused,
 
-    /// Check database health and find issues
+    /// Check database health and find issues, or score each tool's health
+    #[command(after_help = "Examples:
+  hoards insights health              # Database checks
+  hoards insights health --fix        # Fix what can be fixed
+  hoards insights health --worst 20   # 20 tools most in need of attention")]
     Health {
         /// Automatically fix issues where possible
         #[arg(short, long)]
         fix: bool,
+
+        /// List the N installed tools with the lowest health score instead
+        /// (usage recency, update lag, upstream activity, config links)
+        #[arg(long, value_name = "N", conflicts_with = "fix")]
+        worst: Option<usize>,
     },
 
     /// Show database statistics
@@ -531,6 +909,142 @@ pub enum InsightsCommands {
 
     /// Show combined overview dashboard
     Overview,
+
+    /// Show when tools get used (weekday and hour of day)
+    Rhythms {
+        /// Show the rhythm of a single tool
+        tool: Option<String>,
+
+        /// Number of tools in the per-tool table
+        #[arg(short, long, default_value = "15")]
+        limit: usize,
+    },
+
+    /// Propose tools to uninstall: long unused, duplicated or orphaned
+    CleanupPlan {
+        /// Tools unused for more than this many days are proposed
+        #[arg(short, long, default_value = "90")]
+        days: i64,
+
+        /// Pick tools from the plan and uninstall them
+        #[arg(short, long)]
+        apply: bool,
+
+        /// Uninstall just these tools from the plan, without prompting
+        /// (with --apply)
+        #[arg(value_name = "TOOL", requires = "apply")]
+        tools: Vec<String>,
+    },
+
+    /// Check whether recently installed tools actually got used
+    Adoption {
+        /// Show tools installed within this many days
+        #[arg(short, long, default_value = "90")]
+        days: i64,
+
+        /// Keep a tool even though it isn't used
+        #[arg(long, value_name = "TOOL")]
+        keep: Option<String>,
+    },
+
+    /// Summarize tool licenses, flagging copyleft and unlicensed tools
+    Licenses {
+        /// Look up missing licenses on forges and package registries first
+        #[arg(short, long)]
+        fetch: bool,
+    },
+
+    /// Find binaries with more than one copy on PATH and which one runs
+    #[command(after_help = "Examples:
+  hoards insights shadowed             # Tracked binaries with several copies
+  hoards insights shadowed --all       # Every binary on PATH
+  hoards insights shadowed --resolve   # Uninstall or prefer shadowed copies")]
+    Shadowed {
+        /// Include binaries of tools hoards doesn't track
+        #[arg(short, long)]
+        all: bool,
+
+        /// For each tracked binary, pick a copy to uninstall or prefer
+        #[arg(short, long)]
+        resolve: bool,
+    },
+
+    /// Show why tools are installed as a graph (bundles, dependencies, AI)
+    #[command(after_help = "Examples:
+  hoards insights graph | dot -Tsvg > tools.svg   # Render with Graphviz
+  hoards insights graph --format mermaid          # Paste into Markdown
+  hoards insights graph bat                       # Why bat is here")]
+    Graph {
+        /// Only show what leads to this tool and what it leads to
+        tool: Option<String>,
+
+        /// Output format (dot, mermaid)
+        #[arg(short, long, default_value = "dot", value_parser = ["dot", "mermaid"])]
+        format: String,
+    },
+
+    /// Chart installs and removals per month, flagging bursts
+    #[command(after_help = "Examples:
+  hoards insights timeline                    # Last 12 months
+  hoards insights timeline --months 36        # Three years
+  hoards insights timeline --month 2026-03    # Tools installed that month")]
+    Timeline {
+        /// Number of months to chart
+        #[arg(short = 'n', long, default_value = "12")]
+        months: usize,
+
+        /// List the tools installed and removed in a month (YYYY-MM)
+        #[arg(short, long, value_name = "YYYY-MM")]
+        month: Option<String>,
+    },
+
+    /// Measure how much disk each installed tool takes
+    #[command(after_help = "Examples:
+  hoards insights size              # 20 largest tools and the total
+  hoards insights size --limit 50   # More tools
+  hoards --json insights size       # Every tool, in bytes")]
+    Size {
+        /// Number of tools to list
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+
+    /// Find categories and labels crowded with tools doing the same job
+    #[command(after_help = "Examples:
+  hoards insights overlap             # Groups of 3 or more tools
+  hoards insights overlap --min 2     # Any two tools sharing a category
+  hoards --json insights overlap      # Groups, usage and recommendations")]
+    Overlap {
+        /// Fewest tools for a category or label to count as crowded
+        #[arg(short, long, default_value = "3")]
+        min: usize,
+    },
+
+    /// Find tools whose upstream has had no commit or release in a while
+    #[command(after_help = "Examples:
+  hoards insights stale               # Quiet for two years, or archived
+  hoards insights stale --days 365    # Quiet for a year
+  hoards --json insights stale        # With last activity dates")]
+    Stale {
+        /// Days without a commit or release before an upstream counts as stale
+        #[arg(short, long, default_value = "730")]
+        days: i64,
+    },
+
+    /// Write a self-contained HTML dashboard (inventory, usage, categories, updates)
+    #[command(after_help = "Examples:
+  hoards insights report --html out/                 # Writes out/index.html
+  hoards insights report --html 2026-q3.html         # A quarterly snapshot
+  hoards insights report --html out/ --no-updates    # Skip the update check")]
+    Report {
+        /// File to write, or a directory to write index.html into
+        #[arg(long, value_name = "PATH")]
+        html: String,
+
+        /// Don't ask package managers for updates (faster, works offline)
+        #[arg(long)]
+        no_updates: bool,
+    },
 }
 
 // ============================================
@@ -544,6 +1058,21 @@ pub enum AiCommands {
     #[command(subcommand)]
     Config(AiConfigCommands),
 
+    /// Customize the prompts sent to the AI
+    ///
+    /// Templates live in ~/.config/hoards/prompts/<name>.txt. Variables are
+    /// written as {{NAME}} or {name} (e.g. {tool}, {log}).
+    #[command(subcommand)]
+    Prompts(AiPromptsCommands),
+
+    /// Inspect or clear cached AI responses
+    ///
+    /// Describe, categorize, discover and cheatsheet responses are cached
+    /// so re-runs with the same input skip the provider. Set per-feature
+    /// lifetimes with ai.cache_ttl_days in the config (0 disables caching).
+    #[command(subcommand)]
+    Cache(AiCacheCommands),
+
     /// Enrich tool data using AI
     ///
     /// Automatically categorize and describe tools using AI.
@@ -567,6 +1096,10 @@ pub enum AiCommands {
         /// Maximum number of tools to process
         #[arg(short, long)]
         limit: Option<usize>,
+
+        /// Continue an interrupted run, skipping tools it already processed
+        #[arg(long)]
+        resume: bool,
     },
 
     /// Suggest tool bundles based on your installed tools
@@ -602,6 +1135,10 @@ pub enum AiCommands {
     ///
     /// Uses AI to analyze the tool's --help output and create a concise,
     /// categorized cheatsheet of the most useful commands.
+    #[command(after_help = "Examples:
+  hoards ai cheatsheet ripgrep
+  hoards ai cheatsheet --bundle modern-unix
+  hoards ai cheatsheet --export ~/notes/cheatsheets  # Markdown handbook")]
     Cheatsheet {
         /// Tool name (must be installed, omit if using --bundle)
         tool: Option<String>,
@@ -613,6 +1150,10 @@ pub enum AiCommands {
         /// Refresh cached cheatsheet
         #[arg(short, long)]
         refresh: bool,
+
+        /// Write all cached cheatsheets as markdown to this directory and keep it updated
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["tool", "bundle", "refresh"])]
+        export: Option<String>,
     },
 
     /// Discover tools based on natural language description
@@ -636,6 +1177,64 @@ pub enum AiCommands {
         dry_run: bool,
     },
 
+    /// Summarize release notes of pending updates
+    ///
+    /// Fetches GitHub release notes between your installed and the latest
+    /// version of every outdated tool and asks the AI for a prioritized
+    /// digest. Summaries are cached per version pair.
+    #[command(
+        name = "updates-summary",
+        after_help = "Examples:
+  hoards ai updates-summary              # All sources
+  hoards ai updates-summary --source cargo
+  hoards ai updates-summary --refresh    # Ignore cached summaries"
+    )]
+    UpdatesSummary {
+        /// Only check this source (cargo, pip, npm, apt, brew)
+        #[arg(short, long)]
+        source: Option<String>,
+
+        /// Regenerate summaries even if cached
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Carry out a natural-language request
+    ///
+    /// The AI turns the request into a plan of installs and bundle/label
+    /// changes, which is shown for confirmation before anything runs.
+    #[command(after_help = "Examples:
+  hoards ai do \"install a fast json viewer and add it to my data bundle\"
+  hoards ai do \"favorite ripgrep and label it search\" --yes
+  hoards ai do \"set up a kubernetes bundle\" --dry-run")]
+    Do {
+        /// What you want done, in plain language
+        request: String,
+
+        /// Run the plan without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Only show the plan
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Find tools that overlap in purpose
+    ///
+    /// Groups tracked tools with the same job (exa/eza/lsd, fd/find, bat/cat),
+    /// records which one replaces the others, and lists redundant tools you
+    /// never use.
+    #[command(after_help = "Examples:
+  hoards ai dedupe               # Find overlaps and save 'replaces' links
+  hoards ai dedupe --dry-run     # Report only
+  hoards ai dedupe --json        # JSON output for scripts")]
+    Dedupe {
+        /// Don't save relationships to the database
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Analyze your CLI usage and suggest optimizations
     ///
     /// Detects when you use traditional Unix tools but have modern alternatives installed.
@@ -644,12 +1243,9 @@ pub enum AiCommands {
   hoards ai analyze              # Full analysis with AI insights
   hoards ai analyze --no-ai      # Static rules only (fast)
   hoards ai analyze --json       # JSON output for scripts
-  hoards ai analyze --min-uses 5 # Lower threshold")]
+  hoards ai analyze --min-uses 5 # Lower threshold
+  hoards ai analyze --apply      # Create suggested bundles and labels")]
     Analyze {
-        /// Output as JSON for programmatic use
-        #[arg(long)]
-        json: bool,
-
         /// Skip AI-generated insights (faster, static rules only)
         #[arg(long)]
         no_ai: bool,
@@ -657,6 +1253,10 @@ pub enum AiCommands {
         /// Minimum usage count to consider a traditional tool (default: 10)
         #[arg(long, default_value = "10")]
         min_uses: i64,
+
+        /// Create the bundles and labels suggested by the AI audit
+        #[arg(long, conflicts_with_all = ["no_ai", "json"])]
+        apply: bool,
     },
 
     /// Migrate tools between package sources
@@ -682,15 +1282,30 @@ pub enum AiCommands {
         #[arg(long)]
         dry_run: bool,
 
-        /// Output as JSON for programmatic use
-        #[arg(long)]
-        json: bool,
-
         /// Skip AI benefit descriptions (faster)
         #[arg(long)]
         no_ai: bool,
     },
 
+    /// Show AI calls and estimated tokens per feature
+    ///
+    /// Every AI request is recorded with its provider, model, duration and
+    /// estimated prompt/response tokens, so you can see where quota goes.
+    #[command(after_help = "Examples:
+  hoards ai usage                # Last 30 days
+  hoards ai usage --days 0       # All time
+  hoards ai usage --json         # JSON output for scripts
+  hoards ai usage --reset        # Forget recorded calls")]
+    Usage {
+        /// Only count calls from the last N days (0 for all time)
+        #[arg(long, default_value = "30")]
+        days: i64,
+
+        /// Delete all recorded calls
+        #[arg(long, conflicts_with = "json")]
+        reset: bool,
+    },
+
     // Hidden aliases for backward compatibility
     /// Set the AI provider (use 'ai config set' instead)
     #[command(hide = true)]
@@ -733,8 +1348,16 @@ pub enum AiCommands {
 pub enum AiConfigCommands {
     /// Set the AI provider to use
     Set {
-        /// AI provider (claude, gemini, codex, opencode)
+        /// AI provider (claude, gemini, codex, opencode, openai, ollama, anthropic)
         provider: String,
+
+        /// API base URL / Ollama host (native providers only, e.g. http://localhost:1234/v1)
+        #[arg(long)]
+        base_url: Option<String>,
+
+        /// Model name (e.g. gpt-4o-mini; haiku/sonnet/opus for claude and anthropic)
+        #[arg(long)]
+        model: Option<String>,
     },
 
     /// Show current AI configuration
@@ -744,6 +1367,50 @@ pub enum AiConfigCommands {
     Test,
 }
 
+#[derive(Subcommand)]
+#[non_exhaustive]
+pub enum AiPromptsCommands {
+    /// List prompt templates and whether they are customized
+    List,
+
+    /// Print a prompt template
+    Show {
+        /// Template name (see 'ai prompts list')
+        name: String,
+
+        /// Show the built-in default instead of your customized version
+        #[arg(long)]
+        default: bool,
+    },
+
+    /// Write the default templates to the prompts directory for editing
+    Init {
+        /// Overwrite templates you have already customized
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Remove a customized template, restoring the built-in default
+    Reset {
+        /// Template name (see 'ai prompts list')
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+#[non_exhaustive]
+pub enum AiCacheCommands {
+    /// Show cached entries and lifetime per feature
+    List,
+
+    /// Delete cached responses
+    Clear {
+        /// Only clear one feature (see 'ai cache list')
+        #[arg(short, long)]
+        feature: Option<String>,
+    },
+}
+
 // ============================================
 // GITHUB SUBCOMMANDS (power user)
 // ============================================
@@ -762,14 +1429,21 @@ pub enum GhCommands {
         #[arg(short, long)]
         limit: Option<usize>,
 
-        /// Delay between API calls in milliseconds
-        #[arg(long, default_value = "2000")]
+        /// Extra pause between tools in milliseconds
+        #[arg(long, default_value = "0")]
         delay: u64,
     },
 
     /// Show GitHub API rate limit status
     RateLimit,
 
+    /// Show GitHub work queued for later (e.g. tools a sync still has to do)
+    Queue {
+        /// Drop everything from the queue
+        #[arg(long)]
+        clear: bool,
+    },
+
     /// Backfill missing descriptions from cached GitHub data (no API calls)
     Backfill {
         /// Only show what would be changed (dry run)
@@ -781,6 +1455,11 @@ pub enum GhCommands {
     Fetch {
         /// Tool name
         name: String,
+
+        /// Link to this repository URL instead of searching GitHub
+        /// (also accepts Codeberg and other Gitea/Forgejo hosts)
+        #[arg(long)]
+        repo: Option<String>,
     },
 
     /// Search GitHub for a tool
@@ -798,6 +1477,134 @@ pub enum GhCommands {
         /// Tool name
         name: String,
     },
+
+    /// Show a tool's README (cached locally, revalidated after a week)
+    Readme {
+        /// Tool name
+        name: String,
+
+        /// Fetch from the forge even if the cached copy is fresh
+        #[arg(short, long)]
+        refresh: bool,
+    },
+
+    /// Import your starred repositories that look like CLI tools
+    ///
+    /// Matches are added as not-installed tools labeled `starred`, giving
+    /// you a backlog of tools to try.
+    ImportStars {
+        /// Only repos in this language (e.g. rust)
+        #[arg(short, long)]
+        language: Option<String>,
+
+        /// Only repos with this topic (e.g. cli)
+        #[arg(short, long)]
+        topic: Option<String>,
+
+        /// Import all matching stars, not just CLI-looking repos
+        #[arg(long)]
+        all: bool,
+
+        /// Add as interests instead of tools
+        #[arg(long)]
+        interests: bool,
+
+        /// Only show what would be imported
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+
+    /// Install a prebuilt binary from a repository's release assets
+    ///
+    /// Picks the asset for this OS and architecture, installs it into
+    /// install.bin_dir (default ~/.local/bin) and tracks the tool with
+    /// source "github" and the release tag as its version.
+    Install {
+        /// Repository (owner/repo or URL) or a tracked tool with a linked repo
+        repo: String,
+
+        /// Release tag to install (default: latest stable release)
+        #[arg(short, long)]
+        tag: Option<String>,
+
+        /// Tool name (default: repository name)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Executable to take from the archive (default: tool name)
+        #[arg(short, long)]
+        bin: Option<String>,
+
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+// ============================================
+// WATCH SUBCOMMANDS
+// ============================================
+
+#[derive(Subcommand)]
+#[non_exhaustive]
+pub enum WatchCommands {
+    /// List watched tools and unread releases
+    List,
+
+    /// Fetch new releases for all watched tools
+    Check,
+
+    /// Mark releases as read
+    Read {
+        /// Only this tool (default: all)
+        tool: Option<String>,
+    },
+
+    /// Stop watching a tool
+    Remove {
+        /// Tool name
+        tool: String,
+    },
+}
+
+// ============================================
+// ALIAS SUBCOMMANDS
+// ============================================
+
+#[derive(Subcommand)]
+#[non_exhaustive]
+pub enum AliasCommands {
+    /// Add or replace an alias
+    ///
+    /// The alias is tied to the tracked tool its command runs, so uninstalling
+    /// the tool warns about it. Load aliases with
+    /// 'eval "$(hoards usage init --aliases)"'.
+    #[command(after_help = "Examples:
+  hoards alias add cat bat          # cat runs bat
+  hoards alias add ls=\"eza -l\"     # NAME=COMMAND form
+  hoards alias add ll eza -la --git # Command words after the name")]
+    Add {
+        /// Alias name, or NAME=COMMAND
+        name: String,
+
+        /// Command the alias runs
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+
+        /// Tool the alias belongs to (default: the command's program)
+        #[arg(short, long)]
+        tool: Option<String>,
+    },
+
+    /// List aliases
+    List,
+
+    /// Remove aliases
+    Remove {
+        /// Alias names
+        #[arg(required = true)]
+        names: Vec<String>,
+    },
 }
 
 // ============================================
@@ -835,13 +1642,22 @@ pub enum UsageCommands {
     Log {
         /// Command that was executed
         command: String,
+
+        /// Append to the pending usage log without opening the database
+        /// (moved into it on `sync`, `usage show` and TUI start)
+        #[arg(long)]
+        fast: bool,
     },
 
     /// Show shell hook setup instructions
     Init {
         /// Shell type (auto-detected if omitted)
-        #[arg(value_parser = ["fish", "bash", "zsh"])]
+        #[arg(value_parser = ["fish", "bash", "zsh", "nushell"])]
         shell: Option<String>,
+
+        /// Print the aliases from 'hoards alias' as shell code to source
+        #[arg(long)]
+        aliases: bool,
     },
 
     /// View or change usage tracking configuration
@@ -849,6 +1665,10 @@ pub enum UsageCommands {
         /// Set tracking mode
         #[arg(long, value_parser = ["scan", "hook"])]
         mode: Option<String>,
+
+        /// Set the history read in scan mode
+        #[arg(long, value_parser = ["shell", "atuin", "all"])]
+        history: Option<String>,
     },
 
     /// Reset all usage counters to zero
@@ -857,6 +1677,21 @@ pub enum UsageCommands {
         #[arg(short, long)]
         force: bool,
     },
+
+    /// Export usage totals, daily series and last-used times
+    Export {
+        /// Output format
+        #[arg(short, long, default_value = "json", value_parser = ["json", "csv"])]
+        format: String,
+
+        /// Only days from this date on (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -872,12 +1707,30 @@ pub enum CompletionsCommands {
     /// Install completions for detected shells
     Install {
         /// Specific shell to install for (auto-detects if omitted)
-        #[arg(value_parser = ["fish", "bash", "zsh"])]
+        #[arg(value_parser = ["fish", "bash", "zsh", "nushell"])]
         shell: Option<String>,
 
         /// Overwrite existing completions
         #[arg(short, long)]
         force: bool,
+
+        /// Also install man pages into ~/.local/share/man
+        #[arg(long)]
+        man: bool,
+    },
+
+    /// Generate man pages
+    ///
+    /// Without --dir, prints the hoards(1) page. With --dir, writes a page
+    /// for every subcommand (hoards-bundle-install.1, ...).
+    #[command(after_help = "Examples:
+  hoards completions man | man -l -         # Read the page now
+  hoards completions man --dir target/man   # Pages for packaging
+  hoards completions install --man          # Install into your manpath")]
+    Man {
+        /// Write all pages to this directory
+        #[arg(long)]
+        dir: Option<String>,
     },
 
     /// Show completion installation status
@@ -886,7 +1739,7 @@ pub enum CompletionsCommands {
     /// Remove installed completions
     Uninstall {
         /// Specific shell to uninstall for (all detected if omitted)
-        #[arg(value_parser = ["fish", "bash", "zsh"])]
+        #[arg(value_parser = ["fish", "bash", "zsh", "nushell"])]
         shell: Option<String>,
     },
 }
@@ -910,6 +1763,10 @@ pub enum BundleCommands {
         /// Bundle description
         #[arg(short, long)]
         description: Option<String>,
+
+        /// Only include the tools on matching machines (e.g. "os=linux", "host=work-*")
+        #[arg(long, value_name = "CONDITION")]
+        when: Option<String>,
     },
 
     /// List all bundles
@@ -922,16 +1779,35 @@ pub enum BundleCommands {
     },
 
     /// Install all tools in a bundle
+    #[command(after_help = "Examples:
+  hoards bundle install modern-unix
+  hoards bundle install modern-unix --locked
+  hoards bundle install --from-url https://gist.github.com/alice/0123abcd")]
     Install {
         /// Bundle name
-        name: String,
+        #[arg(required_unless_present = "from_url", conflicts_with = "from_url")]
+        name: Option<String>,
+
+        /// Install a bundle shared with 'bundle publish' (gist or raw URL)
+        #[arg(long, value_name = "URL")]
+        from_url: Option<String>,
 
         /// Skip confirmation
         #[arg(short, long)]
         force: bool,
+
+        /// Install the versions recorded in the bundle's lock
+        #[arg(long)]
+        locked: bool,
     },
 
     /// Add tools to an existing bundle
+    #[command(after_help = "Conditions (all given keys must match):
+  os=linux|macos|windows   arch=x86_64|aarch64   host=<pattern with *>
+
+Examples:
+  hoards bundle add shell-essentials mas --when os=macos
+  hoards bundle add shell-essentials apt-file --when os=linux,host=work-*")]
     Add {
         /// Bundle name
         name: String,
@@ -939,6 +1815,27 @@ pub enum BundleCommands {
         /// Tools to add
         #[arg(required = true)]
         tools: Vec<String>,
+
+        /// Only include the tools on matching machines (also updates existing members)
+        #[arg(long, value_name = "CONDITION")]
+        when: Option<String>,
+    },
+
+    /// Include other bundles in a bundle (installed along with it)
+    #[command(after_help = "Examples:
+  hoards bundle include workstation rust-dev media shell
+  hoards bundle include workstation media --remove")]
+    Include {
+        /// Bundle name
+        name: String,
+
+        /// Bundles to include
+        #[arg(required = true)]
+        bundles: Vec<String>,
+
+        /// Stop including the given bundles
+        #[arg(long)]
+        remove: bool,
     },
 
     /// Remove tools from a bundle
@@ -970,12 +1867,132 @@ pub enum BundleCommands {
         #[arg(short, long)]
         yes: bool,
     },
+
+    /// Make this machine match a bundle (install missing members)
+    #[command(
+        after_help = "Members installed through a bundle are labelled bundle:<name>. With --prune,
+tools carrying that label that were removed from the bundle are uninstalled,
+unless another bundle still lists them."
+    )]
+    Sync {
+        /// Bundle name
+        name: String,
+
+        /// Uninstall tools that were removed from the bundle
+        #[arg(long)]
+        prune: bool,
+
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Share a bundle as a GitHub gist
+    Publish {
+        /// Bundle name
+        name: String,
+
+        /// Create a public gist instead of a secret one
+        #[arg(long)]
+        public: bool,
+
+        /// Write the shared bundle to a file instead of creating a gist
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+
+    /// Start from a built-in bundle template
+    #[command(subcommand)]
+    Template(BundleTemplateCommands),
+
+    /// Show the versions locked for a bundle and any drift from them
+    Lock {
+        /// Bundle name
+        name: String,
+
+        /// Re-record the lock from the currently installed versions
+        #[arg(long)]
+        update: bool,
+    },
+
+    /// Propose a bundle from the tools a project needs
+    #[command(
+        after_help = "Looks at Cargo.toml, package.json, pyproject.toml, Makefile, justfile,
+.tool-versions and CI configs (GitHub, GitLab, CircleCI) for the CLI tools
+the project uses, then offers to install the missing ones."
+    )]
+    FromProject {
+        /// Project directory (default: current directory)
+        path: Option<String>,
+
+        /// Bundle name (default: the directory name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Only show the detected tools
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip confirmations
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+#[non_exhaustive]
+pub enum BundleTemplateCommands {
+    /// List the built-in templates
+    List,
+
+    /// Create a bundle from a template
+    Apply {
+        /// Template name (rust-dev, python-dev, kubernetes, ...)
+        template: String,
+
+        /// Name of the bundle to create (default: the template name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Install the bundle right away
+        #[arg(short, long)]
+        install: bool,
+
+        /// Skip the install confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
 }
 
 // ============================================
 // CONFIG SUBCOMMANDS
 // ============================================
 
+#[derive(Subcommand)]
+#[non_exhaustive]
+pub enum ConfigRepoCommands {
+    /// Create a repository and move the config sources into it
+    Init {
+        /// Repository directory (default: ~/dotfiles)
+        path: Option<String>,
+    },
+
+    /// Clone a repository and link its configs on this machine
+    Clone {
+        /// Repository URL
+        url: String,
+
+        /// Where to clone it (default: ~/dotfiles)
+        path: Option<String>,
+    },
+
+    /// Commit local changes and push them
+    Push,
+
+    /// Pull changes and link any new configs
+    Pull,
+}
+
 #[derive(Subcommand)]
 #[non_exhaustive]
 pub enum ConfigCommands {
@@ -993,6 +2010,18 @@ pub enum ConfigCommands {
         /// Associate with a tool
         #[arg(short, long)]
         tool: Option<String>,
+
+        /// Register the source as the variant for a profile or hostname pattern (work-*)
+        #[arg(short, long)]
+        profile: Option<String>,
+
+        /// Symlink the source into place, or copy it (for tools that break on symlinks)
+        #[arg(short, long, default_value = "link", value_parser = ["link", "copy"])]
+        mode: String,
+
+        /// Command that checks the config after sync ({target} is the target path)
+        #[arg(long, value_name = "COMMAND")]
+        validate: Option<String>,
     },
 
     /// Unlink a config (removes from database, optionally removes symlink)
@@ -1007,6 +2036,10 @@ pub enum ConfigCommands {
         /// Skip confirmation
         #[arg(short, long)]
         force: bool,
+
+        /// Only remove the variant for this profile
+        #[arg(short, long)]
+        profile: Option<String>,
     },
 
     /// List all managed configs
@@ -1032,7 +2065,7 @@ pub enum ConfigCommands {
         #[arg(short, long)]
         dry_run: bool,
 
-        /// Force overwrite existing files (not symlinks)
+        /// Replace existing files, moving them to a backup first
         #[arg(short, long)]
         force: bool,
     },
@@ -1040,6 +2073,71 @@ pub enum ConfigCommands {
     /// Check status of all config symlinks
     Status,
 
+    /// Watch linked configs and report targets that get replaced or deleted
+    #[command(
+        after_help = "Runs until interrupted. Installers and package upgrades often replace a
+symlinked dotfile with a copy; watch reports it as it happens instead of at
+the next 'config status'. With --repair the target is re-linked straight
+away, after backing up the file that replaced it."
+    )]
+    Watch {
+        /// Re-link broken targets automatically
+        #[arg(short, long)]
+        repair: bool,
+    },
+
+    /// Show how targets that are real files differ from their sources
+    Diff {
+        /// Config name (default: all configs)
+        name: Option<String>,
+
+        /// Copy the target's changes into the source without asking
+        #[arg(short, long)]
+        adopt: bool,
+    },
+
+    /// Register the configs of an existing stow or chezmoi setup in place
+    Import {
+        /// GNU Stow directory (one package per subdirectory)
+        #[arg(
+            long,
+            value_name = "DIR",
+            conflicts_with = "chezmoi",
+            required_unless_present = "chezmoi"
+        )]
+        stow: Option<String>,
+
+        /// chezmoi source directory (default: `chezmoi source-path`)
+        #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "")]
+        chezmoi: Option<String>,
+
+        /// Directory the configs are deployed to (stow: parent of DIR, chezmoi: home)
+        #[arg(short, long)]
+        target: Option<String>,
+
+        /// Only show what would be registered
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+
+    /// Keep config sources in a git repository shared between machines
+    #[command(subcommand)]
+    Repo(ConfigRepoCommands),
+
+    /// Put back the file a forced sync moved aside
+    Restore {
+        /// Config name
+        name: String,
+
+        /// List backups instead of restoring
+        #[arg(short, long)]
+        list: bool,
+
+        /// Replace a real file at the target (not just a symlink)
+        #[arg(short, long)]
+        force: bool,
+    },
+
     /// Edit a config's paths
     Edit {
         /// Config name
@@ -1056,5 +2154,117 @@ pub enum ConfigCommands {
         /// Associate with a tool
         #[arg(long)]
         tool: Option<String>,
+
+        /// Symlink the source into place, or copy it
+        #[arg(short, long, value_parser = ["link", "copy"])]
+        mode: Option<String>,
+
+        /// Command that checks the config after sync ("" to remove)
+        #[arg(long, value_name = "COMMAND")]
+        validate: Option<String>,
     },
 }
+
+impl Cli {
+    /// Expand a command alias from the `aliases` config in the arguments
+    ///
+    /// Only the command name (the first argument after global flags) is
+    /// looked up, and built-in commands always win. Aliases are loaded only
+    /// when the name isn't a built-in, so plain commands never read the
+    /// config.
+    pub fn expand_alias(
+        args: Vec<String>,
+        aliases: impl FnOnce() -> BTreeMap<String, String>,
+    ) -> Vec<String> {
+        let Some(pos) = args.iter().skip(1).position(|a| !a.starts_with('-')) else {
+            return args;
+        };
+        let pos = pos + 1;
+        let name = args[pos].as_str();
+        if name == "help" || Self::command().find_subcommand(name).is_some() {
+            return args;
+        }
+        let Some(expansion) = aliases().remove(name) else {
+            return args;
+        };
+        tracing::debug!("alias {} = {}", name, expansion);
+
+        let mut expanded = args[..pos].to_vec();
+        expanded.extend(split_words(&expansion));
+        expanded.extend_from_slice(&args[pos + 1..]);
+        expanded
+    }
+}
+
+/// Split a command line into words, honouring single and double quotes
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        split_words(line)
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"grep "wg quick" -f notes ''"#),
+            vec!["grep", "wg quick", "-f", "notes", ""]
+        );
+    }
+
+    #[test]
+    fn test_expand_alias() {
+        let aliases = || {
+            BTreeMap::from([
+                ("up".to_string(), "updates --tracked".to_string()),
+                ("list".to_string(), "stats".to_string()),
+            ])
+        };
+
+        assert_eq!(
+            Cli::expand_alias(args("hoards --json up -s cargo"), aliases),
+            args("hoards --json updates --tracked -s cargo")
+        );
+        // Built-in commands can't be shadowed
+        assert_eq!(
+            Cli::expand_alias(args("hoards list"), aliases),
+            args("hoards list")
+        );
+        assert_eq!(
+            Cli::expand_alias(args("hoards -v"), aliases),
+            args("hoards -v")
+        );
+    }
+}
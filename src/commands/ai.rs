@@ -7,18 +7,20 @@ use colored::Colorize;
 use std::io::IsTerminal;
 use std::process::Command;
 
+use crate::ai::cache;
 use crate::commands::install::{
     SafeCommand, get_safe_install_command, get_safe_uninstall_command, validate_package_name,
 };
+use crate::config::ClaudeModel;
 use crate::{AiProvider, Database, HoardConfig};
 
 /// Set the AI provider
-pub fn cmd_ai_set(provider: &str) -> Result<()> {
+pub fn cmd_ai_set(provider: &str, base_url: Option<&str>, model: Option<&str>) -> Result<()> {
     let ai_provider = AiProvider::from(provider);
 
     if ai_provider == AiProvider::None {
         println!(
-            "{} Unknown provider '{}'. Valid options: claude, gemini, codex, opencode",
+            "{} Unknown provider '{}'. Valid options: claude, gemini, codex, opencode, openai, ollama, anthropic",
             "!".yellow(),
             provider
         );
@@ -37,9 +39,67 @@ pub fn cmd_ai_set(provider: &str) -> Result<()> {
 
     let mut config = HoardConfig::load()?;
     config.set_ai_provider(ai_provider);
+
+    if base_url.is_some() || model.is_some() {
+        match ai_provider {
+            AiProvider::OpenAi => {
+                if let Some(url) = base_url {
+                    config.ai.openai.base_url = url.to_string();
+                }
+                if let Some(model) = model {
+                    config.ai.openai.model = model.to_string();
+                }
+            }
+            AiProvider::Ollama => {
+                if let Some(url) = base_url {
+                    config.ai.ollama.host = url.to_string();
+                }
+                if let Some(model) = model {
+                    config.ai.ollama.model = model.to_string();
+                }
+            }
+            AiProvider::Claude | AiProvider::Anthropic => {
+                if base_url.is_some() {
+                    println!("{} --base-url is ignored for {}", "!".yellow(), ai_provider);
+                }
+                if let Some(name) = model {
+                    match ClaudeModel::from_name(name) {
+                        Some(m) => config.ai.claude_model = Some(m),
+                        None => {
+                            println!(
+                                "{} Unknown Claude model '{}'. Valid options: haiku, sonnet, opus",
+                                "!".yellow(),
+                                name
+                            );
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            _ => println!(
+                "{} --base-url/--model only apply to native API providers; ignored",
+                "!".yellow()
+            ),
+        }
+    }
+
     config.save()?;
 
     println!("{} AI provider set to '{}'", "+".green(), ai_provider);
+    if ai_provider == AiProvider::OpenAi && config.ai.openai.resolve_api_key().is_none() {
+        println!(
+            "  {} No API key found: set {} or ai.openai.api_key in the config (not needed for most local servers)",
+            "!".yellow(),
+            "OPENAI_API_KEY".cyan()
+        );
+    }
+    if ai_provider == AiProvider::Anthropic && config.ai.anthropic.resolve_api_key().is_none() {
+        println!(
+            "  {} No API key found: set {}, ai.anthropic.api_key, or store it in the OS keyring",
+            "!".yellow(),
+            "ANTHROPIC_API_KEY".cyan()
+        );
+    }
     println!(
         "  Config saved to: {}",
         HoardConfig::config_path()?.display()
@@ -59,6 +119,8 @@ pub fn cmd_ai_show() -> Result<()> {
     let provider = &config.ai.provider;
     let status = if provider == &AiProvider::None {
         "not configured".red().to_string()
+    } else if provider.is_native() {
+        "native API".green().to_string()
     } else if provider.is_installed() {
         "installed".green().to_string()
     } else {
@@ -71,6 +133,41 @@ pub fn cmd_ai_show() -> Result<()> {
         println!("Command:  {}", cmd);
     }
 
+    if *provider == AiProvider::OpenAi {
+        let openai = &config.ai.openai;
+        let key_status = if openai.resolve_api_key().is_some() {
+            "set".green()
+        } else {
+            "not set".yellow()
+        };
+        println!("Base URL: {}", openai.base_url);
+        println!("Model:    {}", openai.model);
+        println!("API key:  {}", key_status);
+    }
+
+    if matches!(provider, AiProvider::Claude | AiProvider::Anthropic) {
+        let model = config
+            .ai
+            .claude_model
+            .map(|m| m.to_string())
+            .unwrap_or_else(|| "default".to_string());
+        println!("Model:    {}", model);
+    }
+
+    if *provider == AiProvider::Anthropic {
+        let key_status = if config.ai.anthropic.resolve_api_key().is_some() {
+            "set".green()
+        } else {
+            "not set".yellow()
+        };
+        println!("API key:  {}", key_status);
+    }
+
+    if *provider == AiProvider::Ollama {
+        println!("Host:     {}", config.ai.ollama.host);
+        println!("Model:    {}", config.ai.ollama.model);
+    }
+
     println!();
     println!("Config file: {}", HoardConfig::config_path()?.display());
 
@@ -88,6 +185,65 @@ pub fn cmd_ai_test() -> Result<()> {
     }
 
     let provider = &config.ai.provider;
+
+    // Ollama: check the server is up and the model is pulled before prompting
+    if *provider == AiProvider::Ollama {
+        use crate::ai::ollama;
+
+        let ollama_config = &config.ai.ollama;
+        println!(
+            "{} Checking Ollama at {}...",
+            ">".cyan(),
+            ollama_config.host
+        );
+        match ollama::list_models(ollama_config) {
+            Ok(models) if ollama::has_model(&models, &ollama_config.model) => {
+                println!(
+                    "{} Server reachable, model '{}' available",
+                    "+".green(),
+                    ollama_config.model
+                );
+            }
+            Ok(models) => {
+                println!(
+                    "{} Model '{}' not pulled (available: {})",
+                    "!".yellow(),
+                    ollama_config.model,
+                    if models.is_empty() {
+                        "none".to_string()
+                    } else {
+                        models.join(", ")
+                    }
+                );
+                println!(
+                    "  Run {}",
+                    format!("ollama pull {}", ollama_config.model).cyan()
+                );
+                return Ok(());
+            }
+            Err(e) => {
+                println!("{} {}", "!".red(), e);
+                println!("  Start the server with {}", "ollama serve".cyan());
+                return Ok(());
+            }
+        }
+    }
+
+    // Native providers: send a tiny prompt to verify endpoint, key and model
+    if provider.is_native() {
+        println!("{} Testing {} API...", ">".cyan(), provider);
+        match crate::ai::invoke_ai("test", "Reply with the single word: OK") {
+            Ok(reply) => println!(
+                "{} {} responded: {}",
+                "+".green(),
+                provider,
+                reply.lines().next().unwrap_or_default().dimmed()
+            ),
+            Err(e) => println!("{} {} request failed: {}", "!".red(), provider, e),
+        }
+        return Ok(());
+    }
+
     let cmd = match provider.command() {
         Some(c) => c,
         None => {
@@ -141,9 +297,182 @@ pub fn cmd_ai_test() -> Result<()> {
     Ok(())
 }
 
+/// Look up a prompt template by name, listing valid names on failure
+fn find_prompt(name: &str) -> Result<&'static crate::ai::prompts::PromptTemplate> {
+    use crate::ai::prompts::{TEMPLATES, find};
+
+    find(name).with_context(|| {
+        let names: Vec<&str> = TEMPLATES.iter().map(|t| t.name).collect();
+        format!("Unknown prompt '{}'. Available: {}", name, names.join(", "))
+    })
+}
+
+/// List prompt templates and their customization status
+pub fn cmd_ai_prompts_list() -> Result<()> {
+    use crate::ai::prompts::{TEMPLATES, prompts_dir};
+
+    println!("{}", "AI Prompt Templates".bold());
+    println!("{}", "=".repeat(30));
+    println!();
+
+    for template in TEMPLATES {
+        let status = if template.is_customized() {
+            "custom".green()
+        } else {
+            "default".dimmed()
+        };
+        println!(
+            "{:<18} [{}] {}",
+            template.name.cyan(),
+            status,
+            template.description
+        );
+
+        let vars: Vec<String> = template
+            .variables
+            .iter()
+            .map(|v| format!("{{{}}}", v.to_lowercase()))
+            .collect();
+        println!("{:<18} variables: {}", "", vars.join(", ").dimmed());
+
+        if template.is_customized() {
+            let missing = template.missing_variables(&template.load());
+            if !missing.is_empty() {
+                println!(
+                    "{:<18} {} unused variables: {}",
+                    "",
+                    "!".yellow(),
+                    missing.join(", ")
+                );
+            }
+        }
+    }
+
+    println!();
+    println!("Prompts directory: {}", prompts_dir()?.display());
+    println!(
+        "{} Run {} to write the defaults there for editing",
+        ">".dimmed(),
+        "hoards ai prompts init".yellow()
+    );
+
+    Ok(())
+}
+
+/// Print a prompt template
+pub fn cmd_ai_prompts_show(name: &str, default: bool) -> Result<()> {
+    let template = find_prompt(name)?;
+    if default {
+        print!("{}", template.default);
+    } else {
+        print!("{}", template.load());
+    }
+    Ok(())
+}
+
+/// Write default templates to the prompts directory
+pub fn cmd_ai_prompts_init(force: bool) -> Result<()> {
+    use crate::ai::prompts::{TEMPLATES, prompts_dir};
+
+    let dir = prompts_dir()?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut written = 0;
+    for template in TEMPLATES {
+        let path = template.path()?;
+        if path.exists() && !force {
+            println!("  {} {} (customized, kept)", "-".dimmed(), template.name);
+            continue;
+        }
+        std::fs::write(&path, template.default)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("  {} {}", "+".green(), path.display());
+        written += 1;
+    }
+
+    println!();
+    println!(
+        "{} Wrote {} template(s) to {}",
+        "+".green(),
+        written,
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Remove a customized template so the built-in default is used
+pub fn cmd_ai_prompts_reset(name: &str) -> Result<()> {
+    let template = find_prompt(name)?;
+    let path = template.path()?;
+
+    if !path.exists() {
+        println!("{} '{}' already uses the default", ">".dimmed(), name);
+        return Ok(());
+    }
+
+    std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    println!("{} Restored default prompt for '{}'", "+".green(), name);
+    Ok(())
+}
+
+/// List cached AI features with their entry counts and lifetimes
+pub fn cmd_ai_cache_list(db: &Database) -> Result<()> {
+    let config = HoardConfig::load()?;
+
+    println!("{}", "AI Response Cache".bold());
+    println!("{}", "=".repeat(30));
+    println!();
+
+    for feature in cache::FEATURES {
+        let entries = db.list_ai_cache(feature.prefix)?.len();
+        let ttl = match feature.ttl_days(&config) {
+            0 => "disabled".yellow().to_string(),
+            days => format!("{} days", days),
+        };
+        println!(
+            "{:<18} {:>5} cached  [{}] {}",
+            feature.name.cyan(),
+            entries,
+            ttl,
+            feature.description.dimmed()
+        );
+    }
+
+    println!();
+    println!(
+        "{} Run {} to clear a feature",
+        ">".dimmed(),
+        "hoards ai cache clear --feature <name>".yellow()
+    );
+    Ok(())
+}
+
+/// Clear cached AI responses, optionally for one feature
+pub fn cmd_ai_cache_clear(db: &Database, feature: Option<&str>) -> Result<()> {
+    let removed = match feature {
+        Some(name) => {
+            let feature = cache::find(name).with_context(|| {
+                let names: Vec<&str> = cache::FEATURES.iter().map(|f| f.name).collect();
+                format!(
+                    "Unknown feature '{}'. Available: {}",
+                    name,
+                    names.join(", ")
+                )
+            })?;
+            feature.clear(db)?
+        }
+        None => db.clear_ai_cache(None)?,
+    };
+    println!("{} Removed {} cached response(s)", "-".red(), removed);
+    Ok(())
+}
+
 /// Categorize tools using AI
-pub fn cmd_ai_categorize(dry_run: bool) -> Result<()> {
-    use crate::ai::{categorize_prompt, invoke_ai, parse_categorize_response};
+///
+/// Tools are sent in batches (see [`run_enrich_batches`]); `resume` skips
+/// tools an interrupted run already processed.
+pub fn cmd_ai_categorize(dry_run: bool, resume: bool) -> Result<()> {
+    use crate::ai::{categorize_prompt, parse_categorize_response};
 
     let db = Database::open()?;
 
@@ -175,37 +504,42 @@ pub fn cmd_ai_categorize(dry_run: bool) -> Result<()> {
         .into_iter()
         .collect();
 
-    // Generate prompt and call AI
-    let prompt = categorize_prompt(&uncategorized, &categories);
-
     println!("{} Asking AI to categorize...", ">".cyan());
-    let response = invoke_ai(&prompt)?;
-
-    // Parse response
-    let categorizations = parse_categorize_response(&response)?;
+    println!();
+    let categorized = run_enrich_batches(
+        &db,
+        &cache::CATEGORIZE,
+        uncategorized,
+        EnrichOptions {
+            resume,
+            dry_run,
+            make_prompt: |batch: &[crate::models::Tool]| categorize_prompt(batch, &categories),
+            parse: parse_categorize_response,
+            apply: |tool_name: &str, category: &str| {
+                if dry_run {
+                    println!(
+                        "  {} {} -> {}",
+                        "[dry]".yellow(),
+                        tool_name,
+                        category.cyan()
+                    );
+                    true
+                } else if let Err(e) = db.update_tool_category(tool_name, category) {
+                    println!("  {} {} : {}", "!".red(), tool_name, e);
+                    false
+                } else {
+                    println!("  {} {} -> {}", "+".green(), tool_name, category.cyan());
+                    true
+                }
+            },
+        },
+    )?;
 
-    if categorizations.is_empty() {
+    if categorized == 0 {
         println!("{} AI returned no categorizations", "!".yellow());
         return Ok(());
     }
 
-    // Apply or show results
-    println!();
-    for (tool_name, category) in &categorizations {
-        if dry_run {
-            println!(
-                "  {} {} -> {}",
-                "[dry]".yellow(),
-                tool_name,
-                category.cyan()
-            );
-        } else if let Err(e) = db.update_tool_category(tool_name, category) {
-            println!("  {} {} : {}", "!".red(), tool_name, e);
-        } else {
-            println!("  {} {} -> {}", "+".green(), tool_name, category.cyan());
-        }
-    }
-
     if dry_run {
         println!();
         println!(
@@ -218,14 +552,192 @@ pub fn cmd_ai_categorize(dry_run: bool) -> Result<()> {
         println!(
             "{} Categorized {} tool{}",
             "+".green(),
-            categorizations.len(),
-            if categorizations.len() == 1 { "" } else { "s" }
+            categorized,
+            if categorized == 1 { "" } else { "s" }
         );
     }
 
     Ok(())
 }
 
+/// How an enrichment job asks for and applies its results
+struct EnrichOptions<P, A> {
+    /// Skip tools a previous run already finished
+    resume: bool,
+    /// Leave checkpoints alone
+    dry_run: bool,
+    /// Builds the prompt for one batch of tools
+    make_prompt: P,
+    /// Parses a response into (tool, value) results
+    parse: fn(&str) -> Result<std::collections::HashMap<String, String>>,
+    /// Applies one (tool, value) result, returning whether it was applied
+    apply: A,
+}
+
+/// Run an enrichment job over `tools` in batches
+///
+/// Each batch is one AI request (answered from the cache when possible), and
+/// up to `ai.enrich.concurrency` requests run at once. Failed requests are
+/// retried with exponential backoff; if a batch still fails the run stops,
+/// keeping everything applied so far. Finished tools are checkpointed under
+/// the feature name so a resumed run skips them. Returns the number of
+/// applied results.
+fn run_enrich_batches<P, A>(
+    db: &Database,
+    feature: &cache::CacheFeature,
+    mut tools: Vec<crate::models::Tool>,
+    options: EnrichOptions<P, A>,
+) -> Result<usize>
+where
+    P: Fn(&[crate::models::Tool]) -> String,
+    A: FnMut(&str, &str) -> bool,
+{
+    let EnrichOptions {
+        resume,
+        dry_run,
+        make_prompt,
+        parse,
+        mut apply,
+    } = options;
+    let settings = HoardConfig::load()?.ai.enrich;
+    let job = feature.name;
+
+    if resume {
+        let done = db.get_checkpoint(job)?;
+        let before = tools.len();
+        tools.retain(|t| t.id.is_none_or(|id| !done.contains(&id)));
+        if before > tools.len() {
+            println!(
+                "{} Resuming: skipping {} tool(s) already processed",
+                ">".cyan(),
+                before - tools.len()
+            );
+        }
+    } else if !dry_run {
+        db.clear_checkpoint(job)?;
+    }
+
+    let batches: Vec<(Vec<crate::models::Tool>, String)> = tools
+        .chunks(settings.batch_size.max(1))
+        .map(|batch| (batch.to_vec(), make_prompt(batch)))
+        .collect();
+    let total = batches.len();
+    let mut applied = 0;
+    let mut finished = 0;
+
+    for group in batches.chunks(settings.concurrency.max(1)) {
+        if total > 1 {
+            let first = finished + 1;
+            let last = finished + group.len();
+            let range = if first == last {
+                first.to_string()
+            } else {
+                format!("{}-{}", first, last)
+            };
+            println!("{} Batch {} of {}", ">".dimmed(), range, total);
+        }
+
+        // Cache lookups need the database, so only uncached prompts go to
+        // worker threads
+        let cached: Vec<Option<String>> = group
+            .iter()
+            .map(|(_, prompt)| {
+                feature
+                    .get(db, &feature.prompt_key(prompt))
+                    .filter(|response| parse(response).is_ok())
+            })
+            .collect();
+        let responses: Vec<Result<String>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = group
+                .iter()
+                .zip(&cached)
+                .map(|((_, prompt), cached)| {
+                    let cached = cached.clone();
+                    let settings = &settings;
+                    scope.spawn(move || match cached {
+                        Some(response) => Ok(response),
+                        None => invoke_with_backoff(job, prompt, settings),
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| {
+                    h.join()
+                        .unwrap_or_else(|_| Err(anyhow::anyhow!("AI worker panicked")))
+                })
+                .collect()
+        });
+
+        let mut failure = None;
+        for (((batch, prompt), cached), response) in group.iter().zip(&cached).zip(responses) {
+            let results = match response.and_then(|r| parse(&r).map(|parsed| (r, parsed))) {
+                Ok((response, results)) => {
+                    if cached.is_none() {
+                        feature.set(db, &feature.prompt_key(prompt), &response)?;
+                    }
+                    results
+                }
+                Err(e) => {
+                    failure.get_or_insert(e);
+                    continue;
+                }
+            };
+
+            for (tool_name, value) in &results {
+                if apply(tool_name, value) {
+                    applied += 1;
+                }
+            }
+            if !dry_run {
+                let ids: Vec<i64> = batch.iter().filter_map(|t| t.id).collect();
+                db.add_checkpoint(job, &ids)?;
+            }
+            finished += 1;
+        }
+
+        if let Some(e) = failure {
+            println!();
+            println!(
+                "{} Stopped after {}/{} batches; {} result(s) were kept",
+                "!".yellow(),
+                finished,
+                total,
+                applied
+            );
+            if !dry_run {
+                println!("  Run again with {} to continue", "--resume".yellow());
+            }
+            return Err(e.context(format!("AI {} request failed", job)));
+        }
+    }
+
+    if !dry_run {
+        db.clear_checkpoint(job)?;
+    }
+    Ok(applied)
+}
+
+/// Invoke the AI, retrying failures with exponential backoff
+fn invoke_with_backoff(
+    feature: &str,
+    prompt: &str,
+    settings: &crate::config::EnrichConfig,
+) -> Result<String> {
+    let mut attempt = 0;
+    loop {
+        match crate::ai::invoke_ai(feature, prompt) {
+            Ok(response) => return Ok(response),
+            Err(_) if attempt < settings.max_retries => {
+                let delay = settings.backoff_secs.saturating_mul(1 << attempt.min(16));
+                std::thread::sleep(std::time::Duration::from_secs(delay));
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 /// Suggest bundles using AI based on usage patterns
 pub fn cmd_ai_suggest_bundle(count: usize) -> Result<()> {
     use crate::ai::{invoke_ai, parse_bundle_response, suggest_bundle_prompt};
@@ -288,7 +800,7 @@ pub fn cmd_ai_suggest_bundle(count: usize) -> Result<()> {
 
     // Generate prompt and call AI
     let prompt = suggest_bundle_prompt(&tools, &bundles, &usage_data, count);
-    let response = invoke_ai(&prompt)?;
+    let response = invoke_ai("suggest-bundle", &prompt)?;
 
     // Parse response
     let suggestions = parse_bundle_response(&response)?;
@@ -430,6 +942,7 @@ fn create_bundle_from_suggestion(
         &suggestion.name,
         suggestion.tools.clone(),
         Some(suggestion.description.clone()),
+        None,
     )?;
 
     Ok(())
@@ -451,7 +964,7 @@ fn install_bundle_tools(db: &Database, suggestion: &crate::ai::BundleSuggestion)
 
             // Try to install
             println!("  {} Installing {}...", ">".cyan(), tool_name);
-            if let Err(e) = crate::cmd_install(db, tool_name, None, None, false) {
+            if let Err(e) = crate::cmd_install(db, tool_name, None, None, false, false) {
                 println!("    {} Failed: {}", "!".yellow(), e);
             } else {
                 installed_count += 1;
@@ -478,8 +991,10 @@ fn install_bundle_tools(db: &Database, suggestion: &crate::ai::BundleSuggestion)
 }
 
 /// Generate descriptions for tools using AI
-pub fn cmd_ai_describe(dry_run: bool, limit: Option<usize>) -> Result<()> {
-    use crate::ai::{describe_prompt, invoke_ai, parse_describe_response};
+///
+/// Batched and resumable like [`cmd_ai_categorize`].
+pub fn cmd_ai_describe(dry_run: bool, limit: Option<usize>, resume: bool) -> Result<()> {
+    use crate::ai::{describe_prompt, parse_describe_response};
 
     let db = Database::open()?;
 
@@ -514,34 +1029,46 @@ pub fn cmd_ai_describe(dry_run: bool, limit: Option<usize>) -> Result<()> {
         if no_description.len() == 1 { "" } else { "s" }
     );
 
-    // Generate prompt and call AI
-    let prompt = describe_prompt(&no_description);
+    // Repository descriptions give the AI something to go on
+    let hints: std::collections::HashMap<String, String> = db
+        .get_all_github_info()?
+        .into_iter()
+        .filter_map(|(name, gh)| Some((name, gh.description?)))
+        .collect();
 
     println!("{} Asking AI to generate descriptions...", ">".cyan());
-    let response = invoke_ai(&prompt)?;
-
-    // Parse response
-    let descriptions = parse_describe_response(&response)?;
+    println!();
+    let described = run_enrich_batches(
+        &db,
+        &cache::DESCRIBE,
+        no_description,
+        EnrichOptions {
+            resume,
+            dry_run,
+            make_prompt: |batch: &[crate::models::Tool]| describe_prompt(batch, &hints),
+            parse: parse_describe_response,
+            apply: |tool_name: &str, description: &str| {
+                if dry_run {
+                    println!("  {} {}", "[dry]".yellow(), tool_name.cyan());
+                    println!("       {}", description.dimmed());
+                    true
+                } else if let Err(e) = db.update_tool_description(tool_name, description) {
+                    println!("  {} {} : {}", "!".red(), tool_name, e);
+                    false
+                } else {
+                    println!("  {} {}", "+".green(), tool_name.cyan());
+                    println!("       {}", description.dimmed());
+                    true
+                }
+            },
+        },
+    )?;
 
-    if descriptions.is_empty() {
+    if described == 0 {
         println!("{} AI returned no descriptions", "!".yellow());
         return Ok(());
     }
 
-    // Apply or show results
-    println!();
-    for (tool_name, description) in &descriptions {
-        if dry_run {
-            println!("  {} {}", "[dry]".yellow(), tool_name.cyan());
-            println!("       {}", description.dimmed());
-        } else if let Err(e) = db.update_tool_description(tool_name, description) {
-            println!("  {} {} : {}", "!".red(), tool_name, e);
-        } else {
-            println!("  {} {}", "+".green(), tool_name.cyan());
-            println!("       {}", description.dimmed());
-        }
-    }
-
     if dry_run {
         println!();
         println!(
@@ -554,8 +1081,8 @@ pub fn cmd_ai_describe(dry_run: bool, limit: Option<usize>) -> Result<()> {
         println!(
             "{} Added descriptions for {} tool{}",
             "+".green(),
-            descriptions.len(),
-            if descriptions.len() == 1 { "" } else { "s" }
+            described,
+            if described == 1 { "" } else { "s" }
         );
     }
 
@@ -571,10 +1098,10 @@ pub fn cmd_ai_extract(
     delay_ms: u64,
 ) -> Result<()> {
     use crate::ai::{
-        ExtractedTool, extract_prompt, fetch_readme, fetch_repo_version, invoke_ai,
-        parse_extract_response, parse_github_url,
+        ExtractedTool, extract_prompt, invoke_ai, parse_extract_response, parse_github_url,
     };
     use crate::db::CachedExtraction;
+    use crate::forge::github::{fetch_readme, fetch_repo_version};
     use crate::{InstallSource, Tool};
     use dialoguer::Confirm;
     use std::thread;
@@ -651,7 +1178,7 @@ pub fn cmd_ai_extract(
         let prompt = extract_prompt(&readme);
         println!("  {} Asking AI to extract...", ">".dimmed());
 
-        let response = match invoke_ai(&prompt) {
+        let response = match invoke_ai("extract", &prompt) {
             Ok(r) => r,
             Err(e) => {
                 println!("  {} AI extraction failed: {}", "!".red(), e);
@@ -826,8 +1353,8 @@ pub fn cmd_ai_cheatsheet(tool_name: &str, refresh: bool) -> Result<()> {
     })?;
 
     // Generate prompt and call AI
-    let prompt = cheatsheet_prompt(tool_name, &help_output);
-    let response = invoke_ai(&prompt)?;
+    let prompt = cheatsheet_prompt(tool_name, tool.description.as_deref(), &help_output);
+    let response = invoke_ai("cheatsheet", &prompt)?;
 
     // Parse response
     let cheatsheet = parse_cheatsheet_response(&response)?;
@@ -843,16 +1370,16 @@ pub fn cmd_ai_cheatsheet(tool_name: &str, refresh: bool) -> Result<()> {
 }
 
 /// Get cached cheatsheet from database, checking version for invalidation
-fn get_cached_cheatsheet(
+pub(crate) fn get_cached_cheatsheet(
     db: &Database,
     tool_name: &str,
     binary: &str,
 ) -> Result<Option<crate::ai::Cheatsheet>> {
     use crate::ai::{CachedCheatsheet, get_tool_version};
 
-    let cache_key = format!("cheatsheet:{}", tool_name);
+    let cache_key = format!("{}{}", cache::CHEATSHEET.prefix, tool_name);
 
-    match db.get_ai_cache(&cache_key)? {
+    match cache::CHEATSHEET.get(db, &cache_key) {
         Some(json) => {
             // Try to parse as CachedCheatsheet (new format with version)
             if let Ok(cached) = serde_json::from_str::<CachedCheatsheet>(&json) {
@@ -878,7 +1405,7 @@ fn get_cached_cheatsheet(
 }
 
 /// Cache a cheatsheet in the database with version info
-fn cache_cheatsheet(
+pub(crate) fn cache_cheatsheet(
     db: &Database,
     tool_name: &str,
     binary: &str,
@@ -886,40 +1413,159 @@ fn cache_cheatsheet(
 ) -> Result<()> {
     use crate::ai::{CachedCheatsheet, get_tool_version};
 
-    let cache_key = format!("cheatsheet:{}", tool_name);
+    let cache_key = format!("{}{}", cache::CHEATSHEET.prefix, tool_name);
     let cached = CachedCheatsheet {
         version: get_tool_version(binary),
         cheatsheet: cheatsheet.clone(),
     };
     let json = serde_json::to_string(&cached)?;
-    db.set_ai_cache(&cache_key, &json)?;
-    Ok(())
-}
+    cache::CHEATSHEET.set(db, &cache_key, &json)?;
 
-/// Invalidate cached cheatsheet for a tool (call after install/upgrade)
-pub fn invalidate_cheatsheet_cache(db: &Database, tool_name: &str) -> Result<()> {
-    let cache_key = format!("cheatsheet:{}", tool_name);
-    db.delete_ai_cache(&cache_key)?;
+    // Keep an exported handbook in sync
+    if let Some(dir) = HoardConfig::load()?.ai.cheatsheet_export_dir {
+        write_cheatsheet_file(db, &dir, tool_name, cheatsheet)
+            .and_then(|_| write_cheatsheet_index(db, &dir))
+            .with_context(|| format!("Failed to update cheatsheet export in {}", dir.display()))?;
+    }
     Ok(())
 }
 
-/// Generate a workflow-oriented cheatsheet for all tools in a bundle
-pub fn cmd_ai_bundle_cheatsheet(bundle_name: &str, refresh: bool) -> Result<()> {
-    use crate::ai::{
-        bundle_cheatsheet_prompt, format_cheatsheet, get_help_output, get_tool_version, invoke_ai,
-        parse_cheatsheet_response,
-    };
+/// All cached tool cheatsheets (bundle cheatsheets excluded), by tool name
+fn cached_tool_cheatsheets(db: &Database) -> Result<Vec<(String, crate::ai::Cheatsheet)>> {
+    use crate::ai::{CachedCheatsheet, Cheatsheet};
 
-    let db = Database::open()?;
+    Ok(db
+        .list_ai_cache("cheatsheet:")?
+        .into_iter()
+        .filter(|(key, _)| !key.starts_with("cheatsheet:bundle:"))
+        .filter_map(|(key, json)| {
+            let tool = key.strip_prefix("cheatsheet:")?.to_string();
+            let cheatsheet = serde_json::from_str::<CachedCheatsheet>(&json)
+                .map(|c| c.cheatsheet)
+                .or_else(|_| serde_json::from_str::<Cheatsheet>(&json))
+                .ok()?;
+            Some((tool, cheatsheet))
+        })
+        .collect())
+}
 
-    // Get the bundle
-    let bundle = db
-        .get_bundle(bundle_name)?
-        .ok_or_else(|| anyhow::anyhow!("Bundle '{}' not found", bundle_name))?;
+/// File name used for a tool's exported cheatsheet
+fn cheatsheet_file_name(tool_name: &str) -> String {
+    let safe: String = tool_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{}.md", safe)
+}
 
-    if bundle.tools.is_empty() {
-        println!("Bundle '{}' has no tools", bundle_name);
-        return Ok(());
+/// Write one tool's cheatsheet as markdown
+fn write_cheatsheet_file(
+    db: &Database,
+    dir: &std::path::Path,
+    tool_name: &str,
+    cheatsheet: &crate::ai::Cheatsheet,
+) -> Result<()> {
+    let description = db.get_tool_by_name(tool_name)?.and_then(|t| t.description);
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(
+        dir.join(cheatsheet_file_name(tool_name)),
+        crate::ai::cheatsheet_markdown(cheatsheet, description.as_deref()),
+    )?;
+    Ok(())
+}
+
+/// Rewrite index.md listing every exported cheatsheet
+fn write_cheatsheet_index(db: &Database, dir: &std::path::Path) -> Result<()> {
+    let mut index = vec!["# Tool Cheatsheets".to_string(), String::new()];
+    for (tool, _) in cached_tool_cheatsheets(db)? {
+        let description = db
+            .get_tool_by_name(&tool)?
+            .and_then(|t| t.description)
+            .map(|d| format!(" - {}", d))
+            .unwrap_or_default();
+        index.push(format!(
+            "- [{}]({}){}",
+            tool,
+            cheatsheet_file_name(&tool),
+            description
+        ));
+    }
+    index.push(String::new());
+    std::fs::write(dir.join("index.md"), index.join("\n"))?;
+    Ok(())
+}
+
+/// Export every cached cheatsheet to a markdown directory and keep it in sync
+pub fn cmd_ai_cheatsheet_export(dir: &str) -> Result<()> {
+    let db = Database::open()?;
+    let dir = crate::commands::config::expand_path(dir);
+    let dir = if dir.is_absolute() {
+        dir
+    } else {
+        std::env::current_dir()?.join(dir)
+    };
+
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let cheatsheets = cached_tool_cheatsheets(&db)?;
+    for (tool, cheatsheet) in &cheatsheets {
+        write_cheatsheet_file(&db, &dir, tool, cheatsheet)?;
+    }
+    write_cheatsheet_index(&db, &dir)?;
+
+    let mut config = HoardConfig::load()?;
+    config.ai.cheatsheet_export_dir = Some(dir.clone());
+    config.save()?;
+
+    println!(
+        "{} Exported {} cheatsheet(s) to {}",
+        "+".green(),
+        cheatsheets.len(),
+        dir.display().to_string().bold()
+    );
+    println!(
+        "  {} New and refreshed cheatsheets will be written there too",
+        ">".dimmed()
+    );
+    if cheatsheets.is_empty() {
+        println!(
+            "  {} Generate some with {}",
+            ">".dimmed(),
+            "hoards ai cheatsheet <tool>".cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Invalidate cached cheatsheet for a tool (call after install/upgrade)
+pub fn invalidate_cheatsheet_cache(db: &Database, tool_name: &str) -> Result<()> {
+    let cache_key = format!("cheatsheet:{}", tool_name);
+    db.delete_ai_cache(&cache_key)?;
+    Ok(())
+}
+
+/// Generate a workflow-oriented cheatsheet for all tools in a bundle
+pub fn cmd_ai_bundle_cheatsheet(bundle_name: &str, refresh: bool) -> Result<()> {
+    use crate::ai::{
+        bundle_cheatsheet_prompt, format_cheatsheet, get_help_output, get_tool_version, invoke_ai,
+        parse_cheatsheet_response,
+    };
+
+    let db = Database::open()?;
+
+    // Get the bundle
+    let bundle = db
+        .get_bundle(bundle_name)?
+        .ok_or_else(|| anyhow::anyhow!("Bundle '{}' not found", bundle_name))?;
+
+    if bundle.tools.is_empty() {
+        println!("Bundle '{}' has no tools", bundle_name);
+        return Ok(());
     }
 
     // Collect tool info and versions for cache key
@@ -984,7 +1630,7 @@ pub fn cmd_ai_bundle_cheatsheet(bundle_name: &str, refresh: bool) -> Result<()>
 
     // Generate prompt and call AI
     let prompt = bundle_cheatsheet_prompt(bundle_name, &tools_help);
-    let response = invoke_ai(&prompt)?;
+    let response = invoke_ai("bundle_cheatsheet", &prompt)?;
 
     // Parse response
     let cheatsheet = parse_cheatsheet_response(&response)?;
@@ -1005,9 +1651,9 @@ fn get_cached_bundle_cheatsheet(
     bundle_name: &str,
     tools_info: &[(String, String, Option<String>)],
 ) -> Result<Option<crate::ai::Cheatsheet>> {
-    let cache_key = format!("cheatsheet:bundle:{}", bundle_name);
+    let cache_key = format!("{}bundle:{}", cache::CHEATSHEET.prefix, bundle_name);
 
-    match db.get_ai_cache(&cache_key)? {
+    match cache::CHEATSHEET.get(db, &cache_key) {
         Some(json) => {
             // Parse as CachedBundleCheatsheet which includes version map
             if let Ok(cached) = serde_json::from_str::<CachedBundleCheatsheet>(&json) {
@@ -1040,7 +1686,7 @@ fn cache_bundle_cheatsheet(
     tools_info: &[(String, String, Option<String>)],
     cheatsheet: &crate::ai::Cheatsheet,
 ) -> Result<()> {
-    let cache_key = format!("cheatsheet:bundle:{}", bundle_name);
+    let cache_key = format!("{}bundle:{}", cache::CHEATSHEET.prefix, bundle_name);
 
     // Store all tools - use empty string for tools without version info
     let versions: std::collections::HashMap<String, String> = tools_info
@@ -1054,7 +1700,7 @@ fn cache_bundle_cheatsheet(
     };
 
     let json = serde_json::to_string(&cached)?;
-    db.set_ai_cache(&cache_key, &json)?;
+    cache::CHEATSHEET.set(db, &cache_key, &json)?;
     Ok(())
 }
 
@@ -1075,7 +1721,7 @@ pub fn cmd_ai_discover(
     no_stars: bool,
     dry_run: bool,
 ) -> Result<()> {
-    use crate::ai::{ToolRecommendation, discovery_prompt, invoke_ai, parse_discovery_response};
+    use crate::ai::{ToolRecommendation, discovery_prompt, parse_discovery_response};
     use crate::scanner::is_installed;
     use dialoguer::{MultiSelect, theme::ColorfulTheme};
     use indicatif::{ProgressBar, ProgressStyle};
@@ -1108,11 +1754,12 @@ pub fn cmd_ai_discover(
     spinner.set_message("Asking AI for recommendations...");
     spinner.enable_steady_tick(std::time::Duration::from_millis(80));
 
-    let response = invoke_ai(&prompt)?;
+    let result = cache::DISCOVERY.invoke(db, &prompt, parse_discovery_response);
     spinner.finish_and_clear();
-
-    // Parse response
-    let mut discovery = parse_discovery_response(&response)?;
+    let (mut discovery, cached) = result?;
+    if cached {
+        println!("{} Using cached recommendations", ">".dimmed());
+    }
 
     // Limit results
     if discovery.tools.len() > limit {
@@ -1257,14 +1904,20 @@ pub fn cmd_ai_discover(
     Ok(())
 }
 
+/// Label marking the tools an AI feature suggested and installed, for
+/// `insights graph`
+fn ai_label(feature: &str) -> String {
+    format!("ai:{}", feature)
+}
+
 /// Install a tool discovered via AI, using proper extraction when possible
 fn install_discovered_tool(db: &Database, tool: &crate::ai::ToolRecommendation) -> Result<()> {
     use crate::ai::{
-        ExtractedTool, extract_prompt, fetch_readme, fetch_repo_version, invoke_ai,
-        parse_extract_response, parse_github_url,
+        ExtractedTool, extract_prompt, invoke_ai, parse_extract_response, parse_github_url,
     };
     use crate::commands::install::get_safe_install_command;
     use crate::db::CachedExtraction;
+    use crate::forge::github::{fetch_readme, fetch_repo_version};
     use crate::models::{InstallSource, Tool};
     use indicatif::{ProgressBar, ProgressStyle};
 
@@ -1303,7 +1956,9 @@ fn install_discovered_tool(db: &Database, tool: &crate::ai::ToolRecommendation)
                         Ok(readme) => {
                             spinner.set_message("Extracting tool info with AI...");
                             let prompt = extract_prompt(&readme);
-                            match invoke_ai(&prompt).and_then(|r| parse_extract_response(&r)) {
+                            match invoke_ai("extract", &prompt)
+                                .and_then(|r| parse_extract_response(&r))
+                            {
                                 Ok(ext) => {
                                     spinner.finish_and_clear();
                                     // Cache it
@@ -1449,6 +2104,7 @@ fn install_discovered_tool(db: &Database, tool: &crate::ai::ToolRecommendation)
         } else {
             db.set_tool_installed(&name, true)?;
         }
+        db.add_labels(&name, &[ai_label("discover")])?;
 
         // Invalidate any cached cheatsheet
         let _ = invalidate_cheatsheet_cache(db, &name);
@@ -1493,20 +2149,11 @@ fn format_stars(stars: u64) -> String {
 
 /// Fetch GitHub stars for a repo
 fn fetch_github_stars(repo: &str) -> Result<u64> {
-    // Use the GitHub API
-    let url = format!("https://api.github.com/repos/{}", repo);
-
-    let mut response = ureq::get(&url)
-        .header("User-Agent", "hoards-cli")
-        .header("Accept", "application/vnd.github.v3+json")
-        .call()
-        .context("Failed to fetch GitHub info")?;
-
-    let body = response.body_mut().read_to_string()?;
-    let json: serde_json::Value = serde_json::from_str(&body)?;
-    let stars = json["stargazers_count"].as_u64().unwrap_or(0);
-
-    Ok(stars)
+    let (owner, name) = repo
+        .split_once('/')
+        .with_context(|| format!("Invalid repository: {}", repo))?;
+    let info = crate::forge::github::get_repo_info(owner, name)?;
+    Ok(info.stars.max(0) as u64)
 }
 
 // ==================== AI Analyze ====================
@@ -1573,13 +2220,558 @@ fn detect_shell_aliases() -> std::collections::HashMap<String, String> {
     aliases
 }
 
+/// Summarize release notes of pending updates into a prioritized digest
+pub fn cmd_ai_updates_summary(
+    db: &Database,
+    source_filter: Option<String>,
+    refresh: bool,
+) -> Result<()> {
+    use crate::ai::changelog::{
+        UpdatePriority, cache_notes, cached_notes, parse_summary, pending_updates, sort_notes,
+        summary_prompt,
+    };
+    use crate::ai::invoke_ai;
+    use crate::commands::updates_cmd::collect_updates;
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.cyan} {msg}")
+            .unwrap(),
+    );
+    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+
+    spinner.set_message("Checking for updates...");
+    let updates = collect_updates(source_filter.as_deref());
+
+    if updates.is_empty() {
+        spinner.finish_and_clear();
+        println!("{} All tools are up to date!", "+".green());
+        return Ok(());
+    }
+
+    let pending = pending_updates(db, &updates)?;
+    let (mut notes, uncached) = if refresh {
+        (Vec::new(), pending)
+    } else {
+        cached_notes(db, pending)
+    };
+
+    if !uncached.is_empty() {
+        spinner.set_message(format!(
+            "Summarizing release notes for {} update(s)...",
+            uncached.len()
+        ));
+        let response = invoke_ai("updates_summary", &summary_prompt(&uncached));
+        let fresh = response.and_then(|r| parse_summary(&r));
+        let fresh = match fresh {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                spinner.finish_and_clear();
+                return Err(e);
+            }
+        };
+        cache_notes(db, &uncached, &fresh)?;
+        notes.extend(fresh);
+    }
+    spinner.finish_and_clear();
+    sort_notes(&mut notes);
+
+    println!(
+        "{} {} pending update(s)\n",
+        ">".cyan(),
+        updates.len().to_string().bold()
+    );
+    for note in &notes {
+        let label = format!("{:<11}", note.priority.label());
+        let label = match note.priority {
+            UpdatePriority::Security => label.red().bold(),
+            UpdatePriority::Breaking => label.yellow().bold(),
+            UpdatePriority::Recommended => label.green(),
+            UpdatePriority::Optional => label.normal(),
+            UpdatePriority::Skip => label.dimmed(),
+        };
+        let versions = updates
+            .iter()
+            .find(|u| u.name == note.tool)
+            .map(|u| format!("{} -> {}", u.current, u.latest))
+            .unwrap_or_default();
+        println!("  {} {} {}", label, note.tool.bold(), versions.dimmed());
+        println!("              {}", note.summary);
+    }
+
+    let missing: Vec<&str> = updates
+        .iter()
+        .filter(|u| !notes.iter().any(|n| n.tool == u.name))
+        .map(|u| u.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        println!("\n{} No summary for: {}", "!".yellow(), missing.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Turn a natural-language request into a plan and execute it after confirmation
+pub fn cmd_ai_do(db: &Database, request: &str, yes: bool, dry_run: bool) -> Result<()> {
+    use crate::ai::{action_plan_prompt, invoke_ai, parse_action_plan};
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    let installed_tools: Vec<String> = db
+        .get_all_tools()?
+        .into_iter()
+        .filter(|t| t.is_installed)
+        .map(|t| t.name)
+        .collect();
+    let bundles = db.list_bundles()?;
+
+    let prompt = action_plan_prompt(request, &installed_tools, &bundles);
+
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.cyan} {msg}")
+            .unwrap(),
+    );
+    spinner.set_message("Asking AI for a plan...");
+    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+
+    let response = invoke_ai("action_plan", &prompt);
+    spinner.finish_and_clear();
+    let plan = parse_action_plan(&response?)?;
+
+    if plan.steps.is_empty() {
+        println!("{} Nothing to do: {}", "!".yellow(), plan.summary);
+        return Ok(());
+    }
+
+    // Resolve every install command up front so nothing runs if one is unsafe
+    let mut commands = Vec::with_capacity(plan.steps.len());
+    for step in &plan.steps {
+        let command = match step {
+            crate::ai::PlanStep::Install { tool, source, .. } => Some(
+                get_safe_install_command(tool, source, None)?.with_context(|| {
+                    format!("Cannot install '{}': unsupported source '{}'", tool, source)
+                })?,
+            ),
+            _ => None,
+        };
+        commands.push(command);
+    }
+
+    println!("{} {}", ">".cyan(), plan.summary.bold());
+    println!();
+    for (i, (step, command)) in plan.steps.iter().zip(&commands).enumerate() {
+        println!(
+            "  {}. {}",
+            i + 1,
+            describe_plan_step(step, command.as_ref())
+        );
+    }
+    println!();
+
+    if dry_run {
+        println!("{} Dry run - no changes made", "!".yellow());
+        return Ok(());
+    }
+
+    if !yes {
+        if !std::io::stdout().is_terminal() {
+            anyhow::bail!("Refusing to run the plan without confirmation (use --yes)");
+        }
+        let proceed = dialoguer::Confirm::new()
+            .with_prompt("Run this plan?")
+            .default(false)
+            .interact()?;
+        if !proceed {
+            println!("{} Cancelled", "!".yellow());
+            return Ok(());
+        }
+    }
+
+    let total = plan.steps.len();
+    for (i, (step, command)) in plan.steps.iter().zip(commands).enumerate() {
+        if let Err(e) = execute_plan_step(db, step, command) {
+            println!("{} Step {} failed: {}", "!".red(), i + 1, e);
+            if i + 1 < total {
+                println!(
+                    "  {} Skipped remaining {} step(s)",
+                    ">".dimmed(),
+                    total - i - 1
+                );
+            }
+            return Err(e);
+        }
+    }
+
+    println!("{} Plan complete", "+".green());
+    Ok(())
+}
+
+/// One-line description of a plan step
+fn describe_plan_step(step: &crate::ai::PlanStep, command: Option<&SafeCommand>) -> String {
+    use crate::ai::PlanStep;
+
+    match step {
+        PlanStep::Install {
+            tool, description, ..
+        } => {
+            let mut line = format!("Install {}", tool.bold());
+            if let Some(desc) = description {
+                line.push_str(&format!(" - {}", desc));
+            }
+            if let Some(cmd) = command {
+                line.push_str(&format!(" {}", format!("({})", cmd).dimmed()));
+            }
+            line
+        }
+        PlanStep::CreateBundle { bundle, .. } => format!("Create bundle {}", bundle.bold()),
+        PlanStep::AddToBundle { bundle, tools } => {
+            format!("Add {} to bundle {}", tools.join(", "), bundle.bold())
+        }
+        PlanStep::RemoveFromBundle { bundle, tools } => {
+            format!("Remove {} from bundle {}", tools.join(", "), bundle.bold())
+        }
+        PlanStep::Label { tool, labels } => {
+            format!("Label {} with {}", tool.bold(), labels.join(", "))
+        }
+        PlanStep::Favorite { tool } => format!("Mark {} as favorite", tool.bold()),
+    }
+}
+
+/// Execute one plan step through the regular install and bundle APIs
+fn execute_plan_step(
+    db: &Database,
+    step: &crate::ai::PlanStep,
+    command: Option<SafeCommand>,
+) -> Result<()> {
+    use crate::ai::PlanStep;
+    use crate::models::{Bundle, InstallSource, Tool};
+
+    match step {
+        PlanStep::Install {
+            tool,
+            source,
+            description,
+        } => {
+            if db.get_tool_by_name(tool)?.is_some_and(|t| t.is_installed) {
+                println!("  {} {} is already installed", "-".dimmed(), tool);
+                return Ok(());
+            }
+            let command = command.context("Missing install command")?;
+            println!("  {} Running: {}", ">".cyan(), command);
+            let status = command.execute()?;
+            if !status.success() {
+                anyhow::bail!("Installation of '{}' failed", tool);
+            }
+
+            if db.get_tool_by_name(tool)?.is_none() {
+                let mut new_tool = Tool::new(tool)
+                    .with_source(InstallSource::from(source.as_str()))
+                    .installed();
+                if let Some(desc) = description {
+                    new_tool = new_tool.with_description(desc);
+                }
+                db.insert_tool(&new_tool)?;
+            } else {
+                db.set_tool_installed(tool, true)?;
+            }
+            db.add_labels(tool, &[ai_label("do")])?;
+            let _ = invalidate_cheatsheet_cache(db, tool);
+            println!("  {} Installed {}", "+".green(), tool);
+        }
+        PlanStep::CreateBundle {
+            bundle,
+            description,
+        } => {
+            if db.get_bundle(bundle)?.is_some() {
+                println!("  {} Bundle '{}' already exists", "-".dimmed(), bundle);
+                return Ok(());
+            }
+            let mut new_bundle = Bundle::new(bundle, Vec::new());
+            if let Some(desc) = description {
+                new_bundle = new_bundle.with_description(desc);
+            }
+            db.create_bundle(&new_bundle)?;
+            println!("  {} Created bundle '{}'", "+".green(), bundle);
+        }
+        PlanStep::AddToBundle { bundle, tools } => {
+            if db.get_bundle(bundle)?.is_none() {
+                db.create_bundle(&Bundle::new(bundle, Vec::new()))?;
+                println!("  {} Created bundle '{}'", "+".green(), bundle);
+            }
+            db.add_to_bundle(bundle, tools)?;
+            println!(
+                "  {} Added {} to '{}'",
+                "+".green(),
+                tools.join(", "),
+                bundle
+            );
+        }
+        PlanStep::RemoveFromBundle { bundle, tools } => {
+            if !db.remove_from_bundle(bundle, tools)? {
+                anyhow::bail!("Bundle '{}' not found", bundle);
+            }
+            println!(
+                "  {} Removed {} from '{}'",
+                "+".green(),
+                tools.join(", "),
+                bundle
+            );
+        }
+        PlanStep::Label { tool, labels } => {
+            if !db.add_labels(tool, labels)? {
+                anyhow::bail!("Tool '{}' not found", tool);
+            }
+            println!(
+                "  {} Labeled {} with {}",
+                "+".green(),
+                tool,
+                labels.join(", ")
+            );
+        }
+        PlanStep::Favorite { tool } => {
+            if !db.set_tool_favorite(tool, true)? {
+                anyhow::bail!("Tool '{}' not found", tool);
+            }
+            println!("  {} Marked {} as favorite", "+".green(), tool);
+        }
+    }
+    Ok(())
+}
+
+/// Find tracked tools with overlapping purpose
+///
+/// The AI groups tools (e.g. exa/eza/lsd) and picks one to keep, which is
+/// recorded as replacing the others. Redundant tools without recorded usage
+/// are reported as safe to remove.
+pub fn cmd_ai_dedupe(db: &Database, dry_run: bool, json_output: bool) -> Result<()> {
+    use crate::ai::{dedupe_prompt, invoke_ai, parse_dedupe_response};
+    use crate::db::RelationKind;
+    use indicatif::{ProgressBar, ProgressStyle};
+
+    let tools = db.get_all_tools()?;
+    if tools.len() < 2 {
+        println!("{} Not enough tools tracked to compare", "!".yellow());
+        return Ok(());
+    }
+    let usage: std::collections::HashMap<String, i64> = db
+        .get_all_usage()?
+        .into_iter()
+        .map(|(name, u)| (name, u.use_count))
+        .collect();
+    let known: Vec<String> = tools.iter().map(|t| t.name.clone()).collect();
+
+    let prompt = dedupe_prompt(&tools, &usage);
+
+    let spinner = ProgressBar::new_spinner();
+    spinner.set_style(
+        ProgressStyle::default_spinner()
+            .template("{spinner:.cyan} {msg}")
+            .unwrap(),
+    );
+    spinner.set_message(format!("Comparing {} tools...", tools.len()));
+    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+
+    let response = invoke_ai("dedupe", &prompt);
+    spinner.finish_and_clear();
+    let groups = parse_dedupe_response(&response?, &known)?;
+
+    if !dry_run {
+        for group in &groups {
+            for redundant in group.redundant() {
+                db.add_relation(
+                    &group.keep,
+                    redundant,
+                    RelationKind::Replaces,
+                    Some(&group.reason)
+                        .filter(|r| !r.is_empty())
+                        .map(String::as_str),
+                )?;
+            }
+        }
+    }
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&groups)?);
+        return Ok(());
+    }
+
+    if groups.is_empty() {
+        println!("{} No overlapping tools found", "+".green());
+        return Ok(());
+    }
+
+    let uses = |name: &str| usage.get(name).copied().unwrap_or(0);
+    let mut removable = Vec::new();
+    println!(
+        "{} {} group(s) of overlapping tools",
+        ">".cyan(),
+        groups.len()
+    );
+    for group in &groups {
+        println!();
+        println!("  {}", group.purpose.bold());
+        println!(
+            "    {} {} ({} uses)",
+            "keep".green(),
+            group.keep,
+            uses(&group.keep)
+        );
+        for redundant in group.redundant() {
+            if uses(redundant) == 0 {
+                println!("    {} {} (unused)", "drop".yellow(), redundant);
+                removable.push(redundant.clone());
+            } else {
+                println!(
+                    "    {} {} ({} uses)",
+                    "drop".yellow(),
+                    redundant,
+                    uses(redundant)
+                );
+            }
+        }
+        if !group.reason.is_empty() {
+            println!("    {}", group.reason.dimmed());
+        }
+    }
+
+    println!();
+    if dry_run {
+        println!("{} Dry run - relationships not saved", "!".yellow());
+    } else {
+        println!(
+            "{} Saved 'replaces' relationships (see 'hoards show <tool>')",
+            "+".green()
+        );
+    }
+    if !removable.is_empty() {
+        println!(
+            "{} Unused and safe to remove: {}",
+            ">".cyan(),
+            removable.join(", ")
+        );
+        println!("  Run 'hoards uninstall <tool>' to remove them");
+    }
+
+    Ok(())
+}
+
+/// Show recorded AI calls grouped by feature
+///
+/// `days` limits the report to recent calls (0 for all time); `reset`
+/// deletes the history instead.
+pub fn cmd_ai_usage(db: &Database, days: i64, json_output: bool, reset: bool) -> Result<()> {
+    use comfy_table::{
+        Cell, CellAlignment, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS,
+        presets::UTF8_FULL,
+    };
+
+    if reset {
+        let removed = db.clear_ai_calls()?;
+        println!("{} Removed {} recorded AI call(s)", "-".red(), removed);
+        return Ok(());
+    }
+
+    let since = (days > 0).then(|| chrono::Utc::now() - chrono::Duration::days(days));
+    let summary = db.ai_usage_summary(since)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    let period = if days > 0 {
+        format!(" in the last {} days", days)
+    } else {
+        String::new()
+    };
+    if summary.is_empty() {
+        println!("{} No AI calls recorded{}", "!".yellow(), period);
+        return Ok(());
+    }
+
+    let term_width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0)
+        .unwrap_or(120);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(term_width)
+        .set_header(vec![
+            Cell::new("Feature").fg(Color::Cyan),
+            Cell::new("Provider").fg(Color::Cyan),
+            Cell::new("Calls").fg(Color::Cyan),
+            Cell::new("Failed").fg(Color::Cyan),
+            Cell::new("~Tokens in").fg(Color::Cyan),
+            Cell::new("~Tokens out").fg(Color::Cyan),
+            Cell::new("Avg time").fg(Color::Cyan),
+        ]);
+
+    for row in &summary {
+        let provider = match &row.model {
+            Some(model) => format!("{} ({})", row.provider, model),
+            None => row.provider.clone(),
+        };
+        let failed = if row.failures > 0 {
+            Cell::new(row.failures).fg(Color::Red)
+        } else {
+            Cell::new("-")
+        };
+        table.add_row(vec![
+            Cell::new(&row.feature),
+            Cell::new(provider),
+            Cell::new(row.calls).set_alignment(CellAlignment::Right),
+            failed.set_alignment(CellAlignment::Right),
+            Cell::new(row.prompt_tokens).set_alignment(CellAlignment::Right),
+            Cell::new(row.response_tokens).set_alignment(CellAlignment::Right),
+            Cell::new(format!(
+                "{:.1}s",
+                row.duration_ms as f64 / row.calls as f64 / 1000.0
+            ))
+            .set_alignment(CellAlignment::Right),
+        ]);
+    }
+
+    println!("{table}");
+
+    let calls: i64 = summary.iter().map(|r| r.calls).sum();
+    let tokens: i64 = summary.iter().map(|r| r.total_tokens()).sum();
+    println!(
+        "{} {} call(s), ~{} tokens{} (estimated at 4 characters per token)",
+        ">".cyan(),
+        calls,
+        tokens,
+        period
+    );
+
+    Ok(())
+}
+
 /// Analyze CLI usage and suggest optimizations
-pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i64) -> Result<()> {
+///
+/// With AI enabled this is a full workflow audit: usage patterns, commands
+/// used together and unused tools are sent to the AI, which recommends
+/// aliases, replacements, bundles and labels. `apply` creates the suggested
+/// bundles and labels.
+pub fn cmd_ai_analyze(
+    db: &Database,
+    json_output: bool,
+    no_ai: bool,
+    min_uses: i64,
+    apply: bool,
+) -> Result<()> {
     use crate::ai::{
-        AnalysisResult, AnalyzeTip, MODERN_REPLACEMENTS, UnderutilizedTool, analyze_prompt,
-        invoke_ai, is_binary_installed, parse_analyze_response,
+        AnalysisResult, AnalyzeTip, AuditContext, MODERN_REPLACEMENTS, UnderutilizedTool,
+        analyze_prompt, invoke_ai, is_binary_installed, parse_analyze_response,
+    };
+    use crate::history::{
+        count_command_pairs, count_commands, parse_all_history_entries, top_command_lines,
     };
-    use crate::history::parse_all_histories;
     use indicatif::{ProgressBar, ProgressStyle};
 
     if !json_output {
@@ -1602,7 +2794,8 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
         None
     };
 
-    let raw_counts = parse_all_histories()?;
+    let entries = parse_all_history_entries(&HoardConfig::load().unwrap_or_default().usage)?;
+    let raw_counts = count_commands(&entries);
 
     if let Some(ref sp) = spinner {
         sp.finish_and_clear();
@@ -1655,7 +2848,7 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     }
 
     // Sort tips by usage count (most used first)
-    tips.sort_by(|a, b| b.traditional_uses.cmp(&a.traditional_uses));
+    tips.sort_by_key(|t| std::cmp::Reverse(t.traditional_uses));
 
     // 3. Get unused installed tools (high-value ones)
     let unused_tools = db.get_unused_tools()?;
@@ -1673,40 +2866,56 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     }
 
     // Sort by stars (most popular first) to highlight high-value unused tools
-    underutilized.sort_by(|a, b| b.stars.unwrap_or(0).cmp(&a.stars.unwrap_or(0)));
+    underutilized.sort_by_key(|t| std::cmp::Reverse(t.stars.unwrap_or(0)));
     underutilized.truncate(5);
 
-    // 4. Optional AI insights
-    let ai_insight = if !no_ai && (!tips.is_empty() || !underutilized.is_empty()) {
-        if !json_output {
+    // 4. Optional AI workflow audit
+    let audit = if !no_ai && !raw_counts.is_empty() {
+        let mut top_commands: Vec<(String, i64)> = raw_counts
+            .iter()
+            .map(|(cmd, count)| (cmd.clone(), *count))
+            .collect();
+        top_commands.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        top_commands.truncate(25);
+
+        let mut co_occurring: Vec<((String, String), i64)> = count_command_pairs(&entries)
+            .into_iter()
+            .filter(|(_, count)| *count >= 2)
+            .collect();
+        co_occurring.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        co_occurring.truncate(20);
+
+        let context = AuditContext {
+            top_commands,
+            frequent_lines: top_command_lines(&entries, 15),
+            co_occurring,
+            traditional_usage,
+            modern_tools: modern_installed,
+            unused_tools: underutilized.iter().map(|t| t.name.clone()).collect(),
+            existing_bundles: db.list_bundles()?.into_iter().map(|b| b.name).collect(),
+        };
+        let prompt = analyze_prompt(&context);
+
+        let spinner = (!json_output).then(|| {
             let sp = ProgressBar::new_spinner();
             sp.set_style(
                 ProgressStyle::default_spinner()
                     .template("{spinner:.cyan} {msg}")
                     .unwrap(),
             );
-            sp.set_message("Getting AI insights...");
+            sp.set_message("Auditing workflow with AI...");
             sp.enable_steady_tick(std::time::Duration::from_millis(80));
-
-            let unused_names: Vec<String> = underutilized.iter().map(|t| t.name.clone()).collect();
-            let prompt = analyze_prompt(&traditional_usage, &modern_installed, &unused_names);
-
-            match invoke_ai(&prompt) {
-                Ok(response) => {
-                    sp.finish_and_clear();
-                    parse_analyze_response(&response).ok()
-                }
-                Err(_) => {
-                    sp.finish_and_clear();
-                    None
-                }
-            }
-        } else {
-            let unused_names: Vec<String> = underutilized.iter().map(|t| t.name.clone()).collect();
-            let prompt = analyze_prompt(&traditional_usage, &modern_installed, &unused_names);
-            invoke_ai(&prompt)
-                .ok()
-                .and_then(|r| parse_analyze_response(&r).ok())
+            sp
+        });
+        let audit = invoke_ai("analyze", &prompt).and_then(|r| parse_analyze_response(&r));
+        if let Some(sp) = spinner {
+            sp.finish_and_clear();
+        }
+        match audit {
+            Ok(audit) => Some(audit),
+            // --apply has nothing to apply without the audit
+            Err(e) if apply => return Err(e),
+            Err(_) => None,
         }
     } else {
         None
@@ -1716,7 +2925,8 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     let result = AnalysisResult {
         tips,
         underutilized,
-        ai_insight,
+        ai_insight: audit.as_ref().map(|a| a.insight.clone()),
+        audit,
     };
 
     // 6. Output results
@@ -1784,6 +2994,20 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
         println!();
     }
 
+    if let Some(audit) = &result.audit {
+        display_workflow_audit(audit);
+        if apply {
+            apply_workflow_audit(db, audit)?;
+        } else if !audit.bundles.is_empty() || !audit.labels.is_empty() {
+            println!(
+                "{} Run with {} to create the suggested bundles and labels",
+                ">".dimmed(),
+                "--apply".cyan()
+            );
+            println!();
+        }
+    }
+
     // Summary
     let total_tips = result.tips.len();
     let total_unused = result.underutilized.len();
@@ -1801,6 +3025,115 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     Ok(())
 }
 
+/// Print the recommendations of a workflow audit
+fn display_workflow_audit(audit: &crate::ai::WorkflowAudit) {
+    if !audit.aliases.is_empty() {
+        println!("{}", "Suggested aliases:".magenta().bold());
+        for alias in &audit.aliases {
+            println!(
+                "   {} {}",
+                "•".cyan(),
+                format!("alias {}='{}'", alias.name, alias.command).green()
+            );
+            if !alias.reason.is_empty() {
+                println!("     {}", alias.reason.dimmed());
+            }
+        }
+        println!();
+    }
+
+    if !audit.replacements.is_empty() {
+        println!("{}", "Suggested replacements:".magenta().bold());
+        for replacement in &audit.replacements {
+            println!(
+                "   {} {} -> {}",
+                "•".cyan(),
+                replacement.replace.yellow(),
+                replacement.with.cyan()
+            );
+            if !replacement.reason.is_empty() {
+                println!("     {}", replacement.reason.dimmed());
+            }
+        }
+        println!();
+    }
+
+    if !audit.bundles.is_empty() {
+        println!("{}", "Suggested bundles:".magenta().bold());
+        for bundle in &audit.bundles {
+            println!(
+                "   {} {} ({}) - {}",
+                "•".cyan(),
+                bundle.name.cyan(),
+                bundle.tools.join(", "),
+                bundle.description.dimmed()
+            );
+            if let Some(reasoning) = &bundle.reasoning {
+                println!("     {}", reasoning.dimmed());
+            }
+        }
+        println!();
+    }
+
+    if !audit.labels.is_empty() {
+        println!("{}", "Suggested labels:".magenta().bold());
+        for suggestion in &audit.labels {
+            println!(
+                "   {} {}: {}",
+                "•".cyan(),
+                suggestion.tool.cyan(),
+                suggestion.labels.join(", ")
+            );
+        }
+        println!();
+    }
+}
+
+/// Create the bundles and labels suggested by a workflow audit
+///
+/// Existing bundles are left alone and labels are only added to tools
+/// hoards tracks.
+fn apply_workflow_audit(db: &Database, audit: &crate::ai::WorkflowAudit) -> Result<()> {
+    println!("{}", "Applying audit:".green().bold());
+
+    let mut bundles_created = 0;
+    for bundle in &audit.bundles {
+        if db.get_bundle(&bundle.name)?.is_some() {
+            println!("  {} Bundle '{}' already exists", "-".dimmed(), bundle.name);
+            continue;
+        }
+        create_bundle_from_suggestion(db, bundle)?;
+        bundles_created += 1;
+    }
+
+    let mut tools_labeled = 0;
+    for suggestion in &audit.labels {
+        if db.add_labels(&suggestion.tool, &suggestion.labels)? {
+            println!(
+                "  {} Labeled {} with {}",
+                "+".green(),
+                suggestion.tool,
+                suggestion.labels.join(", ")
+            );
+            tools_labeled += 1;
+        } else {
+            println!(
+                "  {} Tool '{}' not in database, skipped labels",
+                "!".yellow(),
+                suggestion.tool
+            );
+        }
+    }
+
+    println!(
+        "{} Created {} bundle(s), labeled {} tool(s)",
+        "+".green(),
+        bundles_created,
+        tools_labeled
+    );
+    Ok(())
+}
+
 /// Migrate tools between package sources
 ///
 /// Find tools that have newer versions on other package sources and offer to migrate them.
@@ -1911,7 +3244,7 @@ pub fn cmd_ai_migrate(
                 .collect();
 
             let prompt = migrate_prompt(&tools_for_prompt);
-            match invoke_ai(&prompt) {
+            match invoke_ai("migrate", &prompt) {
                 Ok(response) => {
                     spinner.finish_and_clear();
                     if let Ok(benefits) = parse_migrate_response(&response) {
@@ -2319,7 +3652,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["cargo", "install", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "cargo",
+                program: "cargo".into(),
                 args: vec!["install".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2328,7 +3661,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["pip", "install", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "pip",
+                program: "pip".into(),
                 args: vec!["install".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2336,7 +3669,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["pip3", "install", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "pip3",
+                program: "pip3".into(),
                 args: vec!["install".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2345,7 +3678,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["pip", "install", "--upgrade", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "pip",
+                program: "pip".into(),
                 args: vec!["install".into(), "--upgrade".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2353,7 +3686,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["pip3", "install", "--upgrade", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "pip3",
+                program: "pip3".into(),
                 args: vec!["install".into(), "--upgrade".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2362,7 +3695,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["npm", "install", "-g", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "npm",
+                program: "npm".into(),
                 args: vec!["install".into(), "-g".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2371,7 +3704,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["brew", "install", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "brew",
+                program: "brew".into(),
                 args: vec!["install".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2380,7 +3713,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["sudo", "apt", "install", "-y", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "sudo",
+                program: "sudo".into(),
                 args: vec![
                     "apt".into(),
                     "install".into(),
@@ -2394,7 +3727,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["sudo", "snap", "install", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "sudo",
+                program: "sudo".into(),
                 args: vec!["snap".into(), "install".into(), (*package).into()],
                 display: cmd.into(),
             })
@@ -2403,7 +3736,7 @@ fn parse_install_cmd_to_safe_command(cmd: &str) -> Option<SafeCommand> {
         ["flatpak", "install", "-y", package] => {
             validate_package_name(package).ok()?;
             Some(SafeCommand {
-                program: "flatpak",
+                program: "flatpak".into(),
                 args: vec!["install".into(), "-y".into(), (*package).into()],
                 display: cmd.into(),
             })
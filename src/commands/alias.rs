@@ -0,0 +1,215 @@
+//! Alias commands: add, list, remove, and the shell snippet for `usage init`
+
+use anyhow::{Result, bail};
+use colored::Colorize;
+
+use super::usage::detect_shell;
+use crate::config::HoardConfig;
+use crate::db::{Database, ToolAlias};
+
+/// Quote a string for POSIX shells
+fn sh_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Quote a string for fish
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+/// Shell code defining the aliases whose tool is installed
+pub fn alias_snippet(aliases: &[ToolAlias], shell: &str) -> String {
+    let mut snippet = String::from("# Aliases managed by hoards\n");
+    for alias in aliases.iter().filter(|a| a.installed) {
+        let line = match shell {
+            "fish" => format!("alias {} {}", alias.name, fish_quote(&alias.command)),
+            "nushell" => format!("alias {} = {}", alias.name, alias.command),
+            _ => format!("alias {}={}", alias.name, sh_quote(&alias.command)),
+        };
+        snippet.push_str(&line);
+        snippet.push('\n');
+    }
+    snippet
+}
+
+/// Add (or replace) an alias
+///
+/// Accepts `cat bat` as well as `cat=bat`. Without `tool`, the alias is
+/// tied to the tracked tool whose name or binary is the command's first word.
+pub fn cmd_alias_add(
+    db: &Database,
+    name: &str,
+    command: Vec<String>,
+    tool: Option<&str>,
+) -> Result<()> {
+    let (name, command) = match name.split_once('=') {
+        Some((name, rest)) if command.is_empty() => (name.to_string(), rest.to_string()),
+        _ => (name.to_string(), command.join(" ")),
+    };
+    let command = command.trim();
+
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        bail!("Invalid alias name '{}'", name);
+    }
+    if command.is_empty() {
+        bail!(
+            "Alias '{}' needs a command (e.g. 'hoards alias add cat bat')",
+            name
+        );
+    }
+
+    let tool = match tool {
+        Some(tool) => Some(tool.to_string()),
+        None => {
+            let program = command.split_whitespace().next().unwrap_or_default();
+            db.get_all_tools()?
+                .into_iter()
+                .find(|t| t.name == program || t.binary_name.as_deref() == Some(program))
+                .map(|t| t.name)
+        }
+    };
+
+    if !db.set_alias(&name, command, tool.as_deref())? {
+        bail!("Tool '{}' not found", tool.unwrap_or_default());
+    }
+    println!(
+        "{} Alias {} = {}{}",
+        "+".green(),
+        name.cyan(),
+        command,
+        tool.map(|t| format!(" ({})", t).dimmed().to_string())
+            .unwrap_or_default()
+    );
+    println!(
+        "  Load aliases with: {}",
+        "eval \"$(hoards usage init --aliases)\"".cyan()
+    );
+    Ok(())
+}
+
+/// List aliases
+pub fn cmd_alias_list(db: &Database, json_output: bool) -> Result<()> {
+    let aliases = db.list_aliases()?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&aliases)?);
+        return Ok(());
+    }
+
+    if aliases.is_empty() {
+        println!(
+            "No aliases. Add one with {}",
+            "hoards alias add <name> <command>".cyan()
+        );
+        return Ok(());
+    }
+
+    let width = aliases.iter().map(|a| a.name.len()).max().unwrap_or(0);
+    for alias in &aliases {
+        let tool = match &alias.tool {
+            Some(tool) if !alias.installed => format!("({}, not installed)", tool).yellow(),
+            Some(tool) => format!("({})", tool).dimmed(),
+            None => "".normal(),
+        };
+        println!(
+            "  {:width$}  {} {}",
+            alias.name.cyan(),
+            alias.command,
+            tool,
+            width = width
+        );
+    }
+
+    let dangling = aliases.iter().filter(|a| !a.installed).count();
+    if dangling > 0 {
+        println!(
+            "\n{} {} alias(es) run a tool that isn't installed and are left out of the shell snippet",
+            "!".yellow(),
+            dangling
+        );
+    }
+    Ok(())
+}
+
+/// Remove aliases
+pub fn cmd_alias_remove(db: &Database, names: Vec<String>) -> Result<()> {
+    for name in names {
+        if db.remove_alias(&name)? {
+            println!("{} Removed alias {}", "-".red(), name);
+        } else {
+            println!("{} No alias named '{}'", "!".yellow(), name);
+        }
+    }
+    Ok(())
+}
+
+/// Print the aliases as shell code to source (`usage init --aliases`)
+pub fn cmd_alias_snippet(
+    db: &Database,
+    config: &HoardConfig,
+    shell_override: Option<String>,
+) -> Result<()> {
+    let shell = shell_override
+        .or_else(|| config.usage.shell.clone())
+        .unwrap_or_else(detect_shell);
+    print!("{}", alias_snippet(&db.list_aliases()?, &shell));
+    Ok(())
+}
+
+/// Warn about aliases that stop working once a tool is uninstalled
+pub fn warn_dangling_aliases(db: &Database, tool: &str) -> Result<()> {
+    let aliases = db.get_tool_aliases(tool)?;
+    if aliases.is_empty() {
+        return Ok(());
+    }
+    let names: Vec<&str> = aliases.iter().map(|a| a.name.as_str()).collect();
+    println!(
+        "\n{} Alias(es) {} run {} and will no longer be loaded",
+        "!".yellow(),
+        names.join(", ").cyan(),
+        tool
+    );
+    println!(
+        "  Remove them with: {}",
+        format!("hoards alias remove {}", names.join(" ")).cyan()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(name: &str, command: &str, installed: bool) -> ToolAlias {
+        ToolAlias {
+            name: name.to_string(),
+            command: command.to_string(),
+            tool: None,
+            installed,
+        }
+    }
+
+    #[test]
+    fn test_alias_snippet() {
+        let aliases = vec![
+            alias("cat", "bat --style='plain'", true),
+            alias("ls", "eza -l", true),
+            alias("top", "btm", false),
+        ];
+
+        let zsh = alias_snippet(&aliases, "zsh");
+        assert!(zsh.contains(r"alias cat='bat --style='\''plain'\'''"));
+        assert!(zsh.contains("alias ls='eza -l'"));
+        assert!(!zsh.contains("btm"));
+
+        let fish = alias_snippet(&aliases, "fish");
+        assert!(fish.contains(r"alias cat 'bat --style=\'plain\''"));
+
+        let nu = alias_snippet(&aliases, "nushell");
+        assert!(nu.contains("alias ls = eza -l"));
+    }
+}
@@ -0,0 +1,335 @@
+//! Batch add and install from a list of tool names
+//!
+//! `hoards add --from-file` and `hoards install -` read one tool per line,
+//! optionally as `name:source`. Blank lines and `#` comments are skipped,
+//! and `-` reads the list from stdin so other commands can pipe into it.
+
+use anyhow::{Context, Result, bail};
+use colored::Colorize;
+use comfy_table::{Cell, Color, Table, presets::UTF8_FULL};
+use std::io::Read;
+
+use super::install::{SafeCommand, get_safe_install_command, validate_package_name};
+use crate::db::Database;
+use crate::models::{InstallSource, Tool};
+use crate::scanner::is_installed;
+
+/// A tool named in a batch list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchItem {
+    pub name: String,
+    pub source: Option<String>,
+}
+
+/// Parse a batch list: `name` or `name:source` per line
+pub fn parse_batch(content: &str) -> Vec<BatchItem> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_once(':') {
+            Some((name, source)) => BatchItem {
+                name: name.trim().to_string(),
+                source: Some(source.trim().to_string()).filter(|s| !s.is_empty()),
+            },
+            None => BatchItem {
+                name: line.to_string(),
+                source: None,
+            },
+        })
+        .collect()
+}
+
+/// Read a batch list from a file, or from stdin for `-`
+pub fn read_batch(path: &str) -> Result<Vec<BatchItem>> {
+    let content = if path == "-" {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read tool names from stdin")?;
+        content
+    } else {
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?
+    };
+    let items = parse_batch(&content);
+    if items.is_empty() {
+        crate::fail!(
+            Invalid,
+            "No tool names in {}",
+            if path == "-" { "stdin" } else { path }
+        );
+    }
+    Ok(items)
+}
+
+/// Result cell for a name that isn't a valid package name
+fn invalid_name() -> Cell {
+    Cell::new("invalid name").fg(Color::Red)
+}
+
+/// Print the per-tool results
+fn print_summary(rows: &[(String, String, Cell)]) {
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL).set_header(vec![
+        Cell::new("Tool").fg(Color::Cyan),
+        Cell::new("Source").fg(Color::Cyan),
+        Cell::new("Result").fg(Color::Cyan),
+    ]);
+    for (name, source, result) in rows {
+        table.add_row(vec![Cell::new(name), Cell::new(source), result.clone()]);
+    }
+    println!("{table}");
+}
+
+/// Track every tool in a list; `source` and `category` apply to lines
+/// without their own
+pub fn cmd_add_batch(
+    db: &Database,
+    path: &str,
+    source: Option<String>,
+    category: Option<String>,
+) -> Result<()> {
+    let mut rows = Vec::new();
+    let mut added = 0;
+
+    for item in read_batch(path)? {
+        let source = item.source.or_else(|| source.clone());
+        let source_label = source.clone().unwrap_or_else(|| "-".to_string());
+
+        if validate_package_name(&item.name).is_err() {
+            rows.push((item.name, source_label, invalid_name()));
+            continue;
+        }
+        if db.get_tool_by_name(&item.name)?.is_some() {
+            rows.push((item.name, source_label, Cell::new("already tracked")));
+            continue;
+        }
+
+        let mut tool = Tool::new(&item.name);
+        if let Some(source) = &source {
+            tool = tool.with_source(InstallSource::from(source.as_str()));
+        }
+        if let Some(category) = &category {
+            tool = tool.with_category(category);
+        }
+        if is_installed(&item.name) {
+            tool = tool.installed();
+        }
+        db.insert_tool(&tool)?;
+        rows.push((item.name, source_label, Cell::new("added").fg(Color::Green)));
+        added += 1;
+    }
+
+    print_summary(&rows);
+    println!("{} Added {} of {} tools", "+".green(), added, rows.len());
+    Ok(())
+}
+
+/// How a listed tool gets installed
+enum Step {
+    Command(SafeCommand),
+    /// A release, at this tag if pinned
+    Release(Option<String>),
+}
+
+/// Install every tool in a list through the usual safe install commands
+///
+/// Pinned tools install their pinned version. The plan is confirmed once
+/// for the whole list. A list read from stdin
+/// leaves nothing to answer the prompt with, so it needs `force`.
+pub fn cmd_install_batch(
+    db: &Database,
+    path: &str,
+    source: Option<String>,
+    force: bool,
+) -> Result<()> {
+    if path == "-" && !force {
+        bail!("Installing a list from stdin needs --force: stdin can't also answer the prompt");
+    }
+    let items = read_batch(path)?;
+    let pins = db.get_pins()?;
+
+    let mut rows = Vec::new();
+    let mut plan = Vec::new();
+
+    println!("{} Install plan:\n", ">".cyan());
+    for item in items {
+        if validate_package_name(&item.name).is_err() {
+            println!("  {} {} (invalid name)", "!".red(), item.name);
+            rows.push((item.name, "-".to_string(), invalid_name()));
+            continue;
+        }
+        let tool = db.get_tool_by_name(&item.name)?;
+        let binary = tool
+            .as_ref()
+            .and_then(|t| t.binary_name.clone())
+            .unwrap_or_else(|| item.name.clone());
+        let source = item
+            .source
+            .or_else(|| source.clone())
+            .or_else(|| tool.as_ref().map(|t| t.source.to_string()));
+        let source_label = source.clone().unwrap_or_else(|| "-".to_string());
+
+        if is_installed(&binary) {
+            println!(
+                "  {} {} (already installed)",
+                "-".dimmed(),
+                item.name.dimmed()
+            );
+            rows.push((item.name, source_label, Cell::new("already installed")));
+            continue;
+        }
+        let pinned = pins.get(&item.name).cloned().flatten();
+        let step = match source.as_deref() {
+            None | Some("unknown") => {
+                println!("  {} {} (no source)", "!".yellow(), item.name);
+                let hint = format!("no source; use {}:<source>", item.name);
+                rows.push((item.name, source_label, Cell::new(hint).fg(Color::Yellow)));
+                continue;
+            }
+            Some("github") => Step::Release(pinned),
+            Some(source) => match get_safe_install_command(&item.name, source, pinned.as_deref()) {
+                Ok(Some(cmd)) => Step::Command(cmd),
+                Ok(None) => {
+                    println!(
+                        "  {} {} (can't install from {})",
+                        "!".yellow(),
+                        item.name,
+                        source
+                    );
+                    rows.push((
+                        item.name,
+                        source_label,
+                        Cell::new("unsupported source").fg(Color::Yellow),
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    println!("  {} {} ({})", "!".red(), item.name, e);
+                    rows.push((item.name, source_label, Cell::new(e).fg(Color::Red)));
+                    continue;
+                }
+            },
+        };
+        match &step {
+            Step::Command(cmd) => println!("  {} {}: {}", "+".green(), source_label.cyan(), cmd),
+            Step::Release(tag) => println!(
+                "  {} github: release {} of {}",
+                "+".green(),
+                tag.as_deref().unwrap_or("latest"),
+                item.name
+            ),
+        }
+        plan.push((item.name, source_label, binary, step));
+    }
+
+    if plan.is_empty() {
+        println!("\nNothing to install.");
+        print_summary(&rows);
+        return Ok(());
+    }
+
+    if !force {
+        println!();
+        print!("Install {} tool(s)? [y/N] ", plan.len());
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+    println!();
+
+    let skipped = rows.len();
+    let mut failed = 0;
+    for (name, source, binary, step) in plan {
+        println!(
+            "{} Installing {} from {}...",
+            ">".cyan(),
+            name.bold(),
+            source
+        );
+        let ok = match step {
+            Step::Command(cmd) => cmd.execute()?.success(),
+            Step::Release(tag) => {
+                super::github::cmd_gh_install(db, &name, tag.as_deref(), None, None, true).is_ok()
+                    && is_installed(&binary)
+            }
+        };
+        if !ok {
+            println!("{} Failed to install {}", "!".red(), name);
+            rows.push((name, source, Cell::new("failed").fg(Color::Red)));
+            failed += 1;
+            continue;
+        }
+
+        let _ = super::ai::invalidate_cheatsheet_cache(db, &name);
+        if db.get_tool_by_name(&name)?.is_none() {
+            let tool = Tool::new(&name)
+                .with_source(InstallSource::from(source.as_str()))
+                .installed();
+            db.insert_tool(&tool)?;
+        } else {
+            db.set_tool_installed(&name, true)?;
+        }
+        rows.push((name, source, Cell::new("installed").fg(Color::Green)));
+    }
+
+    println!();
+    print_summary(&rows);
+    println!(
+        "{} {} installed, {} failed, {} skipped",
+        if failed == 0 {
+            "+".green()
+        } else {
+            "!".yellow()
+        },
+        (rows.len() - skipped - failed).to_string().green(),
+        failed.to_string().red(),
+        skipped.to_string().dimmed()
+    );
+    if failed > 0 {
+        crate::fail!(
+            Partial,
+            "{} of {} tool(s) failed to install",
+            failed,
+            rows.len() - skipped
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_batch() {
+        let content = "\
+# modern unix
+ripgrep
+bat:cargo   # with a source
+  @biomejs/biome : npm
+
+fd:
+";
+        let items = parse_batch(content);
+        let pairs: Vec<_> = items
+            .iter()
+            .map(|i| (i.name.as_str(), i.source.as_deref()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("ripgrep", None),
+                ("bat", Some("cargo")),
+                ("@biomejs/biome", Some("npm")),
+                ("fd", None),
+            ]
+        );
+    }
+}
@@ -2,12 +2,15 @@
 //!
 //! Bundles are collections of tools that can be installed together.
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use colored::Colorize;
 
+use crate::commands::helpers::{confirm, print_project_tools, report_secrets, scan_project};
+use crate::db::{BundleLock, LockedVersion};
 use crate::{
-    Bundle, Database, InstallSource, SafeCommand, get_safe_install_command,
-    get_safe_uninstall_command, is_installed,
+    BUNDLE_TEMPLATES, Bundle, BundleTemplate, BundleTree, Database, InstallSource, Machine,
+    MemberCondition, SafeCommand, SharedBundle, SharedTool, Tool, cmd_uninstall,
+    get_safe_install_command, get_safe_uninstall_command, is_installed, validate_package_name,
 };
 
 /// Create a new bundle
@@ -16,7 +19,10 @@ pub fn cmd_bundle_create(
     name: &str,
     tools: Vec<String>,
     description: Option<String>,
+    when: Option<&str>,
 ) -> Result<()> {
+    let condition = when.map(MemberCondition::parse).transpose()?;
+
     // Check if bundle already exists
     if db.get_bundle(name)?.is_some() {
         println!("{} Bundle '{}' already exists", "!".yellow(), name);
@@ -31,11 +37,19 @@ pub fn cmd_bundle_create(
     if let Some(desc) = description {
         bundle = bundle.with_description(desc);
     }
+    if let Some(condition) = &condition {
+        for tool in &tools {
+            bundle.conditions.insert(tool.clone(), condition.clone());
+        }
+    }
 
     db.create_bundle(&bundle)?;
 
     println!("{} Created bundle '{}'", "+".green(), name.bold());
     println!("  Tools: {}", tools.join(", "));
+    if let Some(condition) = &condition {
+        println!("  When:  {}", condition.to_string().cyan());
+    }
 
     Ok(())
 }
@@ -71,10 +85,15 @@ pub fn cmd_bundle_list(db: &Database) -> Result<()> {
 
     for bundle in &bundles {
         let desc = bundle.description.as_deref().unwrap_or("-");
+        let count = if bundle.includes.is_empty() {
+            bundle.tools.len().to_string()
+        } else {
+            format!("{} + {}", bundle.tools.len(), bundle.includes.join(", "))
+        };
 
         table.add_row(vec![
             Cell::new(&bundle.name),
-            Cell::new(bundle.tools.len()),
+            Cell::new(count),
             Cell::new(desc),
         ]);
     }
@@ -84,25 +103,248 @@ pub fn cmd_bundle_list(db: &Database) -> Result<()> {
     Ok(())
 }
 
+/// List the built-in bundle templates
+pub fn cmd_bundle_template_list(db: &Database) -> Result<()> {
+    use comfy_table::{
+        Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
+    };
+
+    let term_width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0)
+        .unwrap_or(120);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(term_width)
+        .set_header(vec![
+            Cell::new("📦 Template").fg(Color::Cyan),
+            Cell::new("Installed").fg(Color::Cyan),
+            Cell::new("Description").fg(Color::Cyan),
+            Cell::new("Tools").fg(Color::Cyan),
+        ]);
+
+    for template in BUNDLE_TEMPLATES {
+        let installed = template
+            .known_tools()
+            .filter(|kt| is_installed(kt.binary))
+            .count();
+        let mut name = Cell::new(template.name);
+        if db.get_bundle(template.name)?.is_some() {
+            name = name.fg(Color::DarkGrey);
+        }
+
+        table.add_row(vec![
+            name,
+            Cell::new(format!("{}/{}", installed, template.tools.len())),
+            Cell::new(template.description),
+            Cell::new(template.tools.join(", ")),
+        ]);
+    }
+
+    println!("{table}");
+    println!(
+        "{} {} templates. Create a bundle from one with: hoards bundle template apply <name>",
+        ">".cyan(),
+        BUNDLE_TEMPLATES.len()
+    );
+    Ok(())
+}
+
+/// Create a bundle from a template
+///
+/// Members that aren't tracked yet are added to the database from the
+/// known-tool data. Returns how many were added.
+pub fn create_bundle_from_template(
+    db: &Database,
+    template: &BundleTemplate,
+    name: &str,
+) -> Result<usize> {
+    if db.get_bundle(name)?.is_some() {
+        bail!("Bundle '{}' already exists", name);
+    }
+
+    let mut added = 0;
+    for known in template.known_tools() {
+        if db.get_tool_by_name(known.name)?.is_none() {
+            let mut tool = known.to_tool();
+            tool.is_installed = is_installed(known.binary);
+            db.insert_tool(&tool)?;
+            added += 1;
+        }
+    }
+
+    let tools = template.tools.iter().map(|t| t.to_string()).collect();
+    db.create_bundle(&Bundle::new(name, tools).with_description(template.description))?;
+    Ok(added)
+}
+
+/// Create a bundle from a built-in template, optionally installing it
+pub fn cmd_bundle_template_apply(
+    db: &Database,
+    template: &str,
+    name: Option<&str>,
+    install: bool,
+    force: bool,
+) -> Result<()> {
+    let Some(found) = BundleTemplate::find(template) else {
+        println!("{} Unknown template '{}'", "!".yellow(), template);
+        let names: Vec<&str> = BUNDLE_TEMPLATES.iter().map(|t| t.name).collect();
+        println!("  Available: {}", names.join(", "));
+        return Ok(());
+    };
+    let name = name.unwrap_or(found.name);
+
+    if db.get_bundle(name)?.is_some() {
+        println!("{} Bundle '{}' already exists", "!".yellow(), name);
+        println!(
+            "  Pick another name with {}",
+            format!("hoards bundle template apply {} --name <bundle>", template).cyan()
+        );
+        return Ok(());
+    }
+
+    let added = create_bundle_from_template(db, found, name)?;
+    println!(
+        "{} Created bundle '{}' from template '{}'",
+        "+".green(),
+        name.bold(),
+        found.name
+    );
+    println!("  Tools: {}", found.tools.join(", "));
+    if added > 0 {
+        println!("  Started tracking {} tool(s)", added);
+    }
+
+    if install {
+        println!();
+        cmd_bundle_install(db, name, force, false)
+    } else {
+        println!(
+            "\n  Install with: {}",
+            format!("hoards bundle install {}", name).cyan()
+        );
+        Ok(())
+    }
+}
+
+/// Propose a bundle from the tools a project directory needs
+pub fn cmd_bundle_from_project(
+    db: &Database,
+    path: Option<&str>,
+    name: Option<&str>,
+    dry_run: bool,
+    force: bool,
+) -> Result<()> {
+    let scan = scan_project(db, path)?;
+    if scan.tools.is_empty() {
+        println!("No tools detected in {}", scan.dir.display());
+        return Ok(());
+    }
+    print_project_tools(&scan, "Tools needed by");
+
+    let name = name.unwrap_or(&scan.name);
+    if dry_run {
+        println!(
+            "\n  Create it with: {}",
+            format!("hoards bundle from-project --name {}", name).cyan()
+        );
+        return Ok(());
+    }
+
+    if db.get_bundle(name)?.is_some() {
+        println!("\n{} Bundle '{}' already exists", "!".yellow(), name);
+        println!("  Pick another name with {}", "--name <bundle>".cyan());
+        return Ok(());
+    }
+
+    if !force {
+        print!("\nCreate bundle '{}' with these tools? [y/N] ", name);
+        use std::io::Write;
+        std::io::stdout().flush()?;
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let mut added = 0;
+    for status in scan.tools.iter().filter(|s| !s.tracked) {
+        let mut tool = status.tool.clone();
+        tool.is_installed = status.installed;
+        db.insert_tool(&tool)?;
+        added += 1;
+    }
+
+    let tools = scan.tools.iter().map(|t| t.found.name.clone()).collect();
+    db.create_bundle(
+        &Bundle::new(name, tools)
+            .with_description(format!("Tools used by the {} project", scan.name)),
+    )?;
+    println!("{} Created bundle '{}'", "+".green(), name.bold());
+    if added > 0 {
+        println!("  Started tracking {} tool(s)", added);
+    }
+
+    println!();
+    cmd_bundle_install(db, name, force, false)
+}
+
 /// Show details of a specific bundle
-pub fn cmd_bundle_show(db: &Database, name: &str) -> Result<()> {
+pub fn cmd_bundle_show(db: &Database, name: &str, json_output: bool) -> Result<()> {
     use crate::icons::{source_icon, status_icon};
     use comfy_table::{
         Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
     };
 
-    let bundle = match db.get_bundle(name)? {
-        Some(b) => b,
-        None => {
-            println!("Bundle '{}' not found", name);
-            return Ok(());
+    let Some(tree) = db.get_bundle_tree(name)? else {
+        if json_output {
+            println!("null");
         }
+        crate::fail!(NotFound, "Bundle '{}' not found", name);
     };
+    let bundle = &tree.bundle;
+    let flat = tree.flatten();
+
+    if json_output {
+        // Members of included bundles are listed too
+        let machine = Machine::current();
+        let members: Vec<_> = flat
+            .tools
+            .iter()
+            .map(|tool| {
+                let tracked = db.get_tool_by_name(tool).ok().flatten();
+                serde_json::json!({
+                    "name": tool,
+                    "tracked": tracked.is_some(),
+                    "installed": tracked.is_some_and(|t| t.is_installed),
+                    "applies": flat.applies(tool, &machine),
+                    "when": flat.condition(tool).map(|c| c.to_string()),
+                })
+            })
+            .collect();
+        let details = serde_json::json!({ "bundle": bundle, "members": members });
+        println!("{}", serde_json::to_string_pretty(&details)?);
+        return Ok(());
+    }
 
     println!("{} {}", "📦 Bundle:".bold(), bundle.name.cyan());
     if let Some(desc) = &bundle.description {
         println!("{}", desc.dimmed());
     }
+    let machine = Machine::current();
+    let conditional = !bundle.conditions.is_empty();
+    if !flat.conditions.is_empty() {
+        println!(
+            "{} {}",
+            "This machine:".dimmed(),
+            machine.to_string().dimmed()
+        );
+    }
     println!();
 
     let term_width = terminal_size::terminal_size()
@@ -115,215 +357,1057 @@ pub fn cmd_bundle_show(db: &Database, name: &str) -> Result<()> {
         .apply_modifier(UTF8_ROUND_CORNERS)
         .set_content_arrangement(ContentArrangement::Dynamic)
         .set_width(term_width)
-        .set_header(vec![
-            Cell::new("Tool").fg(Color::Cyan),
-            Cell::new("Src").fg(Color::Cyan),
-            Cell::new("✓").fg(Color::Cyan),
-            Cell::new("Description").fg(Color::Cyan),
-        ]);
+        .set_header({
+            let mut header = vec![
+                Cell::new("Tool").fg(Color::Cyan),
+                Cell::new("Src").fg(Color::Cyan),
+                Cell::new("✓").fg(Color::Cyan),
+                Cell::new("Description").fg(Color::Cyan),
+            ];
+            if conditional {
+                header.push(Cell::new("When").fg(Color::Cyan));
+            }
+            header
+        });
 
-    let mut installed_count = 0;
     for tool_name in &bundle.tools {
-        if let Some(tool) = db.get_tool_by_name(tool_name)? {
+        let mut row = if !bundle.applies(tool_name, &machine) {
+            vec![
+                Cell::new(tool_name).fg(Color::DarkGrey),
+                Cell::new("-").fg(Color::DarkGrey),
+                Cell::new("-").fg(Color::DarkGrey),
+                Cell::new("not for this machine").fg(Color::DarkGrey),
+            ]
+        } else if let Some(tool) = db.get_tool_by_name(tool_name)? {
             let src_icon = source_icon(&tool.source.to_string());
             let (status, color) = if tool.is_installed {
-                installed_count += 1;
                 (status_icon(true), Color::Green)
             } else {
                 (status_icon(false), Color::Red)
             };
             let desc = tool.description.as_deref().unwrap_or("-");
-            table.add_row(vec![
+            vec![
                 Cell::new(tool_name),
                 Cell::new(src_icon),
                 Cell::new(status).fg(color),
                 Cell::new(desc),
-            ]);
+            ]
+        } else {
+            vec![
+                Cell::new(tool_name),
+                Cell::new("?"),
+                Cell::new("⚠").fg(Color::Yellow),
+                Cell::new("not in database"),
+            ]
+        };
+
+        if conditional {
+            let when = bundle.condition(tool_name).map(|c| c.to_string());
+            row.push(Cell::new(when.unwrap_or_default()).fg(Color::DarkGrey));
+        }
+        table.add_row(row);
+    }
+
+    if !bundle.tools.is_empty() {
+        println!("{table}");
+    }
+
+    if !tree.children.is_empty() {
+        println!();
+        println!("{}", "Includes:".bold());
+        print_included(db, &tree.children, "", &machine)?;
+        println!();
+    }
+
+    crate::icons::print_legend_compact();
+
+    // Counts cover included bundles too
+    let (applicable, skipped): (Vec<&String>, Vec<&String>) =
+        flat.tools.iter().partition(|t| flat.applies(t, &machine));
+    let installed = applicable
+        .iter()
+        .filter(|t| is_tracked_installed(db, t))
+        .count();
+    print!(
+        "{} {}/{} installed",
+        ">".cyan(),
+        installed,
+        applicable.len()
+    );
+    if !skipped.is_empty() {
+        print!(", {} not for this machine", skipped.len());
+    }
+    println!();
+    Ok(())
+}
+
+fn is_tracked_installed(db: &Database, tool: &str) -> bool {
+    db.get_tool_by_name(tool)
+        .ok()
+        .flatten()
+        .is_some_and(|t| t.is_installed)
+}
+
+/// Print included bundles and their tools as a tree
+fn print_included(
+    db: &Database,
+    children: &[BundleTree],
+    prefix: &str,
+    machine: &Machine,
+) -> Result<()> {
+    use crate::icons::status_icon;
+
+    for (i, child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let (branch, indent) = if last {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        println!(
+            "{}{}{} {}",
+            prefix.dimmed(),
+            branch.dimmed(),
+            "📦".dimmed(),
+            child.bundle.name.cyan()
+        );
+
+        let child_prefix = format!("{}{}", prefix, indent);
+        let entries = child.bundle.tools.len() + child.children.len();
+        for (j, tool) in child.bundle.tools.iter().enumerate() {
+            let branch = if j + 1 == entries {
+                "└─ "
+            } else {
+                "├─ "
+            };
+            let status = if !child.bundle.applies(tool, machine) {
+                format!("- {}", tool).dimmed().to_string()
+            } else if is_tracked_installed(db, tool) {
+                format!("{} {}", status_icon(true).green(), tool)
+            } else {
+                format!("{} {}", status_icon(false).red(), tool)
+            };
+            match child.bundle.condition(tool) {
+                Some(condition) => println!(
+                    "{}{}{} {}",
+                    child_prefix.dimmed(),
+                    branch.dimmed(),
+                    status,
+                    condition.to_string().dimmed()
+                ),
+                None => println!("{}{}{}", child_prefix.dimmed(), branch.dimmed(), status),
+            }
+        }
+        if !child.children.is_empty() {
+            print_included(db, &child.children, &child_prefix, machine)?;
+        }
+    }
+    Ok(())
+}
+
+/// Install all tools in a bundle
+///
+/// Tools of included bundles are installed too. The versions installed are
+/// then recorded as the bundle's lock; with `locked`, the versions in the
+/// lock are installed instead of the latest ones.
+pub fn cmd_bundle_install(db: &Database, name: &str, force: bool, locked: bool) -> Result<()> {
+    let bundle = match db.get_flattened_bundle(name)? {
+        Some(b) => b,
+        None => crate::fail!(NotFound, "Bundle '{}' not found", name),
+    };
+
+    if bundle.tools.is_empty() {
+        println!("Bundle '{}' has no tools", name);
+        return Ok(());
+    }
+
+    let lock = if locked {
+        match db.get_bundle_lock(name)? {
+            Some(lock) => Some(lock),
+            None => {
+                println!("Bundle '{}' has no lock yet", name);
+                println!(
+                    "  Install it once, or record the installed versions with: hoards bundle lock {} --update",
+                    name
+                );
+                return Ok(());
+            }
+        }
+    } else {
+        None
+    };
+
+    // Build install plan
+    println!(
+        "{} Install plan for bundle '{}'{}:\n",
+        ">".cyan(),
+        name.bold(),
+        if locked { " (locked)" } else { "" }
+    );
+
+    let InstallPlan {
+        to_install,
+        already_installed,
+        unknown_source,
+        other_machine,
+        not_in_lock,
+    } = plan_install(db, &bundle, lock.as_ref())?;
+
+    if to_install.is_empty() {
+        println!("\nNothing to install.");
+        if already_installed > 0 {
+            println!("  {} tool(s) already installed", already_installed);
+        }
+        if !locked {
+            record_lock(db, name, &bundle)?;
+        }
+        return Ok(());
+    }
+
+    println!(
+        "\n  {} to install, {} already installed, {} unknown, {} for other machines",
+        to_install.len().to_string().green(),
+        already_installed,
+        unknown_source,
+        other_machine
+    );
+    if not_in_lock > 0 {
+        println!(
+            "  {} {} tool(s) are not in the lock and get the latest version",
+            "i".cyan(),
+            not_in_lock
+        );
+    }
+
+    // Confirm
+    if !force {
+        println!();
+        print!("Proceed? [y/N] ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    println!();
+
+    // Execute installs (safe: no shell interpolation)
+    let (installed, failed) = run_installs(db, &to_install)?;
+    let success = installed.len();
+
+    println!();
+    println!(
+        "{} Bundle '{}': {} installed, {} failed, {} skipped",
+        if failed == 0 {
+            "+".green()
+        } else {
+            "!".yellow()
+        },
+        name,
+        success.to_string().green(),
+        failed.to_string().red(),
+        (already_installed + unknown_source + other_machine)
+            .to_string()
+            .dimmed()
+    );
+
+    label_installed(db, name, &installed)?;
+    if !locked {
+        let count = record_lock(db, name, &bundle)?;
+        println!("  Locked {} version(s)", count);
+    }
+
+    if failed > 0 {
+        crate::fail!(
+            Partial,
+            "{} of {} tool(s) in bundle '{}' failed to install",
+            failed,
+            success + failed,
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Label marking the tools a bundle installed, for `bundle sync --prune`
+///
+/// Bundle names are case-sensitive, and so is this label's name part.
+fn bundle_label(name: &str) -> String {
+    format!("bundle:{}", name)
+}
+
+/// Label the tools a bundle just installed as belonging to it
+///
+/// Members that were already installed are left alone: they were installed
+/// separately, so pruning the bundle mustn't remove them.
+fn label_installed(db: &Database, name: &str, installed: &[String]) -> Result<()> {
+    let label = [bundle_label(name)];
+    for tool_name in installed {
+        db.add_labels(tool_name, &label)?;
+    }
+    Ok(())
+}
+
+/// Tools a bundle installed that it no longer lists
+#[derive(Debug, Default, PartialEq)]
+struct FormerMembers {
+    /// Still installed and wanted by no other bundle
+    stale: Vec<String>,
+    /// Still installed, kept for another bundle: (tool, bundle)
+    kept: Vec<(String, String)>,
+    /// No longer installed
+    gone: Vec<String>,
+}
+
+/// Find the tools labelled as installed by a bundle that left it
+fn former_members(
+    db: &Database,
+    name: &str,
+    bundle: &Bundle,
+    installed: impl Fn(&str) -> bool,
+) -> Result<FormerMembers> {
+    let mut other_bundles = Vec::new();
+    for other in db.get_bundle_names()? {
+        if other != name
+            && let Some(flat) = db.get_flattened_bundle(&other)?
+        {
+            other_bundles.push(flat);
+        }
+    }
+
+    let mut former = FormerMembers::default();
+    for tool in db.list_tools_by_label(&bundle_label(name))? {
+        if bundle.tools.contains(&tool.name) {
+            continue;
+        }
+        if !installed(tool.binary_name.as_deref().unwrap_or(&tool.name)) {
+            former.gone.push(tool.name);
+        } else if let Some(other) = other_bundles.iter().find(|b| b.tools.contains(&tool.name)) {
+            former.kept.push((tool.name, other.name.clone()));
+        } else {
+            former.stale.push(tool.name);
+        }
+    }
+    Ok(former)
+}
+
+/// Make this machine match a bundle
+///
+/// Installs missing members. With `prune`, tools the bundle installed
+/// earlier (labelled `bundle:<name>`) that have since been removed from it
+/// are uninstalled, unless another bundle still lists them.
+pub fn cmd_bundle_sync(db: &Database, name: &str, prune: bool, force: bool) -> Result<()> {
+    let bundle = match db.get_flattened_bundle(name)? {
+        Some(b) => b,
+        None => crate::fail!(NotFound, "Bundle '{}' not found", name),
+    };
+
+    println!("{} Sync plan for bundle '{}':\n", ">".cyan(), name.bold());
+    let plan = plan_install(db, &bundle, None)?;
+
+    // Former members: labelled as installed by the bundle but no longer in it
+    let label = bundle_label(name);
+    let FormerMembers {
+        stale,
+        kept,
+        gone: mut released, // these only lose the label
+    } = former_members(db, name, &bundle, is_installed)?;
+    for (tool, other) in kept {
+        println!(
+            "  {} {} (removed, kept for bundle '{}')",
+            "-".dimmed(),
+            tool.dimmed(),
+            other
+        );
+        released.push(tool);
+    }
+    for tool in &stale {
+        if prune {
+            println!("  {} {} (removed from bundle)", "-".red(), tool);
+        } else {
+            println!(
+                "  {} {} (removed from bundle, still installed)",
+                "!".yellow(),
+                tool
+            );
+        }
+    }
+
+    let to_remove: &[String] = if prune { &stale } else { &[] };
+    let hint_prune = || {
+        if !prune && !stale.is_empty() {
+            println!(
+                "  {} {} former member(s) still installed; remove them with --prune",
+                "i".cyan(),
+                stale.len()
+            );
+        }
+    };
+
+    if plan.to_install.is_empty() && to_remove.is_empty() {
+        for tool in &released {
+            db.remove_label(tool, &label)?;
+        }
+        println!("\n{} Bundle '{}' is in sync", "+".green(), name);
+        hint_prune();
+        return Ok(());
+    }
+
+    println!(
+        "\n  {} to install, {} to uninstall, {} already installed, {} unknown",
+        plan.to_install.len().to_string().green(),
+        to_remove.len().to_string().red(),
+        plan.already_installed,
+        plan.unknown_source
+    );
+    hint_prune();
+
+    if !force {
+        println!();
+        print!("Proceed? [y/N] ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    println!();
+    let (installed, failed) = run_installs(db, &plan.to_install)?;
+
+    let mut removed = 0;
+    for tool in to_remove {
+        if let Err(e) = cmd_uninstall(db, tool, false, true) {
+            println!("{} {:#}", "!".red(), e);
+        }
+        let binary = db
+            .get_tool_by_name(tool)?
+            .and_then(|t| t.binary_name)
+            .unwrap_or_else(|| tool.clone());
+        if !is_installed(&binary) {
+            released.push(tool.clone());
+            removed += 1;
+        }
+    }
+    for tool in &released {
+        db.remove_label(tool, &label)?;
+    }
+    label_installed(db, name, &installed)?;
+
+    println!();
+    println!(
+        "{} Bundle '{}': {} installed, {} uninstalled, {} failed",
+        if failed == 0 && removed == to_remove.len() {
+            "+".green()
+        } else {
+            "!".yellow()
+        },
+        name,
+        installed.len().to_string().green(),
+        removed.to_string().red(),
+        (failed + to_remove.len() - removed).to_string().red()
+    );
+    let failed = failed + to_remove.len() - removed;
+    if failed > 0 {
+        crate::fail!(Partial, "{} change(s) to bundle '{}' failed", failed, name);
+    }
+    Ok(())
+}
+
+/// What installing a bundle would do on this machine
+#[derive(Default)]
+struct InstallPlan<'a> {
+    /// (name, source, command)
+    to_install: Vec<(&'a str, String, SafeCommand)>,
+    already_installed: usize,
+    unknown_source: usize,
+    other_machine: usize,
+    /// Members installed at the latest version because the lock lacks them
+    not_in_lock: usize,
+}
+
+/// Work out and print which members of a bundle need installing
+///
+/// With a lock, members are installed at their locked versions and
+/// installed members that drifted from them are reinstalled.
+fn plan_install<'a>(
+    db: &Database,
+    bundle: &'a Bundle,
+    lock: Option<&BundleLock>,
+) -> Result<InstallPlan<'a>> {
+    let mut plan = InstallPlan::default();
+    let machine = Machine::current();
+
+    for tool_name in &bundle.tools {
+        if let Some(condition) = bundle.condition(tool_name)
+            && !condition.matches(&machine)
+        {
+            println!(
+                "  {} {} (not for this machine: {})",
+                "-".dimmed(),
+                tool_name.dimmed(),
+                condition
+            );
+            plan.other_machine += 1;
+            continue;
+        }
+
+        // Get tool info from database first
+        let tool_info = db.get_tool_by_name(tool_name)?;
+
+        // Check if installed using binary_name if available
+        let binary = tool_info
+            .as_ref()
+            .and_then(|t| t.binary_name.as_deref())
+            .unwrap_or(tool_name);
+
+        let pinned = lock.and_then(|l| l.get(tool_name));
+
+        // An installed tool is only reinstalled when it drifted from its lock
+        let mut drifted_from = None;
+        if is_installed(binary) {
+            drifted_from = match pinned {
+                Some(pin) if pins_versions(&pin.source) => {
+                    installed_version(db, tool_name, &pin.source)?.filter(|v| *v != pin.version)
+                }
+                _ => None,
+            };
+            if drifted_from.is_none() {
+                println!(
+                    "  {} {} (already installed)",
+                    "-".dimmed(),
+                    tool_name.dimmed()
+                );
+                plan.already_installed += 1;
+                continue;
+            }
+        }
+
+        // Get source from the lock or database, or skip
+        let source = if let Some(pin) = pinned {
+            pin.source.clone()
+        } else if let Some(ref tool) = tool_info {
+            tool.source.to_string()
+        } else {
+            println!(
+                "  {} {} (not in database, skipping)",
+                "?".yellow(),
+                tool_name
+            );
+            plan.unknown_source += 1;
+            continue;
+        };
+
+        // Get safe install command (validates package name)
+        let version = pinned.map(|pin| pin.version.as_str());
+        match get_safe_install_command(tool_name, &source, version) {
+            Ok(Some(cmd)) => {
+                let detail = match (version, &drifted_from) {
+                    (Some(v), Some(current)) => format!("{} {} -> {}", source.cyan(), current, v),
+                    (Some(v), None) => format!("{} {}", source.cyan(), v),
+                    (None, _) if lock.is_some() => {
+                        plan.not_in_lock += 1;
+                        format!("{}, not in lock", source.cyan())
+                    }
+                    (None, _) => source.cyan().to_string(),
+                };
+                println!("  {} {} ({})", "+".green(), tool_name, detail);
+                plan.to_install.push((tool_name, source, cmd));
+            }
+            Ok(None) => {
+                println!(
+                    "  {} {} (unknown source: {})",
+                    "?".yellow(),
+                    tool_name,
+                    source
+                );
+                plan.unknown_source += 1;
+            }
+            Err(e) => {
+                println!("  {} {} (invalid name: {})", "!".red(), tool_name, e);
+                plan.unknown_source += 1;
+            }
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Run planned installs, returning the tools installed and the failure count
+fn run_installs(
+    db: &Database,
+    to_install: &[(&str, String, SafeCommand)],
+) -> Result<(Vec<String>, usize)> {
+    let mut installed = Vec::new();
+    let mut failed = 0;
+
+    for (tool_name, source, cmd) in to_install {
+        println!(
+            "{} Installing {} from {}...",
+            ">".cyan(),
+            tool_name.bold(),
+            source
+        );
+
+        let status = cmd.execute()?;
+
+        if status.success() {
+            db.set_tool_installed(tool_name, true)?;
+            println!("{} Installed {}", "+".green(), tool_name);
+            installed.push(tool_name.to_string());
         } else {
-            table.add_row(vec![
-                Cell::new(tool_name),
-                Cell::new("?"),
-                Cell::new("⚠").fg(Color::Yellow),
-                Cell::new("not in database"),
-            ]);
+            println!("{} Failed to install {}", "!".red(), tool_name);
+            failed += 1;
         }
     }
 
-    println!("{table}");
-    crate::icons::print_legend_compact();
-    println!(
-        "{} {}/{} installed",
-        ">".cyan(),
-        installed_count,
-        bundle.tools.len()
-    );
-    Ok(())
+    Ok((installed, failed))
 }
 
-/// Install all tools in a bundle
-pub fn cmd_bundle_install(db: &Database, name: &str, force: bool) -> Result<()> {
-    let bundle = match db.get_bundle(name)? {
-        Some(b) => b,
-        None => {
-            println!("Bundle '{}' not found", name);
-            return Ok(());
+/// Sources whose install commands honour a requested version
+fn pins_versions(source: &str) -> bool {
+    matches!(source, "cargo" | "pip" | "npm" | "brew")
+}
+
+/// Installed version of a tool: the release tag for release installs,
+/// otherwise whatever the package manager reports
+fn installed_version(db: &Database, tool: &str, source: &str) -> Result<Option<String>> {
+    if let Some(install) = db.get_release_install(tool)? {
+        return Ok(Some(install.tag));
+    }
+    Ok(crate::updates::get_installed_version(tool, source))
+}
+
+/// Versions of the members installed on this machine
+fn installed_versions(db: &Database, bundle: &Bundle) -> Result<Vec<LockedVersion>> {
+    let machine = Machine::current();
+    let mut versions = Vec::new();
+    for tool_name in bundle.tools.iter().filter(|t| bundle.applies(t, &machine)) {
+        let Some(tool) = db.get_tool_by_name(tool_name)? else {
+            continue;
+        };
+        let binary = tool.binary_name.as_deref().unwrap_or(tool_name);
+        if !is_installed(binary) {
+            continue;
+        }
+        let source = tool.source.to_string();
+        if let Some(version) = installed_version(db, tool_name, &source)? {
+            versions.push(LockedVersion {
+                tool: tool_name.clone(),
+                source,
+                version,
+            });
         }
+    }
+    Ok(versions)
+}
+
+/// Record the installed versions of a bundle's members as its lock
+///
+/// Entries for members that don't apply to this machine are kept, so a
+/// lock shared between machines isn't trimmed to the current one. Returns
+/// the number of versions recorded from this machine.
+fn record_lock(db: &Database, name: &str, bundle: &Bundle) -> Result<usize> {
+    let machine = Machine::current();
+    let installed = installed_versions(db, bundle)?;
+    let count = installed.len();
+
+    let mut versions: Vec<LockedVersion> = db
+        .get_bundle_lock(name)?
+        .map(|lock| lock.versions)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|v| bundle.tools.contains(&v.tool) && !bundle.applies(&v.tool, &machine))
+        .collect();
+    versions.extend(installed);
+    db.set_bundle_lock(name, &versions)?;
+    Ok(count)
+}
+
+/// Show a bundle's lock, or refresh it from the installed versions
+pub fn cmd_bundle_lock(db: &Database, name: &str, update: bool) -> Result<()> {
+    let bundle = match db.get_flattened_bundle(name)? {
+        Some(b) => b,
+        None => crate::fail!(NotFound, "Bundle '{}' not found", name),
     };
 
-    if bundle.tools.is_empty() {
-        println!("Bundle '{}' has no tools", name);
+    if update {
+        let count = record_lock(db, name, &bundle)?;
+        println!(
+            "{} Locked {} version(s) for bundle '{}'",
+            "+".green(),
+            count,
+            name
+        );
         return Ok(());
     }
 
-    // Build install plan
+    let lock = match db.get_bundle_lock(name)? {
+        Some(lock) => lock,
+        None => {
+            println!("Bundle '{}' has no lock yet", name);
+            println!("  Record one with: hoards bundle lock {} --update", name);
+            return Ok(());
+        }
+    };
+
     println!(
-        "{} Install plan for bundle '{}':\n",
+        "{} Lock for bundle '{}' ({})\n",
         ">".cyan(),
-        name.bold()
+        name.bold(),
+        lock.locked_at.get(..10).unwrap_or(&lock.locked_at).dimmed()
     );
 
-    let mut to_install: Vec<(&str, String, SafeCommand)> = Vec::new(); // (name, source, command)
-    let mut already_installed = 0;
-    let mut unknown_source = 0;
-
+    let machine = Machine::current();
+    let mut drifted = 0;
     for tool_name in &bundle.tools {
-        // Get tool info from database first
-        let tool_info = db.get_tool_by_name(tool_name)?;
-
-        // Check if installed using binary_name if available
-        let binary = tool_info
-            .as_ref()
-            .and_then(|t| t.binary_name.as_deref())
-            .unwrap_or(tool_name);
-
-        if is_installed(binary) {
+        let Some(pin) = lock.get(tool_name) else {
+            println!("  {} {} (not in lock)", "?".yellow(), tool_name);
+            continue;
+        };
+        if !bundle.applies(tool_name, &machine) {
             println!(
-                "  {} {} (already installed)",
+                "  {} {} {} (not for this machine)",
                 "-".dimmed(),
-                tool_name.dimmed()
+                tool_name.dimmed(),
+                pin.version.dimmed()
             );
-            already_installed += 1;
             continue;
         }
 
-        // Get source from database or skip
-        let source = if let Some(ref tool) = tool_info {
-            tool.source.to_string()
-        } else {
-            println!(
-                "  {} {} (not in database, skipping)",
-                "?".yellow(),
-                tool_name
-            );
-            unknown_source += 1;
-            continue;
-        };
-
-        // Get safe install command (validates package name)
-        match get_safe_install_command(tool_name, &source, None) {
-            Ok(Some(cmd)) => {
-                println!("  {} {} ({})", "+".green(), tool_name, source.cyan());
-                to_install.push((tool_name, source, cmd));
+        match installed_version(db, tool_name, &pin.source)? {
+            Some(current) if current == pin.version => {
+                println!("  {} {} {}", "+".green(), tool_name, pin.version);
             }
-            Ok(None) => {
+            Some(current) => {
                 println!(
-                    "  {} {} (unknown source: {})",
-                    "?".yellow(),
+                    "  {} {} {} (installed: {})",
+                    "!".yellow(),
                     tool_name,
-                    source
+                    pin.version,
+                    current.yellow()
                 );
-                unknown_source += 1;
+                drifted += 1;
             }
-            Err(e) => {
-                println!("  {} {} (invalid name: {})", "!".red(), tool_name, e);
-                unknown_source += 1;
+            None => {
+                println!(
+                    "  {} {} {} (installed version unknown)",
+                    "?".yellow(),
+                    tool_name,
+                    pin.version
+                );
             }
         }
     }
 
-    if to_install.is_empty() {
-        println!("\nNothing to install.");
-        if already_installed > 0 {
-            println!("  {} tool(s) already installed", already_installed);
+    if drifted > 0 {
+        println!(
+            "\n{} {} tool(s) drifted. Restore with 'hoards bundle install {} --locked' or accept with 'hoards bundle lock {} --update'",
+            "!".yellow(),
+            drifted,
+            name,
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// Build the shareable form of a bundle (tools of included bundles flattened in)
+fn shared_bundle(db: &Database, name: &str) -> Result<Option<SharedBundle>> {
+    let Some(bundle) = db.get_flattened_bundle(name)? else {
+        return Ok(None);
+    };
+    let lock = db.get_bundle_lock(name)?;
+
+    let mut tools = Vec::new();
+    for tool_name in &bundle.tools {
+        let tool = db.get_tool_by_name(tool_name)?;
+        let pinned = lock.as_ref().and_then(|l| l.get(tool_name));
+        let Some(source) = pinned
+            .map(|pin| pin.source.clone())
+            .or_else(|| tool.as_ref().map(|t| t.source.to_string()))
+        else {
+            println!(
+                "  {} {} (not in database, left out)",
+                "?".yellow(),
+                tool_name
+            );
+            continue;
+        };
+        tools.push(SharedTool {
+            name: tool_name.clone(),
+            source,
+            description: tool.and_then(|t| t.description),
+            when: bundle.condition(tool_name).map(|c| c.to_string()),
+            version: pinned.map(|pin| pin.version.clone()),
+        });
+    }
+
+    Ok(Some(SharedBundle {
+        name: bundle.name,
+        description: bundle.description,
+        tools,
+    }))
+}
+
+/// Publish a bundle as a gist (or write it to a file) for others to install
+pub fn cmd_bundle_publish(
+    db: &Database,
+    name: &str,
+    public: bool,
+    output: Option<&str>,
+) -> Result<()> {
+    let Some(shared) = shared_bundle(db, name)? else {
+        crate::fail!(NotFound, "Bundle '{}' not found", name);
+    };
+    if shared.tools.is_empty() {
+        println!("Bundle '{}' has no tools to share", name);
+        return Ok(());
+    }
+    let content = shared.to_toml()?;
+
+    let secrets = crate::secrets::scan_text(&content);
+    if !secrets.is_empty() {
+        report_secrets(&secrets);
+        if !confirm("The bundle may contain secrets. Publish anyway?")? {
+            println!("Cancelled.");
+            return Ok(());
         }
+    }
+
+    if let Some(path) = output {
+        std::fs::write(path, &content)?;
+        println!(
+            "{} Wrote bundle '{}' ({} tools) to {}",
+            "+".green(),
+            name.bold(),
+            shared.tools.len(),
+            path
+        );
         return Ok(());
     }
 
+    let description = match &shared.description {
+        Some(desc) => format!("hoards bundle '{}': {}", name, desc),
+        None => format!("hoards bundle '{}'", name),
+    };
+    let url = crate::forge::github::create_gist(
+        &format!("{}.hoards.toml", name),
+        &content,
+        &description,
+        public,
+    )?;
+
     println!(
-        "\n  {} to install, {} already installed, {} unknown",
-        to_install.len().to_string().green(),
-        already_installed,
-        unknown_source
+        "{} Published bundle '{}' ({} tools{})",
+        "+".green(),
+        name.bold(),
+        shared.tools.len(),
+        if public { "" } else { ", secret gist" }
     );
+    println!("  {}", url.cyan());
+    println!("\n  Install with: hoards bundle install --from-url {}", url);
+    Ok(())
+}
 
-    // Confirm
-    if !force {
-        println!();
-        print!("Proceed? [y/N] ");
-        std::io::Write::flush(&mut std::io::stdout())?;
+/// Download a shared bundle from a gist or any URL serving its TOML/JSON
+fn fetch_shared_bundle(url: &str) -> Result<String> {
+    use crate::http::HTTP_AGENT;
 
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
+    let url = url.trim();
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        bail!("Not a URL: {}", url);
+    }
 
-        if !input.trim().eq_ignore_ascii_case("y") {
-            println!("Cancelled");
-            return Ok(());
-        }
+    // Gist pages are read through the API; raw gist URLs are fetched as-is
+    if let Some(path) = url
+        .strip_prefix("https://gist.github.com/")
+        .map(|p| p.split(['#', '?']).next().unwrap_or(p))
+        && let Some(id) = path.trim_end_matches('/').rsplit('/').next()
+        && !id.is_empty()
+    {
+        return crate::forge::github::gist_content(id.trim_end_matches(".git"));
     }
 
-    println!();
+    let mut response = HTTP_AGENT
+        .get(url)
+        .header("User-Agent", "hoards-cli")
+        .call()
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    Ok(response.body_mut().read_to_string()?)
+}
 
-    // Execute installs (safe: no shell interpolation)
-    let mut success = 0;
-    let mut failed = 0;
+/// Preview a shared bundle, save it locally and install it
+///
+/// Tools the bundle lists that aren't tracked yet are added to the
+/// database, and member versions become the local bundle's lock.
+pub fn cmd_bundle_install_from_url(
+    db: &Database,
+    url: &str,
+    force: bool,
+    locked: bool,
+) -> Result<()> {
+    let shared = SharedBundle::parse(&fetch_shared_bundle(url)?)
+        .with_context(|| format!("{} is not a shared hoards bundle", url))?;
+    for tool in &shared.tools {
+        validate_package_name(&tool.name)?;
+    }
 
-    for (tool_name, source, cmd) in &to_install {
+    if db.get_bundle(&shared.name)?.is_some() {
         println!(
-            "{} Installing {} from {}...",
-            ">".cyan(),
-            tool_name.bold(),
-            source
+            "{} Bundle '{}' already exists locally",
+            "!".yellow(),
+            shared.name
+        );
+        println!(
+            "  Install it with 'hoards bundle install {}', or delete it first to replace it",
+            shared.name
         );
+        return Ok(());
+    }
 
-        let status = cmd.execute()?;
+    println!(
+        "{} Bundle '{}' from {}",
+        ">".cyan(),
+        shared.name.bold(),
+        url.dimmed()
+    );
+    if let Some(desc) = &shared.description {
+        println!("  {}", desc);
+    }
+    println!();
 
-        if status.success() {
-            db.set_tool_installed(tool_name, true)?;
-            println!("{} Installed {}", "+".green(), tool_name);
-            success += 1;
+    let machine = Machine::current();
+    let mut new_tools = 0;
+    for tool in &shared.tools {
+        let tracked = db.get_tool_by_name(&tool.name)?.is_some();
+        if !tracked {
+            new_tools += 1;
+        }
+        let applies = tool
+            .when
+            .as_deref()
+            .map(MemberCondition::parse)
+            .transpose()?
+            .is_none_or(|c| c.matches(&machine));
+
+        let mut line = format!("{} ({}", tool.name, tool.source.cyan());
+        if let Some(version) = &tool.version {
+            line.push_str(&format!(" {}", version));
+        }
+        line.push(')');
+        if let Some(when) = &tool.when {
+            line.push_str(&format!(" [{}]", when));
+        }
+        if !applies {
+            println!(
+                "  {} {} (not for this machine)",
+                "-".dimmed(),
+                line.dimmed()
+            );
+        } else if tracked {
+            println!("  {} {}", "+".green(), line);
         } else {
-            println!("{} Failed to install {}", "!".red(), tool_name);
-            failed += 1;
+            println!("  {} {} {}", "+".green(), line, "(new)".yellow());
         }
     }
 
-    println!();
     println!(
-        "{} Bundle '{}': {} installed, {} failed, {} skipped",
-        if failed == 0 {
-            "+".green()
-        } else {
-            "!".yellow()
-        },
-        name,
-        success.to_string().green(),
-        failed.to_string().red(),
-        (already_installed + unknown_source).to_string().dimmed()
+        "\n  {} tools, {} not tracked yet",
+        shared.tools.len(),
+        new_tools
     );
 
-    Ok(())
+    if !force {
+        println!();
+        print!("Save bundle '{}' and install it? [y/N] ", shared.name);
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    for tool in &shared.tools {
+        if db.get_tool_by_name(&tool.name)?.is_none() {
+            let mut new =
+                Tool::new(&tool.name).with_source(InstallSource::from(tool.source.as_str()));
+            if let Some(desc) = &tool.description {
+                new = new.with_description(desc);
+            }
+            db.insert_tool(&new)?;
+        }
+    }
+    db.create_bundle(&shared.to_bundle()?)?;
+
+    let versions: Vec<LockedVersion> = shared
+        .tools
+        .iter()
+        .filter_map(|tool| {
+            Some(LockedVersion {
+                tool: tool.name.clone(),
+                source: tool.source.clone(),
+                version: tool.version.clone()?,
+            })
+        })
+        .collect();
+    if !versions.is_empty() {
+        db.set_bundle_lock(&shared.name, &versions)?;
+    }
+
+    println!("{} Saved bundle '{}'\n", "+".green(), shared.name.bold());
+    if locked && versions.is_empty() {
+        println!(
+            "{} The shared bundle has no versions; installing the latest\n",
+            "i".cyan()
+        );
+    }
+    cmd_bundle_install(db, &shared.name, true, locked && !versions.is_empty())
 }
 
 /// Add tools to an existing bundle
-pub fn cmd_bundle_add(db: &Database, name: &str, tools: Vec<String>) -> Result<()> {
+///
+/// With `when`, the tools (including ones already in the bundle) only apply
+/// to machines matching the condition.
+pub fn cmd_bundle_add(
+    db: &Database,
+    name: &str,
+    tools: Vec<String>,
+    when: Option<&str>,
+) -> Result<()> {
+    let condition = when.map(MemberCondition::parse).transpose()?;
+
     if !db.add_to_bundle(name, &tools)? {
-        println!("Bundle '{}' not found", name);
-        return Ok(());
+        crate::fail!(NotFound, "Bundle '{}' not found", name);
+    }
+    if let Some(condition) = &condition {
+        for tool in &tools {
+            db.set_bundle_condition(name, tool, Some(condition))?;
+        }
     }
 
     println!("{} Added to bundle '{}':", "+".green(), name);
     for tool in &tools {
-        println!("  + {}", tool);
+        match &condition {
+            Some(condition) => {
+                println!("  + {} {}", tool, format!("(when {})", condition).dimmed())
+            }
+            None => println!("  + {}", tool),
+        }
     }
 
     Ok(())
@@ -332,8 +1416,7 @@ pub fn cmd_bundle_add(db: &Database, name: &str, tools: Vec<String>) -> Result<(
 /// Remove tools from a bundle
 pub fn cmd_bundle_remove(db: &Database, name: &str, tools: Vec<String>) -> Result<()> {
     if !db.remove_from_bundle(name, &tools)? {
-        println!("Bundle '{}' not found", name);
-        return Ok(());
+        crate::fail!(NotFound, "Bundle '{}' not found", name);
     }
 
     println!("{} Removed from bundle '{}':", "-".red(), name);
@@ -344,15 +1427,40 @@ pub fn cmd_bundle_remove(db: &Database, name: &str, tools: Vec<String>) -> Resul
     Ok(())
 }
 
+/// Include other bundles in a bundle, or stop including them
+pub fn cmd_bundle_include(
+    db: &Database,
+    name: &str,
+    bundles: Vec<String>,
+    remove: bool,
+) -> Result<()> {
+    if remove {
+        if !db.remove_bundle_includes(name, &bundles)? {
+            crate::fail!(NotFound, "Bundle '{}' not found", name);
+        }
+        println!("{} No longer included in '{}':", "-".red(), name);
+        for bundle in &bundles {
+            println!("  - {}", bundle);
+        }
+        return Ok(());
+    }
+
+    if !db.include_bundles(name, &bundles)? {
+        crate::fail!(NotFound, "Bundle '{}' not found", name);
+    }
+    println!("{} Included in '{}':", "+".green(), name);
+    for bundle in &bundles {
+        println!("  + {}", bundle);
+    }
+    Ok(())
+}
+
 /// Delete a bundle
 pub fn cmd_bundle_delete(db: &Database, name: &str, force: bool) -> Result<()> {
     // Check bundle exists
     let bundle = match db.get_bundle(name)? {
         Some(b) => b,
-        None => {
-            println!("Bundle '{}' not found", name);
-            return Ok(());
-        }
+        None => crate::fail!(NotFound, "Bundle '{}' not found", name),
     };
 
     // Confirm
@@ -383,12 +1491,9 @@ pub fn cmd_bundle_delete(db: &Database, name: &str, force: bool) -> Result<()> {
 pub fn cmd_bundle_update(db: &Database, name: &str, auto_yes: bool) -> Result<()> {
     use crate::updates::*;
 
-    let bundle = match db.get_bundle(name)? {
+    let bundle = match db.get_flattened_bundle(name)? {
         Some(b) => b,
-        None => {
-            println!("Bundle '{}' not found", name);
-            return Ok(());
-        }
+        None => crate::fail!(NotFound, "Bundle '{}' not found", name),
     };
 
     if bundle.tools.is_empty() {
@@ -416,7 +1521,8 @@ pub fn cmd_bundle_update(db: &Database, name: &str, auto_yes: bool) -> Result<()
     let mut not_installed = 0;
     let mut unknown = 0;
 
-    for tool_name in &bundle.tools {
+    let machine = Machine::current();
+    for tool_name in bundle.tools.iter().filter(|t| bundle.applies(t, &machine)) {
         // Get tool info from database
         let tool = match db.get_tool_by_name(tool_name)? {
             Some(t) => t,
@@ -693,3 +1799,86 @@ pub fn cmd_bundle_update(db: &Database, name: &str, auto_yes: bool) -> Result<()
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(tools: &[&str]) -> Vec<String> {
+        tools.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_missing_bundle_is_not_found() -> Result<()> {
+        use crate::exit_code::ExitCode;
+
+        let db = Database::open_in_memory()?;
+        let tools = || names(&["fd"]);
+        let results = [
+            cmd_bundle_install(&db, "nope", true, false),
+            cmd_bundle_sync(&db, "nope", false, true),
+            cmd_bundle_lock(&db, "nope", false),
+            cmd_bundle_publish(&db, "nope", false, None),
+            cmd_bundle_add(&db, "nope", tools(), None),
+            cmd_bundle_remove(&db, "nope", tools()),
+            cmd_bundle_include(&db, "nope", names(&["base"]), false),
+            cmd_bundle_include(&db, "nope", names(&["base"]), true),
+            cmd_bundle_delete(&db, "nope", true),
+            cmd_bundle_update(&db, "nope", true),
+        ];
+        for result in results {
+            assert_eq!(ExitCode::of(&result.unwrap_err()), ExitCode::NotFound);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_former_members() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        for tool in ["bat", "fd", "jq", "rg", "yq"] {
+            db.insert_tool(&Tool::new(tool))?;
+        }
+        // rg was installed separately before joining the bundle
+        db.create_bundle(&Bundle::new("dev", names(&["bat", "fd", "rg"])))?;
+        db.create_bundle(&Bundle::new("data", names(&["jq"])))?;
+        label_installed(&db, "dev", &names(&["bat", "fd", "jq", "yq"]))?;
+
+        // Everything left the bundle; only what it installed is considered
+        let dev = Bundle::new("dev", Vec::new());
+        let former = former_members(&db, "dev", &dev, |tool| tool != "yq")?;
+        assert_eq!(
+            former,
+            FormerMembers {
+                stale: names(&["bat", "fd"]),
+                kept: vec![("jq".to_string(), "data".to_string())],
+                gone: names(&["yq"]),
+            }
+        );
+        assert!(!former.stale.contains(&"rg".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle_labels_keep_case() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("fd"))?;
+        db.insert_tool(&Tool::new("rg"))?;
+        db.create_bundle(&Bundle::new("Dev", names(&["fd"])))?;
+        db.create_bundle(&Bundle::new("dev", names(&["rg"])))?;
+        label_installed(&db, "Dev", &names(&["fd"]))?;
+        label_installed(&db, "dev", &names(&["rg"]))?;
+
+        // Neither bundle claims the other's tool as a former member
+        let dev = db.get_flattened_bundle("dev")?.unwrap();
+        assert_eq!(
+            former_members(&db, "dev", &dev, |_| true)?,
+            FormerMembers::default()
+        );
+        let upper = db.get_flattened_bundle("Dev")?.unwrap();
+        assert_eq!(
+            former_members(&db, "Dev", &upper, |_| true)?,
+            FormerMembers::default()
+        );
+        Ok(())
+    }
+}
@@ -1,12 +1,16 @@
 //! Shell completion installation commands
 //!
-//! Manages installation of hoards shell completions for Fish, Bash, and Zsh.
+//! Manages installation of hoards shell completions for Fish, Bash, Zsh and
+//! Nushell, and of the man pages generated from the same CLI definition.
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::io::IsTerminal;
 use std::path::PathBuf;
 
+/// Shells completions can be installed for
+const SHELLS: [&str; 4] = ["fish", "bash", "zsh", "nushell"];
+
 /// Completion installation status for a shell
 #[derive(Debug)]
 pub struct CompletionStatus {
@@ -24,10 +28,105 @@ fn completion_path(shell: &str) -> Option<PathBuf> {
         "fish" => Some(home.join(".config/fish/completions/hoards.fish")),
         "bash" => Some(home.join(".local/share/bash-completion/completions/hoards")),
         "zsh" => Some(home.join(".zfunc/_hoards")),
+        "nushell" => dirs::config_dir().map(|d| d.join("nushell/completions/hoards.nu")),
         _ => None,
     }
 }
 
+/// Directory man pages are installed to
+///
+/// man finds `~/.local/share/man` on its own when `~/.local/bin` is on PATH.
+fn man_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".local/share/man/man1"))
+}
+
+/// Installed hoards man pages in a directory
+fn man_pages(dir: &std::path::Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut pages: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("hoards") && n.ends_with(".1"))
+        })
+        .collect();
+    pages.sort();
+    pages
+}
+
+/// Write a man page for hoards and every visible subcommand
+fn write_man_pages(dir: &std::path::Path) -> Result<usize> {
+    use clap::CommandFactory;
+
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    clap_mangen::generate_to(crate::cli::Cli::command(), dir)
+        .with_context(|| format!("Failed to write man pages to {}", dir.display()))?;
+    Ok(man_pages(dir).len())
+}
+
+/// Print the hoards man page, or write all pages to a directory
+pub fn cmd_completions_man(dir: Option<&str>) -> Result<()> {
+    use clap::CommandFactory;
+
+    match dir {
+        Some(dir) => {
+            let dir = std::path::Path::new(dir);
+            let count = write_man_pages(dir)?;
+            println!(
+                "{} Wrote {} man pages to {}",
+                "+".green(),
+                count,
+                dir.display()
+            );
+        }
+        None => {
+            let man = clap_mangen::Man::new(crate::cli::Cli::command());
+            man.render(&mut std::io::stdout())?;
+        }
+    }
+    Ok(())
+}
+
+/// Install man pages into the user's manpath
+fn install_man_pages(force: bool) -> Result<()> {
+    let dir = man_dir().context("Could not determine home directory")?;
+
+    if !man_pages(&dir).is_empty() && !force {
+        println!(
+            "  {} man pages already installed (use --force to overwrite)",
+            "!".yellow()
+        );
+        return Ok(());
+    }
+
+    let count = write_man_pages(&dir)?;
+    println!("  {} {} man pages -> {}", "+".green(), count, dir.display());
+
+    // man-db derives the manpath from PATH; other setups need MANPATH
+    let share = dir.parent().unwrap_or(&dir);
+    let found = std::process::Command::new("manpath")
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).contains(&*share.to_string_lossy()))
+        .unwrap_or(false);
+    if !found {
+        println!(
+            "  {} {} is not on your manpath. Add to your shell config:",
+            "!".yellow(),
+            share.display()
+        );
+        println!(
+            "     {}",
+            format!("export MANPATH=\"{}:$MANPATH\"", share.display()).cyan()
+        );
+    }
+
+    Ok(())
+}
+
 /// Check if a shell's config directory exists (indicates shell is used)
 fn shell_config_exists(shell: &str) -> bool {
     let home = match dirs::home_dir() {
@@ -39,6 +138,7 @@ fn shell_config_exists(shell: &str) -> bool {
         "fish" => home.join(".config/fish").exists(),
         "bash" => home.join(".bashrc").exists() || home.join(".bash_profile").exists(),
         "zsh" => home.join(".zshrc").exists(),
+        "nushell" => dirs::config_dir().is_some_and(|d| d.join("nushell").exists()),
         _ => false,
     }
 }
@@ -47,7 +147,7 @@ fn shell_config_exists(shell: &str) -> bool {
 fn detect_shells() -> Vec<String> {
     let mut shells = Vec::new();
 
-    for shell in &["fish", "bash", "zsh"] {
+    for shell in &SHELLS {
         if shell_config_exists(shell) {
             shells.push(shell.to_string());
         }
@@ -61,16 +161,15 @@ fn generate_completion(shell: &str) -> Result<String> {
     use clap::CommandFactory;
     use clap_complete::{Shell, generate};
 
-    let shell_enum = match shell {
-        "fish" => Shell::Fish,
-        "bash" => Shell::Bash,
-        "zsh" => Shell::Zsh,
-        _ => anyhow::bail!("Unsupported shell: {}", shell),
-    };
-
     let mut cmd = crate::cli::Cli::command();
     let mut buf = Vec::new();
-    generate(shell_enum, &mut cmd, "hoards", &mut buf);
+    match shell {
+        "fish" => generate(Shell::Fish, &mut cmd, "hoards", &mut buf),
+        "bash" => generate(Shell::Bash, &mut cmd, "hoards", &mut buf),
+        "zsh" => generate(Shell::Zsh, &mut cmd, "hoards", &mut buf),
+        "nushell" => generate(clap_complete_nushell::Nushell, &mut cmd, "hoards", &mut buf),
+        _ => anyhow::bail!("Unsupported shell: {}", shell),
+    }
 
     // Add version marker comment at the top
     let version = env!("CARGO_PKG_VERSION");
@@ -88,10 +187,9 @@ pub fn cmd_completions_status() -> Result<()> {
     println!("{}", "Shell Completion Status".bold());
     println!("{}", "-".repeat(50));
 
-    let shells = ["fish", "bash", "zsh"];
     let mut any_installed = false;
 
-    for shell in &shells {
+    for shell in &SHELLS {
         let path = completion_path(shell).unwrap_or_default();
         let config_exists = shell_config_exists(shell);
         let installed = path.exists();
@@ -113,13 +211,26 @@ pub fn cmd_completions_status() -> Result<()> {
             format!("{}", "not installed".yellow())
         };
 
-        println!("  {} {:6} {}", status_icon, shell, shell_status);
+        println!("  {} {:8} {}", status_icon, shell, shell_status);
 
         if installed {
-            println!("           {}", path.display().to_string().dimmed());
+            println!("             {}", path.display().to_string().dimmed());
         }
     }
 
+    let man_dir = man_dir().unwrap_or_default();
+    if man_pages(&man_dir).is_empty() {
+        println!(
+            "  {} {:8} {}",
+            " ".dimmed(),
+            "man",
+            "not installed".yellow()
+        );
+    } else {
+        println!("  {} {:8} {}", "+".green(), "man", "installed".green());
+        println!("             {}", man_dir.display().to_string().dimmed());
+    }
+
     println!();
 
     if !any_installed {
@@ -133,8 +244,9 @@ pub fn cmd_completions_status() -> Result<()> {
     Ok(())
 }
 
-/// Install completions for specified or detected shells
-pub fn cmd_completions_install(shell: Option<String>, force: bool) -> Result<()> {
+/// Install completions for specified or detected shells, and the man pages
+/// with `man`
+pub fn cmd_completions_install(shell: Option<String>, force: bool, man: bool) -> Result<()> {
     let shells = match shell {
         Some(s) => vec![s],
         None => detect_shells(),
@@ -145,6 +257,7 @@ pub fn cmd_completions_install(shell: Option<String>, force: bool) -> Result<()>
         println!("  hoards completions install fish");
         println!("  hoards completions install bash");
         println!("  hoards completions install zsh");
+        println!("  hoards completions install nushell");
         return Ok(());
     }
 
@@ -154,6 +267,9 @@ pub fn cmd_completions_install(shell: Option<String>, force: bool) -> Result<()>
     for shell in &shells {
         install_for_shell(shell, force)?;
     }
+    if man {
+        install_man_pages(force)?;
+    }
 
     println!();
     println!(
@@ -193,9 +309,11 @@ fn install_for_shell(shell: &str, force: bool) -> Result<()> {
 
     println!("  {} {} -> {}", "+".green(), shell, path.display());
 
-    // Zsh needs special handling for fpath
+    // Zsh needs special handling for fpath, nushell a `use` in config.nu
     if shell == "zsh" {
         check_zsh_fpath(&path)?;
+    } else if shell == "nushell" {
+        check_nushell_config(&path)?;
     }
 
     Ok(())
@@ -256,11 +374,61 @@ autoload -Uz compinit && compinit
     Ok(())
 }
 
+/// Check that nushell's config.nu loads the completion module
+fn check_nushell_config(completion_path: &std::path::Path) -> Result<()> {
+    let Some(config_nu) = dirs::config_dir().map(|d| d.join("nushell/config.nu")) else {
+        return Ok(());
+    };
+
+    let content = std::fs::read_to_string(&config_nu).unwrap_or_default();
+    if content.contains("completions/hoards.nu") {
+        return Ok(());
+    }
+
+    let use_line = format!("use {} *", completion_path.display());
+    println!();
+    println!(
+        "  {} Nushell loads completions from config.nu. Add to {}:",
+        "!".yellow(),
+        config_nu.display()
+    );
+    println!("     {}", use_line.cyan());
+
+    if std::io::stdout().is_terminal() {
+        use dialoguer::Confirm;
+
+        println!();
+        let add = Confirm::new()
+            .with_prompt("Add it to config.nu automatically?")
+            .default(true)
+            .interact()?;
+
+        if add {
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&config_nu)?;
+
+            use std::io::Write;
+            writeln!(
+                file,
+                "\n# Hoards completions (added by hoards)\n{}",
+                use_line
+            )?;
+
+            println!("  {} Added completions to config.nu", "+".green());
+        }
+    }
+
+    Ok(())
+}
+
 /// Uninstall completions for specified or all shells
 pub fn cmd_completions_uninstall(shell: Option<String>) -> Result<()> {
+    let all = shell.is_none();
     let shells = match shell {
         Some(s) => vec![s],
-        None => vec!["fish".into(), "bash".into(), "zsh".into()],
+        None => SHELLS.iter().map(|s| s.to_string()).collect(),
     };
 
     println!("{} Removing completions...", ">".cyan());
@@ -279,9 +447,45 @@ pub fn cmd_completions_uninstall(shell: Option<String>) -> Result<()> {
         }
     }
 
+    // Man pages go with the rest when no shell is named
+    if all {
+        let pages = man_pages(&man_dir().unwrap_or_default());
+        for page in &pages {
+            std::fs::remove_file(page)
+                .with_context(|| format!("Failed to remove: {}", page.display()))?;
+        }
+        if !pages.is_empty() {
+            println!("  {} Removed {} man pages", "-".red(), pages.len());
+            removed_any = true;
+        }
+    }
+
     if !removed_any {
         println!("  {} No completions were installed", "!".yellow());
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_man_pages() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let man = dir.path().join("man1");
+
+        let count = write_man_pages(&man)?;
+        assert!(count > 1);
+        assert_eq!(man_pages(&man).len(), count);
+        assert!(man.join("hoards.1").is_file());
+        assert!(man.join("hoards-install.1").is_file());
+        // Nested subcommands get their own page too
+        assert!(man.join("hoards-config-link.1").is_file());
+
+        let page = std::fs::read_to_string(man.join("hoards.1"))?;
+        assert!(page.contains(".TH hoards"));
+        Ok(())
+    }
+}
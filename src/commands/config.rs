@@ -2,16 +2,93 @@
 //!
 //! Commands for managing dotfiles and tool configurations.
 
+use crate::commands::helpers::report_secrets;
+use crate::config::HoardConfig;
 use crate::db::Database;
-use crate::models::Config;
+use crate::dotfiles::{
+    Drift, ImportedConfig, ManifestEntry, RenderState, active_profile, changed_paths,
+    chezmoi_configs, commit_message, content_hash, copy_state, drift_path, find_drift, git,
+    is_template, read_manifest, render, render_state, select_variant, stow_configs, template_vars,
+    tool_candidates, tree_hash, unified_diff, validation_args, write_manifest,
+};
+use crate::models::{Config, ConfigMode, Machine};
+use crate::process::output_with_timeout;
+use crate::secrets::scan_path;
 use anyhow::{Context, Result, bail};
+use chrono::Local;
 use colored::Colorize;
+use notify::{Event, RecursiveMode, Watcher};
+use std::cell::LazyCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::os::unix::fs as unix_fs;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Template variables, only looked up once a template needs them
+type TemplateVars = LazyCell<BTreeMap<String, String>>;
+
+fn load_template_vars() -> BTreeMap<String, String> {
+    template_vars(&HoardConfig::load().unwrap_or_default().dotfiles)
+}
+
+/// Render a templated config source
+fn render_source(source: &Path, vars: &TemplateVars) -> Result<String> {
+    let template = fs::read_to_string(source)
+        .with_context(|| format!("Failed to read template: {}", source.display()))?;
+    render(&template, vars)
+}
+
+/// One-word status of a config's target
+///
+/// `linked`, `copy` (a real file matching the source), `conflict`,
+/// `unlinked`, `missing` (no source), and for templates `stale` (needs a
+/// re-render) or `invalid` (fails to render). A config in copy mode is
+/// `linked` while its copy is current and `stale` once the source changed.
+fn config_status(config: &Config, vars: &TemplateVars) -> &'static str {
+    let target_path = expand_path(&config.target_path);
+    let source_path = expand_path(&config.source_path);
+
+    if !source_path.exists() {
+        return "missing";
+    }
+    if is_template(&source_path) {
+        let Ok(rendered) = render_source(&source_path, vars) else {
+            return "invalid";
+        };
+        return match render_state(&target_path, config.rendered_hash.as_deref(), &rendered) {
+            RenderState::Rendered => "linked",
+            RenderState::Stale => "stale",
+            RenderState::Modified => "conflict",
+            RenderState::Missing => "unlinked",
+        };
+    }
+    if config.mode == ConfigMode::Copy {
+        if is_valid_symlink(&target_path, &source_path) {
+            return "stale";
+        }
+        return match copy_state(&source_path, &target_path, config.rendered_hash.as_deref()) {
+            Ok(RenderState::Rendered) => "linked",
+            Ok(RenderState::Stale) => "stale",
+            Ok(RenderState::Missing) => "unlinked",
+            Ok(RenderState::Modified) | Err(_) => "conflict",
+        };
+    }
+    if is_valid_symlink(&target_path, &source_path) {
+        "linked"
+    } else if target_path.exists() {
+        let in_sync = !target_path.is_symlink()
+            && find_drift(&source_path, &target_path).is_ok_and(|d| d.is_empty());
+        if in_sync { "copy" } else { "conflict" }
+    } else {
+        "unlinked"
+    }
+}
 
 /// Expand ~ to home directory
-fn expand_path(path: &str) -> PathBuf {
+pub(crate) fn expand_path(path: &str) -> PathBuf {
     if path.starts_with("~/")
         && let Some(home) = dirs::home_dir()
     {
@@ -20,6 +97,26 @@ fn expand_path(path: &str) -> PathBuf {
     PathBuf::from(path)
 }
 
+/// Create a symlink; Windows links files and directories differently
+pub(crate) fn symlink(original: impl AsRef<Path>, link: impl AsRef<Path>) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(original, link)
+    }
+    #[cfg(windows)]
+    {
+        let (original, link) = (original.as_ref(), link.as_ref());
+        let resolved = link
+            .parent()
+            .map_or_else(|| original.to_path_buf(), |dir| dir.join(original));
+        if resolved.is_dir() {
+            std::os::windows::fs::symlink_dir(original, link)
+        } else {
+            std::os::windows::fs::symlink_file(original, link)
+        }
+    }
+}
+
 /// Check if a path is a symlink pointing to the expected target
 fn is_valid_symlink(link_path: &Path, expected_target: &Path) -> bool {
     if !link_path.is_symlink() {
@@ -45,18 +142,171 @@ fn is_valid_symlink(link_path: &Path, expected_target: &Path) -> bool {
     }
 }
 
+/// Directory holding targets moved aside by `config sync`
+fn backups_root() -> Result<PathBuf> {
+    Ok(Database::db_path()?.with_file_name("backups"))
+}
+
+/// Move a file or directory, copying when a rename can't cross filesystems
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    remove_path(from)
+}
+
+/// Remove a file, symlink or directory tree
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() && !path.is_symlink() {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Copy a file, symlink or directory tree
+pub(crate) fn copy_recursive(from: &Path, to: &Path) -> Result<()> {
+    if from.is_symlink() {
+        symlink(fs::read_link(from)?, to)?;
+    } else if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(from, to)
+            .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    }
+    Ok(())
+}
+
+/// Move a config's existing target into a timestamped backup directory
+///
+/// The backup is recorded so `config restore` can put it back.
+fn backup_target(db: &Database, name: &str, target: &Path, root: &Path) -> Result<PathBuf> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let mut dir = root.join(name).join(&stamp);
+    let mut n = 1;
+    while dir.exists() {
+        n += 1;
+        dir = root.join(name).join(format!("{}-{}", stamp, n));
+    }
+    fs::create_dir_all(&dir)?;
+
+    let file_name = target.file_name().unwrap_or_else(|| name.as_ref());
+    let backup = dir.join(file_name);
+    move_path(target, &backup)
+        .with_context(|| format!("Failed to back up {}", target.display()))?;
+    db.record_config_backup(name, &target.to_string_lossy(), &backup.to_string_lossy())?;
+    Ok(backup)
+}
+
+/// Put the latest backup of a config's target back in place
+fn restore_backup(db: &Database, name: &str, force: bool) -> Result<Option<PathBuf>> {
+    let Some(backup) = db.list_config_backups(name)?.into_iter().next() else {
+        return Ok(None);
+    };
+    let original = PathBuf::from(&backup.original_path);
+    let saved = PathBuf::from(&backup.backup_path);
+    if !saved.exists() && !saved.is_symlink() {
+        bail!("Backup is gone: {}", saved.display());
+    }
+
+    // Only a link can be replaced silently; real files need --force
+    if original.is_symlink() {
+        fs::remove_file(&original)?;
+    } else if original.exists() {
+        if !force {
+            bail!(
+                "{} exists and is not a link (use --force to replace it)",
+                original.display()
+            );
+        }
+        if original.is_dir() {
+            fs::remove_dir_all(&original)?;
+        } else {
+            fs::remove_file(&original)?;
+        }
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    move_path(&saved, &original)?;
+
+    db.delete_config_backup(backup.id)?;
+    if let Some(dir) = saved.parent() {
+        fs::remove_dir(dir).ok(); // only if empty
+    }
+    Ok(Some(original))
+}
+
+/// Configs with the source of the variant picked for this machine
+///
+/// Each comes with the profile of the variant in use, if any.
+fn with_variants(db: &Database, configs: Vec<Config>) -> Result<Vec<(Config, Option<String>)>> {
+    let profile = HoardConfig::load().unwrap_or_default().dotfiles.profile;
+    let hostname = Machine::current().hostname;
+
+    configs
+        .into_iter()
+        .map(|mut config| {
+            let variants = db.list_config_variants(&config.name)?;
+            let profiles: Vec<&str> = variants.iter().map(|v| v.profile.as_str()).collect();
+            let chosen = select_variant(&profiles, profile.as_deref(), &hostname);
+            if let Some(variant) = variants.iter().find(|v| Some(v.profile.as_str()) == chosen) {
+                config.source_path = variant.source_path.clone();
+            }
+            Ok((config, chosen.map(str::to_string)))
+        })
+        .collect()
+}
+
+/// Configs with the sources this machine uses
+fn resolve_variants(db: &Database, configs: Vec<Config>) -> Result<Vec<Config>> {
+    Ok(with_variants(db, configs)?
+        .into_iter()
+        .map(|(config, _)| config)
+        .collect())
+}
+
+/// How `config link` registers a config
+#[derive(Debug, Clone, Default)]
+pub struct LinkOptions {
+    /// Tool the config belongs to
+    pub tool: Option<String>,
+    /// Profile whose variant the source is
+    pub profile: Option<String>,
+    pub mode: ConfigMode,
+    /// Command that checks a new version before `config sync` puts it in place
+    pub validate: Option<String>,
+}
+
 /// Link a config to be managed by hoard
+///
+/// With a profile, the source is registered as that profile's variant,
+/// adding to an existing config of the same name.
 pub fn cmd_config_link(
     db: &Database,
     name: &str,
     target: &str,
     source: &str,
-    tool: Option<String>,
+    options: LinkOptions,
 ) -> Result<()> {
-    // Check if config already exists
-    if db.get_config_by_name(name)?.is_some() {
+    let LinkOptions {
+        tool,
+        profile,
+        mode,
+        validate,
+    } = options;
+    let profile = profile.as_deref();
+    let validate = validate.as_deref();
+    let existing = db.get_config_by_name(name)?;
+    if existing.is_some() && profile.is_none() {
         bail!(
-            "Config '{}' already exists. Use 'hoards config edit' to modify it.",
+            "Config '{}' already exists. Use 'hoards config edit' to modify it, or --profile to add a variant.",
             name
         );
     }
@@ -69,12 +319,36 @@ pub fn cmd_config_link(
         bail!("Source path does not exist: {}", source_path.display());
     }
 
+    if let (Some(existing), Some(profile)) = (existing, profile) {
+        if expand_path(&existing.target_path) != target_path {
+            println!(
+                "{} Variants share the config's target; keeping {}",
+                "!".yellow(),
+                existing.target_path
+            );
+        }
+        db.set_config_variant(name, profile, &source_path.to_string_lossy())?;
+        if validate.is_some() {
+            db.set_config_validate(name, validate)?;
+        }
+        println!(
+            "{} Added '{}' variant of config '{}'",
+            "✓".green(),
+            profile,
+            name
+        );
+        println!("  Source: {}", source_path.display());
+        return Ok(());
+    }
+
     // Create the config entry
     let mut config = Config::new(
         name,
         source_path.to_string_lossy(),
         target_path.to_string_lossy(),
     );
+    config.mode = mode;
+    config.validate_command = validate.map(str::to_string);
 
     // Link to tool if specified
     if let Some(ref tool_name) = tool {
@@ -85,6 +359,9 @@ pub fn cmd_config_link(
     }
 
     db.insert_config(&config)?;
+    if let Some(profile) = profile {
+        db.set_config_variant(name, profile, &source_path.to_string_lossy())?;
+    }
 
     println!("{} Added config '{}'", "✓".green(), name);
     println!("  Source: {}", source_path.display());
@@ -92,23 +369,52 @@ pub fn cmd_config_link(
     if let Some(tool_name) = tool {
         println!("  Tool:   {}", tool_name);
     }
+    if let Some(profile) = profile {
+        println!("  Profile: {}", profile);
+    }
+    if mode == ConfigMode::Copy {
+        println!("  Mode:   copy");
+    }
+    if let Some(command) = validate {
+        println!("  Validate: {}", command);
+    }
     println!();
-    println!("Run {} to create the symlink", "hoards config sync".cyan());
+    if is_template(&source_path) {
+        println!("Run {} to render it", "hoards config sync".cyan());
+    } else if mode == ConfigMode::Copy {
+        println!("Run {} to copy it into place", "hoards config sync".cyan());
+    } else {
+        println!("Run {} to create the symlink", "hoards config sync".cyan());
+    }
 
     Ok(())
 }
 
-/// Unlink a config
+/// Unlink a config, or just one of its profile variants
 pub fn cmd_config_unlink(
     db: &Database,
     name: &str,
     remove_symlink: bool,
     force: bool,
+    profile: Option<&str>,
 ) -> Result<()> {
     let config = db
         .get_config_by_name(name)?
         .ok_or_else(|| anyhow::anyhow!("Config '{}' not found", name))?;
 
+    if let Some(profile) = profile {
+        if !db.remove_config_variant(name, profile)? {
+            bail!("Config '{}' has no '{}' variant", name, profile);
+        }
+        println!(
+            "{} Removed '{}' variant of config '{}'",
+            "✓".green(),
+            profile,
+            name
+        );
+        return Ok(());
+    }
+
     if !force {
         println!("Remove config '{}'?", name);
         println!("  Source: {}", config.source_path);
@@ -151,7 +457,7 @@ pub fn cmd_config_list(db: &Database, broken_only: bool, format: &str) -> Result
         Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
     };
 
-    let configs = db.list_configs()?;
+    let configs = resolve_variants(db, db.list_configs()?)?;
 
     if configs.is_empty() {
         println!(
@@ -184,23 +490,19 @@ pub fn cmd_config_list(db: &Database, broken_only: bool, format: &str) -> Result
             Cell::new("✓").fg(Color::Cyan),
         ]);
 
+    let vars = TemplateVars::new(load_template_vars);
     let mut shown = 0;
     for config in configs {
-        let target_path = expand_path(&config.target_path);
-        let source_path = expand_path(&config.source_path);
-
-        let (status_text, status_color) = if !source_path.exists() {
-            ("missing", Color::Red)
-        } else if is_valid_symlink(&target_path, &source_path) {
-            ("linked", Color::Green)
-        } else if target_path.exists() {
-            ("conflict", Color::Yellow)
-        } else {
-            ("unlinked", Color::Grey)
+        let status_text = config_status(&config, &vars);
+        let status_color = match status_text {
+            "linked" | "copy" => Color::Green,
+            "conflict" | "stale" => Color::Yellow,
+            "unlinked" => Color::Grey,
+            _ => Color::Red,
         };
 
         // Filter if showing broken only
-        if broken_only && (status_text == "linked" || status_text == "unlinked") {
+        if broken_only && matches!(status_text, "linked" | "copy" | "unlinked") {
             continue;
         }
 
@@ -215,7 +517,7 @@ pub fn cmd_config_list(db: &Database, broken_only: bool, format: &str) -> Result
 
     println!("{table}");
     println!(
-        "{} 🔗 linked  ❌ missing  ⚠ conflict  ◯ unlinked",
+        "{} 🔗 linked  ≡ copy  ❌ missing  ⚠ conflict  ↻ stale  ◯ unlinked",
         "".dimmed()
     );
     println!("{} {} configs", ">".cyan(), shown);
@@ -227,26 +529,59 @@ pub fn cmd_config_show(db: &Database, name: &str) -> Result<()> {
     let config = db
         .get_config_by_name(name)?
         .ok_or_else(|| anyhow::anyhow!("Config '{}' not found", name))?;
+    let variants = db.list_config_variants(name)?;
+    let (config, active) = with_variants(db, vec![config])?.remove(0);
 
-    let target_path = expand_path(&config.target_path);
     let source_path = expand_path(&config.source_path);
+    let template = is_template(&source_path);
+    let copy = config.mode == ConfigMode::Copy && !template;
 
     println!("{}", config.name.bold());
     println!();
     println!("  Source: {}", config.source_path);
     println!("  Target: {}", config.target_path);
+    if copy {
+        println!("  Mode:   copy");
+    }
+    if let Some(command) = &config.validate_command {
+        println!("  Validate: {}", command);
+    }
+    if !variants.is_empty() {
+        println!("  Variants:");
+        for variant in &variants {
+            let marker = if Some(&variant.profile) == active.as_ref() {
+                "*".green()
+            } else {
+                " ".normal()
+            };
+            println!(
+                "   {} {:<16} {}",
+                marker, variant.profile, variant.source_path
+            );
+        }
+    }
 
     // Status
-    let status = if !source_path.exists() {
-        "Source missing".red()
-    } else if is_valid_symlink(&target_path, &source_path) {
-        "Linked".green()
-    } else if target_path.exists() {
-        "Conflict (target exists)".yellow()
-    } else {
-        "Not linked".dimmed()
+    let vars = TemplateVars::new(load_template_vars);
+    let status = match (config_status(&config, &vars), template) {
+        ("missing", _) => "Source missing".red(),
+        ("invalid", _) => "Template error".red(),
+        ("linked", _) if copy => "Copied".green(),
+        ("linked", false) => "Linked".green(),
+        ("linked", true) => "Rendered".green(),
+        ("copy", _) => "Copy (matches source)".green(),
+        ("stale", _) if copy => "Stale (source changed since last copy)".yellow(),
+        ("stale", _) => "Stale (template or variables changed)".yellow(),
+        ("conflict", _) if copy => "Conflict (target edited since copy)".yellow(),
+        ("conflict", false) => "Conflict (target exists)".yellow(),
+        ("conflict", true) => "Conflict (target edited since render)".yellow(),
+        (_, false) => "Not linked".dimmed(),
+        (_, true) => "Not rendered".dimmed(),
     };
     println!("  Status: {}", status);
+    if template && let Err(e) = render_source(&source_path, &vars) {
+        println!("  Error:  {}", e);
+    }
 
     // Associated tool
     if let Some(tool_id) = config.tool_id {
@@ -262,182 +597,1346 @@ pub fn cmd_config_show(db: &Database, name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Sync all configs (create symlinks)
-pub fn cmd_config_sync(db: &Database, dry_run: bool, force: bool) -> Result<()> {
-    let configs = db.list_configs()?;
+/// What syncing a single config did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SyncOutcome {
+    Created,
+    Skipped,
+    Failed,
+}
 
-    if configs.is_empty() {
-        println!("No configs to sync.");
+/// How long a validation command may run before it counts as failed
+const VALIDATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Run a config's validation command against its target
+fn run_validation(command: &str, target: &Path) -> Result<()> {
+    let args = validation_args(command, target)?;
+    let output = output_with_timeout(
+        Command::new(&args[0]).args(&args[1..]).stdin(Stdio::null()),
+        VALIDATION_TIMEOUT,
+    )
+    .with_context(|| format!("Failed to run {}", args[0]))?;
+    let Some(output) = output else {
+        bail!(
+            "{} timed out after {}s",
+            args[0],
+            VALIDATION_TIMEOUT.as_secs()
+        );
+    };
+    if output.status.success() {
         return Ok(());
     }
 
-    let mut created = 0;
-    let mut skipped = 0;
-    let mut errors = 0;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stderr
+        .lines()
+        .chain(stdout.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+    {
+        Some(line) => bail!("{} failed ({}): {}", args[0], output.status, line),
+        None => bail!("{} failed ({})", args[0], output.status),
+    }
+}
 
-    for config in configs {
-        let target_path = expand_path(&config.target_path);
-        let source_path = expand_path(&config.source_path);
+/// Link (or render) one config into place, then validate it
+///
+/// With a validation command, the current target is kept aside while the
+/// new one is checked, and put back if the check fails.
+fn sync_one(
+    db: &Database,
+    config: &Config,
+    vars: &TemplateVars,
+    dry_run: bool,
+    force: bool,
+) -> Result<SyncOutcome> {
+    let Some(command) = config.validate_command.as_deref().filter(|_| !dry_run) else {
+        return place_config(db, config, vars, dry_run, force);
+    };
+    let target_path = expand_path(&config.target_path);
+
+    // The saved copy's directory is removed when it goes out of scope
+    let saved_dir = if (target_path.exists() || target_path.is_symlink())
+        && config_status(config, vars) != "linked"
+    {
+        let dir = tempfile::Builder::new()
+            .prefix("hoards-validate-")
+            .tempdir()?;
+        copy_recursive(&target_path, &dir.path().join(&config.name))?;
+        Some(dir)
+    } else {
+        None
+    };
+    let saved = saved_dir.as_ref().map(|dir| dir.path().join(&config.name));
+
+    match place_config(db, config, vars, dry_run, force) {
+        Ok(SyncOutcome::Created) => match run_validation(command, &target_path) {
+            Ok(()) => {
+                println!(
+                    "  {} {}",
+                    "✓".green(),
+                    format!("validated: {}", command).dimmed()
+                );
+                Ok(SyncOutcome::Created)
+            }
+            Err(e) => {
+                println!("{} {} - validation failed: {}", "✗".red(), config.name, e);
+                if target_path.exists() || target_path.is_symlink() {
+                    remove_path(&target_path)?;
+                }
+                if let Some(saved) = &saved {
+                    move_path(saved, &target_path)?;
+                    println!("{} {} - restored previous version", ">".cyan(), config.name);
+                }
+                db.set_config_rendered(&config.name, config.rendered_hash.as_deref())?;
+                db.set_config_symlinked(&config.name, config.is_symlinked && saved.is_some())?;
+                Ok(SyncOutcome::Failed)
+            }
+        },
+        other => other,
+    }
+}
+
+/// Link (or render) one config into place
+fn place_config(
+    db: &Database,
+    config: &Config,
+    vars: &TemplateVars,
+    dry_run: bool,
+    force: bool,
+) -> Result<SyncOutcome> {
+    let target_path = expand_path(&config.target_path);
+    let source_path = expand_path(&config.source_path);
+
+    // Check source exists
+    if !source_path.exists() {
+        println!(
+            "{} {} - source missing: {}",
+            "✗".red(),
+            config.name,
+            source_path.display()
+        );
+        return Ok(SyncOutcome::Failed);
+    }
+
+    // Templates are rendered into place and copies copied rather than linked
+    let written = if is_template(&source_path) {
+        Some(sync_template(
+            db,
+            config,
+            &source_path,
+            &target_path,
+            vars,
+            dry_run,
+            force,
+        ))
+    } else if config.mode == ConfigMode::Copy {
+        Some(sync_copy(
+            db,
+            config,
+            &source_path,
+            &target_path,
+            dry_run,
+            force,
+        ))
+    } else {
+        None
+    };
+    if let Some(written) = written {
+        return match written {
+            Ok(true) => Ok(SyncOutcome::Created),
+            Ok(false) => Ok(SyncOutcome::Skipped),
+            Err(e) => {
+                println!("{} {} - {}", "✗".red(), config.name, e);
+                Ok(SyncOutcome::Failed)
+            }
+        };
+    }
 
-        // Check source exists
-        if !source_path.exists() {
+    // Check if already correctly linked
+    if is_valid_symlink(&target_path, &source_path) {
+        return Ok(SyncOutcome::Skipped);
+    }
+
+    // After a profile switch the target links to another variant
+    let switching = links_to_variant(db, &config.name, &target_path)?;
+    if switching {
+        if dry_run {
             println!(
-                "{} {} - source missing: {}",
-                "✗".red(),
+                "{} {} - would switch variant: {}",
+                ">".cyan(),
                 config.name,
-                source_path.display()
+                target_path.display()
             );
-            errors += 1;
-            continue;
+        } else {
+            fs::remove_file(&target_path)?;
         }
+    }
 
-        // Check if already correctly linked
-        if is_valid_symlink(&target_path, &source_path) {
-            skipped += 1;
-            continue;
+    // An untouched copy from before the config switched to link mode
+    let was_copied = !target_path.is_symlink()
+        && config.rendered_hash.is_some()
+        && tree_hash(&target_path).ok() == config.rendered_hash;
+    if was_copied {
+        if dry_run {
+            println!(
+                "{} {} - would replace copy: {}",
+                ">".cyan(),
+                config.name,
+                target_path.display()
+            );
+        } else {
+            remove_path(&target_path)?;
         }
+    }
 
-        // Check for conflicts
-        if target_path.exists() || target_path.is_symlink() {
-            if force {
-                if dry_run {
-                    println!(
-                        "{} {} - would remove existing: {}",
-                        "!".yellow(),
-                        config.name,
-                        target_path.display()
-                    );
-                } else if target_path.is_dir() && !target_path.is_symlink() {
-                    fs::remove_dir_all(&target_path)?;
-                } else {
-                    fs::remove_file(&target_path)?;
-                }
-            } else {
+    // Check for conflicts
+    if !switching && !was_copied && (target_path.exists() || target_path.is_symlink()) {
+        if force {
+            if dry_run {
                 println!(
-                    "{} {} - target exists: {} (use --force to overwrite)",
+                    "{} {} - would back up existing: {}",
                     "!".yellow(),
                     config.name,
                     target_path.display()
                 );
-                skipped += 1;
-                continue;
-            }
-        }
-
-        // Create parent directory if needed
-        if let Some(parent) = target_path.parent()
-            && !parent.exists()
-        {
-            if dry_run {
-                println!("  Would create directory: {}", parent.display());
             } else {
-                fs::create_dir_all(parent)?;
+                let backup = backup_target(db, &config.name, &target_path, &backups_root()?)?;
+                println!(
+                    "{} {} - backed up to {}",
+                    ">".cyan(),
+                    config.name,
+                    backup.display()
+                );
             }
-        }
-
-        // Create symlink
-        if dry_run {
-            println!(
-                "{} {} → {}",
-                "→".cyan(),
-                target_path.display(),
-                source_path.display()
-            );
         } else {
-            unix_fs::symlink(&source_path, &target_path).with_context(|| {
-                format!(
-                    "Failed to create symlink: {} → {}",
-                    target_path.display(),
-                    source_path.display()
-                )
-            })?;
-
-            db.set_config_symlinked(&config.name, true)?;
             println!(
-                "{} {} → {}",
-                "✓".green(),
+                "{} {} - target exists: {} (use --force to overwrite)",
+                "!".yellow(),
                 config.name,
                 target_path.display()
             );
+            return Ok(SyncOutcome::Skipped);
         }
-        created += 1;
     }
 
-    println!();
+    // Create parent directory if needed
+    if let Some(parent) = target_path.parent()
+        && !parent.exists()
+    {
+        if dry_run {
+            println!("  Would create directory: {}", parent.display());
+        } else {
+            fs::create_dir_all(parent)?;
+        }
+    }
+
+    // Create symlink
     if dry_run {
         println!(
-            "Dry run: {} would be created, {} already linked, {} errors",
-            created, skipped, errors
+            "{} {} → {}",
+            "→".cyan(),
+            target_path.display(),
+            source_path.display()
         );
     } else {
+        symlink(&source_path, &target_path).with_context(|| {
+            format!(
+                "Failed to create symlink: {} → {}",
+                target_path.display(),
+                source_path.display()
+            )
+        })?;
+
+        if config.rendered_hash.is_some() {
+            db.set_config_rendered(&config.name, None)?;
+        }
+        db.set_config_symlinked(&config.name, true)?;
         println!(
-            "Synced: {} created, {} already linked, {} errors",
-            created, skipped, errors
+            "{} {} → {}",
+            "✓".green(),
+            config.name,
+            target_path.display()
         );
     }
-
-    Ok(())
+    Ok(SyncOutcome::Created)
 }
 
-/// Show status of all config symlinks
-pub fn cmd_config_status(db: &Database) -> Result<()> {
-    let configs = db.list_configs()?;
+/// Sync all configs (create symlinks)
+pub fn cmd_config_sync(db: &Database, dry_run: bool, force: bool) -> Result<()> {
+    let configs = resolve_variants(db, db.list_configs()?)?;
 
     if configs.is_empty() {
-        println!("No configs managed.");
+        println!("No configs to sync.");
         return Ok(());
     }
 
-    let mut linked = 0;
-    let mut unlinked = 0;
-    let mut broken = 0;
-    let mut conflicts = 0;
-
-    println!("{}", "Config Status".bold());
-    println!();
-
+    // Warn before credentials get linked from, or committed to, a repository
+    let ignore = HoardConfig::load()
+        .unwrap_or_default()
+        .dotfiles
+        .secrets_ignore;
+    let mut secrets = Vec::new();
     for config in &configs {
-        let target_path = expand_path(&config.target_path);
         let source_path = expand_path(&config.source_path);
+        if source_path.exists() {
+            secrets.extend(scan_path(&source_path, &ignore)?);
+        }
+    }
+    report_secrets(&secrets);
 
-        let (icon, status) = if !source_path.exists() {
-            broken += 1;
-            ("✗".red(), "source missing".red())
-        } else if is_valid_symlink(&target_path, &source_path) {
-            linked += 1;
-            ("✓".green(), "linked".green())
-        } else if target_path.exists() {
-            conflicts += 1;
-            ("!".yellow(), "conflict".yellow())
-        } else {
-            unlinked += 1;
-            ("○".dimmed(), "not linked".dimmed())
-        };
+    let mut created = 0;
+    let mut skipped = 0;
+    let mut errors = 0;
+    let vars = TemplateVars::new(load_template_vars);
 
-        println!("  {} {:<20} {}", icon, config.name, status);
+    for config in &configs {
+        match sync_one(db, config, &vars, dry_run, force)? {
+            SyncOutcome::Created => created += 1,
+            SyncOutcome::Skipped => skipped += 1,
+            SyncOutcome::Failed => errors += 1,
+        }
     }
 
     println!();
-    println!(
-        "Total: {} configs ({} linked, {} unlinked, {} conflicts, {} broken)",
-        configs.len(),
-        linked,
-        unlinked,
-        conflicts,
-        broken
-    );
-
-    if unlinked > 0 || conflicts > 0 {
-        println!();
+    if dry_run {
         println!(
-            "Run {} to create missing symlinks",
-            "hoards config sync".cyan()
+            "Dry run: {} would be created, {} already linked, {} errors",
+            created, skipped, errors
         );
-    }
+    } else {
+        println!(
+            "Synced: {} created, {} already linked, {} errors",
+            created, skipped, errors
+        );
+
+        // Keep the config repository, if any, committed
+        if let Some(repo) = config_repo() {
+            let in_repo = secrets
+                .iter()
+                .filter(|s| s.path.as_ref().is_some_and(|p| p.starts_with(&repo)))
+                .count();
+            if in_repo > 0 {
+                println!(
+                    "{} Not committing {}: {} possible secrets (see above)",
+                    "!".yellow(),
+                    repo.display(),
+                    in_repo
+                );
+                return Ok(());
+            }
+            match commit_repo(db, &repo) {
+                Ok(Some(message)) => println!("{} Committed: {}", ">".cyan(), message),
+                Ok(None) => {}
+                Err(e) => println!(
+                    "{} Could not commit {}: {}",
+                    "!".yellow(),
+                    repo.display(),
+                    e
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ==================== Config Repository ====================
+
+/// The configured config repository, if it is a git checkout
+fn config_repo() -> Option<PathBuf> {
+    let repo = HoardConfig::load().ok()?.dotfiles.repo?;
+    repo.join(".git").exists().then_some(repo)
+}
+
+fn require_config_repo() -> Result<PathBuf> {
+    config_repo().ok_or_else(|| {
+        anyhow::anyhow!("No config repository. Run 'hoards config repo init' or 'clone' first.")
+    })
+}
+
+/// Write a path under the home directory as `~/...`
+fn home_relative(path: &Path) -> String {
+    if let Some(home) = dirs::home_dir()
+        && let Ok(rest) = path.strip_prefix(&home)
+    {
+        return format!("~/{}", rest.display());
+    }
+    path.to_string_lossy().into_owned()
+}
+
+/// Manifest entries for the configs whose source is inside the repository
+fn manifest_entries(db: &Database, repo: &Path) -> Result<Vec<ManifestEntry>> {
+    let tools = db.list_tools(false, None)?;
+    let mut entries = Vec::new();
+    for config in db.list_configs()? {
+        let source = expand_path(&config.source_path);
+        let Ok(relative) = source.strip_prefix(repo) else {
+            continue;
+        };
+        entries.push(ManifestEntry {
+            name: config.name.clone(),
+            source: relative.to_string_lossy().into_owned(),
+            target: home_relative(&expand_path(&config.target_path)),
+            mode: config.mode,
+            validate: config.validate_command.clone(),
+            tool: config
+                .tool_id
+                .and_then(|id| tools.iter().find(|t| t.id == Some(id)))
+                .map(|t| t.name.clone()),
+        });
+    }
+    Ok(entries)
+}
+
+/// Refresh the manifest and commit every change in the repository
+///
+/// Refuses while the repository holds likely secrets.
+fn commit_repo(db: &Database, repo: &Path) -> Result<Option<String>> {
+    let ignore = HoardConfig::load()
+        .unwrap_or_default()
+        .dotfiles
+        .secrets_ignore;
+    let secrets = scan_path(repo, &ignore)?;
+    if !secrets.is_empty() {
+        report_secrets(&secrets);
+        bail!(
+            "{} possible secrets in the repository; remove them or list the files in dotfiles.secrets_ignore",
+            secrets.len()
+        );
+    }
+
+    let mut entries = manifest_entries(db, repo)?;
+    write_manifest(repo, &mut entries)?;
+
+    let changed = changed_paths(repo)?;
+    if changed.is_empty() {
+        return Ok(None);
+    }
+    let message = commit_message(&changed, &entries);
+    git(repo, &["add", "-A"])?;
+    git(repo, &["commit", "-q", "-m", &message])?;
+    Ok(Some(message))
+}
+
+/// Register the configs listed in a repository's manifest
+fn import_manifest(db: &Database, repo: &Path) -> Result<usize> {
+    let mut added = 0;
+    for entry in read_manifest(repo)? {
+        if db.get_config_by_name(&entry.name)?.is_some() {
+            continue;
+        }
+        let mut config = Config::new(
+            &entry.name,
+            repo.join(&entry.source).to_string_lossy(),
+            expand_path(&entry.target).to_string_lossy(),
+        );
+        config.mode = entry.mode;
+        config.validate_command = entry.validate;
+        if let Some(tool) = &entry.tool {
+            config.tool_id = db.get_tool_by_name(tool)?.and_then(|t| t.id);
+        }
+        db.insert_config(&config)?;
+        println!("{} Added config '{}'", "+".green(), entry.name);
+        added += 1;
+    }
+    Ok(added)
+}
+
+/// Remember the repository in the hoards config
+fn save_config_repo(repo: &Path) -> Result<()> {
+    let mut settings = HoardConfig::load()?;
+    settings.dotfiles.repo = Some(repo.to_path_buf());
+    settings.save()
+}
+
+/// Create a config repository and move the config sources into it
+pub fn cmd_config_repo_init(db: &Database, path: Option<&str>) -> Result<()> {
+    let repo = expand_path(path.unwrap_or("~/dotfiles"));
+    fs::create_dir_all(&repo)?;
+    let repo = repo.canonicalize()?;
+    if !repo.join(".git").exists() {
+        git(&repo, &["init", "-q"])?;
+        println!(
+            "{} Initialized git repository in {}",
+            "✓".green(),
+            repo.display()
+        );
+    }
+
+    for config in db.list_configs()? {
+        let source = expand_path(&config.source_path);
+        let target = expand_path(&config.target_path);
+        if !source.exists() || source.starts_with(&repo) {
+            continue;
+        }
+        let file_name = source.file_name().unwrap_or(config.name.as_ref());
+        let dest = repo.join(file_name);
+        if dest.exists() {
+            println!(
+                "{} {} - {} already exists in the repository, skipped",
+                "!".yellow(),
+                config.name,
+                dest.display()
+            );
+            continue;
+        }
+
+        let relink = is_valid_symlink(&target, &source);
+        move_path(&source, &dest)?;
+        if relink {
+            fs::remove_file(&target)?;
+            symlink(&dest, &target)?;
+        }
+        db.update_config_paths(&config.name, &dest.to_string_lossy(), &config.target_path)?;
+        println!("{} {} → {}", ">".cyan(), config.name, dest.display());
+    }
+
+    save_config_repo(&repo)?;
+    match commit_repo(db, &repo) {
+        Ok(Some(message)) => println!("{} Committed: {}", "✓".green(), message),
+        Ok(None) => {}
+        Err(e) => println!("{} Nothing committed: {}", "!".yellow(), e),
+    }
+    println!(
+        "Add a remote with {} and run {}",
+        format!("git -C {} remote add origin <url>", repo.display()).cyan(),
+        "hoards config repo push".cyan()
+    );
+    Ok(())
+}
+
+/// Clone a config repository and link its configs on this machine
+pub fn cmd_config_repo_clone(db: &Database, url: &str, path: Option<&str>) -> Result<()> {
+    let repo = expand_path(path.unwrap_or("~/dotfiles"));
+    if repo.exists() && fs::read_dir(&repo)?.next().is_some() {
+        bail!("{} already exists and is not empty", repo.display());
+    }
+    let parent = repo.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)?;
+    git(parent, &["clone", "-q", url, &repo.to_string_lossy()])?;
+    let repo = repo.canonicalize()?;
+    println!("{} Cloned {} into {}", "✓".green(), url, repo.display());
+
+    save_config_repo(&repo)?;
+    import_manifest(db, &repo)?;
+    cmd_config_sync(db, false, false)
+}
+
+/// Commit local changes to the config repository and push them
+pub fn cmd_config_repo_push(db: &Database) -> Result<()> {
+    let repo = require_config_repo()?;
+    if let Some(message) = commit_repo(db, &repo)? {
+        println!("{} Committed: {}", "✓".green(), message);
+    }
+    git(&repo, &["push", "-q"])?;
+    println!("{} Pushed {}", "✓".green(), repo.display());
+    Ok(())
+}
+
+/// Pull the config repository and link any new configs
+pub fn cmd_config_repo_pull(db: &Database) -> Result<()> {
+    let repo = require_config_repo()?;
+    if let Some(message) = commit_repo(db, &repo)? {
+        println!("{} Committed local changes: {}", ">".cyan(), message);
+    }
+    git(&repo, &["pull", "-q", "--ff-only"])?;
+    println!("{} Pulled {}", "✓".green(), repo.display());
+    import_manifest(db, &repo)?;
+    cmd_config_sync(db, false, false)
+}
+
+/// Whether a target is a link to any of a config's sources
+fn links_to_variant(db: &Database, name: &str, target_path: &Path) -> Result<bool> {
+    if !target_path.is_symlink() {
+        return Ok(false);
+    }
+    let Some(config) = db.get_config_by_name(name)? else {
+        return Ok(false);
+    };
+    let mut sources = vec![config.source_path];
+    sources.extend(
+        db.list_config_variants(name)?
+            .into_iter()
+            .map(|v| v.source_path),
+    );
+    Ok(sources
+        .iter()
+        .any(|source| is_valid_symlink(target_path, &expand_path(source))))
+}
+
+/// Render a templated config into its target
+///
+/// Returns whether the target was (or would be) written. A target edited
+/// since the last render is only replaced with `force`, after a backup.
+fn sync_template(
+    db: &Database,
+    config: &Config,
+    source_path: &Path,
+    target_path: &Path,
+    vars: &TemplateVars,
+    dry_run: bool,
+    force: bool,
+) -> Result<bool> {
+    let rendered = render_source(source_path, vars)?;
+
+    match render_state(target_path, config.rendered_hash.as_deref(), &rendered) {
+        RenderState::Rendered => return Ok(false),
+        RenderState::Modified if !force => {
+            println!(
+                "{} {} - target changed since last render: {} (use --force to overwrite)",
+                "!".yellow(),
+                config.name,
+                target_path.display()
+            );
+            return Ok(false);
+        }
+        RenderState::Modified if dry_run => println!(
+            "{} {} - would back up existing: {}",
+            "!".yellow(),
+            config.name,
+            target_path.display()
+        ),
+        RenderState::Modified => {
+            let backup = backup_target(db, &config.name, target_path, &backups_root()?)?;
+            println!(
+                "{} {} - backed up to {}",
+                ">".cyan(),
+                config.name,
+                backup.display()
+            );
+        }
+        RenderState::Stale | RenderState::Missing => {}
+    }
+
+    if dry_run {
+        println!(
+            "{} {} ⇐ {} (render)",
+            "→".cyan(),
+            target_path.display(),
+            source_path.display()
+        );
+        return Ok(true);
+    }
+
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(target_path, &rendered)
+        .with_context(|| format!("Failed to write {}", target_path.display()))?;
+    db.set_config_rendered(&config.name, Some(&content_hash(rendered.as_bytes())))?;
+    println!(
+        "{} {} ⇐ {} (rendered)",
+        "✓".green(),
+        config.name,
+        target_path.display()
+    );
+    Ok(true)
+}
+
+/// Copy a config's source into place, like [`sync_template`] renders one
+fn sync_copy(
+    db: &Database,
+    config: &Config,
+    source_path: &Path,
+    target_path: &Path,
+    dry_run: bool,
+    force: bool,
+) -> Result<bool> {
+    // Left over from before the config switched to copy mode
+    let linked = is_valid_symlink(target_path, source_path);
+    let state = if linked {
+        RenderState::Missing
+    } else {
+        copy_state(source_path, target_path, config.rendered_hash.as_deref())?
+    };
+
+    match state {
+        RenderState::Rendered => return Ok(false),
+        RenderState::Modified if !force => {
+            println!(
+                "{} {} - target changed since last copy: {} (use --force to overwrite)",
+                "!".yellow(),
+                config.name,
+                target_path.display()
+            );
+            if !target_path.is_symlink() {
+                print_drift(
+                    source_path,
+                    target_path,
+                    &find_drift(source_path, target_path)?,
+                );
+            }
+            return Ok(false);
+        }
+        RenderState::Modified if dry_run => println!(
+            "{} {} - would back up existing: {}",
+            "!".yellow(),
+            config.name,
+            target_path.display()
+        ),
+        RenderState::Modified => {
+            let backup = backup_target(db, &config.name, target_path, &backups_root()?)?;
+            println!(
+                "{} {} - backed up to {}",
+                ">".cyan(),
+                config.name,
+                backup.display()
+            );
+        }
+        RenderState::Stale | RenderState::Missing => {}
+    }
+
+    if dry_run {
+        println!(
+            "{} {} ⇐ {} (copy)",
+            "→".cyan(),
+            target_path.display(),
+            source_path.display()
+        );
+        return Ok(true);
+    }
+
+    // Replace the old copy as a whole, so files deleted from the source go too
+    if linked || state == RenderState::Stale {
+        remove_path(target_path)?;
+    }
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    copy_recursive(source_path, target_path)?;
+    db.set_config_rendered(&config.name, Some(&tree_hash(target_path)?))?;
+    println!(
+        "{} {} ⇐ {} (copied)",
+        "✓".green(),
+        config.name,
+        target_path.display()
+    );
+    Ok(true)
+}
+
+/// (intact, total) configs per tool id, for health scores
+///
+/// A config is intact when its target is linked, or a current copy or
+/// render, using the variant this machine picks.
+pub(crate) fn config_health(db: &Database) -> Result<HashMap<i64, (usize, usize)>> {
+    let vars = TemplateVars::new(load_template_vars);
+    let mut health: HashMap<i64, (usize, usize)> = HashMap::new();
+    for config in resolve_variants(db, db.list_configs()?)? {
+        let Some(tool_id) = config.tool_id else {
+            continue;
+        };
+        let entry = health.entry(tool_id).or_default();
+        if matches!(config_status(&config, &vars), "linked" | "copy") {
+            entry.0 += 1;
+        }
+        entry.1 += 1;
+    }
+    Ok(health)
+}
+
+/// Show status of all config symlinks
+pub fn cmd_config_status(db: &Database) -> Result<()> {
+    let configs = with_variants(db, db.list_configs()?)?;
+
+    if configs.is_empty() {
+        println!("No configs managed.");
+        return Ok(());
+    }
+
+    let mut linked = 0;
+    let mut unlinked = 0;
+    let mut broken = 0;
+    let mut conflicts = 0;
+    let mut stale = 0;
+
+    println!("{}", "Config Status".bold());
+    let (profile, from) = active_profile(&HoardConfig::load().unwrap_or_default().dotfiles);
+    println!(
+        "{}",
+        format!("Profile: {} (from {})", profile, from).dimmed()
+    );
+    println!();
+
+    let vars = TemplateVars::new(load_template_vars);
+    for (config, variant) in &configs {
+        let (icon, status) = match config_status(config, &vars) {
+            "missing" => {
+                broken += 1;
+                ("✗".red(), "source missing".red())
+            }
+            "invalid" => {
+                broken += 1;
+                ("✗".red(), "template error".red())
+            }
+            "linked" if config.mode == ConfigMode::Copy => {
+                linked += 1;
+                ("✓".green(), "copied".green())
+            }
+            "linked" => {
+                linked += 1;
+                ("✓".green(), "linked".green())
+            }
+            "copy" => {
+                linked += 1;
+                ("=".green(), "copy, in sync".green())
+            }
+            "stale"
+                if config.mode == ConfigMode::Copy
+                    && !is_template(&expand_path(&config.source_path)) =>
+            {
+                stale += 1;
+                ("↻".yellow(), "stale copy".yellow())
+            }
+            "stale" => {
+                stale += 1;
+                ("↻".yellow(), "stale render".yellow())
+            }
+            "conflict" => {
+                conflicts += 1;
+                ("!".yellow(), "conflict (see config diff)".yellow())
+            }
+            _ => {
+                unlinked += 1;
+                ("○".dimmed(), "not linked".dimmed())
+            }
+        };
+
+        match variant {
+            Some(profile) => println!(
+                "  {} {:<20} {} {}",
+                icon,
+                config.name,
+                status,
+                format!("[{}]", profile).dimmed()
+            ),
+            None => println!("  {} {:<20} {}", icon, config.name, status),
+        }
+    }
+
+    println!();
+    println!(
+        "Total: {} configs ({} linked, {} unlinked, {} stale, {} conflicts, {} broken)",
+        configs.len(),
+        linked,
+        unlinked,
+        stale,
+        conflicts,
+        broken
+    );
+
+    if unlinked > 0 || stale > 0 || conflicts > 0 {
+        println!();
+        println!(
+            "Run {} to create missing symlinks",
+            "hoards config sync".cyan()
+        );
+    }
 
     Ok(())
 }
 
+/// How long to wait for a burst of filesystem events to settle
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Nearest existing directory above a target, where its changes show up
+fn watch_dir(target: &Path) -> Option<PathBuf> {
+    target
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// What happened to a watched config since the last check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchChange {
+    /// The target points at its source again
+    Relinked,
+    /// The target was deleted
+    Removed,
+    /// Something else took the target's place
+    Replaced,
+    SourceMissing,
+}
+
+/// Classify a config's status change between two checks
+///
+/// None when there's nothing to report: the status didn't change, the
+/// config was linked from the start, or only its source side changed
+/// (stale or broken templates are left to sync).
+fn classify_change(previous: Option<&str>, status: &str) -> Option<WatchChange> {
+    if previous == Some(status) {
+        return None;
+    }
+    match status {
+        "linked" if previous.is_some() => Some(WatchChange::Relinked),
+        "unlinked" => Some(WatchChange::Removed),
+        "conflict" | "copy" => Some(WatchChange::Replaced),
+        "missing" => Some(WatchChange::SourceMissing),
+        _ => None,
+    }
+}
+
+/// Report (and with `repair`, fix) configs whose status changed since the last check
+fn check_watched(
+    db: &Database,
+    configs: &[Config],
+    vars: &TemplateVars,
+    repair: bool,
+    last: &mut HashMap<String, &'static str>,
+) {
+    for config in configs {
+        let status = config_status(config, vars);
+        let previous = last.insert(config.name.clone(), status);
+        let Some(change) = classify_change(previous, status) else {
+            continue;
+        };
+
+        let time = Local::now().format("%H:%M:%S").to_string();
+        let target_path = expand_path(&config.target_path);
+        match change {
+            WatchChange::Relinked => {
+                println!(
+                    "{} {} {} - linked again",
+                    time.dimmed(),
+                    "✓".green(),
+                    config.name
+                );
+            }
+            WatchChange::Removed | WatchChange::Replaced => {
+                let what = if change == WatchChange::Removed {
+                    "removed"
+                } else {
+                    "replaced"
+                };
+                println!(
+                    "{} {} {} - target {}: {}",
+                    time.dimmed(),
+                    "✗".red(),
+                    config.name,
+                    what,
+                    target_path.display()
+                );
+                if repair {
+                    match sync_one(db, config, vars, false, true) {
+                        Ok(SyncOutcome::Created) => {
+                            last.insert(config.name.clone(), "linked");
+                        }
+                        Ok(_) => {}
+                        Err(e) => println!("{} {} - {}", "✗".red(), config.name, e),
+                    }
+                }
+            }
+            WatchChange::SourceMissing => {
+                println!(
+                    "{} {} {} - source missing: {}",
+                    time.dimmed(),
+                    "✗".red(),
+                    config.name,
+                    expand_path(&config.source_path).display()
+                );
+            }
+        }
+    }
+}
+
+/// Watch linked configs for targets that get replaced or deleted
+///
+/// Installers and package upgrades often overwrite a symlinked dotfile with
+/// a fresh copy. Each change is reported as it happens; with `repair`, the
+/// target is re-linked straight away (a replaced file is backed up first).
+pub fn cmd_config_watch(db: &Database, repair: bool) -> Result<()> {
+    let vars = TemplateVars::new(load_template_vars);
+    let configs: Vec<Config> = resolve_variants(db, db.list_configs()?)?
+        .into_iter()
+        .filter(|config| config.is_symlinked || config_status(config, &vars) == "linked")
+        .collect();
+
+    if configs.is_empty() {
+        println!("No linked configs to watch. Run 'hoards config sync' first.");
+        return Ok(());
+    }
+
+    let targets: Vec<PathBuf> = configs
+        .iter()
+        .map(|config| expand_path(&config.target_path))
+        .collect();
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+    let mut watched: HashSet<PathBuf> = HashSet::new();
+    let mut last = HashMap::new();
+
+    println!(
+        "Watching {} config(s){}. Press Ctrl+C to stop.",
+        configs.len(),
+        if repair {
+            ", re-linking broken targets"
+        } else {
+            ""
+        }
+    );
+
+    loop {
+        // A target's directory may have been removed or created since the last check
+        watched.retain(|dir| dir.is_dir());
+        for target in &targets {
+            if let Some(dir) = watch_dir(target)
+                && !watched.contains(&dir)
+            {
+                watcher
+                    .watch(&dir, RecursiveMode::NonRecursive)
+                    .with_context(|| format!("Failed to watch {}", dir.display()))?;
+                watched.insert(dir);
+            }
+        }
+
+        check_watched(db, &configs, &vars, repair, &mut last);
+
+        // Wait for a change touching a target, then let the burst settle
+        loop {
+            let Ok(event) = rx.recv() else {
+                return Ok(());
+            };
+            let relevant = event.is_ok_and(|event: Event| {
+                event
+                    .paths
+                    .iter()
+                    .any(|path| targets.iter().any(|target| target.starts_with(path)))
+            });
+            if relevant {
+                break;
+            }
+        }
+        while rx.recv_timeout(WATCH_DEBOUNCE).is_ok() {}
+    }
+}
+
+/// Print a unified diff with added and removed lines colored
+fn print_diff(diff: &str) {
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            println!("{}", line.bold());
+        } else if line.starts_with('+') {
+            println!("{}", line.green());
+        } else if line.starts_with('-') {
+            println!("{}", line.red());
+        } else if line.starts_with("@@") {
+            println!("{}", line.cyan());
+        } else {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Print each difference between a source and its drifted target
+fn print_drift(source_path: &Path, target_path: &Path, drift: &[Drift]) {
+    for item in drift {
+        match item {
+            Drift::Changed {
+                path,
+                source,
+                target,
+            } => {
+                let old_name = drift_path(source_path, path).display().to_string();
+                let new_name = drift_path(target_path, path).display().to_string();
+                match unified_diff(source, target, &old_name, &new_name) {
+                    Some(diff) => print_diff(&diff),
+                    None => println!("Binary files {} and {} differ", old_name, new_name),
+                }
+            }
+            Drift::OnlyInSource(path) => println!(
+                "{} Only in source: {}",
+                "-".red(),
+                drift_path(source_path, path).display()
+            ),
+            Drift::OnlyInTarget(path) => println!(
+                "{} Only in target: {}",
+                "+".green(),
+                drift_path(target_path, path).display()
+            ),
+        }
+    }
+}
+
+/// Copy changed and added files from a target back into its source
+fn adopt_drift(source: &Path, target: &Path, drift: &[Drift]) -> Result<usize> {
+    let mut adopted = 0;
+    for item in drift {
+        let (Drift::Changed { path, .. } | Drift::OnlyInTarget(path)) = item else {
+            continue;
+        };
+        let dest = drift_path(source, path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(drift_path(target, path), &dest)
+            .with_context(|| format!("Failed to update {}", dest.display()))?;
+        adopted += 1;
+    }
+    Ok(adopted)
+}
+
+/// Show how live targets differ from their sources
+///
+/// Only targets that are real files (copies, or links replaced by an
+/// editor or installer) can drift. Their changes can be adopted back into
+/// the source, with `adopt` or after a prompt.
+pub fn cmd_config_diff(db: &Database, name: Option<&str>, adopt: bool) -> Result<()> {
+    let configs = match name {
+        Some(name) => vec![
+            db.get_config_by_name(name)?
+                .ok_or_else(|| anyhow::anyhow!("Config '{}' not found", name))?,
+        ],
+        None => db.list_configs()?,
+    };
+    let configs = resolve_variants(db, configs)?;
+    let interactive = !adopt && std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    let vars = TemplateVars::new(load_template_vars);
+    let mut drifted = 0;
+
+    for config in &configs {
+        let target_path = expand_path(&config.target_path);
+        let source_path = expand_path(&config.source_path);
+        if !source_path.exists()
+            || !target_path.exists()
+            || is_valid_symlink(&target_path, &source_path)
+        {
+            continue;
+        }
+
+        if is_template(&source_path) {
+            let rendered = match render_source(&source_path, &vars) {
+                Ok(rendered) => rendered,
+                Err(e) => {
+                    println!("{} {} - {}", "✗".red(), config.name, e);
+                    continue;
+                }
+            };
+            let Ok(current) = fs::read(&target_path) else {
+                continue;
+            };
+            if current == rendered.as_bytes() {
+                continue;
+            }
+            drifted += 1;
+            println!("{} {}", "diff".bold(), config.name.cyan());
+            let old_name = format!("{} (rendered)", source_path.display());
+            let new_name = target_path.display().to_string();
+            match unified_diff(rendered.as_bytes(), &current, &old_name, &new_name) {
+                Some(diff) => print_diff(&diff),
+                None => println!("Binary files {} and {} differ", old_name, new_name),
+            }
+            println!(
+                "{} Templates can't adopt changes; edit {}",
+                "!".yellow(),
+                source_path.display()
+            );
+            println!();
+            continue;
+        }
+
+        if target_path.is_symlink() {
+            println!(
+                "{} {} - target links elsewhere: {}",
+                "!".yellow(),
+                config.name,
+                target_path.display()
+            );
+            continue;
+        }
+
+        let drift = find_drift(&source_path, &target_path)?;
+        if drift.is_empty() {
+            continue;
+        }
+        drifted += 1;
+
+        println!("{} {}", "diff".bold(), config.name.cyan());
+        print_drift(&source_path, &target_path, &drift);
+
+        let take = adopt
+            || (interactive
+                && dialoguer::Confirm::new()
+                    .with_prompt(format!(
+                        "Adopt local changes to '{}' into the source?",
+                        config.name
+                    ))
+                    .default(false)
+                    .interact()?);
+        if take {
+            let adopted = adopt_drift(&source_path, &target_path, &drift)?;
+            println!(
+                "{} Adopted {} file(s) into {}",
+                "✓".green(),
+                adopted,
+                source_path.display()
+            );
+        }
+        println!();
+    }
+
+    if drifted == 0 {
+        println!("{} Targets match their sources", "✓".green());
+    }
+    Ok(())
+}
+
+/// chezmoi's source directory, asking chezmoi first
+fn chezmoi_source_dir() -> Result<PathBuf> {
+    if let Ok(output) = std::process::Command::new("chezmoi")
+        .arg("source-path")
+        .output()
+        && output.status.success()
+    {
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !path.is_empty() {
+            return Ok(PathBuf::from(path));
+        }
+    }
+    dirs::data_dir()
+        .map(|d| d.join("chezmoi"))
+        .context("Could not determine chezmoi source directory")
+}
+
+/// Register the configs of an existing stow or chezmoi setup in place
+///
+/// Nothing is moved or linked; each config is matched to a tracked tool by
+/// its name where possible.
+pub fn cmd_config_import(
+    db: &Database,
+    stow: Option<&str>,
+    chezmoi: Option<&str>,
+    target: Option<&str>,
+    dry_run: bool,
+) -> Result<()> {
+    let (manager, found): (&str, Vec<ImportedConfig>) = match (stow, chezmoi) {
+        (Some(dir), _) => {
+            let dir = expand_path(dir).canonicalize()?;
+            // Stow links into the parent of the stow directory by default
+            let target = match target {
+                Some(target) => expand_path(target),
+                None => dir.parent().unwrap_or(Path::new("/")).to_path_buf(),
+            };
+            ("stow", stow_configs(&dir, &target)?)
+        }
+        (None, Some(dir)) => {
+            let dir = if dir.is_empty() {
+                chezmoi_source_dir()?
+            } else {
+                expand_path(dir)
+            };
+            let home = match target {
+                Some(target) => expand_path(target),
+                None => dirs::home_dir().context("Could not determine home directory")?,
+            };
+            ("chezmoi", chezmoi_configs(&dir, &home)?)
+        }
+        (None, None) => bail!("Specify --stow <dir> or --chezmoi"),
+    };
+
+    if found.is_empty() {
+        println!("No configs found in the {} layout.", manager);
+        return Ok(());
+    }
+
+    let tools = db.list_tools(false, None)?;
+    let existing = db.list_configs()?;
+    let mut added = 0;
+    let mut skipped = 0;
+
+    for item in found {
+        let target = item.target.to_string_lossy();
+        if existing
+            .iter()
+            .any(|c| c.name == item.name || c.target_path == target)
+        {
+            skipped += 1;
+            continue;
+        }
+
+        let source_name = item
+            .source
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let tool = tool_candidates(&item.name)
+            .into_iter()
+            .chain(tool_candidates(&source_name))
+            .find_map(|candidate| {
+                tools.iter().find(|t| {
+                    t.name == candidate || t.binary_name.as_deref() == Some(candidate.as_str())
+                })
+            });
+
+        let mut config = Config::new(&item.name, item.source.to_string_lossy(), target.clone());
+        config.tool_id = tool.and_then(|t| t.id);
+        config.is_symlinked = is_valid_symlink(&item.target, &item.source);
+
+        let tool_note = tool.map(|t| format!(" ({})", t.name)).unwrap_or_default();
+        if dry_run {
+            println!("  Would add {}{} → {}", item.name, tool_note, target);
+        } else {
+            db.insert_config(&config)?;
+            println!(
+                "{} {}{} → {}",
+                "+".green(),
+                item.name,
+                tool_note.dimmed(),
+                target
+            );
+        }
+        added += 1;
+    }
+
+    println!();
+    println!(
+        "{} {} configs from {}, {} already managed",
+        if dry_run { "Would import" } else { "Imported" },
+        added,
+        manager,
+        skipped
+    );
+    if !dry_run && added > 0 {
+        println!(
+            "Files stay where they are; {} shows how they line up",
+            "hoards config status".cyan()
+        );
+    }
+    Ok(())
+}
+
+/// Undo a link: put back the file `config sync --force` moved aside
+pub fn cmd_config_restore(db: &Database, name: &str, list: bool, force: bool) -> Result<()> {
+    if list {
+        let backups = db.list_config_backups(name)?;
+        if backups.is_empty() {
+            println!("No backups for '{}'", name);
+            return Ok(());
+        }
+        println!("{} {}", "Backups of".bold(), name.cyan());
+        for backup in backups {
+            let when = chrono::DateTime::parse_from_rfc3339(&backup.created_at)
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or(backup.created_at);
+            println!("  {}  {}", when.dimmed(), backup.backup_path);
+        }
+        return Ok(());
+    }
+
+    match restore_backup(db, name, force)? {
+        Some(path) => {
+            if db.get_config_by_name(name)?.is_some() {
+                db.set_config_symlinked(name, false)?;
+            }
+            println!("{} Restored {}", "✓".green(), path.display());
+        }
+        None => println!("{} No backup to restore for '{}'", "!".yellow(), name),
+    }
+    Ok(())
+}
+
 /// Edit a config's paths
 pub fn cmd_config_edit(
     db: &Database,
@@ -445,6 +1944,8 @@ pub fn cmd_config_edit(
     target: Option<String>,
     source: Option<String>,
     tool: Option<String>,
+    mode: Option<ConfigMode>,
+    validate: Option<String>,
 ) -> Result<()> {
     let config = db
         .get_config_by_name(name)?
@@ -471,6 +1972,24 @@ pub fn cmd_config_edit(
         println!("{} Linked config to tool '{}'", "✓".green(), tool_name);
     }
 
+    if let Some(mode) = mode
+        && mode != config.mode
+    {
+        db.set_config_mode(name, mode)?;
+        println!("{} Set mode of '{}' to {}", "✓".green(), name, mode);
+        println!("Run {} to apply it", "hoards config sync".cyan());
+    }
+
+    // An empty command clears it
+    if let Some(command) = validate {
+        let command = Some(command.trim()).filter(|c| !c.is_empty());
+        db.set_config_validate(name, command)?;
+        match command {
+            Some(command) => println!("{} Validate '{}' with: {}", "✓".green(), name, command),
+            None => println!("{} Removed validation of '{}'", "✓".green(), name),
+        }
+    }
+
     Ok(())
 }
 
@@ -508,7 +2027,7 @@ mod tests {
         fs::write(&source, "test").unwrap();
 
         // Create symlink
-        unix_fs::symlink(&source, &link).unwrap();
+        symlink(&source, &link).unwrap();
 
         assert!(is_valid_symlink(&link, &source));
         assert!(!is_valid_symlink(&source, &link)); // source is not a symlink
@@ -523,8 +2042,90 @@ mod tests {
 
         fs::write(&source, "test").unwrap();
         fs::write(&other, "other").unwrap();
-        unix_fs::symlink(&source, &link).unwrap();
+        symlink(&source, &link).unwrap();
 
         assert!(!is_valid_symlink(&link, &other));
     }
+
+    #[test]
+    fn test_classify_change() {
+        use WatchChange::*;
+        // The first check only records the state
+        assert_eq!(classify_change(None, "linked"), None);
+        assert_eq!(classify_change(Some("linked"), "linked"), None);
+        assert_eq!(classify_change(Some("linked"), "unlinked"), Some(Removed));
+        assert_eq!(classify_change(None, "unlinked"), Some(Removed));
+        assert_eq!(classify_change(Some("linked"), "conflict"), Some(Replaced));
+        assert_eq!(classify_change(Some("linked"), "copy"), Some(Replaced));
+        assert_eq!(classify_change(Some("unlinked"), "linked"), Some(Relinked));
+        assert_eq!(
+            classify_change(Some("linked"), "missing"),
+            Some(SourceMissing)
+        );
+        assert_eq!(classify_change(Some("linked"), "stale"), None);
+    }
+
+    #[test]
+    fn test_check_watched_relinks_removed_target() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        let source = temp.path().join("source.conf");
+        let target = temp.path().join("target.conf");
+        fs::write(&source, "set -g mouse on").unwrap();
+        symlink(&source, &target).unwrap();
+        let mut config = Config::new("tmux", source.to_string_lossy(), target.to_string_lossy());
+        config.is_symlinked = true;
+        db.insert_config(&config).unwrap();
+
+        let configs = vec![config];
+        let vars = TemplateVars::new(BTreeMap::new);
+        let mut last = HashMap::new();
+        check_watched(&db, &configs, &vars, true, &mut last);
+        assert_eq!(last["tmux"], "linked");
+
+        // Without repair a deletion is only reported
+        fs::remove_file(&target).unwrap();
+        check_watched(&db, &configs, &vars, false, &mut last);
+        assert_eq!(last["tmux"], "unlinked");
+        assert!(!target.is_symlink());
+
+        // Still gone on the next event, so it's re-linked
+        last.insert("tmux".to_string(), "linked");
+        check_watched(&db, &configs, &vars, true, &mut last);
+        assert!(is_valid_symlink(&target, &source));
+        assert_eq!(last["tmux"], "linked");
+    }
+
+    #[test]
+    fn test_backup_and_restore_target() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::open_in_memory().unwrap();
+        let target = temp.path().join("nvim");
+        let root = temp.path().join("backups");
+        fs::create_dir(&target).unwrap();
+        fs::write(target.join("init.lua"), "-- mine").unwrap();
+
+        let backup = backup_target(&db, "nvim", &target, &root).unwrap();
+        assert!(!target.exists());
+        assert!(backup.starts_with(root.join("nvim")));
+        assert_eq!(
+            fs::read_to_string(backup.join("init.lua")).unwrap(),
+            "-- mine"
+        );
+
+        // A link in its place is replaced without --force
+        let source = temp.path().join("source");
+        fs::create_dir(&source).unwrap();
+        symlink(&source, &target).unwrap();
+
+        let restored = restore_backup(&db, "nvim", false).unwrap();
+        assert_eq!(restored, Some(target.clone()));
+        assert!(!target.is_symlink());
+        assert_eq!(
+            fs::read_to_string(target.join("init.lua")).unwrap(),
+            "-- mine"
+        );
+        assert!(db.list_config_backups("nvim").unwrap().is_empty());
+        assert_eq!(restore_backup(&db, "nvim", false).unwrap(), None);
+    }
 }
@@ -1,14 +1,15 @@
 //! Core commands: add, list, search, show, remove
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
 use comfy_table::{
     Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
 };
 
-use crate::db::Database;
-use crate::icons::{category_icon, print_legend_compact, source_icon, status_icon};
-use crate::models::{InstallSource, Tool};
+use crate::db::{Database, RelationKind};
+use crate::health::HealthGrade;
+use crate::icons::{self, category_icon, print_legend_compact, source_icon, status_icon};
+use crate::models::{InstallSource, Tool, ToolKind};
 
 /// Add a new tool to the database
 #[allow(clippy::too_many_arguments)]
@@ -61,14 +62,18 @@ pub fn cmd_list(
     installed_only: bool,
     category: Option<String>,
     label: Option<String>,
+    libraries: bool,
     format: &str,
 ) -> Result<()> {
     // If filtering by label, use the label-specific query
-    let tools = if let Some(lbl) = &label {
+    let mut tools = if let Some(lbl) = &label {
         db.list_tools_by_label(lbl)?
     } else {
         db.list_tools(installed_only, category.as_deref())?
     };
+    if !libraries {
+        tools.retain(|t| t.kind != ToolKind::Library);
+    }
 
     if tools.is_empty() {
         println!("No tools found");
@@ -84,6 +89,9 @@ pub fn cmd_list(
                 .map(|(w, _)| w.0)
                 .unwrap_or(120);
 
+            let pins = db.get_pins()?;
+            let health = db.get_tool_health()?;
+
             let mut table = Table::new();
             table
                 .load_preset(UTF8_FULL)
@@ -95,6 +103,7 @@ pub fn cmd_list(
                     Cell::new("Cat").fg(Color::Cyan),
                     Cell::new("Src").fg(Color::Cyan),
                     Cell::new("✓").fg(Color::Cyan),
+                    Cell::new("Health").fg(Color::Cyan),
                     Cell::new("Description").fg(Color::Cyan),
                 ]);
 
@@ -111,13 +120,29 @@ pub fn cmd_list(
                     Cell::new(status_icon(false)).fg(Color::Red)
                 };
 
+                let health_cell = match health.get(&tool.name) {
+                    Some(h) => Cell::new(h.score).fg(match h.grade() {
+                        HealthGrade::Good => Color::Green,
+                        HealthGrade::Fair => Color::Yellow,
+                        HealthGrade::Poor => Color::Red,
+                    }),
+                    None => Cell::new("-").fg(Color::DarkGrey),
+                };
+
                 let desc = tool.description.as_deref().unwrap_or("");
 
+                let name_cell = if pins.contains_key(&tool.name) {
+                    Cell::new(format!("{} {}", tool.name, icons::pin()))
+                } else {
+                    Cell::new(&tool.name)
+                };
+
                 table.add_row(vec![
-                    Cell::new(&tool.name),
+                    name_cell,
                     Cell::new(cat_display),
                     Cell::new(src_display),
                     status_cell,
+                    health_cell,
                     Cell::new(desc),
                 ]);
             }
@@ -132,9 +157,14 @@ pub fn cmd_list(
 }
 
 /// Search for tools
-pub fn cmd_search(db: &Database, query: &str) -> Result<()> {
+pub fn cmd_search(db: &Database, query: &str, json_output: bool) -> Result<()> {
     let tools = db.search_tools(query)?;
 
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&tools)?);
+        return Ok(());
+    }
+
     if tools.is_empty() {
         println!("No tools found matching '{}'", query);
         return Ok(());
@@ -164,7 +194,26 @@ pub fn cmd_search(db: &Database, query: &str) -> Result<()> {
 }
 
 /// Show details of a specific tool
-pub fn cmd_show(db: &Database, name: &str) -> Result<()> {
+pub fn cmd_show(db: &Database, name: &str, json_output: bool) -> Result<()> {
+    if json_output {
+        let details = match db.get_tool_by_name(name)? {
+            Some(tool) => serde_json::json!({
+                "labels": db.get_labels(&tool.name)?,
+                "release": db.get_release_install(&tool.name)?,
+                "repository": db.get_github_info(&tool.name)?,
+                "usage": db.get_usage(&tool.name)?,
+                "relations": db.get_relations(&tool.name)?,
+                "tool": tool,
+            }),
+            None => {
+                println!("null");
+                crate::fail!(NotFound, "Tool '{}' not found", name);
+            }
+        };
+        println!("{}", serde_json::to_string_pretty(&details)?);
+        return Ok(());
+    }
+
     match db.get_tool_by_name(name)? {
         Some(tool) => {
             println!("{}", tool.name.bold());
@@ -188,19 +237,82 @@ pub fn cmd_show(db: &Database, name: &str) -> Result<()> {
             };
             println!("{}: {}", "Status".bold(), status);
 
+            if let Some(pin) = db.get_pins()?.remove(&tool.name) {
+                println!(
+                    "{}: {}",
+                    "Pinned".bold(),
+                    pin.as_deref().unwrap_or("installed version").yellow()
+                );
+            }
+
+            if let Ok(Some(install)) = db.get_release_install(&tool.name) {
+                println!(
+                    "{}: {} ({})",
+                    "Version".bold(),
+                    install.tag,
+                    install.path.dimmed()
+                );
+            } else if let Some(version) = &tool.installed_version {
+                println!("{}: {}", "Version".bold(), version);
+            }
+
             if let Some(bin) = &tool.binary_name {
                 println!("{}: {}", "Binary".bold(), bin);
             }
 
+            if let Some(guidance) = crate::runtimes::upgrade_guidance(&tool) {
+                println!("{}: {} ({})", "Upgrade".bold(), guidance, tool.kind);
+            }
+
             if let Some(cmd) = &tool.install_command {
                 println!("{}: {}", "Install".bold(), cmd);
             }
 
+            if let Some(license) = &tool.license {
+                let kind = crate::models::LicenseKind::of(license);
+                let license = match kind {
+                    crate::models::LicenseKind::Copyleft => license.yellow(),
+                    _ => license.normal(),
+                };
+                println!(
+                    "{}: {} ({})",
+                    "License".bold(),
+                    license,
+                    kind.label().dimmed()
+                );
+            }
+
             // Show GitHub info if available
             if let Ok(Some(gh_info)) = db.get_github_info(&tool.name) {
-                println!("\n{}", "GitHub:".bold());
-                println!("  Repo: {}/{}", gh_info.repo_owner, gh_info.repo_name);
+                if gh_info.is_github() {
+                    println!("\n{}", "GitHub:".bold());
+                    println!("  Repo: {}/{}", gh_info.repo_owner, gh_info.repo_name);
+                } else {
+                    println!("\n{}", "Repository:".bold());
+                    println!("  Repo: {}", gh_info.repo_ref().url());
+                }
                 println!("  Stars: {}", gh_info.stars.to_string().yellow());
+                if let (Some(pushed), Some(days)) = (&gh_info.pushed_at, gh_info.days_since_push())
+                {
+                    println!(
+                        "  Last commit: {} ({} days ago)",
+                        pushed.get(..10).unwrap_or(pushed),
+                        days
+                    );
+                }
+                if let Some(issues) = gh_info.open_issues {
+                    println!("  Open issues: {}", issues);
+                }
+                if let Some(contributors) = gh_info.contributors {
+                    println!("  Contributors: {}", contributors);
+                }
+                if let Some(reason) = gh_info.abandonment() {
+                    println!(
+                        "  {} Upstream looks abandoned: {}",
+                        "!".yellow(),
+                        reason.yellow()
+                    );
+                }
             }
 
             // Show usage if available
@@ -214,6 +326,43 @@ pub fn cmd_show(db: &Database, name: &str) -> Result<()> {
                 );
             }
 
+            // Show replacement (e.g. from 'ai dedupe') and dependency relationships
+            if let Ok(relations) = db.get_relations(&tool.name) {
+                let names = |kind: RelationKind, outgoing: bool| -> Vec<&str> {
+                    relations
+                        .iter()
+                        .filter(|r| r.kind == kind && (r.tool == tool.name) == outgoing)
+                        .map(|r| {
+                            if outgoing {
+                                r.related.as_str()
+                            } else {
+                                r.tool.as_str()
+                            }
+                        })
+                        .collect()
+                };
+                let replaces = names(RelationKind::Replaces, true);
+                if !replaces.is_empty() {
+                    println!("\n{}: {}", "Replaces".bold(), replaces.join(", "));
+                }
+                let replaced_by = names(RelationKind::Replaces, false);
+                if !replaced_by.is_empty() {
+                    println!(
+                        "\n{}: {}",
+                        "Replaced by".bold(),
+                        replaced_by.join(", ").yellow()
+                    );
+                }
+                let depends_on = names(RelationKind::DependsOn, true);
+                if !depends_on.is_empty() {
+                    println!("\n{}: {}", "Depends on".bold(), depends_on.join(", "));
+                }
+                let required_by = names(RelationKind::DependsOn, false);
+                if !required_by.is_empty() {
+                    println!("\n{}: {}", "Required by".bold(), required_by.join(", "));
+                }
+            }
+
             if let Some(notes) = &tool.notes {
                 println!("\n{}", "Notes:".bold());
                 println!("{}", notes);
@@ -225,9 +374,207 @@ pub fn cmd_show(db: &Database, name: &str) -> Result<()> {
                 tool.created_at.format("%Y-%m-%d %H:%M")
             );
         }
-        None => {
-            println!("Tool '{}' not found", name);
+        None => crate::fail!(NotFound, "Tool '{}' not found", name),
+    }
+
+    Ok(())
+}
+
+/// Open a tool's homepage, repository, docs or registry page in the browser
+///
+/// `page` is "repo", "docs" or "registry"; without one the homepage is
+/// preferred, then the repository, then the registry page. With
+/// `print_only` the URL is printed instead.
+pub fn cmd_open(db: &Database, name: &str, page: Option<&str>, print_only: bool) -> Result<()> {
+    let Some(tool) = db.get_tool_by_name(name)? else {
+        anyhow::bail!("Tool '{}' not found", name);
+    };
+    let info = db.get_github_info(&tool.name)?;
+    let repo = info.as_ref().map(|info| info.repo_ref().url());
+    let homepage = info
+        .as_ref()
+        .and_then(|info| info.homepage.clone())
+        .filter(|url| !url.trim().is_empty());
+    let registry = crate::sources::source_for(&tool.source).and_then(|source| {
+        let package = tool
+            .install_command
+            .as_deref()
+            .and_then(super::helpers::extract_package_from_install_cmd)
+            .unwrap_or_else(|| tool.name.clone());
+        source.registry_url(&package)
+    });
+
+    let url = match page {
+        Some("repo") => repo,
+        Some("docs") => homepage.or_else(|| repo.map(|url| format!("{}#readme", url))),
+        Some("registry") => registry,
+        _ => homepage.or(repo).or(registry),
+    };
+    let Some(url) = url else {
+        println!(
+            "{} No {} known for '{}'",
+            "!".yellow(),
+            match page {
+                Some("registry") => "registry page",
+                Some("docs") => "docs",
+                Some(_) => "repository",
+                None => "web page",
+            },
+            tool.name
+        );
+        if page != Some("registry") {
+            println!(
+                "  Fetch repository info with: {}",
+                format!("hoards gh fetch {}", tool.name).cyan()
+            );
+        }
+        return Ok(());
+    };
+
+    if print_only {
+        println!("{}", url);
+        return Ok(());
+    }
+    println!("{} Opening {}", ">".cyan(), url);
+    crate::tui::ExternalCommand::browser(&url).run()
+}
+
+/// Version without a leading `v`, Debian epoch or revision
+fn version_core(version: &str) -> &str {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split_once(':').map_or(version, |(_, rest)| rest);
+    version.split(['-', '+', '~']).next().unwrap_or(version)
+}
+
+/// Show where a tool's binary resolves and how that compares with what
+/// hoards and the package manager recorded
+///
+/// `name` may also be a binary name (`rg` for ripgrep).
+pub fn cmd_which(db: &Database, name: &str) -> Result<()> {
+    let tool = match db.get_tool_by_name(name)? {
+        Some(tool) => tool,
+        None => db
+            .get_all_tools()?
+            .into_iter()
+            .find(|t| t.binary_name.as_deref() == Some(name))
+            .with_context(|| format!("Tool '{}' not found", name))?,
+    };
+    let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+    let source = tool.source.to_string();
+
+    // The same file can be on PATH twice (/bin -> /usr/bin); list it once
+    let mut seen = std::collections::HashSet::new();
+    let paths: Vec<_> = crate::scanner::binary_paths(binary)
+        .into_iter()
+        .filter(|path| seen.insert(std::fs::canonicalize(path).unwrap_or_else(|_| path.clone())))
+        .collect();
+    let release = db.get_release_install(&tool.name)?;
+
+    println!("{}", tool.name.bold());
+    println!("{}", "=".repeat(tool.name.len()));
+    println!("{}: {}", "Binary".bold(), binary);
+
+    // What was installed, according to hoards or the package manager
+    let installed_version = match &release {
+        Some(install) => Some(install.tag.clone()),
+        None => crate::updates::get_installed_version(&tool.name, &source),
+    };
+    println!(
+        "{}: {}{}",
+        "Recorded".bold(),
+        source,
+        installed_version
+            .as_deref()
+            .map(|v| format!(" {}", v))
+            .unwrap_or_default()
+    );
+
+    let Some(resolved) = paths.first() else {
+        println!("{}: {}", "Resolved".bold(), "not on PATH".red());
+        if tool.is_installed {
+            println!(
+                "\n{} Recorded as installed; run {} to update the status",
+                "!".yellow(),
+                "hoards sync".cyan()
+            );
         }
+        return Ok(());
+    };
+
+    let origin = |path: &std::path::Path| match crate::scanner::source_of_path(path) {
+        _ if release
+            .as_ref()
+            .is_some_and(|r| path == std::path::Path::new(&r.path)) =>
+        {
+            Some(InstallSource::GitHub)
+        }
+        origin => origin,
+    };
+    let describe = |path: &std::path::Path| match origin(path) {
+        Some(origin) => format!("{} ({})", path.display(), origin),
+        None => path.display().to_string(),
+    };
+
+    println!("{}: {}", "Resolved".bold(), describe(resolved));
+    if let Ok(target) = std::fs::canonicalize(resolved)
+        && &target != resolved
+    {
+        println!("{}: {}", "Links to".bold(), target.display());
+    }
+    let reported = crate::ai::get_tool_version(&resolved.to_string_lossy());
+    if let Some(reported) = &reported {
+        println!("{}: {}", "Reports".bold(), reported);
+    }
+
+    let mut problems = Vec::new();
+    if !tool.is_installed {
+        problems.push(format!(
+            "found on PATH but recorded as not installed; run {}",
+            "hoards sync".cyan()
+        ));
+    }
+    let recorded_copy = paths[1..]
+        .iter()
+        .find(|path| origin(path) == Some(tool.source.clone()));
+    match (origin(resolved), recorded_copy) {
+        (found, Some(copy)) if found != Some(tool.source.clone()) => {
+            problems.push(format!(
+                "the {} install at {} is shadowed by {}",
+                source,
+                copy.display(),
+                resolved.display()
+            ));
+        }
+        (Some(found), _) if found != tool.source && tool.source != InstallSource::Unknown => {
+            problems.push(format!(
+                "the binary on PATH looks like a {} install, not {}",
+                found, source
+            ));
+        }
+        _ => {}
+    }
+    if let (Some(expected), Some(reported)) = (&installed_version, &reported)
+        && !reported.contains(version_core(expected))
+    {
+        problems.push(format!(
+            "{} has {} installed, but the binary reports '{}'",
+            source, expected, reported
+        ));
+    }
+
+    if paths.len() > 1 {
+        println!("\n{}", "Shadowed on PATH:".bold());
+        for path in &paths[1..] {
+            println!("  {}", describe(path).dimmed());
+        }
+    }
+
+    println!();
+    if problems.is_empty() {
+        println!("{} Binary matches the recorded install", "+".green());
+    }
+    for problem in &problems {
+        println!("{} {}", "!".yellow(), problem);
     }
 
     Ok(())
@@ -251,7 +598,7 @@ pub fn cmd_remove(db: &Database, name: &str, force: bool) -> Result<()> {
     if db.delete_tool(name)? {
         println!("{} Removed '{}'", "-".red(), name);
     } else {
-        println!("Tool '{}' not found", name);
+        crate::fail!(NotFound, "Tool '{}' not found", name);
     }
 
     Ok(())
@@ -1,14 +1,189 @@
-//! Discovery commands: suggest, similar, trending
+//! Discovery commands: suggest, similar, trending, project
 
 use std::collections::HashMap;
 
 use anyhow::Result;
 use colored::Colorize;
 
+use crate::commands::helpers::{print_project_tools, scan_project};
+use crate::commands::install::install_tool;
+use crate::config::HoardConfig;
 use crate::db::Database;
+use crate::forge::SearchResult;
+use crate::get_safe_install_command;
 use crate::models::Tool;
 use crate::scanner::scan_missing_tools;
 
+/// A repository found by topic, matched against the hoard
+#[derive(Debug, Clone)]
+pub struct TopicMatch {
+    pub repo: SearchResult,
+    /// Tracked tool for the repository, if any
+    pub tool: Option<Tool>,
+}
+
+/// Search GitHub for repositories with a topic and match them to tracked tools
+///
+/// Repositories are matched by their linked GitHub repo, falling back to the
+/// tool name for tools without repository info.
+pub fn search_topic(db: &Database, topic: &str, limit: usize) -> Result<Vec<TopicMatch>> {
+    let results = crate::forge::github::search_topic(topic, limit)?;
+
+    let linked: HashMap<String, String> = db
+        .get_all_github_info()?
+        .into_iter()
+        .filter(|(_, info)| info.is_github())
+        .map(|(name, info)| {
+            let full_name = format!("{}/{}", info.repo_owner, info.repo_name);
+            (full_name.to_lowercase(), name)
+        })
+        .collect();
+
+    let mut matches = Vec::with_capacity(results.len());
+    for repo in results {
+        let tool = match linked.get(&repo.full_name.to_lowercase()) {
+            Some(name) => db.get_tool_by_name(name)?,
+            None => db
+                .get_tool_by_name(&repo.name)?
+                .filter(|_| !db.has_github_info(&repo.name).unwrap_or(true)),
+        };
+        matches.push(TopicMatch { repo, tool });
+    }
+    Ok(matches)
+}
+
+/// Find tools by GitHub topic, showing which ones are already in the hoard
+pub fn cmd_discover_topic(db: &Database, topic: &str, limit: usize) -> Result<()> {
+    let topic = topic.trim().to_lowercase();
+
+    let tracked = db.list_tools_by_topic(&topic)?;
+    if !tracked.is_empty() {
+        println!("{} In your hoard ({}):\n", ">".cyan(), topic.bold());
+        for tool in &tracked {
+            print_similar_tool(tool, None);
+        }
+        println!();
+    }
+
+    if !crate::forge::github::is_gh_available() {
+        println!(
+            "{} No GitHub token found, using the anonymous API",
+            "!".yellow()
+        );
+    }
+
+    let matches = search_topic(db, &topic, limit)?;
+    if matches.is_empty() {
+        println!("No GitHub repositories found with topic '{}'", topic);
+        return Ok(());
+    }
+
+    println!("{} On GitHub ({}):\n", ">".cyan(), topic.bold());
+    let mut new = 0;
+    for m in &matches {
+        let status = match &m.tool {
+            Some(tool) if tool.is_installed => "✓".green(),
+            Some(_) => "○".yellow(),
+            None => {
+                new += 1;
+                " ".normal()
+            }
+        };
+        let tracked_as = m
+            .tool
+            .as_ref()
+            .filter(|t| !t.name.eq_ignore_ascii_case(&m.repo.name))
+            .map(|t| format!("  (tracked as {})", t.name).dimmed().to_string())
+            .unwrap_or_default();
+        println!(
+            "  {} {:>6} ★  {}{}",
+            status,
+            m.repo.stars.to_string().yellow(),
+            m.repo.full_name.bold(),
+            tracked_as
+        );
+        if let Some(desc) = &m.repo.description {
+            println!("             {}", desc.dimmed());
+        }
+    }
+
+    println!();
+    println!(
+        "{} {} new, {} already in your hoard ({} installed, {} tracked)",
+        ">".cyan(),
+        new,
+        matches.len() - new,
+        "✓".green(),
+        "○".yellow()
+    );
+
+    Ok(())
+}
+
+/// Report the CLI tools a project's files refer to, and offer to install
+/// the missing ones
+///
+/// Each missing tool goes through the normal install path, so pins are
+/// honored and the install is recorded; `force` skips its confirmation.
+pub fn cmd_discover_project(db: &Database, path: Option<&str>, force: bool) -> Result<()> {
+    let scan = scan_project(db, path)?;
+    if scan.tools.is_empty() {
+        println!("No tools referenced in {}", scan.dir.display());
+        return Ok(());
+    }
+    print_project_tools(&scan, "Tools referenced by");
+
+    let missing: Vec<_> = scan.tools.iter().filter(|s| !s.installed).collect();
+    if missing.is_empty() {
+        println!(
+            "\n{} Everything the project refers to is installed",
+            "+".green()
+        );
+        return Ok(());
+    }
+
+    println!("\n{} {} missing:\n", "!".yellow(), missing.len());
+    let mut installable = Vec::new();
+    for status in missing {
+        let tool = &status.tool;
+        match get_safe_install_command(&tool.name, &tool.source.to_string(), None) {
+            Ok(Some(command)) => {
+                println!("  {:<20} {}", tool.name, command.to_string().cyan());
+                installable.push(status);
+            }
+            _ => println!("  {:<20} {}", tool.name, "install by hand".dimmed()),
+        }
+    }
+
+    let mut installed = 0;
+    for status in installable {
+        let tool = &status.tool;
+        println!();
+        let source = Some(tool.source.to_string());
+        if let Err(e) = install_tool(db, &tool.name, source, None, force, false) {
+            println!("{} {:#}", "!".red(), e);
+            continue;
+        }
+        // The install path tracks new tools under their name alone; keep
+        // what the known tools list says about them
+        match db.get_tool_by_name(&tool.name)? {
+            Some(recorded) if recorded.is_installed => {
+                if !status.tracked {
+                    db.update_tool(&Tool {
+                        id: recorded.id,
+                        ..tool.clone().installed()
+                    })?;
+                }
+                installed += 1;
+            }
+            _ => {}
+        }
+    }
+
+    println!("\n{} Installed {} tool(s)", "+".green(), installed);
+    Ok(())
+}
+
 /// Suggest tools the user might want
 pub fn cmd_suggest(category: Option<String>) -> Result<()> {
     println!("{} Tools you might want to try:\n", ">".cyan());
@@ -55,18 +230,52 @@ pub fn cmd_suggest(category: Option<String>) -> Result<()> {
 }
 
 /// Find tools similar to a given tool
-pub fn cmd_similar(db: &Database, tool_name: &str) -> Result<()> {
+///
+/// Uses the embedding index, falling back to same-category tools when the
+/// embedding backend is unavailable.
+pub fn cmd_similar(db: &Database, tool_name: &str, limit: usize, reindex: bool) -> Result<()> {
+    use crate::ai::embeddings::{similar_tools, update_index};
+
     let tool = match db.get_tool_by_name(tool_name)? {
         Some(t) => t,
-        None => {
-            println!("Tool '{}' not found", tool_name);
-            return Ok(());
-        }
+        None => crate::fail!(NotFound, "Tool '{}' not found", tool_name),
     };
 
+    let config = HoardConfig::load()?;
+    if reindex {
+        db.clear_embeddings()?;
+    }
+
+    let indexed = update_index(db, &config.ai, &mut |done, total| {
+        eprint!("\r{} Indexing tools ({}/{})...", ">".dimmed(), done, total);
+    });
+    match indexed {
+        Ok(0) => {}
+        Ok(count) => eprintln!(
+            "\r{} Indexed {} tool(s) with {}",
+            "+".green(),
+            count,
+            config.ai.embeddings.model_id()
+        ),
+        Err(e) => tracing::warn!("Embedding index not updated: {:#}", e),
+    }
+
+    if let Some(similar) = similar_tools(db, tool_name, limit)?
+        && !similar.is_empty()
+    {
+        println!("{} Tools similar to '{}':\n", ">".cyan(), tool_name.bold());
+        for (name, score) in similar {
+            let Some(t) = db.get_tool_by_name(&name)? else {
+                continue;
+            };
+            print_similar_tool(&t, Some(score));
+        }
+        return Ok(());
+    }
+
     println!("{} Tools similar to '{}':\n", ">".cyan(), tool_name.bold());
 
-    // Find tools in the same category
+    // Fall back to tools in the same category
     let mut similar: Vec<Tool> = Vec::new();
 
     if let Some(ref cat) = tool.category {
@@ -86,38 +295,53 @@ pub fn cmd_similar(db: &Database, tool_name: &str) -> Result<()> {
     // Sort alphabetically
     similar.sort_by(|a, b| a.name.cmp(&b.name));
 
-    for t in similar.iter().take(10) {
-        let status = if t.is_installed {
-            "installed".green()
-        } else {
-            "not installed".dimmed()
-        };
-
-        println!("  {} {} [{}]", t.name.bold(), status, t.source);
-        if let Some(desc) = &t.description {
-            println!("    {}", desc.dimmed());
-        }
+    for t in similar.iter().take(limit) {
+        print_similar_tool(t, None);
     }
 
     Ok(())
 }
 
-/// Show trending tools by GitHub stars
+/// Print one entry of `discover similar`
+fn print_similar_tool(tool: &Tool, score: Option<f32>) {
+    let status = if tool.is_installed {
+        "installed".green()
+    } else {
+        "not installed".dimmed()
+    };
+    let score = score
+        .map(|s| format!(" {:>3.0}%", s * 100.0).cyan().to_string())
+        .unwrap_or_default();
+
+    println!(
+        "  {} {} [{}]{}",
+        tool.name.bold(),
+        status,
+        tool.source,
+        score
+    );
+    if let Some(desc) = &tool.description {
+        println!("    {}", desc.dimmed());
+    }
+}
+
+/// Show trending tools by forge stars (GitHub, Codeberg, ...)
 pub fn cmd_trending(db: &Database, category: Option<String>, limit: usize) -> Result<()> {
-    println!("{} Trending tools by GitHub stars:\n", ">".cyan());
+    println!("{} Trending tools by stars:\n", ">".cyan());
 
     let tools = db.list_tools(false, category.as_deref())?;
 
     // Collect tools with their GitHub star counts
-    let mut tools_with_stars: Vec<(Tool, i64)> = Vec::new();
+    let mut tools_with_stars: Vec<(Tool, i64, Option<String>)> = Vec::new();
     for tool in tools {
         if let Ok(Some(gh_info)) = db.get_github_info(&tool.name) {
-            tools_with_stars.push((tool, gh_info.stars));
+            let host = (!gh_info.is_github()).then_some(gh_info.host);
+            tools_with_stars.push((tool, gh_info.stars, host));
         }
     }
 
     // Sort by stars descending
-    tools_with_stars.sort_by(|a, b| b.1.cmp(&a.1));
+    tools_with_stars.sort_by_key(|t| std::cmp::Reverse(t.1));
 
     if tools_with_stars.is_empty() {
         println!("No tools with GitHub star data found.");
@@ -125,7 +349,7 @@ pub fn cmd_trending(db: &Database, category: Option<String>, limit: usize) -> Re
         return Ok(());
     }
 
-    for (tool, stars) in tools_with_stars.iter().take(limit) {
+    for (tool, stars, host) in tools_with_stars.iter().take(limit) {
         let status = if tool.is_installed {
             "✓".green()
         } else {
@@ -133,13 +357,51 @@ pub fn cmd_trending(db: &Database, category: Option<String>, limit: usize) -> Re
         };
 
         println!(
-            "  {} {:>6} ★  {}  [{}]",
+            "  {} {:>6} ★  {}  [{}]{}",
             status,
             stars.to_string().yellow(),
             tool.name.bold(),
-            tool.category.as_deref().unwrap_or("-")
+            tool.category.as_deref().unwrap_or("-"),
+            host.as_ref()
+                .map(|h| format!("  {}", h).dimmed().to_string())
+                .unwrap_or_default()
         );
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::helpers::resolve_project_tools;
+    use crate::models::InstallSource;
+    use crate::project::detect_project_tools;
+
+    #[test]
+    fn test_resolve_project_tools() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("justfile"), "build:\n\tcargo build\n")?;
+        std::fs::write(dir.path().join("Makefile"), "all:\n\tmake -C sub\n")?;
+        std::fs::write(dir.path().join("skaffold.yaml"), "")?;
+
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("just").with_binary("just-bin"))?;
+
+        let detected = detect_project_tools(dir.path())?;
+        let tools = resolve_project_tools(&db, detected, |binary| binary == "make")?;
+        let status = |name: &str| tools.iter().find(|s| s.found.name == name).unwrap();
+
+        assert!(status("make").installed);
+        assert!(!status("make").tracked);
+        // Tracked tools are checked by their recorded binary
+        assert!(status("just").tracked);
+        assert!(!status("just").installed);
+        assert_eq!(status("just").tool.binary_name.as_deref(), Some("just-bin"));
+        // Unknown tools keep the source the detection guessed
+        assert!(!status("skaffold").installed);
+        assert_eq!(status("skaffold").tool.source, InstallSource::Manual);
+        assert!(status("skaffold").found.reason.contains("skaffold.yaml"));
+        Ok(())
+    }
+}
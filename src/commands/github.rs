@@ -4,28 +4,54 @@
 
 use anyhow::Result;
 use colored::Colorize;
-use std::process::Command;
 
 use crate::Database;
+use crate::forge::{Release, ReleaseAsset, RepoRef};
+
+/// Cached READMEs older than this are revalidated with the forge
+pub const README_MAX_AGE_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Warn that GitHub requests will be anonymous (and heavily rate limited)
+fn warn_if_anonymous() {
+    if !crate::forge::github::is_gh_available() {
+        println!(
+            "{} No GitHub token found, using the anonymous API (60 requests/hour)",
+            "!".yellow()
+        );
+        println!(
+            "  Set {} or run {} for higher limits",
+            "GITHUB_TOKEN".cyan(),
+            "gh auth login".cyan()
+        );
+    }
+}
+
+/// Queue name for tools waiting for a GitHub sync
+const SYNC_JOB: &str = "sync";
+
+/// Tools failing this often (for reasons other than rate limits) leave the queue
+const MAX_SYNC_ATTEMPTS: i64 = 3;
 
 /// Sync GitHub info for tools without it
+///
+/// Tools are queued in the database and removed once synced, so an
+/// interrupted or rate-limited sync resumes where it stopped. When a quota
+/// runs out, the sync waits for its reset and continues.
 pub fn cmd_gh_sync(
     db: &Database,
     dry_run: bool,
     limit: Option<usize>,
     delay_ms: u64,
 ) -> Result<()> {
-    use crate::github::{
-        TopicMapping, find_repo, get_all_rate_limits, is_gh_available, topics_to_category,
+    use crate::forge::github::{
+        GitHubForge, TopicMapping, find_repo, get_all_rate_limits, topics_to_category,
     };
+    use crate::forge::scheduler::{self, RateLimited};
+    use crate::forge::{Forge, ForgeProvider, GITHUB_HOST};
 
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
-        println!("  Install it with: {}", "brew install gh".cyan());
-        return Ok(());
-    }
+    warn_if_anonymous();
 
-    // Check both core and search rate limits
+    // Also seeds the scheduler with the current quotas
     let limits = get_all_rate_limits()?;
 
     println!(
@@ -43,70 +69,50 @@ pub fn cmd_gh_sync(
         limits.search.reset_seconds()
     );
 
-    // Search API is the bottleneck (30/minute vs 5000/hour)
-    // Each tool needs 1 search call + 1 core API call
-    if limits.search.remaining == 0 {
-        println!(
-            "\n{} Search API quota exhausted! Wait {} seconds before retrying.",
-            "!".red(),
-            limits.search.reset_seconds()
-        );
-        return Ok(());
-    }
-
-    // Get tools without GitHub info
-    let mut tools_to_sync = db.get_tools_without_github()?;
+    // Queue tools without GitHub info; earlier unfinished syncs are resumed
+    let missing = db.get_tools_without_github()?;
+    let mut tools_to_sync = if dry_run {
+        missing
+    } else {
+        let added = db.enqueue_github_jobs(SYNC_JOB, &missing)?;
+        let queued: Vec<String> = db
+            .github_jobs(Some(SYNC_JOB))?
+            .into_iter()
+            .map(|job| job.target)
+            .collect();
+        let resumed = queued.len() - added;
+        if resumed > 0 {
+            println!(
+                "{} Resuming {} tool{} queued by an earlier sync",
+                ">".cyan(),
+                resumed,
+                if resumed == 1 { "" } else { "s" }
+            );
+        }
+        queued
+    };
 
     if tools_to_sync.is_empty() {
         println!("{} All tools already have GitHub info", "+".green());
         return Ok(());
     }
 
-    // Limit based on Search API (the stricter limit)
-    // Each tool needs 1 search call
-    let search_limited_max = limits.search.remaining as usize;
-    // Also check core API (each tool needs 1 core call for repo details)
-    let core_limited_max = limits.core.remaining as usize;
-    let rate_limited_max = search_limited_max.min(core_limited_max);
-
     if let Some(max) = limit {
-        tools_to_sync.truncate(max.min(rate_limited_max));
-    } else if tools_to_sync.len() > rate_limited_max {
-        println!(
-            "{} Limiting to {} tools (search quota: {}/min)",
-            "!".yellow(),
-            rate_limited_max,
-            limits.search.limit
-        );
-        tools_to_sync.truncate(rate_limited_max);
-    }
-
-    if tools_to_sync.is_empty() {
-        println!(
-            "{} Not enough API quota. Wait {} sec or use --limit",
-            "!".red(),
-            limits.search.reset_seconds()
-        );
-        return Ok(());
-    }
-
-    // Warn if delay is too short for search API (30/min = 2000ms between calls)
-    let min_safe_delay = 2000;
-    if delay_ms < min_safe_delay && tools_to_sync.len() > 1 {
-        println!(
-            "{} Warning: {}ms delay may hit search rate limit (30/min). Use --delay {} for safety.",
-            "!".yellow(),
-            delay_ms,
-            min_safe_delay
-        );
+        tools_to_sync.truncate(max);
     }
 
+    // Each tool needs 1 search call + 2 core API calls (repo, contributors);
+    // the scheduler waits out exhausted quotas instead of failing
     println!(
-        "{} Syncing {} tool{} ({}ms delay between searches)...",
+        "{} Syncing {} tool{}{}...",
         ">".cyan(),
         tools_to_sync.len(),
         if tools_to_sync.len() == 1 { "" } else { "s" },
-        delay_ms
+        if delay_ms > 0 {
+            format!(" ({}ms delay between tools)", delay_ms)
+        } else {
+            String::new()
+        }
     );
 
     let mapping = TopicMapping::load();
@@ -114,20 +120,54 @@ pub fn cmd_gh_sync(
     let mut not_found = 0;
     let delay = std::time::Duration::from_millis(delay_ms);
 
-    for (i, tool_name) in tools_to_sync.iter().enumerate() {
+    // Park the current tool and wait for the quota to refill
+    let wait_for_reset = |tool_name: &str, limited: &RateLimited| -> Result<()> {
+        if !dry_run {
+            db.defer_github_job(SYNC_JOB, tool_name, limited.reset)?;
+        }
+        println!(
+            "  {} {}, waiting (Ctrl-C is safe, the next sync resumes here)",
+            "!".yellow(),
+            limited
+        );
+        scheduler::sleep_until_reset(limited);
+        Ok(())
+    };
+
+    let mut i = 0;
+    while i < tools_to_sync.len() {
+        let tool_name = &tools_to_sync[i];
+
         // Add delay between requests (except first)
         if i > 0 && delay_ms > 0 {
             std::thread::sleep(delay);
         }
 
         // Get tool's source to improve search accuracy (e.g., cargo -> language:rust)
-        let source = db
-            .get_tool_by_name(tool_name)?
-            .map(|t| t.source.to_string());
+        let Some(tool) = db.get_tool_by_name(tool_name)? else {
+            // Removed since it was queued
+            db.finish_github_job(SYNC_JOB, tool_name)?;
+            i += 1;
+            continue;
+        };
+        if !dry_run && db.get_github_info(tool_name)?.is_some() {
+            // Fetched by another command since it was queued
+            db.finish_github_job(SYNC_JOB, tool_name)?;
+            i += 1;
+            continue;
+        }
+        let source = tool.source.to_string();
+
+        // Don't start a tool whose requests can't go through yet
+        if let Some(limited) =
+            scheduler::exhausted("search").or_else(|| scheduler::exhausted("core"))
+        {
+            wait_for_reset(tool_name, &limited)?;
+        }
 
         print!("  {} {}... ", ">".dimmed(), tool_name);
 
-        match find_repo(tool_name, source.as_deref()) {
+        match find_repo(tool_name, Some(&source)) {
             Ok(Some(info)) => {
                 if dry_run {
                     println!("{}", "[dry] found".yellow());
@@ -136,20 +176,27 @@ pub fn cmd_gh_sync(
                         println!("       topics: {}", info.topics.join(", ").dimmed());
                     }
                 } else {
-                    // Store GitHub info
+                    // Store GitHub info (plus one core call for the contributor count)
+                    let contributors = GitHubForge
+                        .contributor_count(&info.owner.login, &info.name)
+                        .unwrap_or(None);
                     db.set_github_info(
                         tool_name,
                         crate::db::GitHubInfoInput {
-                            repo_owner: &info.owner.login,
-                            repo_name: &info.name,
-                            description: info.description.as_deref(),
-                            stars: info.stars,
-                            language: info.language.as_deref(),
-                            homepage: info.homepage.as_deref(),
+                            contributors,
+                            ..crate::db::GitHubInfoInput::from_repo(
+                                ForgeProvider::GitHub,
+                                GITHUB_HOST,
+                                &info,
+                            )
                         },
                     )?;
+                    if let Some(license) = info.license_id() {
+                        db.set_tool_license(tool_name, Some(license))?;
+                    }
 
-                    // Add topics as labels
+                    // Keep topics for discovery and add them as labels
+                    db.set_topics(tool_name, &info.topics)?;
                     let labels: Vec<String> =
                         info.topics.iter().map(|t| t.to_lowercase()).collect();
                     if !labels.is_empty() {
@@ -157,43 +204,63 @@ pub fn cmd_gh_sync(
                     }
 
                     // Auto-fill description and category if missing
-                    if let Some(tool) = db.get_tool_by_name(tool_name)? {
-                        let mut updates = Vec::new();
-
-                        // Copy description from GitHub if tool has none
-                        if tool.description.is_none()
-                            && let Some(desc) = &info.description
-                        {
-                            db.update_tool_description(tool_name, desc)?;
-                            updates.push("desc".to_string());
-                        }
-
-                        // Auto-categorize from topics if uncategorized
-                        if tool.category.is_none()
-                            && let Some(category) = topics_to_category(&info.topics, &mapping)
-                        {
-                            db.update_tool_category(tool_name, &category)?;
-                            updates.push(format!("→ {}", category));
-                        }
-
-                        if updates.is_empty() {
-                            println!("{}", "+".green());
-                        } else {
-                            println!("{} {}", "+".green(), updates.join(", ").cyan());
-                        }
+                    let mut updates = Vec::new();
+
+                    // Copy description from GitHub if tool has none
+                    if tool.description.is_none()
+                        && let Some(desc) = &info.description
+                    {
+                        db.update_tool_description(tool_name, desc)?;
+                        updates.push("desc".to_string());
                     }
 
+                    // Auto-categorize from topics if uncategorized
+                    if tool.category.is_none()
+                        && let Some(category) = topics_to_category(&info.topics, &mapping)
+                    {
+                        db.update_tool_category(tool_name, &category)?;
+                        updates.push(format!("→ {}", category));
+                    }
+
+                    if updates.is_empty() {
+                        println!("{}", "+".green());
+                    } else {
+                        println!("{} {}", "+".green(), updates.join(", ").cyan());
+                    }
+
+                    db.finish_github_job(SYNC_JOB, tool_name)?;
                     synced += 1;
                 }
             }
             Ok(None) => {
                 println!("{}", "not found".dimmed());
+                if !dry_run {
+                    db.finish_github_job(SYNC_JOB, tool_name)?;
+                }
                 not_found += 1;
             }
             Err(e) => {
+                if let Some(limited) = e.downcast_ref::<RateLimited>() {
+                    // Retry the same tool once the quota is back
+                    println!("{}", "rate limited".yellow());
+                    wait_for_reset(tool_name, limited)?;
+                    continue;
+                }
+
                 println!("{} {}", "!".red(), e);
+                if !dry_run
+                    && db.fail_github_job(SYNC_JOB, tool_name, &e.to_string())? >= MAX_SYNC_ATTEMPTS
+                {
+                    println!(
+                        "    {} giving up after {} attempts",
+                        "-".red(),
+                        MAX_SYNC_ATTEMPTS
+                    );
+                    db.finish_github_job(SYNC_JOB, tool_name)?;
+                }
             }
         }
+        i += 1;
     }
 
     println!();
@@ -211,30 +278,87 @@ pub fn cmd_gh_sync(
             if synced == 1 { "" } else { "s" },
             not_found
         );
+        let left = db.github_jobs(Some(SYNC_JOB))?.len();
+        if left > 0 {
+            println!(
+                "{} {} tool{} still queued; see {}",
+                "i".cyan(),
+                left,
+                if left == 1 { "" } else { "s" },
+                "hoards gh queue".cyan()
+            );
+        }
     }
 
     Ok(())
 }
 
-/// Show GitHub API rate limits
-pub fn cmd_gh_rate_limit() -> Result<()> {
-    use crate::github::{get_all_rate_limits, is_gh_available};
+/// Show queued GitHub work, or clear the queue
+pub fn cmd_gh_queue(db: &Database, clear: bool) -> Result<()> {
+    use crate::forge::scheduler::{format_wait, now};
 
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
+    if clear {
+        let cleared = db.clear_github_jobs(None)?;
+        println!(
+            "{} Removed {} queued job{}",
+            "-".red(),
+            cleared,
+            if cleared == 1 { "" } else { "s" }
+        );
+        return Ok(());
+    }
+
+    let jobs = db.github_jobs(None)?;
+    if jobs.is_empty() {
+        println!("{} No queued GitHub work", "+".green());
         return Ok(());
     }
 
+    println!("{}", "Queued GitHub work:".bold());
+    let now = now();
+    for job in &jobs {
+        let status = if job.not_before > now {
+            format!(
+                "waiting for rate limit ({})",
+                format_wait(job.not_before - now)
+            )
+            .yellow()
+            .to_string()
+        } else if let Some(error) = &job.last_error {
+            format!("{} failed: {}", job.attempts, error)
+                .red()
+                .to_string()
+        } else {
+            "pending".dimmed().to_string()
+        };
+        println!("  {:<6} {:<24} {}", job.job, job.target, status);
+    }
+
+    println!();
+    println!(
+        "{} Resume with {} or drop with {}",
+        ">".cyan(),
+        "hoards gh sync".cyan(),
+        "hoards gh queue --clear".cyan()
+    );
+    Ok(())
+}
+
+/// Show GitHub API rate limits
+pub fn cmd_gh_rate_limit() -> Result<()> {
+    use crate::forge::github::get_all_rate_limits;
+
+    warn_if_anonymous();
     let limits = get_all_rate_limits()?;
 
-    println!("{}", "Core API (5000/hour):".bold());
+    println!("{}", "Core API (per hour):".bold());
     println!("  Limit:     {}", limits.core.limit);
     println!("  Used:      {}", limits.core.used);
     println!("  Remaining: {}", limits.core.remaining);
     println!("  Resets in: {} minutes", limits.core.reset_minutes());
 
     println!();
-    println!("{}", "Search API (30/minute):".bold());
+    println!("{}", "Search API (per minute):".bold());
     println!("  Limit:     {}", limits.search.limit);
     println!("  Used:      {}", limits.search.used);
     println!("  Remaining: {}", limits.search.remaining);
@@ -318,44 +442,66 @@ pub fn cmd_gh_backfill(db: &Database, dry_run: bool) -> Result<()> {
 }
 
 /// Fetch GitHub info for a specific tool
-pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
-    use crate::github::{TopicMapping, find_repo, is_gh_available, topics_to_category};
-
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
-        return Ok(());
+///
+/// With `repo`, the tool is linked to that repository URL instead of being
+/// searched for on GitHub; any Gitea-compatible forge (e.g. Codeberg) works.
+pub fn cmd_gh_fetch(db: &Database, name: &str, repo: Option<&str>) -> Result<()> {
+    use crate::forge::github::{TopicMapping, find_repo, topics_to_category};
+    use crate::forge::{ForgeProvider, GITHUB_HOST, get_forge, parse_repo_url};
+
+    let repo = repo.map(parse_repo_url).transpose()?;
+    let on_github = repo
+        .as_ref()
+        .is_none_or(|r| r.provider == ForgeProvider::GitHub);
+
+    if on_github {
+        warn_if_anonymous();
     }
 
     // Check if tool exists in DB
     let tool = db.get_tool_by_name(name)?;
     if tool.is_none() {
-        println!("{} Tool '{}' not found in database", "!".yellow(), name);
-        println!(
-            "  Add it first with: {}",
-            format!("hoards add {}", name).cyan()
+        crate::fail!(
+            NotFound,
+            "Tool '{}' not found in database; add it first with: hoards add {}",
+            name,
+            name
         );
-        return Ok(());
     }
     let source = tool.map(|t| t.source.to_string());
 
-    println!("{} Fetching GitHub info for '{}'...", ">".cyan(), name);
+    let (provider, host, found) = match &repo {
+        Some(repo) => {
+            println!("{} Fetching {} for '{}'...", ">".cyan(), repo.url(), name);
+            let info = repo.forge().repo_info(&repo.owner, &repo.repo)?;
+            (repo.provider, repo.host.as_str(), Some(info))
+        }
+        None => {
+            println!("{} Fetching GitHub info for '{}'...", ">".cyan(), name);
+            let info = find_repo(name, source.as_deref())?;
+            (ForgeProvider::GitHub, GITHUB_HOST, info)
+        }
+    };
 
-    match find_repo(name, source.as_deref())? {
+    match found {
         Some(info) => {
-            // Store GitHub info
+            // Store repository info
+            let contributors = get_forge(provider, host)
+                .contributor_count(&info.owner.login, &info.name)
+                .unwrap_or(None);
             db.set_github_info(
                 name,
                 crate::db::GitHubInfoInput {
-                    repo_owner: &info.owner.login,
-                    repo_name: &info.name,
-                    description: info.description.as_deref(),
-                    stars: info.stars,
-                    language: info.language.as_deref(),
-                    homepage: info.homepage.as_deref(),
+                    contributors,
+                    ..crate::db::GitHubInfoInput::from_repo(provider, host, &info)
                 },
             )?;
+            if let Some(license) = info.license_id() {
+                db.set_tool_license(name, Some(license))?;
+            }
 
-            // Add topics as labels
+            // Keep topics for discovery and add them as labels
+            db.set_topics(name, &info.topics)?;
             let labels: Vec<String> = info.topics.iter().map(|t| t.to_lowercase()).collect();
             if !labels.is_empty() {
                 db.clear_labels(name)?;
@@ -377,8 +523,13 @@ pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
             }
 
             println!();
-            println!("{}", "GitHub Info:".bold());
-            println!("  Repo:     {}", info.full_name.cyan());
+            if provider == ForgeProvider::GitHub {
+                println!("{}", "GitHub Info:".bold());
+                println!("  Repo:     {}", info.full_name.cyan());
+            } else {
+                println!("{}", "Repository Info:".bold());
+                println!("  Repo:     {}/{}", host, info.full_name.cyan());
+            }
             println!("  Stars:    {}", info.stars);
             if let Some(desc) = &info.description {
                 println!("  Desc:     {}", desc.dimmed());
@@ -390,7 +541,7 @@ pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
                 println!("  Topics:   {}", info.topics.join(", "));
             }
             println!();
-            println!("{} GitHub info saved", "+".green());
+            println!("{} Repository info saved", "+".green());
         }
         None => {
             println!("{} '{}' not found on GitHub", "!".yellow(), name);
@@ -402,45 +553,20 @@ pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
 
 /// Search GitHub repositories
 pub fn cmd_gh_search(query: &str, limit: usize) -> Result<()> {
-    use crate::github::is_gh_available;
+    use crate::forge::Forge;
+    use crate::forge::github::GitHubForge;
 
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
-        return Ok(());
-    }
+    warn_if_anonymous();
 
     println!("{} Searching GitHub for '{}'...", ">".cyan(), query);
 
-    let output = Command::new("gh")
-        .args([
-            "search",
-            "repos",
-            query,
-            "--json",
-            "name,fullName,description,stargazersCount",
-            "--limit",
-            &limit.to_string(),
-        ])
-        .output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        println!("{} Search failed: {}", "!".red(), stderr);
-        return Ok(());
-    }
-
-    #[derive(serde::Deserialize)]
-    struct SearchResult {
-        #[allow(dead_code)]
-        name: String,
-        #[serde(rename = "fullName")]
-        full_name: String,
-        description: Option<String>,
-        #[serde(rename = "stargazersCount")]
-        stars: i64,
-    }
-
-    let results: Vec<SearchResult> = serde_json::from_slice(&output.stdout)?;
+    let results = match GitHubForge.search(query, limit) {
+        Ok(results) => results,
+        Err(e) => {
+            println!("{} Search failed: {}", "!".red(), e);
+            return Ok(());
+        }
+    };
 
     if results.is_empty() {
         println!("{} No results found", "!".yellow());
@@ -464,19 +590,38 @@ pub fn cmd_gh_search(query: &str, limit: usize) -> Result<()> {
 }
 
 /// Show cached GitHub info for a tool
-pub fn cmd_gh_info(db: &Database, name: &str) -> Result<()> {
+pub fn cmd_gh_info(db: &Database, name: &str, json_output: bool) -> Result<()> {
     // Check if tool exists
     let tool = db.get_tool_by_name(name)?;
-    if tool.is_none() {
-        println!("{} Tool '{}' not found in database", "!".yellow(), name);
+
+    if json_output {
+        // Cached data only: no release lookup
+        let info = match tool {
+            Some(_) => serde_json::json!({
+                "repository": db.get_github_info(name)?,
+                "labels": db.get_labels(name)?,
+                "release": db.get_release_install(name)?,
+            }),
+            None => serde_json::Value::Null,
+        };
+        println!("{}", serde_json::to_string_pretty(&info)?);
         return Ok(());
     }
 
+    if tool.is_none() {
+        crate::fail!(NotFound, "Tool '{}' not found in database", name);
+    }
+
     // Get cached GitHub info
     match db.get_github_info(name)? {
         Some(info) => {
-            println!("{}", "GitHub Info:".bold());
-            println!("  Repo:     {}/{}", info.repo_owner, info.repo_name);
+            if info.is_github() {
+                println!("{}", "GitHub Info:".bold());
+                println!("  Repo:     {}/{}", info.repo_owner, info.repo_name);
+            } else {
+                println!("{}", "Repository Info:".bold());
+                println!("  Repo:     {}", info.repo_ref().url());
+            }
             println!("  Stars:    {}", info.stars);
             if let Some(desc) = &info.description {
                 println!("  Desc:     {}", desc.dimmed());
@@ -493,6 +638,21 @@ pub fn cmd_gh_info(db: &Database, name: &str) -> Result<()> {
             if !labels.is_empty() {
                 println!("  Labels:   {}", labels.join(", ").cyan());
             }
+
+            // Installed release, or a prebuilt binary that could be installed
+            if let Some(install) = db.get_release_install(name)? {
+                println!(
+                    "  Release:  {} ({})",
+                    install.tag.green(),
+                    install.asset.dimmed()
+                );
+            } else if let Ok((release, asset)) = find_release_asset(&info.repo_ref(), None) {
+                println!("  Prebuilt: {} ({})", asset.name, release.tag_name);
+                println!(
+                    "  Install with: {}",
+                    format!("hoards gh install {}", name).cyan()
+                );
+            }
         }
         None => {
             println!("{} No GitHub info cached for '{}'", "!".yellow(), name);
@@ -505,3 +665,373 @@ pub fn cmd_gh_info(db: &Database, name: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Show a tool's README, from the local cache when possible
+pub fn cmd_gh_readme(db: &Database, name: &str, refresh: bool) -> Result<()> {
+    let Some(info) = db.get_github_info(name)? else {
+        println!("{} No repository known for '{}'", "!".yellow(), name);
+        println!(
+            "  Fetch it with: {}",
+            format!("hoards gh fetch {}", name).cyan()
+        );
+        return Ok(());
+    };
+
+    let readme = load_readme(db, &info.repo_ref(), refresh)?;
+    println!("{}", readme.trim_end());
+    Ok(())
+}
+
+/// Load a repository's README through the `readmes` cache
+///
+/// Copies younger than `README_MAX_AGE_SECS` are used as-is; older ones are
+/// revalidated with their ETag. If the forge can't be reached, any cached
+/// copy is returned regardless of age. `refresh` skips the freshness check.
+pub fn load_readme(db: &Database, repo: &RepoRef, refresh: bool) -> Result<String> {
+    let cached = db.get_cached_readme(&repo.host, &repo.owner, &repo.repo)?;
+    if let Some(cached) = &cached
+        && !refresh
+        && cached.age_secs < README_MAX_AGE_SECS
+    {
+        return Ok(cached.content.clone());
+    }
+
+    let etag = cached.as_ref().and_then(|c| c.etag.as_deref());
+    match repo
+        .forge()
+        .readme_if_changed(&repo.owner, &repo.repo, etag)
+    {
+        Ok(Some(readme)) => {
+            db.cache_readme(
+                &repo.host,
+                &repo.owner,
+                &repo.repo,
+                &readme.content,
+                readme.etag.as_deref(),
+            )?;
+            Ok(readme.content)
+        }
+        Ok(None) => {
+            db.touch_readme(&repo.host, &repo.owner, &repo.repo)?;
+            Ok(cached.map(|c| c.content).unwrap_or_default())
+        }
+        Err(e) => cached.map(|c| c.content).ok_or(e),
+    }
+}
+
+/// Latest stable release (or the one tagged `tag`) and its asset for this platform
+pub fn find_release_asset(repo: &RepoRef, tag: Option<&str>) -> Result<(Release, ReleaseAsset)> {
+    use crate::forge::assets::{platform, platform_asset};
+    use anyhow::Context;
+
+    let releases = repo.forge().releases(&repo.owner, &repo.repo)?;
+    let release = match tag {
+        Some(tag) => releases.into_iter().find(|r| {
+            r.tag_name == tag || r.tag_name.trim_start_matches('v') == tag.trim_start_matches('v')
+        }),
+        None => releases.into_iter().find(|r| !r.prerelease),
+    }
+    .with_context(|| match tag {
+        Some(tag) => format!("No release {} in {}", tag, repo.url()),
+        None => format!("No releases in {}", repo.url()),
+    })?;
+
+    let asset = platform_asset(&release).cloned().with_context(|| {
+        format!(
+            "Release {} has no prebuilt binary for {}",
+            release.tag_name,
+            platform()
+        )
+    })?;
+    Ok((release, asset))
+}
+
+/// Install a release asset and track the tool as installed from its forge
+///
+/// The tool is added if it isn't tracked yet, with repository metadata
+/// fetched on the way; the release tag is recorded as its version. `binary`
+/// is the executable to take from an archive (default: the tool name).
+/// Returns the installed path.
+pub fn install_release(
+    db: &Database,
+    repo: &RepoRef,
+    release: &Release,
+    asset: &ReleaseAsset,
+    name: &str,
+    binary: Option<&str>,
+) -> Result<std::path::PathBuf> {
+    use crate::commands::install::{validate_binary_name, validate_package_name};
+    use crate::config::HoardConfig;
+    use crate::db::{GitHubInfoInput, ReleaseInstall};
+    use crate::models::{InstallSource, Tool};
+
+    validate_package_name(name)?;
+    let binary = binary.unwrap_or(name);
+    validate_binary_name(binary)?;
+
+    let bin_dir = HoardConfig::load()?.install.bin_dir()?;
+    let path = crate::forge::assets::install_asset(release, asset, binary, &bin_dir)?;
+    let installed_binary = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(binary)
+        .to_string();
+
+    let install_command = format!("hoards gh install {}", repo.url());
+    if db.get_tool_by_name(name)?.is_some() {
+        db.update_tool_source(name, &InstallSource::GitHub.to_string())?;
+        db.set_tool_installed(name, true)?;
+    } else {
+        let tool = Tool::new(name)
+            .with_source(InstallSource::GitHub)
+            .with_binary(&installed_binary)
+            .with_install_command(install_command)
+            .installed();
+        db.insert_tool(&tool)?;
+    }
+
+    // Repository metadata is a nicety; the install already succeeded
+    if !db.has_github_info(name)?
+        && let Ok(info) = repo.forge().repo_info(&repo.owner, &repo.repo)
+    {
+        db.set_github_info(
+            name,
+            GitHubInfoInput::from_repo(repo.provider, &repo.host, &info),
+        )?;
+        db.set_topics(name, &info.topics)?;
+        if let Some(desc) = &info.description
+            && db
+                .get_tool_by_name(name)?
+                .is_some_and(|t| t.description.is_none())
+        {
+            db.update_tool_description(name, desc)?;
+        }
+        if let Some(license) = info.license_id() {
+            db.set_tool_license(name, Some(license))?;
+        }
+    }
+
+    db.record_release_install(&ReleaseInstall {
+        tool: name.to_string(),
+        host: repo.host.clone(),
+        repo_owner: repo.owner.clone(),
+        repo_name: repo.repo.clone(),
+        tag: release.tag_name.clone(),
+        asset: asset.name.clone(),
+        path: path.display().to_string(),
+        installed_at: String::new(),
+    })?;
+
+    Ok(path)
+}
+
+/// Install a prebuilt binary from a repository's releases
+///
+/// `target` is a repository (owner/repo or URL) or a tracked tool with a
+/// linked repository.
+pub fn cmd_gh_install(
+    db: &Database,
+    target: &str,
+    tag: Option<&str>,
+    name: Option<&str>,
+    binary: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    let (repo, default_name) = match db.get_github_info(target)? {
+        Some(info) if !target.contains('/') => (info.repo_ref(), target.to_string()),
+        _ => {
+            let repo = crate::forge::parse_repo_url(target)?;
+            let name = repo.repo.to_lowercase();
+            (repo, name)
+        }
+    };
+    let name = name.unwrap_or(&default_name);
+
+    if repo.provider == crate::forge::ForgeProvider::GitHub {
+        warn_if_anonymous();
+    }
+    let (release, asset) = find_release_asset(&repo, tag)?;
+    let bin_dir = crate::config::HoardConfig::load()?.install.bin_dir()?;
+
+    println!("{} Install plan for '{}':\n", ">".cyan(), name.bold());
+    println!(
+        "  Release: {} ({})",
+        release.tag_name.green(),
+        repo.url().dimmed()
+    );
+    println!(
+        "  Asset:   {} ({:.1} MB)",
+        asset.name,
+        asset.size as f64 / 1_048_576.0
+    );
+    println!("  Into:    {}", bin_dir.display());
+
+    if !force {
+        println!();
+        print!("Proceed? [y/N] ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    println!();
+    println!("{} Downloading {}...", ">".cyan(), asset.name);
+    let path = install_release(db, &repo, &release, &asset, name, binary)?;
+    println!(
+        "{} Installed '{}' {} to {}",
+        "+".green(),
+        name,
+        release.tag_name,
+        path.display()
+    );
+
+    let in_path = std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|p| Some(p.as_path()) == path.parent())
+    });
+    if !in_path {
+        println!(
+            "  {} {} is not in your PATH",
+            "!".yellow(),
+            bin_dir.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Import starred GitHub repositories that look like CLI tools
+///
+/// Matching repos are added as not-installed tools (with GitHub info, topics
+/// as labels and a `starred` label), or as interests with `as_interests`.
+pub fn cmd_gh_import_stars(
+    db: &Database,
+    language: Option<&str>,
+    topic: Option<&str>,
+    all: bool,
+    as_interests: bool,
+    dry_run: bool,
+) -> Result<()> {
+    use crate::forge::github::{is_gh_available, list_starred, looks_like_cli_tool};
+    use crate::forge::{ForgeProvider, GITHUB_HOST};
+    use crate::models::{Interest, Tool};
+
+    if !is_gh_available() {
+        println!("{} Importing stars needs a GitHub token", "!".red());
+        println!(
+            "  Set {} or run {}",
+            "GITHUB_TOKEN".cyan(),
+            "gh auth login".cyan()
+        );
+        return Ok(());
+    }
+
+    println!("{} Fetching starred repositories...", ">".cyan());
+    let starred = list_starred()?;
+    let total = starred.len();
+
+    let matches: Vec<_> = starred
+        .into_iter()
+        .filter(|repo| {
+            language.is_none_or(|lang| {
+                repo.language
+                    .as_deref()
+                    .is_some_and(|l| l.eq_ignore_ascii_case(lang))
+            })
+        })
+        .filter(|repo| {
+            topic.is_none_or(|topic| repo.topics.iter().any(|t| t.eq_ignore_ascii_case(topic)))
+        })
+        .filter(|repo| all || looks_like_cli_tool(repo))
+        .collect();
+
+    println!(
+        "{} {} of {} starred repos match",
+        ">".cyan(),
+        matches.len(),
+        total
+    );
+
+    let existing_interests: Vec<String> = if as_interests {
+        db.list_interests()?.into_iter().map(|i| i.name).collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut added = 0;
+    let mut skipped = 0;
+    for repo in &matches {
+        let name = repo.name.to_lowercase();
+        let exists = if as_interests {
+            existing_interests.contains(&name)
+        } else {
+            db.get_tool_by_name(&name)?.is_some()
+        };
+        if exists {
+            skipped += 1;
+            continue;
+        }
+
+        let desc = repo.description.as_deref().unwrap_or("");
+        if dry_run {
+            println!(
+                "  {} {} ({} ★) {}",
+                "[dry]".yellow(),
+                name,
+                repo.stars,
+                desc.dimmed()
+            );
+            added += 1;
+            continue;
+        }
+
+        if as_interests {
+            let mut interest = Interest::new(&name);
+            interest.description = repo.description.clone();
+            db.insert_interest(&interest)?;
+        } else {
+            let mut tool = Tool::new(&name);
+            tool.description = repo.description.clone();
+            tool.license = repo.license_id().map(String::from);
+            db.insert_tool(&tool)?;
+            db.set_github_info(
+                &name,
+                crate::db::GitHubInfoInput::from_repo(ForgeProvider::GitHub, GITHUB_HOST, repo),
+            )?;
+            db.set_topics(&name, &repo.topics)?;
+            let mut labels: Vec<String> = repo.topics.iter().map(|t| t.to_lowercase()).collect();
+            labels.push("starred".to_string());
+            db.add_labels(&name, &labels)?;
+        }
+        println!("  {} {} ({} ★)", "+".green(), name, repo.stars);
+        added += 1;
+    }
+
+    println!();
+    if dry_run {
+        println!(
+            "{} Would import {} repo{} ({} already tracked). Run without {} to apply",
+            ">".cyan(),
+            added,
+            if added == 1 { "" } else { "s" },
+            skipped,
+            "--dry-run".yellow()
+        );
+    } else {
+        println!(
+            "{} Imported {} repo{} as {} ({} already tracked)",
+            "+".green(),
+            added,
+            if added == 1 { "" } else { "s" },
+            if as_interests { "interests" } else { "tools" },
+            skipped
+        );
+    }
+
+    Ok(())
+}
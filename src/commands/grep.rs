@@ -0,0 +1,198 @@
+//! Grep command: search the free text hoards keeps about tools
+//!
+//! Unlike `search`, which matches names, descriptions and categories, this
+//! looks through every text field line by line, including notes and cached
+//! AI output, and shows the matching lines.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::ai::cache;
+use crate::db::Database;
+
+/// A line of text that matched
+#[derive(Debug, Clone, Serialize)]
+pub struct GrepMatch {
+    /// Tool (or `bundle:<name>` for bundle cheatsheets)
+    pub tool: String,
+    pub field: &'static str,
+    pub text: String,
+}
+
+/// Byte ranges of case-insensitive matches of `pattern` in `text`
+fn match_ranges(text: &str, pattern: &str) -> Vec<(usize, usize)> {
+    if pattern.is_empty() {
+        return Vec::new();
+    }
+    // ASCII lowercasing keeps byte offsets valid for the original text
+    let haystack = text.to_ascii_lowercase();
+    let needle = pattern.to_ascii_lowercase();
+    haystack
+        .match_indices(&needle)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect()
+}
+
+/// The text with its matches highlighted
+fn highlight(text: &str, pattern: &str) -> String {
+    let mut out = String::new();
+    let mut last = 0;
+    for (start, end) in match_ranges(text, pattern) {
+        out.push_str(&text[last..start]);
+        out.push_str(&text[start..end].red().bold().to_string());
+        last = end;
+    }
+    out.push_str(&text[last..]);
+    out
+}
+
+/// Every searchable line, by tool and field
+fn text_lines(db: &Database, fields: &[String]) -> Result<Vec<GrepMatch>> {
+    let wanted = |field: &str| fields.is_empty() || fields.iter().any(|f| f == field);
+    let mut lines = Vec::new();
+    let mut push = |tool: &str, field: &'static str, text: &str| {
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            lines.push(GrepMatch {
+                tool: tool.to_string(),
+                field,
+                text: line.to_string(),
+            });
+        }
+    };
+
+    if wanted("description") || wanted("notes") {
+        for tool in db.get_all_tools()? {
+            if wanted("description")
+                && let Some(description) = &tool.description
+            {
+                push(&tool.name, "description", description);
+            }
+            if wanted("notes")
+                && let Some(notes) = &tool.notes
+            {
+                push(&tool.name, "notes", notes);
+            }
+        }
+    }
+
+    if wanted("labels") {
+        let mut labels: Vec<_> = db.get_all_tool_labels()?.into_iter().collect();
+        labels.sort();
+        for (tool, labels) in labels {
+            push(&tool, "labels", &labels.join(", "));
+        }
+    }
+
+    if wanted("cheatsheet") {
+        for (key, json) in db.list_ai_cache(cache::CHEATSHEET.prefix)? {
+            let name = key.trim_start_matches(cache::CHEATSHEET.prefix);
+            // Tool cheatsheets carry the version they were made for
+            let cheatsheet = serde_json::from_str::<crate::ai::CachedCheatsheet>(&json)
+                .map(|cached| cached.cheatsheet)
+                .or_else(|_| serde_json::from_str::<crate::ai::Cheatsheet>(&json));
+            let Ok(cheatsheet) = cheatsheet else {
+                continue;
+            };
+            for section in &cheatsheet.sections {
+                for command in &section.commands {
+                    push(
+                        name,
+                        "cheatsheet",
+                        &format!("{}  # {}", command.cmd, command.desc),
+                    );
+                }
+            }
+        }
+    }
+
+    if wanted("extraction") {
+        for extraction in db.list_cached_extractions()? {
+            push(&extraction.name, "extraction", &extraction.description);
+        }
+    }
+
+    Ok(lines)
+}
+
+/// Lines of the chosen fields (all when empty) containing `pattern`,
+/// grouped by tool
+pub fn grep(db: &Database, pattern: &str, fields: &[String]) -> Result<Vec<GrepMatch>> {
+    let mut matches: Vec<GrepMatch> = text_lines(db, fields)?
+        .into_iter()
+        .filter(|line| !match_ranges(&line.text, pattern).is_empty())
+        .collect();
+    matches.sort_by(|a, b| a.tool.cmp(&b.tool));
+    Ok(matches)
+}
+
+/// Search notes, descriptions, labels, cheatsheets and AI extractions
+pub fn cmd_grep(db: &Database, pattern: &str, fields: &[String], json_output: bool) -> Result<()> {
+    let matches = grep(db, pattern, fields)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&matches)?);
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        println!("No matches for '{}'", pattern);
+        return Ok(());
+    }
+
+    let mut tools = 0;
+    let mut current: Option<&str> = None;
+    for m in &matches {
+        if current != Some(m.tool.as_str()) {
+            if current.is_some() {
+                println!();
+            }
+            println!("{}", m.tool.bold());
+            current = Some(&m.tool);
+            tools += 1;
+        }
+        println!("  {:12} {}", m.field.dimmed(), highlight(&m.text, pattern));
+    }
+
+    println!(
+        "\n{} {} match(es) in {} tool(s)",
+        ">".cyan(),
+        matches.len(),
+        tools
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Tool;
+
+    #[test]
+    fn test_match_ranges() {
+        assert_eq!(
+            match_ranges("WireGuard and wireguard-go", "wireguard"),
+            vec![(0, 9), (14, 23)]
+        );
+        assert_eq!(match_ranges("héllo wire", "WIRE"), vec![(7, 11)]);
+        assert!(match_ranges("anything", "").is_empty());
+    }
+
+    #[test]
+    fn test_grep_fields() {
+        let db = Database::open_in_memory().unwrap();
+        let mut tool = Tool::new("wg-tool").with_description("VPN helper");
+        tool.notes = Some("first line\nuse with WireGuard peers".to_string());
+        db.insert_tool(&tool).unwrap();
+        db.insert_tool(&Tool::new("other").with_description("wireguard UI"))
+            .unwrap();
+
+        let all = grep(&db, "wireguard", &[]).unwrap();
+        let found: Vec<_> = all.iter().map(|m| (m.tool.as_str(), m.field)).collect();
+        assert_eq!(found, vec![("other", "description"), ("wg-tool", "notes")]);
+        assert_eq!(all[1].text, "use with WireGuard peers");
+
+        let notes = grep(&db, "wireguard", &["notes".to_string()]).unwrap();
+        assert_eq!(notes.len(), 1);
+    }
+}
@@ -1,9 +1,12 @@
 //! Shared helper functions for command implementations
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
 
-use crate::models::Tool;
+use crate::db::Database;
+use crate::models::{InstallSource, Tool};
+use crate::project::ProjectTool;
+use crate::scanner::KnownTool;
 use crate::sources::{ManualSource, source_for};
 
 /// Prompt user for confirmation
@@ -17,6 +20,23 @@ pub fn confirm(prompt: &str) -> Result<bool> {
     Ok(input.trim().eq_ignore_ascii_case("y"))
 }
 
+/// Warn about likely secrets on stderr, one line per finding
+pub fn report_secrets(findings: &[crate::secrets::SecretFinding]) {
+    for finding in findings {
+        let location = match &finding.path {
+            Some(path) => format!("{}:{}", path.display(), finding.line),
+            None => format!("line {}", finding.line),
+        };
+        eprintln!(
+            "{} Possible {} at {}: {}",
+            "!".yellow(),
+            finding.kind,
+            location,
+            finding.excerpt.dimmed()
+        );
+    }
+}
+
 /// Extract package name from install command (e.g., "cargo install git-delta" -> "git-delta")
 pub fn extract_package_from_install_cmd(cmd: &str) -> Option<String> {
     let prefixes = [
@@ -62,6 +82,114 @@ pub fn fetch_tool_description(tool: &Tool) -> Option<(String, &'static str)> {
         .or_else(|| ManualSource::fetch_help_description(binary).map(|d| (d, "--help")))
 }
 
+/// Fetch the license of a single tool from its forge or package registry
+///
+/// Returns the license and where it came from.
+pub fn fetch_tool_license(
+    tool: &Tool,
+    repo: Option<&crate::forge::RepoRef>,
+) -> Option<(String, &'static str)> {
+    if let Some(repo) = repo
+        && let Ok(info) = repo.forge().repo_info(&repo.owner, &repo.repo)
+        && let Some(license) = info.license_id()
+    {
+        return Some((license.to_string(), repo.provider.as_str()));
+    }
+
+    let pkg = tool
+        .install_command
+        .as_ref()
+        .and_then(|c| extract_package_from_install_cmd(c))
+        .unwrap_or_else(|| tool.name.clone());
+    let source = source_for(&tool.source)?;
+    source.fetch_license(&pkg).map(|l| (l, source.name()))
+}
+
+/// A tool a project refers to, resolved against the database
+pub struct ProjectToolStatus {
+    pub found: ProjectTool,
+    /// The tracked tool, or one built from the known tools or the detection
+    pub tool: Tool,
+    pub tracked: bool,
+    pub installed: bool,
+}
+
+/// The tools detected in a project directory
+pub struct ProjectScan {
+    pub dir: std::path::PathBuf,
+    /// Directory name, used to name bundles
+    pub name: String,
+    pub tools: Vec<ProjectToolStatus>,
+}
+
+/// Detect the tools a project directory (default: the current one) refers to
+pub fn scan_project(db: &Database, path: Option<&str>) -> Result<ProjectScan> {
+    let dir = std::path::Path::new(path.unwrap_or("."))
+        .canonicalize()
+        .with_context(|| format!("Cannot open project directory '{}'", path.unwrap_or(".")))?;
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+    let detected = crate::project::detect_project_tools(&dir)?;
+    let tools = resolve_project_tools(db, detected, crate::scanner::is_installed)?;
+    Ok(ProjectScan { dir, name, tools })
+}
+
+/// Look detected tools up in the database, checking each binary with
+/// `is_installed`
+pub fn resolve_project_tools(
+    db: &Database,
+    detected: Vec<ProjectTool>,
+    is_installed: impl Fn(&str) -> bool,
+) -> Result<Vec<ProjectToolStatus>> {
+    let mut tools = Vec::new();
+    for found in detected {
+        let tracked = db.get_tool_by_name(&found.name)?;
+        let is_tracked = tracked.is_some();
+        let tool = tracked
+            .or_else(|| KnownTool::find(&found.name).map(KnownTool::to_tool))
+            .unwrap_or_else(|| {
+                Tool::new(&found.name)
+                    .with_source(found.source.clone().unwrap_or(InstallSource::Unknown))
+            });
+        let installed = is_installed(tool.binary_name.as_deref().unwrap_or(&tool.name));
+        tools.push(ProjectToolStatus {
+            found,
+            tool,
+            tracked: is_tracked,
+            installed,
+        });
+    }
+    Ok(tools)
+}
+
+/// List a project's tools under `heading`, marking installed and new ones
+pub fn print_project_tools(scan: &ProjectScan, heading: &str) {
+    println!(
+        "{} {} '{}' ({} detected):\n",
+        ">".cyan(),
+        heading,
+        scan.name.bold(),
+        scan.tools.len()
+    );
+    for status in &scan.tools {
+        let mark = if status.installed {
+            "+".green()
+        } else {
+            "-".red()
+        };
+        let new = if status.tracked { "" } else { " (new)" };
+        println!(
+            "  {} {:<20}{} {}",
+            mark,
+            status.found.name,
+            new.cyan(),
+            status.found.reason.dimmed()
+        );
+    }
+}
+
 /// Print a status change line
 pub fn print_status_change(name: &str, old_installed: bool, new_installed: bool) {
     let status = if new_installed {
@@ -1,16 +1,35 @@
-//! Insights commands: stats, info, overview, categories
+//! Insights commands: stats, info, overview, categories, licenses, rhythms,
+//! cleanup plan, adoption, shadowed binaries, disk usage, relationship graph,
+//! install timeline, overlapping tools, stale upstreams, HTML report, health
+//! scores
 
 use anyhow::Result;
 use colored::Colorize;
+use std::collections::BTreeMap;
 
-use crate::db::Database;
-use crate::scanner::KNOWN_TOOLS;
+use crate::db::{Database, UsageRhythm};
+use crate::health::ToolHealth;
+use crate::models::LicenseKind;
+use crate::scanner::known_tools;
 
 /// Show statistics about tracked tools
-pub fn cmd_stats(db: &Database) -> Result<()> {
+pub fn cmd_stats(db: &Database, json_output: bool) -> Result<()> {
     let (total, installed, favorites) = db.get_stats()?;
     let categories = db.get_categories()?;
 
+    if json_output {
+        let stats = serde_json::json!({
+            "total": total,
+            "installed": installed,
+            "missing": total - installed,
+            "favorites": favorites,
+            "categories": categories.len(),
+            "known_tools": known_tools().len(),
+        });
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
     println!("{}", "Hoard Statistics".bold());
     println!("{}", "=".repeat(20));
     println!();
@@ -20,7 +39,7 @@ pub fn cmd_stats(db: &Database) -> Result<()> {
     println!("Favorites:       {}", favorites.to_string().yellow());
     println!("Categories:      {}", categories.len());
     println!();
-    println!("Known tools:     {}", KNOWN_TOOLS.len());
+    println!("Known tools:     {}", known_tools().len());
 
     Ok(())
 }
@@ -78,7 +97,7 @@ pub fn cmd_overview(db: &Database) -> Result<()> {
             tools_with_usage.push((tool.name.clone(), usage.use_count));
         }
     }
-    tools_with_usage.sort_by(|a, b| b.1.cmp(&a.1));
+    tools_with_usage.sort_by_key(|t| std::cmp::Reverse(t.1));
 
     if tools_with_usage.is_empty() {
         println!("   (no usage data - run 'hoards sync --usage')");
@@ -127,10 +146,1669 @@ pub fn cmd_overview(db: &Database) -> Result<()> {
     Ok(())
 }
 
+/// Weekday names, Monday first
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Show when tools get used: a weekday × hour heatmap and per-tool peaks
+///
+/// Buckets come from timestamped history (fish, zsh extended, nushell) and
+/// from the shell hook.
+pub fn cmd_rhythms(db: &Database, tool: Option<&str>, limit: usize) -> Result<()> {
+    use crate::icons::heat_shade;
+
+    crate::commands::flush_usage_log(db)?;
+    let rhythms = db.get_all_usage_rhythms()?;
+
+    let rhythm = match tool {
+        Some(name) => rhythms.get(name).cloned().unwrap_or_default(),
+        None => {
+            let mut all = UsageRhythm::default();
+            for rhythm in rhythms.values() {
+                all.merge(rhythm);
+            }
+            all
+        }
+    };
+
+    if rhythm.total() == 0 {
+        println!("{} No timestamped usage yet", "!".yellow());
+        println!("  Rhythms need history with timestamps (fish, zsh extended history, nushell)");
+        println!(
+            "  or hook mode: {}",
+            "hoards usage config --mode hook".cyan()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} Usage rhythm of {} ({} timed uses)\n",
+        ">".cyan(),
+        tool.unwrap_or("all tools").bold(),
+        rhythm.total()
+    );
+
+    // Two columns per hour, labelled every three hours
+    let hours: String = (0..24).step_by(3).map(|h| format!("{:<6}", h)).collect();
+    println!("     {}", hours.dimmed());
+    let max = rhythm.counts.iter().flatten().copied().max().unwrap_or(0);
+    for (day, counts) in WEEKDAYS.iter().zip(&rhythm.counts) {
+        let cells: String = counts
+            .iter()
+            .map(|&c| heat_shade(c, max).to_string().repeat(2))
+            .collect();
+        println!("  {} {}", day, cells.green());
+    }
+
+    if let (Some(hour), Some(day)) = (rhythm.peak_hour(), rhythm.peak_weekday()) {
+        println!(
+            "\n  Busiest: {} and {:02}:00-{:02}:00 · weekend share {:.0}%",
+            WEEKDAYS[day].cyan(),
+            hour,
+            (hour + 1) % 24,
+            rhythm.weekend_share() * 100.0
+        );
+    }
+
+    if tool.is_none() {
+        let mut tools: Vec<(&String, &UsageRhythm)> = rhythms.iter().collect();
+        tools.sort_by_key(|(name, r)| (std::cmp::Reverse(r.total()), name.to_string()));
+
+        println!(
+            "\n  {:<20} {:>6}  {:>9}  {:>8}  {:>7}",
+            "Tool".bold(),
+            "Uses".bold(),
+            "Peak hour".bold(),
+            "Peak day".bold(),
+            "Weekend".bold()
+        );
+        for (name, r) in tools.iter().take(limit) {
+            println!(
+                "  {:<20} {:>6}  {:>9}  {:>8}  {:>6.0}%",
+                name,
+                r.total(),
+                r.peak_hour()
+                    .map(|h| format!("{:02}:00", h))
+                    .unwrap_or_default(),
+                r.peak_weekday().map(|d| WEEKDAYS[d]).unwrap_or_default(),
+                r.weekend_share() * 100.0
+            );
+        }
+        if tools.len() > limit {
+            println!(
+                "\n{} Showing top {} of {} tools. Use {} to see more.",
+                ">".cyan(),
+                limit,
+                tools.len(),
+                "--limit".yellow()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Show installs and removals per month, flagging bursts of installs
+///
+/// Only the last `months` months are charted. With `month` (`YYYY-MM`), the
+/// tools installed and removed that month are listed instead.
+pub fn cmd_timeline(
+    db: &Database,
+    months: usize,
+    month: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    use crate::db::monthly_timeline;
+    use std::collections::HashMap;
+
+    let categories: HashMap<String, String> = db
+        .list_tools(false, None)?
+        .into_iter()
+        .filter_map(|t| Some((t.name, t.category?)))
+        .collect();
+    let timeline = monthly_timeline(&db.get_timeline_events()?, &categories);
+
+    if let Some(month) = month {
+        let Some(entry) = timeline.iter().find(|m| m.month == month) else {
+            crate::fail!(NotFound, "No installs or removals in {}", month);
+        };
+        if json_output {
+            println!("{}", serde_json::to_string_pretty(entry)?);
+            return Ok(());
+        }
+        println!(
+            "{} {}{}\n",
+            ">".cyan(),
+            month.bold(),
+            entry
+                .burst
+                .as_ref()
+                .map(|b| format!(" (burst: {})", b).yellow().to_string())
+                .unwrap_or_default()
+        );
+        for tool in &entry.installed {
+            let category = categories.get(tool).map(String::as_str).unwrap_or("-");
+            println!("  {} {} {}", "+".green(), tool, category.dimmed());
+        }
+        for tool in &entry.removed {
+            println!("  {} {}", "-".red(), tool);
+        }
+        return Ok(());
+    }
+
+    let shown = &timeline[timeline.len().saturating_sub(months)..];
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(shown)?);
+        return Ok(());
+    }
+    if shown.is_empty() {
+        println!("No installs recorded yet. Run 'hoards scan' first.");
+        return Ok(());
+    }
+
+    println!("{}", "📅 Install timeline".bold());
+    println!();
+    const BAR_WIDTH: usize = 30;
+    let max = shown
+        .iter()
+        .map(|m| m.installed.len())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for entry in shown {
+        let installs = entry.installed.len();
+        let bar = "█".repeat((installs * BAR_WIDTH).div_ceil(max));
+        let removed = if entry.removed.is_empty() {
+            String::new()
+        } else {
+            format!(" -{}", entry.removed.len()).red().to_string()
+        };
+        let burst = entry
+            .burst
+            .as_ref()
+            .map(|b| format!("  ! burst: {}", b).yellow().to_string())
+            .unwrap_or_default();
+        println!(
+            "  {}  {:<width$} {:>3}{}{}",
+            entry.month,
+            bar.green(),
+            installs,
+            removed,
+            burst,
+            width = BAR_WIDTH
+        );
+    }
+
+    let installs: usize = shown.iter().map(|m| m.installed.len()).sum();
+    let removals: usize = shown.iter().map(|m| m.removed.len()).sum();
+    println!(
+        "\n  {} installs, {} removals over {} month{}",
+        installs,
+        removals,
+        shown.len(),
+        if shown.len() == 1 { "" } else { "s" }
+    );
+    if let Some(latest) = shown.iter().rfind(|m| m.burst.is_some()) {
+        println!(
+            "\n{} See the tools of the latest burst with {}",
+            ">".cyan(),
+            format!("hoards insights timeline --month {}", latest.month).cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Report whether recently installed tools actually got used
+///
+/// Each install opens a 30-day adoption window. Tools still unused when it
+/// closes are listed as forgotten; `keep` marks a tool as wanted anyway.
+pub fn cmd_adoption(db: &Database, days: i64, keep: Option<&str>) -> Result<()> {
+    use crate::db::{ADOPTION_WINDOW_DAYS, AdoptionStatus};
+    use chrono::Utc;
+
+    if let Some(tool) = keep {
+        if db.keep_adoption(tool)? {
+            println!("{} Keeping '{}'", "+".green(), tool);
+        } else {
+            println!("{} '{}' has no adoption window", "!".yellow(), tool);
+            println!("  Windows open when hoards sees a tool get installed");
+        }
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    let adoptions = db.list_adoptions(days)?;
+    if adoptions.is_empty() {
+        println!(
+            "{} No tools installed in the last {} days",
+            ">".dimmed(),
+            days
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {}",
+        "Tool adoption".bold(),
+        format!("(installed in the last {} days)", days).dimmed()
+    );
+    println!();
+
+    for adoption in &adoptions {
+        let age = (now - adoption.installed_at).num_days();
+        let (marker, status) = match adoption.status(now) {
+            AdoptionStatus::Adopted => ("+".green(), format!("used {} times", adoption.uses)),
+            AdoptionStatus::Pending => (
+                "~".yellow(),
+                format!("not used yet, {} days left", ADOPTION_WINDOW_DAYS - age),
+            ),
+            AdoptionStatus::Forgotten => ("-".red(), "never used".to_string()),
+            AdoptionStatus::Kept => ("*".cyan(), "kept".to_string()),
+        };
+        println!(
+            "  {} {:20} {:>4}d ago  {}",
+            marker, adoption.tool, age, status
+        );
+    }
+
+    let count = |status| adoptions.iter().filter(|a| a.status(now) == status).count();
+    let forgotten = count(AdoptionStatus::Forgotten);
+    println!();
+    println!(
+        "  {} adopted, {} pending, {} forgotten, {} kept",
+        count(AdoptionStatus::Adopted).to_string().green(),
+        count(AdoptionStatus::Pending).to_string().yellow(),
+        forgotten.to_string().red(),
+        count(AdoptionStatus::Kept).to_string().cyan()
+    );
+    if forgotten > 0 {
+        println!(
+            "\n{} Uninstall forgotten tools with {} or keep them with {}",
+            ">".cyan(),
+            "hoards uninstall <tool>".yellow(),
+            "hoards insights adoption --keep <tool>".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Why a tool is proposed for removal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CleanupReason {
+    Unused,
+    Duplicate,
+    Orphaned,
+}
+
+impl CleanupReason {
+    fn label(&self) -> &'static str {
+        match self {
+            CleanupReason::Unused => "unused",
+            CleanupReason::Duplicate => "duplicate",
+            CleanupReason::Orphaned => "orphaned",
+        }
+    }
+}
+
+/// One proposed removal
+struct CleanupCandidate {
+    name: String,
+    source: String,
+    reason: CleanupReason,
+    detail: String,
+}
+
+/// Build a cleanup plan, sorted by reason then name
+///
+/// Unused tools are only proposed when some usage is recorded: without it a
+/// tool's age says nothing about whether it gets used.
+fn plan_cleanup(
+    tools: &[crate::models::Tool],
+    usage: &std::collections::HashMap<String, crate::db::ToolUsage>,
+    replaces: &[crate::db::ToolRelation],
+    bundled: &std::collections::HashSet<String>,
+    labels: &std::collections::HashMap<String, Vec<String>>,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Vec<CleanupCandidate> {
+    use chrono::{DateTime, Utc};
+    use std::collections::HashSet;
+
+    let uses = |name: &str| usage.get(name).map(|u| u.use_count).unwrap_or(0);
+    let mut plan: BTreeMap<String, CleanupCandidate> = BTreeMap::new();
+    let mut propose = |tool: &crate::models::Tool, reason: CleanupReason, detail: String| {
+        plan.entry(tool.name.clone())
+            .or_insert_with(|| CleanupCandidate {
+                name: tool.name.clone(),
+                source: tool.source.to_string(),
+                reason,
+                detail,
+            });
+    };
+
+    // Unused: last use (or, if never used, when it was added) before the cutoff
+    for tool in tools.iter().filter(|_| !usage.is_empty()) {
+        let detail = match usage.get(&tool.name) {
+            Some(u) if u.use_count > 0 => {
+                let Some(last) = u
+                    .last_used
+                    .as_deref()
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                else {
+                    continue;
+                };
+                if last.with_timezone(&Utc) >= cutoff {
+                    continue;
+                }
+                format!("last used {}", last.format("%Y-%m-%d"))
+            }
+            _ if tool.created_at < cutoff => {
+                format!("never used, added {}", tool.created_at.format("%Y-%m-%d"))
+            }
+            _ => continue,
+        };
+        propose(tool, CleanupReason::Unused, detail);
+    }
+
+    // Duplicates: an installed replacement that is used more
+    let installed: HashSet<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+    for relation in replaces {
+        let Some(tool) = tools.iter().find(|t| t.name == relation.related) else {
+            continue;
+        };
+        if installed.contains(relation.tool.as_str()) && uses(&relation.tool) > uses(&tool.name) {
+            let detail = format!(
+                "replaced by {} ({} vs {} uses)",
+                relation.tool,
+                uses(&relation.tool),
+                uses(&tool.name)
+            );
+            propose(tool, CleanupReason::Duplicate, detail);
+        }
+    }
+
+    // Orphaned: installed by a bundle that no longer lists them
+    for tool in tools {
+        let Some(from) = labels
+            .get(&tool.name)
+            .and_then(|l| l.iter().find_map(|l| l.strip_prefix("bundle:")))
+        else {
+            continue;
+        };
+        if !bundled.contains(&tool.name) {
+            propose(
+                tool,
+                CleanupReason::Orphaned,
+                format!("installed by bundle '{}', no longer listed", from),
+            );
+        }
+    }
+
+    let mut plan: Vec<CleanupCandidate> = plan.into_values().collect();
+    plan.sort_by(|a, b| a.reason.cmp(&b.reason).then(a.name.cmp(&b.name)));
+    plan
+}
+
+/// Indices of the named tools in a plan, refusing names it doesn't have
+fn select_named(plan: &[CleanupCandidate], names: &[String]) -> Result<Vec<usize>> {
+    let missing: Vec<&str> = names
+        .iter()
+        .filter(|name| !plan.iter().any(|c| &c.name == *name))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        crate::fail!(
+            Invalid,
+            "Not in the cleanup plan: {}; nothing was uninstalled",
+            missing.join(", ")
+        );
+    }
+    Ok((0..plan.len())
+        .filter(|i| names.contains(&plan[*i].name))
+        .collect())
+}
+
+/// Propose a concrete removal list from usage and scan data
+///
+/// Candidates are installed tools unused for more than `days` days,
+/// tools with a `replaces` relation to an installed replacement that gets
+/// more use, and tools a bundle installed (labelled `bundle:<name>`) that
+/// no bundle lists any more. Favorites and libraries (which have no commands
+/// to use) are never proposed. Each candidate's disk footprint is measured
+/// so the plan shows what removing it frees. With `apply`, the tools picked
+/// from a checklist, or the `only` tools named, are uninstalled.
+pub fn cmd_cleanup_plan(db: &Database, days: i64, apply: bool, only: &[String]) -> Result<()> {
+    use crate::commands::install::cmd_uninstall;
+    use crate::db::RelationKind;
+    use crate::footprint::{format_size, measure_tools};
+    use crate::scanner::is_installed;
+    use chrono::{Duration, Utc};
+    use comfy_table::{
+        Cell, CellAlignment, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS,
+        presets::UTF8_FULL,
+    };
+    use std::collections::{HashMap, HashSet};
+
+    let cutoff = Utc::now() - Duration::days(days);
+    let tools: Vec<_> = db
+        .list_tools(true, None)?
+        .into_iter()
+        .filter(|t| !t.is_favorite && t.kind != crate::models::ToolKind::Library)
+        .collect();
+    let usage: HashMap<String, _> = db.get_all_usage()?.into_iter().collect();
+    let mut bundled: HashSet<String> = HashSet::new();
+    for name in db.get_bundle_names()? {
+        if let Some(bundle) = db.get_flattened_bundle(&name)? {
+            bundled.extend(bundle.tools);
+        }
+    }
+
+    // Skip anything already gone from PATH
+    let plan: Vec<CleanupCandidate> = plan_cleanup(
+        &tools,
+        &usage,
+        &db.list_relations(RelationKind::Replaces)?,
+        &bundled,
+        &db.get_all_tool_labels()?,
+        cutoff,
+    )
+    .into_iter()
+    .filter(|c| {
+        let binary = tools
+            .iter()
+            .find(|t| t.name == c.name)
+            .and_then(|t| t.binary_name.as_deref())
+            .unwrap_or(&c.name);
+        is_installed(binary)
+    })
+    .collect();
+
+    if plan.is_empty() {
+        println!("{} Nothing to clean up", "+".green());
+        if usage.is_empty() {
+            println!(
+                "  Run {} first so unused tools can be found",
+                "hoards usage scan".cyan()
+            );
+        }
+        return Ok(());
+    }
+
+    let candidates: Vec<_> = tools
+        .iter()
+        .filter(|t| plan.iter().any(|c| c.name == t.name))
+        .cloned()
+        .collect();
+    let sizes: HashMap<String, u64> = measure_tools(&candidates).into_iter().collect();
+    for (name, bytes) in &sizes {
+        db.set_tool_size(name, *bytes)?;
+    }
+    let size_of = |name: &str| format_size(sizes.get(name).copied().unwrap_or(0));
+
+    println!("{}", "🧹 Cleanup plan".bold());
+    println!();
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Tool").fg(Color::Cyan),
+            Cell::new("Source").fg(Color::Cyan),
+            Cell::new("Reason").fg(Color::Cyan),
+            Cell::new("Detail").fg(Color::Cyan),
+            Cell::new("Size").fg(Color::Cyan),
+        ]);
+    for c in &plan {
+        table.add_row(vec![
+            Cell::new(&c.name),
+            Cell::new(&c.source),
+            Cell::new(c.reason.label()),
+            Cell::new(&c.detail),
+            Cell::new(size_of(&c.name)).set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!("{table}");
+
+    let count = |reason| plan.iter().filter(|c| c.reason == reason).count();
+    println!(
+        "\n  {} unused (>{} days), {} duplicate(s), {} orphaned",
+        count(CleanupReason::Unused).to_string().yellow(),
+        days,
+        count(CleanupReason::Duplicate).to_string().yellow(),
+        count(CleanupReason::Orphaned).to_string().yellow()
+    );
+    println!(
+        "  Removing all of them frees about {}",
+        format_size(sizes.values().sum()).bold()
+    );
+    if usage.is_empty() {
+        println!(
+            "  {} No usage recorded, so unused tools aren't proposed; run {} first",
+            "i".cyan(),
+            "hoards usage scan".cyan()
+        );
+    }
+
+    if !apply {
+        println!(
+            "\n{} Run with {} to pick tools to uninstall",
+            ">".cyan(),
+            "--apply".yellow()
+        );
+        return Ok(());
+    }
+
+    let selected: Vec<usize> = if !only.is_empty() {
+        select_named(&plan, only)?
+    } else {
+        use dialoguer::{MultiSelect, theme::ColorfulTheme};
+
+        let options: Vec<String> = plan
+            .iter()
+            .map(|c| {
+                format!(
+                    "{} ({}: {}, {})",
+                    c.name,
+                    c.reason.label(),
+                    c.detail,
+                    size_of(&c.name)
+                )
+            })
+            .collect();
+        println!();
+        match MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select tools to uninstall")
+            .items(&options)
+            .interact_opt()?
+        {
+            Some(indices) if !indices.is_empty() => indices,
+            _ => {
+                println!("{} No tools selected", ">".dimmed());
+                return Ok(());
+            }
+        }
+    };
+
+    println!();
+    let mut failed = 0;
+    for idx in &selected {
+        if let Err(e) = cmd_uninstall(db, &plan[*idx].name, false, true) {
+            println!("{} {:#}", "!".red(), e);
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        crate::fail!(
+            Partial,
+            "{} of {} uninstall(s) failed",
+            failed,
+            selected.len()
+        );
+    }
+    Ok(())
+}
+
+/// What to do about a shadowed binary
+enum ShadowAction {
+    Keep,
+    Uninstall(crate::commands::SafeCommand),
+    Prefer(std::path::PathBuf),
+}
+
+/// List binaries with more than one copy on PATH, and with `resolve` pick
+/// for each tracked one whether to uninstall a shadowed copy or alias the
+/// binary to it
+///
+/// Only binaries of installed tracked tools are shown unless `all`.
+pub fn cmd_shadowed(db: &Database, all: bool, resolve: bool, json_output: bool) -> Result<()> {
+    use crate::commands::{cmd_alias_add, get_safe_uninstall_command, validate_binary_name};
+    use crate::scanner::scan_shadowed_binaries;
+    use dialoguer::{Select, theme::ColorfulTheme};
+
+    let tools = db.list_tools(true, None)?;
+    let tool_of = |binary: &str| {
+        tools
+            .iter()
+            .find(|t| t.binary_name.as_deref().unwrap_or(&t.name) == binary)
+    };
+    let shadowed: Vec<_> = scan_shadowed_binaries()
+        .into_iter()
+        .filter(|s| all || tool_of(&s.binary).is_some())
+        .collect();
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&shadowed)?);
+        return Ok(());
+    }
+
+    if shadowed.is_empty() {
+        println!(
+            "{} No {}binaries shadowed on PATH",
+            "+".green(),
+            if all { "" } else { "tracked " }
+        );
+        return Ok(());
+    }
+
+    let source_label = |source: &Option<crate::models::InstallSource>| {
+        source
+            .as_ref()
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "unknown source".to_string())
+    };
+    println!(
+        "{} {} binaries with more than one copy on PATH:\n",
+        ">".cyan(),
+        shadowed.len()
+    );
+    for s in &shadowed {
+        let tool = tool_of(&s.binary);
+        println!(
+            "  {}{}",
+            s.binary.bold(),
+            tool.filter(|t| t.name != s.binary)
+                .map(|t| format!(" ({})", t.name).dimmed().to_string())
+                .unwrap_or_default()
+        );
+        println!(
+            "    {}     {} ({})",
+            "runs".green(),
+            s.winner().path.display(),
+            source_label(&s.winner().source)
+        );
+        for copy in s.shadowed() {
+            println!(
+                "    {} {} ({})",
+                "shadowed".dimmed(),
+                copy.path.display(),
+                source_label(&copy.source)
+            );
+        }
+        if let Some(tool) = tool
+            && s.winner().source.as_ref() != Some(&tool.source)
+            && s.shadowed()
+                .iter()
+                .any(|c| c.source.as_ref() == Some(&tool.source))
+        {
+            println!(
+                "    {} the {} install hoards tracks doesn't run",
+                "!".yellow(),
+                tool.source
+            );
+        }
+    }
+
+    if !resolve {
+        println!(
+            "\n{} Run with {} to uninstall or prefer a shadowed copy",
+            ">".cyan(),
+            "--resolve".yellow()
+        );
+        return Ok(());
+    }
+
+    println!();
+    let mut failed = 0;
+    for s in &shadowed {
+        let Some(tool) = tool_of(&s.binary) else {
+            continue;
+        };
+        let mut options = vec![format!("Keep {} first", s.winner().path.display())];
+        let mut actions = vec![ShadowAction::Keep];
+        for copy in s.shadowed() {
+            if let Some(source) = &copy.source {
+                // The tracked package if it's the tracked source's copy
+                let package = if *source == tool.source {
+                    &tool.name
+                } else {
+                    &s.binary
+                };
+                if let Ok(Some(cmd)) = get_safe_uninstall_command(package, &source.to_string()) {
+                    options.push(format!("Uninstall {} ({})", copy.path.display(), cmd));
+                    actions.push(ShadowAction::Uninstall(cmd));
+                }
+            }
+            if validate_binary_name(&s.binary).is_ok() {
+                options.push(format!(
+                    "Prefer {} (alias {} to it)",
+                    copy.path.display(),
+                    s.binary
+                ));
+                actions.push(ShadowAction::Prefer(copy.path.clone()));
+            }
+        }
+
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("{}: which copy should run?", s.binary))
+            .items(&options)
+            .default(0)
+            .interact_opt()?;
+        match choice.map(|i| &actions[i]) {
+            None | Some(ShadowAction::Keep) => {}
+            Some(ShadowAction::Uninstall(cmd)) => {
+                println!("{} Running: {}", ">".cyan(), cmd);
+                if !cmd.execute()?.success() {
+                    println!("{} Failed to uninstall the shadowed copy", "!".red());
+                    failed += 1;
+                }
+            }
+            Some(ShadowAction::Prefer(path)) => {
+                let path = path.to_string_lossy().to_string();
+                cmd_alias_add(db, &s.binary, vec![path], Some(&tool.name))?;
+            }
+        }
+    }
+
+    if failed > 0 {
+        crate::fail!(Partial, "{} uninstall(s) failed", failed);
+    }
+    Ok(())
+}
+
+/// Measure the disk footprint of installed tools and list the largest
+///
+/// Sizes are stored for the cleanup plan. Only the `limit` largest tools are
+/// listed; the total covers all of them.
+pub fn cmd_size(db: &Database, limit: usize, json_output: bool) -> Result<()> {
+    use crate::footprint::{format_size, measure_tools};
+    use comfy_table::{
+        Cell, CellAlignment, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS,
+        presets::UTF8_FULL,
+    };
+
+    let tools = db.list_tools(true, None)?;
+    if tools.is_empty() {
+        println!("No installed tools found. Run 'hoards scan' first.");
+        return Ok(());
+    }
+
+    if !json_output {
+        println!(
+            "{} Measuring {} installed tools...\n",
+            ">".cyan(),
+            tools.len()
+        );
+    }
+    let mut sizes = measure_tools(&tools);
+    for (name, bytes) in &sizes {
+        db.set_tool_size(name, *bytes)?;
+    }
+    sizes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let total: u64 = sizes.iter().map(|(_, bytes)| bytes).sum();
+
+    if json_output {
+        let rows: Vec<_> = sizes
+            .iter()
+            .map(|(name, bytes)| serde_json::json!({ "name": name, "bytes": bytes }))
+            .collect();
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "tools": rows, "total": total }))?
+        );
+        return Ok(());
+    }
+
+    let source_of = |name: &str| {
+        tools
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| t.source.to_string())
+            .unwrap_or_default()
+    };
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Tool").fg(Color::Cyan),
+            Cell::new("Source").fg(Color::Cyan),
+            Cell::new("Size").fg(Color::Cyan),
+        ]);
+    for (name, bytes) in sizes.iter().take(limit) {
+        table.add_row(vec![
+            Cell::new(name),
+            Cell::new(source_of(name)),
+            Cell::new(format_size(*bytes)).set_alignment(CellAlignment::Right),
+        ]);
+    }
+    println!("{table}");
+
+    if sizes.len() > limit {
+        let rest: u64 = sizes.iter().skip(limit).map(|(_, bytes)| bytes).sum();
+        println!(
+            "  ... and {} more ({})",
+            sizes.len() - limit,
+            format_size(rest)
+        );
+    }
+    println!(
+        "\n  Total: {} across {} tools",
+        format_size(total).bold(),
+        sizes.len()
+    );
+
+    Ok(())
+}
+
+/// Categories that say what kind of tool something is rather than the job
+/// it does, so many tools sharing one isn't overlap
+const BROAD_CATEGORIES: [&str; 6] = ["cli", "dev", "lang", "library", "runtime", "system"];
+
+/// One tool in a crowded category
+#[derive(serde::Serialize)]
+struct OverlapTool {
+    name: String,
+    uses: i64,
+    last_used: Option<String>,
+    favorite: bool,
+}
+
+/// Installed tools doing the same job
+#[derive(serde::Serialize)]
+struct Overlap {
+    /// Category or label the tools share
+    group: String,
+    /// Most used first
+    tools: Vec<OverlapTool>,
+    /// The most used tool, when any of them has been used
+    keep: Option<String>,
+}
+
+/// Group tools by shared category or label, keeping groups of `min` or more
+///
+/// Groups are sorted most crowded first; each lists its tools most used
+/// first and keeps the most used one, if any has been used.
+fn find_overlaps(
+    tools: &[crate::models::Tool],
+    usage: &std::collections::HashMap<String, crate::db::ToolUsage>,
+    labels: &std::collections::HashMap<String, Vec<String>>,
+    min: usize,
+) -> Vec<Overlap> {
+    let mut groups: BTreeMap<String, Vec<&crate::models::Tool>> = BTreeMap::new();
+    for tool in tools {
+        let category = tool
+            .category
+            .iter()
+            .filter(|c| !BROAD_CATEGORIES.contains(&c.as_str()));
+        let tool_labels = labels
+            .get(&tool.name)
+            .into_iter()
+            .flatten()
+            .filter(|l| !l.contains(':'));
+        for group in category.chain(tool_labels) {
+            let members = groups.entry(group.to_lowercase()).or_default();
+            if !members.iter().any(|m| m.name == tool.name) {
+                members.push(tool);
+            }
+        }
+    }
+
+    let mut overlaps: Vec<Overlap> = groups
+        .into_iter()
+        .filter(|(_, members)| members.len() >= min.max(2))
+        .map(|(group, members)| {
+            let mut tools: Vec<OverlapTool> = members
+                .iter()
+                .map(|t| {
+                    let usage = usage.get(&t.name);
+                    OverlapTool {
+                        name: t.name.clone(),
+                        uses: usage.map_or(0, |u| u.use_count),
+                        last_used: usage.and_then(|u| u.last_used.clone()),
+                        favorite: t.is_favorite,
+                    }
+                })
+                .collect();
+            tools.sort_by(|a, b| {
+                b.uses
+                    .cmp(&a.uses)
+                    .then(b.last_used.cmp(&a.last_used))
+                    .then(a.name.cmp(&b.name))
+            });
+            let keep = tools.first().filter(|t| t.uses > 0).map(|t| t.name.clone());
+            Overlap { group, tools, keep }
+        })
+        .collect();
+    overlaps.sort_by_key(|o| std::cmp::Reverse(o.tools.len()));
+    overlaps
+}
+
+/// Find categories and labels where several installed tools do the same job
+///
+/// Installed applications are grouped by category and by label (a tool in
+/// category `search` and one labelled `search` share a group), and groups of
+/// at least `min` tools are reported, most crowded first. The most used tool
+/// of each is recommended to keep and the others, favorites aside, are
+/// candidates to uninstall. Broad categories (`cli`, `lang`, ...) and
+/// provenance labels (`bundle:`, `ai:`) say nothing about overlap and are
+/// skipped.
+pub fn cmd_overlap(db: &Database, min: usize, json_output: bool) -> Result<()> {
+    use crate::models::ToolKind;
+    use std::collections::HashMap;
+
+    let tools: Vec<_> = db
+        .list_tools(true, None)?
+        .into_iter()
+        .filter(|t| t.kind == ToolKind::Application)
+        .collect();
+    let usage: HashMap<_, _> = db.get_all_usage()?.into_iter().collect();
+    let labels = db.get_all_tool_labels()?;
+    let overlaps = find_overlaps(&tools, &usage, &labels, min);
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&overlaps)?);
+        return Ok(());
+    }
+
+    if overlaps.is_empty() {
+        println!(
+            "{} No category or label has {} or more installed tools",
+            "+".green(),
+            min.max(2)
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {}",
+        "Overlapping tools".bold(),
+        format!("(categories and labels with {}+ tools)", min.max(2)).dimmed()
+    );
+
+    let mut unused_groups = 0;
+    for overlap in &overlaps {
+        println!();
+        println!(
+            "{} {} ({} tools)",
+            "!".yellow(),
+            overlap.group.bold(),
+            overlap.tools.len()
+        );
+        for tool in &overlap.tools {
+            let uses = match tool.uses {
+                0 => format!("{:>12}", "never used").dimmed(),
+                1 => format!("{:>12}", "1 use").normal(),
+                n => format!("{:>12}", format!("{} uses", n)).normal(),
+            };
+            let mut note = String::new();
+            if overlap.keep.as_deref() == Some(tool.name.as_str()) {
+                note.push_str(&" keep".green().to_string());
+            }
+            if tool.favorite {
+                note.push_str(&" *".yellow().to_string());
+            }
+            println!("    {:20} {}{}", tool.name, uses, note);
+        }
+
+        match &overlap.keep {
+            Some(keep) => {
+                let drop: Vec<&str> = overlap
+                    .tools
+                    .iter()
+                    .filter(|t| &t.name != keep && !t.favorite)
+                    .map(|t| t.name.as_str())
+                    .collect();
+                if drop.is_empty() {
+                    println!("  {} Keep {}; the others are favorites", ">".cyan(), keep);
+                } else {
+                    println!(
+                        "  {} Keep {}; consider uninstalling {}",
+                        ">".cyan(),
+                        keep.green(),
+                        drop.join(", ")
+                    );
+                }
+            }
+            None => {
+                unused_groups += 1;
+                println!("  {} None of these have recorded usage", "i".cyan());
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} Uninstall tools with {}",
+        ">".cyan(),
+        "hoards uninstall <tool>".yellow()
+    );
+    if unused_groups > 0 {
+        println!(
+            "{} Record usage with {} to get a recommendation for every group",
+            ">".cyan(),
+            "hoards usage scan".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// A tracked tool whose upstream has gone quiet
+#[derive(serde::Serialize)]
+struct StaleUpstream {
+    name: String,
+    repo: String,
+    /// Newest of the last push and the last release (RFC 3339)
+    last_activity: Option<String>,
+    /// Days since `last_activity`
+    days: Option<i64>,
+    archived: bool,
+    installed: bool,
+}
+
+/// Pick the repositories quiet for at least `days` days, or archived
+///
+/// Returns them archived first, then quietest first, along with how many
+/// repositories had no push or release date to judge by.
+fn find_stale(
+    infos: &[(String, crate::db::GitHubInfo)],
+    releases: &std::collections::HashMap<String, String>,
+    installed: &std::collections::HashMap<String, bool>,
+    days: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> (Vec<StaleUpstream>, usize) {
+    use chrono::{DateTime, Utc};
+
+    let mut stale = Vec::new();
+    let mut undated = 0;
+    for (name, info) in infos {
+        let last_activity = [info.pushed_at.as_ref(), releases.get(name)]
+            .into_iter()
+            .flatten()
+            .filter_map(|at| DateTime::parse_from_rfc3339(at).ok())
+            .max();
+        let age = last_activity.map(|at| (now - at.with_timezone(&Utc)).num_days());
+        if age.is_none() {
+            undated += 1;
+        }
+        if !info.archived && age.is_none_or(|age| age < days) {
+            continue;
+        }
+        let repo = if info.is_github() {
+            format!("{}/{}", info.repo_owner, info.repo_name)
+        } else {
+            format!("{}/{}/{}", info.host, info.repo_owner, info.repo_name)
+        };
+        stale.push(StaleUpstream {
+            name: name.clone(),
+            repo,
+            last_activity: last_activity.map(|at| at.to_rfc3339()),
+            days: age,
+            archived: info.archived,
+            installed: installed.get(name).copied().unwrap_or(false),
+        });
+    }
+    stale.sort_by(|a, b| {
+        b.archived
+            .cmp(&a.archived)
+            .then(b.days.cmp(&a.days))
+            .then(a.name.cmp(&b.name))
+    });
+    (stale, undated)
+}
+
+/// List tracked tools whose upstream has had no commit or release in `days`
+/// days
+///
+/// Works from stored forge metadata: each repository's last push and, for
+/// watched tools, the newest release seen. Archived repositories are always
+/// listed. This is about upstream health only; whether a tool gets used
+/// locally is `insights unused`.
+pub fn cmd_stale(db: &Database, days: i64, json_output: bool) -> Result<()> {
+    use comfy_table::{
+        Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
+    };
+    use std::collections::HashMap;
+
+    let infos = db.get_all_github_info()?;
+    let releases = db.get_latest_release_dates()?;
+    let installed: HashMap<String, bool> = db
+        .list_tools(false, None)?
+        .into_iter()
+        .map(|t| (t.name, t.is_installed))
+        .collect();
+    let (stale, undated) = find_stale(&infos, &releases, &installed, days, chrono::Utc::now());
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&stale)?);
+
+        return Ok(());
+    }
+
+    if infos.is_empty() {
+        println!(
+            "{} No forge metadata yet. Fetch it with {}",
+            "i".cyan(),
+            "hoards gh sync".cyan()
+        );
+        return Ok(());
+    }
+
+    if stale.is_empty() {
+        println!(
+            "{} Every upstream has had a commit or release in the last {} days",
+            "+".green(),
+            days
+        );
+    } else {
+        println!(
+            "{} {}",
+            "Stale upstreams".bold(),
+            format!("(no commit or release in {} days)", days).dimmed()
+        );
+        println!();
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(vec![
+                Cell::new("Tool").fg(Color::Cyan),
+                Cell::new("Repository").fg(Color::Cyan),
+                Cell::new("Last activity").fg(Color::Cyan),
+                Cell::new("Status").fg(Color::Cyan),
+            ]);
+        for upstream in &stale {
+            let last = upstream
+                .last_activity
+                .as_deref()
+                .map(|at| at[..10].to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let status = match upstream.days {
+                _ if upstream.archived => Cell::new("archived").fg(Color::Red),
+                Some(age) if age >= 730 => Cell::new(format!("{} years quiet", age / 365)),
+                Some(age) if age >= 60 => Cell::new(format!("{} months quiet", age / 30)),
+                Some(age) => Cell::new(format!("{} days quiet", age)),
+                None => Cell::new("-"),
+            };
+            let mut name = Cell::new(&upstream.name);
+            if !upstream.installed {
+                name = name.fg(Color::DarkGrey);
+            }
+            table.add_row(vec![
+                name,
+                Cell::new(&upstream.repo),
+                Cell::new(last),
+                status,
+            ]);
+        }
+        println!("{table}");
+        println!(
+            "\n  {} of {} tools with forge metadata",
+            stale.len().to_string().yellow(),
+            infos.len()
+        );
+    }
+
+    if undated > 0 {
+        println!(
+            "{} {} tool(s) have no commit or release date; refresh them with {}",
+            "i".cyan(),
+            undated,
+            "hoards gh sync".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Write a self-contained HTML dashboard of the hoard
+///
+/// `html` is the file to write, or a directory to write `index.html` into.
+/// Libraries are left out, as in `list`. With `check_updates`, package
+/// managers are asked for newer versions first, as `hoards updates` does.
+pub fn cmd_report(db: &Database, html: &std::path::Path, check_updates: bool) -> Result<()> {
+    use super::updates_cmd::collect_updates;
+    use crate::models::ToolKind;
+    use crate::report::{Report, ReportTool};
+    use std::collections::HashMap;
+
+    let path = if html.extension().is_some_and(|ext| ext == "html") {
+        html.to_path_buf()
+    } else {
+        html.join("index.html")
+    };
+
+    let updates: HashMap<(String, String), String> = if check_updates {
+        println!("{} Checking for updates...", ">".cyan());
+        collect_updates(None)
+            .into_iter()
+            .map(|u| ((u.name, u.source), u.latest))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+    let usage: HashMap<_, _> = db.get_all_usage()?.into_iter().collect();
+
+    let tools = db
+        .list_tools(false, None)?
+        .into_iter()
+        .filter(|t| t.kind != ToolKind::Library)
+        .map(|t| {
+            let usage = usage.get(&t.name);
+            let source = t.source.to_string();
+            ReportTool {
+                update: updates.get(&(t.name.clone(), source.clone())).cloned(),
+                uses: usage.map_or(0, |u| u.use_count),
+                last_used: usage.and_then(|u| u.last_used.clone()),
+                name: t.name,
+                source,
+                category: t.category,
+                version: t.installed_version,
+                installed: t.is_installed,
+            }
+        })
+        .collect();
+    let report = Report {
+        generated_at: chrono::Utc::now(),
+        tools,
+        updates_checked: check_updates,
+    };
+
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, report.to_html())?;
+    println!(
+        "{} Wrote report of {} tools to {}",
+        "+".green(),
+        report.tools.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Recompute and store the health score of every installed tool
+///
+/// Usage only counts once usage is tracked at all, updates only for watched
+/// tools with a known installed version. Scores are returned lowest first.
+pub(crate) fn refresh_health(db: &Database) -> Result<Vec<(String, ToolHealth)>> {
+    use super::config::config_health;
+    use crate::health::{config_score, update_score, upstream_score, usage_score};
+    use crate::models::ToolKind;
+    use crate::updates::version_is_newer;
+    use chrono::{DateTime, Utc};
+    use std::collections::HashMap;
+
+    let now = Utc::now();
+    let days_since = |at: &String| {
+        DateTime::parse_from_rfc3339(at)
+            .ok()
+            .map(|at| (now - at.with_timezone(&Utc)).num_days())
+    };
+
+    let usage: HashMap<_, _> = db.get_all_usage()?.into_iter().collect();
+    let github: HashMap<_, _> = db.get_all_github_info()?.into_iter().collect();
+    let release_dates = db.get_latest_release_dates()?;
+    let release_tags = db.get_release_tags()?;
+    let configs = config_health(db)?;
+
+    let mut scores = Vec::new();
+    for tool in db.list_tools(true, None)? {
+        if tool.kind == ToolKind::Library {
+            continue;
+        }
+        let usage_signal = (!usage.is_empty()).then(|| match usage.get(&tool.name) {
+            // Usage from before timestamps were kept counts as recent
+            Some(u) if u.use_count > 0 => {
+                usage_score(Some(u.last_used.as_ref().and_then(days_since).unwrap_or(0)))
+            }
+            _ => usage_score(None),
+        });
+        let updates_signal = tool
+            .installed_version
+            .as_deref()
+            .zip(release_tags.get(&tool.name))
+            .map(|(installed, tags)| {
+                update_score(
+                    tags.iter()
+                        .filter(|tag| version_is_newer(tag, installed))
+                        .count(),
+                )
+            });
+        let upstream_signal = github.get(&tool.name).and_then(|info| {
+            let quiet_days = [info.pushed_at.as_ref(), release_dates.get(&tool.name)]
+                .into_iter()
+                .flatten()
+                .filter_map(days_since)
+                .min();
+            upstream_score(quiet_days, info.archived)
+        });
+        let configs_signal = tool
+            .id
+            .and_then(|id| configs.get(&id))
+            .and_then(|(intact, total)| config_score(*intact, *total));
+
+        if let Some(health) = ToolHealth::from_signals(
+            usage_signal,
+            updates_signal,
+            upstream_signal,
+            configs_signal,
+        ) {
+            scores.push((tool.name, health));
+        }
+    }
+    db.replace_tool_health(&scores)?;
+
+    scores.sort_by(|a, b| a.1.score.cmp(&b.1.score).then(a.0.cmp(&b.0)));
+    Ok(scores)
+}
+
+/// List the installed tools most in need of attention, by health score
+///
+/// Scores are recomputed first, which also refreshes the badges `list` and
+/// the TUI show.
+pub fn cmd_health_scores(db: &Database, limit: usize, json_output: bool) -> Result<()> {
+    use crate::health::HealthGrade;
+    use comfy_table::{
+        Cell, CellAlignment, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS,
+        presets::UTF8_FULL,
+    };
+
+    let scores = refresh_health(db)?;
+
+    if json_output {
+        let rows: Vec<_> = scores
+            .iter()
+            .take(limit)
+            .map(|(name, health)| serde_json::json!({ "name": name, "health": health }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if scores.is_empty() {
+        println!("No installed tools to score. Run 'hoards scan' first.");
+        return Ok(());
+    }
+
+    let color = |score: u8| {
+        if score >= 70 {
+            Color::Green
+        } else if score >= 40 {
+            Color::Yellow
+        } else {
+            Color::Red
+        }
+    };
+    let signal = |score: Option<u8>| match score {
+        Some(score) => Cell::new(score)
+            .fg(color(score))
+            .set_alignment(CellAlignment::Right),
+        None => Cell::new("-")
+            .fg(Color::DarkGrey)
+            .set_alignment(CellAlignment::Right),
+    };
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Tool").fg(Color::Cyan),
+            Cell::new("Health").fg(Color::Cyan),
+            Cell::new("Usage").fg(Color::Cyan),
+            Cell::new("Updates").fg(Color::Cyan),
+            Cell::new("Upstream").fg(Color::Cyan),
+            Cell::new("Configs").fg(Color::Cyan),
+            Cell::new("Weakest").fg(Color::Cyan),
+        ]);
+    for (name, health) in scores.iter().take(limit) {
+        table.add_row(vec![
+            Cell::new(name),
+            signal(Some(health.score)),
+            signal(health.usage),
+            signal(health.updates),
+            signal(health.upstream),
+            signal(health.configs),
+            Cell::new(health.weakest().unwrap_or("-")),
+        ]);
+    }
+    println!("{table}");
+
+    let poor = scores
+        .iter()
+        .filter(|(_, h)| h.grade() == HealthGrade::Poor)
+        .count();
+    println!(
+        "\n  {} of {} scored tools below 40",
+        poor.to_string().red(),
+        scores.len()
+    );
+    println!(
+        "{} Signals come from {}, watched releases, {} and {}; '-' means unknown",
+        "i".cyan(),
+        "hoards usage scan".cyan(),
+        "hoards gh sync".cyan(),
+        "hoards config".cyan()
+    );
+
+    Ok(())
+}
+
+/// Print why installed tools are on the machine as a Graphviz or Mermaid graph
+///
+/// Edges come from bundles (their members and the `bundle:<name>` labels
+/// bundle installs leave), AI suggestions (`ai:<feature>` labels),
+/// dependencies recorded by scans and `replaces` relations. With `tool`, only
+/// what leads to that tool and what it leads to is kept.
+pub fn cmd_graph(db: &Database, format: &str, tool: Option<&str>) -> Result<()> {
+    use crate::db::RelationKind;
+    use crate::graph::{EdgeKind, Graph, NodeKind};
+    use std::collections::HashSet;
+
+    let installed: HashSet<String> = db
+        .list_tools(true, None)?
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    let mut graph = Graph::default();
+
+    for bundle in db.list_bundles()? {
+        for member in bundle.tools.iter().filter(|t| installed.contains(*t)) {
+            graph.add_edge(
+                (NodeKind::Bundle, &bundle.name),
+                EdgeKind::Installs,
+                (NodeKind::Tool, member),
+            );
+        }
+        for included in &bundle.includes {
+            graph.add_edge(
+                (NodeKind::Bundle, &bundle.name),
+                EdgeKind::Includes,
+                (NodeKind::Bundle, included),
+            );
+        }
+    }
+    for (name, labels) in db.get_all_tool_labels()? {
+        if !installed.contains(&name) {
+            continue;
+        }
+        for label in labels {
+            if let Some(bundle) = label.strip_prefix("bundle:") {
+                graph.add_edge(
+                    (NodeKind::Bundle, bundle),
+                    EdgeKind::Installs,
+                    (NodeKind::Tool, &name),
+                );
+            } else if let Some(feature) = label.strip_prefix("ai:") {
+                graph.add_edge(
+                    (NodeKind::Ai, feature),
+                    EdgeKind::Suggested,
+                    (NodeKind::Tool, &name),
+                );
+            }
+        }
+    }
+    for (kind, edge) in [
+        (RelationKind::DependsOn, EdgeKind::DependsOn),
+        (RelationKind::Replaces, EdgeKind::Replaces),
+    ] {
+        for relation in db.list_relations(kind)? {
+            if installed.contains(&relation.tool) && installed.contains(&relation.related) {
+                graph.add_edge(
+                    (NodeKind::Tool, &relation.tool),
+                    edge,
+                    (NodeKind::Tool, &relation.related),
+                );
+            }
+        }
+    }
+
+    if let Some(tool) = tool {
+        graph = graph.around(tool);
+    }
+    if graph.is_empty() {
+        // Keep stdout clean for whatever the graph is piped into
+        eprintln!(
+            "{} No relationships found. Bundles, AI installs and 'hoards scan' record them.",
+            "i".cyan()
+        );
+        return Ok(());
+    }
+
+    match format {
+        "mermaid" => print!("{}", graph.to_mermaid()),
+        _ => print!("{}", graph.to_dot()),
+    }
+    Ok(())
+}
+
+/// Summarize the licenses of tracked tools
+///
+/// Copyleft tools and tools without a known license are listed separately
+/// for users with compliance requirements. With `fetch`, missing licenses are
+/// looked up on the tool's forge or package registry first.
+pub fn cmd_licenses(db: &Database, fetch: bool) -> Result<()> {
+    use super::helpers::fetch_tool_license;
+
+    let mut tools = db.list_tools(false, None)?;
+    if tools.is_empty() {
+        println!("No tools found. Add some tools first.");
+        return Ok(());
+    }
+
+    if fetch {
+        println!("{} Fetching missing licenses...", ">".cyan());
+        let mut found = 0;
+        for tool in tools.iter_mut().filter(|t| t.license.is_none()) {
+            let repo = db.get_github_info(&tool.name)?.map(|info| info.repo_ref());
+            if let Some((license, from)) = fetch_tool_license(tool, repo.as_ref()) {
+                db.set_tool_license(&tool.name, Some(&license))?;
+                println!(
+                    "  {} {} {} {}",
+                    "+".green(),
+                    tool.name,
+                    license,
+                    from.dimmed()
+                );
+                tool.license = Some(license);
+                found += 1;
+            }
+        }
+        println!(
+            "  Found {} license{}",
+            found,
+            if found == 1 { "" } else { "s" }
+        );
+        println!();
+    }
+
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut copyleft = Vec::new();
+    let mut unknown = Vec::new();
+    for tool in &tools {
+        match tool.license.as_deref() {
+            Some(license) => {
+                *counts.entry(license).or_default() += 1;
+                match LicenseKind::of(license) {
+                    LicenseKind::Copyleft | LicenseKind::WeakCopyleft => copyleft.push(tool),
+                    LicenseKind::Unknown => unknown.push(tool),
+                    LicenseKind::Permissive => {}
+                }
+            }
+            None => unknown.push(tool),
+        }
+    }
+
+    let mut counts: Vec<(&str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    println!("{}", "License Distribution".bold());
+    println!();
+    for (license, count) in &counts {
+        let kind = LicenseKind::of(license);
+        println!("  {:<28} {:>4}  {}", license, count, kind.label().dimmed());
+    }
+    let missing = tools.iter().filter(|t| t.license.is_none()).count();
+    if missing > 0 {
+        println!("  {:<28} {:>4}", "(none known)".dimmed(), missing);
+    }
+
+    if !copyleft.is_empty() {
+        println!();
+        println!(
+            "{} {}",
+            "Copyleft:".bold(),
+            copyleft.len().to_string().yellow()
+        );
+        for tool in &copyleft {
+            let license = tool.license.as_deref().unwrap_or_default();
+            println!(
+                "  {} {:<24} {} {}",
+                "!".yellow(),
+                tool.name,
+                license,
+                LicenseKind::of(license).label().dimmed()
+            );
+        }
+    }
+
+    if !unknown.is_empty() {
+        println!();
+        println!(
+            "{} {}",
+            "Unlicensed or unrecognized:".bold(),
+            unknown.len().to_string().red()
+        );
+        for tool in &unknown {
+            println!(
+                "  {} {:<24} {}",
+                "?".red(),
+                tool.name,
+                tool.license.as_deref().unwrap_or("-").dimmed()
+            );
+        }
+        if !fetch && missing > 0 {
+            println!();
+            println!(
+                "{} Look up missing licenses with {}",
+                ">".cyan(),
+                "hoards insights licenses --fetch".cyan()
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Show all categories with counts
-pub fn cmd_categories(db: &Database) -> Result<()> {
+pub fn cmd_categories(db: &Database, json_output: bool) -> Result<()> {
     let category_counts = db.get_category_counts()?;
 
+    if json_output {
+        let counts: BTreeMap<_, _> = category_counts.into_iter().collect();
+        println!("{}", serde_json::to_string_pretty(&counts)?);
+        return Ok(());
+    }
+
     if category_counts.is_empty() {
         println!("No categories found. Add some tools first.");
         return Ok(());
@@ -144,3 +1822,202 @@ pub fn cmd_categories(db: &Database) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{RelationKind, ToolRelation, ToolUsage};
+    use crate::models::Tool;
+    use chrono::{Duration, Utc};
+    use std::collections::{HashMap, HashSet};
+
+    fn old_tool(name: &str) -> Tool {
+        let mut tool = Tool::new(name);
+        tool.created_at = Utc::now() - Duration::days(365);
+        tool
+    }
+
+    fn used(count: i64, days_ago: i64) -> ToolUsage {
+        ToolUsage {
+            use_count: count,
+            last_used: Some((Utc::now() - Duration::days(days_ago)).to_rfc3339()),
+            first_seen: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_plan_cleanup() {
+        let tools = vec![
+            old_tool("exa"),
+            old_tool("eza"),
+            old_tool("httpie"),
+            old_tool("jq"),
+            old_tool("tldr"),
+        ];
+        let usage = HashMap::from([
+            ("eza".to_string(), used(50, 1)),
+            ("exa".to_string(), used(3, 10)),
+            ("jq".to_string(), used(8, 200)),
+        ]);
+        let replaces = vec![ToolRelation {
+            tool: "eza".to_string(),
+            related: "exa".to_string(),
+            kind: RelationKind::Replaces,
+            note: None,
+        }];
+        let labels = HashMap::from([("tldr".to_string(), vec!["bundle:docs".to_string()])]);
+        let cutoff = Utc::now() - Duration::days(90);
+
+        let plan = plan_cleanup(&tools, &usage, &replaces, &HashSet::new(), &labels, cutoff);
+        let found: Vec<(&str, CleanupReason)> =
+            plan.iter().map(|c| (c.name.as_str(), c.reason)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("httpie", CleanupReason::Unused),
+                ("jq", CleanupReason::Unused),
+                ("tldr", CleanupReason::Unused),
+                ("exa", CleanupReason::Duplicate),
+            ]
+        );
+        assert!(plan[0].detail.starts_with("never used"));
+
+        // Still listed by a bundle: not orphaned
+        let bundled = HashSet::from(["tldr".to_string()]);
+        let plan = plan_cleanup(&tools, &usage, &[], &bundled, &labels, cutoff);
+        assert!(plan.iter().all(|c| c.reason == CleanupReason::Unused));
+    }
+
+    #[test]
+    fn test_plan_cleanup_without_usage() {
+        // Nothing recorded yet: tool age alone must not propose removals
+        let tools = vec![old_tool("jq"), old_tool("tldr")];
+        let labels = HashMap::from([("tldr".to_string(), vec!["bundle:docs".to_string()])]);
+        let cutoff = Utc::now() - Duration::days(90);
+
+        let plan = plan_cleanup(
+            &tools,
+            &HashMap::new(),
+            &[],
+            &HashSet::new(),
+            &labels,
+            cutoff,
+        );
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].name, "tldr");
+        assert_eq!(plan[0].reason, CleanupReason::Orphaned);
+    }
+
+    #[test]
+    fn test_select_named() {
+        let candidate = |name: &str| CleanupCandidate {
+            name: name.to_string(),
+            source: "cargo".to_string(),
+            reason: CleanupReason::Unused,
+            detail: String::new(),
+        };
+        let plan = vec![candidate("exa"), candidate("httpie"), candidate("jq")];
+
+        let names = vec!["jq".to_string(), "exa".to_string()];
+        assert_eq!(select_named(&plan, &names).unwrap(), vec![0, 2]);
+
+        let names = vec!["jq".to_string(), "ripgrep".to_string()];
+        let err = select_named(&plan, &names).unwrap_err();
+        assert_eq!(
+            crate::exit_code::ExitCode::of(&err),
+            crate::exit_code::ExitCode::Invalid
+        );
+    }
+
+    #[test]
+    fn test_find_overlaps() {
+        let mut fav = Tool::new("ag").with_category("search");
+        fav.is_favorite = true;
+        let tools = vec![
+            Tool::new("ripgrep").with_category("search"),
+            fav,
+            Tool::new("fd").with_category("files"),
+            Tool::new("broot").with_category("cli"),
+            Tool::new("ugrep").with_category("cli"),
+            Tool::new("eza").with_category("files"),
+        ];
+        let labels = HashMap::from([
+            ("ugrep".to_string(), vec!["Search".to_string()]),
+            ("broot".to_string(), vec!["bundle:nav".to_string()]),
+            ("fd".to_string(), vec!["bundle:nav".to_string()]),
+        ]);
+        let usage = HashMap::from([
+            ("ripgrep".to_string(), used(40, 30)),
+            ("ugrep".to_string(), used(40, 2)),
+            ("ag".to_string(), used(5, 1)),
+        ]);
+
+        // Categories and labels share groups; `cli` and `bundle:` don't count
+        let overlaps = find_overlaps(&tools, &usage, &labels, 2);
+        let groups: Vec<_> = overlaps.iter().map(|o| o.group.as_str()).collect();
+        assert_eq!(groups, vec!["search", "files"]);
+
+        // Most used first, the most recently used breaking ties
+        let search: Vec<_> = overlaps[0].tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(search, vec!["ugrep", "ripgrep", "ag"]);
+        assert_eq!(overlaps[0].keep.as_deref(), Some("ugrep"));
+        assert!(overlaps[0].tools[2].favorite);
+
+        // Nothing in the group has been used
+        assert_eq!(overlaps[1].keep, None);
+
+        let overlaps = find_overlaps(&tools, &usage, &labels, 3);
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].group, "search");
+    }
+
+    #[test]
+    fn test_find_stale() {
+        use crate::db::GitHubInfo;
+        use crate::forge::ForgeProvider;
+
+        let now = Utc::now();
+        let ago = |days: i64| Some((now - Duration::days(days)).to_rfc3339());
+        let info = |name: &str, pushed_at: Option<String>, archived: bool| {
+            let info = GitHubInfo {
+                provider: ForgeProvider::GitHub,
+                host: "github.com".to_string(),
+                repo_owner: "owner".to_string(),
+                repo_name: name.to_string(),
+                description: None,
+                stars: 0,
+                language: None,
+                homepage: None,
+                pushed_at,
+                open_issues: None,
+                archived,
+                contributors: None,
+            };
+            (name.to_string(), info)
+        };
+        let mut codeberg = info("lazy", ago(500), false);
+        codeberg.1.provider = ForgeProvider::Gitea;
+        codeberg.1.host = "codeberg.org".to_string();
+        let infos = vec![
+            info("fresh", ago(10), false),
+            info("quiet", ago(400), false),
+            info("released", ago(400), false),
+            info("archived", ago(5), true),
+            info("undated", None, false),
+            codeberg,
+        ];
+        // A recent release keeps a repository with old pushes off the list
+        let releases = HashMap::from([("released".to_string(), ago(3).unwrap())]);
+        let installed = HashMap::from([("quiet".to_string(), true)]);
+
+        let (stale, undated) = find_stale(&infos, &releases, &installed, 365, now);
+        assert_eq!(undated, 1);
+        let names: Vec<_> = stale.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["archived", "lazy", "quiet"]);
+        assert_eq!(stale[0].days, Some(5));
+        assert_eq!(stale[1].repo, "codeberg.org/owner/lazy");
+        assert_eq!(stale[2].repo, "owner/quiet");
+        assert!(stale[2].installed);
+        assert!(!stale[1].installed);
+    }
+}
@@ -1,12 +1,14 @@
-//! Install, uninstall, and upgrade commands
+//! Install, uninstall, upgrade and pin commands
 //!
 //! Provides safe command execution without shell interpolation.
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use colored::Colorize;
 use std::process::Command;
 
-use crate::{Database, InstallSource, Tool, is_installed};
+use super::alias::warn_dangling_aliases;
+use crate::fail;
+use crate::{Database, InstallSource, Tool, ToolKind, is_installed};
 
 // ==================== Safe Command Execution ====================
 
@@ -14,7 +16,7 @@ use crate::{Database, InstallSource, Tool, is_installed};
 #[derive(Debug, Clone)]
 pub struct SafeCommand {
     /// The program to run (e.g., "cargo", "sudo")
-    pub program: &'static str,
+    pub program: String,
     /// Arguments to pass to the program
     pub args: Vec<String>,
     /// Human-readable description for display
@@ -24,10 +26,13 @@ pub struct SafeCommand {
 impl SafeCommand {
     /// Execute the command and return its exit status
     pub fn execute(&self) -> Result<std::process::ExitStatus> {
-        Command::new(self.program)
+        tracing::debug!("running {}", self.display);
+        let status = Command::new(&self.program)
             .args(&self.args)
             .status()
-            .with_context(|| format!("Failed to execute: {}", self.display))
+            .with_context(|| format!("Failed to execute: {}", self.display))?;
+        tracing::debug!("{} exited with {}", self.display, status);
+        Ok(status)
     }
 }
 
@@ -197,10 +202,10 @@ pub fn handle_running_process(binary_name: &str) -> Result<bool> {
 /// Returns an error if the name contains dangerous characters
 pub fn validate_package_name(name: &str) -> Result<()> {
     if name.is_empty() {
-        anyhow::bail!("Package name cannot be empty");
+        fail!(Invalid, "Package name cannot be empty");
     }
     if name.len() > 200 {
-        anyhow::bail!("Package name too long (max 200 characters)");
+        fail!(Invalid, "Package name too long (max 200 characters)");
     }
     // Allow alphanumeric, dash, underscore, dot, and @ (for scoped npm packages)
     // Also allow / for npm scoped packages like @types/node
@@ -208,7 +213,8 @@ pub fn validate_package_name(name: &str) -> Result<()> {
         c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '@' || c == '/'
     });
     if !valid {
-        anyhow::bail!(
+        fail!(
+            Invalid,
             "Package name '{}' contains invalid characters. \
              Only alphanumeric, dash, underscore, dot, @, and / are allowed.",
             name
@@ -216,7 +222,7 @@ pub fn validate_package_name(name: &str) -> Result<()> {
     }
     // Prevent path traversal
     if name.contains("..") {
-        anyhow::bail!("Package name cannot contain '..'");
+        fail!(Invalid, "Package name cannot contain '..'");
     }
     Ok(())
 }
@@ -225,17 +231,18 @@ pub fn validate_package_name(name: &str) -> Result<()> {
 /// More restrictive than package names - no @ or / allowed
 pub fn validate_binary_name(name: &str) -> Result<()> {
     if name.is_empty() {
-        anyhow::bail!("Binary name cannot be empty");
+        fail!(Invalid, "Binary name cannot be empty");
     }
     if name.len() > 100 {
-        anyhow::bail!("Binary name too long (max 100 characters)");
+        fail!(Invalid, "Binary name too long (max 100 characters)");
     }
     // Binary names: alphanumeric, dash, underscore, dot only
     let valid = name
         .chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
     if !valid {
-        anyhow::bail!(
+        fail!(
+            Invalid,
             "Binary name '{}' contains invalid characters. \
              Only alphanumeric, dash, underscore, and dot are allowed.",
             name
@@ -243,7 +250,7 @@ pub fn validate_binary_name(name: &str) -> Result<()> {
     }
     // Prevent path traversal
     if name.contains("..") {
-        anyhow::bail!("Binary name cannot contain '..'");
+        fail!(Invalid, "Binary name cannot contain '..'");
     }
     Ok(())
 }
@@ -251,17 +258,18 @@ pub fn validate_binary_name(name: &str) -> Result<()> {
 /// Validate a version string
 pub fn validate_version(version: &str) -> Result<()> {
     if version.is_empty() {
-        anyhow::bail!("Version cannot be empty");
+        fail!(Invalid, "Version cannot be empty");
     }
     if version.len() > 50 {
-        anyhow::bail!("Version too long (max 50 characters)");
+        fail!(Invalid, "Version too long (max 50 characters)");
     }
     // Allow alphanumeric, dash, dot, plus (for semver build metadata)
     let valid = version
         .chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '+');
     if !valid {
-        anyhow::bail!(
+        fail!(
+            Invalid,
             "Version '{}' contains invalid characters. \
              Only alphanumeric, dash, dot, and + are allowed.",
             version
@@ -311,61 +319,64 @@ pub fn get_safe_install_command(
 
     let cmd = match (source, version) {
         ("cargo", Some(v)) => Some(SafeCommand {
-            program: "cargo",
+            program: "cargo".into(),
             args: vec!["install".into(), format!("{}@{}", name, v)],
             display: format!("cargo install {}@{}", name, v),
         }),
         ("cargo", None) => Some(SafeCommand {
-            program: "cargo",
+            program: "cargo".into(),
             args: vec!["install".into(), name.into()],
             display: format!("cargo install {}", name),
         }),
         ("pip", Some(v)) => Some(SafeCommand {
-            program: "pip",
+            program: "pip".into(),
             args: vec!["install".into(), format!("{}=={}", name, v)],
             display: format!("pip install {}=={}", name, v),
         }),
         ("pip", None) => Some(SafeCommand {
-            program: "pip",
+            program: "pip".into(),
             args: vec!["install".into(), "--upgrade".into(), name.into()],
             display: format!("pip install --upgrade {}", name),
         }),
         ("npm", Some(v)) => Some(SafeCommand {
-            program: "npm",
+            program: "npm".into(),
             args: vec!["install".into(), "-g".into(), format!("{}@{}", name, v)],
             display: format!("npm install -g {}@{}", name, v),
         }),
         ("npm", None) => Some(SafeCommand {
-            program: "npm",
+            program: "npm".into(),
             args: vec!["install".into(), "-g".into(), name.into()],
             display: format!("npm install -g {}", name),
         }),
         ("apt", _) => Some(SafeCommand {
-            program: "sudo",
+            program: "sudo".into(),
             args: vec!["apt".into(), "install".into(), "-y".into(), name.into()],
             display: format!("sudo apt install -y {}", name),
         }),
         ("brew", Some(v)) => Some(SafeCommand {
-            program: "brew",
+            program: "brew".into(),
             args: vec!["install".into(), format!("{}@{}", name, v)],
             display: format!("brew install {}@{}", name, v),
         }),
         ("brew", None) => Some(SafeCommand {
-            program: "brew",
+            program: "brew".into(),
             args: vec!["install".into(), name.into()],
             display: format!("brew install {}", name),
         }),
         ("snap", _) => Some(SafeCommand {
-            program: "sudo",
+            program: "sudo".into(),
             args: vec!["snap".into(), "install".into(), name.into()],
             display: format!("sudo snap install {}", name),
         }),
         ("flatpak", _) => Some(SafeCommand {
-            program: "flatpak",
+            program: "flatpak".into(),
             args: vec!["install".into(), "-y".into(), name.into()],
             display: format!("flatpak install -y {}", name),
         }),
-        _ => None,
+        (source, version) => match crate::sources::plugin(source) {
+            Some(plugin) => Some(plugin.install_safe_command(name, version)?),
+            None => None,
+        },
     };
     Ok(cmd)
 }
@@ -376,53 +387,131 @@ pub fn get_safe_uninstall_command(name: &str, source: &str) -> Result<Option<Saf
 
     let cmd = match source {
         "cargo" => Some(SafeCommand {
-            program: "cargo",
+            program: "cargo".into(),
             args: vec!["uninstall".into(), name.into()],
             display: format!("cargo uninstall {}", name),
         }),
         "pip" => Some(SafeCommand {
-            program: "pip",
+            program: "pip".into(),
             args: vec!["uninstall".into(), "-y".into(), name.into()],
             display: format!("pip uninstall -y {}", name),
         }),
         "npm" => Some(SafeCommand {
-            program: "npm",
+            program: "npm".into(),
             args: vec!["uninstall".into(), "-g".into(), name.into()],
             display: format!("npm uninstall -g {}", name),
         }),
         "apt" => Some(SafeCommand {
-            program: "sudo",
+            program: "sudo".into(),
             args: vec!["apt".into(), "remove".into(), "-y".into(), name.into()],
             display: format!("sudo apt remove -y {}", name),
         }),
         "brew" => Some(SafeCommand {
-            program: "brew",
+            program: "brew".into(),
             args: vec!["uninstall".into(), name.into()],
             display: format!("brew uninstall {}", name),
         }),
         "snap" => Some(SafeCommand {
-            program: "sudo",
+            program: "sudo".into(),
             args: vec!["snap".into(), "remove".into(), name.into()],
             display: format!("sudo snap remove {}", name),
         }),
         "flatpak" => Some(SafeCommand {
-            program: "flatpak",
+            program: "flatpak".into(),
             args: vec!["uninstall".into(), "-y".into(), name.into()],
             display: format!("flatpak uninstall -y {}", name),
         }),
-        _ => None,
+        source => match crate::sources::plugin(source) {
+            Some(plugin) => plugin.uninstall_safe_command(name)?,
+            None => None,
+        },
     };
     Ok(cmd)
 }
 
+// ==================== Pins ====================
+
+/// Whether installing `version` (None = latest) would move a tool off its
+/// pin; a pin without a version holds whatever is installed
+fn moves_pin(pin: Option<&str>, version: Option<&str>) -> bool {
+    match pin {
+        Some(pinned) => version != Some(pinned),
+        None => true,
+    }
+}
+
+/// Refuse to move a pinned tool unless forced with `flag`
+fn check_pin(name: &str, pin: Option<&str>, force: bool, flag: &str) -> Result<()> {
+    let pinned_at = pin.map_or_else(|| "its installed version".to_string(), str::to_string);
+    if !force {
+        bail!(
+            "'{}' is pinned at {}; use {} to override, or 'hoards unpin {}'",
+            name,
+            pinned_at,
+            flag,
+            name
+        );
+    }
+    println!(
+        "{} '{}' is pinned at {}; overriding with {}",
+        "!".yellow(),
+        name,
+        pinned_at,
+        flag
+    );
+    Ok(())
+}
+
+/// The version to install given the tool's pin (`Some(None)` for a hold)
+///
+/// A pinned version is what gets installed unless another is asked for,
+/// which needs `force_pin`. A hold has no version to install, so a missing
+/// tool installs as asked, but naming a version still moves it.
+fn pinned_install_version(
+    name: &str,
+    pin: Option<Option<String>>,
+    version: Option<String>,
+    force_pin: bool,
+) -> Result<Option<String>> {
+    match (pin, version) {
+        (Some(Some(pinned)), None) => {
+            println!("{} '{}' is pinned at {}", "i".cyan(), name, pinned);
+            Ok(Some(pinned))
+        }
+        (Some(pin), Some(version)) => {
+            if moves_pin(pin.as_deref(), Some(&version)) {
+                check_pin(name, pin.as_deref(), force_pin, "--override-pin")?;
+            }
+            Ok(Some(version))
+        }
+        (_, version) => Ok(version),
+    }
+}
+
 // ==================== Commands ====================
 
+/// Install a tool, where `force` skips the confirmation and `override_pin`
+/// installs a pinned tool at another version
 pub fn cmd_install(
     db: &Database,
     name: &str,
     source: Option<String>,
     version: Option<String>,
     force: bool,
+    override_pin: bool,
+) -> Result<()> {
+    install_tool(db, name, source, version, force, override_pin)
+}
+
+/// Install a tool, where `skip_prompt` skips the confirmation and
+/// `force_pin` allows moving a pinned tool to another version
+pub(crate) fn install_tool(
+    db: &Database,
+    name: &str,
+    source: Option<String>,
+    version: Option<String>,
+    skip_prompt: bool,
+    force_pin: bool,
 ) -> Result<()> {
     // Check if already installed
     if is_installed(name) {
@@ -442,24 +531,37 @@ pub fn cmd_install(
         tool.source.to_string()
     } else {
         // Tool not in database, need source argument
-        println!("{} Tool '{}' not in database", "!".yellow(), name);
-        println!(
-            "  Specify a source with: hoards install {} --source <cargo|pip|npm|apt|brew|snap>",
+        fail!(
+            NotFound,
+            "Tool '{}' not in database; specify a source with: hoards install {} --source <cargo|pip|npm|apt|brew|snap>",
+            name,
             name
         );
-        return Ok(());
     };
 
+    let version = pinned_install_version(name, db.get_pins()?.remove(name), version, force_pin)?;
+
+    // Release binaries are downloaded directly rather than via a package manager
+    if install_source == "github" {
+        return crate::commands::github::cmd_gh_install(
+            db,
+            name,
+            version.as_deref(),
+            None,
+            None,
+            skip_prompt,
+        );
+    }
+
     // Get safe install command (validates package name)
     let install_cmd = match get_safe_install_command(name, &install_source, version.as_deref())? {
         Some(cmd) => cmd,
-        None => {
-            println!(
-                "Don't know how to install '{}' from '{}'",
-                name, install_source
-            );
-            return Ok(());
-        }
+        None => fail!(
+            Invalid,
+            "Don't know how to install '{}' from '{}'",
+            name,
+            install_source
+        ),
     };
 
     // Show plan
@@ -467,7 +569,7 @@ pub fn cmd_install(
     println!("  {}: {}", install_source.cyan(), install_cmd);
 
     // Confirm
-    if !force {
+    if !skip_prompt {
         println!();
         print!("Proceed? [y/N] ");
         std::io::Write::flush(&mut std::io::stdout())?;
@@ -488,8 +590,7 @@ pub fn cmd_install(
     let status = install_cmd.execute()?;
 
     if !status.success() {
-        println!("{} Install failed", "!".red());
-        return Ok(());
+        bail!("Install of '{}' failed", name);
     }
 
     let version_msg = version
@@ -521,15 +622,31 @@ pub fn cmd_install(
     Ok(())
 }
 
+/// Warn about user services that run a tool about to be uninstalled
+fn warn_services(db: &Database, tool: &str) -> Result<()> {
+    for service in db.get_tool_services(tool)? {
+        println!(
+            "{} '{}' is running as a service: {} ({})",
+            "!".yellow(),
+            tool,
+            service.name,
+            service.manager
+        );
+        println!("  Stop it first with: {}", service.disable_command().cyan());
+    }
+    Ok(())
+}
+
 pub fn cmd_uninstall(db: &Database, name: &str, remove_from_db: bool, force: bool) -> Result<()> {
     // Find the tool in database
     let tool = match db.get_tool_by_name(name)? {
         Some(t) => t,
-        None => {
-            println!("Tool '{}' not found in database.", name);
-            println!("  Add it first with: hoards add {} --source <source>", name);
-            return Ok(());
-        }
+        None => fail!(
+            NotFound,
+            "Tool '{}' not found in database; add it first with: hoards add {} --source <source>",
+            name,
+            name
+        ),
     };
 
     // Check if installed
@@ -544,14 +661,21 @@ pub fn cmd_uninstall(db: &Database, name: &str, remove_from_db: bool, force: boo
     }
 
     let source = tool.source.to_string();
+    warn_services(db, name)?;
+
+    if let Some(install) = db.get_release_install(name)? {
+        return uninstall_release(db, &install, remove_from_db, force);
+    }
 
     // Get safe uninstall command (validates package name)
     let uninstall_cmd = match get_safe_uninstall_command(name, &source)? {
         Some(cmd) => cmd,
-        None => {
-            println!("Don't know how to uninstall '{}' from '{}'", name, source);
-            return Ok(());
-        }
+        None => fail!(
+            Invalid,
+            "Don't know how to uninstall '{}' from '{}'",
+            name,
+            source
+        ),
     };
 
     // Show plan
@@ -560,6 +684,7 @@ pub fn cmd_uninstall(db: &Database, name: &str, remove_from_db: bool, force: boo
     if remove_from_db {
         println!("  Also removing from database");
     }
+    warn_dangling_aliases(db, name)?;
 
     // Confirm
     if !force {
@@ -583,8 +708,7 @@ pub fn cmd_uninstall(db: &Database, name: &str, remove_from_db: bool, force: boo
     let status = uninstall_cmd.execute()?;
 
     if !status.success() {
-        println!("{} Uninstall failed", "!".red());
-        return Ok(());
+        bail!("Uninstall of '{}' failed", name);
     }
 
     println!("{} Uninstalled '{}'", "-".red(), name);
@@ -601,6 +725,56 @@ pub fn cmd_uninstall(db: &Database, name: &str, remove_from_db: bool, force: boo
     Ok(())
 }
 
+/// Remove a binary installed from a release asset
+fn uninstall_release(
+    db: &Database,
+    install: &crate::db::ReleaseInstall,
+    remove_from_db: bool,
+    force: bool,
+) -> Result<()> {
+    let name = install.tool.as_str();
+    println!("{} Uninstall plan for '{}':\n", ">".cyan(), name.bold());
+    println!("  {}: remove {}", "github".red(), install.path);
+    if remove_from_db {
+        println!("  Also removing from database");
+    }
+    warn_dangling_aliases(db, name)?;
+
+    if !force {
+        println!();
+        print!("Proceed? [y/N] ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Cancelled");
+            return Ok(());
+        }
+    }
+
+    match std::fs::remove_file(&install.path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(e).with_context(|| format!("Uninstall of '{}' failed", name));
+        }
+    }
+    println!("{} Uninstalled '{}'", "-".red(), name);
+
+    if remove_from_db {
+        db.delete_tool(name)?;
+        println!("{} Removed '{}' from database", "-".red(), name);
+    } else {
+        db.remove_release_install(name)?;
+        db.set_tool_installed(name, false)?;
+        println!("{} Marked '{}' as not installed", "i".cyan(), name);
+    }
+
+    Ok(())
+}
+
 pub fn cmd_upgrade(
     db: &Database,
     name: &str,
@@ -611,14 +785,38 @@ pub fn cmd_upgrade(
     // Find the tool in database
     let tool = match db.get_tool_by_name(name)? {
         Some(t) => t,
-        None => {
-            println!(
-                "Tool '{}' not found in database. Run 'hoards scan' first.",
+        None => fail!(
+            NotFound,
+            "Tool '{}' not found in database. Run 'hoards scan' first.",
+            name
+        ),
+    };
+
+    // Runtimes belong to their own version managers
+    if tool.kind == ToolKind::Runtime && to_source.is_none() {
+        match crate::runtimes::upgrade_guidance(&tool) {
+            Some(guidance) => {
+                println!(
+                    "{} '{}' is a runtime managed outside hoards. To upgrade it:\n\n  {}",
+                    "i".cyan(),
+                    name.bold(),
+                    guidance
+                );
+                return Ok(());
+            }
+            None => fail!(
+                NotFound,
+                "Runtime '{}' is no longer on this machine. Run 'hoards scan' to refresh.",
                 name
-            );
-            return Ok(());
+            ),
         }
-    };
+    }
+
+    if let Some(pin) = db.get_pins()?.remove(name)
+        && moves_pin(pin.as_deref(), version.as_deref())
+    {
+        check_pin(name, pin.as_deref(), force, "--force")?;
+    }
 
     let current_source = tool.source.to_string();
 
@@ -639,13 +837,12 @@ pub fn cmd_upgrade(
 
     let install_cmd = match install_cmd {
         Some(cmd) => cmd,
-        None => {
-            println!(
-                "Don't know how to install '{}' from '{}'",
-                name, target_source
-            );
-            return Ok(());
-        }
+        None => fail!(
+            Invalid,
+            "Don't know how to install '{}' from '{}'",
+            name,
+            target_source
+        ),
     };
 
     // Show plan
@@ -702,8 +899,11 @@ pub fn cmd_upgrade(
         let status = uninstall.execute()?;
 
         if !status.success() {
-            println!("{} Uninstall failed, aborting", "!".red());
-            return Ok(());
+            bail!(
+                "Uninstall of '{}' from {} failed, aborting",
+                name,
+                current_source
+            );
         }
         println!("{} Uninstalled from {}", "+".green(), current_source);
     }
@@ -713,8 +913,7 @@ pub fn cmd_upgrade(
     let status = install_cmd.execute()?;
 
     if !status.success() {
-        println!("{} Install failed", "!".red());
-        return Ok(());
+        bail!("Install of '{}' from {} failed", name, target_source);
     }
 
     let version_msg = version
@@ -750,6 +949,66 @@ pub fn cmd_upgrade(
     Ok(())
 }
 
+/// Pin a tool so install and upgrade leave it alone, or list pins
+///
+/// Without `version` the tool is held at whatever is installed.
+pub fn cmd_pin(
+    db: &Database,
+    name: Option<&str>,
+    version: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    let Some(name) = name else {
+        let mut pins: Vec<_> = db.get_pins()?.into_iter().collect();
+        pins.sort();
+        if json_output {
+            let pins: Vec<_> = pins
+                .iter()
+                .map(|(tool, version)| serde_json::json!({ "tool": tool, "version": version }))
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&pins)?);
+            return Ok(());
+        }
+        if pins.is_empty() {
+            println!("No pinned tools");
+            return Ok(());
+        }
+        for (tool, version) in &pins {
+            let version = version.as_deref().unwrap_or("installed version");
+            println!(
+                "  {} {} {}",
+                crate::icons::pin(),
+                tool.bold(),
+                version.dimmed()
+            );
+        }
+        println!("\n{} {} pinned", ">".cyan(), pins.len());
+        return Ok(());
+    };
+
+    if let Some(version) = version {
+        validate_version(version)?;
+    }
+    if !db.pin_tool(name, version)? {
+        fail!(NotFound, "Tool '{}' not found in database", name);
+    }
+    match version {
+        Some(version) => println!("{} Pinned '{}' at {}", "+".green(), name, version),
+        None => println!("{} Pinned '{}' at its installed version", "+".green(), name),
+    }
+    Ok(())
+}
+
+/// Remove a tool's pin
+pub fn cmd_unpin(db: &Database, name: &str) -> Result<()> {
+    if db.unpin_tool(name)? {
+        println!("{} Unpinned '{}'", "-".red(), name);
+    } else {
+        println!("{} '{}' is not pinned", "!".yellow(), name);
+    }
+    Ok(())
+}
+
 // ==================== Tests ====================
 
 #[cfg(test)]
@@ -895,6 +1154,52 @@ mod tests {
         assert!(get_safe_uninstall_command("foo && cat /etc/passwd", "cargo").is_err());
     }
 
+    #[test]
+    fn test_moves_pin() {
+        assert!(!moves_pin(Some("1.2.0"), Some("1.2.0")));
+        assert!(moves_pin(Some("1.2.0"), Some("1.3.0")));
+        assert!(moves_pin(Some("1.2.0"), None));
+        // A hold blocks any version
+        assert!(moves_pin(None, Some("1.2.0")));
+        assert!(moves_pin(None, None));
+    }
+
+    #[test]
+    fn test_pinned_install_version() {
+        let version = |v: &str| Some(v.to_string());
+        let pin = |v: &str| Some(Some(v.to_string()));
+
+        assert_eq!(
+            pinned_install_version("rg", None, version("1.0"), false).unwrap(),
+            version("1.0")
+        );
+        // A pin supplies its version, and only the override moves it
+        assert_eq!(
+            pinned_install_version("rg", pin("1.2"), None, false).unwrap(),
+            version("1.2")
+        );
+        assert_eq!(
+            pinned_install_version("rg", pin("1.2"), version("1.2"), false).unwrap(),
+            version("1.2")
+        );
+        assert!(pinned_install_version("rg", pin("1.2"), version("1.3"), false).is_err());
+        assert_eq!(
+            pinned_install_version("rg", pin("1.2"), version("1.3"), true).unwrap(),
+            version("1.3")
+        );
+
+        // A hold lets a missing tool install, but not at a chosen version
+        assert_eq!(
+            pinned_install_version("rg", Some(None), None, false).unwrap(),
+            None
+        );
+        assert!(pinned_install_version("rg", Some(None), version("1.3"), false).is_err());
+        assert_eq!(
+            pinned_install_version("rg", Some(None), version("1.3"), true).unwrap(),
+            version("1.3")
+        );
+    }
+
     #[test]
     fn test_safe_command_unknown_source() {
         assert!(
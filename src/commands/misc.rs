@@ -1,93 +1,136 @@
 //! Miscellaneous command implementations
 //!
-//! Export, import, doctor, and edit commands.
+//! Export, import, compare, doctor, and edit commands.
 
 use anyhow::Result;
 use colored::Colorize;
 use dialoguer::{Confirm, Input, Select};
 
-use crate::{Database, InstallSource, Tool};
+use crate::db::{MergeStrategy, UserService};
+use crate::export::{ExportBundle, ExportConfig, ExportFile, ExportTool};
+use crate::formats::{brewfile, container, home_manager, tool_versions, topgrade};
+use crate::icons::check_icon;
+use crate::scanner::{ShadowedBinary, scan_shadowed_binaries};
+use crate::services::scan_user_services;
+use crate::{Bundle, BundleTree, Config, Database, InstallSource, MemberCondition, Tool};
+use std::collections::HashMap;
 
 /// Maximum number of items to display in doctor command output
 const MAX_DISPLAY_ITEMS: usize = 10;
 
-/// Export tools to JSON or TOML
+/// Names of a bundle and every bundle it includes
+fn bundle_tree_names(tree: &BundleTree, names: &mut Vec<String>) {
+    if !names.contains(&tree.bundle.name) {
+        names.push(tree.bundle.name.clone());
+    }
+    for child in &tree.children {
+        bundle_tree_names(child, names);
+    }
+}
+
+/// Export tools, with their labels, bundles and config links, to JSON,
+/// YAML or TOML, or the tools alone to another tool's format
+///
+/// With a bundle, only it, its members (including those of bundles it
+/// includes) and their configs are exported.
 pub fn cmd_export(
     db: &Database,
     output: Option<String>,
     format: &str,
     installed_only: bool,
+    bundle: Option<&str>,
 ) -> Result<()> {
-    use std::io::Write;
-
-    let tools = if installed_only {
+    let mut tools = if installed_only {
         db.list_tools(true, None)?
     } else {
         db.get_all_tools()?
     };
+    let mut bundles = db.list_bundles()?;
+    if let Some(name) = bundle {
+        let tree = db
+            .get_bundle_tree(name)?
+            .ok_or_else(|| anyhow::anyhow!("Bundle '{}' not found", name))?;
+        let members = tree.flatten().tools;
+        tools.retain(|t| members.contains(&t.name));
+        let mut names = Vec::new();
+        bundle_tree_names(&tree, &mut names);
+        bundles.retain(|b| names.contains(&b.name));
+    }
 
     if tools.is_empty() {
         println!("{} No tools to export", "!".yellow());
         return Ok(());
     }
 
-    // Convert to exportable format
-    #[derive(serde::Serialize)]
-    struct ExportTool {
-        name: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        description: Option<String>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        category: Option<String>,
-        source: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        install_command: Option<String>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        binary_name: Option<String>,
-        installed: bool,
-    }
-
-    #[derive(serde::Serialize)]
-    struct Export {
-        version: String,
-        exported_at: String,
-        tools: Vec<ExportTool>,
-    }
-
-    let export = Export {
-        version: "1.0".to_string(),
-        exported_at: chrono::Utc::now().to_rfc3339(),
-        tools: tools
+    let mut labels = db.get_all_tool_labels()?;
+    let mut pins = db.get_pins()?;
+    let tool_names: HashMap<i64, &str> = tools
+        .iter()
+        .filter_map(|t| Some((t.id?, t.name.as_str())))
+        .collect();
+    let configs = db
+        .list_configs()?
+        .into_iter()
+        .filter_map(|c| {
+            let tool = match c.tool_id {
+                Some(id) => Some(*tool_names.get(&id)?),
+                // Configs without a tool only go with a full export
+                None if bundle.is_some() => return None,
+                None => None,
+            };
+            Some(ExportConfig {
+                name: c.name,
+                source: c.source_path,
+                target: c.target_path,
+                tool: tool.map(str::to_string),
+                mode: c.mode,
+                validate: c.validate_command,
+            })
+        })
+        .collect();
+
+    let export = ExportFile::new(
+        tools
             .iter()
             .map(|t| ExportTool {
-                name: t.name.clone(),
-                description: t.description.clone(),
-                category: t.category.clone(),
-                source: t.source.to_string(),
-                install_command: t.install_command.clone(),
-                binary_name: t.binary_name.clone(),
-                installed: t.is_installed,
+                labels: labels.remove(&t.name).unwrap_or_default(),
+                pinned: pins.remove(&t.name).flatten(),
+                ..ExportTool::from_tool(t)
             })
             .collect(),
-    };
+        bundles
+            .into_iter()
+            .map(|b| ExportBundle {
+                conditions: b
+                    .conditions
+                    .iter()
+                    .map(|(tool, condition)| (tool.clone(), condition.to_string()))
+                    .collect(),
+                name: b.name,
+                description: b.description,
+                tools: b.tools,
+                includes: b.includes,
+            })
+            .collect(),
+        configs,
+    );
 
     let content = match format {
         "toml" => toml::to_string_pretty(&export)?,
+        "yaml" => serde_yaml_ng::to_string(&export)?,
+        "brewfile" => brewfile::write(&tools),
+        "home-manager" => home_manager::write(&tools),
+        "dockerfile" => container::dockerfile(&tools),
+        "devcontainer" => container::devcontainer(&tools)?,
+        "topgrade" => topgrade::write(&tools)?,
         _ => serde_json::to_string_pretty(&export)?,
     };
+    crate::commands::report_secrets(&crate::secrets::scan_text(&content));
 
     match output {
         Some(path) => {
-            // Validate path to prevent directory traversal
             let path = std::path::Path::new(&path);
-            if path
-                .components()
-                .any(|c| matches!(c, std::path::Component::ParentDir))
-            {
-                anyhow::bail!("Output path cannot contain '..' components");
-            }
-            let mut file = std::fs::File::create(path)?;
-            file.write_all(content.as_bytes())?;
+            write_output(path, &content)?;
             println!(
                 "{} Exported {} tools to {}",
                 "+".green(),
@@ -103,34 +146,93 @@ pub fn cmd_export(
     Ok(())
 }
 
-/// Import tools from JSON or TOML
-pub fn cmd_import(db: &Database, file: &str, skip_existing: bool, dry_run: bool) -> Result<()> {
-    use std::fs;
+/// Write an export, refusing paths that climb out with `..`
+fn write_output(path: &std::path::Path, content: &str) -> Result<()> {
+    use std::io::Write;
 
-    let content = fs::read_to_string(file)?;
+    // Validate path to prevent directory traversal
+    if path
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        anyhow::bail!("Output path cannot contain '..' components");
+    }
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
 
-    #[derive(serde::Deserialize)]
-    struct ImportTool {
-        name: String,
-        description: Option<String>,
-        category: Option<String>,
-        source: Option<String>,
-        install_command: Option<String>,
-        binary_name: Option<String>,
-        #[serde(default)]
-        installed: bool,
+/// Print (or write) the JSON Schema of the export file
+pub fn cmd_export_schema(output: Option<String>) -> Result<()> {
+    let schema = crate::export::json_schema()?;
+    match output {
+        Some(path) => {
+            let path = std::path::Path::new(&path);
+            write_output(path, &schema)?;
+            println!(
+                "{} Wrote the export schema (version {}) to {}",
+                "+".green(),
+                crate::export::SCHEMA_VERSION,
+                path.display().to_string().cyan()
+            );
+        }
+        None => println!("{}", schema),
     }
+    Ok(())
+}
+
+/// Read an export file, a Brewfile, `.tool-versions` or `mise.toml`
+fn read_import(file: &str) -> Result<ExportFile> {
+    let content = std::fs::read_to_string(file)?;
 
-    #[derive(serde::Deserialize)]
-    struct Import {
-        tools: Vec<ImportTool>,
+    /// A tool read from another manager's package list
+    fn import_tool(tool: Tool, pinned: Option<String>) -> ExportTool {
+        ExportTool {
+            pinned,
+            ..ExportTool::from_tool(&tool)
+        }
     }
 
-    let import: Import = if file.ends_with(".toml") {
-        toml::from_str(&content)?
+    let file_name = std::path::Path::new(file)
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    Ok(if brewfile::is_brewfile(&file_name) {
+        let tools = brewfile::tools(&brewfile::parse(&content));
+        ExportFile::new(
+            tools.into_iter().map(|t| import_tool(t, None)).collect(),
+            Vec::new(),
+            Vec::new(),
+        )
+    } else if let Some(manager) = tool_versions::manager(&file_name) {
+        let entries = match manager {
+            tool_versions::Manager::Asdf => tool_versions::parse_tool_versions(&content),
+            tool_versions::Manager::Mise => tool_versions::parse_mise(&content)?,
+        };
+        let tools = tool_versions::tools(&entries, manager)
+            .into_iter()
+            .map(|(t, version)| {
+                let pinned = tool_versions::is_exact(&version).then_some(version);
+                import_tool(t, pinned)
+            })
+            .collect();
+        ExportFile::new(tools, Vec::new(), Vec::new())
     } else {
-        serde_json::from_str(&content)?
-    };
+        // Parsed loosely first, so older layouts can be migrated
+        let value: serde_json::Value = if file.ends_with(".toml") {
+            toml::from_str(&content)?
+        } else if file.ends_with(".yaml") || file.ends_with(".yml") {
+            serde_yaml_ng::from_str(&content)?
+        } else {
+            serde_json::from_str(&content)?
+        };
+        ExportFile::from_value(value)?
+    })
+}
+
+/// Import tools from JSON, TOML or a Brewfile
+pub fn cmd_import(db: &Database, file: &str, skip_existing: bool, dry_run: bool) -> Result<()> {
+    let import = read_import(file)?;
 
     println!(
         "{} Found {} tools in {}",
@@ -157,12 +259,18 @@ pub fn cmd_import(db: &Database, file: &str, skip_existing: bool, dry_run: bool)
             }
         }
 
+        let pin_note = tool
+            .pinned
+            .as_ref()
+            .map(|v| format!(" pinned at {}", v))
+            .unwrap_or_default();
         if dry_run {
             println!(
-                "  {} {} ({})",
+                "  {} {} ({}){}",
                 "[dry]".yellow(),
                 tool.name.cyan(),
-                tool.source.as_deref().unwrap_or("unknown")
+                tool.source.as_deref().unwrap_or("unknown"),
+                pin_note
             );
         } else {
             let mut new_tool = Tool::new(&tool.name);
@@ -186,25 +294,93 @@ pub fn cmd_import(db: &Database, file: &str, skip_existing: bool, dry_run: bool)
             }
 
             db.insert_tool(&new_tool)?;
-            println!("  {} {}", "+".green(), tool.name.cyan());
+            if !tool.labels.is_empty() {
+                db.add_labels(&tool.name, &tool.labels)?;
+            }
+            if let Some(version) = &tool.pinned {
+                db.pin_tool(&tool.name, Some(version))?;
+            }
+            println!("  {} {}{}", "+".green(), tool.name.cyan(), pin_note);
         }
         added += 1;
     }
 
+    // Bundles are created before any includes, which may point at each other
+    let mut new_bundles = Vec::new();
+    for bundle in import.bundles {
+        if db.get_bundle(&bundle.name)?.is_some() {
+            skipped += 1;
+            continue;
+        }
+        if dry_run {
+            println!("  {} bundle {}", "[dry]".yellow(), bundle.name.cyan());
+        } else {
+            let mut new_bundle = Bundle::new(&bundle.name, bundle.tools.clone());
+            new_bundle.description.clone_from(&bundle.description);
+            for (tool, condition) in &bundle.conditions {
+                new_bundle
+                    .conditions
+                    .insert(tool.clone(), MemberCondition::parse(condition)?);
+            }
+            db.create_bundle(&new_bundle)?;
+            println!("  {} bundle {}", "+".green(), bundle.name.cyan());
+        }
+        new_bundles.push(bundle);
+    }
+    if !dry_run {
+        for bundle in &new_bundles {
+            if !bundle.includes.is_empty() {
+                db.include_bundles(&bundle.name, &bundle.includes)?;
+            }
+        }
+    }
+
+    let mut new_configs = 0;
+    for config in import.configs {
+        if db.get_config_by_name(&config.name)?.is_some() {
+            skipped += 1;
+            continue;
+        }
+        if dry_run {
+            println!("  {} config {}", "[dry]".yellow(), config.name.cyan());
+        } else {
+            let mut new_config = Config::new(&config.name, config.source, config.target);
+            new_config.mode = config.mode;
+            new_config.validate_command = config.validate;
+            if let Some(tool) = &config.tool {
+                new_config.tool_id = db.get_tool_by_name(tool)?.and_then(|t| t.id);
+            }
+            db.insert_config(&new_config)?;
+            println!("  {} config {}", "+".green(), config.name.cyan());
+        }
+        new_configs += 1;
+    }
+
+    // Bundles and configs are only mentioned when the file has them
+    let mut extra = String::new();
+    if !new_bundles.is_empty() {
+        extra.push_str(&format!(", {} bundles", new_bundles.len()));
+    }
+    if new_configs > 0 {
+        extra.push_str(&format!(", {} configs", new_configs));
+    }
+
     println!();
     if dry_run {
         println!(
-            "{} Would add {} tools ({} skipped). Run without {} to apply.",
+            "{} Would add {} tools{} ({} skipped). Run without {} to apply.",
             ">".cyan(),
             added,
+            extra,
             skipped,
             "--dry-run".yellow()
         );
     } else {
         println!(
-            "{} Added {} tools ({} skipped)",
+            "{} Added {} tools{} ({} skipped)",
             "+".green(),
             added,
+            extra,
             skipped
         );
     }
@@ -212,74 +388,378 @@ pub fn cmd_import(db: &Database, file: &str, skip_existing: bool, dry_run: bool)
     Ok(())
 }
 
-/// Run health checks on the database
-pub fn cmd_doctor(db: &Database, fix: bool) -> Result<()> {
-    println!("{}", "Running health checks...".bold());
+/// Merge another machine's database into this one
+pub fn cmd_import_db(
+    db: &Database,
+    path: &str,
+    strategy: MergeStrategy,
+    dry_run: bool,
+) -> Result<()> {
+    let path = std::path::Path::new(path);
+    let local = Database::db_path()?;
+    if path.canonicalize().ok() == local.canonicalize().ok() {
+        anyhow::bail!("'{}' is this machine's database", path.display());
+    }
+
+    println!("{} Merging {}", ">".cyan(), path.display());
+    let report = db.merge_from(path, strategy, dry_run)?;
+
+    for name in &report.tools_added {
+        let label = if dry_run {
+            "[dry]".yellow()
+        } else {
+            "+".green()
+        };
+        println!("  {} {}", label, name.cyan());
+        // The other machine's installed state says nothing about this one
+        if !dry_run
+            && let Some(tool) = db.get_tool_by_name(name)?
+            && crate::is_installed(tool.binary_name.as_deref().unwrap_or(name))
+        {
+            db.set_tool_installed(name, true)?;
+        }
+    }
+
     println!();
+    let summary = format!(
+        "{} tools added, {} updated, {} bundles added, {} updated, {} labels, usage for {} tools, {} cached repos",
+        report.tools_added.len(),
+        report.tools_updated,
+        report.bundles_added,
+        report.bundles_updated,
+        report.labels_added,
+        report.usage_merged,
+        report.github_merged
+    );
+    if report.usage_skipped {
+        println!(
+            "{} This database was merged before; its usage was left out",
+            "i".cyan()
+        );
+    }
+    if dry_run {
+        println!(
+            "{} Would merge: {}. Run without {} to apply.",
+            ">".cyan(),
+            summary,
+            "--dry-run".yellow()
+        );
+    } else {
+        println!("{} Merged: {}", "+".green(), summary);
+    }
 
-    let mut issues_found = 0;
-    let mut fixed = 0;
+    Ok(())
+}
 
-    // Check 1: Tools marked as installed but binary not found
-    println!("{}", "Checking installed tools...".dimmed());
-    let tools = db.get_all_tools()?;
-    let mut missing_binaries: Vec<(String, String)> = Vec::new();
+/// Compare this machine's installed tools with another machine's export
+/// file or hoards.db
+///
+/// With `install_missing`, the tools only the other machine has can be
+/// installed here (all of them with `force`, otherwise the ones picked),
+/// from the other machine's source and at its pinned version.
+pub fn cmd_compare(
+    db: &Database,
+    other: &str,
+    install_missing: bool,
+    force: bool,
+    json_output: bool,
+) -> Result<()> {
+    use crate::commands::cmd_install;
+    use crate::export::{ToolDifference, compare};
+    use std::io::Read;
+
+    let path = std::path::Path::new(other);
+    let mut header = [0u8; 16];
+    let is_database = std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut header))
+        .is_ok()
+        && &header == b"SQLite format 3\0";
+    let remote: Vec<ExportTool> = if is_database {
+        Database::read_tools_from(path)?
+            .iter()
+            .map(ExportTool::from_tool)
+            .collect()
+    } else {
+        read_import(other)?.tools
+    };
+    let local: Vec<ExportTool> = db
+        .get_all_tools()?
+        .iter()
+        .map(ExportTool::from_tool)
+        .collect();
+    let comparison = compare(&local, &remote);
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&comparison)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} Comparing with {} ({} installed tools there, {} here)",
+        ">".cyan(),
+        other.cyan(),
+        remote.iter().filter(|t| t.installed).count(),
+        local.iter().filter(|t| t.installed).count()
+    );
+    if comparison.is_empty() {
+        println!("\n{} Both machines have the same tools", "+".green());
+        return Ok(());
+    }
+
+    let print_names = |title: &str, names: &[String], marker: colored::ColoredString| {
+        if names.is_empty() {
+            return;
+        }
+        println!("\n{} ({})", title.bold(), names.len());
+        for name in names {
+            println!("  {} {}", marker, name);
+        }
+    };
+    let print_differences = |title: &str, differences: &[ToolDifference]| {
+        if differences.is_empty() {
+            return;
+        }
+        println!("\n{} ({})", title.bold(), differences.len());
+        for d in differences {
+            println!(
+                "  {} {:20} here {}, there {}",
+                "~".yellow(),
+                d.name,
+                d.local.cyan(),
+                d.remote.cyan()
+            );
+        }
+    };
+    print_names("Only here", &comparison.only_local, "-".red());
+    print_names("Only there", &comparison.only_remote, "+".green());
+    print_differences("Different versions", &comparison.versions);
+    print_differences("Different sources", &comparison.sources);
+
+    if comparison.only_remote.is_empty() {
+        return Ok(());
+    }
+    if !install_missing {
+        println!(
+            "\n{} Install the tools only there with {}",
+            ">".cyan(),
+            format!("hoards compare {} --install-missing", other).yellow()
+        );
+        return Ok(());
+    }
+
+    let missing: Vec<&ExportTool> = comparison
+        .only_remote
+        .iter()
+        .filter_map(|name| remote.iter().find(|t| &t.name == name))
+        .collect();
+    let selected: Vec<usize> = if force {
+        (0..missing.len()).collect()
+    } else {
+        use dialoguer::{MultiSelect, theme::ColorfulTheme};
+
+        let options: Vec<String> = missing
+            .iter()
+            .map(|t| format!("{} ({})", t.name, t.source.as_deref().unwrap_or("unknown")))
+            .collect();
+        println!();
+        match MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Select tools to install")
+            .items(&options)
+            .defaults(&vec![true; options.len()])
+            .interact_opt()?
+        {
+            Some(indices) if !indices.is_empty() => indices,
+            _ => {
+                println!("{} No tools selected", ">".dimmed());
+                return Ok(());
+            }
+        }
+    };
+
+    println!();
+    let mut failed = 0;
+    for idx in &selected {
+        let tool = missing[*idx];
+        if let Err(e) = cmd_install(
+            db,
+            &tool.name,
+            tool.source.clone(),
+            tool.pinned.clone(),
+            true,
+            false,
+        ) {
+            println!("{} {:#}", "!".red(), e);
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        crate::fail!(
+            Partial,
+            "{} of {} install(s) failed",
+            failed,
+            selected.len()
+        );
+    }
+    Ok(())
+}
+
+/// What `doctor` found
+#[derive(Debug, Default, serde::Serialize)]
+struct DoctorReport {
+    /// Tools marked installed whose binary is not on PATH: (tool, binary)
+    missing_binaries: Vec<(String, String)>,
+    no_description: Vec<String>,
+    no_category: Vec<String>,
+    no_source: Vec<String>,
+    orphaned_usage: usize,
+    /// Binaries claimed by more than one tool
+    duplicate_binaries: std::collections::BTreeMap<String, Vec<String>>,
+    /// Tools whose upstream looks abandoned: (tool, reason)
+    abandoned: Vec<(String, String)>,
+    /// Tracked binaries with more than one copy on PATH
+    shadowed: Vec<ShadowedBinary>,
+    /// User services whose program is gone
+    broken_services: Vec<UserService>,
+    issues: usize,
+    fixed: usize,
+}
+
+impl DoctorReport {
+    fn collect(db: &Database) -> Result<Self> {
+        let tools = db.get_all_tools()?;
+        let mut report = Self::default();
 
-    for tool in &tools {
-        if tool.is_installed {
+        for tool in &tools {
             let binary = tool.binary_name.as_ref().unwrap_or(&tool.name);
-            if which::which(binary).is_err() {
-                missing_binaries.push((tool.name.clone(), binary.clone()));
+            if tool.is_installed && !crate::is_installed(binary) {
+                report
+                    .missing_binaries
+                    .push((tool.name.clone(), binary.clone()));
             }
+            if tool.description.is_none() {
+                report.no_description.push(tool.name.clone());
+            }
+            if tool.category.is_none() {
+                report.no_category.push(tool.name.clone());
+            }
+            if matches!(tool.source, InstallSource::Unknown) {
+                report.no_source.push(tool.name.clone());
+            }
+            report
+                .duplicate_binaries
+                .entry(binary.clone())
+                .or_default()
+                .push(tool.name.clone());
+        }
+        report.duplicate_binaries.retain(|_, names| names.len() > 1);
+        let tracked: std::collections::HashSet<&str> = tools
+            .iter()
+            .filter(|t| t.is_installed)
+            .map(|t| t.binary_name.as_deref().unwrap_or(&t.name))
+            .collect();
+        report.shadowed = scan_shadowed_binaries()
+            .into_iter()
+            .filter(|s| tracked.contains(s.binary.as_str()))
+            .collect();
+        report.broken_services = scan_user_services(&tools)
+            .into_iter()
+            .filter(|s| !s.binary_exists())
+            .collect();
+        report.orphaned_usage = db.count_orphaned_usage()?;
+        report.abandoned = db
+            .get_all_github_info()?
+            .into_iter()
+            .filter_map(|(name, info)| info.abandonment().map(|reason| (name, reason)))
+            .collect();
+
+        report.issues = report.missing_binaries.len()
+            + report.no_description.len()
+            + report.no_category.len()
+            + report.no_source.len()
+            + report.orphaned_usage
+            + report.duplicate_binaries.len()
+            + report.abandoned.len()
+            + report.shadowed.len()
+            + report.broken_services.len();
+        Ok(report)
+    }
+
+    /// Mark tools with missing binaries as not installed and drop orphaned
+    /// usage records
+    fn fix(&mut self, db: &Database) -> Result<()> {
+        for (name, _) in &self.missing_binaries {
+            db.set_tool_installed(name, false)?;
+        }
+        if self.orphaned_usage > 0 {
+            db.delete_orphaned_usage()?;
         }
+        self.fixed = self.missing_binaries.len() + self.orphaned_usage;
+        Ok(())
+    }
+}
+
+/// Print up to [`MAX_DISPLAY_ITEMS`] tool names
+fn print_names(names: &[String]) {
+    for name in names.iter().take(MAX_DISPLAY_ITEMS) {
+        println!("    {}", name);
+    }
+    if names.len() > MAX_DISPLAY_ITEMS {
+        println!("    ... and {} more", names.len() - MAX_DISPLAY_ITEMS);
+    }
+}
+
+/// Run health checks on the database
+pub fn cmd_doctor(db: &Database, fix: bool, json_output: bool) -> Result<()> {
+    let mut report = DoctorReport::collect(db)?;
+    if fix {
+        report.fix(db)?;
+    }
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
     }
 
+    println!("{}", "Running health checks...".bold());
+    println!();
+
+    // Check 1: Tools marked as installed but binary not found
+    println!("{}", "Checking installed tools...".dimmed());
+    let missing_binaries = &report.missing_binaries;
     if !missing_binaries.is_empty() {
         println!(
             "  {} {} tools marked installed but binary not found:",
             "!".yellow(),
             missing_binaries.len()
         );
-        for (name, binary) in &missing_binaries {
+        for (name, binary) in missing_binaries {
             println!("    {} (binary: {})", name.red(), binary);
         }
-        issues_found += missing_binaries.len();
 
         if fix {
-            for (name, _) in &missing_binaries {
-                db.set_tool_installed(name, false)?;
-                fixed += 1;
-            }
             println!(
                 "    {} Marked {} tools as not installed",
-                "✓".green(),
+                check_icon().green(),
                 missing_binaries.len()
             );
         }
     } else {
-        println!("  {} All installed tools have valid binaries", "✓".green());
+        println!(
+            "  {} All installed tools have valid binaries",
+            check_icon().green()
+        );
     }
 
     // Check 2: Tools without descriptions
     println!("{}", "Checking for missing descriptions...".dimmed());
-    let no_description: Vec<_> = tools.iter().filter(|t| t.description.is_none()).collect();
-
-    if !no_description.is_empty() {
+    if !report.no_description.is_empty() {
         println!(
             "  {} {} tools have no description:",
             "!".yellow(),
-            no_description.len()
+            report.no_description.len()
         );
-        for tool in no_description.iter().take(MAX_DISPLAY_ITEMS) {
-            println!("    {}", tool.name);
-        }
-        if no_description.len() > MAX_DISPLAY_ITEMS {
-            println!(
-                "    ... and {} more",
-                no_description.len() - MAX_DISPLAY_ITEMS
-            );
-        }
-        issues_found += no_description.len();
+        print_names(&report.no_description);
         println!(
             "    {} Run {} to fetch from package registries",
             "?".blue(),
@@ -291,120 +771,176 @@ pub fn cmd_doctor(db: &Database, fix: bool) -> Result<()> {
             "hoards gh sync".cyan()
         );
     } else {
-        println!("  {} All tools have descriptions", "✓".green());
+        println!("  {} All tools have descriptions", check_icon().green());
     }
 
     // Check 3: Tools without categories
     println!("{}", "Checking for missing categories...".dimmed());
-    let no_category: Vec<_> = tools.iter().filter(|t| t.category.is_none()).collect();
-
-    if !no_category.is_empty() {
+    if !report.no_category.is_empty() {
         println!(
             "  {} {} tools have no category:",
             "!".yellow(),
-            no_category.len()
+            report.no_category.len()
         );
-        for tool in no_category.iter().take(MAX_DISPLAY_ITEMS) {
-            println!("    {}", tool.name);
-        }
-        if no_category.len() > MAX_DISPLAY_ITEMS {
-            println!("    ... and {} more", no_category.len() - MAX_DISPLAY_ITEMS);
-        }
-        issues_found += no_category.len();
+        print_names(&report.no_category);
         println!(
             "    {} Run {} to auto-categorize",
             "?".blue(),
             "hoards ai categorize".cyan()
         );
     } else {
-        println!("  {} All tools have categories", "✓".green());
+        println!("  {} All tools have categories", check_icon().green());
     }
 
     // Check 4: Tools without installation source
     println!("{}", "Checking for missing sources...".dimmed());
-    let no_source: Vec<_> = tools
-        .iter()
-        .filter(|t| matches!(t.source, InstallSource::Unknown))
-        .collect();
-
-    if !no_source.is_empty() {
+    if !report.no_source.is_empty() {
         println!(
             "  {} {} tools have no installation source:",
             "!".yellow(),
-            no_source.len()
+            report.no_source.len()
         );
-        for tool in no_source.iter().take(MAX_DISPLAY_ITEMS) {
-            println!("    {}", tool.name);
-        }
-        if no_source.len() > MAX_DISPLAY_ITEMS {
-            println!("    ... and {} more", no_source.len() - MAX_DISPLAY_ITEMS);
-        }
-        issues_found += no_source.len();
+        print_names(&report.no_source);
     } else {
-        println!("  {} All tools have installation sources", "✓".green());
+        println!(
+            "  {} All tools have installation sources",
+            check_icon().green()
+        );
     }
 
     // Check 5: Orphaned usage records
     println!("{}", "Checking usage records...".dimmed());
-    let orphaned_count = db.count_orphaned_usage()?;
-
+    let orphaned_count = report.orphaned_usage;
     if orphaned_count > 0 {
         println!(
             "  {} {} orphaned usage records found",
             "!".yellow(),
             orphaned_count
         );
-        issues_found += orphaned_count;
 
         if fix {
-            db.delete_orphaned_usage()?;
-            fixed += orphaned_count;
             println!(
                 "    {} Deleted {} orphaned records",
-                "✓".green(),
+                check_icon().green(),
                 orphaned_count
             );
         }
     } else {
-        println!("  {} No orphaned usage records", "✓".green());
+        println!("  {} No orphaned usage records", check_icon().green());
     }
 
     // Check 6: Duplicate binaries (different tools pointing to same binary)
     println!("{}", "Checking for duplicate binaries...".dimmed());
-    let mut binary_map: std::collections::HashMap<String, Vec<String>> =
-        std::collections::HashMap::new();
-    for tool in &tools {
-        let binary = tool.binary_name.as_ref().unwrap_or(&tool.name).clone();
-        binary_map
-            .entry(binary)
-            .or_default()
-            .push(tool.name.clone());
-    }
-    let duplicates: Vec<_> = binary_map
-        .iter()
-        .filter(|(_, names)| names.len() > 1)
-        .collect();
-
+    let duplicates = &report.duplicate_binaries;
     if !duplicates.is_empty() {
         println!(
             "  {} {} binaries shared by multiple tools:",
             "!".yellow(),
             duplicates.len()
         );
-        for (binary, tools) in &duplicates {
+        for (binary, tools) in duplicates {
             println!("    {} -> {}", binary.cyan(), tools.join(", "));
         }
-        issues_found += duplicates.len();
     } else {
-        println!("  {} No duplicate binaries", "✓".green());
+        println!("  {} No duplicate binaries", check_icon().green());
+    }
+
+    // Check 7: Tools whose upstream looks abandoned
+    println!("{}", "Checking upstream health...".dimmed());
+    let abandoned = &report.abandoned;
+    if !abandoned.is_empty() {
+        println!(
+            "  {} {} tools have an upstream that looks abandoned:",
+            "!".yellow(),
+            abandoned.len()
+        );
+        for (name, reason) in abandoned.iter().take(MAX_DISPLAY_ITEMS) {
+            println!("    {} ({})", name.yellow(), reason);
+        }
+        if abandoned.len() > MAX_DISPLAY_ITEMS {
+            println!("    ... and {} more", abandoned.len() - MAX_DISPLAY_ITEMS);
+        }
+        println!(
+            "    {} Refresh with {} or look for a maintained alternative",
+            "?".blue(),
+            "hoards gh fetch <tool>".cyan()
+        );
+    } else {
+        println!("  {} No abandoned upstreams", check_icon().green());
+    }
+
+    // Check 8: Tracked binaries shadowed by another copy on PATH
+    println!("{}", "Checking for shadowed binaries...".dimmed());
+    let shadowed = &report.shadowed;
+    if !shadowed.is_empty() {
+        println!(
+            "  {} {} binaries have more than one copy on PATH:",
+            "!".yellow(),
+            shadowed.len()
+        );
+        for s in shadowed.iter().take(MAX_DISPLAY_ITEMS) {
+            println!(
+                "    {} -> {} shadows {}",
+                s.binary.cyan(),
+                s.winner().path.display(),
+                s.shadowed()
+                    .iter()
+                    .map(|c| c.path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        if shadowed.len() > MAX_DISPLAY_ITEMS {
+            println!("    ... and {} more", shadowed.len() - MAX_DISPLAY_ITEMS);
+        }
+        println!(
+            "    {} Run {} to uninstall or prefer a shadowed copy",
+            "?".blue(),
+            "hoards insights shadowed --resolve".cyan()
+        );
+    } else {
+        println!("  {} No shadowed binaries", check_icon().green());
+    }
+
+    // Check 9: User services whose program vanished
+    println!("{}", "Checking user services...".dimmed());
+    let broken_services = &report.broken_services;
+    if !broken_services.is_empty() {
+        println!(
+            "  {} {} services run a program that's gone:",
+            "!".yellow(),
+            broken_services.len()
+        );
+        for service in broken_services.iter().take(MAX_DISPLAY_ITEMS) {
+            println!(
+                "    {} ({}) -> {}  {}",
+                service.name.cyan(),
+                service.manager,
+                service.binary,
+                service.disable_command().dimmed()
+            );
+        }
+        if broken_services.len() > MAX_DISPLAY_ITEMS {
+            println!(
+                "    ... and {} more",
+                broken_services.len() - MAX_DISPLAY_ITEMS
+            );
+        }
+        println!(
+            "    {} Reinstall their tools, or disable them with the commands shown",
+            "?".blue()
+        );
+    } else {
+        println!("  {} No broken services", check_icon().green());
     }
 
     // Summary
+    let (issues_found, fixed) = (report.issues, report.fixed);
     println!();
     if issues_found == 0 {
         println!(
             "{} {}",
-            "✓".green().bold(),
+            check_icon().green().bold(),
             "Database is healthy!".green().bold()
         );
     } else {
@@ -430,16 +966,64 @@ pub fn cmd_doctor(db: &Database, fix: bool) -> Result<()> {
     Ok(())
 }
 
+/// A note line stamped with the local time
+fn stamped_note(text: &str, now: chrono::DateTime<chrono::Local>) -> String {
+    format!("[{}] {}", now.format("%Y-%m-%d %H:%M"), text.trim())
+}
+
+/// Show, append to, or edit a tool's notes
+///
+/// Text is appended as a timestamped line; `edit` opens all the notes in
+/// $VISUAL/$EDITOR, where clearing the file removes them.
+pub fn cmd_note(db: &Database, name: &str, text: Vec<String>, edit: bool) -> Result<()> {
+    let Some(tool) = db.get_tool_by_name(name)? else {
+        anyhow::bail!("Tool '{}' not found", name);
+    };
+    let current = tool.notes.unwrap_or_default();
+    let text = text.join(" ");
+
+    if edit {
+        let Some(edited) = dialoguer::Editor::new().extension(".md").edit(&current)? else {
+            println!("Notes unchanged");
+            return Ok(());
+        };
+        let edited = edited.trim();
+        db.set_tool_notes(&tool.name, (!edited.is_empty()).then_some(edited))?;
+        println!("{} Saved notes for {}", "+".green(), tool.name.cyan());
+        return Ok(());
+    }
+
+    if text.trim().is_empty() {
+        if current.trim().is_empty() {
+            println!("No notes for {}", tool.name);
+            println!(
+                "  Add one with: {}",
+                format!("hoards note {} \"text\"", tool.name).cyan()
+            );
+        } else {
+            println!("{}", current);
+        }
+        return Ok(());
+    }
+
+    let line = stamped_note(&text, chrono::Local::now());
+    let notes = if current.trim().is_empty() {
+        line
+    } else {
+        format!("{}\n{}", current.trim_end(), line)
+    };
+    db.set_tool_notes(&tool.name, Some(&notes))?;
+    println!("{} Added note to {}", "+".green(), tool.name.cyan());
+    Ok(())
+}
+
 /// Interactive tool editor
 pub fn cmd_edit(db: &Database, name: &str) -> Result<()> {
     let tool = db.get_tool_by_name(name)?;
 
     let mut tool = match tool {
         Some(t) => t,
-        None => {
-            println!("{} Tool '{}' not found", "✗".red(), name);
-            return Ok(());
-        }
+        None => crate::fail!(NotFound, "Tool '{}' not found", name),
     };
 
     println!("{} {}", "Editing:".bold(), tool.name.cyan().bold());
@@ -630,7 +1214,7 @@ pub fn cmd_edit(db: &Database, name: &str) -> Result<()> {
 
     db.update_tool(&tool)?;
 
-    println!("{} Updated '{}'", "✓".green(), name);
+    println!("{} Updated '{}'", check_icon().green(), name);
 
     Ok(())
 }
@@ -3,39 +3,65 @@
 //! Each submodule handles a group of related commands.
 
 pub mod ai;
+pub mod alias;
+pub mod batch;
 pub mod bundle;
 pub mod completions;
 pub mod config;
 pub mod core;
 pub mod discover;
 pub mod github;
+pub mod grep;
 pub mod helpers;
 pub mod insights;
 pub mod install;
 pub mod misc;
+pub mod pick;
+pub mod run;
+pub mod serve;
 pub mod sync;
 pub mod updates_cmd;
 pub mod usage;
+pub mod watch;
 pub mod workflow;
 
 // Re-export commonly used items from install
 pub use install::{
-    ProcessAction, SafeCommand, cmd_install, cmd_uninstall, cmd_upgrade, get_install_command,
-    get_install_command_versioned, get_safe_install_command, get_safe_uninstall_command,
-    handle_running_process, validate_binary_name, validate_package_name, validate_version,
+    ProcessAction, SafeCommand, cmd_install, cmd_pin, cmd_uninstall, cmd_unpin, cmd_upgrade,
+    get_install_command, get_install_command_versioned, get_safe_install_command,
+    get_safe_uninstall_command, handle_running_process, validate_binary_name,
+    validate_package_name, validate_version,
 };
 
+// Re-export batch commands
+pub use batch::{cmd_add_batch, cmd_install_batch};
+
 // Re-export core commands
-pub use core::{cmd_add, cmd_list, cmd_remove, cmd_search, cmd_show};
+pub use core::{cmd_add, cmd_list, cmd_open, cmd_remove, cmd_search, cmd_show, cmd_which};
+
+// Re-export pick command
+pub use pick::cmd_pick;
+
+// Re-export run command
+pub use run::cmd_run;
+
+// Re-export grep command
+pub use grep::cmd_grep;
 
 // Re-export sync commands
 pub use sync::{cmd_fetch_descriptions, cmd_scan, cmd_sync_status};
 
 // Re-export discover commands
-pub use discover::{cmd_similar, cmd_suggest, cmd_trending};
+pub use discover::{
+    cmd_discover_project, cmd_discover_topic, cmd_similar, cmd_suggest, cmd_trending,
+};
 
 // Re-export insights commands
-pub use insights::{cmd_categories, cmd_info, cmd_overview, cmd_stats};
+pub use insights::{
+    cmd_adoption, cmd_categories, cmd_cleanup_plan, cmd_graph, cmd_health_scores, cmd_info,
+    cmd_licenses, cmd_overlap, cmd_overview, cmd_report, cmd_rhythms, cmd_shadowed, cmd_size,
+    cmd_stale, cmd_stats, cmd_timeline,
+};
 
 // Re-export workflow commands
 pub use workflow::{cmd_cleanup, cmd_init, cmd_maintain};
@@ -44,40 +70,69 @@ pub use workflow::{cmd_cleanup, cmd_init, cmd_maintain};
 pub use updates_cmd::{cmd_updates, cmd_updates_cross, cmd_updates_tracked};
 
 // Re-export helpers
-pub use helpers::{confirm, extract_package_from_install_cmd, fetch_tool_description};
+pub use helpers::{
+    confirm, extract_package_from_install_cmd, fetch_tool_description, report_secrets,
+};
+
+// Re-export alias commands
+pub use alias::{cmd_alias_add, cmd_alias_list, cmd_alias_remove, cmd_alias_snippet};
 
 // Re-export bundle commands
 pub use bundle::{
-    cmd_bundle_add, cmd_bundle_create, cmd_bundle_delete, cmd_bundle_install, cmd_bundle_list,
-    cmd_bundle_remove, cmd_bundle_show, cmd_bundle_update,
+    cmd_bundle_add, cmd_bundle_create, cmd_bundle_delete, cmd_bundle_from_project,
+    cmd_bundle_include, cmd_bundle_install, cmd_bundle_install_from_url, cmd_bundle_list,
+    cmd_bundle_lock, cmd_bundle_publish, cmd_bundle_remove, cmd_bundle_show, cmd_bundle_sync,
+    cmd_bundle_template_apply, cmd_bundle_template_list, cmd_bundle_update,
+    create_bundle_from_template,
 };
 
 // Re-export AI commands
 pub use ai::{
-    cmd_ai_analyze, cmd_ai_bundle_cheatsheet, cmd_ai_categorize, cmd_ai_cheatsheet,
-    cmd_ai_describe, cmd_ai_discover, cmd_ai_extract, cmd_ai_migrate, cmd_ai_set, cmd_ai_show,
-    cmd_ai_suggest_bundle, cmd_ai_test, invalidate_cheatsheet_cache,
+    cmd_ai_analyze, cmd_ai_bundle_cheatsheet, cmd_ai_cache_clear, cmd_ai_cache_list,
+    cmd_ai_categorize, cmd_ai_cheatsheet, cmd_ai_cheatsheet_export, cmd_ai_dedupe, cmd_ai_describe,
+    cmd_ai_discover, cmd_ai_do, cmd_ai_extract, cmd_ai_migrate, cmd_ai_prompts_init,
+    cmd_ai_prompts_list, cmd_ai_prompts_reset, cmd_ai_prompts_show, cmd_ai_set, cmd_ai_show,
+    cmd_ai_suggest_bundle, cmd_ai_test, cmd_ai_updates_summary, cmd_ai_usage,
+    invalidate_cheatsheet_cache,
 };
 
 // Re-export GitHub commands
 pub use github::{
-    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_info, cmd_gh_rate_limit, cmd_gh_search, cmd_gh_sync,
+    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_import_stars, cmd_gh_info, cmd_gh_install, cmd_gh_queue,
+    cmd_gh_rate_limit, cmd_gh_readme, cmd_gh_search, cmd_gh_sync,
 };
 
 // Re-export usage commands
 pub use usage::{
-    cmd_labels, cmd_recommend, cmd_unused, cmd_usage_config, cmd_usage_init, cmd_usage_log,
-    cmd_usage_reset, cmd_usage_scan, cmd_usage_show, cmd_usage_tool, ensure_usage_configured,
+    cmd_labels, cmd_recommend, cmd_unused, cmd_usage_config, cmd_usage_export, cmd_usage_flush,
+    cmd_usage_init, cmd_usage_log, cmd_usage_log_fast, cmd_usage_project, cmd_usage_reset,
+    cmd_usage_scan, cmd_usage_show, cmd_usage_tool, ensure_usage_configured, flush_usage_log,
+};
+
+// Re-export watch commands
+pub use watch::{
+    check_watched_releases, cmd_watch_add, cmd_watch_check, cmd_watch_list, cmd_watch_read,
+    cmd_watch_remove,
 };
 
 // Re-export misc commands
-pub use misc::{cmd_doctor, cmd_edit, cmd_export, cmd_import};
+pub use misc::{
+    cmd_compare, cmd_doctor, cmd_edit, cmd_export, cmd_export_schema, cmd_import, cmd_import_db,
+    cmd_note,
+};
+
+// Re-export serve command
+pub use serve::cmd_serve;
 
 // Re-export config commands
 pub use config::{
-    cmd_config_edit, cmd_config_link, cmd_config_list, cmd_config_show, cmd_config_status,
-    cmd_config_sync, cmd_config_unlink,
+    LinkOptions, cmd_config_diff, cmd_config_edit, cmd_config_import, cmd_config_link,
+    cmd_config_list, cmd_config_repo_clone, cmd_config_repo_init, cmd_config_repo_pull,
+    cmd_config_repo_push, cmd_config_restore, cmd_config_show, cmd_config_status, cmd_config_sync,
+    cmd_config_unlink, cmd_config_watch,
 };
 
 // Re-export completions commands
-pub use completions::{cmd_completions_install, cmd_completions_status, cmd_completions_uninstall};
+pub use completions::{
+    cmd_completions_install, cmd_completions_man, cmd_completions_status, cmd_completions_uninstall,
+};
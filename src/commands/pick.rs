@@ -0,0 +1,46 @@
+//! Pick command: choose a tool with an inline fuzzy selector
+
+use anyhow::{Result, bail};
+use dialoguer::FuzzySelect;
+use std::io::IsTerminal;
+
+use crate::db::Database;
+
+/// Let the user pick a tool and print its name to stdout
+///
+/// The selector draws on stderr, so `hoards pick | xargs hoards show` works.
+/// Cancelling with Esc prints nothing and fails, stopping the pipeline.
+pub fn cmd_pick(db: &Database, installed_only: bool, category: Option<&str>) -> Result<()> {
+    if !std::io::stderr().is_terminal() {
+        bail!("pick needs a terminal to draw the selector on (stderr)");
+    }
+
+    let tools = db.list_tools(installed_only, category)?;
+    if tools.is_empty() {
+        bail!("No tools to pick from");
+    }
+
+    // Descriptions are part of the item, so they match too
+    let width = tools.iter().map(|t| t.name.len()).max().unwrap_or(0);
+    let items: Vec<String> = tools
+        .iter()
+        .map(|t| match &t.description {
+            Some(description) => format!("{:width$}  {}", t.name, description, width = width),
+            None => t.name.clone(),
+        })
+        .collect();
+
+    let selection = FuzzySelect::new()
+        .with_prompt("Pick a tool")
+        .items(&items)
+        .max_length(15)
+        .interact_opt()?;
+
+    match selection {
+        Some(index) => {
+            println!("{}", tools[index].name);
+            Ok(())
+        }
+        None => bail!("Nothing picked"),
+    }
+}
@@ -0,0 +1,110 @@
+//! Run command: execute a tool, installing it first if needed
+
+use anyhow::{Context, Result, bail};
+use colored::Colorize;
+use std::process::Command;
+
+use super::install::cmd_install;
+use super::usage::cmd_usage_log;
+use crate::{Database, Tool, is_installed};
+
+/// The tracked tool called `name`, or else the one whose binary it is
+fn find_tool(db: &Database, name: &str) -> Result<Option<Tool>> {
+    if let Some(tool) = db.get_tool_by_name(name)? {
+        return Ok(Some(tool));
+    }
+    Ok(db
+        .get_all_tools()?
+        .into_iter()
+        .find(|t| t.binary_name.as_deref() == Some(name)))
+}
+
+/// Run a tool with arguments, offering to install it when it is missing
+///
+/// The tool is found by name or binary name. A missing tool goes through
+/// the usual install plan and confirmation (skipped with `yes`), at its
+/// pinned version if it has one; untracked tools need `source`. The run is
+/// recorded as a usage event, and the tool's exit code becomes hoards' own.
+pub fn cmd_run(
+    db: &Database,
+    name: &str,
+    args: &[String],
+    source: Option<String>,
+    yes: bool,
+) -> Result<()> {
+    let tool = find_tool(db, name)?;
+    let tool_name = tool.as_ref().map_or(name, |t| t.name.as_str());
+    let binary = tool
+        .as_ref()
+        .and_then(|t| t.binary_name.as_deref())
+        .unwrap_or(tool_name);
+
+    if !is_installed(binary) {
+        if tool.is_none() && source.is_none() {
+            bail!(
+                "'{}' is not installed or tracked; run it with: hoards run --source <cargo|pip|npm|apt|brew|snap> {}",
+                name,
+                name
+            );
+        }
+        eprintln!(
+            "{} '{}' is not installed; installing it first",
+            "!".yellow(),
+            tool_name
+        );
+        cmd_install(db, tool_name, source, None, yes, false)?;
+        if !is_installed(binary) {
+            bail!("'{}' is not installed", tool_name);
+        }
+    }
+
+    let command_line = std::iter::once(binary)
+        .chain(args.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Err(e) = cmd_usage_log(db, &command_line) {
+        tracing::warn!("Failed to record usage of {}: {:#}", tool_name, e);
+    }
+
+    tracing::debug!("running {}", command_line);
+    let status = Command::new(binary)
+        .args(args)
+        .status()
+        .with_context(|| format!("Failed to run {}", binary))?;
+    if !status.success() {
+        // Killed by a signal: no code, report a plain failure
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_tool_by_binary_name() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("ripgrep").with_binary("rg"))?;
+        db.insert_tool(&Tool::new("rg"))?;
+        db.insert_tool(&Tool::new("fd-find").with_binary("fd"))?;
+
+        // A tool's own name wins over another tool's binary
+        assert_eq!(find_tool(&db, "rg")?.unwrap().name, "rg");
+        assert_eq!(find_tool(&db, "fd")?.unwrap().name, "fd-find");
+        assert_eq!(find_tool(&db, "ripgrep")?.unwrap().name, "ripgrep");
+        assert!(find_tool(&db, "bat")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_untracked_without_source() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        let err = cmd_run(&db, "hoards-no-such-tool", &[], None, true).unwrap_err();
+        assert!(err.to_string().contains("not installed or tracked"));
+        assert!(err.to_string().contains("--source"));
+        // Nothing was installed or recorded
+        assert!(db.get_all_tools()?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,539 @@
+//! REST API daemon
+//!
+//! `hoards serve` answers JSON requests about the hoard so editors,
+//! dashboards and other machines can use it without shelling out. Reads are
+//! answered by a few handler threads, each with its own database connection;
+//! syncs and installs are queued and run one at a time on a worker thread,
+//! and their progress is polled through `/api/jobs`.
+
+use anyhow::Result;
+use chrono::Utc;
+use colored::Colorize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io::BufReader;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::install::{install_tool, validate_package_name, validate_version};
+use super::sync::{cmd_scan, cmd_sync_status};
+use super::usage::cmd_usage_flush;
+use crate::db::Database;
+use crate::models::ToolKind;
+use crate::server::{Request, Response, generate_token, read_request, tokens_match};
+
+/// Environment variable holding the API token when `--token` isn't given
+pub const TOKEN_ENV: &str = "HOARDS_API_TOKEN";
+
+/// Jobs kept for `/api/jobs`; new ones are refused while this many are
+/// still queued or running
+const MAX_JOBS: usize = 100;
+
+/// Threads answering requests, so one slow client doesn't hold up the rest
+const HANDLERS: usize = 4;
+
+/// How long a client gets to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait for the other connection's database lock
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Work queued through the API
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Task {
+    Sync {
+        scan: bool,
+    },
+    Install {
+        name: String,
+        source: Option<String>,
+        version: Option<String>,
+        /// Move a pinned tool to `version`
+        force: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// A queued task and how it went
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    pub id: u64,
+    #[serde(flatten)]
+    pub task: Task,
+    pub status: JobStatus,
+    pub error: Option<String>,
+    pub queued_at: String,
+    pub finished_at: Option<String>,
+}
+
+/// Jobs seen so far, and the worker's queue
+pub struct Jobs {
+    list: Arc<Mutex<Vec<Job>>>,
+    sender: Sender<u64>,
+}
+
+impl Jobs {
+    /// Start the worker thread that runs queued tasks in order
+    pub fn start() -> Self {
+        let list: Arc<Mutex<Vec<Job>>> = Arc::default();
+        let (sender, receiver) = mpsc::channel::<u64>();
+
+        let worker_list = Arc::clone(&list);
+        std::thread::spawn(move || {
+            let db = Database::open().and_then(|db| {
+                db.set_busy_timeout(BUSY_TIMEOUT)?;
+                Ok(db)
+            });
+            for id in receiver {
+                let Some(task) = update_job(&worker_list, id, |job| {
+                    job.status = JobStatus::Running;
+                    job.task.clone()
+                }) else {
+                    continue;
+                };
+                let result = match &db {
+                    Ok(db) => run_task(db, &task),
+                    Err(e) => Err(anyhow::anyhow!("{:#}", e)),
+                };
+                update_job(&worker_list, id, |job| {
+                    job.finished_at = Some(Utc::now().to_rfc3339());
+                    match result {
+                        Ok(()) => job.status = JobStatus::Done,
+                        Err(e) => {
+                            job.status = JobStatus::Failed;
+                            job.error = Some(format!("{:#}", e));
+                        }
+                    }
+                });
+            }
+        });
+
+        Self { list, sender }
+    }
+
+    /// Queue a task, returning its job, or None when the queue is full
+    pub fn queue(&self, task: Task) -> Option<Job> {
+        let mut list = self.list.lock().unwrap_or_else(|e| e.into_inner());
+        while list.len() >= MAX_JOBS {
+            let oldest = list.iter().position(|j| j.finished_at.is_some())?;
+            list.remove(oldest);
+        }
+        let id = list.last().map_or(1, |job| job.id + 1);
+        let job = Job {
+            id,
+            task,
+            status: JobStatus::Queued,
+            error: None,
+            queued_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+        };
+        list.push(job.clone());
+        drop(list);
+
+        let _ = self.sender.send(id);
+        Some(job)
+    }
+
+    /// Every kept job, newest first
+    pub fn all(&self) -> Vec<Job> {
+        let list = self.list.lock().unwrap_or_else(|e| e.into_inner());
+        list.iter().rev().cloned().collect()
+    }
+
+    pub fn get(&self, id: u64) -> Option<Job> {
+        let list = self.list.lock().unwrap_or_else(|e| e.into_inner());
+        list.iter().find(|job| job.id == id).cloned()
+    }
+}
+
+fn update_job<T>(list: &Mutex<Vec<Job>>, id: u64, f: impl FnOnce(&mut Job) -> T) -> Option<T> {
+    let mut list = list.lock().unwrap_or_else(|e| e.into_inner());
+    list.iter_mut().find(|job| job.id == id).map(f)
+}
+
+fn run_task(db: &Database, task: &Task) -> Result<()> {
+    match task {
+        Task::Sync { scan } => {
+            cmd_sync_status(db, false)?;
+            cmd_usage_flush(db)?;
+            if *scan {
+                cmd_scan(db, false, false)?;
+            }
+            Ok(())
+        }
+        // Nobody is there to confirm, but pins still hold unless forced
+        Task::Install {
+            name,
+            source,
+            version,
+            force,
+        } => install_tool(db, name, source.clone(), version.clone(), true, *force),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SyncBody {
+    scan: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct InstallBody {
+    name: String,
+    source: Option<String>,
+    version: Option<String>,
+    #[serde(default)]
+    force: bool,
+}
+
+/// Parse a JSON body, where an empty one means all defaults
+fn parse_body<T: DeserializeOwned + Default>(request: &Request) -> Result<T, Response> {
+    if request.body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(T::default());
+    }
+    serde_json::from_slice(&request.body)
+        .map_err(|e| Response::error(400, format!("Invalid request body: {}", e)))
+}
+
+/// 202 with the queued job, or 503 when the queue is full
+fn queued(job: Option<Job>) -> Response {
+    match job {
+        Some(job) => Response::json(202, &job),
+        None => Response::error(
+            503,
+            format!("{} jobs are already waiting; try again later", MAX_JOBS),
+        ),
+    }
+}
+
+/// Answer one request, after checking its token
+pub fn handle(db: &Database, jobs: &Jobs, token: &str, request: &Request) -> Response {
+    if !request
+        .bearer_token()
+        .is_some_and(|given| tokens_match(given, token))
+    {
+        return Response::error(401, "Missing or wrong bearer token");
+    }
+    route(db, jobs, request).unwrap_or_else(|e| Response::error(500, format!("{:#}", e)))
+}
+
+fn route(db: &Database, jobs: &Jobs, request: &Request) -> Result<Response> {
+    let segments = request.segments();
+    let Some(("api", rest)) = segments.split_first().map(|(first, rest)| (*first, rest)) else {
+        return Ok(Response::error(404, "Endpoints are under /api"));
+    };
+
+    Ok(match (request.method.as_str(), rest) {
+        ("GET", ["tools"]) => {
+            let category = request.query.get("category").map(String::as_str);
+            let mut tools = match request.query.get("label") {
+                Some(label) => db.list_tools_by_label(label)?,
+                None => db.list_tools(request.flag("installed"), category)?,
+            };
+            if !request.flag("libraries") {
+                tools.retain(|t| t.kind != ToolKind::Library);
+            }
+            Response::ok(&tools)
+        }
+        ("GET", ["tools", "search"]) => match request.query.get("q") {
+            Some(query) => Response::ok(&db.search_tools(query)?),
+            None => Response::error(400, "Missing query parameter 'q'"),
+        },
+        ("GET", ["tools", name]) => match db.get_tool_by_name(name)? {
+            Some(tool) => Response::ok(&serde_json::json!({
+                "labels": db.get_labels(name)?,
+                "usage": db.get_usage(name)?,
+                "health": db.get_tool_health()?.remove(*name),
+                "tool": tool,
+            })),
+            None => Response::error(404, format!("Tool '{}' not found", name)),
+        },
+        ("POST", ["sync"]) => match parse_body::<SyncBody>(request) {
+            Ok(body) => queued(jobs.queue(Task::Sync { scan: body.scan })),
+            Err(response) => response,
+        },
+        ("POST", ["install"]) => {
+            let body: InstallBody = match parse_body(request) {
+                Ok(body) => body,
+                Err(response) => return Ok(response),
+            };
+            let valid = validate_package_name(&body.name)
+                .and_then(|()| body.version.as_deref().map_or(Ok(()), validate_version));
+            match valid {
+                Ok(()) => queued(jobs.queue(Task::Install {
+                    name: body.name,
+                    source: body.source,
+                    version: body.version,
+                    force: body.force,
+                })),
+                Err(e) => Response::error(400, format!("{:#}", e)),
+            }
+        }
+        ("GET", ["jobs"]) => Response::ok(&jobs.all()),
+        ("GET", ["jobs", id]) => match id.parse().ok().and_then(|id| jobs.get(id)) {
+            Some(job) => Response::ok(&job),
+            None => Response::error(404, format!("Job '{}' not found", id)),
+        },
+        ("GET", ["insights", "stats"]) => {
+            let (total, installed, favorites) = db.get_stats()?;
+            Response::ok(&serde_json::json!({
+                "total": total,
+                "installed": installed,
+                "missing": total - installed,
+                "favorites": favorites,
+                "categories": db.get_categories()?.len(),
+            }))
+        }
+        ("GET", ["insights", "usage"]) => {
+            let usage: Vec<_> = db
+                .get_all_usage()?
+                .into_iter()
+                .map(|(name, stats)| serde_json::json!({ "tool": name, "usage": stats }))
+                .collect();
+            Response::ok(&usage)
+        }
+        ("GET", ["insights", "unused"]) => Response::ok(&db.get_unused_tools()?),
+        ("GET", ["insights", "health"]) => {
+            let mut scores: Vec<_> = db.get_tool_health()?.into_iter().collect();
+            scores.sort_by(|a, b| a.1.score.cmp(&b.1.score).then(a.0.cmp(&b.0)));
+            let scores: Vec<_> = scores
+                .into_iter()
+                .map(|(name, health)| serde_json::json!({ "tool": name, "health": health }))
+                .collect();
+            Response::ok(&scores)
+        }
+        (_, ["tools" | "sync" | "install" | "jobs" | "insights", ..]) => Response::error(
+            405,
+            format!("{} isn't supported on {}", request.method, request.path),
+        ),
+        _ => Response::error(404, format!("No endpoint at {}", request.path)),
+    })
+}
+
+fn serve_connection(db: &Database, jobs: &Jobs, token: &str, stream: TcpStream) -> Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let response = match read_request(&mut reader) {
+        Ok(request) => {
+            let response = handle(db, jobs, token, &request);
+            tracing::info!("{} {} -> {}", request.method, request.path, response.status);
+            response
+        }
+        Err(e) => Response::error(400, format!("{:#}", e)),
+    };
+    response.write_to(&mut &stream)
+}
+
+/// Answer connections handed over by the accept loop, one at a time
+fn run_handler(db: Database, jobs: &Jobs, token: &str, streams: &Mutex<Receiver<TcpStream>>) {
+    loop {
+        let stream = match streams.lock().unwrap_or_else(|e| e.into_inner()).recv() {
+            Ok(stream) => stream,
+            Err(_) => return,
+        };
+        if let Err(e) = serve_connection(&db, jobs, token, stream) {
+            tracing::warn!("Failed to answer a request: {:#}", e);
+        }
+    }
+}
+
+/// Serve the JSON API until interrupted
+pub fn cmd_serve(listen: &str, token: Option<String>) -> Result<()> {
+    let (token, generated) = match token.or_else(|| std::env::var(TOKEN_ENV).ok()) {
+        Some(token) if !token.trim().is_empty() => (token.trim().to_string(), false),
+        _ => (generate_token()?, true),
+    };
+
+    let listener = TcpListener::bind(listen)
+        .map_err(|e| anyhow::anyhow!("Can't listen on {}: {}", listen, e))?;
+    let address = listener.local_addr()?;
+    let jobs = Arc::new(Jobs::start());
+
+    // Connections wait here while every handler is busy
+    let (sender, streams) = mpsc::sync_channel::<TcpStream>(HANDLERS * 4);
+    let streams = Arc::new(Mutex::new(streams));
+    let token = Arc::new(token);
+    for _ in 0..HANDLERS {
+        let db = Database::open()?;
+        db.set_busy_timeout(BUSY_TIMEOUT)?;
+        let (jobs, token, streams) = (Arc::clone(&jobs), Arc::clone(&token), Arc::clone(&streams));
+        std::thread::spawn(move || run_handler(db, &jobs, &token, &streams));
+    }
+
+    println!(
+        "{} Serving the hoards API on {}",
+        ">".cyan(),
+        format!("http://{}/api", address).bold()
+    );
+    if generated {
+        println!("  Token: {}", token.as_str().yellow());
+        println!(
+            "  {}",
+            format!(
+                "Set {} or pass --token to keep it across restarts",
+                TOKEN_ENV
+            )
+            .dimmed()
+        );
+    }
+    if !address.ip().is_loopback() {
+        println!(
+            "{} Listening beyond this machine: requests and the token travel unencrypted",
+            "!".yellow()
+        );
+    }
+    println!();
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("Failed to accept a connection: {}", e);
+                continue;
+            }
+        };
+        if sender.send(stream).is_err() {
+            anyhow::bail!("Every request handler has stopped");
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{InstallSource, Tool};
+
+    fn request(method: &str, path: &str, body: &str) -> Request {
+        let raw = format!(
+            "{} {} HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        );
+        read_request(&mut raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_routes() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(&Tool::new("ripgrep").with_source(InstallSource::Cargo))
+            .unwrap();
+        let (sender, _receiver) = mpsc::channel();
+        let jobs = Jobs {
+            list: Arc::default(),
+            sender,
+        };
+
+        let mut unauthorized = request("GET", "/api/tools", "");
+        unauthorized.headers.remove("authorization");
+        assert_eq!(handle(&db, &jobs, "secret", &unauthorized).status, 401);
+
+        let list = handle(&db, &jobs, "secret", &request("GET", "/api/tools", ""));
+        assert_eq!(list.status, 200);
+        assert_eq!(list.body[0]["name"], "ripgrep");
+
+        let search = request("GET", "/api/tools/search?q=rip", "");
+        assert_eq!(
+            handle(&db, &jobs, "secret", &search).body[0]["name"],
+            "ripgrep"
+        );
+
+        let show = handle(
+            &db,
+            &jobs,
+            "secret",
+            &request("GET", "/api/tools/ripgrep", ""),
+        );
+        assert_eq!(show.body["tool"]["name"], "ripgrep");
+        let missing = request("GET", "/api/tools/nope", "");
+        assert_eq!(handle(&db, &jobs, "secret", &missing).status, 404);
+
+        let install = request(
+            "POST",
+            "/api/install",
+            r#"{"name": "fd", "source": "cargo"}"#,
+        );
+        let queued = handle(&db, &jobs, "secret", &install);
+        assert_eq!(queued.status, 202);
+        assert_eq!(queued.body["kind"], "install");
+        assert_eq!(queued.body["status"], "queued");
+        let bad_name = request("POST", "/api/install", r#"{"name": "fd; rm -rf /"}"#);
+        assert_eq!(handle(&db, &jobs, "secret", &bad_name).status, 400);
+
+        let sync = handle(&db, &jobs, "secret", &request("POST", "/api/sync", ""));
+        assert_eq!(sync.body["id"], 2);
+        let job = handle(&db, &jobs, "secret", &request("GET", "/api/jobs/1", ""));
+        assert_eq!(job.body["name"], "fd");
+
+        let wrong_method = request("DELETE", "/api/tools", "");
+        assert_eq!(handle(&db, &jobs, "secret", &wrong_method).status, 405);
+        let stats = handle(
+            &db,
+            &jobs,
+            "secret",
+            &request("GET", "/api/insights/stats", ""),
+        );
+        assert_eq!(stats.body["total"], 1);
+    }
+
+    #[test]
+    fn test_queue_is_capped() {
+        let (sender, _receiver) = mpsc::channel();
+        let jobs = Jobs {
+            list: Arc::default(),
+            sender,
+        };
+        let sync = || Task::Sync { scan: false };
+        for _ in 0..MAX_JOBS {
+            assert!(jobs.queue(sync()).is_some());
+        }
+        assert!(jobs.queue(sync()).is_none());
+        assert_eq!(queued(jobs.queue(sync())).status, 503);
+
+        // Finished jobs make room for new ones
+        update_job(&jobs.list, 1, |job| {
+            job.finished_at = Some(Utc::now().to_rfc3339())
+        });
+        let job = jobs.queue(sync()).unwrap();
+        assert_eq!(job.id, MAX_JOBS as u64 + 1);
+        assert!(jobs.get(1).is_none());
+        assert_eq!(jobs.all().len(), MAX_JOBS);
+    }
+
+    #[test]
+    fn test_install_keeps_pins() {
+        let db = Database::open_in_memory().unwrap();
+        let name = "hoards-test-pinned-tool";
+        db.insert_tool(&Tool::new(name).with_source(InstallSource::Cargo))
+            .unwrap();
+        db.pin_tool(name, Some("1.0.0")).unwrap();
+
+        let install = |force| Task::Install {
+            name: name.to_string(),
+            source: None,
+            version: Some("2.0.0".to_string()),
+            force,
+        };
+        let error = run_task(&db, &install(false)).unwrap_err().to_string();
+        assert!(error.contains("pinned at 1.0.0"), "{}", error);
+
+        let forced = request("POST", "/api/install", r#"{"name": "fd", "force": true}"#);
+        assert!(parse_body::<InstallBody>(&forced).unwrap().force);
+        let plain = request("POST", "/api/install", r#"{"name": "fd"}"#);
+        assert!(!parse_body::<InstallBody>(&plain).unwrap().force);
+    }
+}
@@ -1,14 +1,20 @@
 //! Sync commands: sync_status, scan, fetch_descriptions
 
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::thread;
+use std::time::UNIX_EPOCH;
 
 use anyhow::Result;
 use colored::Colorize;
 
-use crate::db::Database;
-use crate::models::Tool;
-use crate::scanner::{is_installed, scan_known_tools, scan_path_tools};
+use crate::db::{Database, RelationKind, ScanCacheEntry};
+use crate::dependencies::scan_dependencies;
+use crate::dotfiles::content_hash;
+use crate::models::{Tool, ToolKind};
+use crate::runtimes::{Runtime, detect_runtimes};
+use crate::scanner::{binary_version, is_installed, scan_known_tools, scan_path_tools};
+use crate::services::scan_user_services;
 use crate::sources::all_sources;
 
 use super::helpers::fetch_tool_description;
@@ -58,12 +64,102 @@ pub fn cmd_sync_status(db: &Database, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
+/// Newest modification time among the paths that exist, in Unix seconds
+fn state_mtime(paths: &[PathBuf]) -> Option<i64> {
+    paths
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .max()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+}
+
+/// Hash of what a listing would add, leaving out timestamps
+fn listing_hash(tools: &[Tool]) -> String {
+    let key: Vec<_> = tools
+        .iter()
+        .map(|t| {
+            (
+                &t.name,
+                &t.binary_name,
+                t.source.to_string(),
+                &t.category,
+                &t.description,
+                &t.installed_version,
+            )
+        })
+        .collect();
+    content_hash(serde_json::to_string(&key).unwrap_or_default().as_bytes())
+}
+
+/// Fill in missing versions from each binary's `--version`, a batch of
+/// binaries at a time
+fn probe_versions(tools: &mut [Tool]) {
+    for batch in tools.chunks_mut(16) {
+        thread::scope(|s| {
+            for tool in batch.iter_mut().filter(|t| t.installed_version.is_none()) {
+                s.spawn(move || {
+                    let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+                    tool.installed_version = binary_version(binary);
+                });
+            }
+        });
+    }
+}
+
+/// A source's listing for this scan
+struct Listing {
+    entry: ScanCacheEntry,
+    /// Same listing as the last scan, so there's nothing to diff
+    unchanged: bool,
+}
+
+/// List a source, reusing the last scan's listing while the source's state
+/// files are untouched; `full` always lists afresh
+fn list_source(
+    db: &Database,
+    source: &str,
+    state_paths: &[PathBuf],
+    full: bool,
+    scan: impl FnOnce() -> Result<Vec<Tool>>,
+) -> Result<Listing> {
+    let mtime = state_mtime(state_paths);
+    let cached = if full {
+        None
+    } else {
+        db.get_scan_cache(source)?
+    };
+    if let Some(cached) = &cached
+        && mtime.is_some()
+        && cached.mtime == mtime
+    {
+        tracing::debug!("{} state unchanged, reusing last scan", source);
+        return Ok(Listing {
+            entry: cached.clone(),
+            unchanged: true,
+        });
+    }
+
+    let tools = scan()?;
+    let hash = listing_hash(&tools);
+    Ok(Listing {
+        unchanged: cached.is_some_and(|c| c.hash == hash),
+        entry: ScanCacheEntry { mtime, hash, tools },
+    })
+}
+
 /// Scan system for new tools
-pub fn cmd_scan(db: &Database, dry_run: bool) -> Result<()> {
+///
+/// Sources whose listing hasn't changed since the last scan are skipped;
+/// `full` rescans and diffs every source.
+pub fn cmd_scan(db: &Database, dry_run: bool, full: bool) -> Result<()> {
     println!("{} Scanning for new tools...\n", ">".cyan());
 
     let mut added = 0;
     let mut skipped = 0;
+    let mut unchanged = 0;
+    let mut versions = 0;
+    let mut reclassified = 0;
     let mut tracked_binaries: HashSet<String> = HashSet::new();
     let mut newly_added: Vec<Tool> = Vec::new();
 
@@ -76,71 +172,123 @@ pub fn cmd_scan(db: &Database, dry_run: bool) -> Result<()> {
     }
 
     // Helper to process tools from any source
-    let mut process_tools =
-        |tools: Vec<Tool>, source_name: &str, track: bool| -> Result<Vec<Tool>> {
-            if tools.is_empty() {
-                return Ok(Vec::new());
+    let mut process_tools = |listing: &Listing, source_name: &str| -> Result<Vec<Tool>> {
+        let tools = &listing.entry.tools;
+
+        // Track binaries for PATH scan exclusion
+        for tool in tools {
+            if let Some(ref bin) = tool.binary_name {
+                tracked_binaries.insert(bin.clone());
             }
+            tracked_binaries.insert(tool.name.clone());
+        }
+
+        if tools.is_empty() || listing.unchanged {
+            return Ok(Vec::new());
+        }
 
-            println!("{} {} tools:", ">".cyan(), source_name);
-            let mut added_tools = Vec::new();
+        println!("{} {} tools:", ">".cyan(), source_name);
+        let mut added_tools = Vec::new();
 
-            for tool in tools {
-                // Track binary for PATH scan exclusion
-                if track {
-                    if let Some(ref bin) = tool.binary_name {
-                        tracked_binaries.insert(bin.clone());
+        for tool in tools {
+            // Check if already in database, refreshing its version
+            if let Some(existing) = db.get_tool_by_name(&tool.name)? {
+                if existing.kind != tool.kind && tool.kind != ToolKind::Application {
+                    if !dry_run {
+                        db.set_tool_kind(&tool.name, tool.kind)?;
                     }
-                    tracked_binaries.insert(tool.name.clone());
+                    reclassified += 1;
                 }
-
-                // Check if already in database
-                if db.get_tool_by_name(&tool.name)?.is_some() {
-                    skipped += 1;
-                    continue;
+                if tool.installed_version.is_some()
+                    && existing.source == tool.source
+                    && existing.installed_version != tool.installed_version
+                {
+                    if !dry_run {
+                        db.set_tool_installed_version(
+                            &tool.name,
+                            tool.installed_version.as_deref(),
+                        )?;
+                    }
+                    versions += 1;
                 }
+                skipped += 1;
+                continue;
+            }
 
-                println!(
-                    "  {} {} ({})",
-                    "+".green(),
-                    tool.name,
-                    tool.category.as_deref().unwrap_or("?")
-                );
-
-                if !dry_run {
-                    db.insert_tool(&tool)?;
-                }
-                added += 1;
+            println!(
+                "  {} {} ({})",
+                "+".green(),
+                tool.name,
+                tool.category.as_deref().unwrap_or("?")
+            );
 
-                // Track tools that need descriptions
-                if tool.description.is_none() {
-                    added_tools.push(tool);
-                }
+            if !dry_run {
+                db.insert_tool(tool)?;
             }
-            println!();
-            Ok(added_tools)
-        };
+            added += 1;
 
-    // 1. Scan known tools (curated list with good metadata)
-    newly_added.extend(process_tools(scan_known_tools(), "Known", true)?);
-
-    // 2. Scan all package sources using the trait-based system
+            // Track tools that need descriptions
+            if tool.description.is_none() {
+                added_tools.push(tool.clone());
+            }
+        }
+        println!();
+        Ok(added_tools)
+    };
+
+    // Remember a listing once its tools are in the database
+    let mut finish = |source: &str, listing: Listing| -> Result<()> {
+        if listing.unchanged {
+            unchanged += 1;
+        } else if !dry_run {
+            db.set_scan_cache(source, &listing.entry)?;
+        }
+        Ok(())
+    };
+
+    // 1. Detect runtimes and toolchains, ahead of the known tools so docker,
+    // kubectl and the like are filed as runtimes
+    let listing = list_source(db, "runtimes", &[], full, || {
+        let mut tools: Vec<Tool> = detect_runtimes().iter().map(Runtime::to_tool).collect();
+        probe_versions(&mut tools);
+        Ok(tools)
+    })?;
+    newly_added.extend(process_tools(&listing, "Runtime")?);
+    finish("runtimes", listing)?;
+
+    // 2. Scan known tools (curated list with good metadata)
+    let listing = list_source(db, "known", &[], full, || {
+        let mut tools = scan_known_tools();
+        probe_versions(&mut tools);
+        Ok(tools)
+    })?;
+    newly_added.extend(process_tools(&listing, "Known")?);
+    finish("known", listing)?;
+
+    // 3. Scan all package sources using the trait-based system
     for source in all_sources() {
         // Skip manual source in the main scan loop
         if source.name() == "manual" {
             continue;
         }
 
-        match source.scan() {
-            Ok(tools) => {
-                let label = format!("{} ({})", source.name(), tools.len());
-                newly_added.extend(process_tools(tools, &label, true)?);
+        match list_source(db, source.name(), &source.state_paths(), full, || {
+            source.scan()
+        }) {
+            Ok(listing) => {
+                let count = listing.entry.tools.len();
+                tracing::debug!("{} scan found {} tool(s)", source.name(), count);
+                let label = format!("{} ({})", source.name(), count);
+                newly_added.extend(process_tools(&listing, &label)?);
+                finish(source.name(), listing)?;
             }
             Err(e) => {
-                // Skip silently if source not installed (e.g., brew)
+                // Only a debug note if the source isn't installed (e.g., brew)
                 let err_str = e.to_string();
                 if !err_str.contains("No such file") && !err_str.contains("not found") {
-                    eprintln!("  {} {} scan: {}", "!".yellow(), source.name(), e);
+                    tracing::warn!("{} scan failed: {:#}", source.name(), e);
+                } else {
+                    tracing::debug!("{} scan skipped: {}", source.name(), e);
                 }
             }
         }
@@ -149,12 +297,18 @@ pub fn cmd_scan(db: &Database, dry_run: bool) -> Result<()> {
     // Scan PATH for untracked binaries (go tools, manual installs, etc.)
     match scan_path_tools(&tracked_binaries) {
         Ok(tools) if !tools.is_empty() => {
-            println!("{} PATH (untracked) tools:", ">".cyan());
+            let mut untracked = Vec::new();
             for tool in tools {
                 if db.get_tool_by_name(&tool.name)?.is_some() {
                     skipped += 1;
-                    continue;
+                } else {
+                    untracked.push(tool);
                 }
+            }
+            probe_versions(&mut untracked);
+
+            println!("{} PATH (untracked) tools:", ">".cyan());
+            for tool in untracked {
                 println!(
                     "  {} {} ({})",
                     "+".green(),
@@ -172,7 +326,46 @@ pub fn cmd_scan(db: &Database, dry_run: bool) -> Result<()> {
             println!();
         }
         Ok(_) => {}
-        Err(e) => eprintln!("  {} path scan: {}", "!".yellow(), e),
+        Err(e) => tracing::warn!("PATH scan failed: {:#}", e),
+    }
+
+    // Record user services and the tools they run
+    let services = scan_user_services(&db.get_all_tools()?);
+    let recorded: HashSet<(String, String)> = db
+        .list_services()?
+        .into_iter()
+        .map(|s| (s.manager, s.name))
+        .collect();
+    let new_services: Vec<_> = services
+        .iter()
+        .filter(|s| !recorded.contains(&(s.manager.clone(), s.name.clone())))
+        .collect();
+    if !new_services.is_empty() {
+        println!("{} Services:", ">".cyan());
+        for service in new_services {
+            println!(
+                "  {} {} ({}) -> {}",
+                "+".green(),
+                service.name,
+                service.manager,
+                service.tool.as_deref().unwrap_or(&service.binary)
+            );
+        }
+        println!();
+    }
+    if !dry_run {
+        db.replace_services(&services)?;
+    }
+
+    // Record which tracked tools other tracked tools depend on
+    let dependencies = if dry_run {
+        0
+    } else {
+        db.replace_relations(RelationKind::DependsOn, &scan_dependencies())?
+    };
+    if !dry_run {
+        // Keep the health badges of `list` and the TUI current
+        super::insights::refresh_health(db)?;
     }
 
     // Fetch descriptions in parallel for newly added tools
@@ -208,6 +401,36 @@ pub fn cmd_scan(db: &Database, dry_run: bool) -> Result<()> {
     }
 
     // Summary
+    if versions > 0 {
+        println!(
+            "{} {} {} installed version(s) of tracked tools",
+            "i".cyan(),
+            if dry_run { "Would update" } else { "Updated" },
+            versions
+        );
+    }
+    if reclassified > 0 {
+        println!(
+            "{} {} {} tracked tool(s) as runtimes or libraries",
+            "i".cyan(),
+            if dry_run { "Would mark" } else { "Marked" },
+            reclassified
+        );
+    }
+    if dependencies > 0 {
+        println!(
+            "{} Recorded {} dependencies between tracked tools",
+            "i".cyan(),
+            dependencies
+        );
+    }
+    if unchanged > 0 {
+        println!(
+            "{} {} source(s) unchanged since the last scan (use --full to rescan them)",
+            "i".cyan(),
+            unchanged
+        );
+    }
     if added == 0 && skipped == 0 {
         println!("No new tools found on system");
     } else if dry_run {
@@ -298,3 +521,42 @@ pub fn cmd_fetch_descriptions(db: &Database, dry_run: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_source() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        let dir = tempfile::tempdir()?;
+        let state = vec![dir.path().join("status")];
+        std::fs::write(&state[0], "rg")?;
+        let tools = || Ok(vec![Tool::new("rg").with_binary("rg")]);
+
+        // First scan lists the source
+        let listing = list_source(&db, "apt", &state, false, tools)?;
+        assert!(!listing.unchanged);
+        db.set_scan_cache("apt", &listing.entry)?;
+
+        // Untouched state files reuse the listing without scanning
+        let listing = list_source(&db, "apt", &state, false, || unreachable!())?;
+        assert!(listing.unchanged);
+        assert_eq!(listing.entry.tools[0].name, "rg");
+
+        // A full scan lists and diffs again; without state files only the
+        // listing hash tells
+        let listing = list_source(&db, "apt", &state, true, tools)?;
+        assert!(!listing.unchanged);
+        let listing = list_source(&db, "known", &[], false, tools)?;
+        assert!(!listing.unchanged);
+        db.set_scan_cache("known", &listing.entry)?;
+        let listing = list_source(&db, "known", &[], false, tools)?;
+        assert!(listing.unchanged);
+
+        // A changed listing needs a diff
+        let more = || Ok(vec![Tool::new("rg"), Tool::new("fd")]);
+        assert!(!list_source(&db, "known", &[], false, more)?.unchanged);
+        Ok(())
+    }
+}
@@ -2,10 +2,44 @@
 
 use anyhow::Result;
 use colored::Colorize;
+use serde::Serialize;
 
 use crate::db::Database;
+use crate::models::InstallSource;
+use crate::sources::{PackageSource, plugin, plugins};
 use crate::updates::*;
 
+/// Asks one package manager for its pending updates
+type UpdateCheck = fn() -> Result<Vec<Update>>;
+
+/// Package managers `updates` asks, in order
+const UPDATE_SOURCES: &[(&str, UpdateCheck)] = &[
+    ("cargo", check_cargo_updates),
+    ("pip", check_pip_updates),
+    ("npm", check_npm_updates),
+    ("apt", check_apt_updates),
+    ("brew", check_brew_updates),
+    ("shell", check_shell_updates),
+];
+
+/// Updates from every package manager (or just `source_filter`)
+///
+/// Sources whose manager is missing are left out.
+pub(crate) fn collect_updates(source_filter: Option<&str>) -> Vec<Update> {
+    let mut all = Vec::new();
+    for &(name, check_fn) in UPDATE_SOURCES {
+        if source_filter.is_none_or(|filter| filter == name) {
+            all.extend(check_fn().unwrap_or_default());
+        }
+    }
+    for plugin in plugins() {
+        if source_filter.is_none_or(|filter| filter == plugin.name()) {
+            all.extend(check_plugin_updates(plugin).unwrap_or_default());
+        }
+    }
+    all
+}
+
 /// Check for available updates
 pub fn cmd_updates(
     db: &Database,
@@ -13,21 +47,28 @@ pub fn cmd_updates(
     cross: bool,
     tracked: bool,
     all_versions: bool,
+    json_output: bool,
 ) -> Result<()> {
     if cross {
-        return cmd_updates_cross(db);
+        return cmd_updates_cross(db, json_output);
     }
 
     // If --tracked or --all-versions, use the tracked tools mode
     if tracked || all_versions {
-        return cmd_updates_tracked(db, source_filter, all_versions);
+        return cmd_updates_tracked(db, source_filter, all_versions, json_output);
+    }
+
+    if json_output {
+        let all = collect_updates(source_filter.as_deref());
+        println!("{}", serde_json::to_string_pretty(&all)?);
+        return Ok(());
     }
 
     println!("{} Checking for updates...\n", ">".cyan());
 
     let mut total_updates = 0;
 
-    let check_source = |name: &str, check_fn: fn() -> Result<Vec<Update>>| -> Result<usize> {
+    let check_source = |name: &str, check_fn: &dyn Fn() -> Result<Vec<Update>>| -> Result<usize> {
         print!("  {} {}... ", ">".cyan(), name);
         std::io::Write::flush(&mut std::io::stdout())?;
 
@@ -59,22 +100,21 @@ pub fn cmd_updates(
         }
     };
 
-    #[allow(clippy::type_complexity)]
-    let sources: Vec<(&str, fn() -> Result<Vec<Update>>)> = vec![
-        ("cargo", check_cargo_updates),
-        ("pip", check_pip_updates),
-        ("npm", check_npm_updates),
-        ("apt", check_apt_updates),
-        ("brew", check_brew_updates),
-    ];
-
-    for (name, check_fn) in sources {
+    for &(name, check_fn) in UPDATE_SOURCES {
         if let Some(ref filter) = source_filter
             && filter != name
         {
             continue;
         }
-        total_updates += check_source(name, check_fn)?;
+        total_updates += check_source(name, &check_fn)?;
+    }
+    for plugin in plugins() {
+        if source_filter
+            .as_deref()
+            .is_none_or(|filter| filter == plugin.name())
+        {
+            total_updates += check_source(plugin.name(), &|| check_plugin_updates(plugin))?;
+        }
     }
 
     println!();
@@ -87,17 +127,30 @@ pub fn cmd_updates(
     Ok(())
 }
 
+/// An update for a tracked tool, with every newer version for
+/// `--all-versions`
+#[derive(Serialize)]
+struct TrackedUpdate {
+    #[serde(flatten)]
+    update: Update,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    versions: Vec<String>,
+}
+
 /// Check tracked tools for updates
 pub fn cmd_updates_tracked(
     db: &Database,
     source_filter: Option<String>,
     all_versions: bool,
+    json_output: bool,
 ) -> Result<()> {
-    println!(
-        "{} Checking tracked tools for updates{}...\n",
-        ">".cyan(),
-        if all_versions { " (all versions)" } else { "" }
-    );
+    if !json_output {
+        println!(
+            "{} Checking tracked tools for updates{}...\n",
+            ">".cyan(),
+            if all_versions { " (all versions)" } else { "" }
+        );
+    }
 
     // Get all installed tools from database
     let tools = db.list_tools(true, None)?;
@@ -109,25 +162,33 @@ pub fn cmd_updates_tracked(
             if let Some(ref filter) = source_filter {
                 t.source.to_string() == *filter
             } else {
-                // Only check sources we can query (cargo, pip, npm)
+                // Only check sources we can query (cargo, pip, npm, plugins)
                 matches!(t.source.to_string().as_str(), "cargo" | "pip" | "npm")
+                    || matches!(t.source, InstallSource::Plugin(_))
             }
         })
         .collect();
 
-    if tools.is_empty() {
+    if tools.is_empty() && !json_output {
         println!("No tracked tools found for the specified source(s).");
-        println!("  Note: Only cargo, pip, and npm tools can be checked for updates.");
+        println!(
+            "  Note: Only cargo, pip, npm and source plugin tools can be checked for updates."
+        );
         return Ok(());
     }
 
     let mut updates_found = 0;
+    let mut found = Vec::new();
 
     for tool in &tools {
         let source = tool.source.to_string();
 
-        // Get current installed version
-        let current = match get_installed_version(&tool.name, &source) {
+        // Current version, as recorded by the last scan or from the source
+        let current = match tool
+            .installed_version
+            .clone()
+            .or_else(|| get_installed_version(&tool.name, &source))
+        {
             Some(v) => v,
             None => continue,
         };
@@ -135,7 +196,19 @@ pub fn cmd_updates_tracked(
         if all_versions {
             // Get all newer versions
             let versions = get_available_versions(&tool.name, &source, &current);
-            if !versions.is_empty() {
+            if json_output {
+                if let Some(latest) = versions.last() {
+                    found.push(TrackedUpdate {
+                        update: Update {
+                            name: tool.name.clone(),
+                            current,
+                            latest: latest.clone(),
+                            source,
+                        },
+                        versions,
+                    });
+                }
+            } else if !versions.is_empty() {
                 updates_found += 1;
                 println!(
                     "  {} ({}) {} -> ",
@@ -158,12 +231,24 @@ pub fn cmd_updates_tracked(
                 "cargo" => get_crates_io_latest(&tool.name),
                 "pip" => get_pypi_latest(&tool.name),
                 "npm" => get_npm_latest(&tool.name),
-                _ => None,
+                source => plugin(source).and_then(|p| p.latest_version(&tool.name)),
             };
 
             if let Some(latest) = latest
                 && version_is_newer(&latest, &current)
             {
+                if json_output {
+                    found.push(TrackedUpdate {
+                        update: Update {
+                            name: tool.name.clone(),
+                            current,
+                            latest,
+                            source,
+                        },
+                        versions: Vec::new(),
+                    });
+                    continue;
+                }
                 updates_found += 1;
                 println!(
                     "  {} ({}) {} -> {}",
@@ -176,6 +261,11 @@ pub fn cmd_updates_tracked(
         }
     }
 
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&found)?);
+        return Ok(());
+    }
+
     println!();
     if updates_found == 0 {
         println!("{} All tracked tools are up to date!", "+".green());
@@ -197,11 +287,13 @@ pub fn cmd_updates_tracked(
 }
 
 /// Check for cross-source upgrade opportunities
-pub fn cmd_updates_cross(db: &Database) -> Result<()> {
-    println!(
-        "{} Checking apt/snap tools for newer versions on other sources...\n",
-        ">".cyan()
-    );
+pub fn cmd_updates_cross(db: &Database, json_output: bool) -> Result<()> {
+    if !json_output {
+        println!(
+            "{} Checking apt/snap tools for newer versions on other sources...\n",
+            ">".cyan()
+        );
+    }
 
     // Get all apt/snap tools from database with their versions
     let tools = db.list_tools(true, None)?;
@@ -218,6 +310,12 @@ pub fn cmd_updates_cross(db: &Database) -> Result<()> {
         })
         .collect();
 
+    if json_output {
+        let upgrades = check_cross_source_upgrades(&apt_snap_tools);
+        println!("{}", serde_json::to_string_pretty(&upgrades)?);
+        return Ok(());
+    }
+
     if apt_snap_tools.is_empty() {
         println!("No apt/snap tools found in database.");
         return Ok(());
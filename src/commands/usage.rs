@@ -2,7 +2,7 @@
 //!
 //! Commands for tracking and analyzing tool usage from shell history.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
 
 use crate::Database;
@@ -48,15 +48,41 @@ pub fn cmd_labels(db: &Database) -> Result<()> {
 
 /// Scan shell history for usage data
 pub fn cmd_usage_scan(db: &Database, dry_run: bool, reset: bool) -> Result<()> {
-    use crate::history::parse_all_histories;
+    use crate::config::{HistorySource, HoardConfig};
+    use crate::history::{
+        count_command_patterns, count_command_rhythms, count_commands, merge_histories,
+        read_histories,
+    };
 
-    println!("{} Scanning shell history...", ">".cyan());
+    let usage = HoardConfig::load().unwrap_or_default().usage;
+    let what = match usage.history {
+        HistorySource::Shell => "shell history",
+        HistorySource::Atuin => "atuin history",
+        HistorySource::All => "shell and atuin history",
+    };
+    println!("{} Scanning {}...", ">".cyan(), what);
 
-    // Parse all shell histories
-    let counts = parse_all_histories()?;
+    // Parse the configured histories, merging shells into one pass
+    let merged = merge_histories(read_histories(&usage));
+    for (shell, read, kept) in &merged.per_shell {
+        let dupes = read - kept;
+        println!(
+            "  {} {:8} {:>7} entries{}",
+            "-".dimmed(),
+            shell,
+            kept,
+            if dupes > 0 {
+                format!(" ({} already seen)", dupes).dimmed().to_string()
+            } else {
+                String::new()
+            }
+        );
+    }
+    let entries = merged.entries;
+    let counts = count_commands(&entries);
 
     if counts.is_empty() {
-        println!("{} No shell history found", "!".yellow());
+        println!("{} No history found", "!".yellow());
         return Ok(());
     }
 
@@ -107,7 +133,30 @@ pub fn cmd_usage_scan(db: &Database, dry_run: bool, reset: bool) -> Result<()> {
     }
 
     // Sort by count descending
-    tool_counts.sort_by(|a, b| b.1.cmp(&a.1));
+    tool_counts.sort_by_key(|t| std::cmp::Reverse(t.1));
+
+    // Weekday/hour buckets from histories that record timestamps
+    if !dry_run {
+        for ((cmd, weekday, hour), count) in count_command_rhythms(&entries) {
+            let tool_name = binary_to_tool
+                .get(&cmd)
+                .cloned()
+                .or_else(|| tool_names.contains(&cmd).then_some(cmd));
+            if let Some(name) = tool_name {
+                db.record_usage_rhythm(&name, weekday, hour, count)?;
+            }
+        }
+        for ((cmd, pattern), count) in count_command_patterns(&entries) {
+            let tool_name = binary_to_tool
+                .get(&cmd)
+                .cloned()
+                .or_else(|| tool_names.contains(&cmd).then_some(cmd));
+            if let Some(name) = tool_name {
+                db.record_usage_pattern(&name, &pattern, count)?;
+            }
+        }
+        db.prune_usage_patterns()?;
+    }
 
     if tool_counts.is_empty() {
         println!("{} No matching tools found in history", "!".yellow());
@@ -158,13 +207,25 @@ pub fn cmd_usage_scan(db: &Database, dry_run: bool, reset: bool) -> Result<()> {
 }
 
 /// Show usage statistics
-pub fn cmd_usage_show(db: &Database, limit: usize) -> Result<()> {
+pub fn cmd_usage_show(db: &Database, limit: usize, json_output: bool) -> Result<()> {
     use comfy_table::{
         Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
     };
 
+    // Include events the shell hook logged since the last flush
+    flush_usage_log(db)?;
     let usage = db.get_all_usage()?;
 
+    if json_output {
+        let tools: Vec<_> = usage
+            .iter()
+            .take(limit)
+            .map(|(name, stats)| serde_json::json!({ "tool": name, "usage": stats }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&tools)?);
+        return Ok(());
+    }
+
     if usage.is_empty() {
         println!(
             "{} No usage data yet. Run {} first.",
@@ -223,10 +284,266 @@ pub fn cmd_usage_show(db: &Database, limit: usize) -> Result<()> {
     Ok(())
 }
 
+/// Export per-tool totals, daily series and last-used times as JSON or CSV
+///
+/// CSV has one row per tool and day (tools without daily data get one row
+/// with empty date and count), so it loads straight into a spreadsheet.
+pub fn cmd_usage_export(
+    db: &Database,
+    format: &str,
+    since: Option<&str>,
+    output: Option<&str>,
+) -> Result<()> {
+    use std::collections::BTreeMap;
+
+    if let Some(date) = since {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid --since date '{}' (expected YYYY-MM-DD)", date))?;
+    }
+
+    #[derive(serde::Serialize)]
+    struct DailyCount {
+        date: String,
+        count: i64,
+    }
+
+    #[derive(serde::Serialize)]
+    struct ToolExport {
+        name: String,
+        total: i64,
+        first_seen: String,
+        last_used: Option<String>,
+        daily: Vec<DailyCount>,
+        /// Totals of days past the retention window
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        monthly: Vec<MonthlyCount>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct MonthlyCount {
+        month: String,
+        count: i64,
+    }
+
+    #[derive(serde::Serialize)]
+    struct UsageExport {
+        exported_at: String,
+        since: Option<String>,
+        tools: Vec<ToolExport>,
+    }
+
+    flush_usage_log(db)?;
+
+    let mut daily: BTreeMap<String, Vec<DailyCount>> = BTreeMap::new();
+    for (tool, date, count) in db.get_daily_usage_series(since)? {
+        daily
+            .entry(tool)
+            .or_default()
+            .push(DailyCount { date, count });
+    }
+
+    let mut monthly: BTreeMap<String, Vec<MonthlyCount>> = BTreeMap::new();
+    for (tool, month, count) in db.get_monthly_usage_series(since)? {
+        monthly
+            .entry(tool)
+            .or_default()
+            .push(MonthlyCount { month, count });
+    }
+
+    let mut tools = Vec::new();
+    for (name, usage) in db.get_all_usage()? {
+        let series = daily.remove(&name).unwrap_or_default();
+        let months = monthly.remove(&name).unwrap_or_default();
+        // With --since, keep tools that were active in the period
+        if let Some(date) = since
+            && series.is_empty()
+            && months.is_empty()
+            && usage.last_used.as_deref().is_none_or(|last| last < date)
+        {
+            continue;
+        }
+        tools.push(ToolExport {
+            name,
+            total: usage.use_count,
+            first_seen: usage.first_seen,
+            last_used: usage.last_used,
+            daily: series,
+            monthly: months,
+        });
+    }
+
+    let content = match format {
+        "csv" => {
+            let mut csv = String::from("tool,date,count,total,first_seen,last_used\n");
+            for tool in &tools {
+                let fixed = format!(
+                    "{},{},{}",
+                    tool.total,
+                    csv_field(&tool.first_seen),
+                    csv_field(tool.last_used.as_deref().unwrap_or_default())
+                );
+                if tool.daily.is_empty() {
+                    csv.push_str(&format!("{},,,{}\n", csv_field(&tool.name), fixed));
+                }
+                for day in &tool.daily {
+                    csv.push_str(&format!(
+                        "{},{},{},{}\n",
+                        csv_field(&tool.name),
+                        day.date,
+                        day.count,
+                        fixed
+                    ));
+                }
+            }
+            csv
+        }
+        _ => {
+            serde_json::to_string_pretty(&UsageExport {
+                exported_at: chrono::Utc::now().to_rfc3339(),
+                since: since.map(String::from),
+                tools,
+            })? + "\n"
+        }
+    };
+
+    match output {
+        Some(path) => {
+            let path = std::path::Path::new(path);
+            if path
+                .components()
+                .any(|c| matches!(c, std::path::Component::ParentDir))
+            {
+                anyhow::bail!("Output path cannot contain '..' components");
+            }
+            std::fs::write(path, content)?;
+            println!(
+                "{} Exported usage to {}",
+                "+".green(),
+                path.display().to_string().cyan()
+            );
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Show which tools are used within a project (recorded by hook mode)
+pub fn cmd_usage_project(db: &Database, path: &str, limit: usize, json_output: bool) -> Result<()> {
+    use comfy_table::{
+        Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
+    };
+
+    let dir = std::path::Path::new(path)
+        .canonicalize()
+        .with_context(|| format!("Cannot open directory '{}'", path))?;
+    let project = crate::project::project_root(&dir);
+    let project = project.to_string_lossy();
+
+    flush_usage_log(db)?;
+    let usage = db.get_project_usage(&project)?;
+
+    if json_output {
+        let tools = &usage[..usage.len().min(limit)];
+        let report = serde_json::json!({ "project": project, "tools": tools });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if usage.is_empty() {
+        println!("{} No usage recorded in {}", "!".yellow(), project);
+        println!(
+            "  Per-project usage is recorded in hook mode: {}",
+            "hoards usage config --mode hook".cyan()
+        );
+        let projects = db.get_usage_projects()?;
+        if !projects.is_empty() {
+            println!("\n  Projects with usage:");
+            for (project, total) in projects.iter().take(10) {
+                println!("    {} {}", project, format!("({} uses)", total).dimmed());
+            }
+        }
+        return Ok(());
+    }
+
+    let total: i64 = usage.iter().map(|u| u.use_count).sum();
+    let term_width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0)
+        .unwrap_or(120);
+
+    println!("{} Tools used in {}\n", ">".cyan(), project.bold());
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(term_width)
+        .set_header(vec![
+            Cell::new("📊 Tool").fg(Color::Cyan),
+            Cell::new("Uses").fg(Color::Cyan),
+            Cell::new("%").fg(Color::Cyan),
+            Cell::new("Last used").fg(Color::Cyan),
+        ]);
+
+    for stats in usage.iter().take(limit) {
+        let percent = (stats.use_count as f64 / total as f64) * 100.0;
+        let last_used = stats
+            .last_used
+            .as_deref()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+        table.add_row(vec![
+            Cell::new(&stats.tool),
+            Cell::new(stats.use_count),
+            Cell::new(format!("{:.1}", percent)),
+            Cell::new(last_used),
+        ]);
+    }
+
+    println!("{table}");
+
+    if usage.len() > limit {
+        println!(
+            "{} Showing top {} of {} tools. Use {} to see more.",
+            ">".cyan(),
+            limit,
+            usage.len(),
+            "--limit".yellow()
+        );
+    }
+
+    println!("📈 Total: {} uses across {} tools", total, usage.len());
+
+    Ok(())
+}
+
 /// Show usage for a specific tool
-pub fn cmd_usage_tool(db: &Database, name: &str) -> Result<()> {
+pub fn cmd_usage_tool(db: &Database, name: &str, json_output: bool) -> Result<()> {
     let usage = db.get_usage(name)?;
 
+    if json_output {
+        let patterns: Vec<_> = db
+            .get_usage_patterns(name, 10)?
+            .into_iter()
+            .map(|(pattern, count)| serde_json::json!({ "pattern": pattern, "count": count }))
+            .collect();
+        let report = serde_json::json!({ "tool": name, "usage": usage, "patterns": patterns });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
     match usage {
         Some(stats) => {
             println!("{} {}", "Usage for".bold(), name.cyan());
@@ -235,6 +552,23 @@ pub fn cmd_usage_tool(db: &Database, name: &str) -> Result<()> {
                 println!("  Last used:  {}", last);
             }
             println!("  First seen: {}", stats.first_seen);
+
+            let patterns = db.get_usage_patterns(name, 10)?;
+            if !patterns.is_empty() {
+                let width = patterns.iter().map(|(p, _)| p.len()).max().unwrap_or(0);
+                println!();
+                println!("  {}", "Top subcommands and flags:".bold());
+                for (pattern, count) in &patterns {
+                    let share = *count as f64 / stats.use_count.max(1) as f64 * 100.0;
+                    println!(
+                        "    {:width$}  {:>6}  {}",
+                        pattern.cyan(),
+                        count,
+                        format!("{:.0}%", share).dimmed(),
+                        width = width
+                    );
+                }
+            }
         }
         None => {
             println!("{} No usage data for '{}'", "!".yellow(), name);
@@ -247,7 +581,7 @@ pub fn cmd_usage_tool(db: &Database, name: &str) -> Result<()> {
 
 /// Show unused tools
 pub fn cmd_unused(db: &Database) -> Result<()> {
-    use crate::icons::source_icon;
+    use crate::icons::{check_icon, source_icon};
     use comfy_table::{
         Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
     };
@@ -255,7 +589,10 @@ pub fn cmd_unused(db: &Database) -> Result<()> {
     let unused = db.get_unused_tools()?;
 
     if unused.is_empty() {
-        println!("{} All installed tools have been used!", "✓".green());
+        println!(
+            "{} All installed tools have been used!",
+            check_icon().green()
+        );
         println!(
             "  Run {} first if you haven't already",
             "hoards usage scan".cyan()
@@ -336,7 +673,7 @@ pub fn cmd_recommend(db: &Database, count: usize) -> Result<()> {
 
     // Sort categories by usage
     let mut cats: Vec<_> = category_scores.into_iter().collect();
-    cats.sort_by(|a, b| b.1.cmp(&a.1));
+    cats.sort_by_key(|c| std::cmp::Reverse(c.1));
 
     if cats.is_empty() {
         println!("{} Not enough data for recommendations", "!".yellow());
@@ -402,7 +739,7 @@ pub fn cmd_recommend(db: &Database, count: usize) -> Result<()> {
 /// Log a single command usage (for shell hooks)
 /// This is called by shell preexec hooks and must be fast and silent
 pub fn cmd_usage_log(db: &Database, command: &str) -> Result<()> {
-    use crate::history::extract_command;
+    use crate::history::{extract_command, extract_command_pattern};
 
     // Extract base command (handles sudo, env vars, etc.)
     let cmd = match extract_command(command) {
@@ -416,28 +753,155 @@ pub fn cmd_usage_log(db: &Database, command: &str) -> Result<()> {
 
     // Fast lookup: is this a tracked tool?
     if let Some(tool_name) = db.match_command_to_tool(cmd)? {
-        let now = chrono::Utc::now().to_rfc3339();
+        let now = chrono::Utc::now();
+        if let Some((weekday, hour)) = crate::history::time_bucket(now.timestamp()) {
+            db.record_usage_rhythm(&tool_name, weekday, hour, 1)?;
+        }
+        let now = now.to_rfc3339();
         db.record_usage(&tool_name, 1, Some(&now))?;
+        if let Some((_, pattern)) = extract_command_pattern(command) {
+            db.record_usage_pattern(&tool_name, pattern, 1)?;
+            db.prune_usage_patterns()?;
+        }
+
+        // The hook runs in the shell's working directory
+        if let Ok(cwd) = std::env::current_dir() {
+            let project = crate::project::project_root(&cwd);
+            db.record_project_usage(&tool_name, &project.to_string_lossy(), 1, Some(&now))?;
+        }
     }
 
     Ok(())
 }
 
+/// Log a command without opening the database (`usage log --fast`)
+///
+/// Only appends to the pending usage log, so shell prompts never wait on
+/// SQLite. [`flush_usage_log`] moves the events into the database later.
+pub fn cmd_usage_log_fast(command: &str) -> Result<()> {
+    use crate::history::{
+        append_usage_log, extract_command, extract_command_pattern, usage_log_path,
+    };
+
+    let Some(cmd) = extract_command(command) else {
+        return Ok(());
+    };
+    let pattern = extract_command_pattern(command).map(|(_, p)| p);
+    let cwd = std::env::current_dir().ok();
+    append_usage_log(
+        &usage_log_path()?,
+        cmd,
+        pattern,
+        cwd.as_deref(),
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+/// Move events logged by `usage log --fast` into the database
+///
+/// Returns how many events matched a tracked tool. The log is renamed before
+/// reading, so hooks that fire meanwhile start a new one. Everything is
+/// recorded in one transaction that commits before the renamed file is
+/// removed; if recording fails, nothing is written and the next flush picks
+/// the file up again.
+pub fn flush_usage_log(db: &Database) -> Result<usize> {
+    use crate::db::UsageBatch;
+    use crate::history::{parse_usage_log, usage_log_path};
+    use std::collections::HashMap;
+
+    let path = usage_log_path()?;
+    let taken = path.with_extension("log.flushing");
+    if !taken.exists() {
+        if !path.exists() {
+            return Ok(0);
+        }
+        std::fs::rename(&path, &taken)?;
+    }
+
+    // Batch per tool and per (tool, project): one count and the latest timestamp
+    let mut batch = UsageBatch::default();
+    let mut roots = HashMap::new();
+    for event in parse_usage_log(&taken)? {
+        let Some(tool) = db.match_command_to_tool(&event.command)? else {
+            continue;
+        };
+        if let Some(cwd) = event.cwd {
+            let project = roots
+                .entry(cwd)
+                .or_insert_with_key(|cwd| {
+                    crate::project::project_root(cwd)
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .clone();
+            let (count, last) = batch.projects.entry((tool.clone(), project)).or_default();
+            *count += 1;
+            *last = (*last).max(event.timestamp);
+        }
+        if let Some((weekday, hour)) = crate::history::time_bucket(event.timestamp) {
+            *batch
+                .rhythm
+                .entry((tool.clone(), weekday, hour))
+                .or_default() += 1;
+        }
+        if let Some(pattern) = event.pattern {
+            *batch.patterns.entry((tool.clone(), pattern)).or_default() += 1;
+        }
+        let (count, last) = batch.tools.entry(tool).or_default();
+        *count += 1;
+        *last = (*last).max(event.timestamp);
+    }
+
+    let recorded = db.record_usage_batch(&batch)?;
+    std::fs::remove_file(&taken)?;
+    Ok(recorded)
+}
+
+/// Flush the pending usage log, reporting what was recorded
+pub fn cmd_usage_flush(db: &Database) -> Result<()> {
+    let flushed = flush_usage_log(db)?;
+    if flushed > 0 {
+        println!(
+            "{} Recorded {} command(s) logged by the shell hook",
+            "+".green(),
+            flushed
+        );
+    }
+    Ok(())
+}
+
 /// Detect the current shell from environment
-fn detect_shell() -> String {
-    // Try SHELL env var first
-    if let Ok(shell) = std::env::var("SHELL") {
-        if shell.contains("fish") {
-            return "fish".to_string();
-        } else if shell.contains("zsh") {
-            return "zsh".to_string();
-        } else if shell.contains("bash") {
-            return "bash".to_string();
+pub(crate) fn detect_shell() -> String {
+    // The shell hoards was started from wins: fish users often keep bash as
+    // their login shell
+    #[cfg(target_os = "linux")]
+    {
+        let comm = format!("/proc/{}/comm", std::os::unix::process::parent_id());
+        if let Ok(parent) = std::fs::read_to_string(comm)
+            && let Some(shell) = shell_name(parent.trim())
+        {
+            return shell.to_string();
         }
     }
 
-    // Fallback: check parent process or default to bash
-    "bash".to_string()
+    // Then the SHELL env var, defaulting to bash
+    std::env::var("SHELL")
+        .ok()
+        .and_then(|shell| shell_name(&shell))
+        .unwrap_or("bash")
+        .to_string()
+}
+
+/// Supported shell named by a program name or path
+fn shell_name(program: &str) -> Option<&'static str> {
+    let program = program.rsplit('/').next().unwrap_or(program);
+    match program.trim_start_matches('-') {
+        "fish" => Some("fish"),
+        "zsh" => Some("zsh"),
+        "bash" => Some("bash"),
+        "nu" => Some("nushell"),
+        _ => None,
+    }
 }
 
 /// Offer to set up shell hook automatically, or print manual instructions
@@ -450,37 +914,55 @@ fn print_hook_instructions(shell: &str) {
     // For fish and zsh, offer automatic setup
     if let Err(e) = offer_shell_hook_setup(shell) {
         // If interactive setup fails (e.g., not a terminal), show manual instructions
-        eprintln!("{} Could not run interactive setup: {}", "!".yellow(), e);
+        tracing::warn!("Could not run interactive setup: {}", e);
         print_manual_hook_instructions(shell);
     }
 }
 
-/// Offer automatic shell hook setup for fish/zsh
-fn offer_shell_hook_setup(shell: &str) -> Result<()> {
-    use dialoguer::Confirm;
-
-    let home = dirs::home_dir().unwrap_or_default();
-
-    let (config_path, hook_code) = match shell {
-        "fish" => {
-            let path = home.join(".config/fish/config.fish");
-            let code = r#"
+/// Fish hook: `fish_preexec` passes the command line as `$argv[1]`
+const FISH_HOOK: &str = r#"
 # Hoards usage tracking (added by hoards)
 function __hoard_log --on-event fish_preexec
-    command hoards usage log "$argv[1]" &>/dev/null &
+    command hoards usage log --fast "$argv[1]" &>/dev/null &
     disown 2>/dev/null
 end
 "#;
-            (path, code)
-        }
-        "zsh" => {
-            let path = home.join(".zshrc");
-            let code = r#"
+
+/// Zsh hook
+const ZSH_HOOK: &str = r#"
 # Hoards usage tracking (added by hoards)
-preexec() { command hoards usage log "$1" &>/dev/null & }
+preexec() { command hoards usage log --fast "$1" &>/dev/null & }
 "#;
-            (path, code)
-        }
+
+/// Nushell hook: a `pre_execution` closure that logs the command line in a
+/// background job
+const NUSHELL_HOOK: &str = r#"
+# Hoards usage tracking (added by hoards)
+$env.config.hooks.pre_execution = (
+    $env.config.hooks.pre_execution | default [] | append {||
+        let cmd = (commandline)
+        job spawn { ^hoards usage log --fast $cmd o+e>| ignore } | ignore
+    }
+)
+"#;
+
+/// Nushell's config file
+fn nushell_config_path() -> std::path::PathBuf {
+    dirs::config_dir()
+        .unwrap_or_default()
+        .join("nushell/config.nu")
+}
+
+/// Offer automatic shell hook setup for fish/zsh/nushell
+fn offer_shell_hook_setup(shell: &str) -> Result<()> {
+    use dialoguer::Confirm;
+
+    let home = dirs::home_dir().unwrap_or_default();
+
+    let (config_path, hook_code) = match shell {
+        "fish" => (home.join(".config/fish/config.fish"), FISH_HOOK),
+        "zsh" => (home.join(".zshrc"), ZSH_HOOK),
+        "nushell" => (nushell_config_path(), NUSHELL_HOOK),
         _ => {
             println!("{} Unsupported shell: {}", "!".yellow(), shell);
             return Ok(());
@@ -516,7 +998,7 @@ preexec() { command hoards usage log "$1" &>/dev/null & }
         return Ok(());
     }
 
-    // Ensure parent directory exists (for fish)
+    // Ensure parent directory exists (for fish and nushell)
     if let Some(parent) = config_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -537,6 +1019,7 @@ preexec() { command hoards usage log "$1" &>/dev/null & }
 
     let source_cmd = match shell {
         "fish" => "source ~/.config/fish/config.fish".to_string(),
+        "nushell" => format!("source {}", config_path.display()),
         _ => format!("source ~/.{}rc", shell),
     };
 
@@ -558,16 +1041,18 @@ fn print_manual_hook_instructions(shell: &str) {
     match shell {
         "fish" => {
             println!("{}", "# Add to ~/.config/fish/config.fish".dimmed());
-            println!(r#"function __hoard_log --on-event fish_preexec"#);
-            println!(r#"    command hoards usage log "$argv[1]" &>/dev/null &"#);
-            println!(r#"    disown 2>/dev/null"#);
-            println!(r#"end"#);
+            println!("{}", FISH_HOOK.trim());
         }
         "zsh" => {
             println!("{}", "# Add to ~/.zshrc".dimmed());
-            println!(r#"preexec() {{"#);
-            println!(r#"    command hoards usage log "$1" &>/dev/null &"#);
-            println!(r#"}}"#);
+            println!("{}", ZSH_HOOK.trim());
+        }
+        "nushell" => {
+            println!(
+                "{}",
+                format!("# Add to {}", nushell_config_path().display()).dimmed()
+            );
+            println!("{}", NUSHELL_HOOK.trim());
         }
         "bash" => {
             println!(
@@ -576,7 +1061,7 @@ fn print_manual_hook_instructions(shell: &str) {
             );
             println!(r#"[[ -f ~/.bash-preexec.sh ]] && source ~/.bash-preexec.sh"#);
             println!(r#"preexec() {{"#);
-            println!(r#"    command hoards usage log "$1" &>/dev/null &"#);
+            println!(r#"    command hoards usage log --fast "$1" &>/dev/null &"#);
             println!(r#"}}"#);
         }
         _ => {
@@ -587,6 +1072,7 @@ fn print_manual_hook_instructions(shell: &str) {
     println!();
     let source_cmd = match shell {
         "fish" => "source ~/.config/fish/config.fish",
+        "nushell" => &format!("source {}", nushell_config_path().display()),
         _ => &format!("source ~/.{}rc", shell),
     };
     println!(
@@ -632,8 +1118,30 @@ pub fn cmd_usage_init(
 pub fn cmd_usage_config(
     config: &mut crate::config::HoardConfig,
     mode: Option<String>,
+    history: Option<String>,
 ) -> Result<()> {
-    use crate::config::UsageMode;
+    use crate::config::{HistorySource, UsageMode};
+
+    if let Some(history) = history {
+        config.usage.history = match history.as_str() {
+            "shell" => HistorySource::Shell,
+            "atuin" => HistorySource::Atuin,
+            "all" => HistorySource::All,
+            _ => anyhow::bail!(
+                "Invalid history '{}'. Use 'shell', 'atuin' or 'all'.",
+                history
+            ),
+        };
+        if mode.is_none() {
+            config.save()?;
+            println!(
+                "{} Scans now read {} history.",
+                "+".green(),
+                config.usage.history.to_string().cyan()
+            );
+            return Ok(());
+        }
+    }
 
     match mode {
         None => {
@@ -643,14 +1151,18 @@ pub fn cmd_usage_config(
 
             match config.usage.mode {
                 UsageMode::Scan => {
-                    println!("  Mode:  {} (manual)", "scan".cyan());
-                    println!("  Info:  Run 'hoards usage scan' periodically");
+                    println!("  Mode:    {} (manual)", "scan".cyan());
+                    println!("  History: {}", config.usage.history.to_string().cyan());
+                    if let Some(db) = &config.usage.atuin_db {
+                        println!("  Atuin:   {}", db.display());
+                    }
+                    println!("  Info:    Run 'hoards usage scan' periodically");
                 }
                 UsageMode::Hook => {
                     let shell = config.usage.shell.as_deref().unwrap_or("unknown");
-                    println!("  Mode:  {} (automatic)", "hook".cyan());
-                    println!("  Shell: {}", shell.cyan());
-                    println!("  Info:  Commands tracked in real-time via shell hook");
+                    println!("  Mode:    {} (automatic)", "hook".cyan());
+                    println!("  Shell:   {}", shell.cyan());
+                    println!("  Info:    Commands tracked in real-time via shell hook");
                 }
             }
         }
@@ -741,7 +1253,7 @@ fn offer_bash_preexec_install() -> Result<()> {
         println!();
         println!("2. Add to ~/.bashrc:");
         println!("   [[ -f ~/.bash-preexec.sh ]] && source ~/.bash-preexec.sh");
-        println!("   preexec() {{ command hoards usage log \"$1\" &>/dev/null & }}");
+        println!("   preexec() {{ command hoards usage log --fast \"$1\" &>/dev/null & }}");
         println!();
         return Ok(());
     }
@@ -768,7 +1280,7 @@ fn offer_bash_preexec_install() -> Result<()> {
 
 # Hoards usage tracking (added by hoards)
 [[ -f ~/.bash-preexec.sh ]] && source ~/.bash-preexec.sh
-preexec() { command hoards usage log "$1" &>/dev/null & }
+preexec() { command hoards usage log --fast "$1" &>/dev/null & }
 "#;
 
         let mut file = std::fs::OpenOptions::new()
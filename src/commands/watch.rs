@@ -0,0 +1,193 @@
+//! Release watching commands
+//!
+//! Watched tools have their upstream releases checked on demand and during
+//! `hoards maintain`, regardless of how they were installed.
+
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::Database;
+use crate::forge::{Release, parse_repo_url};
+
+/// Start watching a tool's releases
+///
+/// With `repo`, the tool is first linked to that repository URL.
+pub fn cmd_watch_add(db: &Database, name: &str, repo: Option<&str>) -> Result<()> {
+    if db.get_tool_by_name(name)?.is_none() {
+        crate::fail!(NotFound, "Tool '{}' not found in database", name);
+    }
+
+    if let Some(url) = repo {
+        let repo = parse_repo_url(url)?;
+        let info = repo.forge().repo_info(&repo.owner, &repo.repo)?;
+        db.set_github_info(
+            name,
+            crate::db::GitHubInfoInput::from_repo(repo.provider, &repo.host, &info),
+        )?;
+        if let Some(license) = info.license_id() {
+            db.set_tool_license(name, Some(license))?;
+        }
+    }
+
+    let Some(info) = db.get_github_info(name)? else {
+        println!("{} No repository known for '{}'", "!".yellow(), name);
+        println!(
+            "  Link one with: {}",
+            format!("hoards watch {} --repo <url>", name).cyan()
+        );
+        return Ok(());
+    };
+
+    if !db.add_watch(name)? {
+        println!("{} Already watching '{}'", "!".yellow(), name);
+        return Ok(());
+    }
+
+    // Existing releases become the baseline; only later ones are reported
+    let repo = info.repo_ref();
+    let releases = repo.forge().releases(&repo.owner, &repo.repo)?;
+    let releases = stable(releases);
+    db.record_releases(name, &releases, true)?;
+
+    println!(
+        "{} Watching {} ({})",
+        "+".green(),
+        name.bold(),
+        repo.url().dimmed()
+    );
+    if let Some(latest) = releases.first() {
+        println!("  Latest release: {}", latest.tag_name.cyan());
+    }
+
+    Ok(())
+}
+
+/// Stop watching a tool
+pub fn cmd_watch_remove(db: &Database, name: &str) -> Result<()> {
+    if db.remove_watch(name)? {
+        println!("{} Stopped watching '{}'", "-".red(), name);
+    } else {
+        println!("{} '{}' is not watched", "!".yellow(), name);
+    }
+    Ok(())
+}
+
+/// List watched tools and their unread releases
+pub fn cmd_watch_list(db: &Database) -> Result<()> {
+    let watches = db.list_watches()?;
+    if watches.is_empty() {
+        println!("{} No watched tools", "!".yellow());
+        println!("  Watch one with: {}", "hoards watch <tool>".cyan());
+        return Ok(());
+    }
+
+    println!("{}", "Watched tools:".bold());
+    for watch in &watches {
+        let unread = if watch.unread > 0 {
+            format!("{} unread", watch.unread).yellow().to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<20} {:<14} {}",
+            watch.tool,
+            watch.latest.as_deref().unwrap_or("-").dimmed(),
+            unread
+        );
+    }
+
+    print_unread(db)
+}
+
+/// Fetch new releases for watched tools and show everything unread
+pub fn cmd_watch_check(db: &Database) -> Result<()> {
+    if db.list_watches()?.is_empty() {
+        println!("{} No watched tools", "!".yellow());
+        return Ok(());
+    }
+
+    let found = check_watched_releases(db)?;
+    let count: usize = found.iter().map(|(_, tags)| tags.len()).sum();
+    println!(
+        "{} {} new release{}",
+        ">".cyan(),
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+
+    print_unread(db)
+}
+
+/// Mark releases as read, for one tool or all
+pub fn cmd_watch_read(db: &Database, name: Option<&str>) -> Result<()> {
+    let marked = db.mark_releases_read(name)?;
+    println!(
+        "{} Marked {} release{} as read",
+        "+".green(),
+        marked,
+        if marked == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// Fetch releases for every watched tool, returning newly seen tags per tool
+///
+/// Failures for single tools are reported and skipped.
+pub fn check_watched_releases(db: &Database) -> Result<Vec<(String, Vec<String>)>> {
+    let mut found = Vec::new();
+    for watch in db.list_watches()? {
+        let Some(info) = db.get_github_info(&watch.tool)? else {
+            continue;
+        };
+        let repo = info.repo_ref();
+        match repo.forge().releases(&repo.owner, &repo.repo) {
+            Ok(releases) => {
+                let new = db.record_releases(&watch.tool, &stable(releases), false)?;
+                if !new.is_empty() {
+                    found.push((watch.tool, new));
+                }
+            }
+            Err(e) => println!("  {} {}: {}", "!".yellow(), watch.tool, e),
+        }
+    }
+    Ok(found)
+}
+
+/// Drop pre-releases
+fn stable(releases: Vec<Release>) -> Vec<Release> {
+    releases.into_iter().filter(|r| !r.prerelease).collect()
+}
+
+fn print_unread(db: &Database) -> Result<()> {
+    let unread = db.unread_releases(None)?;
+    if unread.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", "Unread releases:".bold());
+    for release in &unread {
+        let date = release
+            .published_at
+            .as_deref()
+            .map(|d| d.get(..10).unwrap_or(d))
+            .unwrap_or("");
+        println!(
+            "  {} {} {} {}",
+            "*".cyan(),
+            release.tool.bold(),
+            release.tag.green(),
+            date.dimmed()
+        );
+        if let Some(url) = &release.url {
+            println!("    {}", url.dimmed());
+        }
+    }
+    println!();
+    println!(
+        "{} Mark as read with {}",
+        ">".cyan(),
+        "hoards watch read [tool]".cyan()
+    );
+    Ok(())
+}
@@ -3,6 +3,7 @@
 use anyhow::Result;
 use colored::Colorize;
 
+use crate::config::HoardConfig;
 use crate::db::Database;
 
 use super::completions::cmd_completions_install;
@@ -12,11 +13,12 @@ use super::misc::cmd_doctor;
 use super::sync::{cmd_fetch_descriptions, cmd_scan, cmd_sync_status};
 use super::updates_cmd::cmd_updates;
 use super::usage::cmd_usage_scan;
+use super::watch::check_watched_releases;
 
 /// Run AI categorization if available
 fn try_ai_categorize() {
     // Import dynamically to avoid circular dependency
-    if let Err(e) = super::ai::cmd_ai_categorize(false) {
+    if let Err(e) = super::ai::cmd_ai_categorize(false, false) {
         println!("  {} AI categorization failed: {}", "!".yellow(), e);
     }
 }
@@ -30,7 +32,7 @@ pub fn cmd_init(db: &Database, auto: bool) -> Result<()> {
 
     // Step 1: Scan for tools
     println!("{} Scanning system for installed tools...", "1.".bold());
-    cmd_scan(db, false)?;
+    cmd_scan(db, false, true)?;
 
     // Step 2: Sync status
     println!("\n{} Syncing installation status...", "2.".bold());
@@ -42,7 +44,7 @@ pub fn cmd_init(db: &Database, auto: bool) -> Result<()> {
 
     // Step 4: Install shell completions
     println!("\n{} Installing shell completions...", "4.".bold());
-    if let Err(e) = cmd_completions_install(None, false) {
+    if let Err(e) = cmd_completions_install(None, false, false) {
         println!("  {} Failed to install completions: {}", "!".yellow(), e);
     }
 
@@ -101,15 +103,27 @@ pub fn cmd_maintain(db: &Database, auto: bool, dry_run: bool) -> Result<()> {
 
     // Step 2: Check for updates
     println!("\n{} Checking for updates...", "2.".bold());
-    cmd_updates(db, None, false, true, false)?;
+    cmd_updates(db, None, false, true, false, false)?;
 
-    // Step 3: Scan usage
-    println!("\n{} Scanning shell history for usage...", "3.".bold());
+    // Step 3: Watched releases
+    println!("\n{} Checking watched releases...", "3.".bold());
+    report_watched_releases(db, dry_run)?;
+
+    // Step 4: Scan usage
+    println!("\n{} Scanning shell history for usage...", "4.".bold());
     cmd_usage_scan(db, dry_run, false)?;
 
-    // Step 4: Health check
-    println!("\n{} Running health check...", "4.".bold());
-    cmd_doctor(db, false)?;
+    // Step 5: Usage retention
+    println!("\n{} Applying usage retention...", "5.".bold());
+    apply_usage_retention(db, dry_run)?;
+
+    // Step 6: Adoption of newly installed tools
+    println!("\n{} Checking new tools got used...", "6.".bold());
+    report_forgotten_tools(db)?;
+
+    // Step 7: Health check
+    println!("\n{} Running health check...", "7.".bold());
+    cmd_doctor(db, false, false)?;
 
     if !auto && !dry_run {
         println!();
@@ -122,6 +136,105 @@ pub fn cmd_maintain(db: &Database, auto: bool, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
+/// Roll daily usage rows past `usage.retention.daily_days` into monthly totals
+fn apply_usage_retention(db: &Database, dry_run: bool) -> Result<()> {
+    let days = HoardConfig::load()
+        .unwrap_or_default()
+        .usage
+        .retention
+        .daily_days;
+    if days == 0 {
+        println!("   {} Daily usage kept forever", "-".dimmed());
+        return Ok(());
+    }
+
+    let cutoff = (chrono::Local::now().date_naive() - chrono::Duration::days(days as i64))
+        .format("%Y-%m-%d")
+        .to_string();
+    let count = if dry_run {
+        db.count_daily_usage_before(&cutoff)?
+    } else {
+        db.rollup_daily_usage(&cutoff)?
+    };
+
+    if count == 0 {
+        println!("   {} No daily usage older than {} days", "+".green(), days);
+    } else {
+        println!(
+            "   {} {} {} daily row(s) older than {} days into monthly totals",
+            "+".green(),
+            if dry_run {
+                "Would roll up"
+            } else {
+                "Rolled up"
+            },
+            count,
+            days
+        );
+    }
+    Ok(())
+}
+
+/// Nudge about tools installed more than the adoption window ago and never used
+fn report_forgotten_tools(db: &Database) -> Result<()> {
+    use crate::db::{ADOPTION_WINDOW_DAYS, AdoptionStatus};
+
+    let now = chrono::Utc::now();
+    let forgotten: Vec<_> = db
+        .list_adoptions(ADOPTION_WINDOW_DAYS * 3)?
+        .into_iter()
+        .filter(|a| a.status(now) == AdoptionStatus::Forgotten)
+        .collect();
+    if forgotten.is_empty() {
+        println!("   {} No forgotten installs", "+".green());
+        return Ok(());
+    }
+
+    for adoption in &forgotten {
+        println!(
+            "   {} {} installed {} days ago, never used",
+            "!".yellow(),
+            adoption.tool.bold(),
+            (now - adoption.installed_at).num_days()
+        );
+    }
+    println!(
+        "   Uninstall with {} or keep with {}",
+        "hoards uninstall <tool>".cyan(),
+        "hoards insights adoption --keep <tool>".cyan()
+    );
+    Ok(())
+}
+
+/// Report new and unread releases of watched tools
+///
+/// A dry run only shows releases already recorded as unread.
+fn report_watched_releases(db: &Database, dry_run: bool) -> Result<()> {
+    if db.list_watches()?.is_empty() {
+        println!("   {} No watched tools", "-".dimmed());
+        return Ok(());
+    }
+
+    if !dry_run {
+        check_watched_releases(db)?;
+    }
+    let unread = db.unread_releases(None)?;
+    if unread.is_empty() {
+        println!("   {} No new releases", "+".green());
+    } else {
+        for release in &unread {
+            println!(
+                "   {} {} {}",
+                "*".cyan(),
+                release.tool.bold(),
+                release.tag.green()
+            );
+        }
+        println!("   Mark as read with: {}", "hoards watch read".cyan());
+    }
+    Ok(())
+}
+
 /// Cleanup wizard for removing unused tools
 pub fn cmd_cleanup(db: &Database, force: bool, dry_run: bool) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".cyan());
@@ -185,7 +298,7 @@ pub fn cmd_cleanup(db: &Database, force: bool, dry_run: bool) -> Result<()> {
 
     // Step 3: Run health fix
     println!("\n{} Running health checks...", "3.".bold());
-    cmd_doctor(db, !dry_run && force)?;
+    cmd_doctor(db, !dry_run && force, false)?;
 
     println!();
     if dry_run {
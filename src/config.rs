@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// AI provider options
@@ -12,6 +13,12 @@ pub enum AiProvider {
     Gemini,
     Codex,
     Opencode,
+    /// Native HTTP client for OpenAI-compatible endpoints
+    OpenAi,
+    /// Local models served by Ollama
+    Ollama,
+    /// Native Anthropic Messages API client (no claude CLI needed)
+    Anthropic,
 }
 
 impl std::fmt::Display for AiProvider {
@@ -21,6 +28,9 @@ impl std::fmt::Display for AiProvider {
             Self::Gemini => write!(f, "gemini"),
             Self::Codex => write!(f, "codex"),
             Self::Opencode => write!(f, "opencode"),
+            Self::OpenAi => write!(f, "openai"),
+            Self::Ollama => write!(f, "ollama"),
+            Self::Anthropic => write!(f, "anthropic"),
             Self::None => write!(f, "none"),
         }
     }
@@ -33,6 +43,9 @@ impl From<&str> for AiProvider {
             "gemini" => Self::Gemini,
             "codex" => Self::Codex,
             "opencode" | "open-code" => Self::Opencode,
+            "openai" | "openai-compatible" => Self::OpenAi,
+            "ollama" => Self::Ollama,
+            "anthropic" | "anthropic-api" => Self::Anthropic,
             _ => Self::None,
         }
     }
@@ -46,13 +59,20 @@ impl AiProvider {
             Self::Gemini => Some("gemini"),
             Self::Codex => Some("codex"),
             Self::Opencode => Some("opencode"),
-            Self::None => None,
+            Self::OpenAi | Self::Ollama | Self::Anthropic | Self::None => None,
         }
     }
 
-    /// Check if the CLI tool is installed
+    /// Check if this provider talks to an HTTP API directly (no CLI needed)
+    pub fn is_native(&self) -> bool {
+        matches!(self, Self::OpenAi | Self::Ollama | Self::Anthropic)
+    }
+
+    /// Check if the provider is usable: CLI found in PATH, or a native API provider
     pub fn is_installed(&self) -> bool {
-        if let Some(cmd) = self.command() {
+        if self.is_native() {
+            true
+        } else if let Some(cmd) = self.command() {
             which::which(cmd).is_ok()
         } else {
             false
@@ -67,6 +87,9 @@ impl AiProvider {
             AiProvider::Gemini,
             AiProvider::Codex,
             AiProvider::Opencode,
+            AiProvider::OpenAi,
+            AiProvider::Ollama,
+            AiProvider::Anthropic,
         ]
     }
 }
@@ -91,14 +114,67 @@ impl std::fmt::Display for UsageMode {
     }
 }
 
+/// History read by `usage scan`
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HistorySource {
+    /// Shell history files (fish, bash, zsh, nushell)
+    #[default]
+    Shell,
+    /// Atuin's history database
+    Atuin,
+    /// Both shell history files and atuin
+    All,
+}
+
+impl std::fmt::Display for HistorySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shell => write!(f, "shell"),
+            Self::Atuin => write!(f, "atuin"),
+            Self::All => write!(f, "all"),
+        }
+    }
+}
+
 /// Usage tracking configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UsageConfig {
     /// Tracking mode: scan (manual) or hook (automatic)
     #[serde(default)]
     pub mode: UsageMode,
-    /// Shell for hook mode (fish, bash, zsh)
+    /// Shell for hook mode (fish, bash, zsh, nushell)
     pub shell: Option<String>,
+    /// History scanned in scan mode: shell files, atuin, or all
+    #[serde(default)]
+    pub history: HistorySource,
+    /// Atuin database, if not at the default location
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub atuin_db: Option<PathBuf>,
+    /// How long raw usage rows are kept, applied by `maintain`
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+/// Usage data retention
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionConfig {
+    /// Days of daily counts kept before they're rolled up into monthly
+    /// totals (0 keeps them forever)
+    #[serde(default = "default_daily_retention_days")]
+    pub daily_days: u32,
+}
+
+fn default_daily_retention_days() -> u32 {
+    180
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            daily_days: default_daily_retention_days(),
+        }
+    }
 }
 
 /// AI-specific configuration
@@ -106,6 +182,305 @@ pub struct UsageConfig {
 pub struct AiConfig {
     #[serde(default)]
     pub provider: AiProvider,
+    /// Settings for the OpenAI-compatible HTTP provider
+    #[serde(default)]
+    pub openai: OpenAiConfig,
+    /// Settings for the local Ollama provider
+    #[serde(default)]
+    pub ollama: OllamaConfig,
+    /// Claude model used by the claude CLI and the Anthropic API
+    /// (unset: CLI default / Sonnet)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_model: Option<ClaudeModel>,
+    /// Settings for the native Anthropic API provider
+    #[serde(default)]
+    pub anthropic: AnthropicConfig,
+    /// Embedding backend for similar-tool search
+    #[serde(default)]
+    pub embeddings: EmbeddingsConfig,
+    /// Directory kept in sync with cached cheatsheets as markdown files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cheatsheet_export_dir: Option<PathBuf>,
+    /// Days AI responses stay cached, per feature (0 disables caching)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cache_ttl_days: BTreeMap<String, u64>,
+    /// Batching and retry settings for `ai enrich`
+    #[serde(default)]
+    pub enrich: EnrichConfig,
+}
+
+/// How `ai enrich` splits work and handles provider errors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnrichConfig {
+    /// Tools sent per AI request
+    #[serde(default = "default_enrich_batch_size")]
+    pub batch_size: usize,
+    /// Requests run in parallel
+    #[serde(default = "default_enrich_concurrency")]
+    pub concurrency: usize,
+    /// Retries of a failed request before the run stops
+    #[serde(default = "default_enrich_max_retries")]
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on each further retry
+    #[serde(default = "default_enrich_backoff_secs")]
+    pub backoff_secs: u64,
+}
+
+fn default_enrich_batch_size() -> usize {
+    25
+}
+
+fn default_enrich_concurrency() -> usize {
+    1
+}
+
+fn default_enrich_max_retries() -> u32 {
+    3
+}
+
+fn default_enrich_backoff_secs() -> u64 {
+    5
+}
+
+impl Default for EnrichConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: default_enrich_batch_size(),
+            concurrency: default_enrich_concurrency(),
+            max_retries: default_enrich_max_retries(),
+            backoff_secs: default_enrich_backoff_secs(),
+        }
+    }
+}
+
+impl AiConfig {
+    /// Model used by the current provider, if hoards knows it
+    ///
+    /// CLI providers other than claude pick their own model.
+    pub fn model_name(&self) -> Option<String> {
+        match self.provider {
+            AiProvider::OpenAi => Some(self.openai.model.clone()),
+            AiProvider::Ollama => Some(self.ollama.model.clone()),
+            AiProvider::Anthropic => {
+                Some(self.claude_model.unwrap_or_default().api_id().to_string())
+            }
+            AiProvider::Claude => self.claude_model.map(|m| m.cli_alias().to_string()),
+            AiProvider::Gemini | AiProvider::Codex | AiProvider::Opencode | AiProvider::None => {
+                None
+            }
+        }
+    }
+}
+
+/// Backend used to embed tool text for similarity search
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingProvider {
+    /// Offline hashed bag-of-words (no model needed)
+    #[default]
+    Local,
+    /// `/embeddings` on the OpenAI-compatible endpoint (`ai.openai`)
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// `/api/embed` on the Ollama server (`ai.ollama`)
+    Ollama,
+}
+
+/// Embedding settings for similar-tool search
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EmbeddingsConfig {
+    #[serde(default)]
+    pub provider: EmbeddingProvider,
+    /// Embedding model (default: text-embedding-3-small / nomic-embed-text)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl EmbeddingsConfig {
+    /// Model name sent to the provider
+    pub fn model_name(&self) -> &str {
+        match (self.provider, self.model.as_deref()) {
+            (EmbeddingProvider::Local, _) => "hash-v1",
+            (_, Some(model)) => model,
+            (EmbeddingProvider::OpenAi, None) => "text-embedding-3-small",
+            (EmbeddingProvider::Ollama, None) => "nomic-embed-text",
+        }
+    }
+
+    /// Identifier stored with each vector; vectors from different models
+    /// are never compared
+    pub fn model_id(&self) -> String {
+        let provider = match self.provider {
+            EmbeddingProvider::Local => "local",
+            EmbeddingProvider::OpenAi => "openai",
+            EmbeddingProvider::Ollama => "ollama",
+        };
+        format!("{}:{}", provider, self.model_name())
+    }
+}
+
+/// Claude model family
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaudeModel {
+    Haiku,
+    #[default]
+    Sonnet,
+    Opus,
+}
+
+impl std::fmt::Display for ClaudeModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.cli_alias())
+    }
+}
+
+impl ClaudeModel {
+    /// Parse a model name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "haiku" => Some(Self::Haiku),
+            "sonnet" => Some(Self::Sonnet),
+            "opus" => Some(Self::Opus),
+            _ => None,
+        }
+    }
+
+    /// Alias accepted by `claude --model`
+    pub fn cli_alias(&self) -> &'static str {
+        match self {
+            Self::Haiku => "haiku",
+            Self::Sonnet => "sonnet",
+            Self::Opus => "opus",
+        }
+    }
+
+    /// Model id for the Anthropic Messages API
+    pub fn api_id(&self) -> &'static str {
+        match self {
+            Self::Haiku => "claude-haiku-4-5",
+            Self::Sonnet => "claude-sonnet-4-5",
+            Self::Opus => "claude-opus-4-1",
+        }
+    }
+}
+
+/// Anthropic API settings
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AnthropicConfig {
+    /// API key; prefer `ANTHROPIC_API_KEY` or the OS keyring
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+impl AnthropicConfig {
+    /// Resolve the API key: `ANTHROPIC_API_KEY`, then config, then OS keyring
+    pub fn resolve_api_key(&self) -> Option<String> {
+        std::env::var("ANTHROPIC_API_KEY")
+            .ok()
+            .filter(|key| !key.is_empty())
+            .or_else(|| self.api_key.clone().filter(|key| !key.is_empty()))
+            .or_else(|| keyring_secret("anthropic"))
+    }
+}
+
+/// Look up a secret in the OS keyring
+///
+/// Uses `secret-tool` (libsecret) on Linux and `security` on macOS, with
+/// service `hoards` and account/key `key` (e.g. `anthropic`, `github`).
+pub(crate) fn keyring_secret(key: &str) -> Option<String> {
+    use std::process::Command;
+
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", "hoards", "-a", key, "-w"])
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", "hoards", "key", key])
+            .output()
+    }
+    .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let key = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!key.is_empty()).then_some(key)
+}
+
+/// OpenAI-compatible API settings
+///
+/// Works with OpenAI itself and any server exposing `/chat/completions`
+/// (OpenRouter, Groq, LM Studio, llama.cpp, vLLM, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAiConfig {
+    /// API base URL (without the `/chat/completions` suffix)
+    #[serde(default = "default_openai_base_url")]
+    pub base_url: String,
+    /// Model name sent with each request
+    #[serde(default = "default_openai_model")]
+    pub model: String,
+    /// API key; falls back to the `OPENAI_API_KEY` environment variable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+impl Default for OpenAiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_openai_base_url(),
+            model: default_openai_model(),
+            api_key: None,
+        }
+    }
+}
+
+impl OpenAiConfig {
+    /// Resolve the API key from config or environment
+    pub fn resolve_api_key(&self) -> Option<String> {
+        self.api_key
+            .clone()
+            .filter(|key| !key.is_empty())
+            .or_else(|| std::env::var("OPENAI_API_KEY").ok())
+            .filter(|key| !key.is_empty())
+    }
+}
+
+/// Ollama server settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaConfig {
+    /// Server address
+    #[serde(default = "default_ollama_host")]
+    pub host: String,
+    /// Model to use (must be pulled with `ollama pull`)
+    #[serde(default = "default_ollama_model")]
+    pub model: String,
+}
+
+impl Default for OllamaConfig {
+    fn default() -> Self {
+        Self {
+            host: default_ollama_host(),
+            model: default_ollama_model(),
+        }
+    }
+}
+
+fn default_ollama_host() -> String {
+    "http://localhost:11434".to_string()
+}
+
+fn default_ollama_model() -> String {
+    "llama3.2".to_string()
+}
+
+fn default_openai_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+fn default_openai_model() -> String {
+    "gpt-4o-mini".to_string()
 }
 
 /// TUI theme options
@@ -183,10 +558,75 @@ impl TuiTheme {
 }
 
 /// TUI configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TuiConfig {
     #[serde(default)]
     pub theme: TuiTheme,
+    /// Accessibility mode: ASCII icons and high-contrast colors
+    #[serde(default)]
+    pub accessible: bool,
+    /// External "open with" actions for the selected tool
+    #[serde(default = "default_open_actions")]
+    pub open_with: Vec<OpenAction>,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            theme: TuiTheme::default(),
+            accessible: false,
+            open_with: default_open_actions(),
+        }
+    }
+}
+
+/// External command launched from the TUI for the selected tool
+///
+/// The command is split on whitespace (no shell is involved) and each
+/// argument may contain the placeholders `{name}`, `{binary}`, `{repo}`
+/// and `{source}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OpenAction {
+    /// Action name (used by the `:open <name>` command)
+    pub name: String,
+    /// Normal-mode key that triggers the action (must not shadow a built-in key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<char>,
+    /// Command line with placeholders
+    pub command: String,
+    /// Suspend the TUI and hand over the terminal (pagers, editors, man)
+    #[serde(default)]
+    pub interactive: bool,
+}
+
+impl OpenAction {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            key: None,
+            command: command.into(),
+            interactive: false,
+        }
+    }
+
+    pub fn with_key(mut self, key: char) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    pub fn interactive(mut self) -> Self {
+        self.interactive = true;
+        self
+    }
+}
+
+fn default_open_actions() -> Vec<OpenAction> {
+    vec![
+        OpenAction::new("github", "gh repo view --web {repo}").with_key('o'),
+        OpenAction::new("man", "man {binary}")
+            .with_key('M')
+            .interactive(),
+    ]
 }
 
 /// Package source configuration
@@ -288,6 +728,46 @@ impl SourcesConfig {
     }
 }
 
+/// Installation settings for binaries hoards manages itself
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstallConfig {
+    /// Directory for binaries installed from release assets (default: ~/.local/bin)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bin_dir: Option<PathBuf>,
+}
+
+impl InstallConfig {
+    /// Directory release binaries are installed into
+    pub fn bin_dir(&self) -> Result<PathBuf> {
+        match &self.bin_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => dirs::home_dir()
+                .context("Could not determine home directory")
+                .map(|home| home.join(".local").join("bin")),
+        }
+    }
+}
+
+/// Settings for managed config files (dotfiles)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DotfilesConfig {
+    /// Git repository holding config sources (set by `config repo init/clone`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<PathBuf>,
+
+    /// Values for `{{ name }}` in `.tmpl` sources, overriding the built-ins
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
+
+    /// Profile picking config variants (default: match variants to the hostname)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
+    /// Files not checked for secrets (`*` wildcards, `~` for home)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets_ignore: Vec<String>,
+}
+
 /// Hoard configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HoardConfig {
@@ -306,6 +786,17 @@ pub struct HoardConfig {
 
     #[serde(default)]
     pub sources: SourcesConfig,
+
+    #[serde(default)]
+    pub install: InstallConfig,
+
+    #[serde(default)]
+    pub dotfiles: DotfilesConfig,
+
+    /// Command aliases: name -> hoards command line with default flags
+    /// (e.g. "up" -> "updates --tracked")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
 }
 
 impl HoardConfig {
@@ -363,9 +854,13 @@ impl HoardConfig {
                 usage: UsageConfig {
                     mode: legacy.usage.mode.unwrap_or_default(),
                     shell: legacy.usage.shell,
+                    ..Default::default()
                 },
                 tui: TuiConfig::default(),
                 sources: SourcesConfig::default(),
+                install: InstallConfig::default(),
+                dotfiles: DotfilesConfig::default(),
+                aliases: BTreeMap::new(),
             };
 
             // Save as JSON
@@ -454,6 +949,9 @@ mod tests {
         assert_eq!(AiProvider::from("claude"), AiProvider::Claude);
         assert_eq!(AiProvider::from("GEMINI"), AiProvider::Gemini);
         assert_eq!(AiProvider::from("unknown"), AiProvider::None);
+        assert_eq!(AiProvider::from("openai"), AiProvider::OpenAi);
+        assert!(AiProvider::OpenAi.is_native());
+        assert!(AiProvider::OpenAi.command().is_none());
     }
 
     #[test]
@@ -514,4 +1012,20 @@ mod tests {
         assert!(config.sources.cargo);
         assert!(config.sources.pip);
     }
+
+    #[test]
+    fn test_open_actions_default_and_custom() {
+        let config: HoardConfig = serde_json::from_str(r#"{ "tui": {} }"#).unwrap();
+        assert!(config.tui.open_with.iter().any(|a| a.name == "github"));
+
+        let json = r#"{
+            "tui": { "open_with": [
+                { "name": "docs", "key": "w", "command": "xdg-open https://docs.rs/{name}" }
+            ] }
+        }"#;
+        let config: HoardConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.tui.open_with.len(), 1);
+        assert_eq!(config.tui.open_with[0].key, Some('w'));
+        assert!(!config.tui.open_with[0].interactive);
+    }
 }
@@ -0,0 +1,122 @@
+//! Adoption windows: whether newly installed tools actually get used
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+use super::Database;
+
+/// Days a newly installed tool has to get used before it counts as forgotten
+pub const ADOPTION_WINDOW_DAYS: i64 = 30;
+
+/// Where a newly installed tool stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AdoptionStatus {
+    /// Used since it was installed
+    Adopted,
+    /// Not used yet, window still open
+    Pending,
+    /// Not used and the window has passed
+    Forgotten,
+    /// Kept on purpose, used or not
+    Kept,
+}
+
+/// Adoption state of one installed tool
+#[derive(Debug, Clone)]
+pub struct Adoption {
+    pub tool: String,
+    pub installed_at: DateTime<Utc>,
+    /// Uses recorded since the install
+    pub uses: i64,
+    pub kept: bool,
+}
+
+impl Adoption {
+    /// Status as of `now`
+    pub fn status(&self, now: DateTime<Utc>) -> AdoptionStatus {
+        if self.kept {
+            AdoptionStatus::Kept
+        } else if self.uses > 0 {
+            AdoptionStatus::Adopted
+        } else if now - self.installed_at < chrono::Duration::days(ADOPTION_WINDOW_DAYS) {
+            AdoptionStatus::Pending
+        } else {
+            AdoptionStatus::Forgotten
+        }
+    }
+}
+
+impl Database {
+    // ==================== Adoption Operations ====================
+
+    /// Open an adoption window for a tool that was just installed
+    ///
+    /// Uses recorded so far are the baseline, so only later ones count.
+    pub(super) fn start_adoption(&self, tool_name: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "INSERT OR REPLACE INTO tool_adoption (tool_id, installed_at, baseline_uses, kept_at)
+             SELECT t.id, ?2, COALESCE(tu.use_count, 0), NULL FROM tools t
+             LEFT JOIN tool_usage tu ON tu.tool_id = t.id
+             WHERE t.name = ?1",
+            params![tool_name, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Close a tool's adoption window (it was uninstalled)
+    pub(super) fn end_adoption(&self, tool_name: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM tool_adoption WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
+            [tool_name],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Mark a newly installed tool as kept (returns false if it has no window)
+    pub fn keep_adoption(&self, tool_name: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE tool_adoption SET kept_at = ?2
+             WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
+            params![tool_name, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Adoption state of tools installed in the last `days` days, newest first
+    pub fn list_adoptions(&self, days: i64) -> Result<Vec<Adoption>> {
+        let since = (Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, a.installed_at,
+                    MAX(COALESCE(tu.use_count, 0) - a.baseline_uses, 0), a.kept_at IS NOT NULL
+             FROM tool_adoption a
+             JOIN tools t ON a.tool_id = t.id
+             LEFT JOIN tool_usage tu ON tu.tool_id = t.id
+             WHERE a.installed_at >= ?1
+             ORDER BY a.installed_at DESC, t.name",
+        )?;
+        let rows = stmt
+            .query_map([since], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, bool>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(tool, installed_at, uses, kept)| {
+                let installed_at = DateTime::parse_from_rfc3339(&installed_at).ok()?;
+                Some(Adoption {
+                    tool,
+                    installed_at: installed_at.with_timezone(&Utc),
+                    uses,
+                    kept,
+                })
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,99 @@
+//! AI invocation accounting
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::Serialize;
+
+use super::Database;
+
+/// One recorded AI invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AiCall {
+    /// Calling feature (prompt name, e.g. "cheatsheet")
+    pub feature: String,
+    pub provider: String,
+    pub model: Option<String>,
+    /// Estimated tokens sent
+    pub prompt_tokens: i64,
+    /// Estimated tokens received
+    pub response_tokens: i64,
+    pub duration_ms: i64,
+    pub success: bool,
+}
+
+/// Aggregated AI usage for one (feature, provider, model)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AiUsageSummary {
+    pub feature: String,
+    pub provider: String,
+    pub model: Option<String>,
+    pub calls: i64,
+    pub failures: i64,
+    pub prompt_tokens: i64,
+    pub response_tokens: i64,
+    pub duration_ms: i64,
+}
+
+impl AiUsageSummary {
+    /// Estimated tokens in both directions
+    pub fn total_tokens(&self) -> i64 {
+        self.prompt_tokens + self.response_tokens
+    }
+}
+
+impl Database {
+    // ==================== AI Usage Operations ====================
+
+    /// Record an AI invocation
+    pub fn record_ai_call(&self, call: &AiCall) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO ai_calls (feature, provider, model, prompt_tokens, response_tokens, duration_ms, success, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                call.feature,
+                call.provider,
+                call.model,
+                call.prompt_tokens,
+                call.response_tokens,
+                call.duration_ms,
+                call.success,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// AI usage grouped by feature, provider and model, most tokens first
+    ///
+    /// With `since`, only calls made after that time are counted.
+    pub fn ai_usage_summary(&self, since: Option<DateTime<Utc>>) -> Result<Vec<AiUsageSummary>> {
+        let since = since.map(|t| t.to_rfc3339()).unwrap_or_default();
+        let mut stmt = self.conn.prepare(
+            "SELECT feature, provider, model, COUNT(*), SUM(NOT success),
+                    SUM(prompt_tokens), SUM(response_tokens), SUM(duration_ms)
+             FROM ai_calls
+             WHERE created_at >= ?1
+             GROUP BY feature, provider, model
+             ORDER BY SUM(prompt_tokens) + SUM(response_tokens) DESC, feature",
+        )?;
+        let rows = stmt.query_map([since], |row| {
+            Ok(AiUsageSummary {
+                feature: row.get(0)?,
+                provider: row.get(1)?,
+                model: row.get(2)?,
+                calls: row.get(3)?,
+                failures: row.get(4)?,
+                prompt_tokens: row.get(5)?,
+                response_tokens: row.get(6)?,
+                duration_ms: row.get(7)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Delete all recorded AI calls, returning how many were removed
+    pub fn clear_ai_calls(&self) -> Result<usize> {
+        Ok(self.conn.execute("DELETE FROM ai_calls", [])?)
+    }
+}
@@ -0,0 +1,95 @@
+//! Shell aliases tied to tools
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, params};
+use serde::Serialize;
+
+use super::Database;
+
+/// A shell alias, such as `cat` for `bat --paging=never`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolAlias {
+    pub name: String,
+    pub command: String,
+    /// Tool the command runs, if tracked
+    pub tool: Option<String>,
+    /// Whether that tool is installed (true for aliases without a tool)
+    pub installed: bool,
+}
+
+fn alias_from_row(row: &rusqlite::Row) -> rusqlite::Result<ToolAlias> {
+    let tool: Option<String> = row.get(2)?;
+    let installed: Option<bool> = row.get(3)?;
+    Ok(ToolAlias {
+        name: row.get(0)?,
+        command: row.get(1)?,
+        installed: installed.unwrap_or(tool.is_none()),
+        tool,
+    })
+}
+
+const ALIAS_SELECT: &str = "SELECT a.name, a.command, t.name, t.is_installed
+     FROM aliases a
+     LEFT JOIN tools t ON a.tool_id = t.id";
+
+impl Database {
+    // ==================== Alias Operations ====================
+
+    /// Add or replace an alias, tied to a tracked tool if given (returns
+    /// false if that tool is not tracked)
+    pub fn set_alias(&self, name: &str, command: &str, tool: Option<&str>) -> Result<bool> {
+        let tool_id: Option<i64> = match tool {
+            Some(tool) => {
+                let id = self
+                    .conn
+                    .query_row("SELECT id FROM tools WHERE name = ?1", [tool], |row| {
+                        row.get(0)
+                    })
+                    .optional()?;
+                if id.is_none() {
+                    return Ok(false);
+                }
+                id
+            }
+            None => None,
+        };
+        self.conn.execute(
+            "INSERT OR REPLACE INTO aliases (name, command, tool_id, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![name, command, tool_id, Utc::now().to_rfc3339()],
+        )?;
+        Ok(true)
+    }
+
+    /// Remove an alias (returns false if there was none)
+    pub fn remove_alias(&self, name: &str) -> Result<bool> {
+        let rows = self
+            .conn
+            .execute("DELETE FROM aliases WHERE name = ?1", [name])?;
+        Ok(rows > 0)
+    }
+
+    /// All aliases, by name
+    pub fn list_aliases(&self) -> Result<Vec<ToolAlias>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("{} ORDER BY a.name", ALIAS_SELECT))?;
+        let aliases = stmt
+            .query_map([], alias_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(aliases)
+    }
+
+    /// Aliases that run a tool
+    pub fn get_tool_aliases(&self, tool: &str) -> Result<Vec<ToolAlias>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE t.name = ?1 ORDER BY a.name",
+            ALIAS_SELECT
+        ))?;
+        let aliases = stmt
+            .query_map([tool], alias_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(aliases)
+    }
+}
@@ -1,9 +1,10 @@
 //! Bundle database operations
 
-use anyhow::Result;
-use rusqlite::params;
+use anyhow::{Result, bail};
+use rusqlite::{OptionalExtension, params};
+use std::collections::HashMap;
 
-use crate::models::Bundle;
+use crate::models::{Bundle, BundleTree, MemberCondition};
 
 use super::Database;
 use super::tools::parse_datetime;
@@ -29,8 +30,19 @@ impl Database {
         // Insert bundle tools in transaction
         for tool_name in &bundle.tools {
             tx.execute(
-                "INSERT INTO bundle_tools (bundle_id, tool_name) VALUES (?1, ?2)",
-                params![bundle_id, tool_name],
+                "INSERT INTO bundle_tools (bundle_id, tool_name, condition) VALUES (?1, ?2, ?3)",
+                params![
+                    bundle_id,
+                    tool_name,
+                    bundle.condition(tool_name).map(|c| c.to_string())
+                ],
+            )?;
+        }
+        for included in &bundle.includes {
+            tx.execute(
+                "INSERT OR IGNORE INTO bundle_includes (bundle_id, included_id)
+                 SELECT ?1, id FROM bundles WHERE name = ?2",
+                params![bundle_id, included],
             )?;
         }
 
@@ -57,19 +69,26 @@ impl Database {
             Ok((id, name, description, created_at)) => {
                 // Get tools for this bundle
                 let mut stmt = self.conn.prepare(
-                    "SELECT tool_name FROM bundle_tools WHERE bundle_id = ?1 ORDER BY tool_name",
+                    "SELECT tool_name, condition FROM bundle_tools
+                     WHERE bundle_id = ?1 ORDER BY tool_name",
                 )?;
-                let tools: Vec<String> =
-                    stmt.query_map([id], |row| row.get(0))?
-                        .collect::<Result<Vec<_>, _>>()?;
+                let members: Vec<(String, Option<String>)> = stmt
+                    .query_map([id], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<Result<Vec<_>, _>>()?;
 
-                Ok(Some(Bundle {
+                let mut bundle = Bundle {
                     id: Some(id),
                     name,
                     description,
-                    tools,
+                    tools: Vec::new(),
+                    conditions: Default::default(),
+                    includes: self.bundle_includes(id)?,
                     created_at: parse_datetime(created_at),
-                }))
+                };
+                for (tool, condition) in members {
+                    push_member(&mut bundle, tool, condition);
+                }
+                Ok(Some(bundle))
             }
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e.into()),
@@ -80,12 +99,26 @@ impl Database {
     pub fn list_bundles(&self) -> Result<Vec<Bundle>> {
         // Single query with LEFT JOIN to get bundles and their tools
         let mut stmt = self.conn.prepare(
-            "SELECT b.id, b.name, b.description, b.created_at, bt.tool_name
+            "SELECT b.id, b.name, b.description, b.created_at, bt.tool_name, bt.condition
              FROM bundles b
              LEFT JOIN bundle_tools bt ON b.id = bt.bundle_id
              ORDER BY b.name, bt.tool_name",
         )?;
 
+        // Included bundle names, by including bundle
+        let mut includes: HashMap<i64, Vec<String>> = HashMap::new();
+        {
+            let mut stmt = self.conn.prepare(
+                "SELECT i.bundle_id, b.name FROM bundle_includes i
+                 JOIN bundles b ON i.included_id = b.id
+                 ORDER BY b.name",
+            )?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                includes.entry(row.get(0)?).or_default().push(row.get(1)?);
+            }
+        }
+
         // Group rows by bundle
         let mut bundles: Vec<Bundle> = Vec::new();
         let mut current_id: Option<i64> = None;
@@ -97,21 +130,23 @@ impl Database {
             let description: Option<String> = row.get(2)?;
             let created_at: String = row.get(3)?;
             let tool_name: Option<String> = row.get(4)?;
+            let condition: Option<String> = row.get(5)?;
             if current_id != Some(id) {
                 // New bundle
                 bundles.push(Bundle {
                     id: Some(id),
                     name,
                     description,
-                    tools: tool_name.into_iter().collect(),
+                    tools: Vec::new(),
+                    conditions: Default::default(),
+                    includes: includes.remove(&id).unwrap_or_default(),
                     created_at: parse_datetime(created_at),
                 });
                 current_id = Some(id);
-            } else if let Some(tool) = tool_name {
-                // Add tool to current bundle
-                if let Some(bundle) = bundles.last_mut() {
-                    bundle.tools.push(tool);
-                }
+            }
+            // Add tool to current bundle
+            if let (Some(tool), Some(bundle)) = (tool_name, bundles.last_mut()) {
+                push_member(bundle, tool, condition);
             }
         }
 
@@ -175,6 +210,135 @@ impl Database {
         Ok(true)
     }
 
+    /// Set or clear the condition of a bundle member
+    ///
+    /// Returns false if the tool is not in the bundle.
+    pub fn set_bundle_condition(
+        &self,
+        bundle_name: &str,
+        tool_name: &str,
+        condition: Option<&MemberCondition>,
+    ) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE bundle_tools SET condition = ?3
+             WHERE bundle_id = (SELECT id FROM bundles WHERE name = ?1) AND tool_name = ?2",
+            params![bundle_name, tool_name, condition.map(|c| c.to_string())],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Names of the bundles a bundle includes directly
+    fn bundle_includes(&self, bundle_id: i64) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT b.name FROM bundle_includes i
+             JOIN bundles b ON i.included_id = b.id
+             WHERE i.bundle_id = ?1
+             ORDER BY b.name",
+        )?;
+        let names = stmt
+            .query_map([bundle_id], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(names)
+    }
+
+    pub(super) fn bundle_id(&self, name: &str) -> Result<Option<i64>> {
+        Ok(self
+            .conn
+            .query_row("SELECT id FROM bundles WHERE name = ?1", [name], |row| {
+                row.get(0)
+            })
+            .optional()?)
+    }
+
+    /// Include bundles in another bundle
+    ///
+    /// Fails if an included bundle doesn't exist or including it would
+    /// create a cycle. Returns false if `bundle_name` doesn't exist.
+    pub fn include_bundles(&self, bundle_name: &str, included: &[String]) -> Result<bool> {
+        let Some(bundle_id) = self.bundle_id(bundle_name)? else {
+            return Ok(false);
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+        for name in included {
+            let Some(included_id) = self.bundle_id(name)? else {
+                bail!("Bundle '{}' not found", name);
+            };
+            // A cycle exists if the parent is reachable from the included bundle
+            let reachable: bool = tx.query_row(
+                "WITH RECURSIVE reach(id) AS (
+                     SELECT ?1
+                     UNION
+                     SELECT i.included_id FROM bundle_includes i JOIN reach r ON i.bundle_id = r.id
+                 )
+                 SELECT COUNT(*) > 0 FROM reach WHERE id = ?2",
+                params![included_id, bundle_id],
+                |row| row.get(0),
+            )?;
+            if reachable {
+                bail!(
+                    "Including '{}' in '{}' would create a cycle",
+                    name,
+                    bundle_name
+                );
+            }
+            tx.execute(
+                "INSERT OR IGNORE INTO bundle_includes (bundle_id, included_id) VALUES (?1, ?2)",
+                params![bundle_id, included_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Stop including bundles in another bundle
+    pub fn remove_bundle_includes(&self, bundle_name: &str, included: &[String]) -> Result<bool> {
+        let Some(bundle_id) = self.bundle_id(bundle_name)? else {
+            return Ok(false);
+        };
+        let tx = self.conn.unchecked_transaction()?;
+        for name in included {
+            tx.execute(
+                "DELETE FROM bundle_includes
+                 WHERE bundle_id = ?1 AND included_id = (SELECT id FROM bundles WHERE name = ?2)",
+                params![bundle_id, name],
+            )?;
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Get a bundle with its included bundles, recursively
+    pub fn get_bundle_tree(&self, name: &str) -> Result<Option<BundleTree>> {
+        self.bundle_tree(name, &mut Vec::new())
+    }
+
+    fn bundle_tree(&self, name: &str, path: &mut Vec<String>) -> Result<Option<BundleTree>> {
+        // Cycles are refused on insert; stay safe against hand-edited databases
+        if path.iter().any(|p| p == name) {
+            return Ok(None);
+        }
+        let Some(bundle) = self.get_bundle(name)? else {
+            return Ok(None);
+        };
+
+        path.push(name.to_string());
+        let mut children = Vec::new();
+        for included in &bundle.includes {
+            if let Some(child) = self.bundle_tree(included, path)? {
+                children.push(child);
+            }
+        }
+        path.pop();
+
+        Ok(Some(BundleTree { bundle, children }))
+    }
+
+    /// Get a bundle with the tools of its included bundles merged in
+    pub fn get_flattened_bundle(&self, name: &str) -> Result<Option<Bundle>> {
+        Ok(self.get_bundle_tree(name)?.map(|tree| tree.flatten()))
+    }
+
     /// Get all bundle names (for completions)
     pub fn get_bundle_names(&self) -> Result<Vec<String>> {
         let mut stmt = self
@@ -186,3 +350,13 @@ impl Database {
         Ok(names)
     }
 }
+
+/// Add a member row to a bundle, keeping its condition
+///
+/// Conditions are validated when stored, so unparsable ones are ignored.
+fn push_member(bundle: &mut Bundle, tool: String, condition: Option<String>) {
+    if let Some(condition) = condition.and_then(|c| MemberCondition::parse(&c).ok()) {
+        bundle.conditions.insert(tool.clone(), condition);
+    }
+    bundle.tools.push(tool);
+}
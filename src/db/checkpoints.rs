@@ -0,0 +1,47 @@
+//! Checkpoints for resumable batch jobs
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+use std::collections::HashSet;
+
+use super::Database;
+
+impl Database {
+    // ==================== Checkpoint Operations ====================
+
+    /// Record tools processed by a job
+    pub fn add_checkpoint(&self, job: &str, tool_ids: &[i64]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO job_checkpoints (job, tool_id, processed_at)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            let now = Utc::now().to_rfc3339();
+            for id in tool_ids {
+                stmt.execute(params![job, id, now])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Ids of tools a job has already processed
+    pub fn get_checkpoint(&self, job: &str) -> Result<HashSet<i64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT tool_id FROM job_checkpoints WHERE job = ?")?;
+        let ids = stmt
+            .query_map([job], |row| row.get(0))?
+            .collect::<rusqlite::Result<HashSet<i64>>>()?;
+        Ok(ids)
+    }
+
+    /// Forget a job's progress, returning how many tools were recorded
+    pub fn clear_checkpoint(&self, job: &str) -> Result<usize> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM job_checkpoints WHERE job = ?", [job])?)
+    }
+}
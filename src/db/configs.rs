@@ -4,11 +4,31 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::params;
 
-use crate::models::Config;
+use crate::models::{Config, ConfigMode};
 
 use super::Database;
 use super::tools::parse_datetime;
 
+/// A target moved aside before hoards replaced it with a link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigBackup {
+    pub id: i64,
+    pub config: String,
+    /// Where the file or directory was
+    pub original_path: String,
+    /// Where it was moved to
+    pub backup_path: String,
+    pub created_at: String,
+}
+
+/// A per-machine alternative to a config's source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigVariant {
+    /// Profile name, or a hostname pattern such as `work-*`
+    pub profile: String,
+    pub source_path: String,
+}
+
 impl Database {
     // ==================== Config Operations ====================
 
@@ -16,8 +36,9 @@ impl Database {
     pub fn insert_config(&self, config: &Config) -> Result<i64> {
         self.conn.execute(
             r#"
-            INSERT INTO configs (name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            INSERT INTO configs (name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at, mode,
+                                 validate_command)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
             "#,
             params![
                 config.name,
@@ -27,6 +48,8 @@ impl Database {
                 config.is_symlinked,
                 config.created_at.to_rfc3339(),
                 config.updated_at.to_rfc3339(),
+                config.mode.to_string(),
+                config.validate_command,
             ],
         )?;
 
@@ -36,7 +59,8 @@ impl Database {
     /// List all configs
     pub fn list_configs(&self) -> Result<Vec<Config>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at
+            "SELECT id, name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at,
+                    rendered_hash, mode, validate_command
              FROM configs ORDER BY name",
         )?;
 
@@ -55,6 +79,9 @@ impl Database {
                     updated_at: DateTime::parse_from_rfc3339(&row.get::<_, String>(7)?)
                         .map(|dt| dt.with_timezone(&Utc))
                         .unwrap_or_else(|_| Utc::now()),
+                    rendered_hash: row.get(8)?,
+                    mode: row.get::<_, String>(9)?.as_str().into(),
+                    validate_command: row.get(10)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -65,7 +92,8 @@ impl Database {
     /// Get a config by name
     pub fn get_config_by_name(&self, name: &str) -> Result<Option<Config>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at
+            "SELECT id, name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at,
+                    rendered_hash, mode, validate_command
              FROM configs WHERE name = ?1",
         )?;
 
@@ -80,6 +108,9 @@ impl Database {
                     is_symlinked: row.get(5)?,
                     created_at: parse_datetime(row.get(6)?),
                     updated_at: parse_datetime(row.get(7)?),
+                    rendered_hash: row.get(8)?,
+                    mode: row.get::<_, String>(9)?.as_str().into(),
+                    validate_command: row.get(10)?,
                 })
             })
             .optional()?;
@@ -90,7 +121,8 @@ impl Database {
     /// Get configs associated with a tool
     pub fn get_configs_for_tool(&self, tool_id: i64) -> Result<Vec<Config>> {
         let mut stmt = self.conn.prepare(
-            "SELECT id, name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at
+            "SELECT id, name, source_path, target_path, tool_id, is_symlinked, created_at, updated_at,
+                    rendered_hash, mode, validate_command
              FROM configs WHERE tool_id = ?1 ORDER BY name",
         )?;
 
@@ -105,6 +137,9 @@ impl Database {
                     is_symlinked: row.get(5)?,
                     created_at: parse_datetime(row.get(6)?),
                     updated_at: parse_datetime(row.get(7)?),
+                    rendered_hash: row.get(8)?,
+                    mode: row.get::<_, String>(9)?.as_str().into(),
+                    validate_command: row.get(10)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -122,6 +157,36 @@ impl Database {
         Ok(())
     }
 
+    /// Set whether a config is linked or copied into place
+    pub fn set_config_mode(&self, name: &str, mode: ConfigMode) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "UPDATE configs SET mode = ?1, updated_at = ?2 WHERE name = ?3",
+            params![mode.to_string(), now, name],
+        )?;
+        Ok(())
+    }
+
+    /// Set (or clear) the command that validates a config after sync
+    pub fn set_config_validate(&self, name: &str, command: Option<&str>) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "UPDATE configs SET validate_command = ?1, updated_at = ?2 WHERE name = ?3",
+            params![command, now, name],
+        )?;
+        Ok(())
+    }
+
+    /// Remember the hash of what a templated or copied config was last written as
+    pub fn set_config_rendered(&self, name: &str, hash: Option<&str>) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        self.conn.execute(
+            "UPDATE configs SET rendered_hash = ?1, is_symlinked = ?2, updated_at = ?3 WHERE name = ?4",
+            params![hash, hash.is_some(), now, name],
+        )?;
+        Ok(())
+    }
+
     /// Update a config's paths
     pub fn update_config_paths(
         &self,
@@ -158,6 +223,101 @@ impl Database {
             .execute("DELETE FROM configs WHERE name = ?1", [name])?;
         Ok(rows > 0)
     }
+
+    // ==================== Config Variant Operations ====================
+
+    /// Add or replace a config's source for a profile
+    pub fn set_config_variant(&self, config_name: &str, profile: &str, source: &str) -> Result<()> {
+        let rows = self.conn.execute(
+            "INSERT INTO config_variants (config_id, profile, source_path)
+             SELECT id, ?2, ?3 FROM configs WHERE name = ?1
+             ON CONFLICT(config_id, profile) DO UPDATE SET source_path = excluded.source_path",
+            params![config_name, profile, source],
+        )?;
+        if rows == 0 {
+            anyhow::bail!("Config '{}' not found", config_name);
+        }
+        Ok(())
+    }
+
+    /// A config's variants, by profile
+    pub fn list_config_variants(&self, config_name: &str) -> Result<Vec<ConfigVariant>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT v.profile, v.source_path FROM config_variants v
+             JOIN configs c ON c.id = v.config_id
+             WHERE c.name = ?1 ORDER BY v.profile",
+        )?;
+        let variants = stmt
+            .query_map([config_name], |row| {
+                Ok(ConfigVariant {
+                    profile: row.get(0)?,
+                    source_path: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(variants)
+    }
+
+    /// Remove a config's variant for a profile
+    pub fn remove_config_variant(&self, config_name: &str, profile: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM config_variants
+             WHERE config_id = (SELECT id FROM configs WHERE name = ?1) AND profile = ?2",
+            params![config_name, profile],
+        )?;
+        Ok(rows > 0)
+    }
+
+    // ==================== Config Backup Operations ====================
+
+    /// Record a backup of a config's target
+    pub fn record_config_backup(
+        &self,
+        config_name: &str,
+        original_path: &str,
+        backup_path: &str,
+    ) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO config_backups (config_name, original_path, backup_path, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                config_name,
+                original_path,
+                backup_path,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Backups of a config, newest first
+    pub fn list_config_backups(&self, config_name: &str) -> Result<Vec<ConfigBackup>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, config_name, original_path, backup_path, created_at
+             FROM config_backups WHERE config_name = ?1
+             ORDER BY created_at DESC, id DESC",
+        )?;
+        let backups = stmt
+            .query_map([config_name], |row| {
+                Ok(ConfigBackup {
+                    id: row.get(0)?,
+                    config: row.get(1)?,
+                    original_path: row.get(2)?,
+                    backup_path: row.get(3)?,
+                    created_at: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(backups)
+    }
+
+    /// Forget a backup (after it was restored)
+    pub fn delete_config_backup(&self, id: i64) -> Result<bool> {
+        let rows = self
+            .conn
+            .execute("DELETE FROM config_backups WHERE id = ?1", [id])?;
+        Ok(rows > 0)
+    }
 }
 
 // Import OptionalExtension for .optional() method
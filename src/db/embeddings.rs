@@ -0,0 +1,102 @@
+//! Tool embedding storage for similar-tool search
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+
+use super::Database;
+
+/// Encode a vector as little-endian f32 bytes
+fn encode_vector(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Decode little-endian f32 bytes into a vector
+fn decode_vector(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+impl Database {
+    // ==================== Embedding Operations ====================
+
+    /// Store the embedding of a tool, replacing any previous one
+    pub fn set_embedding(
+        &self,
+        tool_name: &str,
+        model: &str,
+        content_hash: &str,
+        vector: &[f32],
+    ) -> Result<bool> {
+        let updated = self.conn.execute(
+            "INSERT OR REPLACE INTO tool_embeddings
+             (tool_id, model, content_hash, vector, updated_at)
+             SELECT id, ?2, ?3, ?4, ?5 FROM tools WHERE name = ?1",
+            params![
+                tool_name,
+                model,
+                content_hash,
+                encode_vector(vector),
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Content hashes of tools embedded with `model` (tool name -> hash)
+    pub fn get_embedding_hashes(&self, model: &str) -> Result<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, te.content_hash FROM tool_embeddings te
+             JOIN tools t ON te.tool_id = t.id
+             WHERE te.model = ?1",
+        )?;
+        let hashes = stmt
+            .query_map([model], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(hashes)
+    }
+
+    /// Get a tool's embedding and the model that produced it
+    pub fn get_embedding(&self, tool_name: &str) -> Result<Option<(String, Vec<f32>)>> {
+        let result = self.conn.query_row(
+            "SELECT te.model, te.vector FROM tool_embeddings te
+             JOIN tools t ON te.tool_id = t.id
+             WHERE t.name = ?1",
+            [tool_name],
+            |row| {
+                let bytes: Vec<u8> = row.get(1)?;
+                Ok((row.get(0)?, decode_vector(&bytes)))
+            },
+        );
+        match result {
+            Ok(embedding) => Ok(Some(embedding)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get all embeddings produced by `model` (tool name, vector)
+    pub fn get_embeddings(&self, model: &str) -> Result<Vec<(String, Vec<f32>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, te.vector FROM tool_embeddings te
+             JOIN tools t ON te.tool_id = t.id
+             WHERE te.model = ?1",
+        )?;
+        let embeddings = stmt
+            .query_map([model], |row| {
+                let bytes: Vec<u8> = row.get(1)?;
+                Ok((row.get(0)?, decode_vector(&bytes)))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(embeddings)
+    }
+
+    /// Remove all stored embeddings (forces a full re-index)
+    pub fn clear_embeddings(&self) -> Result<usize> {
+        Ok(self.conn.execute("DELETE FROM tool_embeddings", [])?)
+    }
+}
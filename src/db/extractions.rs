@@ -147,6 +147,27 @@ impl Database {
         Ok(result)
     }
 
+    /// Get a cached value no older than `max_age` (any age when `None`)
+    pub fn get_ai_cache_fresh(
+        &self,
+        key: &str,
+        max_age: Option<chrono::Duration>,
+    ) -> Result<Option<String>> {
+        let Some(max_age) = max_age else {
+            return self.get_ai_cache(key);
+        };
+        let result: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT content FROM ai_cache
+                 WHERE cache_key = ?1 AND created_at >= datetime('now', ?2)",
+                rusqlite::params![key, format!("-{} seconds", max_age.num_seconds())],
+                |row| row.get(0),
+            )
+            .ok();
+        Ok(result)
+    }
+
     /// Set a cached value
     pub fn set_ai_cache(&self, key: &str, content: &str) -> Result<()> {
         self.conn.execute(
@@ -157,6 +178,19 @@ impl Database {
         Ok(())
     }
 
+    /// List cached values whose key starts with `prefix`, ordered by key
+    pub fn list_ai_cache(&self, prefix: &str) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT cache_key, content FROM ai_cache
+             WHERE substr(cache_key, 1, length(?1)) = ?1
+             ORDER BY cache_key",
+        )?;
+        let entries = stmt
+            .query_map([prefix], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(entries)
+    }
+
     /// Delete a cached value
     pub fn delete_ai_cache(&self, key: &str) -> Result<bool> {
         let count = self
@@ -164,4 +198,16 @@ impl Database {
             .execute("DELETE FROM ai_cache WHERE cache_key = ?", [key])?;
         Ok(count > 0)
     }
+
+    /// Delete cached values whose key starts with `prefix` (all when `None`)
+    pub fn clear_ai_cache(&self, prefix: Option<&str>) -> Result<usize> {
+        let count = match prefix {
+            Some(prefix) => self.conn.execute(
+                "DELETE FROM ai_cache WHERE substr(cache_key, 1, length(?1)) = ?1",
+                [prefix],
+            )?,
+            None => self.conn.execute("DELETE FROM ai_cache", [])?,
+        };
+        Ok(count)
+    }
 }
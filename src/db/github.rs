@@ -1,31 +1,141 @@
 //! GitHub data database operations
+//!
+//! Despite the name, rows may describe a repository on any forge; `provider`
+//! and `host` record where it lives (GitHub unless linked elsewhere).
 
 use anyhow::Result;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rusqlite::params;
+use serde::Serialize;
 
 use super::Database;
+use super::tools::tool_from_row;
+use crate::forge::{ForgeProvider, RepoInfo, RepoRef};
+use crate::models::Tool;
+
+/// Days without a commit after which an upstream is considered abandoned
+pub const ABANDONED_AFTER_DAYS: i64 = 730;
 
 /// GitHub repository info
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GitHubInfo {
+    pub provider: ForgeProvider,
+    /// Forge host, e.g. "github.com" or "codeberg.org"
+    pub host: String,
     pub repo_owner: String,
     pub repo_name: String,
     pub description: Option<String>,
     pub stars: i64,
     pub language: Option<String>,
     pub homepage: Option<String>,
+    /// Time of the last push to the repository (RFC 3339)
+    pub pushed_at: Option<String>,
+    pub open_issues: Option<i64>,
+    pub archived: bool,
+    pub contributors: Option<i64>,
+}
+
+impl GitHubInfo {
+    /// Whether the repository is hosted on GitHub
+    pub fn is_github(&self) -> bool {
+        self.provider == ForgeProvider::GitHub
+    }
+
+    /// Reference to the repository on its forge
+    pub fn repo_ref(&self) -> RepoRef {
+        RepoRef {
+            provider: self.provider,
+            host: self.host.clone(),
+            owner: self.repo_owner.clone(),
+            repo: self.repo_name.clone(),
+        }
+    }
+
+    /// Days since the last push, if known
+    pub fn days_since_push(&self) -> Option<i64> {
+        let pushed = DateTime::parse_from_rfc3339(self.pushed_at.as_deref()?).ok()?;
+        Some((Utc::now() - pushed.with_timezone(&Utc)).num_days())
+    }
+
+    /// Why the upstream looks dead, if it does
+    ///
+    /// An archived repository or one without a push in
+    /// `ABANDONED_AFTER_DAYS` counts as abandoned.
+    pub fn abandonment(&self) -> Option<String> {
+        if self.archived {
+            return Some("repository is archived".to_string());
+        }
+        let days = self.days_since_push()?;
+        (days >= ABANDONED_AFTER_DAYS).then(|| format!("no commits in {} years", days / 365))
+    }
 }
 
 /// Input data for storing GitHub repo info
 #[derive(Debug)]
 pub struct GitHubInfoInput<'a> {
+    pub provider: ForgeProvider,
+    pub host: &'a str,
     pub repo_owner: &'a str,
     pub repo_name: &'a str,
     pub description: Option<&'a str>,
     pub stars: i64,
     pub language: Option<&'a str>,
     pub homepage: Option<&'a str>,
+    pub pushed_at: Option<&'a str>,
+    pub open_issues: Option<i64>,
+    pub archived: bool,
+    pub contributors: Option<i64>,
+}
+
+impl<'a> GitHubInfoInput<'a> {
+    /// Input from repository metadata fetched from a forge
+    ///
+    /// The contributor count needs a separate request and is left unset.
+    pub fn from_repo(provider: ForgeProvider, host: &'a str, info: &'a RepoInfo) -> Self {
+        Self {
+            provider,
+            host,
+            repo_owner: &info.owner.login,
+            repo_name: &info.name,
+            description: info.description.as_deref(),
+            stars: info.stars,
+            language: info.language.as_deref(),
+            homepage: info.homepage.as_deref(),
+            pushed_at: info.pushed_at.as_deref(),
+            open_issues: Some(info.open_issues),
+            archived: info.archived,
+            contributors: None,
+        }
+    }
+}
+
+/// Columns read by `info_from_row`, in order
+const INFO_COLUMNS: &str = "tg.repo_owner, tg.repo_name, tg.description, tg.stars, tg.language,
+     tg.homepage, tg.provider, tg.host, tg.pushed_at, tg.open_issues, tg.archived,
+     tg.contributors";
+
+/// Map `INFO_COLUMNS` starting at column `start` to a GitHubInfo
+fn info_from_row(row: &rusqlite::Row, start: usize) -> rusqlite::Result<GitHubInfo> {
+    let host: String = row.get(start + 7)?;
+    Ok(GitHubInfo {
+        provider: provider_from_row(&row.get::<_, String>(start + 6)?, &host),
+        host,
+        repo_owner: row.get(start)?,
+        repo_name: row.get(start + 1)?,
+        description: row.get(start + 2)?,
+        stars: row.get(start + 3)?,
+        language: row.get(start + 4)?,
+        homepage: row.get(start + 5)?,
+        pushed_at: row.get(start + 8)?,
+        open_issues: row.get(start + 9)?,
+        archived: row.get(start + 10)?,
+        contributors: row.get(start + 11)?,
+    })
+}
+
+/// Stored provider name, falling back to a guess from the host
+fn provider_from_row(provider: &str, host: &str) -> ForgeProvider {
+    ForgeProvider::parse(provider).unwrap_or_else(|| ForgeProvider::for_host(host))
 }
 
 impl Database {
@@ -46,8 +156,9 @@ impl Database {
 
         self.conn.execute(
             "INSERT OR REPLACE INTO tool_github
-             (tool_id, repo_owner, repo_name, description, stars, language, homepage, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+             (tool_id, repo_owner, repo_name, description, stars, language, homepage, updated_at,
+              provider, host, pushed_at, open_issues, archived, contributors)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
             params![
                 tool_id,
                 info.repo_owner,
@@ -56,7 +167,13 @@ impl Database {
                 info.stars,
                 info.language,
                 info.homepage,
-                Utc::now().to_rfc3339()
+                Utc::now().to_rfc3339(),
+                info.provider.as_str(),
+                info.host,
+                info.pushed_at,
+                info.open_issues,
+                info.archived,
+                info.contributors
             ],
         )?;
 
@@ -66,21 +183,14 @@ impl Database {
     /// Get GitHub info for a tool
     pub fn get_github_info(&self, tool_name: &str) -> Result<Option<GitHubInfo>> {
         let result = self.conn.query_row(
-            "SELECT tg.repo_owner, tg.repo_name, tg.description, tg.stars, tg.language, tg.homepage
-             FROM tool_github tg
-             JOIN tools t ON tg.tool_id = t.id
-             WHERE t.name = ?1",
+            &format!(
+                "SELECT {INFO_COLUMNS}
+                 FROM tool_github tg
+                 JOIN tools t ON tg.tool_id = t.id
+                 WHERE t.name = ?1"
+            ),
             [tool_name],
-            |row| {
-                Ok(GitHubInfo {
-                    repo_owner: row.get(0)?,
-                    repo_name: row.get(1)?,
-                    description: row.get(2)?,
-                    stars: row.get(3)?,
-                    language: row.get(4)?,
-                    homepage: row.get(5)?,
-                })
-            },
+            |row| info_from_row(row, 0),
         );
 
         match result {
@@ -134,27 +244,74 @@ impl Database {
 
     /// Get all GitHub info for all tools (for batch loading in TUI)
     pub fn get_all_github_info(&self) -> Result<Vec<(String, GitHubInfo)>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT t.name, tg.repo_owner, tg.repo_name, tg.description, tg.stars, tg.language, tg.homepage
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT t.name, {INFO_COLUMNS}
              FROM tools t
              INNER JOIN tool_github tg ON t.id = tg.tool_id
-             ORDER BY t.name",
-        )?;
+             ORDER BY t.name"
+        ))?;
         let results = stmt
             .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    GitHubInfo {
-                        repo_owner: row.get(1)?,
-                        repo_name: row.get(2)?,
-                        description: row.get(3)?,
-                        stars: row.get(4)?,
-                        language: row.get(5)?,
-                        homepage: row.get(6)?,
-                    },
-                ))
+                Ok((row.get::<_, String>(0)?, info_from_row(row, 1)?))
             })?
             .collect::<Result<Vec<_>, _>>()?;
         Ok(results)
     }
+
+    /// Replace the repository topics of a tool
+    pub fn set_topics(&self, tool_name: &str, topics: &[String]) -> Result<bool> {
+        let tool_id: i64 =
+            match self
+                .conn
+                .query_row("SELECT id FROM tools WHERE name = ?1", [tool_name], |row| {
+                    row.get(0)
+                }) {
+                Ok(id) => id,
+                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(false),
+                Err(e) => return Err(e.into()),
+            };
+
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM tool_topics WHERE tool_id = ?1", [tool_id])?;
+        for topic in topics {
+            tx.execute(
+                "INSERT OR IGNORE INTO tool_topics (tool_id, topic) VALUES (?1, ?2)",
+                params![tool_id, topic.to_lowercase()],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(true)
+    }
+
+    /// Get the repository topics of a tool
+    pub fn get_topics(&self, tool_name: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tt.topic FROM tool_topics tt
+             JOIN tools t ON tt.tool_id = t.id
+             WHERE t.name = ?1
+             ORDER BY tt.topic",
+        )?;
+        let topics = stmt
+            .query_map([tool_name], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(topics)
+    }
+
+    /// List tools whose repository has a topic
+    pub fn list_tools_by_topic(&self, topic: &str) -> Result<Vec<Tool>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.id, t.name, t.description, t.category, t.source, t.install_command,
+                    t.binary_name, t.is_installed, t.is_favorite, t.notes,
+                    t.created_at, t.updated_at, t.license, t.installed_version, t.kind
+             FROM tools t
+             JOIN tool_topics tt ON t.id = tt.tool_id
+             WHERE tt.topic = ?1
+             ORDER BY t.name",
+        )?;
+
+        let tool_iter = stmt.query_map([topic.to_lowercase()], tool_from_row)?;
+
+        tool_iter.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
 }
@@ -0,0 +1,179 @@
+//! Persistent GitHub work queue and requests shared between processes
+//!
+//! Times in these tables are Unix seconds, matching the `x-ratelimit-reset`
+//! header they are compared against.
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, Transaction, TransactionBehavior, params};
+
+use super::Database;
+
+/// How long a finished response is handed to other processes
+const SHARED_RESPONSE_SECS: i64 = 30;
+
+/// After this long, an unfinished request is assumed abandoned
+const IN_FLIGHT_TIMEOUT_SECS: i64 = 30;
+
+/// A queued unit of GitHub work (e.g. syncing one tool)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuedJob {
+    pub job: String,
+    pub target: String,
+    /// Unix time before which the job waits for a rate-limit reset
+    pub not_before: i64,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+}
+
+/// Outcome of claiming a shared GitHub request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestClaim {
+    /// The caller should perform the request
+    Claimed,
+    /// Another process is performing it right now
+    InFlight,
+    /// Another process just finished it; here is the response body
+    Shared(String),
+}
+
+impl Database {
+    // ==================== GitHub Queue Operations ====================
+
+    /// Queue targets for a job, returning how many were not queued already
+    pub fn enqueue_github_jobs(&self, job: &str, targets: &[String]) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut added = 0;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO github_queue (job, target, queued_at)
+                 VALUES (?1, ?2, ?3)",
+            )?;
+            let now = Utc::now().to_rfc3339();
+            for target in targets {
+                added += stmt.execute(params![job, target, now])?;
+            }
+        }
+        tx.commit()?;
+        Ok(added)
+    }
+
+    /// Queued jobs, optionally of one kind, oldest first
+    pub fn github_jobs(&self, job: Option<&str>) -> Result<Vec<QueuedJob>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT job, target, not_before, attempts, last_error FROM github_queue
+             WHERE ?1 IS NULL OR job = ?1
+             ORDER BY queued_at, job, target",
+        )?;
+        let jobs = stmt
+            .query_map([job], |row| {
+                Ok(QueuedJob {
+                    job: row.get(0)?,
+                    target: row.get(1)?,
+                    not_before: row.get(2)?,
+                    attempts: row.get(3)?,
+                    last_error: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(jobs)
+    }
+
+    /// Park a job until a rate limit resets
+    pub fn defer_github_job(&self, job: &str, target: &str, not_before: i64) -> Result<()> {
+        self.conn.execute(
+            "UPDATE github_queue SET not_before = ?3 WHERE job = ?1 AND target = ?2",
+            params![job, target, not_before],
+        )?;
+        Ok(())
+    }
+
+    /// Record a failed attempt, returning the number of attempts so far
+    pub fn fail_github_job(&self, job: &str, target: &str, error: &str) -> Result<i64> {
+        self.conn.execute(
+            "UPDATE github_queue SET attempts = attempts + 1, last_error = ?3
+             WHERE job = ?1 AND target = ?2",
+            params![job, target, error],
+        )?;
+        let attempts = self
+            .conn
+            .query_row(
+                "SELECT attempts FROM github_queue WHERE job = ?1 AND target = ?2",
+                params![job, target],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(attempts.unwrap_or(0))
+    }
+
+    /// Remove a finished job
+    pub fn finish_github_job(&self, job: &str, target: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM github_queue WHERE job = ?1 AND target = ?2",
+            params![job, target],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Empty the queue, optionally for one kind of job only
+    pub fn clear_github_jobs(&self, job: Option<&str>) -> Result<usize> {
+        Ok(self.conn.execute(
+            "DELETE FROM github_queue WHERE ?1 IS NULL OR job = ?1",
+            [job],
+        )?)
+    }
+
+    /// Claim a GitHub request for process `pid`, unless it is already in flight
+    ///
+    /// Runs in an immediate transaction so two processes can't both claim it.
+    pub fn claim_github_request(&self, key: &str, pid: u32, now: i64) -> Result<RequestClaim> {
+        let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+        let existing: Option<(u32, Option<String>, i64)> = tx
+            .query_row(
+                "SELECT pid, body, updated_at FROM github_requests WHERE key = ?",
+                [key],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?;
+
+        let claim = match existing {
+            Some((_, Some(body), at)) if now - at < SHARED_RESPONSE_SECS => {
+                RequestClaim::Shared(body)
+            }
+            Some((owner, None, at)) if owner != pid && now - at < IN_FLIGHT_TIMEOUT_SECS => {
+                RequestClaim::InFlight
+            }
+            _ => {
+                tx.execute(
+                    "INSERT OR REPLACE INTO github_requests (key, pid, body, updated_at)
+                     VALUES (?1, ?2, NULL, ?3)",
+                    params![key, pid, now],
+                )?;
+                RequestClaim::Claimed
+            }
+        };
+        tx.commit()?;
+        Ok(claim)
+    }
+
+    /// Publish the response of a claimed request, or release it on failure
+    pub fn finish_github_request(&self, key: &str, body: Option<&str>, now: i64) -> Result<()> {
+        match body {
+            Some(body) => self.conn.execute(
+                "UPDATE github_requests SET body = ?2, updated_at = ?3 WHERE key = ?1",
+                params![key, body, now],
+            )?,
+            None => self
+                .conn
+                .execute("DELETE FROM github_requests WHERE key = ?", [key])?,
+        };
+        Ok(())
+    }
+
+    /// Drop shared requests last touched before `before`
+    pub fn prune_github_requests(&self, before: i64) -> Result<usize> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM github_requests WHERE updated_at < ?", [before])?)
+    }
+}
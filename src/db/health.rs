@@ -0,0 +1,61 @@
+//! Stored per-tool health scores
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+use std::collections::HashMap;
+
+use super::Database;
+use crate::health::ToolHealth;
+
+impl Database {
+    // ==================== Health Operations ====================
+
+    /// Replace every stored health score, returning how many were stored
+    pub fn replace_tool_health(&self, scores: &[(String, ToolHealth)]) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM tool_health", [])?;
+        let now = Utc::now().to_rfc3339();
+        let mut stored = 0;
+        for (name, health) in scores {
+            stored += tx.execute(
+                "INSERT INTO tool_health (tool_id, score, usage, updates, upstream, configs, computed_at)
+                 SELECT id, ?2, ?3, ?4, ?5, ?6, ?7 FROM tools WHERE name = ?1",
+                params![
+                    name,
+                    health.score,
+                    health.usage,
+                    health.updates,
+                    health.upstream,
+                    health.configs,
+                    now
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(stored)
+    }
+
+    /// Last computed health of every scored tool, by name
+    pub fn get_tool_health(&self) -> Result<HashMap<String, ToolHealth>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, h.score, h.usage, h.updates, h.upstream, h.configs
+             FROM tool_health h JOIN tools t ON t.id = h.tool_id",
+        )?;
+        let scores = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ToolHealth {
+                        score: row.get(1)?,
+                        usage: row.get(2)?,
+                        updates: row.get(3)?,
+                        upstream: row.get(4)?,
+                        configs: row.get(5)?,
+                    },
+                ))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(scores)
+    }
+}
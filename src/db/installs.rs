@@ -0,0 +1,89 @@
+//! Binaries installed from forge release assets
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+use serde::Serialize;
+
+use super::Database;
+
+/// A tool installed from a release asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleaseInstall {
+    pub tool: String,
+    pub host: String,
+    pub repo_owner: String,
+    pub repo_name: String,
+    /// Release tag, used as the installed version
+    pub tag: String,
+    pub asset: String,
+    /// Path of the installed executable
+    pub path: String,
+    pub installed_at: String,
+}
+
+impl Database {
+    // ==================== Release Install Operations ====================
+
+    /// Record a release install (returns false if the tool is not tracked)
+    ///
+    /// `installed_at` is set to the current time.
+    pub fn record_release_install(&self, install: &ReleaseInstall) -> Result<bool> {
+        let rows = self.conn.execute(
+            "INSERT OR REPLACE INTO release_installs
+             (tool_id, host, repo_owner, repo_name, tag, asset, path, installed_at)
+             SELECT id, ?2, ?3, ?4, ?5, ?6, ?7, ?8 FROM tools WHERE name = ?1",
+            params![
+                install.tool,
+                install.host,
+                install.repo_owner,
+                install.repo_name,
+                install.tag,
+                install.asset,
+                install.path,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Get the release install of a tool
+    pub fn get_release_install(&self, tool_name: &str) -> Result<Option<ReleaseInstall>> {
+        let result = self.conn.query_row(
+            "SELECT t.name, r.host, r.repo_owner, r.repo_name, r.tag, r.asset, r.path,
+                    r.installed_at
+             FROM release_installs r
+             JOIN tools t ON r.tool_id = t.id
+             WHERE t.name = ?1",
+            [tool_name],
+            |row| {
+                Ok(ReleaseInstall {
+                    tool: row.get(0)?,
+                    host: row.get(1)?,
+                    repo_owner: row.get(2)?,
+                    repo_name: row.get(3)?,
+                    tag: row.get(4)?,
+                    asset: row.get(5)?,
+                    path: row.get(6)?,
+                    installed_at: row.get(7)?,
+                })
+            },
+        );
+
+        match result {
+            Ok(install) => Ok(Some(install)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Forget the release install of a tool
+    pub fn remove_release_install(&self, tool_name: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM release_installs
+             WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
+            [tool_name],
+        )?;
+        Ok(rows > 0)
+    }
+}
@@ -10,6 +10,17 @@ use crate::models::Tool;
 use super::Database;
 use super::tools::tool_from_row;
 
+/// Labels are case-insensitive, except the bundle name in `bundle:<name>`:
+/// bundle names are case-sensitive, so "Dev" and "dev" keep separate labels
+fn normalize_label(label: &str) -> String {
+    match label.split_once(':') {
+        Some((prefix, bundle)) if prefix.eq_ignore_ascii_case("bundle") => {
+            format!("bundle:{}", bundle)
+        }
+        _ => label.to_lowercase(),
+    }
+}
+
 impl Database {
     // ==================== Label Operations ====================
 
@@ -30,7 +41,7 @@ impl Database {
         for label in labels {
             tx.execute(
                 "INSERT OR IGNORE INTO tool_labels (tool_id, label) VALUES (?1, ?2)",
-                params![tool_id, label.to_lowercase()],
+                params![tool_id, normalize_label(label)],
             )?;
         }
         tx.commit()?;
@@ -81,18 +92,28 @@ impl Database {
         let mut stmt = self.conn.prepare(
             "SELECT t.id, t.name, t.description, t.category, t.source, t.install_command,
                     t.binary_name, t.is_installed, t.is_favorite, t.notes,
-                    t.created_at, t.updated_at
+                    t.created_at, t.updated_at, t.license, t.installed_version, t.kind
              FROM tools t
              JOIN tool_labels tl ON t.id = tl.tool_id
              WHERE tl.label = ?1
              ORDER BY t.name",
         )?;
 
-        let tool_iter = stmt.query_map([label.to_lowercase()], tool_from_row)?;
+        let tool_iter = stmt.query_map([normalize_label(label)], tool_from_row)?;
 
         tool_iter.collect::<Result<Vec<_>, _>>().map_err(Into::into)
     }
 
+    /// Remove one label from a tool
+    pub fn remove_label(&self, tool_name: &str, label: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM tool_labels
+             WHERE tool_id = (SELECT id FROM tools WHERE name = ?1) AND label = ?2",
+            params![tool_name, normalize_label(label)],
+        )?;
+        Ok(rows > 0)
+    }
+
     /// Clear labels for a tool
     pub fn clear_labels(&self, tool_name: &str) -> Result<bool> {
         let rows = self.conn.execute(
@@ -0,0 +1,89 @@
+//! Versions recorded when a bundle was last applied
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+
+use super::Database;
+
+/// The exact version of one bundle member
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedVersion {
+    pub tool: String,
+    pub source: String,
+    pub version: String,
+}
+
+/// A bundle's lock: the versions to reproduce with `bundle install --locked`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleLock {
+    pub versions: Vec<LockedVersion>,
+    pub locked_at: String,
+}
+
+impl BundleLock {
+    /// Locked version of a tool
+    pub fn get(&self, tool: &str) -> Option<&LockedVersion> {
+        self.versions.iter().find(|v| v.tool == tool)
+    }
+}
+
+impl Database {
+    // ==================== Bundle Lock Operations ====================
+
+    /// Replace a bundle's lock (returns false if the bundle doesn't exist)
+    ///
+    /// `locked_at` is set to the current time.
+    pub fn set_bundle_lock(&self, bundle_name: &str, versions: &[LockedVersion]) -> Result<bool> {
+        let Some(bundle_id) = self.bundle_id(bundle_name)? else {
+            return Ok(false);
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM bundle_locks WHERE bundle_id = ?1", [bundle_id])?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO bundle_locks
+                 (bundle_id, tool_name, source, version, locked_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            let now = Utc::now().to_rfc3339();
+            for v in versions {
+                stmt.execute(params![bundle_id, v.tool, v.source, v.version, now])?;
+            }
+        }
+        tx.commit()?;
+        Ok(true)
+    }
+
+    /// Get a bundle's lock, if it has ever been recorded
+    pub fn get_bundle_lock(&self, bundle_name: &str) -> Result<Option<BundleLock>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT l.tool_name, l.source, l.version, l.locked_at
+             FROM bundle_locks l
+             JOIN bundles b ON l.bundle_id = b.id
+             WHERE b.name = ?1
+             ORDER BY l.tool_name",
+        )?;
+        let rows = stmt
+            .query_map([bundle_name], |row| {
+                Ok((
+                    LockedVersion {
+                        tool: row.get(0)?,
+                        source: row.get(1)?,
+                        version: row.get(2)?,
+                    },
+                    row.get::<_, String>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let Some(locked_at) = rows.iter().map(|(_, at)| at.clone()).max() else {
+            return Ok(None);
+        };
+        Ok(Some(BundleLock {
+            versions: rows.into_iter().map(|(v, _)| v).collect(),
+            locked_at,
+        }))
+    }
+}
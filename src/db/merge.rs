@@ -0,0 +1,403 @@
+//! Merging another machine's database into this one, or reading it to
+//! compare against
+//!
+//! The other database is copied and brought up to the current schema, then
+//! attached as `remote`. Rows are matched by tool and bundle name, since ids
+//! differ between machines.
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use tempfile::NamedTempFile;
+
+use super::Database;
+use super::schema;
+use crate::models::Tool;
+
+/// Tool ids on this machine for the other machine's rows
+const TOOL_MAP: &str = "JOIN remote.tools rt ON rt.id = r.tool_id
+     JOIN main.tools t ON t.name = rt.name";
+
+/// Which side wins when a tool, bundle or cached entry exists on both
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep what this machine has
+    #[default]
+    PreferLocal,
+    /// Take the other machine's version
+    PreferRemote,
+    /// Take whichever was updated last
+    Newest,
+}
+
+impl From<&str> for MergeStrategy {
+    fn from(s: &str) -> Self {
+        match s {
+            "prefer-remote" => Self::PreferRemote,
+            "newest" => Self::Newest,
+            _ => Self::PreferLocal,
+        }
+    }
+}
+
+impl MergeStrategy {
+    /// SQL condition for replacing a local row, given both rows' timestamps
+    fn replaces(self, local: &str, remote: &str) -> String {
+        match self {
+            Self::PreferLocal => "0".to_string(),
+            Self::PreferRemote => "1".to_string(),
+            Self::Newest => format!("{remote} > {local}"),
+        }
+    }
+}
+
+/// What a merge changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// Tools that were only on the other machine
+    pub tools_added: Vec<String>,
+    /// Tools on both machines that took the other machine's details
+    pub tools_updated: usize,
+    pub bundles_added: usize,
+    /// Bundles on both machines whose members were replaced or combined
+    pub bundles_updated: usize,
+    pub labels_added: usize,
+    /// Tools whose usage counts were added in
+    pub usage_merged: usize,
+    /// Usage was left out because this database was merged before
+    pub usage_skipped: bool,
+    /// Forge metadata and READMEs taken from the other machine
+    pub github_merged: usize,
+}
+
+impl Database {
+    // ==================== Merge Operations ====================
+
+    /// Merge tools, bundles, labels, pins, usage and forge caches from
+    /// another hoards database
+    ///
+    /// The file itself is never written to. Usage counts are added together,
+    /// once per database: merging a file with the same contents again skips
+    /// its usage. With `dry_run`, the changes are counted and rolled back.
+    pub fn merge_from(
+        &self,
+        path: &Path,
+        strategy: MergeStrategy,
+        dry_run: bool,
+    ) -> Result<MergeReport> {
+        // Work on a migrated copy so older databases line up with this schema
+        let copy = private_copy(path)?;
+        let hash = format!("{:x}", Sha256::digest(std::fs::read(copy.path())?));
+        Connection::open(copy.path())
+            .context("Not a hoards database")
+            .and_then(|conn| schema::init_schema(&conn))?;
+        self.merge_attached(copy.path(), &hash, strategy, dry_run)
+    }
+
+    /// Tools tracked in another hoards database
+    ///
+    /// Like [`Database::merge_from`], this reads a migrated copy and never
+    /// writes to the file itself.
+    pub fn read_tools_from(path: &Path) -> Result<Vec<Tool>> {
+        let copy = private_copy(path)?;
+        let conn = Connection::open(copy.path()).context("Not a hoards database")?;
+        schema::init_schema(&conn)?;
+        Database { conn }.get_all_tools()
+    }
+
+    fn merge_attached(
+        &self,
+        copy: &Path,
+        hash: &str,
+        strategy: MergeStrategy,
+        dry_run: bool,
+    ) -> Result<MergeReport> {
+        self.conn
+            .execute("ATTACH DATABASE ?1 AS remote", [copy.to_string_lossy()])?;
+        let result = self.merge_tables(hash, strategy, dry_run);
+        self.conn.execute("DETACH DATABASE remote", [])?;
+        result
+    }
+
+    fn merge_tables(
+        &self,
+        hash: &str,
+        strategy: MergeStrategy,
+        dry_run: bool,
+    ) -> Result<MergeReport> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut report = MergeReport {
+            usage_skipped: tx.query_row(
+                "SELECT EXISTS (SELECT 1 FROM main.merged_databases WHERE hash = ?1)",
+                [hash],
+                |row| row.get(0),
+            )?,
+            ..Default::default()
+        };
+
+        // Tools: details of shared ones first, then the new ones. Installed
+        // state belongs to each machine and is left alone.
+        report.tools_updated = tx.execute(
+            &format!(
+                "UPDATE main.tools AS l SET
+                     description = r.description, category = r.category, source = r.source,
+                     install_command = r.install_command, binary_name = r.binary_name,
+                     is_favorite = r.is_favorite, notes = r.notes, license = r.license,
+                     updated_at = r.updated_at
+                 FROM remote.tools r
+                 WHERE r.name = l.name AND {}
+                   AND (l.description IS NOT r.description OR l.category IS NOT r.category
+                        OR l.source IS NOT r.source OR l.install_command IS NOT r.install_command
+                        OR l.binary_name IS NOT r.binary_name OR l.is_favorite IS NOT r.is_favorite
+                        OR l.notes IS NOT r.notes OR l.license IS NOT r.license)",
+                strategy.replaces("l.updated_at", "r.updated_at")
+            ),
+            [],
+        )?;
+        report.tools_added = {
+            let mut stmt = tx.prepare(
+                "SELECT name FROM remote.tools
+                 WHERE name NOT IN (SELECT name FROM main.tools) ORDER BY name",
+            )?;
+            stmt.query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?
+        };
+        tx.execute(
+            "INSERT INTO main.tools (name, description, category, source, install_command,
+                 binary_name, is_installed, is_favorite, notes, created_at, updated_at, license)
+             SELECT name, description, category, source, install_command, binary_name, 0,
+                 is_favorite, notes, created_at, updated_at, license
+             FROM remote.tools WHERE name NOT IN (SELECT name FROM main.tools)",
+            [],
+        )?;
+
+        report.labels_added = tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.tool_labels (tool_id, label)
+                 SELECT t.id, r.label FROM remote.tool_labels r {TOOL_MAP}"
+            ),
+            [],
+        )?;
+        tx.execute(
+            &format!(
+                "INSERT INTO main.tool_pins (tool_id, version, pinned_at)
+                 SELECT t.id, r.version, r.pinned_at FROM remote.tool_pins r {TOOL_MAP} WHERE true
+                 ON CONFLICT(tool_id) DO UPDATE SET
+                     version = excluded.version, pinned_at = excluded.pinned_at
+                 WHERE {}",
+                strategy.replaces("tool_pins.pinned_at", "excluded.pinned_at")
+            ),
+            [],
+        )?;
+
+        self.merge_bundles(&tx, strategy, &mut report)?;
+
+        // Forge caches
+        report.github_merged = tx.execute(
+            &format!(
+                "INSERT INTO main.tool_github (tool_id, repo_owner, repo_name, description, stars,
+                     language, homepage, updated_at, host, provider, pushed_at, open_issues,
+                     archived, contributors)
+                 SELECT t.id, r.repo_owner, r.repo_name, r.description, r.stars, r.language,
+                     r.homepage, r.updated_at, r.host, r.provider, r.pushed_at, r.open_issues,
+                     r.archived, r.contributors
+                 FROM remote.tool_github r {TOOL_MAP} WHERE true
+                 ON CONFLICT(tool_id) DO UPDATE SET
+                     repo_owner = excluded.repo_owner, repo_name = excluded.repo_name,
+                     description = excluded.description, stars = excluded.stars,
+                     language = excluded.language, homepage = excluded.homepage,
+                     updated_at = excluded.updated_at, host = excluded.host,
+                     provider = excluded.provider, pushed_at = excluded.pushed_at,
+                     open_issues = excluded.open_issues, archived = excluded.archived,
+                     contributors = excluded.contributors
+                 WHERE {}",
+                strategy.replaces("tool_github.updated_at", "excluded.updated_at")
+            ),
+            [],
+        )?;
+        tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.tool_topics (tool_id, topic)
+                 SELECT t.id, r.topic FROM remote.tool_topics r {TOOL_MAP}"
+            ),
+            [],
+        )?;
+        report.github_merged += tx.execute(
+            &format!(
+                "INSERT INTO main.readmes (host, owner, repo, content, etag, fetched_at)
+                 SELECT host, owner, repo, content, etag, fetched_at FROM remote.readmes WHERE true
+                 ON CONFLICT(host, owner, repo) DO UPDATE SET
+                     content = excluded.content, etag = excluded.etag,
+                     fetched_at = excluded.fetched_at
+                 WHERE {}",
+                strategy.replaces("readmes.fetched_at", "excluded.fetched_at")
+            ),
+            [],
+        )?;
+
+        if !report.usage_skipped {
+            report.usage_merged = Self::merge_usage(&tx)?;
+            tx.execute(
+                "INSERT INTO main.merged_databases (hash, merged_at) VALUES (?1, ?2)",
+                rusqlite::params![hash, chrono::Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        if !dry_run {
+            tx.commit()?;
+        }
+        Ok(report)
+    }
+
+    /// Usage happened on both machines, so it adds up. Returns how many
+    /// tools' counts were added in.
+    fn merge_usage(tx: &rusqlite::Transaction) -> Result<usize> {
+        let merged = tx.execute(
+            &format!(
+                "INSERT INTO main.tool_usage (tool_id, use_count, last_used, first_seen, updated_at)
+                 SELECT t.id, r.use_count, r.last_used, r.first_seen, r.updated_at
+                 FROM remote.tool_usage r {TOOL_MAP} WHERE true
+                 ON CONFLICT(tool_id) DO UPDATE SET
+                     use_count = use_count + excluded.use_count,
+                     last_used = CASE WHEN excluded.last_used > COALESCE(last_used, '')
+                                 THEN excluded.last_used ELSE last_used END,
+                     first_seen = MIN(first_seen, excluded.first_seen)"
+            ),
+            [],
+        )?;
+        for (table, key) in [
+            ("usage_daily", "date"),
+            ("usage_monthly", "month"),
+            ("usage_patterns", "pattern"),
+        ] {
+            tx.execute(
+                &format!(
+                    "INSERT INTO main.{table} (tool_id, {key}, count)
+                     SELECT t.id, r.{key}, r.count FROM remote.{table} r {TOOL_MAP} WHERE true
+                     ON CONFLICT(tool_id, {key}) DO UPDATE SET count = count + excluded.count"
+                ),
+                [],
+            )?;
+        }
+        tx.execute(
+            &format!(
+                "INSERT INTO main.usage_rhythm (tool_id, weekday, hour, count)
+                 SELECT t.id, r.weekday, r.hour, r.count FROM remote.usage_rhythm r {TOOL_MAP}
+                 WHERE true
+                 ON CONFLICT(tool_id, weekday, hour) DO UPDATE SET count = count + excluded.count"
+            ),
+            [],
+        )?;
+        tx.execute(
+            &format!(
+                "INSERT INTO main.usage_projects (tool_id, project, use_count, last_used)
+                 SELECT t.id, r.project, r.use_count, r.last_used
+                 FROM remote.usage_projects r {TOOL_MAP} WHERE true
+                 ON CONFLICT(tool_id, project) DO UPDATE SET
+                     use_count = use_count + excluded.use_count,
+                     last_used = CASE WHEN excluded.last_used > COALESCE(last_used, '')
+                                 THEN excluded.last_used ELSE last_used END"
+            ),
+            [],
+        )?;
+        Ok(merged)
+    }
+
+    /// Bundles only on the other machine are added. Shared bundles keep
+    /// their local members, take the remote ones, or (as bundles have no
+    /// edit time) get the members of both with `Newest`.
+    fn merge_bundles(
+        &self,
+        tx: &rusqlite::Transaction,
+        strategy: MergeStrategy,
+        report: &mut MergeReport,
+    ) -> Result<()> {
+        let shared: Vec<String> = {
+            let mut stmt = tx.prepare(
+                "SELECT rb.name FROM remote.bundles rb JOIN main.bundles b ON b.name = rb.name",
+            )?;
+            stmt.query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?
+        };
+
+        let shared_json = serde_json::to_string(&shared)?;
+        match strategy {
+            MergeStrategy::PreferLocal => {}
+            MergeStrategy::PreferRemote => {
+                tx.execute(
+                    "UPDATE main.bundles AS b SET description = rb.description
+                     FROM remote.bundles rb WHERE rb.name = b.name",
+                    [],
+                )?;
+                for table in ["bundle_tools", "bundle_includes"] {
+                    tx.execute(
+                        &format!(
+                            "DELETE FROM main.{table} WHERE bundle_id IN
+                             (SELECT b.id FROM main.bundles b
+                              JOIN remote.bundles rb ON rb.name = b.name)"
+                        ),
+                        [],
+                    )?;
+                }
+                report.bundles_updated = shared.len();
+            }
+            MergeStrategy::Newest => {
+                report.bundles_updated = tx.query_row(
+                    "SELECT COUNT(*) FROM remote.bundles rb
+                     JOIN main.bundles b ON b.name = rb.name
+                     WHERE EXISTS (SELECT 1 FROM remote.bundle_tools r
+                                   WHERE r.bundle_id = rb.id AND r.tool_name NOT IN
+                                   (SELECT tool_name FROM main.bundle_tools WHERE bundle_id = b.id))",
+                    [],
+                    |row| row.get(0),
+                )?;
+            }
+        }
+
+        report.bundles_added = tx.execute(
+            "INSERT INTO main.bundles (name, description, created_at)
+             SELECT name, description, created_at FROM remote.bundles
+             WHERE name NOT IN (SELECT name FROM main.bundles)",
+            [],
+        )?;
+
+        // Members and includes of shared bundles are skipped when local wins
+        let scope = "NOT (?2 AND rb.name IN (SELECT value FROM json_each(?1)))";
+        let keep_local = strategy == MergeStrategy::PreferLocal;
+        tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.bundle_tools (bundle_id, tool_name, condition)
+                 SELECT b.id, r.tool_name, r.condition FROM remote.bundle_tools r
+                 JOIN remote.bundles rb ON rb.id = r.bundle_id
+                 JOIN main.bundles b ON b.name = rb.name
+                 WHERE {scope}"
+            ),
+            rusqlite::params![shared_json, keep_local],
+        )?;
+        tx.execute(
+            &format!(
+                "INSERT OR IGNORE INTO main.bundle_includes (bundle_id, included_id)
+                 SELECT b.id, bi.id FROM remote.bundle_includes r
+                 JOIN remote.bundles rb ON rb.id = r.bundle_id
+                 JOIN remote.bundles rbi ON rbi.id = r.included_id
+                 JOIN main.bundles b ON b.name = rb.name
+                 JOIN main.bundles bi ON bi.name = rbi.name
+                 WHERE {scope}"
+            ),
+            rusqlite::params![shared_json, keep_local],
+        )?;
+        Ok(())
+    }
+}
+
+/// Copy a database into a temp file only this user can open
+fn private_copy(path: &Path) -> Result<NamedTempFile> {
+    let copy = tempfile::Builder::new()
+        .prefix("hoards-merge-")
+        .suffix(".db")
+        .tempfile()?;
+    std::fs::copy(path, copy.path())
+        .with_context(|| format!("Cannot read database '{}'", path.display()))?;
+    Ok(copy)
+}
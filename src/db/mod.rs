@@ -6,28 +6,81 @@
 //! - `bundles`: Bundle operations
 //! - `configs`: Config file tracking
 //! - `labels`: Tool labeling operations
-//! - `github`: GitHub metadata storage
+//! - `github`: Repository metadata from GitHub and other forges
 //! - `usage`: Usage tracking operations
 //! - `extractions`: AI extraction cache
-
+//! - `embeddings`: Tool embeddings for similar-tool search
+//! - `relations`: Relationships between tools (e.g. replacements)
+//! - `ai_usage`: AI invocation accounting
+//! - `checkpoints`: Progress of resumable batch jobs
+//! - `watches`: Watched upstream releases
+//! - `readmes`: Cached repository READMEs
+//! - `installs`: Binaries installed from release assets
+//! - `github_queue`: Queued GitHub work and requests shared between processes
+//! - `locks`: Versions recorded when a bundle was last applied
+//! - `adoption`: Whether newly installed tools get used
+//! - `pins`: Tools held at a version
+//! - `merge`: Merging another machine's database
+//! - `aliases`: Shell aliases tied to tools
+//! - `scan_cache`: Per-source listings for incremental scans
+//! - `services`: User services and the tools they run
+//! - `sizes`: Measured disk footprint of tools
+//! - `timeline`: When tools were installed and removed
+//! - `health`: Per-tool health scores
+
+mod adoption;
+mod ai_usage;
+mod aliases;
 mod bundles;
+mod checkpoints;
 mod configs;
+mod embeddings;
 mod extractions;
 mod github;
+mod github_queue;
+mod health;
+mod installs;
 mod labels;
+mod locks;
+mod merge;
+mod pins;
+mod readmes;
+mod relations;
+mod scan_cache;
 mod schema;
+mod services;
+mod sizes;
+mod timeline;
 mod tools;
 mod usage;
+mod watches;
 
 // Re-export commonly used types
+pub use adoption::{ADOPTION_WINDOW_DAYS, Adoption, AdoptionStatus};
+pub use ai_usage::{AiCall, AiUsageSummary};
+pub use aliases::ToolAlias;
+pub use configs::{ConfigBackup, ConfigVariant};
 pub use extractions::CachedExtraction;
-pub use github::{GitHubInfo, GitHubInfoInput};
-pub use usage::ToolUsage;
+pub use github::{ABANDONED_AFTER_DAYS, GitHubInfo, GitHubInfoInput};
+pub use github_queue::{QueuedJob, RequestClaim};
+pub use installs::ReleaseInstall;
+pub use locks::{BundleLock, LockedVersion};
+pub use merge::{MergeReport, MergeStrategy};
+pub use readmes::CachedReadme;
+pub use relations::{RelationKind, ToolRelation};
+pub use scan_cache::ScanCacheEntry;
+pub use services::UserService;
+pub use timeline::{
+    BURST_MIN_INSTALLS, TimelineEvent, TimelineEventKind, TimelineMonth, monthly_timeline,
+};
+pub use usage::{ProjectUsage, ToolUsage, UsageBatch, UsageRhythm};
+pub use watches::{WatchedRelease, WatchedTool};
 
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use rusqlite::Connection;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Database wrapper for hoards
 pub struct Database {
@@ -61,6 +114,13 @@ impl Database {
         Ok(db)
     }
 
+    /// Wait up to `timeout` for another connection's lock instead of
+    /// failing straight away
+    pub fn set_busy_timeout(&self, timeout: Duration) -> Result<()> {
+        self.conn.busy_timeout(timeout)?;
+        Ok(())
+    }
+
     /// Get the database file path
     pub fn db_path() -> Result<PathBuf> {
         let proj_dirs = ProjectDirs::from("dev", "hoards", "hoards")
@@ -187,6 +247,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_set_tool_notes() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("wg"))?;
+
+        assert!(db.set_tool_notes("wg", Some("[2026-01-01 10:00] needs resolvconf"))?);
+        let fetched = db.get_tool_by_name("wg")?.unwrap();
+        assert_eq!(
+            fetched.notes.as_deref(),
+            Some("[2026-01-01 10:00] needs resolvconf")
+        );
+
+        assert!(db.set_tool_notes("wg", None)?);
+        assert!(db.get_tool_by_name("wg")?.unwrap().notes.is_none());
+        assert!(!db.set_tool_notes("missing", Some("x"))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_adoption_window() -> Result<()> {
+        use chrono::{Duration, Utc};
+
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("fd"))?;
+        db.insert_tool(&Tool::new("bat"))?;
+        db.insert_tool(&Tool::new("preinstalled").installed())?;
+        db.record_usage("fd", 3, None)?; // used before this install
+
+        db.set_tool_installed("fd", true)?;
+        db.set_tool_installed("bat", true)?;
+        db.set_tool_installed("preinstalled", true)?; // no transition
+
+        let now = Utc::now();
+        let adoptions = db.list_adoptions(90)?;
+        assert_eq!(adoptions.len(), 2);
+        assert!(adoptions.iter().all(|a| a.uses == 0));
+        assert!(
+            adoptions
+                .iter()
+                .all(|a| a.status(now) == AdoptionStatus::Pending)
+        );
+
+        db.record_usage("fd", 1, None)?;
+        let later = now + Duration::days(ADOPTION_WINDOW_DAYS + 1);
+        let status = |tool: &str| -> Result<AdoptionStatus> {
+            let a = db.list_adoptions(90)?.into_iter().find(|a| a.tool == tool);
+            Ok(a.unwrap().status(later))
+        };
+        assert_eq!(status("fd")?, AdoptionStatus::Adopted);
+        assert_eq!(status("bat")?, AdoptionStatus::Forgotten);
+
+        assert!(db.keep_adoption("bat")?);
+        assert_eq!(status("bat")?, AdoptionStatus::Kept);
+        assert!(!db.keep_adoption("preinstalled")?);
+
+        // Uninstalling closes the window
+        db.set_tool_installed("bat", false)?;
+        assert_eq!(db.list_adoptions(90)?.len(), 1);
+
+        Ok(())
+    }
+
     #[test]
     fn test_list_tools_filter_installed() -> Result<()> {
         let db = Database::open_in_memory()?;
@@ -309,6 +432,108 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_nested_bundles() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.create_bundle(&Bundle::new("rust-dev", vec!["bacon".to_string()]))?;
+        db.create_bundle(&Bundle::new("media", vec!["ffmpeg".to_string()]))?;
+        db.create_bundle(&Bundle::new("workstation", vec!["fd".to_string()]))?;
+
+        let includes = vec!["rust-dev".to_string(), "media".to_string()];
+        assert!(db.include_bundles("workstation", &includes)?);
+        assert!(!db.include_bundles("missing", &includes)?);
+        assert!(
+            db.include_bundles("workstation", &["nope".to_string()])
+                .is_err()
+        );
+
+        // Direct and indirect cycles are refused
+        assert!(db.include_bundles("media", &["media".to_string()]).is_err());
+        assert!(
+            db.include_bundles("media", &["workstation".to_string()])
+                .is_err()
+        );
+
+        let tree = db.get_bundle_tree("workstation")?.unwrap();
+        assert_eq!(tree.bundle.includes, vec!["media", "rust-dev"]);
+        assert_eq!(tree.children.len(), 2);
+        let flat = db.get_flattened_bundle("workstation")?.unwrap();
+        assert_eq!(flat.tools, vec!["fd", "ffmpeg", "bacon"]);
+
+        let listed = db.list_bundles()?;
+        let workstation = listed.iter().find(|b| b.name == "workstation").unwrap();
+        assert_eq!(workstation.includes.len(), 2);
+
+        // Deleting an included bundle drops it from its parents
+        db.delete_bundle("media")?;
+        assert_eq!(
+            db.get_bundle("workstation")?.unwrap().includes,
+            vec!["rust-dev"]
+        );
+        assert!(db.remove_bundle_includes("workstation", &["rust-dev".to_string()])?);
+        assert!(db.get_bundle("workstation")?.unwrap().includes.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle_locks() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.create_bundle(&Bundle::new("search", vec!["ripgrep".to_string()]))?;
+        assert!(db.get_bundle_lock("search")?.is_none());
+
+        let locked = |tool: &str, version: &str| LockedVersion {
+            tool: tool.to_string(),
+            source: "cargo".to_string(),
+            version: version.to_string(),
+        };
+        assert!(db.set_bundle_lock("search", &[locked("ripgrep", "14.1.0")])?);
+        assert!(!db.set_bundle_lock("missing", &[])?);
+
+        // Updating replaces the whole lock
+        db.set_bundle_lock(
+            "search",
+            &[locked("fd", "10.2.0"), locked("ripgrep", "14.1.1")],
+        )?;
+        let lock = db.get_bundle_lock("search")?.unwrap();
+        assert_eq!(lock.versions.len(), 2);
+        assert_eq!(lock.get("ripgrep").unwrap().version, "14.1.1");
+
+        // Deleting the bundle drops its lock
+        db.delete_bundle("search")?;
+        assert!(db.get_bundle_lock("search")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_bundle_conditions() -> Result<()> {
+        use crate::models::MemberCondition;
+
+        let db = Database::open_in_memory()?;
+        let mut bundle = Bundle::new("shell", vec!["fd".to_string(), "mas".to_string()]);
+        bundle
+            .conditions
+            .insert("mas".to_string(), MemberCondition::parse("os=macos")?);
+        db.create_bundle(&bundle)?;
+
+        let fetched = db.get_bundle("shell")?.unwrap();
+        assert_eq!(fetched.tools, vec!["fd", "mas"]);
+        assert_eq!(
+            fetched.condition("mas").map(|c| c.to_string()).as_deref(),
+            Some("os=macos")
+        );
+        assert!(fetched.condition("fd").is_none());
+
+        let apt_only = MemberCondition::parse("os=linux,host=work-*")?;
+        assert!(db.set_bundle_condition("shell", "fd", Some(&apt_only))?);
+        assert!(db.set_bundle_condition("shell", "mas", None)?);
+        assert!(!db.set_bundle_condition("shell", "bat", None)?);
+
+        let listed = db.list_bundles()?;
+        assert_eq!(listed[0].condition("fd"), Some(&apt_only));
+        assert!(listed[0].condition("mas").is_none());
+        Ok(())
+    }
+
     // ==================== Labels Tests ====================
 
     #[test]
@@ -337,6 +562,11 @@ mod tests {
         assert_eq!(labels.len(), 1);
         assert_eq!(labels[0], "cli");
 
+        // Remove a single label
+        assert!(db.remove_label("test", "CLI")?);
+        assert!(!db.remove_label("test", "cli")?);
+        assert!(db.get_labels("test")?.is_empty());
+
         Ok(())
     }
 
@@ -358,9 +588,273 @@ mod tests {
         let search_tools = db.list_tools_by_label("search")?;
         assert_eq!(search_tools.len(), 1);
 
+        // Bundle names keep their case, so "Dev" and "dev" don't share tools
+        db.add_labels("rg", &["bundle:Dev".to_string()])?;
+        db.add_labels("fd", &["Bundle:dev".to_string()])?;
+        assert_eq!(db.list_tools_by_label("bundle:Dev")?[0].name, "rg");
+        assert_eq!(db.list_tools_by_label("bundle:dev")?[0].name, "fd");
+        assert!(db.remove_label("rg", "BUNDLE:Dev")?);
+        assert!(db.list_tools_by_label("bundle:Dev")?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_from() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("other.db");
+        {
+            let remote = Database {
+                conn: Connection::open(&path)?,
+            };
+            schema::init_schema(&remote.conn)?;
+            let mut rg = Tool::new("rg").with_description("remote").installed();
+            rg.updated_at += chrono::Duration::hours(1);
+            remote.insert_tool(&rg)?;
+            remote.insert_tool(&Tool::new("fd").installed())?;
+            remote.add_labels("fd", &["rust".to_string()])?;
+            remote.record_usage("rg", 5, None)?;
+            remote.create_bundle(&Bundle::new("cli", vec!["rg".into(), "fd".into()]))?;
+        }
+
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("rg").with_description("local"))?;
+        db.record_usage("rg", 3, None)?;
+        db.create_bundle(&Bundle::new("cli", vec!["rg".into()]))?;
+
+        // A dry run changes nothing
+        let report = db.merge_from(&path, MergeStrategy::Newest, true)?;
+        assert_eq!(report.tools_added, vec!["fd".to_string()]);
+        assert_eq!(report.tools_updated, 1);
+        assert!(db.get_tool_by_name("fd")?.is_none());
+
+        let report = db.merge_from(&path, MergeStrategy::PreferLocal, false)?;
+        assert!(!report.usage_skipped);
+        assert_eq!(report.tools_updated, 0);
+        assert_eq!(report.labels_added, 1);
+        let rg = db.get_tool_by_name("rg")?.unwrap();
+        assert_eq!(rg.description.as_deref(), Some("local"));
+        assert_eq!(db.get_usage("rg")?.unwrap().use_count, 8);
+        assert_eq!(db.get_bundle("cli")?.unwrap().tools, vec!["rg"]);
+        // Installed state stays with each machine
+        assert!(!db.get_tool_by_name("fd")?.unwrap().is_installed);
+
+        // Merging the same database again doesn't count its usage twice
+        let report = db.merge_from(&path, MergeStrategy::Newest, false)?;
+        assert!(report.usage_skipped);
+        assert_eq!(db.get_usage("rg")?.unwrap().use_count, 8);
+        assert_eq!(report.tools_updated, 1);
+        assert_eq!(report.bundles_updated, 1);
+        let rg = db.get_tool_by_name("rg")?.unwrap();
+        assert_eq!(rg.description.as_deref(), Some("remote"));
+        assert!(!rg.is_installed);
+        assert_eq!(db.get_bundle("cli")?.unwrap().tools.len(), 2);
+
         Ok(())
     }
 
+    #[test]
+    fn test_pins() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("node"))?;
+        db.insert_tool(&Tool::new("jq"))?;
+
+        assert!(db.pin_tool("node", Some("20.11.0"))?);
+        assert!(db.pin_tool("jq", None)?);
+        assert!(!db.pin_tool("missing", Some("1.0"))?);
+
+        // Pinning again replaces the version
+        db.pin_tool("node", Some("22.1.0"))?;
+        let pins = db.get_pins()?;
+        assert_eq!(pins.len(), 2);
+        assert_eq!(pins["node"].as_deref(), Some("22.1.0"));
+        assert_eq!(pins["jq"], None);
+
+        assert!(db.unpin_tool("jq")?);
+        assert!(!db.unpin_tool("jq")?);
+        assert_eq!(db.get_pins()?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_aliases() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("bat").installed())?;
+        db.insert_tool(&Tool::new("eza"))?;
+
+        assert!(db.set_alias("cat", "bat", Some("bat"))?);
+        assert!(db.set_alias("ls", "eza -l", Some("eza"))?);
+        assert!(db.set_alias("gs", "git status", None)?);
+        assert!(!db.set_alias("x", "missing", Some("missing"))?);
+
+        let aliases = db.list_aliases()?;
+        let names: Vec<_> = aliases.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["cat", "gs", "ls"]);
+        assert!(aliases[0].installed);
+        assert!(aliases[1].installed, "aliases without a tool always apply");
+        assert!(!aliases[2].installed);
+
+        // Replacing keeps one alias per name
+        db.set_alias("cat", "bat --paging=never", Some("bat"))?;
+        let bat = db.get_tool_aliases("bat")?;
+        assert_eq!(bat.len(), 1);
+        assert_eq!(bat[0].command, "bat --paging=never");
+
+        // Removing the tool keeps the alias, untied
+        db.delete_tool("eza")?;
+        assert_eq!(db.list_aliases()?[2].tool, None);
+        assert!(db.remove_alias("ls")?);
+        assert!(!db.remove_alias("ls")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_services() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("atuin").installed())?;
+
+        let service = |name: &str, binary: &str, tool: Option<&str>| UserService {
+            name: name.to_string(),
+            manager: "systemd".to_string(),
+            path: format!("/home/me/.config/systemd/user/{}", name),
+            binary: binary.to_string(),
+            tool: tool.map(str::to_string),
+        };
+        db.replace_services(&[
+            service("atuin.service", "/usr/bin/atuin", Some("atuin")),
+            service("backup.service", "/opt/backup", None),
+        ])?;
+        assert_eq!(db.list_services()?.len(), 2);
+        let atuin = db.get_tool_services("atuin")?;
+        assert_eq!(atuin.len(), 1);
+        assert_eq!(atuin[0].name, "atuin.service");
+        assert_eq!(
+            atuin[0].disable_command(),
+            "systemctl --user disable --now atuin.service"
+        );
+
+        // A rescan replaces what was recorded
+        db.replace_services(&[service("backup.service", "/opt/backup", None)])?;
+        assert!(db.get_tool_services("atuin")?.is_empty());
+        assert_eq!(db.list_services()?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tool_sizes() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+
+        assert!(db.set_tool_size("ripgrep", 4_500_000)?);
+        assert!(!db.set_tool_size("missing", 10)?);
+        // A new measurement replaces the last
+        db.set_tool_size("ripgrep", 5_000_000)?;
+        let sizes = db.get_tool_sizes()?;
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(sizes["ripgrep"], 5_000_000);
+
+        db.delete_tool("ripgrep")?;
+        assert!(db.get_tool_sizes()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tool_health() -> Result<()> {
+        use crate::health::ToolHealth;
+
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+        db.insert_tool(&Tool::new("fd").installed())?;
+
+        let health = ToolHealth::from_signals(Some(80), None, Some(40), None).unwrap();
+        let stored = db.replace_tool_health(&[
+            ("ripgrep".to_string(), health),
+            ("missing".to_string(), health),
+        ])?;
+        assert_eq!(stored, 1);
+        assert_eq!(db.get_tool_health()?["ripgrep"], health);
+
+        // Replacing drops scores that weren't recomputed
+        db.replace_tool_health(&[("fd".to_string(), health)])?;
+        let scores = db.get_tool_health()?;
+        assert_eq!(scores.len(), 1);
+        assert!(scores.contains_key("fd"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeline_events() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+        db.insert_tool(&Tool::new("fd"))?;
+        db.insert_tool(&Tool::new("untouched"))?;
+
+        db.set_tool_installed("fd", true)?;
+        db.set_tool_installed("ripgrep", false)?;
+
+        let events = db.get_timeline_events()?;
+        let summary: Vec<(&str, TimelineEventKind)> =
+            events.iter().map(|e| (e.tool.as_str(), e.kind)).collect();
+        // ripgrep predates event recording, so its install is when it was added
+        assert_eq!(
+            summary,
+            vec![
+                ("ripgrep", TimelineEventKind::Install),
+                ("fd", TimelineEventKind::Install),
+                ("ripgrep", TimelineEventKind::Remove),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_monthly_timeline() {
+        use chrono::{TimeZone, Utc};
+        use std::collections::HashMap;
+
+        let event = |tool: &str, kind, month| TimelineEvent {
+            tool: tool.to_string(),
+            kind,
+            at: Utc.with_ymd_and_hms(2026, month, 10, 12, 0, 0).unwrap(),
+        };
+        let mut events = vec![
+            event("ripgrep", TimelineEventKind::Install, 1),
+            event("fd", TimelineEventKind::Install, 1),
+            event("ranger", TimelineEventKind::Remove, 5),
+        ];
+        let file_managers = ["yazi", "lf", "nnn", "ranger", "broot", "xplr"];
+        events.extend(
+            file_managers
+                .iter()
+                .map(|t| event(t, TimelineEventKind::Install, 3)),
+        );
+        let mut categories: HashMap<String, String> = file_managers
+            .iter()
+            .map(|t| (t.to_string(), "files".to_string()))
+            .collect();
+        categories.insert("ripgrep".to_string(), "search".to_string());
+
+        let months = monthly_timeline(&events, &categories);
+        let names: Vec<&str> = months.iter().map(|m| m.month.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["2026-01", "2026-02", "2026-03", "2026-04", "2026-05"]
+        );
+        assert_eq!(months[0].installed, vec!["ripgrep", "fd"]);
+        assert_eq!(months[0].burst, None);
+        assert!(months[1].installed.is_empty());
+        assert_eq!(months[2].burst.as_deref(), Some("6 files tools"));
+        assert_eq!(months[4].removed, vec!["ranger"]);
+
+        assert!(monthly_timeline(&[], &categories).is_empty());
+    }
+
     // ==================== Usage Tests ====================
 
     #[test]
@@ -517,6 +1011,141 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_project_usage() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+        db.insert_tool(&Tool::new("just").installed())?;
+
+        assert!(db.record_project_usage("ripgrep", "/src/app", 3, Some("2026-01-01T00:00:00Z"))?);
+        assert!(db.record_project_usage("ripgrep", "/src/app", 2, None)?);
+        assert!(db.record_project_usage("just", "/src/app", 1, None)?);
+        assert!(db.record_project_usage("just", "/src/lib", 4, None)?);
+        assert!(!db.record_project_usage("nonexistent", "/src/app", 1, None)?);
+
+        let app = db.get_project_usage("/src/app")?;
+        assert_eq!(app.len(), 2);
+        assert_eq!(app[0].tool, "ripgrep");
+        assert_eq!(app[0].use_count, 5);
+        assert_eq!(app[0].last_used.as_deref(), Some("2026-01-01T00:00:00Z"));
+        assert_eq!(app[1].tool, "just");
+
+        assert_eq!(
+            db.get_usage_projects()?,
+            vec![("/src/app".to_string(), 6), ("/src/lib".to_string(), 4)]
+        );
+
+        db.clear_usage()?;
+        assert!(db.get_usage_projects()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_usage_rhythm() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+        db.insert_tool(&Tool::new("just").installed())?;
+
+        db.record_usage_rhythm("ripgrep", 0, 9, 3)?; // Monday 09:00
+        db.record_usage_rhythm("ripgrep", 0, 9, 2)?;
+        db.record_usage_rhythm("ripgrep", 5, 22, 1)?; // Saturday 22:00
+        db.record_usage_rhythm("just", 2, 14, 4)?;
+        assert!(!db.record_usage_rhythm("nonexistent", 0, 0, 1)?);
+
+        let rhythms = db.get_all_usage_rhythms()?;
+        let rg = &rhythms["ripgrep"];
+        assert_eq!(rg.counts[0][9], 5);
+        assert_eq!(rg.total(), 6);
+        assert_eq!(rg.peak_hour(), Some(9));
+        assert_eq!(rg.peak_weekday(), Some(0));
+        assert!((rg.weekend_share() - 1.0 / 6.0).abs() < 1e-9);
+
+        let mut all = UsageRhythm::default();
+        for rhythm in rhythms.values() {
+            all.merge(rhythm);
+        }
+        assert_eq!(all.total(), 10);
+        assert_eq!(all.by_hour()[14], 4);
+        assert_eq!(UsageRhythm::default().peak_hour(), None);
+
+        db.clear_usage()?;
+        assert!(db.get_all_usage_rhythms()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_usage_patterns() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("git").installed())?;
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+
+        db.record_usage_pattern("git", "commit", 5)?;
+        db.record_usage_pattern("git", "commit", 2)?;
+        db.record_usage_pattern("git", "status", 9)?;
+        db.record_usage_pattern("ripgrep", "-i", 1)?;
+        assert!(!db.record_usage_pattern("nonexistent", "x", 1)?);
+        for i in 0..25 {
+            db.record_usage_pattern("ripgrep", &format!("--flag{}", i), 2)?;
+        }
+
+        assert_eq!(
+            db.get_usage_patterns("git", 10)?,
+            vec![("status".to_string(), 9), ("commit".to_string(), 7)]
+        );
+
+        // Only the top 20 per tool survive, dropping the least used first
+        assert_eq!(db.prune_usage_patterns()?, 6);
+        let rg = db.get_usage_patterns("ripgrep", 100)?;
+        assert_eq!(rg.len(), 20);
+        assert!(rg.iter().all(|(p, _)| p != "-i"));
+
+        let all = db.get_all_usage_patterns(1)?;
+        assert_eq!(all["git"], vec![("status".to_string(), 9)]);
+        assert_eq!(all["ripgrep"].len(), 1);
+
+        db.clear_usage()?;
+        assert!(db.get_all_usage_patterns(5)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_usage_batch() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("git").installed())?;
+
+        let mut batch = UsageBatch::default();
+        batch.tools.insert("git".to_string(), (3, 1_700_000_000));
+        batch.projects.insert(
+            ("git".to_string(), "/src/app".to_string()),
+            (2, 1_700_000_000),
+        );
+        batch.rhythm.insert(("git".to_string(), 0, 9), 3);
+        batch
+            .patterns
+            .insert(("git".to_string(), "commit".to_string()), 3);
+        assert_eq!(db.record_usage_batch(&batch)?, 3);
+        assert_eq!(db.get_usage("git")?.unwrap().use_count, 3);
+        assert_eq!(db.get_project_usage("/src/app")?[0].use_count, 2);
+        assert_eq!(
+            db.get_usage_patterns("git", 10)?,
+            vec![("commit".to_string(), 3)]
+        );
+
+        // A write failing partway leaves every count as it was
+        db.conn.execute("DROP TABLE usage_patterns", [])?;
+        assert!(db.record_usage_batch(&batch).is_err());
+        assert_eq!(db.get_usage("git")?.unwrap().use_count, 3);
+        assert_eq!(db.get_project_usage("/src/app")?[0].use_count, 2);
+
+        Ok(())
+    }
+
     // ==================== Daily Usage Tests ====================
 
     #[test]
@@ -537,10 +1166,75 @@ mod tests {
         assert_eq!(daily[6], 8);
 
         // Previous days should be 0
-        for i in 0..6 {
-            assert_eq!(daily[i], 0);
+        for (i, count) in daily.iter().enumerate().take(6) {
+            assert_eq!(*count, 0, "day {}", i);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_daily_usage_series() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+        db.insert_tool(&Tool::new("fd").installed())?;
+        db.record_usage("ripgrep", 2, None)?;
+        db.record_usage("fd", 1, None)?;
+        db.conn.execute(
+            "INSERT INTO usage_daily (tool_id, date, count)
+             SELECT id, '2020-01-01', 7 FROM tools WHERE name = 'ripgrep'",
+            [],
+        )?;
+
+        let all = db.get_daily_usage_series(None)?;
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].0, "fd");
+        assert_eq!(all[1], ("ripgrep".to_string(), "2020-01-01".to_string(), 7));
+
+        let recent = db.get_daily_usage_series(Some("2021-01-01"))?;
+        assert_eq!(recent.len(), 2);
+        assert!(recent.iter().all(|(_, date, _)| date.as_str() > "2021"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rollup_daily_usage() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+        db.record_usage("ripgrep", 2, None)?; // today
+        for (date, count) in [("2020-01-03", 4), ("2020-01-20", 1), ("2020-02-01", 5)] {
+            db.conn.execute(
+                "INSERT INTO usage_daily (tool_id, date, count)
+                 SELECT id, ?1, ?2 FROM tools WHERE name = 'ripgrep'",
+                rusqlite::params![date, count],
+            )?;
         }
 
+        assert_eq!(db.count_daily_usage_before("2020-02-01")?, 2);
+        assert_eq!(db.rollup_daily_usage("2020-02-01")?, 2);
+        assert_eq!(db.get_daily_usage_series(None)?.len(), 2);
+
+        // A second rollup adds to the existing month
+        db.conn.execute(
+            "INSERT INTO usage_daily (tool_id, date, count)
+             SELECT id, '2020-01-31', 3 FROM tools WHERE name = 'ripgrep'",
+            [],
+        )?;
+        assert_eq!(db.rollup_daily_usage("2020-03-01")?, 2);
+
+        let monthly = db.get_monthly_usage_series(None)?;
+        assert_eq!(
+            monthly,
+            vec![
+                ("ripgrep".to_string(), "2020-01".to_string(), 8),
+                ("ripgrep".to_string(), "2020-02".to_string(), 5),
+            ]
+        );
+        assert_eq!(db.get_monthly_usage_series(Some("2020-02-15"))?.len(), 1);
+
         Ok(())
     }
 
@@ -577,4 +1271,571 @@ mod tests {
 
         Ok(())
     }
+
+    // ==================== Embedding Tests ====================
+
+    #[test]
+    fn test_embedding_storage() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(&Tool::new("ripgrep")).unwrap();
+
+        let vector = vec![0.5, -1.25, 3.0];
+        assert!(
+            db.set_embedding("ripgrep", "local:hash-v1", "abc", &vector)
+                .unwrap()
+        );
+        assert!(
+            !db.set_embedding("missing", "local:hash-v1", "abc", &vector)
+                .unwrap()
+        );
+
+        let hashes = db.get_embedding_hashes("local:hash-v1").unwrap();
+        assert_eq!(hashes.get("ripgrep").map(String::as_str), Some("abc"));
+        assert!(db.get_embedding_hashes("openai:x").unwrap().is_empty());
+
+        let (model, stored) = db.get_embedding("ripgrep").unwrap().unwrap();
+        assert_eq!(model, "local:hash-v1");
+        assert_eq!(stored, vector);
+        assert_eq!(db.get_embeddings("local:hash-v1").unwrap().len(), 1);
+
+        assert_eq!(db.clear_embeddings().unwrap(), 1);
+        assert!(db.get_embedding("ripgrep").unwrap().is_none());
+    }
+
+    // ==================== Relation Tests ====================
+
+    #[test]
+    fn test_tool_relations() {
+        let db = Database::open_in_memory().unwrap();
+        for name in ["eza", "exa", "lsd"] {
+            db.insert_tool(&Tool::new(name)).unwrap();
+        }
+
+        assert!(
+            db.add_relation(
+                "eza",
+                "exa",
+                RelationKind::Replaces,
+                Some("exa is unmaintained")
+            )
+            .unwrap()
+        );
+        assert!(
+            db.add_relation("eza", "lsd", RelationKind::Replaces, None)
+                .unwrap()
+        );
+        // Unknown tools and self-relations are rejected
+        assert!(
+            !db.add_relation("eza", "missing", RelationKind::Replaces, None)
+                .unwrap()
+        );
+        assert!(
+            !db.add_relation("eza", "eza", RelationKind::Replaces, None)
+                .unwrap()
+        );
+        // Re-adding replaces the note
+        assert!(
+            db.add_relation("eza", "exa", RelationKind::Replaces, None)
+                .unwrap()
+        );
+
+        let relations = db.get_relations("exa").unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].tool, "eza");
+        assert_eq!(relations[0].note, None);
+        assert_eq!(db.list_relations(RelationKind::Replaces).unwrap().len(), 2);
+
+        // Deleting a tool removes its relations
+        db.delete_tool("lsd").unwrap();
+        assert_eq!(db.get_relations("eza").unwrap().len(), 1);
+
+        // Dependencies are replaced wholesale, leaving other kinds alone
+        let pairs = |list: &[(&str, &str)]| -> Vec<(String, String)> {
+            list.iter()
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .collect()
+        };
+        let recorded = db
+            .replace_relations(
+                RelationKind::DependsOn,
+                &pairs(&[("eza", "exa"), ("eza", "libgit2")]),
+            )
+            .unwrap();
+        assert_eq!(recorded, 1);
+        db.replace_relations(RelationKind::DependsOn, &pairs(&[("exa", "eza")]))
+            .unwrap();
+        let deps = db.list_relations(RelationKind::DependsOn).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(
+            (deps[0].tool.as_str(), deps[0].related.as_str()),
+            ("exa", "eza")
+        );
+        assert_eq!(db.list_relations(RelationKind::Replaces).unwrap().len(), 1);
+    }
+
+    // ==================== AI Usage Tests ====================
+
+    #[test]
+    fn test_ai_usage_summary() {
+        let db = Database::open_in_memory().unwrap();
+        let call = |feature: &str, tokens: i64, success: bool| AiCall {
+            feature: feature.to_string(),
+            provider: "openai".to_string(),
+            model: Some("gpt-4o-mini".to_string()),
+            prompt_tokens: tokens,
+            response_tokens: 10,
+            duration_ms: 500,
+            success,
+        };
+        db.record_ai_call(&call("cheatsheet", 100, true)).unwrap();
+        db.record_ai_call(&call("cheatsheet", 200, false)).unwrap();
+        db.record_ai_call(&call("describe", 50, true)).unwrap();
+
+        let summary = db.ai_usage_summary(None).unwrap();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].feature, "cheatsheet");
+        assert_eq!(summary[0].calls, 2);
+        assert_eq!(summary[0].failures, 1);
+        assert_eq!(summary[0].total_tokens(), 320);
+        assert_eq!(summary[0].duration_ms, 1000);
+
+        let future = chrono::Utc::now() + chrono::Duration::hours(1);
+        assert!(db.ai_usage_summary(Some(future)).unwrap().is_empty());
+
+        assert_eq!(db.clear_ai_calls().unwrap(), 3);
+        assert!(db.ai_usage_summary(None).unwrap().is_empty());
+    }
+
+    // ==================== Checkpoint Tests ====================
+
+    #[test]
+    fn test_job_checkpoints() {
+        let db = Database::open_in_memory().unwrap();
+        let rg = db.insert_tool(&Tool::new("ripgrep")).unwrap();
+        let fd = db.insert_tool(&Tool::new("fd")).unwrap();
+
+        db.add_checkpoint("describe", &[rg, fd]).unwrap();
+        db.add_checkpoint("describe", &[rg]).unwrap();
+        db.add_checkpoint("categorize", &[fd]).unwrap();
+        assert_eq!(db.get_checkpoint("describe").unwrap().len(), 2);
+
+        // Deleting a tool drops it from checkpoints
+        db.delete_tool("fd").unwrap();
+        assert_eq!(
+            db.get_checkpoint("describe").unwrap(),
+            std::collections::HashSet::from([rg])
+        );
+        assert_eq!(db.clear_checkpoint("describe").unwrap(), 1);
+        assert!(db.get_checkpoint("categorize").unwrap().is_empty());
+    }
+
+    // ==================== GitHub Queue Tests ====================
+
+    #[test]
+    fn test_github_queue() {
+        let db = Database::open_in_memory().unwrap();
+        let tools = vec!["ripgrep".to_string(), "fd".to_string()];
+
+        assert_eq!(db.enqueue_github_jobs("sync", &tools).unwrap(), 2);
+        assert_eq!(db.enqueue_github_jobs("sync", &tools[..1]).unwrap(), 0);
+
+        db.defer_github_job("sync", "ripgrep", 1_700_000_000)
+            .unwrap();
+        assert_eq!(db.fail_github_job("sync", "fd", "timeout").unwrap(), 1);
+        assert_eq!(db.fail_github_job("sync", "fd", "timeout").unwrap(), 2);
+
+        let jobs = db.github_jobs(Some("sync")).unwrap();
+        assert_eq!(jobs.len(), 2);
+        let rg = jobs.iter().find(|j| j.target == "ripgrep").unwrap();
+        assert_eq!(rg.not_before, 1_700_000_000);
+        let fd = jobs.iter().find(|j| j.target == "fd").unwrap();
+        assert_eq!(fd.last_error.as_deref(), Some("timeout"));
+
+        assert!(db.finish_github_job("sync", "fd").unwrap());
+        assert!(!db.finish_github_job("sync", "fd").unwrap());
+        assert_eq!(db.clear_github_jobs(None).unwrap(), 1);
+        assert!(db.github_jobs(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_github_request_claims() {
+        let db = Database::open_in_memory().unwrap();
+        let key = "repos/BurntSushi/ripgrep";
+
+        assert_eq!(
+            db.claim_github_request(key, 1, 1000).unwrap(),
+            RequestClaim::Claimed
+        );
+        // Another process waits while the first one is fetching
+        assert_eq!(
+            db.claim_github_request(key, 2, 1005).unwrap(),
+            RequestClaim::InFlight
+        );
+
+        db.finish_github_request(key, Some("{}"), 1010).unwrap();
+        assert_eq!(
+            db.claim_github_request(key, 2, 1015).unwrap(),
+            RequestClaim::Shared("{}".to_string())
+        );
+
+        // Stale responses and failed requests are fetched again
+        assert_eq!(
+            db.claim_github_request(key, 2, 2000).unwrap(),
+            RequestClaim::Claimed
+        );
+        db.finish_github_request(key, None, 2001).unwrap();
+        assert_eq!(
+            db.claim_github_request(key, 3, 2002).unwrap(),
+            RequestClaim::Claimed
+        );
+        assert_eq!(db.prune_github_requests(3000).unwrap(), 1);
+    }
+
+    // ==================== AI Cache Tests ====================
+
+    #[test]
+    fn test_list_ai_cache_by_prefix() {
+        let db = Database::open_in_memory().unwrap();
+        db.set_ai_cache("cheatsheet:rg", "{}").unwrap();
+        db.set_ai_cache("cheatsheet:bat", "{}").unwrap();
+        db.set_ai_cache("updates-summary:rg:1:2", "{}").unwrap();
+        // The prefix is matched literally
+        db.set_ai_cache("cheat_sheet:fd", "{}").unwrap();
+
+        let keys: Vec<String> = db
+            .list_ai_cache("cheatsheet:")
+            .unwrap()
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(keys, vec!["cheatsheet:bat", "cheatsheet:rg"]);
+    }
+
+    #[test]
+    fn test_ai_cache_ttl_and_clear() {
+        let db = Database::open_in_memory().unwrap();
+        db.set_ai_cache("ai:describe:abc", "fresh").unwrap();
+        db.conn
+            .execute(
+                "INSERT INTO ai_cache (cache_key, content, created_at)
+                 VALUES ('ai:describe:old', 'stale', datetime('now', '-10 days'))",
+                [],
+            )
+            .unwrap();
+        db.set_ai_cache("cheatsheet:rg", "{}").unwrap();
+
+        let week = Some(chrono::Duration::days(7));
+        assert_eq!(
+            db.get_ai_cache_fresh("ai:describe:abc", week).unwrap(),
+            Some("fresh".to_string())
+        );
+        assert_eq!(
+            db.get_ai_cache_fresh("ai:describe:old", week).unwrap(),
+            None
+        );
+        assert_eq!(
+            db.get_ai_cache_fresh("ai:describe:old", None).unwrap(),
+            Some("stale".to_string())
+        );
+
+        assert_eq!(db.clear_ai_cache(Some("ai:describe:")).unwrap(), 2);
+        assert_eq!(db.clear_ai_cache(None).unwrap(), 1);
+    }
+
+    // ==================== GitHub Data Tests ====================
+
+    #[test]
+    fn test_github_info_host() {
+        use crate::forge::ForgeProvider;
+
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(&Tool::new("foot")).unwrap();
+        let info = |host| GitHubInfoInput {
+            provider: ForgeProvider::for_host(host),
+            host,
+            repo_owner: "dnkl",
+            repo_name: "foot",
+            description: None,
+            stars: 1200,
+            language: Some("C"),
+            homepage: None,
+            pushed_at: None,
+            open_issues: None,
+            archived: false,
+            contributors: None,
+        };
+
+        db.set_github_info("foot", info("codeberg.org")).unwrap();
+        let stored = db.get_github_info("foot").unwrap().unwrap();
+        assert_eq!(stored.host, "codeberg.org");
+        assert_eq!(stored.provider, ForgeProvider::Gitea);
+        assert!(!stored.is_github());
+        assert_eq!(stored.repo_ref().url(), "https://codeberg.org/dnkl/foot");
+
+        db.set_github_info("foot", info("github.com")).unwrap();
+        assert!(db.get_github_info("foot").unwrap().unwrap().is_github());
+
+        // Re-running the schema setup on an existing database is a no-op
+        schema::init_schema(&db.conn).unwrap();
+    }
+
+    #[test]
+    fn test_github_info_health() {
+        use crate::forge::ForgeProvider;
+
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(&Tool::new("exa")).unwrap();
+        let pushed = (chrono::Utc::now() - chrono::Duration::days(3 * 365)).to_rfc3339();
+        let input = GitHubInfoInput {
+            provider: ForgeProvider::GitHub,
+            host: "github.com",
+            repo_owner: "ogham",
+            repo_name: "exa",
+            description: None,
+            stars: 23000,
+            language: Some("Rust"),
+            homepage: None,
+            pushed_at: Some(&pushed),
+            open_issues: Some(300),
+            archived: false,
+            contributors: Some(120),
+        };
+        db.set_github_info("exa", input).unwrap();
+
+        let info = db.get_github_info("exa").unwrap().unwrap();
+        assert_eq!(info.open_issues, Some(300));
+        assert_eq!(info.contributors, Some(120));
+        assert_eq!(info.days_since_push(), Some(3 * 365));
+        assert_eq!(info.abandonment().as_deref(), Some("no commits in 3 years"));
+
+        let fresh = GitHubInfo {
+            pushed_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..info.clone()
+        };
+        assert_eq!(fresh.abandonment(), None);
+        let archived = GitHubInfo {
+            archived: true,
+            ..fresh
+        };
+        assert_eq!(
+            archived.abandonment().as_deref(),
+            Some("repository is archived")
+        );
+
+        let (name, all) = db.get_all_github_info().unwrap().remove(0);
+        assert_eq!(name, "exa");
+        assert_eq!(all.open_issues, Some(300));
+    }
+
+    #[test]
+    fn test_topics() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(&Tool::new("bat")).unwrap();
+        db.insert_tool(&Tool::new("fd")).unwrap();
+
+        let topics = vec!["CLI".to_string(), "terminal".to_string()];
+        assert!(db.set_topics("bat", &topics).unwrap());
+        db.set_topics("fd", &["cli".to_string()]).unwrap();
+        assert!(!db.set_topics("missing", &topics).unwrap());
+        assert_eq!(db.get_topics("bat").unwrap(), vec!["cli", "terminal"]);
+
+        let names = |topic: &str| -> Vec<String> {
+            db.list_tools_by_topic(topic)
+                .unwrap()
+                .into_iter()
+                .map(|t| t.name)
+                .collect()
+        };
+        assert_eq!(names("Cli"), vec!["bat", "fd"]);
+        assert_eq!(names("terminal"), vec!["bat"]);
+
+        // Setting topics replaces the previous ones
+        db.set_topics("bat", &["pager".to_string()]).unwrap();
+        assert_eq!(names("terminal"), Vec::<String>::new());
+        assert_eq!(db.get_topics("bat").unwrap(), vec!["pager"]);
+    }
+
+    // ==================== Release Install Tests ====================
+
+    #[test]
+    fn test_release_installs() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(&Tool::new("jq")).unwrap();
+
+        let install = ReleaseInstall {
+            tool: "jq".to_string(),
+            host: "github.com".to_string(),
+            repo_owner: "jqlang".to_string(),
+            repo_name: "jq".to_string(),
+            tag: "jq-1.7.1".to_string(),
+            asset: "jq-linux-amd64".to_string(),
+            path: "/home/me/.local/bin/jq".to_string(),
+            installed_at: String::new(),
+        };
+        assert!(db.record_release_install(&install).unwrap());
+        assert!(
+            !db.record_release_install(&ReleaseInstall {
+                tool: "missing".to_string(),
+                ..install.clone()
+            })
+            .unwrap()
+        );
+
+        let stored = db.get_release_install("jq").unwrap().unwrap();
+        assert_eq!(stored.tag, "jq-1.7.1");
+        assert_eq!(stored.path, install.path);
+        assert!(!stored.installed_at.is_empty());
+
+        // Reinstalling replaces the record
+        db.record_release_install(&ReleaseInstall {
+            tag: "jq-1.8.0".to_string(),
+            ..install
+        })
+        .unwrap();
+        assert_eq!(
+            db.get_release_install("jq").unwrap().unwrap().tag,
+            "jq-1.8.0"
+        );
+
+        assert!(db.remove_release_install("jq").unwrap());
+        assert!(db.get_release_install("jq").unwrap().is_none());
+    }
+
+    // ==================== Release Watch Tests ====================
+
+    #[test]
+    fn test_release_watches() {
+        use crate::forge::Release;
+
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(&Tool::new("foot")).unwrap();
+        let release = |tag: &str| Release {
+            tag_name: tag.to_string(),
+            published_at: Some(format!("2026-01-0{}T00:00:00Z", &tag[4..5])),
+            ..Default::default()
+        };
+
+        assert!(!db.add_watch("missing").unwrap());
+        assert!(db.add_watch("foot").unwrap());
+        assert!(!db.add_watch("foot").unwrap());
+        assert!(db.is_watched("foot").unwrap());
+
+        // Baseline releases are stored as read
+        db.record_releases("foot", &[release("1.0.1")], true)
+            .unwrap();
+        let new = db
+            .record_releases("foot", &[release("1.0.2"), release("1.0.1")], false)
+            .unwrap();
+        assert_eq!(new, vec!["1.0.2"]);
+
+        let watches = db.list_watches().unwrap();
+        assert_eq!(watches[0].latest.as_deref(), Some("1.0.2"));
+        assert_eq!(watches[0].unread, 1);
+        assert_eq!(db.unread_releases(Some("foot")).unwrap()[0].tag, "1.0.2");
+        assert_eq!(
+            db.get_latest_release_dates().unwrap()["foot"],
+            "2026-01-02T00:00:00Z"
+        );
+        assert_eq!(db.get_release_tags().unwrap()["foot"].len(), 2);
+
+        assert_eq!(db.mark_releases_read(Some("foot")).unwrap(), 1);
+        assert!(db.unread_releases(None).unwrap().is_empty());
+
+        assert!(db.remove_watch("foot").unwrap());
+        assert!(db.list_watches().unwrap().is_empty());
+    }
+
+    // ==================== README Cache Tests ====================
+
+    #[test]
+    fn test_readme_cache() {
+        let db = Database::open_in_memory().unwrap();
+        assert!(
+            db.get_cached_readme("github.com", "sharkdp", "bat")
+                .unwrap()
+                .is_none()
+        );
+        assert!(!db.touch_readme("github.com", "sharkdp", "bat").unwrap());
+
+        db.cache_readme("github.com", "sharkdp", "bat", "# bat", Some("\"v1\""))
+            .unwrap();
+        let cached = db
+            .get_cached_readme("GitHub.com", "SharkDP", "bat")
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.content, "# bat");
+        assert_eq!(cached.etag.as_deref(), Some("\"v1\""));
+        assert!(cached.age_secs < 60);
+
+        // Replacing keeps a single row per repository
+        db.cache_readme("github.com", "sharkdp", "bat", "# bat 2", None)
+            .unwrap();
+        let cached = db
+            .get_cached_readme("github.com", "sharkdp", "bat")
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.content, "# bat 2");
+        assert_eq!(cached.etag, None);
+        assert!(db.touch_readme("github.com", "sharkdp", "bat").unwrap());
+    }
+
+    #[test]
+    fn test_config_mode_and_validation() {
+        let mut config = crate::models::Config::new("tmux", "/dot/tmux.conf", "~/.tmux.conf");
+        config.mode = crate::models::ConfigMode::Copy;
+        let db = Database::open_in_memory().unwrap();
+        db.insert_config(&config).unwrap();
+
+        let stored = db.get_config_by_name("tmux").unwrap().unwrap();
+        assert_eq!(stored.mode, crate::models::ConfigMode::Copy);
+        assert_eq!(stored.validate_command, None);
+
+        db.set_config_mode("tmux", crate::models::ConfigMode::Link)
+            .unwrap();
+        db.set_config_validate("tmux", Some("tmux -f {target} -C exit"))
+            .unwrap();
+        let stored = &db.list_configs().unwrap()[0];
+        assert!(stored.mode.is_link());
+        assert_eq!(
+            stored.validate_command.as_deref(),
+            Some("tmux -f {target} -C exit")
+        );
+    }
+
+    #[test]
+    fn test_config_variants() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_config(&crate::models::Config::new(
+            "git",
+            "/dot/git",
+            "~/.gitconfig",
+        ))
+        .unwrap();
+
+        db.set_config_variant("git", "work-*", "/dot/git-work")
+            .unwrap();
+        db.set_config_variant("git", "home", "/dot/git-home")
+            .unwrap();
+        db.set_config_variant("git", "home", "/dot/git-home2")
+            .unwrap();
+        assert!(db.set_config_variant("nope", "home", "/x").is_err());
+
+        let variants = db.list_config_variants("git").unwrap();
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].profile, "home");
+        assert_eq!(variants[0].source_path, "/dot/git-home2");
+
+        assert!(db.remove_config_variant("git", "home").unwrap());
+        assert!(!db.remove_config_variant("git", "home").unwrap());
+
+        // Variants go with their config
+        db.delete_config("git").unwrap();
+        db.insert_config(&crate::models::Config::new(
+            "git",
+            "/dot/git",
+            "~/.gitconfig",
+        ))
+        .unwrap();
+        assert!(db.list_config_variants("git").unwrap().is_empty());
+    }
 }
@@ -0,0 +1,46 @@
+//! Pinned tool versions
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+
+use super::Database;
+
+impl Database {
+    // ==================== Pin Operations ====================
+
+    /// Pin a tool, at a version or at whatever is installed (returns false if
+    /// the tool is not tracked)
+    pub fn pin_tool(&self, tool_name: &str, version: Option<&str>) -> Result<bool> {
+        let rows = self.conn.execute(
+            "INSERT OR REPLACE INTO tool_pins (tool_id, version, pinned_at)
+             SELECT id, ?2, ?3 FROM tools WHERE name = ?1",
+            params![tool_name, version, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Remove a tool's pin (returns false if it wasn't pinned)
+    pub fn unpin_tool(&self, tool_name: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "DELETE FROM tool_pins WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
+            params![tool_name],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Pinned tools with their pinned version, if any, by tool name
+    pub fn get_pins(&self) -> Result<HashMap<String, Option<String>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, p.version
+             FROM tool_pins p
+             JOIN tools t ON p.tool_id = t.id",
+        )?;
+        let pins = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(pins)
+    }
+}
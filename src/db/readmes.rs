@@ -0,0 +1,90 @@
+//! README cache operations
+
+use anyhow::Result;
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+/// A README stored from an earlier fetch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedReadme {
+    pub content: String,
+    /// Validator for conditional refetches, if the forge sent one
+    pub etag: Option<String>,
+    pub fetched_at: String,
+    /// Seconds since the README was fetched or last revalidated
+    pub age_secs: i64,
+}
+
+impl Database {
+    // ==================== README Cache ====================
+
+    /// Get the cached README of a repository
+    pub fn get_cached_readme(
+        &self,
+        host: &str,
+        owner: &str,
+        repo: &str,
+    ) -> Result<Option<CachedReadme>> {
+        let readme = self
+            .conn
+            .query_row(
+                "SELECT content, etag, fetched_at,
+                        CAST(strftime('%s', 'now') - strftime('%s', fetched_at) AS INTEGER)
+                 FROM readmes
+                 WHERE host = ?1 AND owner = ?2 AND repo = ?3",
+                params![
+                    host.to_lowercase(),
+                    owner.to_lowercase(),
+                    repo.to_lowercase()
+                ],
+                |row| {
+                    Ok(CachedReadme {
+                        content: row.get(0)?,
+                        etag: row.get(1)?,
+                        fetched_at: row.get(2)?,
+                        age_secs: row.get(3)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(readme)
+    }
+
+    /// Store a freshly fetched README (replaces any cached copy)
+    pub fn cache_readme(
+        &self,
+        host: &str,
+        owner: &str,
+        repo: &str,
+        content: &str,
+        etag: Option<&str>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO readmes (host, owner, repo, content, etag, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))",
+            params![
+                host.to_lowercase(),
+                owner.to_lowercase(),
+                repo.to_lowercase(),
+                content,
+                etag
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Mark a cached README as current again (after the forge reported no change)
+    pub fn touch_readme(&self, host: &str, owner: &str, repo: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE readmes SET fetched_at = datetime('now')
+             WHERE host = ?1 AND owner = ?2 AND repo = ?3",
+            params![
+                host.to_lowercase(),
+                owner.to_lowercase(),
+                repo.to_lowercase()
+            ],
+        )?;
+        Ok(rows > 0)
+    }
+}
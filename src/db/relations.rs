@@ -0,0 +1,134 @@
+//! Relationships between tracked tools
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+use serde::Serialize;
+
+use super::Database;
+
+/// Kind of relationship between two tools
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationKind {
+    /// The tool is a modern replacement for the related tool
+    Replaces,
+    /// The tool needs the related tool installed (from package metadata)
+    #[serde(rename = "depends_on")]
+    DependsOn,
+}
+
+impl RelationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationKind::Replaces => "replaces",
+            RelationKind::DependsOn => "depends_on",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "replaces" => Some(RelationKind::Replaces),
+            "depends_on" => Some(RelationKind::DependsOn),
+            _ => None,
+        }
+    }
+}
+
+/// A directed relationship: `tool` <kind> `related`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolRelation {
+    pub tool: String,
+    pub related: String,
+    pub kind: RelationKind,
+    pub note: Option<String>,
+}
+
+impl Database {
+    // ==================== Relation Operations ====================
+
+    /// Record a relationship (returns false if either tool is not tracked)
+    pub fn add_relation(
+        &self,
+        tool: &str,
+        related: &str,
+        kind: RelationKind,
+        note: Option<&str>,
+    ) -> Result<bool> {
+        let rows = self.conn.execute(
+            "INSERT OR REPLACE INTO tool_relations (tool_id, related_id, kind, note, created_at)
+             SELECT a.id, b.id, ?3, ?4, ?5 FROM tools a, tools b
+             WHERE a.name = ?1 AND b.name = ?2 AND a.id != b.id",
+            params![tool, related, kind.as_str(), note, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Replace every relationship of one kind (pairs naming untracked tools
+    /// are dropped); returns how many were recorded
+    pub fn replace_relations(
+        &self,
+        kind: RelationKind,
+        pairs: &[(String, String)],
+    ) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM tool_relations WHERE kind = ?1",
+            [kind.as_str()],
+        )?;
+        let mut recorded = 0;
+        for (tool, related) in pairs {
+            if self.add_relation(tool, related, kind, None)? {
+                recorded += 1;
+            }
+        }
+        tx.commit()?;
+        Ok(recorded)
+    }
+
+    /// Relationships involving a tool, in either direction
+    pub fn get_relations(&self, tool_name: &str) -> Result<Vec<ToolRelation>> {
+        self.query_relations(
+            "WHERE a.name = ?1 OR b.name = ?1 ORDER BY a.name, b.name",
+            tool_name,
+        )
+    }
+
+    /// All relationships of one kind
+    pub fn list_relations(&self, kind: RelationKind) -> Result<Vec<ToolRelation>> {
+        self.query_relations("WHERE r.kind = ?1 ORDER BY a.name, b.name", kind.as_str())
+    }
+
+    fn query_relations(&self, filter: &str, param: &str) -> Result<Vec<ToolRelation>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT a.name, b.name, r.kind, r.note FROM tool_relations r
+             JOIN tools a ON r.tool_id = a.id
+             JOIN tools b ON r.related_id = b.id
+             {}",
+            filter
+        ))?;
+        let rows = stmt.query_map([param], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?;
+
+        let mut relations = Vec::new();
+        for row in rows {
+            let (tool, related, kind, note) = row?;
+            // Skip kinds written by a newer version
+            if let Some(kind) = RelationKind::parse(&kind) {
+                relations.push(ToolRelation {
+                    tool,
+                    related,
+                    kind,
+                    note,
+                });
+            }
+        }
+        Ok(relations)
+    }
+}
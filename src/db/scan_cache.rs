@@ -0,0 +1,63 @@
+//! Per-source listings from the last scan
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+use crate::models::Tool;
+
+/// A source's listing as of the last scan
+#[derive(Debug, Clone)]
+pub struct ScanCacheEntry {
+    /// Newest modification time of the source's state files, in Unix seconds
+    pub mtime: Option<i64>,
+    /// Hash of the listing, to tell whether anything changed
+    pub hash: String,
+    pub tools: Vec<Tool>,
+}
+
+impl Database {
+    // ==================== Scan Cache ====================
+
+    /// Get the cached listing of a source
+    pub fn get_scan_cache(&self, source: &str) -> Result<Option<ScanCacheEntry>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT mtime, hash, tools FROM scan_cache WHERE source = ?1",
+                params![source],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<i64>>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                    ))
+                },
+            )
+            .optional()?;
+        let Some((mtime, hash, tools)) = row else {
+            return Ok(None);
+        };
+        // A listing written by another version is just a cache miss
+        Ok(serde_json::from_str(&tools)
+            .ok()
+            .map(|tools| ScanCacheEntry { mtime, hash, tools }))
+    }
+
+    /// Store a source's listing (replaces the previous one)
+    pub fn set_scan_cache(&self, source: &str, entry: &ScanCacheEntry) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO scan_cache (source, mtime, hash, tools, scanned_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                source,
+                entry.mtime,
+                entry.hash,
+                serde_json::to_string(&entry.tools)?,
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+}
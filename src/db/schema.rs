@@ -19,7 +19,10 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             is_favorite INTEGER NOT NULL DEFAULT 0,
             notes TEXT,
             created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
+            updated_at TEXT NOT NULL,
+            license TEXT,
+            installed_version TEXT,
+            kind TEXT NOT NULL DEFAULT 'application'
         );
 
         CREATE TABLE IF NOT EXISTS interests (
@@ -37,10 +40,30 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             target_path TEXT NOT NULL,
             tool_id INTEGER REFERENCES tools(id),
             is_symlinked INTEGER NOT NULL DEFAULT 0,
+            rendered_hash TEXT,
+            mode TEXT NOT NULL DEFAULT 'link',
+            validate_command TEXT,
             created_at TEXT NOT NULL,
             updated_at TEXT NOT NULL
         );
 
+        -- Targets moved aside before a config link replaced them
+        CREATE TABLE IF NOT EXISTS config_backups (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            config_name TEXT NOT NULL,  -- kept after the config is unlinked
+            original_path TEXT NOT NULL,
+            backup_path TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        -- Per-machine alternatives to a config's source
+        CREATE TABLE IF NOT EXISTS config_variants (
+            config_id INTEGER NOT NULL REFERENCES configs(id) ON DELETE CASCADE,
+            profile TEXT NOT NULL,      -- profile name or hostname pattern (work-*)
+            source_path TEXT NOT NULL,
+            PRIMARY KEY (config_id, profile)
+        );
+
         CREATE INDEX IF NOT EXISTS idx_tools_name ON tools(name);
         CREATE INDEX IF NOT EXISTS idx_tools_category ON tools(category);
         CREATE INDEX IF NOT EXISTS idx_tools_source ON tools(source);
@@ -56,6 +79,24 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
         CREATE TABLE IF NOT EXISTS bundle_tools (
             bundle_id INTEGER NOT NULL REFERENCES bundles(id) ON DELETE CASCADE,
             tool_name TEXT NOT NULL,
+            condition TEXT,
+            PRIMARY KEY (bundle_id, tool_name)
+        );
+
+        -- Bundles included in other bundles
+        CREATE TABLE IF NOT EXISTS bundle_includes (
+            bundle_id INTEGER NOT NULL REFERENCES bundles(id) ON DELETE CASCADE,
+            included_id INTEGER NOT NULL REFERENCES bundles(id) ON DELETE CASCADE,
+            PRIMARY KEY (bundle_id, included_id)
+        );
+
+        -- Versions recorded when a bundle was last applied
+        CREATE TABLE IF NOT EXISTS bundle_locks (
+            bundle_id INTEGER NOT NULL REFERENCES bundles(id) ON DELETE CASCADE,
+            tool_name TEXT NOT NULL,
+            source TEXT NOT NULL,
+            version TEXT NOT NULL,
+            locked_at TEXT NOT NULL,
             PRIMARY KEY (bundle_id, tool_name)
         );
 
@@ -65,6 +106,28 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             PRIMARY KEY (tool_id, label)
         );
 
+        -- Shell aliases, tied to the tool they run when it is tracked
+        CREATE TABLE IF NOT EXISTS aliases (
+            name TEXT PRIMARY KEY,
+            command TEXT NOT NULL,
+            tool_id INTEGER REFERENCES tools(id) ON DELETE SET NULL,
+            created_at TEXT NOT NULL
+        );
+
+        -- Tools held at a version (or at the installed one when NULL)
+        CREATE TABLE IF NOT EXISTS tool_pins (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            version TEXT,
+            pinned_at TEXT NOT NULL
+        );
+
+        -- Repository topics, as reported by the forge
+        CREATE TABLE IF NOT EXISTS tool_topics (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            topic TEXT NOT NULL,
+            PRIMARY KEY (tool_id, topic)
+        );
+
         CREATE TABLE IF NOT EXISTS tool_github (
             tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
             repo_owner TEXT NOT NULL,
@@ -73,7 +136,13 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             stars INTEGER DEFAULT 0,
             language TEXT,
             homepage TEXT,
-            updated_at TEXT NOT NULL
+            updated_at TEXT NOT NULL,
+            host TEXT NOT NULL DEFAULT 'github.com',
+            provider TEXT NOT NULL DEFAULT 'github',
+            pushed_at TEXT,
+            open_issues INTEGER,
+            archived INTEGER NOT NULL DEFAULT 0,
+            contributors INTEGER
         );
 
         CREATE TABLE IF NOT EXISTS tool_usage (
@@ -94,6 +163,40 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_usage_daily_date ON usage_daily(date);
 
+        -- Monthly totals of daily rows past the retention window
+        CREATE TABLE IF NOT EXISTS usage_monthly (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            month TEXT NOT NULL,  -- YYYY-MM format
+            count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (tool_id, month)
+        );
+
+        -- Usage by weekday (0 = Monday) and local hour, from timestamped history
+        CREATE TABLE IF NOT EXISTS usage_rhythm (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            weekday INTEGER NOT NULL,
+            hour INTEGER NOT NULL,
+            count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (tool_id, weekday, hour)
+        );
+
+        -- First subcommand or flag per tool (`git commit`, `rg -i`), top N kept
+        CREATE TABLE IF NOT EXISTS usage_patterns (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            pattern TEXT NOT NULL,
+            count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (tool_id, pattern)
+        );
+
+        -- Usage per project, recorded by shell hooks from the working directory
+        CREATE TABLE IF NOT EXISTS usage_projects (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            project TEXT NOT NULL,  -- git root, or the directory itself
+            use_count INTEGER NOT NULL DEFAULT 0,
+            last_used TEXT,
+            PRIMARY KEY (tool_id, project)
+        );
+
         CREATE TABLE IF NOT EXISTS extraction_cache (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             repo_owner TEXT NOT NULL,
@@ -111,6 +214,7 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_bundles_name ON bundles(name);
         CREATE INDEX IF NOT EXISTS idx_tool_labels_label ON tool_labels(label);
+        CREATE INDEX IF NOT EXISTS idx_tool_topics_topic ON tool_topics(topic);
         CREATE INDEX IF NOT EXISTS idx_extraction_cache_repo ON extraction_cache(repo_owner, repo_name);
 
         CREATE TABLE IF NOT EXISTS ai_cache (
@@ -118,8 +222,234 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             content TEXT NOT NULL,
             created_at TEXT NOT NULL
         );
+
+        -- One embedding per tool; vector is little-endian f32
+        CREATE TABLE IF NOT EXISTS tool_embeddings (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            model TEXT NOT NULL,
+            content_hash TEXT NOT NULL,
+            vector BLOB NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        -- Directed relationships: tool <kind> related (e.g. eza replaces exa)
+        CREATE TABLE IF NOT EXISTS tool_relations (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            related_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            kind TEXT NOT NULL,
+            note TEXT,
+            created_at TEXT NOT NULL,
+            PRIMARY KEY (tool_id, related_id, kind)
+        );
+
+        -- AI invocations, for usage and quota accounting (token counts are estimates)
+        CREATE TABLE IF NOT EXISTS ai_calls (
+            id INTEGER PRIMARY KEY,
+            feature TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            model TEXT,
+            prompt_tokens INTEGER NOT NULL,
+            response_tokens INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL,
+            success INTEGER NOT NULL,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ai_calls_created ON ai_calls(created_at);
+
+        -- Tools already processed by a resumable batch job (e.g. ai enrich)
+        CREATE TABLE IF NOT EXISTS job_checkpoints (
+            job TEXT NOT NULL,
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            processed_at TEXT NOT NULL,
+            PRIMARY KEY (job, tool_id)
+        );
+
+        -- Tools whose upstream releases are watched
+        CREATE TABLE IF NOT EXISTS release_watches (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            created_at TEXT NOT NULL
+        );
+
+        -- Releases seen for watched tools, with read/unread state
+        CREATE TABLE IF NOT EXISTS watched_releases (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            tag TEXT NOT NULL,
+            url TEXT,
+            published_at TEXT,
+            is_read INTEGER NOT NULL DEFAULT 0,
+            seen_at TEXT NOT NULL,
+            PRIMARY KEY (tool_id, tag)
+        );
+
+        -- Fetched READMEs, revalidated with their ETag once stale
+        CREATE TABLE IF NOT EXISTS readmes (
+            host TEXT NOT NULL,
+            owner TEXT NOT NULL,
+            repo TEXT NOT NULL,
+            content TEXT NOT NULL,
+            etag TEXT,
+            fetched_at TEXT NOT NULL,
+            PRIMARY KEY (host, owner, repo)
+        );
+
+        -- Binaries installed from forge release assets
+        CREATE TABLE IF NOT EXISTS release_installs (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            host TEXT NOT NULL,
+            repo_owner TEXT NOT NULL,
+            repo_name TEXT NOT NULL,
+            tag TEXT NOT NULL,
+            asset TEXT NOT NULL,
+            path TEXT NOT NULL,
+            installed_at TEXT NOT NULL
+        );
+
+        -- Adoption window opened when a tool gets installed
+        CREATE TABLE IF NOT EXISTS tool_adoption (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            installed_at TEXT NOT NULL,
+            baseline_uses INTEGER NOT NULL DEFAULT 0,  -- use_count at install
+            kept_at TEXT  -- set when the user keeps it regardless of use
+        );
+
+        -- GitHub work waiting for API quota, resumed by later runs
+        CREATE TABLE IF NOT EXISTS github_queue (
+            job TEXT NOT NULL,
+            target TEXT NOT NULL,
+            not_before INTEGER NOT NULL DEFAULT 0,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT,
+            queued_at TEXT NOT NULL,
+            PRIMARY KEY (job, target)
+        );
+
+        -- GitHub API requests shared between hoards processes (body NULL while in flight)
+        CREATE TABLE IF NOT EXISTS github_requests (
+            key TEXT PRIMARY KEY,
+            pid INTEGER NOT NULL,
+            body TEXT,
+            updated_at INTEGER NOT NULL
+        );
+
+        -- Each source's listing from the last scan, to skip unchanged sources
+        CREATE TABLE IF NOT EXISTS scan_cache (
+            source TEXT PRIMARY KEY,
+            mtime INTEGER,  -- newest mtime of the source's state files
+            hash TEXT NOT NULL,
+            tools TEXT NOT NULL,  -- JSON
+            scanned_at TEXT NOT NULL
+        );
+
+        -- systemd user units and launchd agents, and the tools they run
+        CREATE TABLE IF NOT EXISTS services (
+            manager TEXT NOT NULL,
+            name TEXT NOT NULL,
+            path TEXT NOT NULL,
+            binary TEXT NOT NULL,
+            tool_id INTEGER REFERENCES tools(id) ON DELETE SET NULL,
+            scanned_at TEXT NOT NULL,
+            PRIMARY KEY (manager, name)
+        );
+
+        -- Disk footprint last measured by `insights size`
+        CREATE TABLE IF NOT EXISTS tool_sizes (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            bytes INTEGER NOT NULL,
+            measured_at TEXT NOT NULL
+        );
+
+        -- Installs and removals, by tool name so they outlive the tool
+        CREATE TABLE IF NOT EXISTS tool_events (
+            tool TEXT NOT NULL,
+            event TEXT NOT NULL,  -- install, remove
+            at TEXT NOT NULL
+        );
+
+        -- Last computed health score per tool (0-100), with its signals
+        CREATE TABLE IF NOT EXISTS tool_health (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            score INTEGER NOT NULL,
+            usage INTEGER,
+            updates INTEGER,
+            upstream INTEGER,
+            configs INTEGER,
+            computed_at TEXT NOT NULL
+        );
+
+        -- Databases merged in, by content hash, so their usage adds up once
+        CREATE TABLE IF NOT EXISTS merged_databases (
+            hash TEXT PRIMARY KEY,
+            merged_at TEXT NOT NULL
+        );
         "#,
     )?;
 
+    migrate(conn)?;
+
+    Ok(())
+}
+
+/// Bring databases created by older versions up to the current schema
+fn migrate(conn: &Connection) -> Result<()> {
+    // Forge host for repository info (previously GitHub only)
+    add_column_if_missing(
+        conn,
+        "tool_github",
+        "host",
+        "TEXT NOT NULL DEFAULT 'github.com'",
+    )?;
+    add_column_if_missing(
+        conn,
+        "tool_github",
+        "provider",
+        "TEXT NOT NULL DEFAULT 'github'",
+    )?;
+
+    // Repository health metrics
+    add_column_if_missing(conn, "tool_github", "pushed_at", "TEXT")?;
+    add_column_if_missing(conn, "tool_github", "open_issues", "INTEGER")?;
+    add_column_if_missing(
+        conn,
+        "tool_github",
+        "archived",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(conn, "tool_github", "contributors", "INTEGER")?;
+
+    // Upstream license, from forge or registry metadata
+    add_column_if_missing(conn, "tools", "license", "TEXT")?;
+
+    // Installed version, recorded by scans
+    add_column_if_missing(conn, "tools", "installed_version", "TEXT")?;
+
+    // Application vs runtime/toolchain
+    add_column_if_missing(conn, "tools", "kind", "TEXT NOT NULL DEFAULT 'application'")?;
+
+    // Per-machine conditions on bundle members ("os=linux,host=work-*")
+    add_column_if_missing(conn, "bundle_tools", "condition", "TEXT")?;
+
+    // Hash of the last render of a templated config
+    add_column_if_missing(conn, "configs", "rendered_hash", "TEXT")?;
+
+    // Whether a config is linked or copied into place
+    add_column_if_missing(conn, "configs", "mode", "TEXT NOT NULL DEFAULT 'link'")?;
+
+    // Command that checks a config after sync
+    add_column_if_missing(conn, "configs", "validate_command", "TEXT")?;
+
+    Ok(())
+}
+
+/// Add a column to an existing table unless it is already there
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let exists: bool = conn.query_row(
+        &format!("SELECT COUNT(*) > 0 FROM pragma_table_info('{table}') WHERE name = ?1"),
+        [column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {decl}"))?;
+    }
     Ok(())
 }
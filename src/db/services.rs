@@ -0,0 +1,107 @@
+//! User services and background daemons found by scans
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+use serde::Serialize;
+
+use super::Database;
+
+/// A systemd user unit or launchd agent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserService {
+    /// Unit file name ("syncthing.service") or launchd label
+    pub name: String,
+    /// `systemd` or `launchd`
+    pub manager: String,
+    /// Unit file or agent plist
+    pub path: String,
+    /// Program the service runs, as written in the unit
+    pub binary: String,
+    /// Tracked tool that program belongs to
+    pub tool: Option<String>,
+}
+
+impl UserService {
+    /// Whether the program the service runs is still there
+    pub fn binary_exists(&self) -> bool {
+        if std::path::Path::new(&self.binary).is_absolute() {
+            std::path::Path::new(&self.binary).exists()
+        } else {
+            crate::scanner::is_installed(&self.binary)
+        }
+    }
+
+    /// Command that stops the service and keeps it from starting again
+    pub fn disable_command(&self) -> String {
+        match self.manager.as_str() {
+            "launchd" => format!("launchctl unload -w {}", self.path),
+            _ => format!("systemctl --user disable --now {}", self.name),
+        }
+    }
+}
+
+fn service_from_row(row: &rusqlite::Row) -> rusqlite::Result<UserService> {
+    Ok(UserService {
+        name: row.get(0)?,
+        manager: row.get(1)?,
+        path: row.get(2)?,
+        binary: row.get(3)?,
+        tool: row.get(4)?,
+    })
+}
+
+const SERVICE_SELECT: &str = "SELECT s.name, s.manager, s.path, s.binary, t.name
+     FROM services s
+     LEFT JOIN tools t ON s.tool_id = t.id";
+
+impl Database {
+    // ==================== Service Operations ====================
+
+    /// Replace the recorded services with a scan's, tying each to the
+    /// tracked tool its program belongs to
+    pub fn replace_services(&self, services: &[UserService]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM services", [])?;
+        let now = Utc::now().to_rfc3339();
+        for service in services {
+            tx.execute(
+                "INSERT OR REPLACE INTO services (manager, name, path, binary, tool_id, scanned_at)
+                 VALUES (?1, ?2, ?3, ?4, (SELECT id FROM tools WHERE name = ?5), ?6)",
+                params![
+                    service.manager,
+                    service.name,
+                    service.path,
+                    service.binary,
+                    service.tool,
+                    now
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// All recorded services, by manager and name
+    pub fn list_services(&self) -> Result<Vec<UserService>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("{} ORDER BY s.manager, s.name", SERVICE_SELECT))?;
+        let services = stmt
+            .query_map([], service_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(services)
+    }
+
+    /// Services that run a tracked tool
+    pub fn get_tool_services(&self, tool: &str) -> Result<Vec<UserService>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE t.name = ?1 ORDER BY s.manager, s.name",
+            SERVICE_SELECT
+        ))?;
+        let services = stmt
+            .query_map([tool], service_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(services)
+    }
+}
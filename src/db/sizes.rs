@@ -0,0 +1,35 @@
+//! Measured on-disk footprint of tools
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+use std::collections::HashMap;
+
+use super::Database;
+
+impl Database {
+    // ==================== Size Operations ====================
+
+    /// Record a tool's measured footprint in bytes (returns false if not tracked)
+    pub fn set_tool_size(&self, name: &str, bytes: u64) -> Result<bool> {
+        let rows = self.conn.execute(
+            "INSERT OR REPLACE INTO tool_sizes (tool_id, bytes, measured_at)
+             SELECT id, ?2, ?3 FROM tools WHERE name = ?1",
+            params![name, bytes as i64, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Last measured footprint of every measured tool, by name
+    pub fn get_tool_sizes(&self) -> Result<HashMap<String, u64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT t.name, s.bytes FROM tool_sizes s JOIN tools t ON t.id = s.tool_id")?;
+        let sizes = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(sizes)
+    }
+}
@@ -0,0 +1,177 @@
+//! Install timeline: when tools were installed and removed
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use rusqlite::params;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::Database;
+
+/// Fewest installs in a month for it to count as a burst
+pub const BURST_MIN_INSTALLS: usize = 5;
+
+/// Whether a tool was installed or removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineEventKind {
+    Install,
+    Remove,
+}
+
+impl TimelineEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimelineEventKind::Install => "install",
+            TimelineEventKind::Remove => "remove",
+        }
+    }
+}
+
+/// One install or removal
+#[derive(Debug, Clone)]
+pub struct TimelineEvent {
+    pub tool: String,
+    pub kind: TimelineEventKind,
+    pub at: DateTime<Utc>,
+}
+
+/// Installs and removals in one month
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimelineMonth {
+    /// `YYYY-MM`
+    pub month: String,
+    pub installed: Vec<String>,
+    pub removed: Vec<String>,
+    /// Why the month stands out, if it's a burst of installs
+    pub burst: Option<String>,
+}
+
+/// Group events by month, oldest first and with empty months filled in
+///
+/// A month is a burst when it has at least [`BURST_MIN_INSTALLS`] installs
+/// and twice the median of months with any. Bursts are described by the
+/// category most of their tools share, when at least three do.
+pub fn monthly_timeline(
+    events: &[TimelineEvent],
+    categories: &HashMap<String, String>,
+) -> Vec<TimelineMonth> {
+    let Some(first) = events.iter().map(|e| e.at).min() else {
+        return Vec::new();
+    };
+    let last = events.iter().map(|e| e.at).max().unwrap_or(first);
+
+    let mut months = Vec::new();
+    let mut date = NaiveDate::from_ymd_opt(first.year(), first.month(), 1).unwrap_or_default();
+    let end = NaiveDate::from_ymd_opt(last.year(), last.month(), 1).unwrap_or_default();
+    while date <= end {
+        let month = date.format("%Y-%m").to_string();
+        let in_month = |kind| {
+            events
+                .iter()
+                .filter(|e| e.kind == kind && e.at.format("%Y-%m").to_string() == month)
+                .map(|e| e.tool.clone())
+                .collect::<Vec<_>>()
+        };
+        months.push(TimelineMonth {
+            installed: in_month(TimelineEventKind::Install),
+            removed: in_month(TimelineEventKind::Remove),
+            month,
+            burst: None,
+        });
+        date = date
+            .checked_add_months(chrono::Months::new(1))
+            .unwrap_or(NaiveDate::MAX);
+    }
+
+    let mut active: Vec<usize> = months
+        .iter()
+        .map(|m| m.installed.len())
+        .filter(|n| *n > 0)
+        .collect();
+    active.sort();
+    // Lower median, so one busy month among few doesn't hide itself
+    let median = active
+        .get(active.len().saturating_sub(1) / 2)
+        .copied()
+        .unwrap_or(0);
+
+    for month in &mut months {
+        let count = month.installed.len();
+        if count < BURST_MIN_INSTALLS || count < median * 2 {
+            continue;
+        }
+        let mut by_category: HashMap<&str, usize> = HashMap::new();
+        for tool in &month.installed {
+            if let Some(category) = categories.get(tool) {
+                *by_category.entry(category.as_str()).or_default() += 1;
+            }
+        }
+        month.burst = Some(
+            match by_category
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+            {
+                Some((category, n)) if n >= 3 => format!("{} {} tools", n, category),
+                _ => format!("{} installs", count),
+            },
+        );
+    }
+
+    months
+}
+
+impl Database {
+    // ==================== Timeline Operations ====================
+
+    /// Record that a tool was installed or removed
+    pub(super) fn record_tool_event(&self, tool_name: &str, kind: TimelineEventKind) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO tool_events (tool, event, at) VALUES (?1, ?2, ?3)",
+            params![tool_name, kind.as_str(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    /// Every install and removal, oldest first
+    ///
+    /// Tools installed before events were recorded count as installed when
+    /// they were added.
+    pub fn get_timeline_events(&self) -> Result<Vec<TimelineEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tool, event, at FROM tool_events
+             UNION ALL
+             SELECT t.name, 'install', t.created_at FROM tools t
+             WHERE NOT EXISTS (
+                     SELECT 1 FROM tool_events e WHERE e.tool = t.name AND e.event = 'install')
+               AND (t.is_installed = 1
+                    OR EXISTS (SELECT 1 FROM tool_events e WHERE e.tool = t.name))
+             ORDER BY 3",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut events = Vec::new();
+        for row in rows {
+            let (tool, kind, at) = row?;
+            let kind = match kind.as_str() {
+                "install" => TimelineEventKind::Install,
+                "remove" => TimelineEventKind::Remove,
+                _ => continue,
+            };
+            let Ok(at) = DateTime::parse_from_rfc3339(&at) else {
+                continue;
+            };
+            events.push(TimelineEvent {
+                tool,
+                kind,
+                at: at.with_timezone(&Utc),
+            });
+        }
+        Ok(events)
+    }
+}
@@ -2,11 +2,11 @@
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::params;
+use rusqlite::{OptionalExtension, params};
 
-use crate::models::{InstallSource, Interest, Tool};
+use crate::models::{InstallSource, Interest, Tool, ToolKind};
 
-use super::Database;
+use super::{Database, TimelineEventKind};
 
 /// Parse a datetime from a string column, returning current time on failure
 pub(crate) fn parse_datetime(s: String) -> DateTime<Utc> {
@@ -30,6 +30,9 @@ pub(crate) fn tool_from_row(row: &rusqlite::Row) -> rusqlite::Result<Tool> {
         notes: row.get(9)?,
         created_at: parse_datetime(row.get(10)?),
         updated_at: parse_datetime(row.get(11)?),
+        license: row.get(12)?,
+        installed_version: row.get(13)?,
+        kind: ToolKind::from(row.get::<_, String>(14)?.as_str()),
     })
 }
 
@@ -41,8 +44,9 @@ impl Database {
         self.conn.execute(
             r#"
             INSERT INTO tools (name, description, category, source, install_command,
-                             binary_name, is_installed, is_favorite, notes, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                             binary_name, is_installed, is_favorite, notes, created_at, updated_at,
+                             license, installed_version, kind)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
             "#,
             params![
                 tool.name,
@@ -56,6 +60,9 @@ impl Database {
                 tool.notes,
                 tool.created_at.to_rfc3339(),
                 tool.updated_at.to_rfc3339(),
+                tool.license,
+                tool.installed_version,
+                tool.kind.to_string(),
             ],
         )?;
 
@@ -71,8 +78,9 @@ impl Database {
             UPDATE tools SET
                 name = ?1, description = ?2, category = ?3, source = ?4,
                 install_command = ?5, binary_name = ?6, is_installed = ?7,
-                is_favorite = ?8, notes = ?9, updated_at = ?10
-            WHERE id = ?11
+                is_favorite = ?8, notes = ?9, installed_version = ?10, kind = ?11,
+                updated_at = ?12
+            WHERE id = ?13
             "#,
             params![
                 tool.name,
@@ -84,6 +92,8 @@ impl Database {
                 tool.is_installed,
                 tool.is_favorite,
                 tool.notes,
+                tool.installed_version,
+                tool.kind.to_string(),
                 Utc::now().to_rfc3339(),
                 id,
             ],
@@ -92,6 +102,44 @@ impl Database {
         Ok(())
     }
 
+    /// Set the license of a tool (returns false if the tool is not tracked)
+    pub fn set_tool_license(&self, name: &str, license: Option<&str>) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE tools SET license = ?1 WHERE name = ?2",
+            params![license, name],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Record the version a scan found installed (returns false if the tool
+    /// is not tracked)
+    pub fn set_tool_installed_version(&self, name: &str, version: Option<&str>) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE tools SET installed_version = ?1 WHERE name = ?2",
+            params![version, name],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Set what kind of tool an entry is (returns false if the tool is not
+    /// tracked)
+    pub fn set_tool_kind(&self, name: &str, kind: ToolKind) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE tools SET kind = ?1 WHERE name = ?2",
+            params![kind.to_string(), name],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Replace a tool's notes (returns false if the tool is not tracked)
+    pub fn set_tool_notes(&self, name: &str, notes: Option<&str>) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE tools SET notes = ?1, updated_at = ?2 WHERE name = ?3",
+            params![notes, Utc::now().to_rfc3339(), name],
+        )?;
+        Ok(rows > 0)
+    }
+
     /// Update only the description of a tool
     pub fn update_tool_description(&self, name: &str, description: &str) -> Result<bool> {
         let rows = self.conn.execute(
@@ -123,7 +171,8 @@ impl Database {
     pub fn get_tool_by_name(&self, name: &str) -> Result<Option<Tool>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, description, category, source, install_command,
-                    binary_name, is_installed, is_favorite, notes, created_at, updated_at
+                    binary_name, is_installed, is_favorite, notes, created_at, updated_at,
+                    license, installed_version, kind
              FROM tools WHERE name = ?1",
         )?;
 
@@ -140,7 +189,8 @@ impl Database {
     pub fn list_tools(&self, installed_only: bool, category: Option<&str>) -> Result<Vec<Tool>> {
         let mut query = String::from(
             "SELECT id, name, description, category, source, install_command,
-                    binary_name, is_installed, is_favorite, notes, created_at, updated_at
+                    binary_name, is_installed, is_favorite, notes, created_at, updated_at,
+                    license, installed_version, kind
              FROM tools WHERE 1=1",
         );
 
@@ -171,7 +221,8 @@ impl Database {
 
         let mut stmt = self.conn.prepare(
             "SELECT id, name, description, category, source, install_command,
-                    binary_name, is_installed, is_favorite, notes, created_at, updated_at
+                    binary_name, is_installed, is_favorite, notes, created_at, updated_at,
+                    license, installed_version, kind
              FROM tools
              WHERE name LIKE ?1 OR description LIKE ?1 OR category LIKE ?1
              ORDER BY name",
@@ -185,9 +236,34 @@ impl Database {
     }
 
     /// Update install status for a tool
+    ///
+    /// Installing a tool that wasn't installed opens its adoption window;
+    /// uninstalling closes it. The recorded version is cleared either way,
+    /// until the next scan finds the new one.
     pub fn set_tool_installed(&self, name: &str, installed: bool) -> Result<bool> {
+        let was_installed: Option<bool> = self
+            .conn
+            .query_row(
+                "SELECT is_installed FROM tools WHERE name = ?1",
+                [name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match (was_installed, installed) {
+            (Some(false), true) => {
+                self.start_adoption(name)?;
+                self.record_tool_event(name, TimelineEventKind::Install)?;
+            }
+            (Some(true), false) => {
+                self.end_adoption(name)?;
+                self.record_tool_event(name, TimelineEventKind::Remove)?;
+            }
+            _ => {}
+        }
+
         let rows = self.conn.execute(
-            "UPDATE tools SET is_installed = ?1, updated_at = ?2 WHERE name = ?3",
+            "UPDATE tools SET is_installed = ?1, installed_version = NULL, updated_at = ?2
+             WHERE name = ?3",
             params![installed, Utc::now().to_rfc3339(), name],
         )?;
 
@@ -264,7 +340,8 @@ impl Database {
     pub fn get_all_tools(&self) -> Result<Vec<Tool>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, name, description, category, source, install_command,
-                    binary_name, is_installed, is_favorite, notes, created_at, updated_at
+                    binary_name, is_installed, is_favorite, notes, created_at, updated_at,
+                    license, installed_version, kind
              FROM tools ORDER BY name",
         )?;
 
@@ -3,20 +3,110 @@
 use anyhow::Result;
 use chrono::Utc;
 use rusqlite::{OptionalExtension, params};
+use serde::Serialize;
+use std::collections::HashMap;
 
 use crate::models::Tool;
 
 use super::Database;
 use super::tools::tool_from_row;
 
+/// Subcommands and flags kept per tool
+const USAGE_PATTERNS_KEPT: usize = 20;
+
 /// Tool usage statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ToolUsage {
     pub use_count: i64,
     pub last_used: Option<String>,
     pub first_seen: String,
 }
 
+/// Usage of one tool within a project
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectUsage {
+    pub tool: String,
+    pub use_count: i64,
+    pub last_used: Option<String>,
+}
+
+/// Usage events counted up for one write, as logged by the shell hook
+#[derive(Debug, Clone, Default)]
+pub struct UsageBatch {
+    /// Uses and latest Unix timestamp per tool
+    pub tools: HashMap<String, (i64, i64)>,
+    /// Uses and latest Unix timestamp per (tool, project root)
+    pub projects: HashMap<(String, String), (i64, i64)>,
+    /// Uses per (tool, weekday, hour)
+    pub rhythm: HashMap<(String, u32, u32), i64>,
+    /// Uses per (tool, subcommand or flag)
+    pub patterns: HashMap<(String, String), i64>,
+}
+
+/// Uses bucketed by weekday (0 = Monday) and local hour
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageRhythm {
+    pub counts: [[i64; 24]; 7],
+}
+
+impl UsageRhythm {
+    /// Total bucketed uses
+    pub fn total(&self) -> i64 {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Uses per hour of the day, all weekdays combined
+    pub fn by_hour(&self) -> [i64; 24] {
+        let mut hours = [0; 24];
+        for day in &self.counts {
+            for (hour, count) in day.iter().enumerate() {
+                hours[hour] += count;
+            }
+        }
+        hours
+    }
+
+    /// Uses per weekday
+    pub fn by_weekday(&self) -> [i64; 7] {
+        self.counts.map(|day| day.iter().sum())
+    }
+
+    /// Busiest hour, if anything was recorded
+    pub fn peak_hour(&self) -> Option<usize> {
+        peak(&self.by_hour())
+    }
+
+    /// Busiest weekday (0 = Monday), if anything was recorded
+    pub fn peak_weekday(&self) -> Option<usize> {
+        peak(&self.by_weekday())
+    }
+
+    /// Share of uses on Saturday and Sunday (0.0 to 1.0)
+    pub fn weekend_share(&self) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        let days = self.by_weekday();
+        (days[5] + days[6]) as f64 / total as f64
+    }
+
+    /// Add another rhythm's counts to this one
+    pub fn merge(&mut self, other: &UsageRhythm) {
+        for (day, other_day) in self.counts.iter_mut().zip(&other.counts) {
+            for (count, other_count) in day.iter_mut().zip(other_day) {
+                *count += other_count;
+            }
+        }
+    }
+}
+
+/// Index of the largest non-zero value (earliest on ties)
+fn peak(values: &[i64]) -> Option<usize> {
+    let max = *values.iter().max()?;
+    (max > 0).then(|| values.iter().position(|&v| v == max))?
+}
+
 impl Database {
     // ==================== Usage Tracking ====================
 
@@ -145,9 +235,288 @@ impl Database {
         Ok(results)
     }
 
+    /// Record a whole batch of usage in one transaction
+    ///
+    /// Either every count is written or none is, so a failed batch can be
+    /// retried without counting anything twice. Returns the number of uses
+    /// recorded per tool.
+    pub fn record_usage_batch(&self, batch: &UsageBatch) -> Result<usize> {
+        let rfc3339 = |ts: i64| chrono::DateTime::from_timestamp(ts, 0).map(|t| t.to_rfc3339());
+        let tx = self.conn.unchecked_transaction()?;
+        let mut recorded = 0;
+        for (tool, (count, last)) in &batch.tools {
+            self.record_usage(tool, *count, rfc3339(*last).as_deref())?;
+            recorded += *count as usize;
+        }
+        for ((tool, project), (count, last)) in &batch.projects {
+            self.record_project_usage(tool, project, *count, rfc3339(*last).as_deref())?;
+        }
+        for ((tool, weekday, hour), count) in &batch.rhythm {
+            self.record_usage_rhythm(tool, *weekday, *hour, *count)?;
+        }
+        for ((tool, pattern), count) in &batch.patterns {
+            self.record_usage_pattern(tool, pattern, *count)?;
+        }
+        if !batch.patterns.is_empty() {
+            self.prune_usage_patterns()?;
+        }
+        tx.commit()?;
+        Ok(recorded)
+    }
+
+    /// Add uses to a tool's weekday/hour bucket (returns false for unknown tools)
+    pub fn record_usage_rhythm(
+        &self,
+        tool_name: &str,
+        weekday: u32,
+        hour: u32,
+        count: i64,
+    ) -> Result<bool> {
+        let updated = self.conn.execute(
+            "INSERT INTO usage_rhythm (tool_id, weekday, hour, count)
+             SELECT id, ?2, ?3, ?4 FROM tools WHERE name = ?1
+             ON CONFLICT(tool_id, weekday, hour) DO UPDATE SET count = count + ?4",
+            params![tool_name, weekday % 7, hour % 24, count],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Add uses of a subcommand or flag (returns false for unknown tools)
+    pub fn record_usage_pattern(&self, tool_name: &str, pattern: &str, count: i64) -> Result<bool> {
+        let updated = self.conn.execute(
+            "INSERT INTO usage_patterns (tool_id, pattern, count)
+             SELECT id, ?2, ?3 FROM tools WHERE name = ?1
+             ON CONFLICT(tool_id, pattern) DO UPDATE SET count = count + ?3",
+            params![tool_name, pattern, count],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Drop all but the most used patterns of each tool
+    pub fn prune_usage_patterns(&self) -> Result<usize> {
+        let deleted = self.conn.execute(
+            "DELETE FROM usage_patterns WHERE rowid IN (
+                 SELECT rowid FROM (
+                     SELECT rowid, ROW_NUMBER() OVER (
+                         PARTITION BY tool_id ORDER BY count DESC, pattern
+                     ) AS rank
+                     FROM usage_patterns
+                 ) WHERE rank > ?1
+             )",
+            [USAGE_PATTERNS_KEPT as i64],
+        )?;
+        Ok(deleted)
+    }
+
+    /// Most used subcommands and flags of a tool, as (pattern, count)
+    pub fn get_usage_patterns(&self, tool_name: &str, limit: usize) -> Result<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.pattern, p.count
+             FROM usage_patterns p
+             INNER JOIN tools t ON p.tool_id = t.id
+             WHERE t.name = ?1
+             ORDER BY p.count DESC, p.pattern
+             LIMIT ?2",
+        )?;
+        let patterns = stmt
+            .query_map(params![tool_name, limit as i64], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(patterns)
+    }
+
+    /// Most used patterns of every tool, up to `limit` each
+    pub fn get_all_usage_patterns(
+        &self,
+        limit: usize,
+    ) -> Result<HashMap<String, Vec<(String, i64)>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, p.pattern, p.count
+             FROM usage_patterns p
+             INNER JOIN tools t ON p.tool_id = t.id
+             ORDER BY t.name, p.count DESC, p.pattern",
+        )?;
+        let mut patterns: HashMap<String, Vec<(String, i64)>> = HashMap::new();
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (tool, pattern, count) = row?;
+            let entry = patterns.entry(tool).or_default();
+            if entry.len() < limit {
+                entry.push((pattern, count));
+            }
+        }
+        Ok(patterns)
+    }
+
+    /// Get the weekday/hour rhythm of every tool with bucketed usage
+    pub fn get_all_usage_rhythms(&self) -> Result<HashMap<String, UsageRhythm>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, r.weekday, r.hour, r.count
+             FROM usage_rhythm r
+             INNER JOIN tools t ON r.tool_id = t.id",
+        )?;
+
+        let mut rhythms: HashMap<String, UsageRhythm> = HashMap::new();
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, usize>(1)?,
+                row.get::<_, usize>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (tool, weekday, hour, count) = row?;
+            if weekday < 7 && hour < 24 {
+                rhythms.entry(tool).or_default().counts[weekday][hour] += count;
+            }
+        }
+
+        Ok(rhythms)
+    }
+
+    /// Get daily counts as (tool, date, count), by tool then date
+    ///
+    /// `since` is an inclusive `YYYY-MM-DD` date; `None` returns all history.
+    pub fn get_daily_usage_series(
+        &self,
+        since: Option<&str>,
+    ) -> Result<Vec<(String, String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, ud.date, ud.count
+             FROM usage_daily ud
+             JOIN tools t ON ud.tool_id = t.id
+             WHERE ?1 IS NULL OR ud.date >= ?1
+             ORDER BY t.name, ud.date",
+        )?;
+
+        let rows = stmt
+            .query_map([since], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    /// Count daily usage rows dated before `before` (`YYYY-MM-DD`)
+    pub fn count_daily_usage_before(&self, before: &str) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM usage_daily WHERE date < ?1",
+            [before],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Roll daily rows dated before `before` (`YYYY-MM-DD`) into monthly
+    /// totals, returning how many daily rows were removed
+    pub fn rollup_daily_usage(&self, before: &str) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO usage_monthly (tool_id, month, count)
+             SELECT tool_id, substr(date, 1, 7), SUM(count) FROM usage_daily
+             WHERE date < ?1
+             GROUP BY tool_id, substr(date, 1, 7)
+             ON CONFLICT(tool_id, month) DO UPDATE SET count = count + excluded.count",
+            [before],
+        )?;
+        let removed = tx.execute("DELETE FROM usage_daily WHERE date < ?1", [before])?;
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    /// Get monthly totals as (tool, month, count), by tool then month
+    ///
+    /// `since` is an inclusive `YYYY-MM-DD` date; its month is included.
+    pub fn get_monthly_usage_series(
+        &self,
+        since: Option<&str>,
+    ) -> Result<Vec<(String, String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, um.month, um.count
+             FROM usage_monthly um
+             JOIN tools t ON um.tool_id = t.id
+             WHERE ?1 IS NULL OR um.month >= substr(?1, 1, 7)
+             ORDER BY t.name, um.month",
+        )?;
+
+        let rows = stmt
+            .query_map([since], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(rows)
+    }
+
+    /// Record tool usage within a project (returns false for unknown tools)
+    pub fn record_project_usage(
+        &self,
+        tool_name: &str,
+        project: &str,
+        count: i64,
+        last_used: Option<&str>,
+    ) -> Result<bool> {
+        let updated = self.conn.execute(
+            "INSERT INTO usage_projects (tool_id, project, use_count, last_used)
+             SELECT id, ?2, ?3, ?4 FROM tools WHERE name = ?1
+             ON CONFLICT(tool_id, project) DO UPDATE SET
+                use_count = use_count + ?3,
+                last_used = COALESCE(?4, last_used)",
+            params![tool_name, project, count, last_used],
+        )?;
+        Ok(updated > 0)
+    }
+
+    /// Get usage within a project, most used first
+    pub fn get_project_usage(&self, project: &str) -> Result<Vec<ProjectUsage>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, up.use_count, up.last_used
+             FROM usage_projects up
+             INNER JOIN tools t ON up.tool_id = t.id
+             WHERE up.project = ?1
+             ORDER BY up.use_count DESC, t.name",
+        )?;
+
+        let results = stmt
+            .query_map([project], |row| {
+                Ok(ProjectUsage {
+                    tool: row.get(0)?,
+                    use_count: row.get(1)?,
+                    last_used: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// Get projects with recorded usage and their total use counts
+    pub fn get_usage_projects(&self) -> Result<Vec<(String, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT project, SUM(use_count) AS total
+             FROM usage_projects
+             GROUP BY project
+             ORDER BY total DESC, project",
+        )?;
+
+        let results = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
     /// Clear all usage data
     pub fn clear_usage(&self) -> Result<()> {
         self.conn.execute("DELETE FROM tool_usage", [])?;
+        self.conn.execute("DELETE FROM usage_projects", [])?;
+        self.conn.execute("DELETE FROM usage_rhythm", [])?;
+        self.conn.execute("DELETE FROM usage_patterns", [])?;
         Ok(())
     }
 
@@ -170,14 +539,16 @@ impl Database {
         Ok(deleted)
     }
 
-    /// Get installed tools with no usage data (never used)
+    /// Get installed tools with no usage data (never used), libraries aside
     pub fn get_unused_tools(&self) -> Result<Vec<Tool>> {
         let mut stmt = self.conn.prepare(
             "SELECT t.id, t.name, t.description, t.category, t.source, t.install_command,
-                    t.binary_name, t.is_installed, t.is_favorite, t.notes, t.created_at, t.updated_at
+                    t.binary_name, t.is_installed, t.is_favorite, t.notes, t.created_at, t.updated_at,
+                    t.license, t.installed_version, t.kind
              FROM tools t
              LEFT JOIN tool_usage tu ON t.id = tu.tool_id
-             WHERE t.is_installed = 1 AND (tu.tool_id IS NULL OR tu.use_count = 0)
+             WHERE t.is_installed = 1 AND t.kind != 'library'
+               AND (tu.tool_id IS NULL OR tu.use_count = 0)
              ORDER BY t.name",
         )?;
 
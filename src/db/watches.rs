@@ -0,0 +1,198 @@
+//! Release watching: subscriptions and seen releases
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+use std::collections::HashMap;
+
+use super::Database;
+use crate::forge::Release;
+
+/// A watched tool with its release counts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedTool {
+    pub tool: String,
+    /// Newest known release tag
+    pub latest: Option<String>,
+    pub unread: i64,
+}
+
+/// A release seen for a watched tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedRelease {
+    pub tool: String,
+    pub tag: String,
+    pub url: Option<String>,
+    pub published_at: Option<String>,
+    pub is_read: bool,
+}
+
+impl Database {
+    // ==================== Release Watch Operations ====================
+
+    /// Start watching a tool's releases (returns false if the tool is not tracked)
+    pub fn add_watch(&self, tool_name: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "INSERT OR IGNORE INTO release_watches (tool_id, created_at)
+             SELECT id, ?2 FROM tools WHERE name = ?1",
+            params![tool_name, Utc::now().to_rfc3339()],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Stop watching a tool, forgetting its seen releases
+    pub fn remove_watch(&self, tool_name: &str) -> Result<bool> {
+        self.conn.execute(
+            "DELETE FROM watched_releases
+             WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
+            [tool_name],
+        )?;
+        let rows = self.conn.execute(
+            "DELETE FROM release_watches
+             WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
+            [tool_name],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Check if a tool is watched
+    pub fn is_watched(&self, tool_name: &str) -> Result<bool> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM release_watches w
+             JOIN tools t ON w.tool_id = t.id
+             WHERE t.name = ?1",
+            [tool_name],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// All watched tools with their newest release and unread count
+    pub fn list_watches(&self) -> Result<Vec<WatchedTool>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name,
+                    (SELECT r.tag FROM watched_releases r WHERE r.tool_id = w.tool_id
+                     ORDER BY COALESCE(r.published_at, r.seen_at) DESC LIMIT 1),
+                    (SELECT COUNT(*) FROM watched_releases r
+                     WHERE r.tool_id = w.tool_id AND r.is_read = 0)
+             FROM release_watches w
+             JOIN tools t ON w.tool_id = t.id
+             ORDER BY t.name",
+        )?;
+        let watches = stmt
+            .query_map([], |row| {
+                Ok(WatchedTool {
+                    tool: row.get(0)?,
+                    latest: row.get(1)?,
+                    unread: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(watches)
+    }
+
+    /// When each watched tool last published a release (RFC 3339), for tools
+    /// with a dated release
+    pub fn get_latest_release_dates(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, MAX(r.published_at)
+             FROM watched_releases r
+             JOIN tools t ON r.tool_id = t.id
+             WHERE r.published_at IS NOT NULL
+             GROUP BY t.name",
+        )?;
+        let dates = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(dates)
+    }
+
+    /// Tags of every release seen, per watched tool
+    pub fn get_release_tags(&self) -> Result<HashMap<String, Vec<String>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, r.tag FROM watched_releases r
+             JOIN tools t ON r.tool_id = t.id
+             ORDER BY t.name",
+        )?;
+        let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+        for row in stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (tool, tag) = row?;
+            tags.entry(tool).or_default().push(tag);
+        }
+        Ok(tags)
+    }
+
+    /// Record releases of a watched tool, returning the tags not seen before
+    ///
+    /// With `read`, new releases are stored as already read (used for the
+    /// baseline when a watch starts).
+    pub fn record_releases(
+        &self,
+        tool_name: &str,
+        releases: &[Release],
+        read: bool,
+    ) -> Result<Vec<String>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut new_tags = Vec::new();
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO watched_releases
+                 (tool_id, tag, url, published_at, is_read, seen_at)
+                 SELECT w.tool_id, ?2, ?3, ?4, ?5, ?6 FROM release_watches w
+                 JOIN tools t ON w.tool_id = t.id
+                 WHERE t.name = ?1",
+            )?;
+            let now = Utc::now().to_rfc3339();
+            for release in releases {
+                let rows = stmt.execute(params![
+                    tool_name,
+                    release.tag_name,
+                    release.html_url,
+                    release.published_at,
+                    read,
+                    now
+                ])?;
+                if rows > 0 {
+                    new_tags.push(release.tag_name.clone());
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(new_tags)
+    }
+
+    /// Unread releases, optionally for one tool, newest first
+    pub fn unread_releases(&self, tool_name: Option<&str>) -> Result<Vec<WatchedRelease>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, r.tag, r.url, r.published_at, r.is_read
+             FROM watched_releases r
+             JOIN tools t ON r.tool_id = t.id
+             WHERE r.is_read = 0 AND (?1 IS NULL OR t.name = ?1)
+             ORDER BY COALESCE(r.published_at, r.seen_at) DESC, t.name",
+        )?;
+        let releases = stmt
+            .query_map([tool_name], |row| {
+                Ok(WatchedRelease {
+                    tool: row.get(0)?,
+                    tag: row.get(1)?,
+                    url: row.get(2)?,
+                    published_at: row.get(3)?,
+                    is_read: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(releases)
+    }
+
+    /// Mark releases as read, optionally for one tool only
+    pub fn mark_releases_read(&self, tool_name: Option<&str>) -> Result<usize> {
+        Ok(self.conn.execute(
+            "UPDATE watched_releases SET is_read = 1
+             WHERE is_read = 0
+               AND (?1 IS NULL OR tool_id = (SELECT id FROM tools WHERE name = ?1))",
+            [tool_name],
+        )?)
+    }
+}
@@ -0,0 +1,107 @@
+//! Dependencies between installed packages
+//!
+//! Scans read what each apt, Homebrew and pip package requires, so the
+//! relationship graph can show which tools are only installed because
+//! another one needs them.
+
+use std::process::Command;
+
+use crate::sources::distribution_requirements;
+
+/// Packages an apt package depends on, from a dpkg `Depends:` field
+///
+/// Only the first of alternatives (`a | b`) counts; versions and
+/// architecture qualifiers are dropped.
+fn parse_dpkg_depends(field: &str) -> Vec<String> {
+    field
+        .split(',')
+        .filter_map(|group| group.split('|').next())
+        .filter_map(|dep| dep.split_whitespace().next())
+        .map(|dep| dep.split(':').next().unwrap_or(dep).to_string())
+        .filter(|dep| !dep.is_empty())
+        .collect()
+}
+
+/// (package, dependency) pairs for installed apt packages
+fn apt_dependencies() -> Vec<(String, String)> {
+    let Ok(output) = Command::new("dpkg-query")
+        .args(["-W", "-f=${Package}\t${Depends}, ${Pre-Depends}\n"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .flat_map(|(package, depends)| {
+            parse_dpkg_depends(depends)
+                .into_iter()
+                .map(move |dep| (package.to_string(), dep))
+        })
+        .collect()
+}
+
+/// (formula, dependency) pairs from `brew deps --installed`
+/// (`formula: dep dep ...` lines)
+fn parse_brew_deps(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .flat_map(|(formula, deps)| {
+            deps.split_whitespace()
+                .map(move |dep| (formula.trim().to_string(), dep.to_string()))
+        })
+        .collect()
+}
+
+/// (formula, dependency) pairs for installed Homebrew formulae
+fn brew_dependencies() -> Vec<(String, String)> {
+    Command::new("brew")
+        .args(["deps", "--installed"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| parse_brew_deps(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// (tool, dependency) pairs across apt, Homebrew and pip, by package name
+pub fn scan_dependencies() -> Vec<(String, String)> {
+    let mut pairs = apt_dependencies();
+    pairs.extend(brew_dependencies());
+    for (package, requires) in distribution_requirements() {
+        pairs.extend(requires.into_iter().map(|dep| (package.clone(), dep)));
+    }
+    pairs.retain(|(package, dep)| package != dep);
+    pairs.sort();
+    pairs.dedup();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dpkg_depends() {
+        let field = "libc6 (>= 2.34), libpcre2-8-0 (>= 10.22) | libpcre3, python3:any, ";
+        assert_eq!(
+            parse_dpkg_depends(field),
+            vec!["libc6", "libpcre2-8-0", "python3"]
+        );
+        assert!(parse_dpkg_depends(", ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_brew_deps() {
+        let output = "bat: libgit2 oniguruma\njq: oniguruma\nripgrep:\n";
+        assert_eq!(
+            parse_brew_deps(output),
+            vec![
+                ("bat".to_string(), "libgit2".to_string()),
+                ("bat".to_string(), "oniguruma".to_string()),
+                ("jq".to_string(), "oniguruma".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,912 @@
+//! Templated config files
+//!
+//! A config source ending in `.tmpl` is rendered into its target instead of
+//! being symlinked. Placeholders are written `{{ name }}` and filled from
+//! the machine (`hostname`, `os`, `arch`, `user`, `home`, `email`) and from
+//! `dotfiles.vars` in the hoards config, which wins over the built-ins.
+//!
+//! The hash of each render is stored with the config so a later sync can
+//! tell a stale target (template or variables changed) from one that was
+//! edited by hand.
+//!
+//! Targets that are real files rather than links can drift from their
+//! source; [`find_drift`] lists the differences for `config diff`.
+//!
+//! Sources can live in a git repository (`config repo`). Its
+//! [`MANIFEST_FILE`] lists the configs so another machine can register
+//! and link them after a clone or pull.
+//!
+//! Existing GNU Stow and chezmoi layouts can be registered in place with
+//! [`stow_configs`] and [`chezmoi_configs`].
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::DotfilesConfig;
+use crate::models::{ConfigMode, Machine};
+
+/// Extension marking a config source as a template
+pub const TEMPLATE_EXTENSION: &str = "tmpl";
+
+/// Whether a config source is rendered rather than linked
+pub fn is_template(source: &Path) -> bool {
+    source.is_file()
+        && source
+            .extension()
+            .is_some_and(|ext| ext == TEMPLATE_EXTENSION)
+}
+
+/// Variables available to templates on this machine
+pub fn template_vars(config: &DotfilesConfig) -> BTreeMap<String, String> {
+    let machine = Machine::current();
+    let mut vars = BTreeMap::new();
+    vars.insert("hostname".to_string(), machine.hostname);
+    vars.insert("os".to_string(), machine.os);
+    vars.insert("arch".to_string(), machine.arch);
+    vars.insert(
+        "user".to_string(),
+        std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "home".to_string(),
+        dirs::home_dir()
+            .map(|h| h.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    );
+    vars.insert("email".to_string(), git_email().unwrap_or_default());
+
+    for (key, value) in &config.vars {
+        vars.insert(key.clone(), value.clone());
+    }
+    vars
+}
+
+/// The user's git email, the usual source for `{{ email }}`
+fn git_email() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["config", "--global", "user.email"])
+        .output()
+        .ok()?;
+    let email = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !email.is_empty()).then_some(email)
+}
+
+/// Fill the `{{ name }}` placeholders of a template
+///
+/// Fails on an unknown variable or an unclosed `{{`, naming the line.
+pub fn render(template: &str, vars: &BTreeMap<String, String>) -> Result<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let line = template[..template.len() - rest.len() + start]
+            .matches('\n')
+            .count()
+            + 1;
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            bail!("Unclosed {{{{ on line {}", line);
+        };
+        let name = after[..end].trim();
+        match vars.get(name) {
+            Some(value) => out.push_str(value),
+            None => bail!("Unknown template variable '{}' on line {}", name, line),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// The profile config variants are picked for on this machine
+///
+/// Returns the name and where it came from: `dotfiles.profile`, or the
+/// hostname when no profile is set.
+pub fn active_profile(config: &DotfilesConfig) -> (String, &'static str) {
+    match &config.profile {
+        Some(profile) => (profile.clone(), "dotfiles.profile"),
+        None => (Machine::current().hostname, "hostname"),
+    }
+}
+
+/// Pick the variant profile for a machine
+///
+/// A profile set in the hoards config must match a variant exactly;
+/// otherwise variants are hostname patterns (`work-*`), exact names first.
+pub fn select_variant<'a>(
+    profiles: &[&'a str],
+    profile: Option<&str>,
+    hostname: &str,
+) -> Option<&'a str> {
+    if let Some(profile) = profile {
+        return profiles.iter().copied().find(|p| *p == profile);
+    }
+    profiles
+        .iter()
+        .copied()
+        .find(|p| *p == hostname)
+        .or_else(|| {
+            profiles
+                .iter()
+                .copied()
+                .find(|p| p.contains('*') && crate::models::glob_match(p, hostname))
+        })
+}
+
+/// Stable hash of rendered content (FNV-1a, hex)
+pub fn content_hash(content: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in content {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Where a rendered or copied config's target stands against its source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderState {
+    /// Target matches the current render (or the source, for a copy)
+    Rendered,
+    /// Target is what was last written, but the source changed since
+    Stale,
+    /// Target was changed after it was rendered (or isn't ours)
+    Modified,
+    /// Nothing at the target yet
+    Missing,
+}
+
+/// Compare a target with the last and the current render
+pub fn render_state(target: &Path, last_hash: Option<&str>, rendered: &str) -> RenderState {
+    if target.is_symlink() || target.is_dir() {
+        return RenderState::Modified;
+    }
+    let Ok(current) = std::fs::read(target) else {
+        return RenderState::Missing;
+    };
+    let current = content_hash(&current);
+    if current == content_hash(rendered.as_bytes()) {
+        RenderState::Rendered
+    } else if last_hash == Some(current.as_str()) {
+        RenderState::Stale
+    } else {
+        RenderState::Modified
+    }
+}
+
+/// Hash of a file, or of every file (and its path) under a directory
+pub fn tree_hash(root: &Path) -> Result<String> {
+    let mut content = Vec::new();
+    for path in list_files(root)? {
+        content.extend(path.to_string_lossy().as_bytes());
+        content.push(0);
+        content.extend(fs::read(drift_path(root, &path))?);
+        content.push(0);
+    }
+    Ok(content_hash(&content))
+}
+
+/// Compare a copied config's target with its source and the last copy
+pub fn copy_state(source: &Path, target: &Path, last_hash: Option<&str>) -> Result<RenderState> {
+    if target.is_symlink() {
+        return Ok(RenderState::Modified);
+    }
+    if !target.exists() {
+        return Ok(RenderState::Missing);
+    }
+    let current = tree_hash(target)?;
+    Ok(if current == tree_hash(source)? {
+        RenderState::Rendered
+    } else if last_hash == Some(current.as_str()) {
+        RenderState::Stale
+    } else {
+        RenderState::Modified
+    })
+}
+
+/// A difference between a config source and a target that is not a link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// File on both sides with different content
+    Changed {
+        path: PathBuf,
+        source: Vec<u8>,
+        target: Vec<u8>,
+    },
+    /// File missing from the target
+    OnlyInSource(PathBuf),
+    /// File added in the target
+    OnlyInTarget(PathBuf),
+}
+
+impl Drift {
+    /// Path relative to the config root (empty for a single-file config)
+    pub fn path(&self) -> &Path {
+        match self {
+            Drift::Changed { path, .. } | Drift::OnlyInSource(path) | Drift::OnlyInTarget(path) => {
+                path
+            }
+        }
+    }
+}
+
+/// Files under a config root, relative to it; `.git` is skipped
+fn list_files(root: &Path) -> Result<BTreeSet<PathBuf>> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeSet<PathBuf>) -> Result<()> {
+        for entry in
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?
+        {
+            let path = entry?.path();
+            if path.file_name().is_some_and(|n| n == ".git") {
+                continue;
+            }
+            if path.is_dir() {
+                walk(root, &path, files)?;
+            } else if let Ok(rel) = path.strip_prefix(root) {
+                files.insert(rel.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeSet::new();
+    if root.is_dir() {
+        walk(root, root, &mut files)?;
+    } else if root.exists() {
+        files.insert(PathBuf::new());
+    }
+    Ok(files)
+}
+
+/// A path from [`Drift::path`] under a config root
+pub fn drift_path(root: &Path, rel: &Path) -> PathBuf {
+    if rel.as_os_str().is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(rel)
+    }
+}
+
+/// Compare a config source with its live target, file by file
+pub fn find_drift(source: &Path, target: &Path) -> Result<Vec<Drift>> {
+    let source_files = list_files(source)?;
+    let target_files = list_files(target)?;
+    let mut drift = Vec::new();
+
+    for path in source_files.union(&target_files) {
+        match (source_files.contains(path), target_files.contains(path)) {
+            (true, true) => {
+                let source = fs::read(drift_path(source, path))?;
+                let target = fs::read(drift_path(target, path))?;
+                if source != target {
+                    drift.push(Drift::Changed {
+                        path: path.clone(),
+                        source,
+                        target,
+                    });
+                }
+            }
+            (true, false) => drift.push(Drift::OnlyInSource(path.clone())),
+            _ => drift.push(Drift::OnlyInTarget(path.clone())),
+        }
+    }
+    Ok(drift)
+}
+
+/// Unified diff of two texts, `None` when either isn't UTF-8
+pub fn unified_diff(old: &[u8], new: &[u8], old_name: &str, new_name: &str) -> Option<String> {
+    let old = std::str::from_utf8(old).ok()?;
+    let new = std::str::from_utf8(new).ok()?;
+    Some(
+        similar::TextDiff::from_lines(old, new)
+            .unified_diff()
+            .context_radius(3)
+            .header(old_name, new_name)
+            .to_string(),
+    )
+}
+
+/// A config found in another dotfiles manager's layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedConfig {
+    pub name: String,
+    pub source: PathBuf,
+    pub target: PathBuf,
+}
+
+/// Directory entries sorted by name
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Files stow leaves alone in a package by default
+fn stow_ignored(name: &str) -> bool {
+    matches!(
+        name,
+        ".git" | ".gitignore" | ".stow-local-ignore" | "COPYING"
+    ) || name.starts_with("README")
+        || name.starts_with("LICENSE")
+}
+
+/// Configs in a GNU Stow directory
+///
+/// Each package mirrors the target directory: `nvim/.config/nvim` is linked
+/// to `<target>/.config/nvim`. Every entry under `.config` is a config of
+/// its own, as is every other top-level file or directory of a package.
+pub fn stow_configs(stow_dir: &Path, target: &Path) -> Result<Vec<ImportedConfig>> {
+    let mut found = Vec::new();
+    for package in sorted_entries(stow_dir)? {
+        let package_name = file_name(&package);
+        if !package.is_dir() || package_name.starts_with('.') {
+            continue;
+        }
+
+        let mut entries = Vec::new();
+        for entry in sorted_entries(&package)? {
+            let name = file_name(&entry);
+            if stow_ignored(&name) {
+                continue;
+            }
+            if name == ".config" && entry.is_dir() {
+                for child in sorted_entries(&entry)? {
+                    let child_name = file_name(&child);
+                    let target = target.join(".config").join(&child_name);
+                    entries.push((child_name, child, target));
+                }
+            } else {
+                let target = target.join(&name);
+                entries.push((name, entry, target));
+            }
+        }
+
+        let single = entries.len() == 1;
+        for (entry_name, source, target) in entries {
+            let entry_name = entry_name.trim_start_matches('.');
+            let name = if single || entry_name == package_name {
+                package_name.clone()
+            } else {
+                format!("{}-{}", package_name, entry_name)
+            };
+            found.push(ImportedConfig {
+                name,
+                source,
+                target,
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Target name of a chezmoi source entry
+///
+/// `None` for entries chezmoi doesn't copy as-is: scripts, modify and
+/// remove entries, encrypted files, symlinks and its own templates.
+pub fn chezmoi_target_name(name: &str) -> Option<String> {
+    const SKIPPED: &[&str] = &["run_", "modify_", "remove_", "encrypted_", "symlink_"];
+    const ATTRIBUTES: &[&str] = &[
+        "create_",
+        "private_",
+        "readonly_",
+        "empty_",
+        "executable_",
+        "exact_",
+        "external_",
+        "once_",
+        "onchange_",
+        "before_",
+        "after_",
+    ];
+
+    if name.starts_with('.') || name.ends_with(".tmpl") {
+        return None;
+    }
+    let mut rest = name;
+    loop {
+        if SKIPPED.iter().any(|p| rest.starts_with(p)) {
+            return None;
+        }
+        match ATTRIBUTES.iter().find(|p| rest.starts_with(*p)) {
+            Some(prefix) => rest = &rest[prefix.len()..],
+            None => break,
+        }
+    }
+    let rest = rest.strip_suffix(".literal").unwrap_or(rest);
+    Some(match rest.strip_prefix("literal_") {
+        Some(literal) => literal.to_string(),
+        None => match rest.strip_prefix("dot_") {
+            Some(dotted) => format!(".{}", dotted),
+            None => rest.to_string(),
+        },
+    })
+}
+
+/// Configs in a chezmoi source directory
+///
+/// Like [`stow_configs`], every entry under `dot_config` is a config of its
+/// own, as is every other top-level entry. chezmoi copies files rather than
+/// linking them, so the targets are compared as copies.
+pub fn chezmoi_configs(source_dir: &Path, home: &Path) -> Result<Vec<ImportedConfig>> {
+    let mut found = Vec::new();
+    for entry in sorted_entries(source_dir)? {
+        let Some(target_name) = chezmoi_target_name(&file_name(&entry)) else {
+            continue;
+        };
+        if target_name == ".config" && entry.is_dir() {
+            for child in sorted_entries(&entry)? {
+                let Some(child_name) = chezmoi_target_name(&file_name(&child)) else {
+                    continue;
+                };
+                found.push(ImportedConfig {
+                    name: child_name.trim_start_matches('.').to_string(),
+                    target: home.join(".config").join(&child_name),
+                    source: child,
+                });
+            }
+        } else {
+            found.push(ImportedConfig {
+                name: target_name.trim_start_matches('.').to_string(),
+                target: home.join(&target_name),
+                source: entry,
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Tool names a config may belong to, most likely first
+///
+/// `.zshrc` gives `zsh`, `.tmux.conf` gives `tmux`, `gitconfig` gives `git`.
+pub fn tool_candidates(name: &str) -> Vec<String> {
+    let base = name.trim_start_matches('.').to_lowercase();
+    let stem = base.split('.').next().unwrap_or(&base).to_string();
+    let mut candidates = vec![base.clone(), stem.clone()];
+    for suffix in ["rc", "config", "conf"] {
+        if let Some(trimmed) = stem.strip_suffix(suffix) {
+            let trimmed = trimmed.trim_end_matches(['-', '_']);
+            if !trimmed.is_empty() {
+                candidates.push(trimmed.to_string());
+            }
+        }
+    }
+    candidates.dedup();
+    candidates
+}
+
+/// File at the root of a config repository listing its configs
+pub const MANIFEST_FILE: &str = "hoards-configs.json";
+
+/// A config as recorded in the repository manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    /// Relative to the repository root
+    pub source: String,
+    /// With the home directory written as `~`
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(default, skip_serializing_if = "ConfigMode::is_link")]
+    pub mode: ConfigMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate: Option<String>,
+}
+
+/// Read a repository's manifest (empty if it has none)
+pub fn read_manifest(repo: &Path) -> Result<Vec<ManifestEntry>> {
+    let path = repo.join(MANIFEST_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+}
+
+/// Write a repository's manifest, sorted by name
+pub fn write_manifest(repo: &Path, entries: &mut [ManifestEntry]) -> Result<()> {
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    let json = serde_json::to_string_pretty(entries)?;
+    fs::write(repo.join(MANIFEST_FILE), json + "\n")?;
+    Ok(())
+}
+
+/// Run git, returning stdout or failing with its stderr
+pub fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Paths with uncommitted changes, from `git status --porcelain`
+pub fn changed_paths(repo: &Path) -> Result<Vec<String>> {
+    let status = git(repo, &["status", "--porcelain", "--untracked-files=all"])?;
+    Ok(status
+        .lines()
+        .filter_map(|line| line.get(3..))
+        // Renames are written "old -> new"
+        .map(|path| path.rsplit(" -> ").next().unwrap_or(path))
+        .map(|path| path.trim_matches('"').to_string())
+        .collect())
+}
+
+/// Commit message naming the configs (and their tools) a change touches
+pub fn commit_message(changed: &[String], entries: &[ManifestEntry]) -> String {
+    let mut touched: Vec<String> = Vec::new();
+    for entry in entries {
+        let source = entry.source.trim_end_matches('/');
+        let hit = changed
+            .iter()
+            .any(|path| path == source || path.starts_with(&format!("{}/", source)));
+        if hit {
+            touched.push(match &entry.tool {
+                Some(tool) if *tool != entry.name => format!("{} ({})", entry.name, tool),
+                _ => entry.name.clone(),
+            });
+        }
+    }
+
+    const SHOWN: usize = 5;
+    match touched.len() {
+        0 => "Update config list".to_string(),
+        n if n <= SHOWN => format!("Update {}", touched.join(", ")),
+        n => format!(
+            "Update {} and {} more",
+            touched[..SHOWN].join(", "),
+            n - SHOWN
+        ),
+    }
+}
+
+/// Placeholder for the target path in a validation command
+pub const TARGET_PLACEHOLDER: &str = "{target}";
+
+/// Split a config's validation command into program and arguments
+///
+/// Words are split on whitespace, with single or double quotes grouping;
+/// no shell is involved. `{target}` is replaced by the target path.
+pub fn validation_args(command: &str, target: &Path) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in command.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        bail!("Unclosed quote in validation command: {}", command);
+    }
+    if in_word {
+        args.push(word);
+    }
+    if args.is_empty() {
+        bail!("Validation command is empty");
+    }
+
+    let target = target.to_string_lossy();
+    Ok(args
+        .into_iter()
+        .map(|arg| arg.replace(TARGET_PLACEHOLDER, &target))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn vars() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("hostname".to_string(), "work-laptop".to_string()),
+            ("email".to_string(), "me@work.example".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let out = render("[user]\n  email = {{ email }}\n# {{hostname}}\n", &vars()).unwrap();
+        assert_eq!(out, "[user]\n  email = me@work.example\n# work-laptop\n");
+        assert_eq!(
+            render("no placeholders", &vars()).unwrap(),
+            "no placeholders"
+        );
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let err = render("a\n{{ nope }}", &vars()).unwrap_err().to_string();
+        assert!(err.contains("'nope'") && err.contains("line 2"), "{err}");
+        assert!(render("{{ email", &vars()).is_err());
+    }
+
+    #[test]
+    fn test_render_state() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("gitconfig");
+        assert_eq!(render_state(&target, None, "v1"), RenderState::Missing);
+
+        std::fs::write(&target, "v1").unwrap();
+        let last = content_hash(b"v1");
+        assert_eq!(
+            render_state(&target, Some(&last), "v1"),
+            RenderState::Rendered
+        );
+        assert_eq!(render_state(&target, Some(&last), "v2"), RenderState::Stale);
+
+        std::fs::write(&target, "edited").unwrap();
+        assert_eq!(
+            render_state(&target, Some(&last), "v2"),
+            RenderState::Modified
+        );
+    }
+
+    #[test]
+    fn test_copy_state() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("config.toml"), "v1").unwrap();
+        assert_eq!(
+            copy_state(&source, &target, None).unwrap(),
+            RenderState::Missing
+        );
+
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("config.toml"), "v1").unwrap();
+        let last = tree_hash(&target).unwrap();
+        let state = |last| copy_state(&source, &target, last).unwrap();
+        assert_eq!(state(Some(&last)), RenderState::Rendered);
+
+        // A file renamed on one side changes the hash
+        fs::rename(target.join("config.toml"), target.join("other.toml")).unwrap();
+        assert_eq!(state(Some(&last)), RenderState::Modified);
+        fs::rename(target.join("other.toml"), target.join("config.toml")).unwrap();
+
+        fs::write(source.join("config.toml"), "v2").unwrap();
+        assert_eq!(state(Some(&last)), RenderState::Stale);
+
+        fs::write(target.join("config.toml"), "edited").unwrap();
+        assert_eq!(state(Some(&last)), RenderState::Modified);
+    }
+
+    #[test]
+    fn test_find_drift() {
+        let temp = TempDir::new().unwrap();
+        let source = temp.path().join("source");
+        let target = temp.path().join("target");
+        for dir in [&source, &target] {
+            fs::create_dir_all(dir.join(".git")).unwrap();
+            fs::write(dir.join(".git/HEAD"), dir.to_string_lossy().as_bytes()).unwrap();
+            fs::write(dir.join("same.conf"), "same").unwrap();
+        }
+        fs::write(source.join("init.lua"), "a\nb\n").unwrap();
+        fs::write(target.join("init.lua"), "a\nc\n").unwrap();
+        fs::write(source.join("gone.lua"), "").unwrap();
+        fs::create_dir(target.join("lua")).unwrap();
+        fs::write(target.join("lua/new.lua"), "").unwrap();
+
+        let drift = find_drift(&source, &target).unwrap();
+        let paths: Vec<_> = drift.iter().map(|d| d.path().to_path_buf()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("gone.lua"),
+                PathBuf::from("init.lua"),
+                PathBuf::from("lua/new.lua")
+            ]
+        );
+        assert!(matches!(drift[0], Drift::OnlyInSource(_)));
+        assert!(matches!(drift[2], Drift::OnlyInTarget(_)));
+
+        let Drift::Changed {
+            source: old,
+            target: new,
+            ..
+        } = &drift[1]
+        else {
+            panic!("expected a change");
+        };
+        let diff = unified_diff(old, new, "a", "b").unwrap();
+        assert!(diff.contains("-b\n") && diff.contains("+c\n"), "{diff}");
+
+        // Single files compare directly
+        let file = temp.path().join("file");
+        fs::write(&file, "same").unwrap();
+        assert!(
+            find_drift(&file, &target.join("same.conf"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_commit_message() {
+        let entry = |name: &str, source: &str, tool: Option<&str>| ManifestEntry {
+            name: name.to_string(),
+            source: source.to_string(),
+            target: format!("~/.config/{}", name),
+            tool: tool.map(String::from),
+            mode: ConfigMode::Link,
+            validate: None,
+        };
+        let entries = vec![
+            entry("nvim", "nvim", Some("neovim")),
+            entry("git", "gitconfig.tmpl", Some("git")),
+            entry("fish", "fish", None),
+        ];
+        let changed = |paths: &[&str]| paths.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            commit_message(&changed(&["nvim/init.lua", "gitconfig.tmpl"]), &entries),
+            "Update nvim (neovim), git"
+        );
+        // "fish" must not match "fishy/"
+        assert_eq!(
+            commit_message(&changed(&["fishy/x", MANIFEST_FILE]), &entries),
+            "Update config list"
+        );
+    }
+
+    #[test]
+    fn test_stow_configs() {
+        let temp = TempDir::new().unwrap();
+        let stow = temp.path().join("dotfiles");
+        fs::create_dir_all(stow.join("nvim/.config/nvim")).unwrap();
+        fs::create_dir_all(stow.join("shell/.config/fish")).unwrap();
+        fs::create_dir_all(stow.join("tmux")).unwrap();
+        fs::write(stow.join("shell/.zshrc"), "").unwrap();
+        fs::write(stow.join("tmux/.tmux.conf"), "").unwrap();
+        fs::write(stow.join("tmux/README.md"), "").unwrap();
+        fs::create_dir_all(stow.join(".git")).unwrap();
+
+        let home = Path::new("/home/me");
+        let found: Vec<_> = stow_configs(&stow, home)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.name, c.target))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("nvim".to_string(), home.join(".config/nvim")),
+                ("shell-fish".to_string(), home.join(".config/fish")),
+                ("shell-zshrc".to_string(), home.join(".zshrc")),
+                ("tmux".to_string(), home.join(".tmux.conf")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chezmoi_configs() {
+        assert_eq!(chezmoi_target_name("dot_zshrc").as_deref(), Some(".zshrc"));
+        assert_eq!(
+            chezmoi_target_name("private_executable_dot_local").as_deref(),
+            Some(".local")
+        );
+        assert_eq!(
+            chezmoi_target_name("literal_dot_x").as_deref(),
+            Some("dot_x")
+        );
+        assert_eq!(chezmoi_target_name("run_once_install.sh"), None);
+        assert_eq!(chezmoi_target_name("dot_gitconfig.tmpl"), None);
+        assert_eq!(chezmoi_target_name(".chezmoiignore"), None);
+
+        let temp = TempDir::new().unwrap();
+        let source = temp.path();
+        fs::create_dir_all(source.join("dot_config/private_fish")).unwrap();
+        fs::write(source.join("dot_config/starship.toml"), "").unwrap();
+        fs::write(source.join("dot_gitconfig"), "").unwrap();
+        fs::write(source.join(".chezmoi.toml.tmpl"), "").unwrap();
+
+        let home = Path::new("/home/me");
+        let found: Vec<_> = chezmoi_configs(source, home)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.name, c.target))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("fish".to_string(), home.join(".config/fish")),
+                (
+                    "starship.toml".to_string(),
+                    home.join(".config/starship.toml")
+                ),
+                ("gitconfig".to_string(), home.join(".gitconfig")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tool_candidates() {
+        assert_eq!(tool_candidates(".zshrc"), vec!["zshrc", "zsh"]);
+        assert_eq!(tool_candidates(".tmux.conf"), vec!["tmux.conf", "tmux"]);
+        assert_eq!(tool_candidates("gitconfig"), vec!["gitconfig", "git"]);
+        assert_eq!(tool_candidates("nvim"), vec!["nvim"]);
+    }
+
+    #[test]
+    fn test_select_variant() {
+        let profiles = ["home", "work-*", "work-laptop"];
+        assert_eq!(
+            select_variant(&profiles, None, "work-laptop"),
+            Some("work-laptop")
+        );
+        assert_eq!(
+            select_variant(&profiles, None, "work-desktop"),
+            Some("work-*")
+        );
+        assert_eq!(select_variant(&profiles, None, "home"), Some("home"));
+        assert_eq!(select_variant(&profiles, None, "nas"), None);
+
+        // An explicit profile wins over the hostname, and must exist
+        assert_eq!(
+            select_variant(&profiles, Some("home"), "work-laptop"),
+            Some("home")
+        );
+        assert_eq!(
+            select_variant(&profiles, Some("gaming"), "work-laptop"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_validation_args() {
+        let target = Path::new("/home/me/.tmux.conf");
+        assert_eq!(
+            validation_args("tmux -f {target} -C exit", target).unwrap(),
+            vec!["tmux", "-f", "/home/me/.tmux.conf", "-C", "exit"]
+        );
+        assert_eq!(
+            validation_args("nvim --headless -c 'lua print(1)' \"\" +q", target).unwrap(),
+            vec!["nvim", "--headless", "-c", "lua print(1)", "", "+q"]
+        );
+        assert!(validation_args("sh -c 'exit", target).is_err());
+        assert!(validation_args("  ", target).is_err());
+    }
+}
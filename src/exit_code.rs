@@ -0,0 +1,143 @@
+//! Exit codes for scripting
+//!
+//! Commands that know what went wrong return an error made with
+//! [`ExitCode::error`]; `main` maps the failure of a command to its exit
+//! code with [`ExitCode::of`], so wrapper scripts and CI can branch on the
+//! outcome instead of parsing output. The codes are stable: new ones may be
+//! added, but existing numbers never change meaning.
+
+use std::fmt;
+
+/// How a hoards command ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// The command did what it was asked
+    Success = 0,
+    /// Any failure without a more specific code
+    Error = 1,
+    /// A tool, bundle or other named item doesn't exist
+    NotFound = 2,
+    /// Invalid input: bad arguments, names, versions or files
+    Invalid = 3,
+    /// Some items of a batch (list or bundle install) failed
+    Partial = 4,
+    /// A network request failed (unreachable host, timeout, HTTP error)
+    Network = 5,
+}
+
+impl ExitCode {
+    /// Every exit code, in numeric order
+    pub const ALL: [ExitCode; 6] = [
+        ExitCode::Success,
+        ExitCode::Error,
+        ExitCode::NotFound,
+        ExitCode::Invalid,
+        ExitCode::Partial,
+        ExitCode::Network,
+    ];
+
+    /// The process exit status
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// What the code means, for `hoards help exit-codes`
+    pub fn description(self) -> &'static str {
+        match self {
+            ExitCode::Success => "success",
+            ExitCode::Error => "error without a more specific code",
+            ExitCode::NotFound => "tool, bundle or other named item not found",
+            ExitCode::Invalid => "invalid input: arguments, names, versions or files",
+            ExitCode::Partial => "partial failure: some tools of a list or bundle failed",
+            ExitCode::Network => "network error: unreachable host, timeout or HTTP error",
+        }
+    }
+
+    /// An error that makes hoards exit with this code
+    pub fn error(self, message: impl Into<String>) -> anyhow::Error {
+        Failure {
+            code: self,
+            message: message.into(),
+        }
+        .into()
+    }
+
+    /// The exit code for a failed command
+    ///
+    /// The outermost [`Failure`] in the error chain decides; otherwise an
+    /// HTTP error anywhere in the chain counts as a network error.
+    pub fn of(err: &anyhow::Error) -> ExitCode {
+        if let Some(failure) = err.chain().find_map(|e| e.downcast_ref::<Failure>()) {
+            return failure.code;
+        }
+        if err
+            .chain()
+            .any(|e| e.downcast_ref::<ureq::Error>().is_some())
+        {
+            return ExitCode::Network;
+        }
+        ExitCode::Error
+    }
+}
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> Self {
+        std::process::ExitCode::from(code.code())
+    }
+}
+
+/// An error carrying the exit code it should end hoards with
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct Failure {
+    pub code: ExitCode,
+    message: String,
+}
+
+/// Return early with an error that ends hoards with the given exit code,
+/// like `anyhow::bail!`: `fail!(NotFound, "Tool '{}' not found", name)`
+#[macro_export]
+macro_rules! fail {
+    ($code:ident, $($arg:tt)+) => {
+        return Err($crate::exit_code::ExitCode::$code.error(format!($($arg)+)))
+    };
+}
+
+/// The table shown by `hoards help exit-codes`
+pub struct ExitCodeTable;
+
+impl fmt::Display for ExitCodeTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Exit codes:\n")?;
+        for code in ExitCode::ALL {
+            writeln!(f, "  {}  {}", code.code(), code.description())?;
+        }
+        write!(
+            f,
+            "\n`hoards run` exits with the code of the tool it runs.\n\
+             Invalid command-line arguments exit with {}.",
+            ExitCode::Invalid.code()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_code_of() {
+        let err = ExitCode::NotFound.error("Tool 'x' not found");
+        assert_eq!(ExitCode::of(&err), ExitCode::NotFound);
+        assert_eq!(err.to_string(), "Tool 'x' not found");
+
+        // Context added on the way up keeps the code
+        let err = Err::<(), _>(ExitCode::Invalid.error("bad name"))
+            .context("Failed to add tool")
+            .unwrap_err();
+        assert_eq!(ExitCode::of(&err), ExitCode::Invalid);
+
+        assert_eq!(ExitCode::of(&anyhow::anyhow!("boom")), ExitCode::Error);
+    }
+}
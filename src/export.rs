@@ -0,0 +1,344 @@
+//! The hoards export file
+//!
+//! `hoards export` writes an [`ExportFile`] as JSON, YAML or TOML and
+//! `hoards import` reads it back. The layout is versioned by
+//! `schema_version`: files written by older releases are brought up to date
+//! by [`migrate`] before they are read, and `hoards export --schema` prints
+//! the JSON Schema for other tools to validate against.
+
+use anyhow::{Context, Result, bail};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::models::{ConfigMode, Tool};
+
+/// Layout version written by this release
+///
+/// Bump it, and add a step to [`MIGRATIONS`], whenever a change would stop
+/// older files from reading correctly.
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// A whole export: tools, bundles and config links
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(title = "hoards export")]
+pub struct ExportFile {
+    /// Layout version of the file
+    pub schema_version: u64,
+    /// When the file was written (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<String>,
+    pub tools: Vec<ExportTool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bundles: Vec<ExportBundle>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub configs: Vec<ExportConfig>,
+}
+
+/// A tracked tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportTool {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Install source: cargo, apt, snap, flatpak, npm, pip, brew, github or
+    /// manual (anything else reads as unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_command: Option<String>,
+    /// Binary to look for on PATH, when it differs from the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_name: Option<String>,
+    #[serde(default)]
+    pub installed: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Version the tool is pinned at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<String>,
+    /// Version found installed by the exporting machine's last scan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl ExportTool {
+    /// A tool as exported, without labels or pin
+    pub fn from_tool(tool: &Tool) -> Self {
+        Self {
+            name: tool.name.clone(),
+            description: tool.description.clone(),
+            category: tool.category.clone(),
+            source: Some(tool.source.to_string()),
+            install_command: tool.install_command.clone(),
+            binary_name: tool.binary_name.clone(),
+            installed: tool.is_installed,
+            labels: Vec::new(),
+            pinned: None,
+            version: tool.installed_version.clone(),
+        }
+    }
+}
+
+/// A bundle
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportBundle {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Member tool names
+    pub tools: Vec<String>,
+    /// Names of bundles this one includes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<String>,
+    /// Member conditions (`os=linux,arch=x86_64`), by tool name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub conditions: BTreeMap<String, String>,
+}
+
+/// A config link
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ExportConfig {
+    pub name: String,
+    /// Path in the dotfiles repository
+    pub source: String,
+    /// Where the config is put in place
+    pub target: String,
+    /// Name of the tool the config belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(default, skip_serializing_if = "ConfigMode::is_link")]
+    pub mode: ConfigMode,
+    /// Command run on the target after it is synced (`{target}` is replaced)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate: Option<String>,
+}
+
+impl ExportFile {
+    /// A file at the current schema version, stamped with the time
+    pub fn new(
+        tools: Vec<ExportTool>,
+        bundles: Vec<ExportBundle>,
+        configs: Vec<ExportConfig>,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            exported_at: Some(chrono::Utc::now().to_rfc3339()),
+            tools,
+            bundles,
+            configs,
+        }
+    }
+
+    /// Read a parsed file of any supported version
+    pub fn from_value(mut value: Value) -> Result<Self> {
+        migrate(&mut value)?;
+        serde_json::from_value(value).context("Invalid export file")
+    }
+}
+
+/// Steps from each version to the next, starting at version 1
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[v1_to_v2];
+
+/// Version 1 had a `"version": "1.0"` string and no `schema_version`
+fn v1_to_v2(file: &mut Map<String, Value>) {
+    file.remove("version");
+}
+
+/// Bring a parsed export file up to [`SCHEMA_VERSION`]
+///
+/// Files without `schema_version` predate it and are version 1. Files from
+/// a newer release are refused rather than half-read.
+pub fn migrate(value: &mut Value) -> Result<()> {
+    let file = value
+        .as_object_mut()
+        .context("Export file is not a table of tools, bundles and configs")?;
+    let version = match file.get("schema_version") {
+        Some(version) => version
+            .as_u64()
+            .filter(|v| *v >= 1)
+            .context("schema_version is not a positive number")?,
+        None => 1,
+    };
+    if version > SCHEMA_VERSION {
+        bail!(
+            "Export file has schema version {}, but this hoards reads up to {}; upgrade hoards to import it",
+            version,
+            SCHEMA_VERSION
+        );
+    }
+
+    for step in &MIGRATIONS[(version - 1) as usize..] {
+        step(file);
+    }
+    file.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+    Ok(())
+}
+
+/// A tool installed on both machines with a different version or source
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ToolDifference {
+    pub name: String,
+    pub local: String,
+    pub remote: String,
+}
+
+/// How the installed tools of two machines differ
+#[derive(Debug, Default, Serialize)]
+pub struct Comparison {
+    /// Installed here but not on the other machine
+    pub only_local: Vec<String>,
+    /// Installed on the other machine but not here
+    pub only_remote: Vec<String>,
+    /// Installed versions that differ (both known)
+    pub versions: Vec<ToolDifference>,
+    /// Sources that differ
+    pub sources: Vec<ToolDifference>,
+}
+
+impl Comparison {
+    pub fn is_empty(&self) -> bool {
+        self.only_local.is_empty()
+            && self.only_remote.is_empty()
+            && self.versions.is_empty()
+            && self.sources.is_empty()
+    }
+}
+
+/// Compare the installed tools of two inventories, matched by name
+///
+/// Tools tracked but not installed count as absent.
+pub fn compare(local: &[ExportTool], remote: &[ExportTool]) -> Comparison {
+    let installed = |tools: &[ExportTool]| -> BTreeMap<String, ExportTool> {
+        tools
+            .iter()
+            .filter(|t| t.installed)
+            .map(|t| (t.name.clone(), t.clone()))
+            .collect()
+    };
+    let local = installed(local);
+    let remote = installed(remote);
+
+    let mut comparison = Comparison {
+        only_local: local
+            .keys()
+            .filter(|name| !remote.contains_key(*name))
+            .cloned()
+            .collect(),
+        only_remote: remote
+            .keys()
+            .filter(|name| !local.contains_key(*name))
+            .cloned()
+            .collect(),
+        ..Default::default()
+    };
+    for (name, here) in &local {
+        let Some(there) = remote.get(name) else {
+            continue;
+        };
+        let difference = |a: &Option<String>, b: &Option<String>| match (a, b) {
+            (Some(a), Some(b)) if a != b => Some(ToolDifference {
+                name: name.clone(),
+                local: a.clone(),
+                remote: b.clone(),
+            }),
+            _ => None,
+        };
+        comparison
+            .versions
+            .extend(difference(&here.version, &there.version));
+        comparison
+            .sources
+            .extend(difference(&here.source, &there.source));
+    }
+    comparison
+}
+
+/// JSON Schema of the current export file, pretty-printed
+pub fn json_schema() -> Result<String> {
+    Ok(serde_json::to_string_pretty(&schemars::schema_for!(
+        ExportFile
+    ))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_v1() {
+        let v1 = serde_json::json!({
+            "version": "1.0",
+            "exported_at": "2025-01-01T00:00:00Z",
+            "tools": [{"name": "ripgrep", "source": "cargo", "installed": true}]
+        });
+        let file = ExportFile::from_value(v1).unwrap();
+        assert_eq!(file.schema_version, SCHEMA_VERSION);
+        assert_eq!(file.tools[0].source.as_deref(), Some("cargo"));
+        assert!(file.bundles.is_empty());
+
+        // Written back, the old version string is gone
+        let written = serde_json::to_value(&file).unwrap();
+        assert!(written.get("version").is_none());
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer() {
+        let mut newer = serde_json::json!({"schema_version": SCHEMA_VERSION + 1, "tools": []});
+        let err = migrate(&mut newer).unwrap_err().to_string();
+        assert!(err.contains("upgrade hoards"), "{}", err);
+
+        let mut bad = serde_json::json!({"schema_version": "two", "tools": []});
+        assert!(migrate(&mut bad).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let tool = |name: &str, source: &str, version: Option<&str>, installed: bool| ExportTool {
+            source: Some(source.to_string()),
+            version: version.map(str::to_string),
+            installed,
+            ..ExportTool::from_tool(&Tool::new(name))
+        };
+        let local = vec![
+            tool("bat", "cargo", Some("0.24.0"), true),
+            tool("fd", "cargo", Some("10.1.0"), true),
+            tool("jq", "apt", None, true),
+            tool("htop", "apt", None, false),
+        ];
+        let remote = vec![
+            tool("bat", "cargo", Some("0.25.0"), true),
+            tool("fd", "cargo", None, true),
+            tool("jq", "brew", Some("1.7.1"), true),
+            tool("htop", "apt", None, true),
+            tool("zoxide", "cargo", None, false),
+        ];
+
+        let comparison = compare(&local, &remote);
+        assert!(comparison.only_local.is_empty());
+        assert_eq!(comparison.only_remote, vec!["htop"]);
+        assert_eq!(
+            comparison.versions,
+            vec![ToolDifference {
+                name: "bat".to_string(),
+                local: "0.24.0".to_string(),
+                remote: "0.25.0".to_string(),
+            }]
+        );
+        assert_eq!(comparison.sources.len(), 1);
+        assert_eq!(comparison.sources[0].remote, "brew");
+        assert!(compare(&local, &local).is_empty());
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema: Value = serde_json::from_str(&json_schema().unwrap()).unwrap();
+        assert_eq!(schema["title"], "hoards export");
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&Value::from("schema_version")));
+        assert!(required.contains(&Value::from("tools")));
+    }
+}
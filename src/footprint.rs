@@ -0,0 +1,206 @@
+//! On-disk footprint of tracked tools
+//!
+//! A tool's footprint is its binary plus what its source keeps for it: the
+//! cargo registry sources and `.crate` archives, its npm package directory,
+//! its Homebrew keg, or the files a pip or apt package installed.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::models::{InstallSource, Tool};
+use crate::sources::{BrewSource, PackageSource, distribution_sizes, npm_global_root};
+
+/// Bytes under a path, without following symlinks
+pub fn path_size(path: &Path) -> u64 {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    std::fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|e| path_size(&e.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Human-readable byte count (`4.2 MB`)
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1000 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Whether a registry file or directory (`ripgrep-14.1.0`,
+/// `ripgrep-14.1.0.crate`) belongs to a crate, at a version when known
+fn is_crate_entry(file_name: &str, name: &str, version: Option<&str>) -> bool {
+    let Some(rest) = file_name
+        .strip_prefix(name)
+        .and_then(|r| r.strip_prefix('-'))
+    else {
+        return false;
+    };
+    let rest = rest.strip_suffix(".crate").unwrap_or(rest);
+    match version {
+        Some(version) => rest == version,
+        None => rest.starts_with(|c: char| c.is_ascii_digit()),
+    }
+}
+
+/// A crate's unpacked sources and downloaded archives in the cargo registry
+fn cargo_registry_paths(name: &str, version: Option<&str>) -> Vec<PathBuf> {
+    let Some(home) = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".cargo")))
+    else {
+        return Vec::new();
+    };
+    let mut paths = Vec::new();
+    for kind in ["src", "cache"] {
+        // One directory per registry index (index.crates.io-<hash>, ...)
+        let Ok(registries) = std::fs::read_dir(home.join("registry").join(kind)) else {
+            continue;
+        };
+        for registry in registries.flatten() {
+            let Ok(entries) = std::fs::read_dir(registry.path()) else {
+                continue;
+            };
+            paths.extend(
+                entries
+                    .flatten()
+                    .filter(|e| is_crate_entry(&e.file_name().to_string_lossy(), name, version))
+                    .map(|e| e.path()),
+            );
+        }
+    }
+    paths
+}
+
+/// Installed-Size dpkg records for a package
+fn dpkg_size(package: &str) -> Option<u64> {
+    let output = Command::new("dpkg-query")
+        .args(["-W", "-f=${Installed-Size}", package])
+        .output()
+        .ok()?;
+    let kib: u64 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+/// Source state shared by every tool being measured
+struct SourceData {
+    pip: HashMap<String, u64>,
+    npm_root: Option<PathBuf>,
+}
+
+/// Footprint of one tool
+fn footprint(tool: &Tool, data: &SourceData) -> u64 {
+    // Sources that report the whole package, binary included
+    let package = match tool.source {
+        InstallSource::Pip => data.pip.get(&tool.name).copied(),
+        InstallSource::Apt => dpkg_size(&tool.name),
+        _ => None,
+    };
+    if let Some(bytes) = package.filter(|b| *b > 0) {
+        return bytes;
+    }
+
+    let dirs: Vec<PathBuf> = match tool.source {
+        InstallSource::Cargo => cargo_registry_paths(&tool.name, tool.installed_version.as_deref()),
+        InstallSource::Npm => data
+            .npm_root
+            .iter()
+            .map(|root| root.join(&tool.name))
+            .collect(),
+        InstallSource::Brew => BrewSource
+            .state_paths()
+            .into_iter()
+            .map(|cellar| cellar.join(&tool.name))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let dirs: Vec<PathBuf> = dirs.iter().filter_map(|d| d.canonicalize().ok()).collect();
+    let mut bytes: u64 = dirs.iter().map(|d| path_size(d)).sum();
+
+    // The binary, unless it lives in one of those directories
+    let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+    let location = if Path::new(binary).is_absolute() {
+        Some(PathBuf::from(binary))
+    } else {
+        which::which(binary).ok()
+    };
+    if let Some(location) = location.and_then(|l| l.canonicalize().ok())
+        && !dirs.iter().any(|d| location.starts_with(d))
+    {
+        bytes += path_size(&location);
+    }
+    bytes
+}
+
+/// Footprint in bytes of each tool, in the order given
+pub fn measure_tools(tools: &[Tool]) -> Vec<(String, u64)> {
+    let has = |source: InstallSource| tools.iter().any(|t| t.source == source);
+    let data = SourceData {
+        pip: if has(InstallSource::Pip) {
+            distribution_sizes()
+        } else {
+            HashMap::new()
+        },
+        npm_root: if has(InstallSource::Npm) {
+            npm_global_root()
+        } else {
+            None
+        },
+    };
+    tools
+        .iter()
+        .map(|t| (t.name.clone(), footprint(t, &data)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(4_200_000), "4.2 MB");
+        assert_eq!(format_size(1_500_000_000), "1.5 GB");
+    }
+
+    #[test]
+    fn test_is_crate_entry() {
+        assert!(is_crate_entry("ripgrep-14.1.0", "ripgrep", Some("14.1.0")));
+        assert!(is_crate_entry(
+            "ripgrep-14.1.0.crate",
+            "ripgrep",
+            Some("14.1.0")
+        ));
+        assert!(!is_crate_entry("ripgrep-13.0.0", "ripgrep", Some("14.1.0")));
+        assert!(is_crate_entry("ripgrep-13.0.0", "ripgrep", None));
+        // Other crates sharing the prefix
+        assert!(!is_crate_entry("ripgrep-all-0.10.6", "ripgrep", None));
+        assert!(!is_crate_entry("grep-0.3.1", "ripgrep", None));
+    }
+
+    #[test]
+    fn test_path_size() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a"), [0u8; 100]).unwrap();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub/b"), [0u8; 50]).unwrap();
+        assert_eq!(path_size(dir.path()), 150);
+        assert_eq!(path_size(&dir.path().join("missing")), 0);
+    }
+}
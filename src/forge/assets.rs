@@ -0,0 +1,595 @@
+//! Prebuilt binaries from release assets
+//!
+//! Picks the release asset built for the current platform, downloads it and
+//! installs the executable it contains. Downloads are checked against the
+//! release's published SHA-256 sums when there are any. Archives are
+//! unpacked with the system `tar` and `unzip`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use sha2::{Digest, Sha256};
+
+use super::{Release, ReleaseAsset};
+use crate::http::DOWNLOAD_AGENT;
+
+/// How a release asset is packaged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    /// Compressed tarball (gzip, xz, bzip2 or zstd)
+    Tarball,
+    Zip,
+    /// A bare executable
+    Binary,
+}
+
+/// Tarball extensions understood by `tar -xf`
+const TARBALL_EXTENSIONS: &[&str] = &[
+    ".tar.gz", ".tgz", ".tar.xz", ".txz", ".tar.bz2", ".tbz", ".tar.zst",
+];
+
+/// Assets that are never installable: checksums, signatures, metadata,
+/// distro packages and installers
+const SKIPPED_EXTENSIONS: &[&str] = &[
+    ".sha256",
+    ".sha512",
+    ".sha256sum",
+    ".md5",
+    ".sig",
+    ".asc",
+    ".pem",
+    ".sbom",
+    ".json",
+    ".jsonl",
+    ".txt",
+    ".deb",
+    ".rpm",
+    ".apk",
+    ".msi",
+    ".pkg",
+    ".dmg",
+    ".appimage",
+    ".vsix",
+    ".whl",
+    ".crate",
+    ".tar",
+    ".gz",
+    ".xz",
+    ".7z",
+    ".sh",
+    ".ps1",
+];
+
+impl AssetKind {
+    /// Packaging of an asset, or None when it is not an installable binary
+    pub fn of(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        if TARBALL_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) {
+            return Some(Self::Tarball);
+        }
+        if name.ends_with(".zip") {
+            return Some(Self::Zip);
+        }
+        if SKIPPED_EXTENSIONS.iter().any(|ext| name.ends_with(ext))
+            || name.contains("checksum")
+            || name.contains("sha256")
+        {
+            return None;
+        }
+        // Bare binaries have no extension, or a version-like "extension"
+        let has_extension = name.rsplit_once('.').is_some_and(|(_, ext)| {
+            !ext.is_empty() && ext.len() <= 4 && ext.chars().all(|c| c.is_ascii_alphabetic())
+        });
+        (name.ends_with(".exe") || !has_extension).then_some(Self::Binary)
+    }
+}
+
+/// Names an operating system goes by in asset names
+fn os_aliases(os: &str) -> &'static [&'static str] {
+    match os {
+        "linux" => &["linux", "linux64"],
+        "macos" => &["darwin", "macos", "apple", "osx", "mac"],
+        "windows" => &["windows", "win64", "win32", "win"],
+        "freebsd" => &["freebsd"],
+        _ => &[],
+    }
+}
+
+/// Names a CPU architecture goes by in asset names
+///
+/// `x86_64` is normalized to `amd64` before matching (see `tokens`).
+fn arch_aliases(arch: &str) -> &'static [&'static str] {
+    match arch {
+        "x86_64" => &["amd64", "x64"],
+        "aarch64" => &["aarch64", "arm64"],
+        "x86" => &["i686", "i386", "386", "x86"],
+        "arm" => &["armv7", "armv6", "armhf", "arm"],
+        _ => &[],
+    }
+}
+
+const KNOWN_OSES: &[&str] = &["linux", "macos", "windows", "freebsd"];
+const KNOWN_ARCHES: &[&str] = &["x86_64", "aarch64", "x86", "arm"];
+
+/// Lowercase name split into alphanumeric tokens
+fn tokens(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .replace("x86_64", "amd64")
+        .replace("x86-64", "amd64")
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// How well an asset fits a platform, or None if it is for another one
+fn asset_score(asset: &ReleaseAsset, os: &str, arch: &str) -> Option<i32> {
+    let kind = AssetKind::of(&asset.name)?;
+    let tokens = tokens(&asset.name);
+    let has_any = |aliases: &[&str]| tokens.iter().any(|t| aliases.contains(&t.as_str()));
+
+    // The OS must be named, and no other OS may be
+    if !has_any(os_aliases(os)) {
+        return None;
+    }
+    if KNOWN_OSES
+        .iter()
+        .filter(|other| **other != os)
+        .any(|other| has_any(os_aliases(other)))
+    {
+        return None;
+    }
+
+    let mut score = if has_any(arch_aliases(arch)) {
+        4
+    } else if KNOWN_ARCHES
+        .iter()
+        .filter(|other| **other != arch)
+        .any(|other| has_any(arch_aliases(other)))
+    {
+        return None;
+    } else if os == "macos" && has_any(&["universal", "all"]) {
+        3
+    } else {
+        // No architecture in the name; often amd64 only
+        1
+    };
+
+    // Statically linked builds run on any distribution
+    if os == "linux" {
+        if has_any(&["musl", "static"]) {
+            score += 2;
+        } else if has_any(&["gnu", "glibc"]) {
+            score += 1;
+        }
+    }
+
+    score += match kind {
+        AssetKind::Tarball => 2,
+        AssetKind::Zip if os == "windows" => 2,
+        AssetKind::Zip => 1,
+        AssetKind::Binary => 0,
+    };
+    Some(score)
+}
+
+/// Best asset for an OS and architecture (Rust `std::env::consts` names)
+pub fn pick_asset<'a>(
+    assets: &'a [ReleaseAsset],
+    os: &str,
+    arch: &str,
+) -> Option<&'a ReleaseAsset> {
+    let mut best: Option<(&ReleaseAsset, i32)> = None;
+    for asset in assets {
+        if let Some(score) = asset_score(asset, os, arch)
+            && best.is_none_or(|(_, s)| score > s)
+        {
+            best = Some((asset, score));
+        }
+    }
+    best.map(|(asset, _)| asset)
+}
+
+/// Asset of a release built for the current platform
+pub fn platform_asset(release: &Release) -> Option<&ReleaseAsset> {
+    pick_asset(
+        &release.assets,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    )
+}
+
+/// Current platform as "os/arch"
+pub fn platform() -> String {
+    format!("{}/{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+/// The release asset holding the SHA-256 sum of `name`
+///
+/// A per-asset `<name>.sha256` file wins over a combined checksums list.
+pub fn checksum_asset<'a>(assets: &'a [ReleaseAsset], name: &str) -> Option<&'a ReleaseAsset> {
+    let own = [format!("{}.sha256", name), format!("{}.sha256sum", name)];
+    assets
+        .iter()
+        .find(|a| own.iter().any(|n| a.name.eq_ignore_ascii_case(n)))
+        .or_else(|| {
+            assets.iter().find(|a| {
+                let lower = a.name.to_lowercase();
+                (lower.contains("checksums") || lower.contains("sha256sums"))
+                    && !lower.contains("sha512")
+                    && ![".sig", ".asc", ".pem"]
+                        .iter()
+                        .any(|ext| lower.ends_with(ext))
+            })
+        })
+}
+
+/// The sum listed for `name` in a `sha256sum`-style listing
+///
+/// Lines are `<hex>  <file>` (a `*` marks binary mode); a file holding only
+/// a hash is taken as the sum of the asset it was published for.
+pub fn expected_sha256(listing: &str, name: &str) -> Option<String> {
+    let is_sum = |s: &str| s.len() == 64 && s.bytes().all(|b| b.is_ascii_hexdigit());
+    let mut lines = listing.lines().map(str::split_whitespace);
+    let mut bare = None;
+    let found = lines.find_map(|mut fields| {
+        let hash = fields.next().filter(|h| is_sum(h))?;
+        match fields.next() {
+            Some(file) => {
+                let file = file.trim_start_matches('*');
+                let file = file.rsplit('/').next().unwrap_or(file);
+                (file == name).then(|| hash.to_lowercase())
+            }
+            None => {
+                bare.get_or_insert_with(|| hash.to_lowercase());
+                None
+            }
+        }
+    });
+    found.or(bare)
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut file = fs::File::open(path)?;
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// Download an asset and install the executable `binary` into `bin_dir`
+///
+/// Archives are searched for `binary`, falling back to their only
+/// executable, which keeps its own name (ripgrep ships `rg`). Bare binary
+/// assets are installed as `binary`. When `release` publishes checksums,
+/// the download must match them. Returns the installed path.
+pub fn install_asset(
+    release: &Release,
+    asset: &ReleaseAsset,
+    binary: &str,
+    bin_dir: &Path,
+) -> Result<PathBuf> {
+    let kind = AssetKind::of(&asset.name)
+        .with_context(|| format!("{} is not an installable binary", asset.name))?;
+    if asset.name.contains(['/', '\\']) || asset.name.contains("..") {
+        bail!("Refusing asset with unsafe name: {}", asset.name);
+    }
+
+    let work = tempfile::Builder::new()
+        .prefix("hoards-")
+        .tempdir()
+        .context("Failed to create a download directory")?;
+    let checksums = checksum_asset(&release.assets, &asset.name);
+    install_from(asset, checksums, kind, binary, bin_dir, work.path())
+}
+
+fn install_from(
+    asset: &ReleaseAsset,
+    checksums: Option<&ReleaseAsset>,
+    kind: AssetKind,
+    binary: &str,
+    bin_dir: &Path,
+    work: &Path,
+) -> Result<PathBuf> {
+    let download = work.join(&asset.name);
+    download_to(&asset.url, &download)?;
+    if let Some(checksums) = checksums {
+        verify_download(&download, &asset.name, checksums, work)?;
+    }
+
+    let (executable, file_name) = match kind {
+        AssetKind::Binary => {
+            let file_name = if asset.name.to_lowercase().ends_with(".exe") {
+                format!("{}.exe", binary)
+            } else {
+                binary.to_string()
+            };
+            (download, file_name)
+        }
+        AssetKind::Tarball | AssetKind::Zip => {
+            let unpacked = work.join("unpacked");
+            fs::create_dir_all(&unpacked)?;
+            extract(&download, kind, &unpacked)?;
+            let executable = find_executable(&unpacked, binary)?;
+            let file_name = executable
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(binary)
+                .to_string();
+            (executable, file_name)
+        }
+    };
+
+    fs::create_dir_all(bin_dir)
+        .with_context(|| format!("Failed to create {}", bin_dir.display()))?;
+    let target = bin_dir.join(file_name);
+
+    // Replace rather than overwrite, so a running copy keeps working
+    if target.exists() {
+        fs::remove_file(&target)
+            .with_context(|| format!("Failed to replace {}", target.display()))?;
+    }
+    fs::copy(&executable, &target)
+        .with_context(|| format!("Failed to install {}", target.display()))?;
+    make_executable(&target)?;
+    Ok(target)
+}
+
+fn download_to(url: &str, dest: &Path) -> Result<()> {
+    let mut response = DOWNLOAD_AGENT
+        .get(url)
+        .header("User-Agent", "hoards-cli")
+        .call()
+        .with_context(|| format!("Failed to download {}", url))?;
+    let mut file = fs::File::create(dest)?;
+    io::copy(&mut response.body_mut().as_reader(), &mut file)
+        .with_context(|| format!("Failed to download {}", url))?;
+    Ok(())
+}
+
+/// Check a download against the sum published in `checksums`
+fn verify_download(
+    download: &Path,
+    name: &str,
+    checksums: &ReleaseAsset,
+    work: &Path,
+) -> Result<()> {
+    let listing_path = work.join("checksums");
+    download_to(&checksums.url, &listing_path)?;
+    let listing = fs::read_to_string(&listing_path)
+        .with_context(|| format!("{} is not a checksum list", checksums.name))?;
+    let expected = expected_sha256(&listing, name)
+        .with_context(|| format!("{} lists no SHA-256 sum for {}", checksums.name, name))?;
+    let actual = sha256_file(download)?;
+    if actual != expected {
+        bail!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            name,
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+fn extract(archive: &Path, kind: AssetKind, dest: &Path) -> Result<()> {
+    let (program, mut cmd) = match kind {
+        AssetKind::Tarball => {
+            let mut cmd = Command::new("tar");
+            cmd.arg("-xf").arg(archive).arg("-C").arg(dest);
+            ("tar", cmd)
+        }
+        AssetKind::Zip => {
+            let mut cmd = Command::new("unzip");
+            cmd.args(["-q", "-o"]).arg(archive).arg("-d").arg(dest);
+            ("unzip", cmd)
+        }
+        AssetKind::Binary => return Ok(()),
+    };
+
+    let output = cmd
+        .output()
+        .with_context(|| format!("Failed to run {} (is it installed?)", program))?;
+    if !output.status.success() {
+        bail!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Find the executable named `binary` in an unpacked archive
+///
+/// Falls back to the only executable file when none has that name.
+fn find_executable(dir: &Path, binary: &str) -> Result<PathBuf> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+
+    let named = files.iter().find(|path| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|name| name == binary || name.strip_suffix(".exe") == Some(binary))
+    });
+    if let Some(path) = named {
+        return Ok(path.clone());
+    }
+
+    let executables: Vec<&PathBuf> = files.iter().filter(|p| is_executable(p)).collect();
+    match executables.as_slice() {
+        [only] => Ok((*only).clone()),
+        [] => bail!("No executable found in the archive"),
+        _ => bail!(
+            "No '{}' in the archive; pick one with --bin ({})",
+            binary,
+            executables
+                .iter()
+                .filter_map(|p| p.file_name()?.to_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "exe")
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assets(names: &[&str]) -> Vec<ReleaseAsset> {
+        names
+            .iter()
+            .map(|name| ReleaseAsset {
+                name: name.to_string(),
+                url: format!("https://example.com/{}", name),
+                size: 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_asset_kind() {
+        assert_eq!(
+            AssetKind::of("rg-14.1.0-x86_64-unknown-linux-musl.tar.gz"),
+            Some(AssetKind::Tarball)
+        );
+        assert_eq!(
+            AssetKind::of("fd-v10.1.0-x86_64-pc-windows-msvc.zip"),
+            Some(AssetKind::Zip)
+        );
+        assert_eq!(AssetKind::of("jq-linux-amd64"), Some(AssetKind::Binary));
+        assert_eq!(AssetKind::of("yq_linux_amd64"), Some(AssetKind::Binary));
+        assert_eq!(AssetKind::of("tool-v1.2.3-linux"), Some(AssetKind::Binary));
+        assert_eq!(
+            AssetKind::of("jq-windows-amd64.exe"),
+            Some(AssetKind::Binary)
+        );
+        assert_eq!(AssetKind::of("rg-14.1.0.tar.gz.sha256"), None);
+        assert_eq!(AssetKind::of("ripgrep_14.1.0-1_amd64.deb"), None);
+        assert_eq!(AssetKind::of("checksums.txt"), None);
+        assert_eq!(AssetKind::of("tool.AppImage"), None);
+    }
+
+    #[test]
+    fn test_pick_asset() {
+        let ripgrep = assets(&[
+            "ripgrep-14.1.0-aarch64-apple-darwin.tar.gz",
+            "ripgrep-14.1.0-x86_64-apple-darwin.tar.gz",
+            "ripgrep-14.1.0-x86_64-pc-windows-msvc.zip",
+            "ripgrep-14.1.0-x86_64-unknown-linux-gnu.tar.gz",
+            "ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz",
+            "ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz.sha256",
+            "ripgrep_14.1.0-1_amd64.deb",
+        ]);
+        let pick = |os: &str, arch: &str| pick_asset(&ripgrep, os, arch).map(|a| a.name.as_str());
+
+        assert_eq!(
+            pick("linux", "x86_64"),
+            Some("ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz")
+        );
+        assert_eq!(
+            pick("macos", "aarch64"),
+            Some("ripgrep-14.1.0-aarch64-apple-darwin.tar.gz")
+        );
+        assert_eq!(
+            pick("windows", "x86_64"),
+            Some("ripgrep-14.1.0-x86_64-pc-windows-msvc.zip")
+        );
+        assert!(pick("linux", "aarch64").is_none());
+
+        // Bare binaries, Go-style names
+        let jq = assets(&["jq-linux-amd64", "jq-linux-arm64", "jq-macos-arm64"]);
+        assert_eq!(
+            pick_asset(&jq, "linux", "aarch64").map(|a| a.name.as_str()),
+            Some("jq-linux-arm64")
+        );
+        // "darwin" must not be mistaken for Windows
+        assert!(pick_asset(&jq, "windows", "x86_64").is_none());
+
+        let universal = assets(&["tool-darwin-universal.tar.gz", "tool-linux.tar.gz"]);
+        assert_eq!(
+            pick_asset(&universal, "macos", "aarch64").map(|a| a.name.as_str()),
+            Some("tool-darwin-universal.tar.gz")
+        );
+    }
+
+    #[test]
+    fn test_checksums() {
+        let release = assets(&[
+            "tool-linux.tar.gz",
+            "tool-linux.tar.gz.sha256",
+            "tool-macos.tar.gz",
+            "tool_1.0_checksums.txt",
+            "tool_1.0_checksums.txt.sig",
+        ]);
+        let sums = |name| checksum_asset(&release, name).map(|a| a.name.as_str());
+        assert_eq!(sums("tool-linux.tar.gz"), Some("tool-linux.tar.gz.sha256"));
+        assert_eq!(sums("tool-macos.tar.gz"), Some("tool_1.0_checksums.txt"));
+        assert!(checksum_asset(&assets(&["tool-linux"]), "tool-linux").is_none());
+
+        let a = "a".repeat(64);
+        let b = "B".repeat(64);
+        let listing = format!("{}  tool-linux.tar.gz\n{} *dist/tool-macos.tar.gz\n", a, b);
+        assert_eq!(
+            expected_sha256(&listing, "tool-linux.tar.gz"),
+            Some(a.clone())
+        );
+        assert_eq!(
+            expected_sha256(&listing, "tool-macos.tar.gz"),
+            Some("b".repeat(64))
+        );
+        assert_eq!(expected_sha256(&listing, "tool-windows.zip"), None);
+        assert_eq!(expected_sha256(&format!("{}\n", a), "anything"), Some(a));
+        assert_eq!(expected_sha256("not-a-hash  tool", "tool"), None);
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hello");
+        fs::write(&file, "hello\n").unwrap();
+        assert_eq!(
+            sha256_file(&file).unwrap(),
+            "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"
+        );
+    }
+}
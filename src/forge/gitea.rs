@@ -0,0 +1,186 @@
+//! Gitea-compatible forges (Gitea, Forgejo, Codeberg)
+//!
+//! Uses the public `/api/v1` REST API; no token is needed for public repos.
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use super::{Forge, ForgeProvider, Release, RepoInfo, RepoLicense, RepoOwner, SearchResult};
+use crate::http::HTTP_AGENT;
+
+/// A Gitea-compatible forge on one host
+pub struct GiteaForge {
+    host: String,
+}
+
+impl GiteaForge {
+    pub fn new(host: impl Into<String>) -> Self {
+        Self { host: host.into() }
+    }
+
+    fn get(&self, path: &str, query: &[(&str, &str)]) -> Result<String> {
+        let url = format!("https://{}/api/v1/{}", self.host, path);
+        let mut response = HTTP_AGENT
+            .get(&url)
+            .header("User-Agent", "hoards-cli")
+            .query_pairs(query.iter().copied())
+            .call()
+            .with_context(|| format!("Failed to reach {}", self.host))?;
+        Ok(response.body_mut().read_to_string()?)
+    }
+}
+
+/// Repository as returned by the Gitea API
+#[derive(Debug, Deserialize)]
+struct GiteaRepo {
+    name: String,
+    full_name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    stars_count: i64,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    website: Option<String>,
+    #[serde(default)]
+    topics: Option<Vec<String>>,
+    /// SPDX identifiers (Gitea 1.22+)
+    #[serde(default)]
+    licenses: Option<Vec<String>>,
+    /// Last change to the repository (Gitea has no separate push time)
+    #[serde(default)]
+    updated_at: Option<String>,
+    #[serde(default)]
+    open_issues_count: i64,
+    #[serde(default)]
+    archived: bool,
+    owner: RepoOwner,
+}
+
+/// Gitea reports unset fields as empty strings rather than null
+fn non_empty(s: Option<String>) -> Option<String> {
+    s.filter(|s| !s.is_empty())
+}
+
+impl From<GiteaRepo> for RepoInfo {
+    fn from(repo: GiteaRepo) -> Self {
+        RepoInfo {
+            name: repo.name,
+            full_name: repo.full_name,
+            description: non_empty(repo.description),
+            stars: repo.stars_count,
+            language: non_empty(repo.language),
+            homepage: non_empty(repo.website),
+            topics: repo.topics.unwrap_or_default(),
+            license: repo
+                .licenses
+                .and_then(|l| l.into_iter().next())
+                .map(|id| RepoLicense {
+                    spdx_id: Some(id.clone()),
+                    name: id,
+                }),
+            pushed_at: repo.updated_at,
+            open_issues: repo.open_issues_count,
+            archived: repo.archived,
+            owner: repo.owner,
+        }
+    }
+}
+
+impl From<GiteaRepo> for SearchResult {
+    fn from(repo: GiteaRepo) -> Self {
+        SearchResult {
+            name: repo.name,
+            full_name: repo.full_name,
+            description: non_empty(repo.description),
+            stars: repo.stars_count,
+            owner: repo.owner,
+        }
+    }
+}
+
+impl Forge for GiteaForge {
+    fn provider(&self) -> ForgeProvider {
+        ForgeProvider::Gitea
+    }
+
+    fn host(&self) -> &str {
+        &self.host
+    }
+
+    fn repo_info(&self, owner: &str, repo: &str) -> Result<RepoInfo> {
+        let body = self.get(&format!("repos/{}/{}", owner, repo), &[])?;
+        let repo: GiteaRepo =
+            serde_json::from_str(&body).context("Failed to parse repository response")?;
+        Ok(repo.into())
+    }
+
+    fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        let body = self.get(
+            &format!("repos/{}/{}/releases", owner, repo),
+            &[("limit", "30")],
+        )?;
+        serde_json::from_str(&body).context("Failed to parse releases response")
+    }
+
+    fn readme(&self, owner: &str, repo: &str) -> Result<String> {
+        for name in ["README.md", "README", "readme.md", "README.rst"] {
+            if let Ok(body) = self.get(&format!("repos/{}/{}/raw/{}", owner, repo, name), &[]) {
+                return Ok(body);
+            }
+        }
+        bail!("No README found in {}/{}", owner, repo)
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        #[derive(Deserialize)]
+        struct SearchResponse {
+            data: Vec<GiteaRepo>,
+        }
+
+        let limit = limit.to_string();
+        let body = self.get(
+            "repos/search",
+            &[
+                ("q", query),
+                ("limit", &limit),
+                ("sort", "stars"),
+                ("order", "desc"),
+            ],
+        )?;
+        let response: SearchResponse =
+            serde_json::from_str(&body).context("Failed to parse search response")?;
+        Ok(response.data.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitea_repo_mapping() {
+        let json = r#"{
+            "name": "foot",
+            "full_name": "dnkl/foot",
+            "description": "A fast terminal emulator",
+            "stars_count": 1234,
+            "language": "",
+            "website": "",
+            "owner": {"login": "dnkl", "id": 1}
+        }"#;
+        let repo: GiteaRepo = serde_json::from_str(json).unwrap();
+        let info = RepoInfo::from(repo);
+        assert_eq!(info.full_name, "dnkl/foot");
+        assert_eq!(info.stars, 1234);
+        assert_eq!(
+            info.description.as_deref(),
+            Some("A fast terminal emulator")
+        );
+        assert_eq!(info.language, None);
+        assert_eq!(info.homepage, None);
+        assert!(info.topics.is_empty());
+        assert_eq!(info.owner.login, "dnkl");
+    }
+}
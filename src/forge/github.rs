@@ -0,0 +1,868 @@
+//! GitHub integration for fetching repo info, topics, and descriptions
+//!
+//! Talks to the GitHub REST API directly, authenticated with a token from
+//! the environment, the OS keyring or the `gh` CLI when one is available and
+//! anonymously otherwise. Requests go through the [`scheduler`](super::scheduler),
+//! which paces them by GitHub's rate-limit headers and deduplicates them.
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::process::Command;
+use std::sync::LazyLock;
+
+use crate::config::keyring_secret;
+use crate::http::HTTP_AGENT;
+
+use super::scheduler::{self, Quota};
+use super::{Forge, ForgeProvider, GITHUB_HOST, Readme, Release, RepoInfo, SearchResult};
+
+/// GitHub, queried through its REST API
+pub struct GitHubForge;
+
+impl Forge for GitHubForge {
+    fn provider(&self) -> ForgeProvider {
+        ForgeProvider::GitHub
+    }
+
+    fn host(&self) -> &str {
+        GITHUB_HOST
+    }
+
+    fn repo_info(&self, owner: &str, repo: &str) -> Result<RepoInfo> {
+        get_repo_info(owner, repo)
+    }
+
+    fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        list_releases(owner, repo)
+    }
+
+    fn readme(&self, owner: &str, repo: &str) -> Result<String> {
+        fetch_readme(owner, repo)
+    }
+
+    fn readme_if_changed(
+        &self,
+        owner: &str,
+        repo: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<Readme>> {
+        fetch_readme_if_changed(owner, repo, etag)
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        search_repos(query, limit)
+    }
+
+    fn contributor_count(&self, owner: &str, repo: &str) -> Result<Option<i64>> {
+        contributor_count(owner, repo).map(Some)
+    }
+}
+
+/// GitHub REST API base URL
+const API_URL: &str = "https://api.github.com";
+
+/// Token for the GitHub API, resolved once per process
+///
+/// Looked up in `GITHUB_TOKEN`, `GH_TOKEN`, the OS keyring (service `hoards`,
+/// key `github`) and finally `gh auth token` when the GitHub CLI is installed.
+static TOKEN: LazyLock<Option<String>> = LazyLock::new(|| {
+    ["GITHUB_TOKEN", "GH_TOKEN"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|token| !token.trim().is_empty())
+        .or_else(|| keyring_secret("github"))
+        .or_else(gh_cli_token)
+});
+
+/// Token stored by the GitHub CLI, if it is installed and logged in
+fn gh_cli_token() -> Option<String> {
+    let output = Command::new("gh").args(["auth", "token"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+/// Media type returning file contents as-is
+const RAW_ACCEPT: &str = "application/vnd.github.raw+json";
+
+/// Build a GET request for a GitHub API path, authenticated when a token is available
+///
+/// Error statuses are not turned into errors, so the scheduler can read the
+/// rate-limit headers of rejections.
+fn api_request(
+    path: &str,
+    query: &[(&str, &str)],
+    accept: &str,
+) -> ureq::RequestBuilder<ureq::typestate::WithoutBody> {
+    let url = format!("{}/{}", API_URL, path);
+    let request = HTTP_AGENT
+        .get(&url)
+        .header("User-Agent", "hoards-cli")
+        .header("Accept", accept)
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query_pairs(query.iter().copied())
+        .config()
+        .http_status_as_error(false)
+        .build();
+    match TOKEN.as_deref() {
+        Some(token) => request.header("Authorization", &format!("Bearer {}", token)),
+        None => request,
+    }
+}
+
+/// Send a GET through the scheduler, failing on error statuses
+fn api_fetch(path: &str, query: &[(&str, &str)], accept: &str) -> Result<String> {
+    let mut response = scheduler::send(path, || api_request(path, query, accept))?;
+    if !response.status().is_success() {
+        bail!(
+            "GitHub API request failed: {} ({})",
+            path,
+            response.status()
+        );
+    }
+    Ok(response.body_mut().read_to_string()?)
+}
+
+/// GET a GitHub API path and return the body, sharing identical requests in flight
+fn api_get(path: &str, query: &[(&str, &str)], accept: &str) -> Result<String> {
+    let query_string: Vec<String> = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    let key = format!("{} {}?{}", accept, path, query_string.join("&"));
+    scheduler::shared(&key, || api_fetch(path, query, accept))
+}
+
+/// GET a GitHub API path and parse the JSON response
+fn api_json<T: DeserializeOwned>(path: &str, query: &[(&str, &str)]) -> Result<T> {
+    let body = api_get(path, query, "application/vnd.github+json")?;
+    serde_json::from_str(&body).with_context(|| format!("Failed to parse response for {}", path))
+}
+
+/// GitHub API rate limit info
+#[derive(Debug, Clone, Deserialize)]
+pub struct RateLimit {
+    pub limit: i64,
+    pub remaining: i64,
+    pub reset: i64,
+    pub used: i64,
+}
+
+impl RateLimit {
+    /// Minutes until rate limit resets
+    pub fn reset_minutes(&self) -> i64 {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        ((self.reset - now) / 60).max(0)
+    }
+
+    /// Seconds until rate limit resets
+    pub fn reset_seconds(&self) -> i64 {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        (self.reset - now).max(0)
+    }
+
+    /// Check if we have enough remaining calls
+    pub fn has_remaining(&self, needed: i64) -> bool {
+        self.remaining >= needed
+    }
+}
+
+/// Combined rate limit info (core + search)
+#[derive(Debug, Clone)]
+pub struct RateLimits {
+    pub core: RateLimit,
+    pub search: RateLimit,
+}
+
+/// Get current GitHub API rate limit status (core API)
+pub fn get_rate_limit() -> Result<RateLimit> {
+    Ok(get_all_rate_limits()?.core)
+}
+
+/// Get Search API rate limit (per minute - stricter!)
+pub fn get_search_rate_limit() -> Result<RateLimit> {
+    Ok(get_all_rate_limits()?.search)
+}
+
+/// Get both core and search rate limits
+pub fn get_all_rate_limits() -> Result<RateLimits> {
+    #[derive(Deserialize)]
+    struct Resources {
+        core: RateLimit,
+        search: RateLimit,
+    }
+
+    #[derive(Deserialize)]
+    struct Response {
+        resources: Resources,
+    }
+
+    // Checking the rate limit does not count against it; always ask afresh
+    let body = api_fetch("rate_limit", &[], "application/vnd.github+json")?;
+    let response: Response =
+        serde_json::from_str(&body).context("Failed to parse rate limit response")?;
+    for (resource, limit) in [
+        ("core", &response.resources.core),
+        ("search", &response.resources.search),
+    ] {
+        scheduler::update_quota(
+            resource,
+            Quota {
+                limit: limit.limit,
+                remaining: limit.remaining,
+                reset: limit.reset,
+            },
+        );
+    }
+    Ok(RateLimits {
+        core: response.resources.core,
+        search: response.resources.search,
+    })
+}
+
+/// Check if an authenticated GitHub API is available
+///
+/// Everything also works anonymously, but with much lower rate limits
+/// (60 requests/hour), and starred repositories need a token.
+pub fn is_gh_available() -> bool {
+    TOKEN.is_some()
+}
+
+/// Map installation source to GitHub language filter for better search accuracy
+pub fn source_to_language_filter(source: Option<&str>) -> Option<&'static str> {
+    match source {
+        Some("cargo") => Some("language:rust"),
+        Some("pip") => Some("language:python"),
+        Some("npm") => Some("language:javascript OR language:typescript"),
+        Some("go") => Some("language:go"),
+        _ => None,
+    }
+}
+
+/// Search GitHub for a repository by name, optionally using source for language filtering
+pub fn search_repo(name: &str, source: Option<&str>) -> Result<Option<SearchResult>> {
+    // Build search query with language filter based on installation source
+    let query = match source_to_language_filter(source) {
+        Some(lang_filter) => format!("{} {}", name, lang_filter),
+        None => name.to_string(),
+    };
+
+    Ok(search_repos(&query, 1)?.into_iter().next())
+}
+
+/// Search GitHub repositories, best matches first
+pub fn search_repos(query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+    #[derive(Deserialize)]
+    struct Response {
+        items: Vec<SearchResult>,
+    }
+
+    let limit = limit.clamp(1, 100).to_string();
+    let response: Response =
+        api_json("search/repositories", &[("q", query), ("per_page", &limit)])?;
+    Ok(response.items)
+}
+
+/// Search GitHub repositories tagged with a topic, most starred first
+pub fn search_topic(topic: &str, limit: usize) -> Result<Vec<SearchResult>> {
+    #[derive(Deserialize)]
+    struct Response {
+        items: Vec<SearchResult>,
+    }
+
+    let query = format!("topic:{}", topic.trim().to_lowercase());
+    let limit = limit.clamp(1, 100).to_string();
+    let response: Response = api_json(
+        "search/repositories",
+        &[
+            ("q", &query),
+            ("sort", "stars"),
+            ("order", "desc"),
+            ("per_page", &limit),
+        ],
+    )?;
+    Ok(response.items)
+}
+
+/// Get detailed repo info including topics
+pub fn get_repo_info(owner: &str, repo: &str) -> Result<RepoInfo> {
+    api_json(&format!("repos/{}/{}", owner, repo), &[])
+}
+
+/// List the authenticated user's starred repositories, most recently starred first
+pub fn list_starred() -> Result<Vec<RepoInfo>> {
+    if !is_gh_available() {
+        bail!("Listing starred repositories needs a GitHub token (set GITHUB_TOKEN)");
+    }
+
+    let mut starred = Vec::new();
+    for page in 1.. {
+        let page = page.to_string();
+        let repos: Vec<RepoInfo> =
+            api_json("user/starred", &[("per_page", "100"), ("page", &page)])?;
+        if repos.is_empty() {
+            break;
+        }
+        starred.extend(repos);
+    }
+    Ok(starred)
+}
+
+/// Topics that mark a repository as a command-line tool
+const CLI_TOPICS: &[&str] = &[
+    "cli",
+    "cli-app",
+    "command-line",
+    "command-line-tool",
+    "commandline",
+    "terminal",
+    "tui",
+    "shell",
+    "console",
+];
+
+/// Heuristic: does a repository look like a command-line tool?
+pub fn looks_like_cli_tool(info: &RepoInfo) -> bool {
+    if info
+        .topics
+        .iter()
+        .any(|t| CLI_TOPICS.contains(&t.to_lowercase().as_str()))
+    {
+        return true;
+    }
+
+    let desc = info.description.as_deref().unwrap_or("").to_lowercase();
+    desc.contains("command-line")
+        || desc.contains("command line")
+        || desc
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| matches!(word, "cli" | "terminal" | "tui"))
+}
+
+/// Fetch the most recent releases of a repository, newest first
+pub fn list_releases(owner: &str, repo: &str) -> Result<Vec<Release>> {
+    api_json(
+        &format!("repos/{}/{}/releases", owner, repo),
+        &[("per_page", "30")],
+    )
+}
+
+/// Fetch README content from GitHub
+pub fn fetch_readme(owner: &str, repo: &str) -> Result<String> {
+    api_get(&format!("repos/{}/{}/readme", owner, repo), &[], RAW_ACCEPT)
+        .context("Failed to fetch README")
+}
+
+/// Fetch the README unless it still matches `etag`
+///
+/// A `304 Not Modified` answer returns `None` and does not count against the
+/// rate limit.
+pub fn fetch_readme_if_changed(
+    owner: &str,
+    repo: &str,
+    etag: Option<&str>,
+) -> Result<Option<Readme>> {
+    let path = format!("repos/{}/{}/readme", owner, repo);
+    let mut response = scheduler::send(&path, || {
+        let request = api_request(&path, &[], RAW_ACCEPT);
+        match etag {
+            Some(etag) => request.header("If-None-Match", etag),
+            None => request,
+        }
+    })
+    .context("Failed to fetch README")?;
+    if response.status() == 304 {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        bail!("Failed to fetch README ({})", response.status());
+    }
+    let etag = response
+        .headers()
+        .get("etag")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    Ok(Some(Readme {
+        content: response.body_mut().read_to_string()?,
+        etag,
+    }))
+}
+
+/// Count a repository's contributors (including anonymous ones)
+///
+/// Requests one contributor per page, so the page number of the `last`
+/// link is the total.
+pub fn contributor_count(owner: &str, repo: &str) -> Result<i64> {
+    let path = format!("repos/{}/{}/contributors", owner, repo);
+    let mut response = scheduler::send(&path, || {
+        api_request(
+            &path,
+            &[("per_page", "1"), ("anon", "true")],
+            "application/vnd.github+json",
+        )
+    })?;
+    if !response.status().is_success() {
+        bail!(
+            "GitHub API request failed: {} ({})",
+            path,
+            response.status()
+        );
+    }
+
+    let last_page = response
+        .headers()
+        .get("link")
+        .and_then(|v| v.to_str().ok())
+        .and_then(last_page_from_link);
+    if let Some(last) = last_page {
+        return Ok(last);
+    }
+
+    // Fewer than two contributors: no pagination (empty repos answer 204)
+    let body = response.body_mut().read_to_string().unwrap_or_default();
+    let contributors: Vec<serde_json::Value> = serde_json::from_str(&body).unwrap_or_default();
+    Ok(contributors.len() as i64)
+}
+
+/// Page number of the `rel="last"` entry in a `Link` header
+fn last_page_from_link(link: &str) -> Option<i64> {
+    link.split(',')
+        .find(|part| part.contains(r#"rel="last""#))?
+        .split(['?', '&', '>'])
+        .find_map(|param| param.strip_prefix("page="))?
+        .parse()
+        .ok()
+}
+
+/// Fetch the latest commit SHA for a repo (used for cache versioning)
+pub fn fetch_repo_version(owner: &str, repo: &str) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Commit {
+        sha: String,
+    }
+
+    let commit: Commit = api_json(&format!("repos/{}/{}/commits/HEAD", owner, repo), &[])
+        .context("Failed to fetch repo version")?;
+    Ok(commit.sha)
+}
+
+/// Create a gist holding one file and return its page URL
+///
+/// Needs a token allowed to write gists (the GitHub CLI's token is).
+pub fn create_gist(
+    filename: &str,
+    content: &str,
+    description: &str,
+    public: bool,
+) -> Result<String> {
+    #[derive(Deserialize)]
+    struct Created {
+        html_url: String,
+    }
+
+    let Some(token) = TOKEN.as_deref() else {
+        bail!("Creating a gist needs a GitHub token (set GITHUB_TOKEN or run 'gh auth login')");
+    };
+    let body = serde_json::json!({
+        "description": description,
+        "public": public,
+        "files": { filename: { "content": content } },
+    });
+    let created: Created = HTTP_AGENT
+        .post(format!("{}/gists", API_URL))
+        .header("User-Agent", "hoards-cli")
+        .header("Accept", "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .header("Authorization", &format!("Bearer {}", token))
+        .send_json(&body)
+        .context("Failed to create gist")?
+        .body_mut()
+        .read_json()
+        .context("Failed to parse gist response")?;
+    Ok(created.html_url)
+}
+
+/// Fetch the content of a gist's first file
+pub fn gist_content(id: &str) -> Result<String> {
+    #[derive(Deserialize)]
+    struct GistFile {
+        #[serde(default)]
+        content: String,
+        #[serde(default)]
+        truncated: bool,
+        raw_url: String,
+    }
+
+    #[derive(Deserialize)]
+    struct Gist {
+        files: std::collections::BTreeMap<String, GistFile>,
+    }
+
+    let gist: Gist = api_json(&format!("gists/{}", id), &[])?;
+    let Some(file) = gist.files.into_values().next() else {
+        bail!("Gist {} has no files", id);
+    };
+    if !file.truncated {
+        return Ok(file.content);
+    }
+    // Large files are cut short in the API response
+    let mut response = HTTP_AGENT
+        .get(&file.raw_url)
+        .header("User-Agent", "hoards-cli")
+        .call()
+        .with_context(|| format!("Failed to fetch {}", file.raw_url))?;
+    Ok(response.body_mut().read_to_string()?)
+}
+
+/// Search and get full repo info for a tool, using source for language filtering
+pub fn find_repo(tool_name: &str, source: Option<&str>) -> Result<Option<RepoInfo>> {
+    // First search for the repo, using language filter based on source
+    let search_result = search_repo(tool_name, source)?;
+
+    match search_result {
+        Some(result) => {
+            // Get full info including topics
+            let info = get_repo_info(&result.owner.login, &result.name)?;
+            Ok(Some(info))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Map GitHub topics to a category using the mapping config
+pub fn topics_to_category(topics: &[String], mapping: &TopicMapping) -> Option<String> {
+    // Count matches for each category
+    let mut scores: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+
+    for topic in topics {
+        let topic_lower = topic.to_lowercase();
+        for (category, keywords) in &mapping.categories {
+            if keywords.contains(&topic_lower) {
+                *scores.entry(category.as_str()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    // Return category with highest score
+    scores
+        .into_iter()
+        .max_by_key(|(_, score)| *score)
+        .map(|(cat, _)| cat.to_string())
+}
+
+/// Topic to category mapping configuration
+#[derive(Debug, Clone, Default)]
+pub struct TopicMapping {
+    pub categories: std::collections::HashMap<String, Vec<String>>,
+}
+
+impl TopicMapping {
+    /// Load mapping from TOML file or use defaults
+    pub fn load() -> Self {
+        let config_path = dirs::config_dir().map(|d| d.join("hoard").join("topic-mapping.toml"));
+
+        if let Some(path) = config_path
+            && path.exists()
+            && let Ok(content) = std::fs::read_to_string(&path)
+            && let Ok(mapping) = Self::parse_toml(&content)
+        {
+            return mapping;
+        }
+
+        Self::default_mapping()
+    }
+
+    fn parse_toml(content: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct TomlMapping {
+            categories: std::collections::HashMap<String, Vec<String>>,
+        }
+
+        let parsed: TomlMapping = toml::from_str(content)?;
+        Ok(Self {
+            categories: parsed.categories,
+        })
+    }
+
+    /// Default topic to category mapping
+    pub fn default_mapping() -> Self {
+        let mut categories = std::collections::HashMap::new();
+
+        categories.insert(
+            "search".to_string(),
+            vec!["search", "grep", "regex", "find", "ripgrep", "ag", "ack"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+
+        categories.insert(
+            "files".to_string(),
+            vec![
+                "files",
+                "filesystem",
+                "ls",
+                "file-manager",
+                "directory",
+                "tree",
+                "disk",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        );
+
+        categories.insert(
+            "git".to_string(),
+            vec!["git", "github", "gitlab", "version-control", "vcs"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+
+        categories.insert(
+            "shell".to_string(),
+            vec![
+                "shell",
+                "terminal",
+                "cli",
+                "command-line",
+                "bash",
+                "zsh",
+                "fish",
+                "prompt",
+                "readline",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        );
+
+        categories.insert(
+            "container".to_string(),
+            vec!["docker", "container", "kubernetes", "k8s", "podman", "oci"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+
+        categories.insert(
+            "editor".to_string(),
+            vec!["editor", "vim", "neovim", "emacs", "text-editor", "ide"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+
+        categories.insert(
+            "network".to_string(),
+            vec![
+                "network", "http", "curl", "api", "rest", "web", "dns", "proxy",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        );
+
+        categories.insert(
+            "data".to_string(),
+            vec![
+                "json", "yaml", "csv", "jq", "data", "parsing", "xml", "toml",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        );
+
+        categories.insert(
+            "system".to_string(),
+            vec![
+                "system",
+                "process",
+                "monitoring",
+                "htop",
+                "top",
+                "performance",
+                "benchmark",
+                "profiling",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        );
+
+        categories.insert(
+            "security".to_string(),
+            vec![
+                "security",
+                "encryption",
+                "password",
+                "ssh",
+                "gpg",
+                "crypto",
+                "vault",
+                "secrets",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        );
+
+        categories.insert(
+            "dev".to_string(),
+            vec![
+                "development",
+                "programming",
+                "compiler",
+                "linter",
+                "formatter",
+                "testing",
+                "debugging",
+                "build",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        );
+
+        Self { categories }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::RepoOwner;
+
+    #[test]
+    fn test_topics_to_category() {
+        let mapping = TopicMapping::default_mapping();
+
+        // "search" and "grep" both map to "search" category (2 points vs 1 for cli->shell)
+        let topics = vec!["cli".to_string(), "search".to_string(), "grep".to_string()];
+        assert_eq!(
+            topics_to_category(&topics, &mapping),
+            Some("search".to_string())
+        );
+
+        // "git" and "github" both map to "git" category (2 points vs 1 for cli->shell)
+        let topics = vec!["git".to_string(), "github".to_string(), "cli".to_string()];
+        assert_eq!(
+            topics_to_category(&topics, &mapping),
+            Some("git".to_string())
+        );
+
+        // No matching topics
+        let topics = vec!["unknown".to_string(), "random".to_string()];
+        assert_eq!(topics_to_category(&topics, &mapping), None);
+
+        // Empty topics
+        let topics: Vec<String> = vec![];
+        assert_eq!(topics_to_category(&topics, &mapping), None);
+    }
+
+    #[test]
+    fn test_looks_like_cli_tool() {
+        let repo = |desc: &str, topics: &[&str]| RepoInfo {
+            name: "x".to_string(),
+            full_name: "o/x".to_string(),
+            description: Some(desc.to_string()),
+            stars: 0,
+            language: None,
+            homepage: None,
+            topics: topics.iter().map(|t| t.to_string()).collect(),
+            license: None,
+            pushed_at: None,
+            open_issues: 0,
+            archived: false,
+            owner: RepoOwner {
+                login: "o".to_string(),
+            },
+        };
+
+        assert!(looks_like_cli_tool(&repo("", &["rust", "CLI"])));
+        assert!(looks_like_cli_tool(&repo(
+            "A cat(1) clone with wings, for the terminal",
+            &[]
+        )));
+        assert!(looks_like_cli_tool(&repo(
+            "Fast command line JSON processor",
+            &[]
+        )));
+        assert!(!looks_like_cli_tool(&repo(
+            "A web framework",
+            &["rust", "web"]
+        )));
+        // "cli" must be a whole word
+        assert!(!looks_like_cli_tool(&repo("Client library for Redis", &[])));
+    }
+
+    #[test]
+    fn test_source_to_language_filter() {
+        assert_eq!(
+            source_to_language_filter(Some("cargo")),
+            Some("language:rust")
+        );
+        assert_eq!(
+            source_to_language_filter(Some("pip")),
+            Some("language:python")
+        );
+        assert_eq!(
+            source_to_language_filter(Some("npm")),
+            Some("language:javascript OR language:typescript")
+        );
+        assert_eq!(source_to_language_filter(Some("go")), Some("language:go"));
+        assert_eq!(source_to_language_filter(Some("apt")), None);
+        assert_eq!(source_to_language_filter(None), None);
+    }
+
+    #[test]
+    fn test_last_page_from_link() {
+        let link = r#"<https://api.github.com/repositories/1/contributors?per_page=1&anon=true&page=2>; rel="next", <https://api.github.com/repositories/1/contributors?per_page=1&anon=true&page=412>; rel="last""#;
+        assert_eq!(last_page_from_link(link), Some(412));
+        assert_eq!(
+            last_page_from_link(r#"<https://x/?page=3>; rel="prev""#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rest_repo_payload() {
+        let json = r#"{
+            "name": "ripgrep",
+            "full_name": "BurntSushi/ripgrep",
+            "description": "recursively searches directories",
+            "stargazers_count": 50000,
+            "language": "Rust",
+            "homepage": null,
+            "license": {"key": "unlicense", "name": "The Unlicense", "spdx_id": "Unlicense"},
+            "pushed_at": "2026-09-30T12:00:00Z",
+            "open_issues_count": 87,
+            "archived": false,
+            "owner": {"login": "BurntSushi", "id": 456}
+        }"#;
+        let info: RepoInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.stars, 50000);
+        assert_eq!(info.owner.login, "BurntSushi");
+        assert!(info.topics.is_empty());
+        assert_eq!(info.license_id(), Some("Unlicense"));
+        assert_eq!(info.open_issues, 87);
+        assert_eq!(info.pushed_at.as_deref(), Some("2026-09-30T12:00:00Z"));
+        assert!(!info.archived);
+
+        let other: RepoInfo = serde_json::from_str(
+            &json.replace(r#""spdx_id": "Unlicense""#, r#""spdx_id": "NOASSERTION""#),
+        )
+        .unwrap();
+        assert_eq!(other.license_id(), Some("The Unlicense"));
+
+        let hit: SearchResult = serde_json::from_str(json).unwrap();
+        assert_eq!(hit.full_name, "BurntSushi/ripgrep");
+    }
+}
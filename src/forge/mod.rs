@@ -0,0 +1,375 @@
+//! Source forge trait and implementations
+//!
+//! Each code hosting provider implements the `Forge` trait, providing a
+//! unified interface for repository metadata, releases, READMEs and search.
+//! GitHub and Gitea-compatible forges (Codeberg, self-hosted Gitea/Forgejo)
+//! are both queried through their REST APIs.
+
+pub mod assets;
+mod gitea;
+pub mod github;
+pub mod scheduler;
+
+pub use gitea::GiteaForge;
+pub use github::GitHubForge;
+
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+
+/// Host used for GitHub repositories
+pub const GITHUB_HOST: &str = "github.com";
+
+/// Repository metadata
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoInfo {
+    pub name: String,
+    pub full_name: String,
+    pub description: Option<String>,
+    #[serde(rename = "stargazers_count")]
+    pub stars: i64,
+    pub language: Option<String>,
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub license: Option<RepoLicense>,
+    /// Time of the last push (RFC 3339)
+    #[serde(default)]
+    pub pushed_at: Option<String>,
+    #[serde(default, rename = "open_issues_count")]
+    pub open_issues: i64,
+    #[serde(default)]
+    pub archived: bool,
+    pub owner: RepoOwner,
+}
+
+impl RepoInfo {
+    /// SPDX identifier of the license, or its name when the forge can't classify it
+    pub fn license_id(&self) -> Option<&str> {
+        let license = self.license.as_ref()?;
+        match license.spdx_id.as_deref() {
+            Some(id) if !id.is_empty() && id != "NOASSERTION" => Some(id),
+            _ => Some(license.name.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoOwner {
+    pub login: String,
+}
+
+/// License detected by the forge
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoLicense {
+    #[serde(default)]
+    pub spdx_id: Option<String>,
+    pub name: String,
+}
+
+/// Repository search hit
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchResult {
+    pub name: String,
+    pub full_name: String,
+    pub description: Option<String>,
+    #[serde(rename = "stargazers_count")]
+    pub stars: i64,
+    pub owner: RepoOwner,
+}
+
+/// A release with its notes
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Release page on the forge
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Files attached to the release (binaries, archives, checksums)
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    #[serde(rename = "browser_download_url")]
+    pub url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// A fetched README with the validator for conditional refetches
+#[derive(Debug, Clone)]
+pub struct Readme {
+    pub content: String,
+    pub etag: Option<String>,
+}
+
+/// Kind of forge software behind a host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeProvider {
+    GitHub,
+    Gitea,
+}
+
+impl ForgeProvider {
+    /// Name stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GitHub => "github",
+            Self::Gitea => "gitea",
+        }
+    }
+
+    /// Parse a stored provider name
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "github" => Some(Self::GitHub),
+            "gitea" | "forgejo" | "codeberg" => Some(Self::Gitea),
+            _ => None,
+        }
+    }
+
+    /// Best guess for a host: GitHub for github.com, Gitea API for anything else
+    pub fn for_host(host: &str) -> Self {
+        if host.eq_ignore_ascii_case(GITHUB_HOST) {
+            Self::GitHub
+        } else {
+            Self::Gitea
+        }
+    }
+}
+
+impl std::fmt::Display for ForgeProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Trait for code hosting providers
+///
+/// Implement this trait to add support for a new forge. Each forge provides
+/// methods for:
+/// - Fetching repository metadata (stars, description, topics)
+/// - Listing releases (with their assets)
+/// - Fetching the README
+/// - Searching repositories
+pub trait Forge: Send + Sync {
+    /// Which provider this is
+    fn provider(&self) -> ForgeProvider;
+
+    /// Host name, e.g. "github.com" or "codeberg.org"
+    fn host(&self) -> &str;
+
+    /// Fetch repository metadata
+    fn repo_info(&self, owner: &str, repo: &str) -> Result<RepoInfo>;
+
+    /// Fetch recent releases, newest first
+    fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>>;
+
+    /// Fetch the README as text
+    fn readme(&self, owner: &str, repo: &str) -> Result<String>;
+
+    /// Fetch the README unless it still matches `etag`
+    ///
+    /// Returns `None` when the forge reports the cached copy is current.
+    /// Forges without conditional requests always refetch.
+    fn readme_if_changed(
+        &self,
+        owner: &str,
+        repo: &str,
+        etag: Option<&str>,
+    ) -> Result<Option<Readme>> {
+        let _ = etag;
+        Ok(Some(Readme {
+            content: self.readme(owner, repo)?,
+            etag: None,
+        }))
+    }
+
+    /// Search repositories, best matches first
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>>;
+
+    /// Number of contributors, if the forge can tell cheaply
+    fn contributor_count(&self, owner: &str, repo: &str) -> Result<Option<i64>> {
+        let _ = (owner, repo);
+        Ok(None)
+    }
+}
+
+/// Get the forge for a provider on a host
+pub fn get_forge(provider: ForgeProvider, host: &str) -> Box<dyn Forge> {
+    match provider {
+        ForgeProvider::GitHub => Box::new(GitHubForge),
+        ForgeProvider::Gitea => Box::new(GiteaForge::new(host.to_lowercase())),
+    }
+}
+
+/// Get the forge for a host, guessing the provider from its name
+pub fn forge_for_host(host: &str) -> Box<dyn Forge> {
+    get_forge(ForgeProvider::for_host(host), host)
+}
+
+/// A repository on some forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoRef {
+    pub provider: ForgeProvider,
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RepoRef {
+    /// Web URL of the repository
+    pub fn url(&self) -> String {
+        format!("https://{}/{}/{}", self.host, self.owner, self.repo)
+    }
+
+    /// Forge hosting the repository
+    pub fn forge(&self) -> Box<dyn Forge> {
+        get_forge(self.provider, &self.host)
+    }
+
+    /// Fetch release notes for versions after `current` up to and including `latest`
+    ///
+    /// Releases are returned oldest first.
+    pub fn release_notes(&self, current: &str, latest: &str) -> Result<Vec<Release>> {
+        let releases = self.forge().releases(&self.owner, &self.repo)?;
+        Ok(releases_between(releases, current, latest))
+    }
+}
+
+/// Keep releases newer than `current` and not newer than `latest`, oldest first
+fn releases_between(releases: Vec<Release>, current: &str, latest: &str) -> Vec<Release> {
+    use crate::updates::version_is_newer;
+
+    let mut selected: Vec<Release> = releases
+        .into_iter()
+        .filter(|r| {
+            version_is_newer(&r.tag_name, current) && !version_is_newer(&r.tag_name, latest)
+        })
+        .collect();
+    selected.reverse();
+    selected
+}
+
+/// Parse a repository URL on any forge
+///
+/// Accepts `https://host/owner/repo[.git][/...]`, `git@host:owner/repo.git`,
+/// `host/owner/repo` and the `owner/repo` shorthand (assumed to be GitHub).
+pub fn parse_repo_url(url: &str) -> Result<RepoRef> {
+    let url = url.trim();
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    let (host, path) = if let Some(ssh) = rest.strip_prefix("git@") {
+        ssh.split_once(':')
+            .map(|(host, path)| (host.to_lowercase(), path))
+            .unwrap_or_default()
+    } else {
+        let parts: Vec<&str> = rest.splitn(2, '/').collect();
+        if parts.len() == 2 && parts[0].contains('.') {
+            (parts[0].to_lowercase(), parts[1])
+        } else {
+            (GITHUB_HOST.to_string(), rest)
+        }
+    };
+
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    match (segments.next(), segments.next()) {
+        (Some(owner), Some(repo)) if !host.is_empty() => Ok(RepoRef {
+            provider: ForgeProvider::for_host(&host),
+            host,
+            owner: owner.to_string(),
+            repo: repo.trim_end_matches(".git").to_string(),
+        }),
+        _ => bail!("Invalid repository URL: {}", url),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repo_url() {
+        let repo = |host: &str, owner: &str, name: &str| RepoRef {
+            provider: ForgeProvider::for_host(host),
+            host: host.to_string(),
+            owner: owner.to_string(),
+            repo: name.to_string(),
+        };
+
+        assert_eq!(
+            parse_repo_url("https://codeberg.org/dnkl/foot").unwrap(),
+            repo("codeberg.org", "dnkl", "foot")
+        );
+        assert_eq!(
+            parse_repo_url("https://github.com/BurntSushi/ripgrep.git").unwrap(),
+            repo("github.com", "BurntSushi", "ripgrep")
+        );
+        assert_eq!(
+            parse_repo_url("git@git.example.com:me/tool.git").unwrap(),
+            repo("git.example.com", "me", "tool")
+        );
+        assert_eq!(
+            parse_repo_url("codeberg.org/dnkl/foot/releases").unwrap(),
+            repo("codeberg.org", "dnkl", "foot")
+        );
+        assert_eq!(
+            parse_repo_url("sharkdp/bat").unwrap(),
+            repo("github.com", "sharkdp", "bat")
+        );
+        assert!(parse_repo_url("https://codeberg.org/dnkl").is_err());
+        assert!(parse_repo_url("ripgrep").is_err());
+    }
+
+    #[test]
+    fn test_forge_provider() {
+        assert_eq!(ForgeProvider::for_host("GitHub.com"), ForgeProvider::GitHub);
+        assert_eq!(
+            ForgeProvider::for_host("codeberg.org"),
+            ForgeProvider::Gitea
+        );
+        assert_eq!(ForgeProvider::parse("forgejo"), Some(ForgeProvider::Gitea));
+        assert_eq!(ForgeProvider::parse("svn"), None);
+
+        let forge = get_forge(ForgeProvider::Gitea, "Codeberg.org");
+        assert_eq!(forge.host(), "codeberg.org");
+        assert_eq!(
+            forge_for_host("github.com").provider(),
+            ForgeProvider::GitHub
+        );
+    }
+
+    #[test]
+    fn test_releases_between() {
+        let release = |tag: &str| Release {
+            tag_name: tag.to_string(),
+            ..Default::default()
+        };
+        let releases = vec![
+            release("v1.4.0"),
+            release("v1.3.0"),
+            release("v1.2.1"),
+            release("v1.2.0"),
+        ];
+
+        let tags: Vec<String> = releases_between(releases, "1.2.0", "1.3.0")
+            .into_iter()
+            .map(|r| r.tag_name)
+            .collect();
+        assert_eq!(tags, vec!["v1.2.1", "v1.3.0"]);
+    }
+}
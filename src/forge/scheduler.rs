@@ -0,0 +1,426 @@
+//! Rate-limit-aware scheduling of GitHub API requests
+//!
+//! Every response's `x-ratelimit-*` headers update the known quota of its
+//! resource (`core`, `search`, ...). A request against an exhausted quota
+//! waits for the reset when it is close enough, and otherwise fails with
+//! [`RateLimited`] so batch jobs can park their work in the database queue
+//! and resume after the reset.
+//!
+//! Identical GET requests are deduplicated: callers in this process wait for
+//! a request already in flight and share its response. With sharing enabled,
+//! the same happens across hoards processes (e.g. a CLI command running
+//! next to the TUI) through the `github_requests` table.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, LazyLock, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::Duration;
+
+use crate::Database;
+use crate::db::RequestClaim;
+
+/// Requests answered by a rate-limit rejection are retried this often
+const MAX_ATTEMPTS: usize = 3;
+
+/// Shared requests older than this are pruned when sharing starts
+const SHARED_REQUEST_RETENTION_SECS: i64 = 3600;
+
+/// How often to check on a request another process is performing
+const IN_FLIGHT_POLL: Duration = Duration::from_millis(250);
+
+/// Quota of one rate-limited resource, as last reported by GitHub
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub limit: i64,
+    pub remaining: i64,
+    /// Unix time at which the quota refills
+    pub reset: i64,
+}
+
+/// A request was refused because its quota is exhausted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimited {
+    pub resource: String,
+    /// Unix time at which the quota refills
+    pub reset: i64,
+}
+
+impl RateLimited {
+    /// Seconds until the quota refills
+    pub fn wait_seconds(&self) -> i64 {
+        (self.reset - now()).max(0)
+    }
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "GitHub {} API quota exhausted, resets in {}",
+            self.resource,
+            format_wait(self.wait_seconds())
+        )
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// Human-readable wait, e.g. "42 sec" or "17 min"
+pub fn format_wait(seconds: i64) -> String {
+    if seconds < 60 {
+        format!("{} sec", seconds)
+    } else {
+        format!("{} min", (seconds + 59) / 60)
+    }
+}
+
+/// Current Unix time in seconds
+pub fn now() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+static QUOTAS: LazyLock<Mutex<HashMap<String, Quota>>> = LazyLock::new(Mutex::default);
+
+/// Longest wait for a quota reset before failing with [`RateLimited`]
+static MAX_WAIT_SECS: AtomicU64 = AtomicU64::new(60);
+
+static SHARING: AtomicBool = AtomicBool::new(false);
+static SHARED_DB: OnceLock<Option<Mutex<Database>>> = OnceLock::new();
+
+/// Requests in flight in this process, by key
+static IN_FLIGHT: LazyLock<Mutex<HashMap<String, Arc<Slot>>>> = LazyLock::new(Mutex::default);
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Set how long requests may block waiting for a quota reset
+///
+/// The default of a minute covers the search API's per-minute window.
+/// Interactive callers like the TUI set zero so they never freeze.
+pub fn set_max_wait(wait: Duration) {
+    MAX_WAIT_SECS.store(wait.as_secs(), Ordering::Relaxed);
+}
+
+/// Share in-flight requests with other hoards processes through the database
+pub fn share_between_processes() {
+    SHARING.store(true, Ordering::Relaxed);
+}
+
+/// Connection used for sharing, opened on first use
+fn shared_db() -> Option<&'static Mutex<Database>> {
+    if !SHARING.load(Ordering::Relaxed) {
+        return None;
+    }
+    SHARED_DB
+        .get_or_init(|| {
+            let db = Database::open().ok()?;
+            let _ = db.prune_github_requests(now() - SHARED_REQUEST_RETENTION_SECS);
+            Some(Mutex::new(db))
+        })
+        .as_ref()
+}
+
+/// Rate-limit resource a GitHub API path counts against
+///
+/// `None` for paths that are not rate limited.
+pub fn resource_for(path: &str) -> Option<&'static str> {
+    if path == "rate_limit" {
+        None
+    } else if path.starts_with("search/") {
+        Some("search")
+    } else {
+        Some("core")
+    }
+}
+
+/// Last known quota of a resource
+pub fn quota(resource: &str) -> Option<Quota> {
+    lock(&QUOTAS).get(resource).copied()
+}
+
+/// Record the quota of a resource (e.g. from the `rate_limit` endpoint)
+pub fn update_quota(resource: &str, quota: Quota) {
+    lock(&QUOTAS).insert(resource.to_string(), quota);
+}
+
+/// The rate limit currently blocking a resource, if any
+pub fn exhausted(resource: &str) -> Option<RateLimited> {
+    let quota = quota(resource)?;
+    (quota.remaining <= 0 && quota.reset > now()).then(|| RateLimited {
+        resource: resource.to_string(),
+        reset: quota.reset,
+    })
+}
+
+/// Block until a rate limit has reset
+pub fn sleep_until_reset(limited: &RateLimited) {
+    std::thread::sleep(Duration::from_secs(limited.wait_seconds() as u64 + 1));
+    if let Some(quota) = lock(&QUOTAS).get_mut(&limited.resource) {
+        quota.remaining = quota.limit;
+    }
+}
+
+/// Wait for an exhausted quota to reset, or fail if that takes too long
+fn wait_for_quota(resource: &str) -> Result<(), RateLimited> {
+    let Some(limited) = exhausted(resource) else {
+        return Ok(());
+    };
+    if limited.wait_seconds() as u64 > MAX_WAIT_SECS.load(Ordering::Relaxed) {
+        return Err(limited);
+    }
+    tracing::info!(
+        "{} quota used up, waiting {}s for the reset",
+        resource,
+        limited.wait_seconds()
+    );
+    sleep_until_reset(&limited);
+    Ok(())
+}
+
+/// Read an integer response header
+fn header_i64(response: &ureq::http::Response<ureq::Body>, name: &str) -> Option<i64> {
+    response
+        .headers()
+        .get(name)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Update quotas from a response's rate-limit headers
+fn record_quota(response: &ureq::http::Response<ureq::Body>, resource: &str) {
+    let (Some(limit), Some(remaining), Some(reset)) = (
+        header_i64(response, "x-ratelimit-limit"),
+        header_i64(response, "x-ratelimit-remaining"),
+        header_i64(response, "x-ratelimit-reset"),
+    ) else {
+        return;
+    };
+    let resource = response
+        .headers()
+        .get("x-ratelimit-resource")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or(resource);
+    update_quota(
+        resource,
+        Quota {
+            limit,
+            remaining,
+            reset,
+        },
+    );
+}
+
+/// Reset time if the response is a rate-limit rejection
+///
+/// GitHub answers 403 or 429 with either an exhausted quota or, for its
+/// secondary limits, a `retry-after` header.
+fn rejected_until(response: &ureq::http::Response<ureq::Body>) -> Option<i64> {
+    if !matches!(response.status().as_u16(), 403 | 429) {
+        return None;
+    }
+    if let Some(seconds) = header_i64(response, "retry-after") {
+        return Some(now() + seconds);
+    }
+    match header_i64(response, "x-ratelimit-remaining") {
+        Some(0) => Some(header_i64(response, "x-ratelimit-reset").unwrap_or(now() + 60)),
+        _ => None,
+    }
+}
+
+/// Send a GitHub API request, respecting the quota of its resource
+///
+/// `request` builds the request and may be called again after a rate-limit
+/// rejection. Other error statuses are returned for the caller to handle.
+pub fn send<F>(path: &str, request: F) -> Result<ureq::http::Response<ureq::Body>>
+where
+    F: Fn() -> ureq::RequestBuilder<ureq::typestate::WithoutBody>,
+{
+    let resource = resource_for(path);
+    for _ in 0..MAX_ATTEMPTS {
+        if let Some(resource) = resource {
+            wait_for_quota(resource)?;
+        }
+
+        tracing::debug!("GitHub API request: {}", path);
+        let response = request()
+            .call()
+            .with_context(|| format!("GitHub API request failed: {}", path))?;
+        let Some(resource) = resource else {
+            return Ok(response);
+        };
+        record_quota(&response, resource);
+
+        match rejected_until(&response) {
+            Some(reset) => update_quota(
+                resource,
+                Quota {
+                    remaining: 0,
+                    reset: reset.max(now() + 1),
+                    ..quota(resource).unwrap_or(Quota {
+                        limit: 0,
+                        remaining: 0,
+                        reset,
+                    })
+                },
+            ),
+            None => return Ok(response),
+        }
+    }
+
+    let resource = resource.unwrap_or("core");
+    Err(exhausted(resource)
+        .unwrap_or(RateLimited {
+            resource: resource.to_string(),
+            reset: now() + 60,
+        })
+        .into())
+}
+
+/// Result slot for a request in flight in this process
+#[derive(Default)]
+struct Slot {
+    /// `Some(None)` once the request failed
+    body: Mutex<Option<Option<String>>>,
+    done: Condvar,
+}
+
+impl Slot {
+    fn wait(&self) -> Option<String> {
+        let mut body = lock(&self.body);
+        while body.is_none() {
+            body = self.done.wait(body).unwrap_or_else(PoisonError::into_inner);
+        }
+        body.clone().flatten()
+    }
+
+    fn finish(&self, body: Option<String>) {
+        *lock(&self.body) = Some(body);
+        self.done.notify_all();
+    }
+}
+
+/// Run `fetch` for `key` unless the same request is already in flight
+///
+/// Concurrent callers wait for the first one and reuse its response body;
+/// if it fails, each of them fetches for itself.
+pub fn shared<F>(key: &str, fetch: F) -> Result<String>
+where
+    F: Fn() -> Result<String>,
+{
+    let leader = {
+        let mut in_flight = lock(&IN_FLIGHT);
+        match in_flight.get(key) {
+            Some(slot) => Some(Arc::clone(slot)),
+            None => {
+                in_flight.insert(key.to_string(), Arc::default());
+                None
+            }
+        }
+    };
+    if let Some(slot) = leader {
+        return match slot.wait() {
+            Some(body) => Ok(body),
+            None => fetch(),
+        };
+    }
+
+    let result = fetch_across_processes(key, &fetch);
+    if let Some(slot) = lock(&IN_FLIGHT).remove(key) {
+        slot.finish(result.as_ref().ok().cloned());
+    }
+    result
+}
+
+/// Fetch, or wait for another hoards process fetching the same key
+fn fetch_across_processes<F>(key: &str, fetch: &F) -> Result<String>
+where
+    F: Fn() -> Result<String>,
+{
+    let Some(db) = shared_db() else {
+        return fetch();
+    };
+
+    let pid = std::process::id();
+    loop {
+        // The database is only an optimization: on errors, just fetch
+        match lock(db).claim_github_request(key, pid, now()) {
+            Ok(RequestClaim::Shared(body)) => return Ok(body),
+            Ok(RequestClaim::InFlight) => std::thread::sleep(IN_FLIGHT_POLL),
+            Ok(RequestClaim::Claimed) => break,
+            Err(_) => return fetch(),
+        }
+    }
+
+    let result = fetch();
+    let _ = lock(db).finish_github_request(key, result.as_ref().ok().map(String::as_str), now());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_for() {
+        assert_eq!(resource_for("search/repositories"), Some("search"));
+        assert_eq!(resource_for("repos/sharkdp/bat"), Some("core"));
+        assert_eq!(resource_for("rate_limit"), None);
+    }
+
+    #[test]
+    fn test_exhausted_quota() {
+        let reset = now() + 600;
+        update_quota(
+            "test-exhausted",
+            Quota {
+                limit: 30,
+                remaining: 0,
+                reset,
+            },
+        );
+        let limited = exhausted("test-exhausted").unwrap();
+        assert_eq!(limited.reset, reset);
+        assert!(limited.to_string().contains("resets in 10 min"));
+
+        // Too far away to wait for with the default maximum wait
+        assert_eq!(wait_for_quota("test-exhausted"), Err(limited));
+
+        // A reset in the past no longer blocks
+        update_quota(
+            "test-exhausted",
+            Quota {
+                limit: 30,
+                remaining: 0,
+                reset: now() - 1,
+            },
+        );
+        assert!(exhausted("test-exhausted").is_none());
+        assert!(exhausted("test-unknown").is_none());
+    }
+
+    #[test]
+    fn test_shared_dedups_concurrent_requests() {
+        use std::sync::atomic::AtomicUsize;
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        let fetch = || {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(100));
+            Ok("body".to_string())
+        };
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| std::thread::spawn(move || shared("test-dedup", fetch).unwrap()))
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), "body");
+        }
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    }
+}
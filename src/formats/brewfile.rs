@@ -0,0 +1,223 @@
+//! Homebrew Brewfile, as read and written by `brew bundle`
+//!
+//! Only `tap`, `brew` and `cask` entries are understood; other entries
+//! (`mas`, `vscode`, ...) are kept as-is but not turned into tools.
+
+use crate::models::{InstallSource, Tool};
+
+/// One entry of a Brewfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrewfileEntry {
+    /// `tap`, `brew`, `cask`, `mas`, ...
+    pub kind: String,
+    /// Formula, cask or tap name, e.g. `ripgrep` or `user/tap/formula`
+    pub name: String,
+}
+
+impl BrewfileEntry {
+    /// Name of the tool a formula or cask installs, without its tap
+    pub fn tool_name(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or(&self.name)
+    }
+
+    /// Command that installs the entry
+    pub fn install_command(&self) -> Option<String> {
+        match self.kind.as_str() {
+            "brew" => Some(format!("brew install {}", self.name)),
+            "cask" => Some(format!("brew install --cask {}", self.name)),
+            _ => None,
+        }
+    }
+}
+
+/// The first quoted string of a line, single or double quoted
+fn quoted(text: &str) -> Option<&str> {
+    let start = text.find(['"', '\''])?;
+    let quote = text[start..].chars().next()?;
+    let rest = &text[start + 1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+/// Parse a Brewfile; comments and lines without a quoted name are skipped
+pub fn parse(content: &str) -> Vec<BrewfileEntry> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let kind = line.split(|c: char| c.is_whitespace() || c == '(').next()?;
+            let name = quoted(line)?;
+            Some(BrewfileEntry {
+                kind: kind.to_string(),
+                name: name.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Tools for the formulae and casks of a Brewfile
+pub fn tools(entries: &[BrewfileEntry]) -> Vec<Tool> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let command = entry.install_command()?;
+            let tool = Tool::new(entry.tool_name())
+                .with_source(InstallSource::Brew)
+                .with_install_command(command);
+            Some(if entry.kind == "cask" {
+                tool.with_category("app")
+            } else {
+                tool.with_binary(entry.tool_name())
+            })
+        })
+        .collect()
+}
+
+/// Formula or cask of a Homebrew tool, from its install command if it has one
+fn brew_entry(tool: &Tool) -> BrewfileEntry {
+    let args: Vec<&str> = tool
+        .install_command
+        .as_deref()
+        .and_then(|cmd| cmd.strip_prefix("brew install "))
+        .map(|rest| rest.split_whitespace().collect())
+        .unwrap_or_default();
+    let cask = args.contains(&"--cask");
+    let name = args
+        .iter()
+        .find(|arg| !arg.starts_with('-'))
+        .map_or(tool.name.as_str(), |name| name);
+    BrewfileEntry {
+        kind: if cask { "cask" } else { "brew" }.to_string(),
+        name: name.to_string(),
+    }
+}
+
+/// Write a Brewfile for the Homebrew tools
+///
+/// Tools from other sources are listed in a trailing comment, so nothing
+/// silently goes missing.
+pub fn write(tools: &[Tool]) -> String {
+    let (brew, other): (Vec<&Tool>, Vec<&Tool>) = tools
+        .iter()
+        .partition(|tool| tool.source == InstallSource::Brew);
+    let mut entries: Vec<BrewfileEntry> = brew.into_iter().map(brew_entry).collect();
+    entries.sort_by(|a, b| (&a.kind, &a.name).cmp(&(&b.kind, &b.name)));
+
+    let mut taps: Vec<&str> = entries
+        .iter()
+        .filter_map(|entry| entry.name.rsplit_once('/').map(|(tap, _)| tap))
+        .filter(|tap| tap.contains('/'))
+        .collect();
+    taps.sort();
+    taps.dedup();
+
+    let mut out = String::from("# Generated by hoards\n");
+    for tap in taps {
+        out.push_str(&format!("tap \"{}\"\n", tap));
+    }
+    for entry in &entries {
+        out.push_str(&format!("{} \"{}\"\n", entry.kind, entry.name));
+    }
+    if !other.is_empty() {
+        out.push_str("\n# Not installed with Homebrew:\n");
+        for tool in other {
+            out.push_str(&format!("# {} ({})\n", tool.name, tool.source));
+        }
+    }
+    out
+}
+
+/// Whether a file name looks like a Brewfile (`Brewfile`, `Brewfile.mac`, `work.Brewfile`)
+pub fn is_brewfile(file_name: &str) -> bool {
+    file_name.starts_with("Brewfile") || file_name.ends_with(".Brewfile")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_brewfile() {
+        let content = r#"
+# Tools
+tap "homebrew/bundle"
+brew "ripgrep"
+brew 'neovim', args: ["HEAD"]
+brew "user/tap/fancy", restart_service: :changed
+cask "wezterm"
+mas "Xcode", id: 497799835
+vscode "rust-lang.rust-analyzer"
+"#;
+        let entries = parse(content);
+        let kinds: Vec<_> = entries
+            .iter()
+            .map(|e| (e.kind.as_str(), e.name.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("tap", "homebrew/bundle"),
+                ("brew", "ripgrep"),
+                ("brew", "neovim"),
+                ("brew", "user/tap/fancy"),
+                ("cask", "wezterm"),
+                ("mas", "Xcode"),
+                ("vscode", "rust-lang.rust-analyzer"),
+            ]
+        );
+
+        let tools = tools(&entries);
+        let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["ripgrep", "neovim", "fancy", "wezterm"]);
+        assert_eq!(
+            tools[2].install_command.as_deref(),
+            Some("brew install user/tap/fancy")
+        );
+        assert_eq!(
+            tools[3].install_command.as_deref(),
+            Some("brew install --cask wezterm")
+        );
+    }
+
+    #[test]
+    fn test_write_brewfile() {
+        let tools = vec![
+            Tool::new("ripgrep").with_source(InstallSource::Brew),
+            Tool::new("wezterm")
+                .with_source(InstallSource::Brew)
+                .with_install_command("brew install --cask wezterm"),
+            Tool::new("fancy")
+                .with_source(InstallSource::Brew)
+                .with_install_command("brew install user/tap/fancy"),
+            Tool::new("bat").with_source(InstallSource::Cargo),
+        ];
+        let brewfile = write(&tools);
+        assert_eq!(
+            brewfile,
+            "# Generated by hoards
+tap \"user/tap\"
+brew \"ripgrep\"
+brew \"user/tap/fancy\"
+cask \"wezterm\"
+
+# Not installed with Homebrew:
+# bat (cargo)
+"
+        );
+
+        // Round trip
+        let names: Vec<_> = super::tools(&parse(&brewfile))
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["ripgrep", "fancy", "wezterm"]);
+    }
+
+    #[test]
+    fn test_is_brewfile() {
+        assert!(is_brewfile("Brewfile"));
+        assert!(is_brewfile("Brewfile.mac"));
+        assert!(is_brewfile("work.Brewfile"));
+        assert!(!is_brewfile("tools.json"));
+    }
+}
@@ -0,0 +1,205 @@
+//! Dockerfile and devcontainer install steps
+//!
+//! Tools are grouped by package manager, one `RUN` layer per manager, so a
+//! container image can carry the same tools as the machine (or a bundle).
+//! Sources that don't fit a container (Homebrew, release binaries, manual
+//! installs) are listed in comments.
+
+use anyhow::Result;
+
+use crate::models::{InstallSource, Tool};
+
+/// Managers that work in a container; system packages first, since the
+/// language managers may need them
+const MANAGERS: [InstallSource; 4] = [
+    InstallSource::Apt,
+    InstallSource::Pip,
+    InstallSource::Npm,
+    InstallSource::Cargo,
+];
+
+/// A package manager and the packages it installs
+type Group<'a> = (&'a InstallSource, Vec<&'a str>);
+
+/// Package names by manager in install order, and the tools left over
+fn by_manager(tools: &[Tool]) -> (Vec<Group<'_>>, Vec<&Tool>) {
+    let groups = MANAGERS
+        .iter()
+        .filter_map(|source| {
+            let mut names: Vec<&str> = tools
+                .iter()
+                .filter(|t| t.source == *source)
+                .map(|t| t.name.as_str())
+                .collect();
+            names.sort();
+            names.dedup();
+            (!names.is_empty()).then_some((source, names))
+        })
+        .collect();
+    let other = tools
+        .iter()
+        .filter(|t| !MANAGERS.contains(&t.source))
+        .collect();
+    (groups, other)
+}
+
+/// Shell command installing a group of packages
+fn install_command(source: &InstallSource, names: &[&str]) -> String {
+    let names = names.join(" ");
+    match source {
+        InstallSource::Apt => format!(
+            "apt-get update && apt-get install -y --no-install-recommends {} && rm -rf /var/lib/apt/lists/*",
+            names
+        ),
+        InstallSource::Pip => format!("pip install --no-cache-dir {}", names),
+        InstallSource::Npm => format!("npm install -g {}", names),
+        _ => format!("cargo install --locked {}", names),
+    }
+}
+
+/// Comment lines for tools that can't be installed in a container
+fn skipped_comment(other: &[&Tool], marker: &str) -> String {
+    let mut out = String::new();
+    if !other.is_empty() {
+        out.push_str(&format!(
+            "{} Not installed (no container package manager):\n",
+            marker
+        ));
+        for tool in other {
+            out.push_str(&format!("{}   {} ({})\n", marker, tool.name, tool.source));
+        }
+    }
+    out
+}
+
+/// Dockerfile `RUN` layers for the tools
+pub fn dockerfile(tools: &[Tool]) -> String {
+    let (groups, other) = by_manager(tools);
+    let mut out = String::from("# Generated by hoards\n");
+    for (source, names) in &groups {
+        let prerequisite = match source {
+            InstallSource::Pip => "# pip needs Python (e.g. python3-pip)\n",
+            InstallSource::Npm => "# npm needs Node.js (e.g. FROM node)\n",
+            InstallSource::Cargo => "# cargo needs a Rust toolchain (e.g. FROM rust)\n",
+            _ => "",
+        };
+        out.push_str(prerequisite);
+        out.push_str(&format!("RUN {}\n", install_command(source, names)));
+    }
+    if !other.is_empty() {
+        out.push('\n');
+        out.push_str(&skipped_comment(&other, "#"));
+    }
+    out
+}
+
+/// `devcontainer.json` with features for the package managers and a
+/// post-create command installing the tools
+pub fn devcontainer(tools: &[Tool]) -> Result<String> {
+    let (groups, other) = by_manager(tools);
+    let mut features = serde_json::Map::new();
+    let mut commands = Vec::new();
+    for (source, names) in &groups {
+        match source {
+            InstallSource::Apt => {
+                features.insert(
+                    "ghcr.io/devcontainers-extra/features/apt-packages:1".to_string(),
+                    serde_json::json!({ "packages": names.join(",") }),
+                );
+                continue;
+            }
+            InstallSource::Pip => {
+                features.insert(
+                    "ghcr.io/devcontainers/features/python:1".to_string(),
+                    serde_json::json!({}),
+                );
+            }
+            InstallSource::Npm => {
+                features.insert(
+                    "ghcr.io/devcontainers/features/node:1".to_string(),
+                    serde_json::json!({}),
+                );
+            }
+            _ => {
+                features.insert(
+                    "ghcr.io/devcontainers/features/rust:1".to_string(),
+                    serde_json::json!({}),
+                );
+            }
+        }
+        commands.push(install_command(source, names));
+    }
+
+    let mut config = serde_json::Map::new();
+    config.insert("features".to_string(), features.into());
+    if !commands.is_empty() {
+        config.insert(
+            "postCreateCommand".to_string(),
+            commands.join(" && ").into(),
+        );
+    }
+    let mut out = serde_json::to_string_pretty(&config)?;
+    out.push('\n');
+    if !other.is_empty() {
+        // JSON has no comments; devcontainer.json (JSONC) does
+        out.push_str(&skipped_comment(&other, "//"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tools() -> Vec<Tool> {
+        vec![
+            Tool::new("ripgrep").with_source(InstallSource::Cargo),
+            Tool::new("jq").with_source(InstallSource::Apt),
+            Tool::new("bat").with_source(InstallSource::Cargo),
+            Tool::new("curl").with_source(InstallSource::Apt),
+            Tool::new("httpie").with_source(InstallSource::Pip),
+            Tool::new("wezterm").with_source(InstallSource::Brew),
+        ]
+    }
+
+    #[test]
+    fn test_dockerfile() {
+        assert_eq!(
+            dockerfile(&tools()),
+            "# Generated by hoards
+RUN apt-get update && apt-get install -y --no-install-recommends curl jq && rm -rf /var/lib/apt/lists/*
+# pip needs Python (e.g. python3-pip)
+RUN pip install --no-cache-dir httpie
+# cargo needs a Rust toolchain (e.g. FROM rust)
+RUN cargo install --locked bat ripgrep
+
+# Not installed (no container package manager):
+#   wezterm (brew)
+"
+        );
+    }
+
+    #[test]
+    fn test_devcontainer() {
+        let out = devcontainer(&tools()).unwrap();
+        let (comment, json): (Vec<&str>, Vec<&str>) =
+            out.lines().partition(|line| line.starts_with("//"));
+        let config: serde_json::Value = serde_json::from_str(&json.join("\n")).unwrap();
+        assert_eq!(
+            config["features"]["ghcr.io/devcontainers-extra/features/apt-packages:1"]["packages"],
+            "curl,jq"
+        );
+        assert!(config["features"]["ghcr.io/devcontainers/features/rust:1"].is_object());
+        assert_eq!(
+            config["postCreateCommand"],
+            "pip install --no-cache-dir httpie && cargo install --locked bat ripgrep"
+        );
+        assert_eq!(
+            comment,
+            vec![
+                "// Not installed (no container package manager):",
+                "//   wezterm (brew)"
+            ]
+        );
+    }
+}
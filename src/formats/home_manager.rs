@@ -0,0 +1,183 @@
+//! Nix home-manager module
+//!
+//! Tools with a known nixpkgs attribute become `home.packages` entries, or
+//! `programs.<name>.enable` where home-manager has a module for them; the
+//! rest are listed in a comment to be looked up by hand.
+
+use crate::models::Tool;
+
+/// Tool names whose nixpkgs attribute differs, or that are known to match
+const NIXPKGS: &[(&str, &str)] = &[
+    ("age", "age"),
+    ("atuin", "atuin"),
+    ("awscli", "awscli2"),
+    ("bat", "bat"),
+    ("bottom", "bottom"),
+    ("broot", "broot"),
+    ("btop", "btop"),
+    ("cargo-edit", "cargo-edit"),
+    ("cargo-watch", "cargo-watch"),
+    ("curl", "curl"),
+    ("delta", "delta"),
+    ("difftastic", "difftastic"),
+    ("direnv", "direnv"),
+    ("du-dust", "dust"),
+    ("duf", "duf"),
+    ("dust", "dust"),
+    ("eza", "eza"),
+    ("fd", "fd"),
+    ("fd-find", "fd"),
+    ("fish", "fish"),
+    ("fzf", "fzf"),
+    ("gh", "gh"),
+    ("git", "git"),
+    ("git-delta", "delta"),
+    ("gitui", "gitui"),
+    ("glow", "glow"),
+    ("go", "go"),
+    ("helix", "helix"),
+    ("hexyl", "hexyl"),
+    ("htop", "htop"),
+    ("httpie", "httpie"),
+    ("hyperfine", "hyperfine"),
+    ("jq", "jq"),
+    ("just", "just"),
+    ("k9s", "k9s"),
+    ("kubectl", "kubectl"),
+    ("lazygit", "lazygit"),
+    ("ncdu", "ncdu"),
+    ("neovim", "neovim"),
+    ("node", "nodejs"),
+    ("nodejs", "nodejs"),
+    ("nushell", "nushell"),
+    ("nvim", "neovim"),
+    ("procs", "procs"),
+    ("python", "python3"),
+    ("python3", "python3"),
+    ("ripgrep", "ripgrep"),
+    ("rustup", "rustup"),
+    ("sd", "sd"),
+    ("shellcheck", "shellcheck"),
+    ("starship", "starship"),
+    ("tealdeer", "tealdeer"),
+    ("terraform", "terraform"),
+    ("tmux", "tmux"),
+    ("tokei", "tokei"),
+    ("tree", "tree"),
+    ("watchexec", "watchexec"),
+    ("wget", "wget"),
+    ("xh", "xh"),
+    ("yazi", "yazi"),
+    ("yq", "yq-go"),
+    ("zellij", "zellij"),
+    ("zoxide", "zoxide"),
+    ("zsh", "zsh"),
+];
+
+/// nixpkgs attributes that home-manager configures through `programs.*`
+const PROGRAMS: &[&str] = &[
+    "atuin", "bat", "bottom", "broot", "btop", "direnv", "eza", "fd", "fish", "fzf", "gh", "git",
+    "gitui", "go", "helix", "htop", "jq", "k9s", "lazygit", "neovim", "nushell", "ripgrep",
+    "starship", "tealdeer", "tmux", "yazi", "zellij", "zoxide", "zsh",
+];
+
+/// The nixpkgs attribute of a tool, if known
+pub fn nixpkgs_attr(tool: &Tool) -> Option<&'static str> {
+    [Some(tool.name.as_str()), tool.binary_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(|name| NIXPKGS.iter().find(|(n, _)| *n == name))
+        .map(|(_, attr)| *attr)
+}
+
+/// Write a home-manager module for the tools
+pub fn write(tools: &[Tool]) -> String {
+    let mut packages = Vec::new();
+    let mut programs = Vec::new();
+    let mut unknown = Vec::new();
+    for tool in tools {
+        match nixpkgs_attr(tool) {
+            Some(attr) if PROGRAMS.contains(&attr) => programs.push(attr),
+            Some(attr) => packages.push(attr),
+            None => unknown.push(tool),
+        }
+    }
+    for list in [&mut packages, &mut programs] {
+        list.sort();
+        list.dedup();
+    }
+
+    let mut out = String::from("# Generated by hoards\n{ pkgs, ... }:\n\n{\n");
+    out.push_str("  home.packages = with pkgs; [\n");
+    for attr in &packages {
+        out.push_str(&format!("    {}\n", attr));
+    }
+    out.push_str("  ];\n");
+
+    if !programs.is_empty() {
+        out.push('\n');
+        for attr in &programs {
+            out.push_str(&format!("  programs.{}.enable = true;\n", attr));
+        }
+    }
+
+    if !unknown.is_empty() {
+        out.push_str("\n  # No known nixpkgs attribute (see https://search.nixos.org/packages):\n");
+        for tool in unknown {
+            out.push_str(&format!("  #   {} ({})\n", tool.name, tool.source));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::InstallSource;
+
+    #[test]
+    fn test_nixpkgs_attr() {
+        assert_eq!(nixpkgs_attr(&Tool::new("fd-find")), Some("fd"));
+        assert_eq!(
+            nixpkgs_attr(&Tool::new("git-delta").with_binary("delta")),
+            Some("delta")
+        );
+        assert_eq!(
+            nixpkgs_attr(&Tool::new("some-crate").with_binary("yq")),
+            Some("yq-go")
+        );
+        assert_eq!(nixpkgs_attr(&Tool::new("my-script")), None);
+    }
+
+    #[test]
+    fn test_write_home_manager() {
+        let tools = vec![
+            Tool::new("ripgrep"),
+            Tool::new("hyperfine"),
+            Tool::new("du-dust"),
+            Tool::new("dust"),
+            Tool::new("git"),
+            Tool::new("my-script").with_source(InstallSource::Manual),
+        ];
+        assert_eq!(
+            write(&tools),
+            "# Generated by hoards
+{ pkgs, ... }:
+
+{
+  home.packages = with pkgs; [
+    dust
+    hyperfine
+  ];
+
+  programs.git.enable = true;
+  programs.ripgrep.enable = true;
+
+  # No known nixpkgs attribute (see https://search.nixos.org/packages):
+  #   my-script (manual)
+}
+"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+//! Package lists of other tools
+//!
+//! Reading and writing the formats other setups already keep their tools
+//! in, so `hoards export` and `hoards import` can move a tool list between
+//! them and hoards.
+
+pub mod brewfile;
+pub mod container;
+pub mod home_manager;
+pub mod tool_versions;
+pub mod topgrade;
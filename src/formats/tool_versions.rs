@@ -0,0 +1,208 @@
+//! asdf `.tool-versions` and mise `mise.toml` manifests
+//!
+//! Both record a version per tool. Plain entries (`node`, `python`) are
+//! runtimes installed by the manager itself; mise backends such as
+//! `cargo:ripgrep` or `npm:prettier` map to the matching install source.
+
+use anyhow::Result;
+
+use crate::models::{InstallSource, Tool};
+
+/// A tool and the version a manifest asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionEntry {
+    /// asdf plugin or mise tool, with its backend prefix if any
+    pub name: String,
+    pub version: String,
+}
+
+/// Which manager a manifest belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Manager {
+    Asdf,
+    Mise,
+}
+
+/// Binaries of runtimes whose plugin is named differently
+const BINARIES: &[(&str, &str)] = &[
+    ("nodejs", "node"),
+    ("golang", "go"),
+    ("rust", "rustc"),
+    ("erlang", "erl"),
+];
+
+/// The manager a manifest is for, from its file name
+pub fn manager(file_name: &str) -> Option<Manager> {
+    match file_name {
+        ".tool-versions" => Some(Manager::Asdf),
+        "mise.toml" | ".mise.toml" | "mise.local.toml" | ".mise.local.toml" => Some(Manager::Mise),
+        _ => None,
+    }
+}
+
+/// Parse a `.tool-versions` file; only the first version of a line counts
+pub fn parse_tool_versions(content: &str) -> Vec<VersionEntry> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            Some(VersionEntry {
+                name: words.next()?.to_string(),
+                version: words.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parse the `[tools]` table of a `mise.toml`
+///
+/// Versions may be a string, a list (the first one counts) or a table with
+/// a `version` key.
+pub fn parse_mise(content: &str) -> Result<Vec<VersionEntry>> {
+    let manifest: toml::Table = toml::from_str(content)?;
+    let Some(tools) = manifest.get("tools").and_then(|t| t.as_table()) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(tools
+        .iter()
+        .filter_map(|(name, value)| {
+            let version = match value {
+                toml::Value::Array(versions) => versions.first()?,
+                toml::Value::Table(options) => options.get("version")?,
+                version => version,
+            };
+            Some(VersionEntry {
+                name: name.clone(),
+                version: version.as_str()?.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Whether a version names a release rather than a moving target
+pub fn is_exact(version: &str) -> bool {
+    !matches!(
+        version,
+        "latest" | "lts" | "stable" | "system" | "ref" | "path"
+    ) && !version.starts_with("ref:")
+        && !version.starts_with("path:")
+}
+
+/// Tools for a manifest's entries, each with its version; `system` entries
+/// are left out
+pub fn tools(entries: &[VersionEntry], manager: Manager) -> Vec<(Tool, String)> {
+    entries
+        .iter()
+        .filter(|entry| entry.version != "system")
+        .map(|entry| {
+            let (backend, name) = entry.name.split_once(':').unwrap_or(("", &entry.name));
+            let name = name.rsplit('/').next().unwrap_or(name);
+            let version = &entry.version;
+            let (source, command) = match (manager, backend) {
+                (Manager::Mise, "cargo") => (
+                    InstallSource::Cargo,
+                    format!("cargo install {} --version {}", name, version),
+                ),
+                (Manager::Mise, "npm") => (
+                    InstallSource::Npm,
+                    format!("npm install -g {}@{}", name, version),
+                ),
+                (Manager::Mise, "pipx") => (
+                    InstallSource::Pip,
+                    format!("pip install {}=={}", name, version),
+                ),
+                (Manager::Mise, _) => (
+                    InstallSource::Manual,
+                    format!("mise use -g {}@{}", entry.name, version),
+                ),
+                (Manager::Asdf, _) => (
+                    InstallSource::Manual,
+                    format!("asdf install {} {}", entry.name, version),
+                ),
+            };
+            let binary = BINARIES
+                .iter()
+                .find(|(plugin, _)| *plugin == name)
+                .map_or(name, |(_, binary)| binary);
+            let tool = Tool::new(name)
+                .with_source(source)
+                .with_install_command(command)
+                .with_binary(binary);
+            (tool, version.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tool_versions() {
+        let content = "\
+# runtimes
+nodejs 20.11.0 18.19.0
+python 3.12.1  # for scripts
+ruby system
+";
+        let entries = parse_tool_versions(content);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].version, "20.11.0");
+        assert_eq!(entries[1].version, "3.12.1");
+
+        let tools = tools(&entries, Manager::Asdf);
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[1].1, "3.12.1");
+        assert_eq!(tools[0].0.binary_name.as_deref(), Some("node"));
+        assert_eq!(
+            tools[0].0.install_command.as_deref(),
+            Some("asdf install nodejs 20.11.0")
+        );
+    }
+
+    #[test]
+    fn test_parse_mise() {
+        let content = r#"
+[env]
+RUST_LOG = "debug"
+
+[tools]
+node = "22"
+python = ["3.12", "3.11"]
+"cargo:ripgrep" = "14.1.0"
+"npm:prettier" = { version = "3.2.5" }
+"ubi:BurntSushi/xsv" = "latest"
+"#;
+        let entries = parse_mise(content).unwrap();
+        let versions: Vec<_> = entries
+            .iter()
+            .map(|e| (e.name.as_str(), e.version.as_str()))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("cargo:ripgrep", "14.1.0"),
+                ("node", "22"),
+                ("npm:prettier", "3.2.5"),
+                ("python", "3.12"),
+                ("ubi:BurntSushi/xsv", "latest"),
+            ]
+        );
+        assert!(!is_exact("latest"));
+
+        let tools: Vec<Tool> = tools(&entries, Manager::Mise)
+            .into_iter()
+            .map(|(tool, _)| tool)
+            .collect();
+        assert_eq!(tools[0].name, "ripgrep");
+        assert_eq!(tools[0].source, InstallSource::Cargo);
+        assert_eq!(tools[2].source, InstallSource::Npm);
+        assert_eq!(tools[4].name, "xsv");
+        assert_eq!(
+            tools[4].install_command.as_deref(),
+            Some("mise use -g ubi:BurntSushi/xsv@latest")
+        );
+    }
+}
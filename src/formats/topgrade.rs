@@ -0,0 +1,139 @@
+//! topgrade configuration
+//!
+//! Enables the topgrade steps for the package managers the hoard uses and
+//! disables the rest; tools topgrade can't update on its own get a custom
+//! command.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::models::{InstallSource, Tool};
+
+/// topgrade steps that update each source's packages
+fn steps(source: &InstallSource) -> &'static [&'static str] {
+    match source {
+        InstallSource::Cargo => &["cargo"],
+        InstallSource::Apt => &["system"],
+        InstallSource::Snap => &["snap"],
+        InstallSource::Flatpak => &["flatpak"],
+        InstallSource::Npm => &["node"],
+        InstallSource::Pip => &["pip3"],
+        InstallSource::Brew => &["brew_formula", "brew_cask"],
+        _ => &[],
+    }
+}
+
+const SOURCES: [InstallSource; 7] = [
+    InstallSource::Cargo,
+    InstallSource::Apt,
+    InstallSource::Snap,
+    InstallSource::Flatpak,
+    InstallSource::Npm,
+    InstallSource::Pip,
+    InstallSource::Brew,
+];
+
+#[derive(Serialize)]
+struct Misc {
+    disable: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct TopgradeConfig {
+    misc: Misc,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    commands: BTreeMap<String, String>,
+}
+
+/// Write a `topgrade.toml` for the tools
+///
+/// Release binaries are upgraded through hoards; manual installs re-run
+/// their install command, and are listed in a comment if they have none.
+pub fn write(tools: &[Tool]) -> Result<String> {
+    let mut disable: Vec<&'static str> = SOURCES
+        .iter()
+        .filter(|source| !tools.iter().any(|t| &t.source == *source))
+        .flat_map(steps)
+        .copied()
+        .collect();
+    disable.sort();
+
+    let mut commands = BTreeMap::new();
+    let mut manual = Vec::new();
+    for tool in tools {
+        match (&tool.source, &tool.install_command) {
+            (InstallSource::GitHub, _) => {
+                commands.insert(
+                    tool.name.clone(),
+                    format!("hoards upgrade {} --force", tool.name),
+                );
+            }
+            (InstallSource::Manual | InstallSource::Unknown, Some(command)) => {
+                commands.insert(tool.name.clone(), command.clone());
+            }
+            (InstallSource::Manual | InstallSource::Unknown, None) => manual.push(&tool.name),
+            _ => {}
+        }
+    }
+
+    let config = TopgradeConfig {
+        misc: Misc { disable },
+        commands,
+    };
+    let mut out = String::from("# Generated by hoards\n");
+    out.push_str(&toml::to_string_pretty(&config)?);
+    if !manual.is_empty() {
+        out.push_str("\n# No install command to update with:\n");
+        for name in manual {
+            out.push_str(&format!("#   {}\n", name));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_topgrade() {
+        let tools = vec![
+            Tool::new("ripgrep").with_source(InstallSource::Cargo),
+            Tool::new("jq").with_source(InstallSource::Apt),
+            Tool::new("lazygit").with_source(InstallSource::GitHub),
+            Tool::new("starship")
+                .with_source(InstallSource::Manual)
+                .with_install_command("curl -sS https://starship.rs/install.sh | sh -s -- -y"),
+            Tool::new("mystery").with_source(InstallSource::Manual),
+        ];
+        let out = write(&tools).unwrap();
+        let parsed: toml::Value = toml::from_str(&out).unwrap();
+        let disable: Vec<&str> = parsed["misc"]["disable"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        assert_eq!(
+            disable,
+            vec![
+                "brew_cask",
+                "brew_formula",
+                "flatpak",
+                "node",
+                "pip3",
+                "snap"
+            ]
+        );
+        assert_eq!(
+            parsed["commands"]["lazygit"].as_str(),
+            Some("hoards upgrade lazygit --force")
+        );
+        assert_eq!(
+            parsed["commands"]["starship"].as_str(),
+            Some("curl -sS https://starship.rs/install.sh | sh -s -- -y")
+        );
+        assert!(out.ends_with("# No install command to update with:\n#   mystery\n"));
+    }
+}
@@ -0,0 +1,236 @@
+//! Why tools are installed, as a graph
+//!
+//! Nodes are tools, bundles and AI features. Edges say why a tool is on the
+//! machine (a bundle installs it, another tool depends on it, AI suggested
+//! it) or that one tool replaces another. Graphs render to Graphviz DOT or
+//! Mermaid.
+
+use std::collections::HashSet;
+
+/// What a node stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Tool,
+    Bundle,
+    /// An AI feature that suggested tools (`discover`, `do`)
+    Ai,
+}
+
+/// How two nodes are related
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Bundle -> member tool
+    Installs,
+    /// Bundle -> bundle it includes
+    Includes,
+    /// Tool -> tool it needs
+    DependsOn,
+    /// AI feature -> tool it suggested
+    Suggested,
+    /// Tool -> tool it replaces
+    Replaces,
+}
+
+impl EdgeKind {
+    fn label(&self) -> &'static str {
+        match self {
+            EdgeKind::Installs => "installs",
+            EdgeKind::Includes => "includes",
+            EdgeKind::DependsOn => "depends on",
+            EdgeKind::Suggested => "suggested",
+            EdgeKind::Replaces => "replaces",
+        }
+    }
+}
+
+/// Directed graph of tool relationships
+#[derive(Debug, Default)]
+pub struct Graph {
+    nodes: Vec<(NodeKind, String)>,
+    edges: Vec<(usize, EdgeKind, usize)>,
+}
+
+impl Graph {
+    fn node(&mut self, kind: NodeKind, name: &str) -> usize {
+        match self.nodes.iter().position(|(k, n)| *k == kind && n == name) {
+            Some(index) => index,
+            None => {
+                self.nodes.push((kind, name.to_string()));
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Add an edge, creating its nodes (duplicates are ignored)
+    pub fn add_edge(&mut self, from: (NodeKind, &str), kind: EdgeKind, to: (NodeKind, &str)) {
+        let from = self.node(from.0, from.1);
+        let to = self.node(to.0, to.1);
+        if from != to && !self.edges.contains(&(from, kind, to)) {
+            self.edges.push((from, kind, to));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// The part of the graph explaining one tool: everything that leads to
+    /// it and everything it leads to
+    pub fn around(&self, tool: &str) -> Graph {
+        let mut graph = Graph::default();
+        let Some(start) = self
+            .nodes
+            .iter()
+            .position(|(k, n)| *k == NodeKind::Tool && n == tool)
+        else {
+            return graph;
+        };
+
+        let mut keep: HashSet<usize> = HashSet::new();
+        for forward in [true, false] {
+            let mut seen = HashSet::from([start]);
+            let mut queue = vec![start];
+            while let Some(node) = queue.pop() {
+                for (i, (from, _, to)) in self.edges.iter().enumerate() {
+                    let (here, next) = if forward { (*from, *to) } else { (*to, *from) };
+                    if here == node {
+                        keep.insert(i);
+                        if seen.insert(next) {
+                            queue.push(next);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut kept: Vec<usize> = keep.into_iter().collect();
+        kept.sort();
+        for i in kept {
+            let (from, kind, to) = self.edges[i];
+            let (from_kind, from_name) = &self.nodes[from];
+            let (to_kind, to_name) = &self.nodes[to];
+            graph.add_edge((*from_kind, from_name), kind, (*to_kind, to_name));
+        }
+        graph
+    }
+
+    fn display_name(&self, node: usize) -> String {
+        let (kind, name) = &self.nodes[node];
+        match kind {
+            NodeKind::Tool => name.clone(),
+            NodeKind::Bundle => format!("bundle: {}", name),
+            NodeKind::Ai => format!("AI: {}", name),
+        }
+    }
+
+    /// Render as a Graphviz digraph
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut out = String::from("digraph hoards {\n    rankdir=LR;\n");
+        for (i, (kind, _)) in self.nodes.iter().enumerate() {
+            let shape = match kind {
+                NodeKind::Tool => "ellipse",
+                NodeKind::Bundle => "box",
+                NodeKind::Ai => "diamond",
+            };
+            out.push_str(&format!(
+                "    n{} [label={}, shape={}];\n",
+                i,
+                quote(&self.display_name(i)),
+                shape
+            ));
+        }
+        for (from, kind, to) in &self.edges {
+            let style = if *kind == EdgeKind::Replaces {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    n{} -> n{} [label={}{}];\n",
+                from,
+                to,
+                quote(kind.label()),
+                style
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("graph LR\n");
+        for (i, (kind, _)) in self.nodes.iter().enumerate() {
+            let label = self.display_name(i).replace('"', "#quot;");
+            let node = match kind {
+                NodeKind::Tool => format!("[\"{}\"]", label),
+                NodeKind::Bundle => format!("[[\"{}\"]]", label),
+                NodeKind::Ai => format!("{{{{\"{}\"}}}}", label),
+            };
+            out.push_str(&format!("    n{}{}\n", i, node));
+        }
+        for (from, kind, to) in &self.edges {
+            let arrow = if *kind == EdgeKind::Replaces {
+                "-.->"
+            } else {
+                "-->"
+            };
+            out.push_str(&format!(
+                "    n{} {}|{}| n{}\n",
+                from,
+                arrow,
+                kind.label(),
+                to
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Graph {
+        let mut graph = Graph::default();
+        let tool = |name| (NodeKind::Tool, name);
+        graph.add_edge((NodeKind::Bundle, "dev"), EdgeKind::Installs, tool("bat"));
+        graph.add_edge(tool("bat"), EdgeKind::DependsOn, tool("libgit2"));
+        graph.add_edge(tool("bat"), EdgeKind::DependsOn, tool("libgit2"));
+        graph.add_edge(
+            (NodeKind::Ai, "discover"),
+            EdgeKind::Suggested,
+            tool("zoxide"),
+        );
+        graph
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = sample().to_dot();
+        assert!(dot.starts_with("digraph hoards {"));
+        assert!(dot.contains("n0 [label=\"bundle: dev\", shape=box];"));
+        assert!(dot.contains("n0 -> n1 [label=\"installs\"];"));
+        assert_eq!(dot.matches("depends on").count(), 1);
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mermaid = sample().to_mermaid();
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("    n0[[\"bundle: dev\"]]\n"));
+        assert!(mermaid.contains("    n3{{\"AI: discover\"}}\n"));
+        assert!(mermaid.contains("    n1 -->|depends on| n2\n"));
+    }
+
+    #[test]
+    fn test_around() {
+        let graph = sample().around("bat");
+        let dot = graph.to_dot();
+        assert!(dot.contains("bundle: dev"));
+        assert!(dot.contains("libgit2"));
+        assert!(!dot.contains("zoxide"));
+        assert!(sample().around("missing").is_empty());
+    }
+}
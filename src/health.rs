@@ -0,0 +1,157 @@
+//! Per-tool health score
+//!
+//! A tool's score (0-100) is the average of whichever of four signals are
+//! known for it: how recently it was used, how many releases it is behind,
+//! how active its upstream is and whether its config links are intact.
+//! Signals that can't be measured (no usage tracking, an unwatched tool, no
+//! forge metadata, no configs) are left out rather than counted as bad.
+
+use serde::Serialize;
+
+use crate::db::ABANDONED_AFTER_DAYS;
+
+/// How a health score reads at a glance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthGrade {
+    /// 70 and up
+    Good,
+    /// 40 to 69
+    Fair,
+    /// Below 40
+    Poor,
+}
+
+/// Health of one tool, overall and per signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ToolHealth {
+    pub score: u8,
+    pub usage: Option<u8>,
+    pub updates: Option<u8>,
+    pub upstream: Option<u8>,
+    pub configs: Option<u8>,
+}
+
+impl ToolHealth {
+    /// Combine the known signals, or None when none is known
+    pub fn from_signals(
+        usage: Option<u8>,
+        updates: Option<u8>,
+        upstream: Option<u8>,
+        configs: Option<u8>,
+    ) -> Option<Self> {
+        let known: Vec<u32> = [usage, updates, upstream, configs]
+            .into_iter()
+            .flatten()
+            .map(u32::from)
+            .collect();
+        if known.is_empty() {
+            return None;
+        }
+        Some(Self {
+            score: (known.iter().sum::<u32>() / known.len() as u32) as u8,
+            usage,
+            updates,
+            upstream,
+            configs,
+        })
+    }
+
+    pub fn grade(&self) -> HealthGrade {
+        if self.score >= 70 {
+            HealthGrade::Good
+        } else if self.score >= 40 {
+            HealthGrade::Fair
+        } else {
+            HealthGrade::Poor
+        }
+    }
+
+    /// Name of the lowest known signal, when it is below the others' best
+    pub fn weakest(&self) -> Option<&'static str> {
+        let signals = [
+            ("usage", self.usage),
+            ("updates", self.updates),
+            ("upstream", self.upstream),
+            ("configs", self.configs),
+        ];
+        let (name, lowest) = signals
+            .iter()
+            .filter_map(|(name, score)| Some((*name, (*score)?)))
+            .min_by_key(|(_, score)| *score)?;
+        (lowest < 100).then_some(name)
+    }
+}
+
+/// Usage signal from days since the tool was last used (None: never)
+pub fn usage_score(days_since_use: Option<i64>) -> u8 {
+    let Some(days) = days_since_use else {
+        return 0;
+    };
+    [(7, 100), (30, 80), (90, 60), (180, 40), (365, 20)]
+        .into_iter()
+        .find(|(within, _)| days <= *within)
+        .map_or(0, |(_, score)| score)
+}
+
+/// Update signal from the number of known releases newer than the
+/// installed version
+pub fn update_score(releases_behind: usize) -> u8 {
+    100u8.saturating_sub(25u8.saturating_mul(releases_behind.min(4) as u8))
+}
+
+/// Upstream signal from days since the last commit or release
+pub fn upstream_score(days_since_activity: Option<i64>, archived: bool) -> Option<u8> {
+    if archived {
+        return Some(0);
+    }
+    let days = days_since_activity?;
+    Some(if days <= 90 {
+        100
+    } else if days <= 365 {
+        75
+    } else if days < ABANDONED_AFTER_DAYS {
+        40
+    } else {
+        10
+    })
+}
+
+/// Config signal from how many of a tool's configs are in place
+pub fn config_score(intact: usize, total: usize) -> Option<u8> {
+    (total > 0).then(|| (intact.min(total) * 100 / total) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_scores() {
+        assert_eq!(usage_score(Some(2)), 100);
+        assert_eq!(usage_score(Some(120)), 40);
+        assert_eq!(usage_score(None), 0);
+        assert_eq!(update_score(0), 100);
+        assert_eq!(update_score(2), 50);
+        assert_eq!(update_score(9), 0);
+        assert_eq!(upstream_score(Some(30), false), Some(100));
+        assert_eq!(upstream_score(Some(30), true), Some(0));
+        assert_eq!(upstream_score(Some(ABANDONED_AFTER_DAYS), false), Some(10));
+        assert_eq!(upstream_score(None, false), None);
+        assert_eq!(config_score(1, 2), Some(50));
+        assert_eq!(config_score(0, 0), None);
+    }
+
+    #[test]
+    fn test_from_signals() {
+        assert!(ToolHealth::from_signals(None, None, None, None).is_none());
+
+        let health = ToolHealth::from_signals(Some(20), None, Some(100), Some(50)).unwrap();
+        assert_eq!(health.score, 56);
+        assert_eq!(health.grade(), HealthGrade::Fair);
+        assert_eq!(health.weakest(), Some("usage"));
+
+        let healthy = ToolHealth::from_signals(Some(100), Some(100), None, None).unwrap();
+        assert_eq!(healthy.grade(), HealthGrade::Good);
+        assert_eq!(healthy.weakest(), None);
+    }
+}
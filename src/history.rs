@@ -1,11 +1,14 @@
 //! Shell history parsing for usage tracking
 //!
-//! Parses history files from Fish, Bash, and Zsh to count tool usage.
+//! Parses history files from Fish, Bash, Zsh and Nushell, or atuin's
+//! database, to count tool usage.
 
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::config::{HistorySource, UsageConfig};
 
 /// Parsed command from history
 #[derive(Debug)]
@@ -15,8 +18,15 @@ pub struct HistoryEntry {
 }
 
 /// Get the path to Fish history file
+///
+/// Fish names the file after the `fish_history` session variable when it is
+/// exported (`<session>_history`), and uses `fish_history` otherwise.
 pub fn fish_history_path() -> Option<PathBuf> {
-    dirs::data_local_dir().map(|d| d.join("fish").join("fish_history"))
+    let session = std::env::var("fish_history")
+        .ok()
+        .filter(|s| !s.is_empty() && s != "default")
+        .unwrap_or_else(|| "fish".to_string());
+    dirs::data_local_dir().map(|d| d.join("fish").join(format!("{}_history", session)))
 }
 
 /// Get the path to Bash history file
@@ -29,11 +39,39 @@ pub fn zsh_history_path() -> Option<PathBuf> {
     dirs::home_dir().map(|d| d.join(".zsh_history"))
 }
 
+/// Get the path to Nushell's plaintext history (`history.file_format = "plaintext"`)
+pub fn nushell_history_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("nushell").join("history.txt"))
+}
+
+/// Get the path to Nushell's SQLite history (`history.file_format = "sqlite"`)
+pub fn nushell_sqlite_history_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("nushell").join("history.sqlite3"))
+}
+
+/// Get the path to atuin's history database
+///
+/// Atuin reads `ATUIN_DB_PATH` and otherwise keeps the database under the XDG
+/// data directory (`~/.local/share` on every platform).
+pub fn atuin_db_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("ATUIN_DB_PATH").filter(|p| !p.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|d| d.is_absolute())
+        .or_else(|| dirs::home_dir().map(|h| h.join(".local").join("share")))
+        .map(|d| d.join("atuin").join("history.db"))
+}
+
 /// Parse Fish history file
-/// Format: `- cmd: <command>\n  when: <timestamp>\n`
+/// Format: `- cmd: <command>\n  when: <timestamp>\n  paths:\n    - <path>\n`
+///
+/// Newlines and backslashes inside a command are escaped as `\n` and `\\`.
 pub fn parse_fish_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
-    let content = fs::read_to_string(path)
+    let bytes = fs::read(path)
         .with_context(|| format!("Failed to read fish history: {}", path.display()))?;
+    let content = String::from_utf8_lossy(&bytes);
 
     let mut entries = Vec::new();
     let mut current_cmd: Option<String> = None;
@@ -48,7 +86,7 @@ pub fn parse_fish_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
                     timestamp: current_time.take(),
                 });
             }
-            current_cmd = Some(cmd.to_string());
+            current_cmd = Some(unescape_fish(cmd));
         } else if let Some(when) = line.strip_prefix("  when: ") {
             current_time = when.parse().ok();
         }
@@ -65,19 +103,47 @@ pub fn parse_fish_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
     Ok(entries)
 }
 
-/// Parse Bash history file (simple format, one command per line)
+/// Undo fish's escaping of a history command
+fn unescape_fish(cmd: &str) -> String {
+    let mut out = String::with_capacity(cmd.len());
+    let mut chars = cmd.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Parse Bash history file (one command per line, optionally preceded by
+/// a `#<timestamp>` line)
 pub fn parse_bash_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read bash history: {}", path.display()))?;
 
-    let entries = content
-        .lines()
-        .filter(|line| !line.is_empty() && !line.starts_with('#'))
-        .map(|line| HistoryEntry {
+    // With HISTTIMEFORMAT set, bash writes `#<unix timestamp>` before each command
+    let mut entries = Vec::new();
+    let mut timestamp = None;
+    for line in content.lines().filter(|line| !line.is_empty()) {
+        if let Some(comment) = line.strip_prefix('#') {
+            timestamp = comment.parse().ok();
+            continue;
+        }
+        entries.push(HistoryEntry {
             command: line.to_string(),
-            timestamp: None,
-        })
-        .collect();
+            timestamp: timestamp.take(),
+        });
+    }
 
     Ok(entries)
 }
@@ -115,6 +181,219 @@ pub fn parse_zsh_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
     Ok(entries)
 }
 
+/// Parse Nushell's plaintext history (one command per line, no timestamps)
+pub fn parse_nushell_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read nushell history: {}", path.display()))?;
+
+    let entries = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| HistoryEntry {
+            command: line.to_string(),
+            timestamp: None,
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Parse Nushell's SQLite history (`history` table, `start_timestamp` in ms)
+pub fn parse_nushell_sqlite_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
+    use rusqlite::{Connection, OpenFlags};
+
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open nushell history: {}", path.display()))?;
+    let mut stmt = conn.prepare("SELECT command_line, start_timestamp FROM history ORDER BY id")?;
+    let entries = stmt
+        .query_map([], |row| {
+            Ok(HistoryEntry {
+                command: row.get(0)?,
+                timestamp: row.get::<_, Option<i64>>(1)?.map(|ms| ms / 1000),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Parse atuin's history database (`history` table, `timestamp` in ns)
+///
+/// Entries deleted in atuin are skipped.
+pub fn parse_atuin_history(path: &Path) -> Result<Vec<HistoryEntry>> {
+    use rusqlite::{Connection, OpenFlags};
+
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open atuin history: {}", path.display()))?;
+    // `deleted_at` only exists since atuin 14
+    let mut stmt = conn
+        .prepare(
+            "SELECT command, timestamp FROM history WHERE deleted_at IS NULL ORDER BY timestamp",
+        )
+        .or_else(|_| conn.prepare("SELECT command, timestamp FROM history ORDER BY timestamp"))?;
+    let entries = stmt
+        .query_map([], |row| {
+            Ok(HistoryEntry {
+                command: row.get(0)?,
+                timestamp: row.get::<_, Option<i64>>(1)?.map(|ns| ns / 1_000_000_000),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Read atuin history through its CLI, for when the database can't be opened
+///
+/// Timestamps aren't available this way.
+fn atuin_cli_history() -> Result<Vec<HistoryEntry>> {
+    let output = std::process::Command::new("atuin")
+        .args(["history", "list", "--cmd-only"])
+        .output()
+        .context("Failed to run atuin")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "atuin history list failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| HistoryEntry {
+            command: l.to_string(),
+            timestamp: None,
+        })
+        .collect())
+}
+
+/// Load atuin history from the configured or default database, falling back
+/// to `atuin history list`
+pub fn load_atuin_history(db_override: Option<&Path>) -> Result<Vec<HistoryEntry>> {
+    let path = db_override.map(Path::to_path_buf).or_else(atuin_db_path);
+    if let Some(path) = path.filter(|p| p.exists()) {
+        match parse_atuin_history(&path) {
+            Ok(entries) => return Ok(entries),
+            Err(e) => tracing::warn!("{:#}", e),
+        }
+    }
+    if crate::scanner::is_installed("atuin") {
+        return atuin_cli_history();
+    }
+    anyhow::bail!("atuin history not found")
+}
+
+/// Get the path of the pending usage log written by `hoards usage log --fast`
+///
+/// It sits next to the database and is flushed into it by `sync` and the TUI.
+pub fn usage_log_path() -> Result<PathBuf> {
+    Ok(crate::db::Database::db_path()?.with_file_name("usage.log"))
+}
+
+/// Command logged by the shell hook
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsageEvent {
+    pub command: String,
+    /// First subcommand or flag, see [`extract_command_pattern`]
+    pub pattern: Option<String>,
+    pub timestamp: i64,
+    /// Working directory the command ran in
+    pub cwd: Option<PathBuf>,
+}
+
+/// Append one command to the pending usage log
+/// Format: `<unix timestamp>\t<command>[ <pattern>]\t<cwd>`, one line per event
+pub fn append_usage_log(
+    path: &Path,
+    command: &str,
+    pattern: Option<&str>,
+    cwd: Option<&Path>,
+    timestamp: i64,
+) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open usage log: {}", path.display()))?;
+
+    // One write per line keeps concurrent appends from interleaving
+    let cwd = cwd.map(|d| d.to_string_lossy()).unwrap_or_default();
+    let command = match pattern {
+        Some(pattern) => format!("{} {}", command, pattern),
+        None => command.to_string(),
+    };
+    file.write_all(format!("{}\t{}\t{}\n", timestamp, command, cwd).as_bytes())?;
+    Ok(())
+}
+
+/// Parse a pending usage log (malformed lines are skipped)
+///
+/// Lines written before directories were recorded have no cwd field.
+pub fn parse_usage_log(path: &Path) -> Result<Vec<UsageEvent>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read usage log: {}", path.display()))?;
+
+    let entries = content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let timestamp = fields.next()?.parse().ok()?;
+            let field = fields.next()?;
+            let (command, pattern) = match field.split_once(' ') {
+                Some((command, pattern)) => (command, Some(pattern.to_string())),
+                None => (field, None),
+            };
+            let cwd = fields.next().filter(|d| !d.is_empty()).map(PathBuf::from);
+            Some(UsageEvent {
+                command: command.to_string(),
+                pattern,
+                timestamp,
+                cwd,
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Local weekday (0 = Monday) and hour of a unix timestamp
+pub fn time_bucket(timestamp: i64) -> Option<(u32, u32)> {
+    use chrono::{Datelike, Local, TimeZone, Timelike};
+
+    let time = Local.timestamp_opt(timestamp, 0).single()?;
+    Some((time.weekday().num_days_from_monday(), time.hour()))
+}
+
+/// Count uses per (command, weekday, hour) for entries with timestamps
+pub fn count_command_rhythms(entries: &[HistoryEntry]) -> HashMap<(String, u32, u32), i64> {
+    let mut counts = HashMap::new();
+
+    for entry in entries {
+        let Some((weekday, hour)) = entry.timestamp.and_then(time_bucket) else {
+            continue;
+        };
+        for cmd in entry.command.lines().filter_map(extract_command) {
+            *counts.entry((cmd.to_string(), weekday, hour)).or_insert(0) += 1;
+        }
+    }
+
+    counts
+}
+
+/// Drop a leading `sudo`, `env`, `time` or `command`
+fn strip_wrapper(line: &str) -> &str {
+    line.strip_prefix("sudo ")
+        .or_else(|| line.strip_prefix("env "))
+        .or_else(|| line.strip_prefix("time "))
+        .or_else(|| line.strip_prefix("command "))
+        .unwrap_or(line)
+}
+
 /// Extract the base command from a command line (first word, without path)
 pub fn extract_command(line: &str) -> Option<&str> {
     let line = line.trim();
@@ -125,12 +404,7 @@ pub fn extract_command(line: &str) -> Option<&str> {
     }
 
     // Handle sudo, env, time, etc.
-    let line = line
-        .strip_prefix("sudo ")
-        .or_else(|| line.strip_prefix("env "))
-        .or_else(|| line.strip_prefix("time "))
-        .or_else(|| line.strip_prefix("command "))
-        .unwrap_or(line);
+    let line = strip_wrapper(line);
 
     // Get first word
     let cmd = line.split_whitespace().next()?;
@@ -143,6 +417,8 @@ pub fn extract_command(line: &str) -> Option<&str> {
         "cd", "ls", "echo", "export", "set", "unset", "alias", "source", "if", "then", "else",
         "fi", "for", "do", "done", "while", "case", "esac", "function", "return", "exit", "true",
         "false", "test", "[", "[[", "pwd", "pushd", "popd", "dirs", "history", "clear",
+        // fish
+        "begin", "end", "and", "or", "not", "switch", "abbr",
     ];
 
     if skip.contains(&cmd) {
@@ -152,12 +428,61 @@ pub fn extract_command(line: &str) -> Option<&str> {
     Some(cmd)
 }
 
+/// Extract the base command and its first subcommand or flag
+///
+/// `git commit -m x` gives `commit`, `rg -i todo` gives `-i` and
+/// `curl --header=x` gives `--header`. Arguments that look like paths,
+/// numbers or values are ignored.
+pub fn extract_command_pattern(line: &str) -> Option<(&str, &str)> {
+    let cmd = extract_command(line)?;
+    let arg = strip_wrapper(line.trim()).split_whitespace().nth(1)?;
+
+    let pattern = if arg.starts_with('-') {
+        let flag = arg.split('=').next().unwrap_or(arg);
+        let name = flag.trim_start_matches('-');
+        let valid = !name.is_empty()
+            && flag.len() - name.len() <= 2
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        valid.then_some(flag)?
+    } else {
+        let valid = arg.starts_with(|c: char| c.is_ascii_lowercase())
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        valid.then_some(arg)?
+    };
+
+    (pattern.len() <= 24).then_some((cmd, pattern))
+}
+
+/// Count (command, subcommand or flag) pairs from history entries
+pub fn count_command_patterns(entries: &[HistoryEntry]) -> HashMap<(String, String), i64> {
+    let mut counts = HashMap::new();
+
+    for entry in entries {
+        for line in entry.command.lines() {
+            for segment in line.split(['|', ';']).flat_map(|s| s.split("&&")) {
+                if let Some((cmd, pattern)) = extract_command_pattern(segment) {
+                    *counts
+                        .entry((cmd.to_string(), pattern.to_string()))
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    counts
+}
+
 /// Count command usage from history entries
 pub fn count_commands(entries: &[HistoryEntry]) -> HashMap<String, i64> {
     let mut counts: HashMap<String, i64> = HashMap::new();
 
+    // Multi-line entries (fish loops, functions) count each line's command
     for entry in entries {
-        if let Some(cmd) = extract_command(&entry.command) {
+        for cmd in entry.command.lines().filter_map(extract_command) {
             *counts.entry(cmd.to_string()).or_insert(0) += 1;
         }
     }
@@ -165,56 +490,178 @@ pub fn count_commands(entries: &[HistoryEntry]) -> HashMap<String, i64> {
     counts
 }
 
-/// Parse all available shell histories and combine counts
-pub fn parse_all_histories() -> Result<HashMap<String, i64>> {
-    let mut total_counts: HashMap<String, i64> = HashMap::new();
+/// Entries read from one shell's history (or atuin)
+#[derive(Debug)]
+pub struct ShellHistory {
+    pub shell: &'static str,
+    pub entries: Vec<HistoryEntry>,
+}
 
-    // Try Fish history
-    if let Some(path) = fish_history_path()
-        && path.exists()
-    {
-        match parse_fish_history(&path) {
-            Ok(entries) => {
-                let counts = count_commands(&entries);
-                for (cmd, count) in counts {
-                    *total_counts.entry(cmd).or_insert(0) += count;
-                }
+/// Histories from several shells merged into one list
+#[derive(Debug, Default)]
+pub struct MergedHistory {
+    pub entries: Vec<HistoryEntry>,
+    /// Per shell: (name, entries read, entries kept after deduplication)
+    pub per_shell: Vec<(&'static str, usize, usize)>,
+}
+
+/// Read every configured history source that exists
+///
+/// `[usage] history` picks the shell history files, atuin, or both. A file
+/// shared by two shells (e.g. bash and zsh pointing at one HISTFILE) is only
+/// read once.
+pub fn read_histories(usage: &UsageConfig) -> Vec<ShellHistory> {
+    type Parser = fn(&PathBuf) -> Result<Vec<HistoryEntry>>;
+
+    let mut histories = Vec::new();
+    if matches!(usage.history, HistorySource::Shell | HistorySource::All) {
+        let files: [(&'static str, Option<PathBuf>, Parser); 5] = [
+            ("fish", fish_history_path(), parse_fish_history),
+            ("bash", bash_history_path(), parse_bash_history),
+            ("zsh", zsh_history_path(), parse_zsh_history),
+            (
+                "nushell",
+                nushell_sqlite_history_path(),
+                parse_nushell_sqlite_history,
+            ),
+            ("nushell", nushell_history_path(), parse_nushell_history),
+        ];
+        let mut seen = std::collections::HashSet::new();
+        for (shell, path, parse) in files {
+            let Some(path) = path.filter(|p| p.exists()) else {
+                continue;
+            };
+            if !seen.insert(fs::canonicalize(&path).unwrap_or_else(|_| path.clone())) {
+                continue;
+            }
+            match parse(&path) {
+                Ok(entries) => histories.push(ShellHistory { shell, entries }),
+                Err(e) => tracing::warn!("Failed to parse {} history: {}", shell, e),
             }
-            Err(e) => eprintln!("Warning: Failed to parse fish history: {}", e),
         }
     }
+    if matches!(usage.history, HistorySource::Atuin | HistorySource::All) {
+        match load_atuin_history(usage.atuin_db.as_deref()) {
+            Ok(entries) => histories.push(ShellHistory {
+                shell: "atuin",
+                entries,
+            }),
+            Err(e) => tracing::warn!("Failed to read atuin history: {}", e),
+        }
+    }
+    histories
+}
 
-    // Try Bash history
-    if let Some(path) = bash_history_path()
-        && path.exists()
-    {
-        match parse_bash_history(&path) {
-            Ok(entries) => {
-                let counts = count_commands(&entries);
-                for (cmd, count) in counts {
-                    *total_counts.entry(cmd).or_insert(0) += count;
+/// Merge histories, dropping entries an earlier history already holds
+///
+/// Only timestamped entries can be matched: the same command at the same
+/// second in two histories (atuin imports, shared history files) counts once.
+pub fn merge_histories(histories: Vec<ShellHistory>) -> MergedHistory {
+    use std::collections::HashSet;
+
+    let mut merged = MergedHistory::default();
+    let mut earlier: HashSet<(String, i64)> = HashSet::new();
+    for history in histories {
+        let read = history.entries.len();
+        let mut keys = Vec::new();
+        let mut kept = 0;
+        for entry in history.entries {
+            if let Some(ts) = entry.timestamp {
+                let key = (entry.command.trim().to_string(), ts);
+                if earlier.contains(&key) {
+                    continue;
                 }
+                keys.push(key);
+            }
+            merged.entries.push(entry);
+            kept += 1;
+        }
+        earlier.extend(keys);
+        // Nushell can contribute two files; report them together
+        match merged
+            .per_shell
+            .iter_mut()
+            .find(|(s, _, _)| *s == history.shell)
+        {
+            Some((_, r, k)) => {
+                *r += read;
+                *k += kept;
             }
-            Err(e) => eprintln!("Warning: Failed to parse bash history: {}", e),
+            None => merged.per_shell.push((history.shell, read, kept)),
         }
     }
+    merged
+}
 
-    // Try Zsh history
-    if let Some(path) = zsh_history_path()
-        && path.exists()
-    {
-        match parse_zsh_history(&path) {
-            Ok(entries) => {
-                let counts = count_commands(&entries);
-                for (cmd, count) in counts {
-                    *total_counts.entry(cmd).or_insert(0) += count;
-                }
+/// Parse the configured history sources into one deduplicated list
+pub fn parse_all_history_entries(usage: &UsageConfig) -> Result<Vec<HistoryEntry>> {
+    Ok(merge_histories(read_histories(usage)).entries)
+}
+
+/// Parse the configured history sources and combine counts
+pub fn parse_all_histories(usage: &UsageConfig) -> Result<HashMap<String, i64>> {
+    Ok(count_commands(&parse_all_history_entries(usage)?))
+}
+
+/// Commands run in one history line (each segment of a pipeline or chain)
+fn line_commands(line: &str) -> Vec<&str> {
+    line.split(['|', ';'])
+        .flat_map(|segment| segment.split("&&"))
+        .filter_map(extract_command)
+        .collect()
+}
+
+/// Count pairs of different commands used together
+///
+/// Two commands co-occur when they appear in the same line (pipelines and
+/// `&&`/`;` chains) or in consecutive history entries. Pairs are stored
+/// alphabetically ordered.
+pub fn count_command_pairs(entries: &[HistoryEntry]) -> HashMap<(String, String), i64> {
+    let mut pairs: HashMap<(String, String), i64> = HashMap::new();
+    let mut add = |a: &str, b: &str| {
+        if a != b {
+            let key = if a < b { (a, b) } else { (b, a) };
+            *pairs
+                .entry((key.0.to_string(), key.1.to_string()))
+                .or_insert(0) += 1;
+        }
+    };
+
+    let mut previous: Option<&str> = None;
+    for entry in entries {
+        let commands = line_commands(&entry.command);
+        for (i, a) in commands.iter().enumerate() {
+            for b in &commands[i + 1..] {
+                add(a, b);
             }
-            Err(e) => eprintln!("Warning: Failed to parse zsh history: {}", e),
         }
+        if let (Some(prev), Some(first)) = (previous, commands.first()) {
+            add(prev, first);
+        }
+        previous = commands.last().copied();
     }
 
-    Ok(total_counts)
+    pairs
+}
+
+/// Most frequent full command lines (with arguments), most used first
+pub fn top_command_lines(entries: &[HistoryEntry], limit: usize) -> Vec<(String, i64)> {
+    let mut counts: HashMap<&str, i64> = HashMap::new();
+    for entry in entries {
+        let line = entry.command.trim();
+        // Only multi-word lines are worth an alias
+        if line.contains(' ') && extract_command(line).is_some() {
+            *counts.entry(line).or_insert(0) += 1;
+        }
+    }
+
+    let mut lines: Vec<(String, i64)> = counts
+        .into_iter()
+        .map(|(line, count)| (line.to_string(), count))
+        .collect();
+    lines.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    lines.truncate(limit);
+    lines
 }
 
 #[cfg(test)]
@@ -312,6 +759,89 @@ mod tests {
         assert_eq!(counts.get("rg"), Some(&1));
     }
 
+    #[test]
+    fn test_extract_command_pattern() {
+        assert_eq!(
+            extract_command_pattern("git commit -m 'x'"),
+            Some(("git", "commit"))
+        );
+        assert_eq!(
+            extract_command_pattern("sudo rg -i todo"),
+            Some(("rg", "-i"))
+        );
+        assert_eq!(
+            extract_command_pattern("curl --header=x https://a"),
+            Some(("curl", "--header"))
+        );
+        assert_eq!(extract_command_pattern("bat src/main.rs"), None);
+        assert_eq!(extract_command_pattern("sleep 10"), None);
+        assert_eq!(extract_command_pattern("git"), None);
+        assert_eq!(extract_command_pattern("cd projects"), None);
+    }
+
+    #[test]
+    fn test_count_command_patterns() {
+        let entries: Vec<HistoryEntry> = [
+            "git commit -m x",
+            "git status && git commit --amend",
+            "rg -i foo | fzf",
+        ]
+        .iter()
+        .map(|cmd| HistoryEntry {
+            command: cmd.to_string(),
+            timestamp: None,
+        })
+        .collect();
+
+        let counts = count_command_patterns(&entries);
+        let get = |cmd: &str, pattern: &str| counts.get(&(cmd.to_string(), pattern.to_string()));
+        assert_eq!(get("git", "commit"), Some(&2));
+        assert_eq!(get("git", "status"), Some(&1));
+        assert_eq!(get("rg", "-i"), Some(&1));
+        assert_eq!(counts.len(), 3);
+    }
+
+    #[test]
+    fn test_count_command_pairs() {
+        let entries: Vec<HistoryEntry> =
+            ["rg todo | fzf", "cargo build && cargo test", "git status"]
+                .iter()
+                .map(|cmd| HistoryEntry {
+                    command: cmd.to_string(),
+                    timestamp: None,
+                })
+                .collect();
+
+        let pairs = count_command_pairs(&entries);
+        assert_eq!(pairs.get(&("fzf".to_string(), "rg".to_string())), Some(&1));
+        // Consecutive entries: fzf -> cargo, cargo -> git
+        assert_eq!(
+            pairs.get(&("cargo".to_string(), "fzf".to_string())),
+            Some(&1)
+        );
+        assert_eq!(
+            pairs.get(&("cargo".to_string(), "git".to_string())),
+            Some(&1)
+        );
+        // Repeated command in one line is not a pair
+        assert!(!pairs.contains_key(&("cargo".to_string(), "cargo".to_string())));
+    }
+
+    #[test]
+    fn test_top_command_lines() {
+        let entries: Vec<HistoryEntry> = ["git status", "git status", "htop", "cd src", "git push"]
+            .iter()
+            .map(|cmd| HistoryEntry {
+                command: cmd.to_string(),
+                timestamp: None,
+            })
+            .collect();
+
+        let lines = top_command_lines(&entries, 5);
+        assert_eq!(lines[0], ("git status".to_string(), 2));
+        assert_eq!(lines.len(), 2);
+    }
+
     #[test]
     fn test_count_commands_empty() {
         let entries: Vec<HistoryEntry> = vec![];
@@ -360,6 +890,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_fish_history_multiline_and_paths() -> Result<()> {
+        let mut file = NamedTempFile::new()?;
+        writeln!(file, "- cmd: for f in *.md\\n    bat $f\\nend")?;
+        writeln!(file, "  when: 1704067200")?;
+        writeln!(file, "- cmd: rg 'a\\\\\\\\b' src")?;
+        writeln!(file, "  when: 1704067300")?;
+        writeln!(file, "  paths:")?;
+        writeln!(file, "    - src")?;
+        writeln!(file, "- cmd: eza")?;
+        file.flush()?;
+
+        let entries = parse_fish_history(&file.path().to_path_buf())?;
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].command, "for f in *.md\n    bat $f\nend");
+        assert_eq!(entries[1].command, "rg 'a\\\\b' src");
+        assert_eq!(entries[1].timestamp, Some(1704067300));
+        assert_eq!(entries[2].command, "eza");
+
+        let counts = count_commands(&entries);
+        assert_eq!(counts.get("bat"), Some(&1));
+        assert_eq!(counts.get("rg"), Some(&1));
+        assert!(!counts.contains_key("end"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_parse_fish_history_empty() -> Result<()> {
         let file = NamedTempFile::new()?;
@@ -369,6 +927,128 @@ mod tests {
         Ok(())
     }
 
+    // ==================== Nushell History Parsing Tests ====================
+
+    #[test]
+    fn test_parse_nushell_sqlite_history() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("history.sqlite3");
+        let conn = rusqlite::Connection::open(&path)?;
+        conn.execute_batch(
+            "CREATE TABLE history (id INTEGER PRIMARY KEY, command_line TEXT NOT NULL,
+                                   start_timestamp INTEGER, cwd TEXT);
+             INSERT INTO history (command_line, start_timestamp, cwd)
+             VALUES ('ls | where size > 1kb', 1704067200123, '/tmp'),
+                    ('rg todo', NULL, '/tmp');",
+        )?;
+        drop(conn);
+
+        let entries = parse_nushell_sqlite_history(&path)?;
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "ls | where size > 1kb");
+        assert_eq!(entries[0].timestamp, Some(1704067200));
+        assert_eq!(entries[1].command, "rg todo");
+        assert!(entries[1].timestamp.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_atuin_history() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("history.db");
+        let conn = rusqlite::Connection::open(&path)?;
+        conn.execute_batch(
+            "CREATE TABLE history (id TEXT PRIMARY KEY, timestamp INTEGER NOT NULL,
+                                   duration INTEGER NOT NULL, exit INTEGER NOT NULL,
+                                   command TEXT NOT NULL, cwd TEXT NOT NULL,
+                                   session TEXT NOT NULL, hostname TEXT NOT NULL,
+                                   deleted_at INTEGER);
+             INSERT INTO history VALUES
+                ('b', 1704067260000000000, 0, 0, 'rg todo', '/tmp', 's', 'h', NULL),
+                ('a', 1704067200000000000, 0, 0, 'git status', '/tmp', 's', 'h', NULL),
+                ('c', 1704067300000000000, 0, 0, 'rm -rf x', '/tmp', 's', 'h', 1704067400);",
+        )?;
+        drop(conn);
+
+        let entries = parse_atuin_history(&path)?;
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "git status");
+        assert_eq!(entries[0].timestamp, Some(1704067200));
+        assert_eq!(entries[1].command, "rg todo");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_command_rhythms() {
+        use chrono::{Local, TimeZone};
+
+        // Wednesday 2024-01-03, 14:30 local time
+        let ts = Local
+            .with_ymd_and_hms(2024, 1, 3, 14, 30, 0)
+            .single()
+            .unwrap()
+            .timestamp();
+        assert_eq!(time_bucket(ts), Some((2, 14)));
+
+        let entries = vec![
+            HistoryEntry {
+                command: "rg foo".to_string(),
+                timestamp: Some(ts),
+            },
+            HistoryEntry {
+                command: "rg bar".to_string(),
+                timestamp: Some(ts + 60),
+            },
+            HistoryEntry {
+                command: "fd baz".to_string(),
+                timestamp: None,
+            },
+        ];
+        let counts = count_command_rhythms(&entries);
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[&("rg".to_string(), 2, 14)], 2);
+    }
+
+    // ==================== Pending Usage Log Tests ====================
+
+    #[test]
+    fn test_usage_log_roundtrip() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("nested").join("usage.log");
+
+        append_usage_log(
+            &path,
+            "rg",
+            Some("-i"),
+            Some(Path::new("/src/my repo")),
+            1704067200,
+        )?;
+        append_usage_log(&path, "bat", None, None, 1704067300)?;
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)?
+            .write_all(b"garbage\nnot-a-number\tfd\n1704067400\tfd\n")?;
+
+        let entries = parse_usage_log(&path)?;
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].command, "rg");
+        assert_eq!(entries[0].pattern.as_deref(), Some("-i"));
+        assert_eq!(entries[0].timestamp, 1704067200);
+        assert_eq!(entries[0].cwd, Some(PathBuf::from("/src/my repo")));
+        assert_eq!(entries[1].command, "bat");
+        assert_eq!(entries[1].pattern, None);
+        assert_eq!(entries[1].cwd, None);
+        assert_eq!(entries[2].command, "fd");
+        assert_eq!(entries[2].cwd, None);
+
+        Ok(())
+    }
+
     // ==================== Bash History Parsing Tests ====================
 
     #[test]
@@ -394,6 +1074,58 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_bash_history_timestamps() -> Result<()> {
+        let mut file = NamedTempFile::new()?;
+        writeln!(file, "#1704067200")?;
+        writeln!(file, "git status")?;
+        writeln!(file, "cargo build")?;
+        file.flush()?;
+
+        let entries = parse_bash_history(&file.path().to_path_buf())?;
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timestamp, Some(1704067200));
+        assert!(entries[1].timestamp.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_histories() {
+        let entry = |command: &str, timestamp: Option<i64>| HistoryEntry {
+            command: command.to_string(),
+            timestamp,
+        };
+        let histories = vec![
+            ShellHistory {
+                shell: "fish",
+                entries: vec![entry("git status", Some(100)), entry("rg x", Some(200))],
+            },
+            ShellHistory {
+                shell: "bash",
+                entries: vec![entry("git status", None), entry("fd y", None)],
+            },
+            ShellHistory {
+                shell: "atuin",
+                entries: vec![
+                    entry("git status", Some(100)),
+                    entry("rg x", Some(201)),
+                    entry("fd y", None),
+                ],
+            },
+        ];
+
+        let merged = merge_histories(histories);
+
+        // Only atuin's copy of `git status` at 100 is dropped
+        assert_eq!(merged.entries.len(), 6);
+        assert_eq!(
+            merged.per_shell,
+            vec![("fish", 2, 2), ("bash", 2, 2), ("atuin", 3, 2)]
+        );
+    }
+
     // ==================== Zsh History Parsing Tests ====================
 
     #[test]
@@ -21,3 +21,26 @@ pub static HTTP_AGENT: LazyLock<ureq::Agent> = LazyLock::new(|| {
 pub fn agent() -> &'static ureq::Agent {
     &HTTP_AGENT
 }
+
+/// HTTP agent for AI provider APIs
+///
+/// Model responses can take far longer than registry lookups, and error
+/// bodies carry the useful message, so non-2xx statuses are not turned
+/// into errors here; callers inspect the status themselves.
+pub static AI_AGENT: LazyLock<ureq::Agent> = LazyLock::new(|| {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(180)))
+        .http_status_as_error(false)
+        .build()
+        .new_agent()
+});
+
+/// HTTP agent for file downloads (release assets)
+///
+/// Archives can be tens of megabytes, so only connecting is time-limited.
+pub static DOWNLOAD_AGENT: LazyLock<ureq::Agent> = LazyLock::new(|| {
+    ureq::Agent::config_builder()
+        .timeout_connect(Some(Duration::from_secs(10)))
+        .build()
+        .new_agent()
+});
@@ -1,7 +1,50 @@
 //! Icon mappings for terminal display
+//!
+//! Icons default to emoji and nerd-font glyphs. In ASCII mode (accessibility
+//! setting or `--ascii` flag) every icon is replaced with a plain ASCII marker
+//! so output stays readable on terminals without those fonts.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Global ASCII mode flag (set once at startup)
+static ASCII_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable ASCII-only icons
+pub fn set_ascii_mode(enabled: bool) {
+    ASCII_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if ASCII-only icons are enabled
+pub fn ascii_mode() -> bool {
+    ASCII_MODE.load(Ordering::Relaxed)
+}
+
+/// Pick the fancy or ASCII variant depending on the current mode
+fn pick(fancy: &'static str, ascii: &'static str) -> &'static str {
+    if ascii_mode() { ascii } else { fancy }
+}
 
 /// Get icon for install source
 pub fn source_icon(source: &str) -> &'static str {
+    source_icon_with(source, ascii_mode())
+}
+
+fn source_icon_with(source: &str, ascii: bool) -> &'static str {
+    if ascii {
+        return match source.to_lowercase().as_str() {
+            "cargo" => "[rs]",
+            "pip" => "[py]",
+            "npm" => "[js]",
+            "apt" => "[ap]",
+            "brew" => "[br]",
+            "snap" => "[sn]",
+            "flatpak" => "[fp]",
+            "shell" => "[sh]",
+            "github" => "[gh]",
+            "manual" => "[mn]",
+            _ => "[??]",
+        };
+    }
     match source.to_lowercase().as_str() {
         "cargo" => "🦀",
         "pip" => "🐍",
@@ -10,6 +53,8 @@ pub fn source_icon(source: &str) -> &'static str {
         "brew" => "🍺",
         "snap" => "📸",
         "flatpak" => "📦",
+        "shell" => "🐚",
+        "github" => "\u{f09b}", //
         "manual" => "🔧",
         _ => "📥",
     }
@@ -17,12 +62,44 @@ pub fn source_icon(source: &str) -> &'static str {
 
 /// Get icon for tool status
 pub fn status_icon(installed: bool) -> &'static str {
-    if installed { "✓" } else { "✗" }
+    match (installed, ascii_mode()) {
+        (true, false) => "✓",
+        (false, false) => "✗",
+        (true, true) => "+",
+        (false, true) => "-",
+    }
 }
 
 /// Get icon for category
 pub fn category_icon(category: &str) -> &'static str {
-    match category.to_lowercase().as_str() {
+    category_icon_with(category, ascii_mode())
+}
+
+fn category_icon_with(category: &str, ascii: bool) -> &'static str {
+    let category = category.to_lowercase();
+    if ascii {
+        return match category.as_str() {
+            "cli" | "shell" => "[cl]",
+            "dev" | "development" => "[dv]",
+            "system" => "[sy]",
+            "network" | "net" => "[nt]",
+            "security" | "sec" => "[sc]",
+            "text" | "editor" => "[tx]",
+            "search" => "[sr]",
+            "file" | "files" => "[fs]",
+            "media" | "multimedia" => "[md]",
+            "database" | "db" => "[db]",
+            "container" | "docker" => "[ct]",
+            "cloud" => "[cd]",
+            "terminal" => "[tm]",
+            "git" | "vcs" => "[vc]",
+            "test" | "testing" => "[ts]",
+            "build" => "[bd]",
+            "monitor" | "monitoring" => "[mo]",
+            _ => "[--]",
+        };
+    }
+    match category.as_str() {
         "cli" | "shell" => "💻",
         "dev" | "development" => "🛠",
         "system" => "⚙",
@@ -46,30 +123,192 @@ pub fn category_icon(category: &str) -> &'static str {
 
 /// Get icon for config status
 pub fn config_status_icon(status: &str) -> &'static str {
+    if ascii_mode() {
+        return match status {
+            "linked" => "ok",
+            "missing" => "missing",
+            "conflict" => "conflict",
+            "unlinked" => "unlinked",
+            "stale" => "stale",
+            "copy" => "copy",
+            "invalid" => "invalid",
+            _ => "?",
+        };
+    }
     match status {
         "linked" => "🔗",
         "missing" => "❌",
         "conflict" => "⚠",
         "unlinked" => "◯",
+        "stale" => "↻",
+        "copy" => "≡",
+        "invalid" => "✗",
         _ => "?",
     }
 }
 
+/// Checkbox marker for multi-selection
+pub fn checkbox(checked: bool) -> &'static str {
+    checkbox_with(checked, ascii_mode())
+}
+
+fn checkbox_with(checked: bool, ascii: bool) -> &'static str {
+    match (checked, ascii) {
+        (true, false) => "☑",
+        (false, false) => "☐",
+        (true, true) => "[x]",
+        (false, true) => "[ ]",
+    }
+}
+
+/// Radio button marker for single-choice lists
+pub fn radio(selected: bool) -> &'static str {
+    match (selected, ascii_mode()) {
+        (true, false) => "●",
+        (false, false) => "○",
+        (true, true) => "(*)",
+        (false, true) => "( )",
+    }
+}
+
+/// Star marker (GitHub stars, favorites)
+pub fn star() -> &'static str {
+    pick("★", "*")
+}
+
+/// Marker for a pinned tool
+pub fn pin() -> &'static str {
+    pick("📌", "=")
+}
+
+/// Marker before a tool's health score
+pub fn health_score() -> &'static str {
+    pick("♥", "h")
+}
+
+/// Marker for a tool with a pending update
+pub fn update_icon() -> &'static str {
+    pick("↑", "^")
+}
+
+/// Marker for a passed check or a finished action
+pub fn check_icon() -> &'static str {
+    pick("✓", "+")
+}
+
+/// Marker for an installed tool
+pub fn installed_icon() -> &'static str {
+    pick("●", "+")
+}
+
+/// Marker for a tool that is not installed
+pub fn not_installed_icon() -> &'static str {
+    pick("○", ".")
+}
+
+/// AI provider indicator
+pub fn ai_icon() -> &'static str {
+    pick("🤖", "AI")
+}
+
+/// GitHub indicator
+pub fn github_icon() -> &'static str {
+    pick("\u{f09b}", "GH")
+}
+
+/// Last-sync indicator
+pub fn sync_icon() -> &'static str {
+    pick("⟳", "sync")
+}
+
+/// Usage recency of an installed tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageHealth {
+    /// Used within the last week
+    Active,
+    /// Used within the last month
+    Fading,
+    /// Not used for over a month
+    Stale,
+    /// Never used
+    Unused,
+}
+
+/// Usage health marker
+///
+/// In ASCII mode each state gets a distinct character so it does not rely on
+/// color alone.
+pub fn health_icon(health: UsageHealth) -> &'static str {
+    health_icon_with(health, ascii_mode())
+}
+
+fn health_icon_with(health: UsageHealth, ascii: bool) -> &'static str {
+    if !ascii {
+        return "●";
+    }
+    match health {
+        UsageHealth::Active => "+",
+        UsageHealth::Fading => "~",
+        UsageHealth::Stale => "-",
+        UsageHealth::Unused => "x",
+    }
+}
+
+/// Characters used to draw sparklines, from lowest to highest
+pub fn sparkline_blocks() -> [char; 8] {
+    sparkline_blocks_with(ascii_mode())
+}
+
+fn sparkline_blocks_with(ascii: bool) -> [char; 8] {
+    if ascii {
+        ['.', ',', ':', '-', '=', '+', '*', '#']
+    } else {
+        ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█']
+    }
+}
+
+/// Shades for heatmap cells, from empty to busiest
+pub fn heat_shades() -> [char; 5] {
+    if ascii_mode() {
+        [' ', '.', ':', '+', '#']
+    } else {
+        [' ', '░', '▒', '▓', '█']
+    }
+}
+
+/// Heatmap shade for a value relative to the busiest cell
+pub fn heat_shade(value: i64, max: i64) -> char {
+    let shades = heat_shades();
+    if value <= 0 || max <= 0 {
+        return shades[0];
+    }
+    // Any use gets at least the lightest shade
+    let idx = ((value as f64 / max as f64) * 4.0).ceil() as usize;
+    shades[idx.clamp(1, 4)]
+}
+
 /// Print the icon legend
 pub fn print_legend() {
     use colored::Colorize;
 
     println!();
     println!("{}", "Legend:".dimmed());
-    println!(
-        "  {} 🦀 cargo  🐍 pip  📦 npm  🐧 apt  🍺 brew  📸 snap  🔧 manual",
-        "Sources:".dimmed()
-    );
+    if ascii_mode() {
+        println!(
+            "  {} [rs] cargo  [py] pip  [js] npm  [ap] apt  [br] brew  [sn] snap  [mn] manual",
+            "Sources:".dimmed()
+        );
+    } else {
+        println!(
+            "  {} 🦀 cargo  🐍 pip  📦 npm  🐧 apt  🍺 brew  📸 snap  🔧 manual",
+            "Sources:".dimmed()
+        );
+    }
     println!(
         "  {} {} installed  {} missing",
         "Status:".dimmed(),
-        "✓".green(),
-        "✗".red()
+        status_icon(true).green(),
+        status_icon(false).red()
     );
 }
 
@@ -77,10 +316,67 @@ pub fn print_legend() {
 pub fn print_legend_compact() {
     use colored::Colorize;
 
+    let sources = if ascii_mode() {
+        "[rs]cargo [py]pip [js]npm [ap]apt [br]brew"
+    } else {
+        "🦀cargo 🐍pip 📦npm 🐧apt 🍺brew"
+    };
     println!(
-        "{} 🦀cargo 🐍pip 📦npm 🐧apt 🍺brew | {}installed {}missing",
+        "{} {} | {}installed {}missing",
         "".dimmed(),
-        "✓".green(),
-        "✗".red()
+        sources,
+        status_icon(true).green(),
+        status_icon(false).red()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_mode_icons() {
+        assert_eq!(source_icon_with("cargo", true), "[rs]");
+        assert_eq!(checkbox_with(true, true), "[x]");
+        assert_ne!(
+            health_icon_with(UsageHealth::Active, true),
+            health_icon_with(UsageHealth::Unused, true)
+        );
+        assert!(sparkline_blocks_with(true).iter().all(|c| c.is_ascii()));
+
+        assert_eq!(source_icon_with("cargo", false), "🦀");
+        assert_eq!(checkbox_with(true, false), "☑");
+    }
+
+    #[test]
+    fn test_ascii_category_icons() {
+        let categories = [
+            "cli",
+            "dev",
+            "system",
+            "network",
+            "security",
+            "text",
+            "search",
+            "files",
+            "media",
+            "database",
+            "container",
+            "cloud",
+            "terminal",
+            "git",
+            "testing",
+            "build",
+            "monitoring",
+        ];
+        let tags: std::collections::HashSet<_> = categories
+            .iter()
+            .map(|c| category_icon_with(c, true))
+            .collect();
+        assert_eq!(tags.len(), categories.len());
+        assert!(tags.iter().all(|tag| tag.is_ascii()));
+        assert_eq!(category_icon_with("Shell", true), "[cl]");
+        assert_eq!(category_icon_with("whatever", true), "[--]");
+        assert_eq!(category_icon_with("git", false), "🔀");
+    }
+}
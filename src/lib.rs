@@ -1,34 +1,60 @@
 pub mod ai;
+pub mod catalog;
 pub mod cli;
 pub mod commands;
 pub mod config;
 pub mod db;
-pub mod github;
+pub mod dependencies;
+pub mod dotfiles;
+pub mod exit_code;
+pub mod export;
+pub mod footprint;
+pub mod forge;
+pub mod formats;
+pub mod graph;
+pub mod health;
 pub mod history;
 pub mod http;
 pub mod icons;
+pub mod logging;
 pub mod models;
+pub mod process;
+pub mod project;
+pub mod report;
+pub mod runtimes;
 pub mod scanner;
+pub mod secrets;
+pub mod server;
+pub mod services;
 pub mod sources;
+pub mod templates;
 pub mod tui;
 pub mod updates;
 
 pub use cli::{
-    AiCommands, AiConfigCommands, BundleCommands, Cli, Commands, CompletionsCommands,
-    ConfigCommands, DiscoverCommands, GhCommands, InsightsCommands, UsageCommands,
+    AiCacheCommands, AiCommands, AiConfigCommands, AiPromptsCommands, AliasCommands,
+    BundleCommands, BundleTemplateCommands, Cli, Commands, CompletionsCommands, ConfigCommands,
+    ConfigRepoCommands, DiscoverCommands, GhCommands, InsightsCommands, UsageCommands,
+    WatchCommands,
 };
 
 // Core commands
-pub use commands::{cmd_add, cmd_list, cmd_remove, cmd_search, cmd_show};
+pub use commands::{cmd_add, cmd_list, cmd_open, cmd_remove, cmd_search, cmd_show, cmd_which};
 
 // Sync commands
 pub use commands::{cmd_fetch_descriptions, cmd_scan, cmd_sync_status};
 
 // Discover commands
-pub use commands::{cmd_similar, cmd_suggest, cmd_trending};
+pub use commands::{
+    cmd_discover_project, cmd_discover_topic, cmd_similar, cmd_suggest, cmd_trending,
+};
 
 // Insights commands
-pub use commands::{cmd_categories, cmd_info, cmd_overview, cmd_stats};
+pub use commands::{
+    cmd_adoption, cmd_categories, cmd_cleanup_plan, cmd_graph, cmd_health_scores, cmd_info,
+    cmd_licenses, cmd_overlap, cmd_overview, cmd_report, cmd_rhythms, cmd_shadowed, cmd_size,
+    cmd_stale, cmd_stats, cmd_timeline,
+};
 
 // Workflow commands
 pub use commands::{cmd_cleanup, cmd_init, cmd_maintain};
@@ -42,61 +68,107 @@ pub use updates::{
     check_pip_updates,
 };
 
+// Pick command
+pub use commands::cmd_pick;
+
+// Run command
+pub use commands::cmd_run;
+
+// Batch commands
+pub use commands::{cmd_add_batch, cmd_install_batch};
+
+// Grep command
+pub use commands::cmd_grep;
+
 // Install commands
 pub use commands::{
-    SafeCommand, cmd_install, cmd_uninstall, cmd_upgrade, get_install_command,
+    SafeCommand, cmd_install, cmd_pin, cmd_uninstall, cmd_unpin, cmd_upgrade, get_install_command,
     get_safe_install_command, get_safe_uninstall_command, validate_package_name,
 };
 
 // AI commands
 pub use commands::{
-    cmd_ai_analyze, cmd_ai_bundle_cheatsheet, cmd_ai_categorize, cmd_ai_cheatsheet,
-    cmd_ai_describe, cmd_ai_discover, cmd_ai_extract, cmd_ai_migrate, cmd_ai_set, cmd_ai_show,
-    cmd_ai_suggest_bundle, cmd_ai_test,
+    cmd_ai_analyze, cmd_ai_bundle_cheatsheet, cmd_ai_cache_clear, cmd_ai_cache_list,
+    cmd_ai_categorize, cmd_ai_cheatsheet, cmd_ai_cheatsheet_export, cmd_ai_dedupe, cmd_ai_describe,
+    cmd_ai_discover, cmd_ai_do, cmd_ai_extract, cmd_ai_migrate, cmd_ai_prompts_init,
+    cmd_ai_prompts_list, cmd_ai_prompts_reset, cmd_ai_prompts_show, cmd_ai_set, cmd_ai_show,
+    cmd_ai_suggest_bundle, cmd_ai_test, cmd_ai_updates_summary, cmd_ai_usage,
 };
 
+// Alias commands
+pub use commands::{cmd_alias_add, cmd_alias_list, cmd_alias_remove, cmd_alias_snippet};
+
 // Bundle commands
 pub use commands::{
-    cmd_bundle_add, cmd_bundle_create, cmd_bundle_delete, cmd_bundle_install, cmd_bundle_list,
-    cmd_bundle_remove, cmd_bundle_show, cmd_bundle_update,
+    cmd_bundle_add, cmd_bundle_create, cmd_bundle_delete, cmd_bundle_from_project,
+    cmd_bundle_include, cmd_bundle_install, cmd_bundle_install_from_url, cmd_bundle_list,
+    cmd_bundle_lock, cmd_bundle_publish, cmd_bundle_remove, cmd_bundle_show, cmd_bundle_sync,
+    cmd_bundle_template_apply, cmd_bundle_template_list, cmd_bundle_update,
+    create_bundle_from_template,
 };
 
 // GitHub commands
 pub use commands::{
-    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_info, cmd_gh_rate_limit, cmd_gh_search, cmd_gh_sync,
+    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_import_stars, cmd_gh_info, cmd_gh_install, cmd_gh_queue,
+    cmd_gh_rate_limit, cmd_gh_readme, cmd_gh_search, cmd_gh_sync,
 };
 
 // Usage commands
 pub use commands::{
-    cmd_labels, cmd_recommend, cmd_unused, cmd_usage_config, cmd_usage_init, cmd_usage_log,
-    cmd_usage_reset, cmd_usage_scan, cmd_usage_show, cmd_usage_tool, ensure_usage_configured,
+    cmd_labels, cmd_recommend, cmd_unused, cmd_usage_config, cmd_usage_export, cmd_usage_flush,
+    cmd_usage_init, cmd_usage_log, cmd_usage_log_fast, cmd_usage_project, cmd_usage_reset,
+    cmd_usage_scan, cmd_usage_show, cmd_usage_tool, ensure_usage_configured, flush_usage_log,
+};
+
+// Watch commands
+pub use commands::{
+    cmd_watch_add, cmd_watch_check, cmd_watch_list, cmd_watch_read, cmd_watch_remove,
 };
 
 // Misc commands
-pub use commands::{cmd_doctor, cmd_edit, cmd_export, cmd_import};
+pub use commands::{
+    cmd_compare, cmd_doctor, cmd_edit, cmd_export, cmd_export_schema, cmd_import, cmd_import_db,
+    cmd_note,
+};
+
+// Serve command
+pub use commands::cmd_serve;
 
 // Config commands
 pub use commands::{
-    cmd_config_edit, cmd_config_link, cmd_config_list, cmd_config_show, cmd_config_status,
-    cmd_config_sync, cmd_config_unlink,
+    LinkOptions, cmd_config_diff, cmd_config_edit, cmd_config_import, cmd_config_link,
+    cmd_config_list, cmd_config_repo_clone, cmd_config_repo_init, cmd_config_repo_pull,
+    cmd_config_repo_push, cmd_config_restore, cmd_config_show, cmd_config_status, cmd_config_sync,
+    cmd_config_unlink, cmd_config_watch,
 };
 
 // Completions commands
-pub use commands::{cmd_completions_install, cmd_completions_status, cmd_completions_uninstall};
+pub use commands::{
+    cmd_completions_install, cmd_completions_man, cmd_completions_status, cmd_completions_uninstall,
+};
 
 // Config types
 pub use config::{AiProvider, HoardConfig};
 
 // Database
-pub use db::{CachedExtraction, Database, GitHubInfo, GitHubInfoInput, ToolUsage};
+pub use db::{
+    CachedExtraction, Database, GitHubInfo, GitHubInfoInput, MergeReport, MergeStrategy,
+    ProjectUsage, ToolUsage, UsageRhythm,
+};
 
 // Models
-pub use models::{Bundle, Config, InstallSource, Interest, Tool};
+pub use models::{
+    Bundle, BundleTree, Config, ConfigMode, InstallSource, Interest, LicenseKind, Machine,
+    MemberCondition, SharedBundle, SharedTool, Tool, ToolKind,
+};
 
 // Scanner
 pub use scanner::{
-    KNOWN_TOOLS, is_installed, scan_known_tools, scan_missing_tools, scan_path_tools,
+    KNOWN_TOOLS, is_installed, known_tools, scan_known_tools, scan_missing_tools, scan_path_tools,
 };
 
+// Templates
+pub use templates::{BUNDLE_TEMPLATES, BundleTemplate};
+
 // Sources
 pub use sources::{PackageSource, all_sources, get_source, source_for};
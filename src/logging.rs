@@ -0,0 +1,103 @@
+//! Diagnostic logging
+//!
+//! Warnings and debug output go through `tracing`. The console shows
+//! warnings and errors on stderr by default; `-v`/`-vv` add info and debug
+//! messages and `-q` keeps only errors. Every run also appends debug-level
+//! records to `hoards.log` in the data directory, which is what to attach
+//! when a sync or install goes wrong. `HOARDS_LOG` overrides both filters
+//! with an `EnvFilter` directive such as `hoards=trace`.
+
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use std::fs::{self, File, OpenOptions};
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::prelude::*;
+
+/// Environment variable holding a filter directive
+pub const LOG_ENV: &str = "HOARDS_LOG";
+
+/// Log files larger than this are moved to `hoards.log.1` on start
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+/// Path of the log file
+pub fn log_path() -> Result<PathBuf> {
+    let proj_dirs = ProjectDirs::from("dev", "hoards", "hoards")
+        .context("Failed to determine project directories")?;
+    Ok(proj_dirs.data_dir().join("hoards.log"))
+}
+
+/// Console filter for the `-q`/`-v` flags
+fn console_directive(verbosity: u8, quiet: bool) -> &'static str {
+    match (quiet, verbosity) {
+        (true, _) => "error",
+        (false, 0) => "warn",
+        (false, 1) => "hoards=info,warn",
+        (false, 2) => "hoards=debug,warn",
+        (false, _) => "trace",
+    }
+}
+
+/// Open the log file for appending, rotating it once it gets large
+fn open_log() -> Result<File> {
+    let path = log_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_LOG_BYTES) {
+        fs::rename(&path, path.with_extension("log.1"))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))
+}
+
+/// Install the global subscriber
+///
+/// `console` is false for the TUI, which owns the terminal; it still logs
+/// to the file. A log file that can't be opened is skipped, not an error.
+pub fn init(verbosity: u8, quiet: bool, console: bool) {
+    let env = std::env::var(LOG_ENV).ok().filter(|v| !v.is_empty());
+    let filter =
+        |default: &str| EnvFilter::try_new(env.as_deref().unwrap_or(default)).unwrap_or_default();
+
+    let console = console.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_ansi(std::io::stderr().is_terminal())
+            .without_time()
+            .with_target(verbosity >= 2)
+            .with_filter(filter(console_directive(verbosity, quiet)))
+    });
+    let file = open_log().ok().map(|log| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(Mutex::new(log))
+            .with_ansi(false)
+            .with_filter(filter("hoards=debug"))
+    });
+
+    // Only fails if a subscriber is already set
+    let _ = tracing_subscriber::registry()
+        .with(console)
+        .with(file)
+        .try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_console_directive() {
+        assert_eq!(console_directive(0, false), "warn");
+        assert_eq!(console_directive(2, false), "hoards=debug,warn");
+        assert_eq!(console_directive(5, false), "trace");
+        // -q wins over -v
+        assert_eq!(console_directive(2, true), "error");
+        assert!(EnvFilter::try_new(console_directive(1, false)).is_ok());
+    }
+}
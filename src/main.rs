@@ -8,138 +8,305 @@ use clap::{CommandFactory, Parser};
 use clap_complete::generate;
 
 use hoards::{
+    AiCacheCommands,
     AiCommands,
     AiConfigCommands,
+    AiPromptsCommands,
+    AliasCommands,
     BundleCommands,
+    BundleTemplateCommands,
     Cli,
     Commands,
     CompletionsCommands,
     ConfigCommands,
+    ConfigMode,
+    ConfigRepoCommands,
     Database,
     DiscoverCommands,
     GhCommands,
     HoardConfig,
     InsightsCommands,
+    MergeStrategy,
     UsageCommands,
+    WatchCommands,
     // Core commands
     cmd_add,
+    cmd_add_batch,
+    cmd_adoption,
     // AI commands
     cmd_ai_analyze,
     cmd_ai_bundle_cheatsheet,
+    cmd_ai_cache_clear,
+    cmd_ai_cache_list,
     cmd_ai_categorize,
     cmd_ai_cheatsheet,
+    cmd_ai_cheatsheet_export,
+    cmd_ai_dedupe,
     cmd_ai_describe,
     cmd_ai_discover,
+    cmd_ai_do,
     cmd_ai_extract,
     cmd_ai_migrate,
+    cmd_ai_prompts_init,
+    cmd_ai_prompts_list,
+    cmd_ai_prompts_reset,
+    cmd_ai_prompts_show,
     cmd_ai_set,
     cmd_ai_show,
     cmd_ai_suggest_bundle,
     cmd_ai_test,
+    cmd_ai_updates_summary,
+    cmd_ai_usage,
+    // Alias commands
+    cmd_alias_add,
+    cmd_alias_list,
+    cmd_alias_remove,
+    cmd_alias_snippet,
     // Bundle commands
     cmd_bundle_add,
     cmd_bundle_create,
     cmd_bundle_delete,
+    cmd_bundle_from_project,
+    cmd_bundle_include,
     cmd_bundle_install,
+    cmd_bundle_install_from_url,
     cmd_bundle_list,
+    cmd_bundle_lock,
+    cmd_bundle_publish,
     cmd_bundle_remove,
     cmd_bundle_show,
+    cmd_bundle_sync,
+    cmd_bundle_template_apply,
+    cmd_bundle_template_list,
     cmd_bundle_update,
     // Discover commands
     cmd_categories,
     // Workflow commands
     cmd_cleanup,
+    cmd_cleanup_plan,
+    cmd_compare,
     // Completions commands
     cmd_completions_install,
+    cmd_completions_man,
     cmd_completions_status,
     cmd_completions_uninstall,
     // Config commands
+    cmd_config_diff,
     cmd_config_edit,
+    cmd_config_import,
     cmd_config_link,
     cmd_config_list,
+    cmd_config_repo_clone,
+    cmd_config_repo_init,
+    cmd_config_repo_pull,
+    cmd_config_repo_push,
+    cmd_config_restore,
     cmd_config_show,
     cmd_config_status,
     cmd_config_sync,
     cmd_config_unlink,
+    cmd_config_watch,
     // Misc commands
+    cmd_discover_project,
+    cmd_discover_topic,
     cmd_doctor,
     cmd_edit,
     cmd_export,
+    cmd_export_schema,
     // Sync commands
     cmd_fetch_descriptions,
     // GitHub commands
     cmd_gh_backfill,
     cmd_gh_fetch,
+    cmd_gh_import_stars,
     cmd_gh_info,
+    cmd_gh_install,
+    cmd_gh_queue,
     cmd_gh_rate_limit,
+    cmd_gh_readme,
     cmd_gh_search,
     cmd_gh_sync,
+    cmd_graph,
+    cmd_grep,
+    cmd_health_scores,
     cmd_import,
+    cmd_import_db,
     // Insights commands
     cmd_info,
     cmd_init,
     // Install commands
     cmd_install,
+    cmd_install_batch,
     // Usage commands
     cmd_labels,
+    cmd_licenses,
     cmd_list,
     cmd_maintain,
+    cmd_note,
+    cmd_open,
+    cmd_overlap,
     cmd_overview,
+    cmd_pick,
+    cmd_pin,
     cmd_recommend,
     cmd_remove,
+    cmd_report,
+    cmd_rhythms,
+    cmd_run,
     cmd_scan,
     cmd_search,
+    cmd_serve,
+    cmd_shadowed,
     cmd_show,
     cmd_similar,
+    cmd_size,
+    cmd_stale,
     cmd_stats,
     cmd_suggest,
     cmd_sync_status,
+    cmd_timeline,
     cmd_trending,
     cmd_uninstall,
+    cmd_unpin,
     cmd_unused,
     // Updates commands
     cmd_updates,
     cmd_upgrade,
     cmd_usage_config,
+    cmd_usage_export,
+    cmd_usage_flush,
     cmd_usage_init,
     cmd_usage_log,
+    cmd_usage_log_fast,
+    cmd_usage_project,
     cmd_usage_reset,
     cmd_usage_scan,
     cmd_usage_show,
     cmd_usage_tool,
+    // Watch commands
+    cmd_watch_add,
+    cmd_watch_check,
+    cmd_watch_list,
+    cmd_watch_read,
+    cmd_watch_remove,
+    cmd_which,
     ensure_usage_configured,
+    exit_code::{ExitCode, ExitCodeTable},
 };
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn main() -> std::process::ExitCode {
+    // A help topic rather than a command, so clap can't route it
+    if std::env::args().skip(1).eq(["help", "exit-codes"]) {
+        println!("{}", ExitCodeTable);
+        return ExitCode::Success.into();
+    }
+
+    let args = Cli::expand_alias(std::env::args().collect(), || {
+        HoardConfig::load()
+            .map(|config| config.aliases)
+            .unwrap_or_default()
+    });
+    let cli = match Cli::try_parse_from(args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            // --help and --version end up here too
+            return if e.use_stderr() {
+                ExitCode::Invalid
+            } else {
+                ExitCode::Success
+            }
+            .into();
+        }
+    };
+
+    match run(cli) {
+        Ok(()) => ExitCode::Success.into(),
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::of(&e).into()
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    // Shell hooks run this on every command: never touch SQLite
+    if let Commands::Usage(UsageCommands::Log {
+        command,
+        fast: true,
+    }) = &cli.command
+    {
+        return cmd_usage_log_fast(command);
+    }
+
+    let console = !matches!(cli.command, Commands::Tui { .. });
+    hoards::logging::init(cli.verbose, cli.quiet, console);
+    tracing::info!(
+        "hoards {}",
+        std::env::args().skip(1).collect::<Vec<_>>().join(" ")
+    );
+
     let db = Database::open()?;
+    hoards::forge::scheduler::share_between_processes();
 
-    match cli.command {
+    // Accessibility mode: --ascii flag or `tui.accessible` config setting
+    let accessible = cli.ascii
+        || HoardConfig::load()
+            .map(|config| config.tui.accessible)
+            .unwrap_or(false);
+    hoards::icons::set_ascii_mode(accessible);
+
+    let json = cli.json;
+    let result = match cli.command {
         // ============================================
         // CORE COMMANDS
         // ============================================
         Commands::Add {
             name,
+            from_file,
             description,
             category,
             source,
             install_cmd,
             binary,
             installed,
-        } => cmd_add(
-            &db,
-            name,
-            description,
-            category,
-            source,
-            install_cmd,
-            binary,
-            installed,
-        ),
+        } => match (from_file, name) {
+            (Some(path), _) => cmd_add_batch(&db, &path, source, category),
+            (None, Some(name)) => cmd_add(
+                &db,
+                name,
+                description,
+                category,
+                source,
+                install_cmd,
+                binary,
+                installed,
+            ),
+            (None, None) => unreachable!("clap requires a name or --from-file"),
+        },
 
-        Commands::Show { name } => cmd_show(&db, &name),
+        Commands::Show { name } => cmd_show(&db, &name, json),
+        Commands::Which { name } => cmd_which(&db, &name),
+        Commands::Pick {
+            installed,
+            category,
+        } => cmd_pick(&db, installed, category.as_deref()),
+        Commands::Grep { pattern, field } => cmd_grep(&db, &pattern, &field, json),
+        Commands::Open {
+            name,
+            repo,
+            docs,
+            registry,
+            print,
+        } => {
+            let page = [(repo, "repo"), (docs, "docs"), (registry, "registry")]
+                .into_iter()
+                .find_map(|(set, page)| set.then_some(page));
+            cmd_open(&db, &name, page, print)
+        }
         Commands::Remove { name, force } => cmd_remove(&db, &name, force),
         Commands::Edit { name } => cmd_edit(&db, &name),
+        Commands::Note { name, text, edit } => cmd_note(&db, &name, text, edit),
 
         // ============================================
         // SYNC - Unified sync command
@@ -147,6 +314,7 @@ fn main() -> Result<()> {
         Commands::Sync {
             dry_run,
             scan,
+            full,
             github,
             usage,
             descriptions,
@@ -154,7 +322,7 @@ fn main() -> Result<()> {
             limit,
             delay,
         } => {
-            let do_scan = scan || all;
+            let do_scan = scan || full || all;
             let do_github = github || all;
             let do_usage = usage || all;
             let do_descriptions = descriptions || all;
@@ -162,9 +330,13 @@ fn main() -> Result<()> {
             // Always sync installation status
             cmd_sync_status(&db, dry_run)?;
 
+            if !dry_run {
+                cmd_usage_flush(&db)?;
+            }
+
             if do_scan {
                 println!();
-                cmd_scan(&db, dry_run)?;
+                cmd_scan(&db, dry_run, full)?;
             }
 
             if do_descriptions {
@@ -195,25 +367,41 @@ fn main() -> Result<()> {
                 installed,
                 category,
                 label,
+                libraries,
                 format,
-            } => cmd_list(&db, installed, category, label, &format),
+            } => cmd_list(
+                &db,
+                installed,
+                category,
+                label,
+                libraries,
+                if json { "json" } else { &format },
+            ),
             DiscoverCommands::Search {
                 query,
                 github,
                 limit,
             } => {
-                cmd_search(&db, &query)?;
-                if github {
+                cmd_search(&db, &query, json)?;
+                if github && !json {
                     println!();
                     cmd_gh_search(&query, limit)?;
                 }
                 Ok(())
             }
-            DiscoverCommands::Categories => cmd_categories(&db),
+            DiscoverCommands::Categories => cmd_categories(&db, json),
             DiscoverCommands::Labels => cmd_labels(&db),
             DiscoverCommands::Missing { category } => cmd_suggest(category),
             DiscoverCommands::Recommended { count } => cmd_recommend(&db, count),
-            DiscoverCommands::Similar { tool } => cmd_similar(&db, &tool),
+            DiscoverCommands::Similar {
+                tool,
+                limit,
+                reindex,
+            } => cmd_similar(&db, &tool, limit, reindex),
+            DiscoverCommands::Project { path, force } => {
+                cmd_discover_project(&db, path.as_deref(), force)
+            }
+            DiscoverCommands::Topics { topic, limit } => cmd_discover_topic(&db, &topic, limit),
             DiscoverCommands::Trending { category, limit } => cmd_trending(&db, category, limit),
             _ => unreachable!("all DiscoverCommands variants covered"),
         },
@@ -222,17 +410,43 @@ fn main() -> Result<()> {
         // INSIGHTS COMMANDS
         // ============================================
         Commands::Insights(command) => match command {
-            InsightsCommands::Usage { tool, limit } => {
+            InsightsCommands::Usage {
+                tool,
+                limit,
+                project,
+            } => {
                 if let Some(name) = tool {
-                    cmd_usage_tool(&db, &name)
+                    cmd_usage_tool(&db, &name, json)
+                } else if let Some(path) = project {
+                    cmd_usage_project(&db, &path, limit, json)
                 } else {
-                    cmd_usage_show(&db, limit)
+                    cmd_usage_show(&db, limit, json)
                 }
             }
             InsightsCommands::Unused => cmd_unused(&db),
-            InsightsCommands::Health { fix } => cmd_doctor(&db, fix),
-            InsightsCommands::Stats => cmd_stats(&db),
+            InsightsCommands::Health { fix, worst } => match worst {
+                Some(limit) => cmd_health_scores(&db, limit, json),
+                None => cmd_doctor(&db, fix, json),
+            },
+            InsightsCommands::Stats => cmd_stats(&db, json),
             InsightsCommands::Overview => cmd_overview(&db),
+            InsightsCommands::Licenses { fetch } => cmd_licenses(&db, fetch),
+            InsightsCommands::Rhythms { tool, limit } => cmd_rhythms(&db, tool.as_deref(), limit),
+            InsightsCommands::Adoption { days, keep } => cmd_adoption(&db, days, keep.as_deref()),
+            InsightsCommands::CleanupPlan { days, apply, tools } => {
+                cmd_cleanup_plan(&db, days, apply, &tools)
+            }
+            InsightsCommands::Shadowed { all, resolve } => cmd_shadowed(&db, all, resolve, json),
+            InsightsCommands::Size { limit } => cmd_size(&db, limit, json),
+            InsightsCommands::Overlap { min } => cmd_overlap(&db, min, json),
+            InsightsCommands::Stale { days } => cmd_stale(&db, days, json),
+            InsightsCommands::Report { html, no_updates } => {
+                cmd_report(&db, std::path::Path::new(&html), !no_updates)
+            }
+            InsightsCommands::Timeline { months, month } => {
+                cmd_timeline(&db, months, month.as_deref(), json)
+            }
+            InsightsCommands::Graph { tool, format } => cmd_graph(&db, &format, tool.as_deref()),
             _ => unreachable!("all InsightsCommands variants covered"),
         },
 
@@ -244,7 +458,19 @@ fn main() -> Result<()> {
             cross,
             tracked,
             all_versions,
-        } => cmd_updates(&db, source, cross, tracked, all_versions),
+        } => cmd_updates(&db, source, cross, tracked, all_versions, json),
+        Commands::Watch {
+            tool,
+            repo,
+            command,
+        } => match (command, tool) {
+            (Some(WatchCommands::List), _) | (None, None) => cmd_watch_list(&db),
+            (Some(WatchCommands::Check), _) => cmd_watch_check(&db),
+            (Some(WatchCommands::Read { tool }), _) => cmd_watch_read(&db, tool.as_deref()),
+            (Some(WatchCommands::Remove { tool }), _) => cmd_watch_remove(&db, &tool),
+            (None, Some(tool)) => cmd_watch_add(&db, &tool, repo.as_deref()),
+            _ => unreachable!("all WatchCommands variants covered"),
+        },
 
         // ============================================
         // WORKFLOW COMMANDS
@@ -256,17 +482,44 @@ fn main() -> Result<()> {
         // ============================================
         // TUI
         // ============================================
-        Commands::Tui => hoards::tui::run(&db),
+        Commands::Tui {
+            tab,
+            query,
+            filter,
+            bundle,
+        } => {
+            let options = hoards::tui::LaunchOptions {
+                tab: tab.as_deref().and_then(hoards::tui::Tab::from_name),
+                query,
+                filter,
+                bundle,
+            };
+            hoards::tui::run(&db, &options)
+        }
 
         // ============================================
         // INSTALL/UNINSTALL/UPGRADE
         // ============================================
         Commands::Install {
             name,
+            from_file,
             source,
             version,
             force,
-        } => cmd_install(&db, &name, source, version, force),
+            override_pin,
+        } => match (from_file, name) {
+            (Some(path), _) => cmd_install_batch(&db, &path, source, force),
+            (None, Some(name)) if name == "-" => cmd_install_batch(&db, "-", source, force),
+            (None, Some(name)) => cmd_install(&db, &name, source, version, force, override_pin),
+            (None, None) => unreachable!("clap requires a name or --from-file"),
+        },
+
+        Commands::Run {
+            source,
+            yes,
+            name,
+            args,
+        } => cmd_run(&db, &name, &args, source, yes),
 
         Commands::Uninstall {
             name,
@@ -281,6 +534,10 @@ fn main() -> Result<()> {
             force,
         } => cmd_upgrade(&db, &name, to, version, force),
 
+        Commands::Pin { name, version } => cmd_pin(&db, name.as_deref(), version.as_deref(), json),
+
+        Commands::Unpin { name } => cmd_unpin(&db, &name),
+
         // ============================================
         // GITHUB (advanced)
         // ============================================
@@ -291,10 +548,40 @@ fn main() -> Result<()> {
                 delay,
             } => cmd_gh_sync(&db, dry_run, limit, delay),
             GhCommands::RateLimit => cmd_gh_rate_limit(),
+            GhCommands::Queue { clear } => cmd_gh_queue(&db, clear),
             GhCommands::Backfill { dry_run } => cmd_gh_backfill(&db, dry_run),
-            GhCommands::Fetch { name } => cmd_gh_fetch(&db, &name),
+            GhCommands::Fetch { name, repo } => cmd_gh_fetch(&db, &name, repo.as_deref()),
             GhCommands::Search { query, limit } => cmd_gh_search(&query, limit),
-            GhCommands::Info { name } => cmd_gh_info(&db, &name),
+            GhCommands::Info { name } => cmd_gh_info(&db, &name, json),
+            GhCommands::Readme { name, refresh } => cmd_gh_readme(&db, &name, refresh),
+            GhCommands::Install {
+                repo,
+                tag,
+                name,
+                bin,
+                force,
+            } => cmd_gh_install(
+                &db,
+                &repo,
+                tag.as_deref(),
+                name.as_deref(),
+                bin.as_deref(),
+                force,
+            ),
+            GhCommands::ImportStars {
+                language,
+                topic,
+                all,
+                interests,
+                dry_run,
+            } => cmd_gh_import_stars(
+                &db,
+                language.as_deref(),
+                topic.as_deref(),
+                all,
+                interests,
+                dry_run,
+            ),
             _ => unreachable!("all GhCommands variants covered"),
         },
 
@@ -303,26 +590,43 @@ fn main() -> Result<()> {
         // ============================================
         Commands::Ai(command) => match command {
             AiCommands::Config(config_cmd) => match config_cmd {
-                AiConfigCommands::Set { provider } => cmd_ai_set(&provider),
+                AiConfigCommands::Set {
+                    provider,
+                    base_url,
+                    model,
+                } => cmd_ai_set(&provider, base_url.as_deref(), model.as_deref()),
                 AiConfigCommands::Show => cmd_ai_show(),
                 AiConfigCommands::Test => cmd_ai_test(),
                 _ => unreachable!("all AiConfigCommands variants covered"),
             },
+            AiCommands::Prompts(prompts_cmd) => match prompts_cmd {
+                AiPromptsCommands::List => cmd_ai_prompts_list(),
+                AiPromptsCommands::Show { name, default } => cmd_ai_prompts_show(&name, default),
+                AiPromptsCommands::Init { force } => cmd_ai_prompts_init(force),
+                AiPromptsCommands::Reset { name } => cmd_ai_prompts_reset(&name),
+                _ => unreachable!("all AiPromptsCommands variants covered"),
+            },
+            AiCommands::Cache(cache_cmd) => match cache_cmd {
+                AiCacheCommands::List => cmd_ai_cache_list(&db),
+                AiCacheCommands::Clear { feature } => cmd_ai_cache_clear(&db, feature.as_deref()),
+                _ => unreachable!("all AiCacheCommands variants covered"),
+            },
             AiCommands::Enrich {
                 categorize,
                 describe,
                 all,
                 dry_run,
                 limit,
+                resume,
             } => {
                 let do_categorize = categorize || all;
                 let do_describe = describe || all;
                 if do_categorize {
-                    cmd_ai_categorize(dry_run)?;
+                    cmd_ai_categorize(dry_run, resume)?;
                 }
                 if do_describe {
                     println!();
-                    cmd_ai_describe(dry_run, limit)?;
+                    cmd_ai_describe(dry_run, limit, resume)?;
                 }
                 Ok(())
             }
@@ -337,8 +641,11 @@ fn main() -> Result<()> {
                 tool,
                 bundle,
                 refresh,
+                export,
             } => {
-                if let Some(bundle_name) = bundle {
+                if let Some(dir) = export {
+                    cmd_ai_cheatsheet_export(&dir)
+                } else if let Some(bundle_name) = bundle {
                     cmd_ai_bundle_cheatsheet(&bundle_name, refresh)
                 } else if let Some(tool_name) = tool {
                     cmd_ai_cheatsheet(&tool_name, refresh)
@@ -352,24 +659,33 @@ fn main() -> Result<()> {
                 no_stars,
                 dry_run,
             } => cmd_ai_discover(&db, &query, limit, no_stars, dry_run),
+            AiCommands::UpdatesSummary { source, refresh } => {
+                cmd_ai_updates_summary(&db, source, refresh)
+            }
+            AiCommands::Do {
+                request,
+                yes,
+                dry_run,
+            } => cmd_ai_do(&db, &request, yes, dry_run),
+            AiCommands::Dedupe { dry_run } => cmd_ai_dedupe(&db, dry_run, json),
+            AiCommands::Usage { days, reset } => cmd_ai_usage(&db, days, json, reset),
             AiCommands::Analyze {
-                json,
                 no_ai,
                 min_uses,
-            } => cmd_ai_analyze(&db, json, no_ai, min_uses),
+                apply,
+            } => cmd_ai_analyze(&db, json, no_ai, min_uses, apply),
             AiCommands::Migrate {
                 from,
                 to,
                 dry_run,
-                json,
                 no_ai,
             } => cmd_ai_migrate(&db, from, to, dry_run, json, no_ai),
             // Hidden backward compatibility aliases
-            AiCommands::Set { provider } => cmd_ai_set(&provider),
+            AiCommands::Set { provider } => cmd_ai_set(&provider, None, None),
             AiCommands::ShowConfig => cmd_ai_show(),
             AiCommands::Test => cmd_ai_test(),
-            AiCommands::Categorize { dry_run } => cmd_ai_categorize(dry_run),
-            AiCommands::Describe { dry_run, limit } => cmd_ai_describe(dry_run, limit),
+            AiCommands::Categorize { dry_run } => cmd_ai_categorize(dry_run, false),
+            AiCommands::Describe { dry_run, limit } => cmd_ai_describe(dry_run, limit, false),
             _ => unreachable!("all AiCommands variants covered"),
         },
 
@@ -381,14 +697,56 @@ fn main() -> Result<()> {
                 name,
                 tools,
                 description,
-            } => cmd_bundle_create(&db, &name, tools, description),
+                when,
+            } => cmd_bundle_create(&db, &name, tools, description, when.as_deref()),
             BundleCommands::List => cmd_bundle_list(&db),
-            BundleCommands::Show { name } => cmd_bundle_show(&db, &name),
-            BundleCommands::Install { name, force } => cmd_bundle_install(&db, &name, force),
-            BundleCommands::Add { name, tools } => cmd_bundle_add(&db, &name, tools),
+            BundleCommands::Show { name } => cmd_bundle_show(&db, &name, json),
+            BundleCommands::Install {
+                name,
+                from_url,
+                force,
+                locked,
+            } => match (from_url, name) {
+                (Some(url), _) => cmd_bundle_install_from_url(&db, &url, force, locked),
+                (None, Some(name)) => cmd_bundle_install(&db, &name, force, locked),
+                (None, None) => unreachable!("clap requires a name or --from-url"),
+            },
+            BundleCommands::Add { name, tools, when } => {
+                cmd_bundle_add(&db, &name, tools, when.as_deref())
+            }
+            BundleCommands::Include {
+                name,
+                bundles,
+                remove,
+            } => cmd_bundle_include(&db, &name, bundles, remove),
             BundleCommands::Remove { name, tools } => cmd_bundle_remove(&db, &name, tools),
             BundleCommands::Delete { name, force } => cmd_bundle_delete(&db, &name, force),
             BundleCommands::Update { name, yes } => cmd_bundle_update(&db, &name, yes),
+            BundleCommands::Sync { name, prune, force } => {
+                cmd_bundle_sync(&db, &name, prune, force)
+            }
+            BundleCommands::Publish {
+                name,
+                public,
+                output,
+            } => cmd_bundle_publish(&db, &name, public, output.as_deref()),
+            BundleCommands::Template(command) => match command {
+                BundleTemplateCommands::List => cmd_bundle_template_list(&db),
+                BundleTemplateCommands::Apply {
+                    template,
+                    name,
+                    install,
+                    force,
+                } => cmd_bundle_template_apply(&db, &template, name.as_deref(), install, force),
+                _ => unreachable!("all BundleTemplateCommands variants covered"),
+            },
+            BundleCommands::Lock { name, update } => cmd_bundle_lock(&db, &name, update),
+            BundleCommands::FromProject {
+                path,
+                name,
+                dry_run,
+                force,
+            } => cmd_bundle_from_project(&db, path.as_deref(), name.as_deref(), dry_run, force),
             _ => unreachable!("all BundleCommands variants covered"),
         },
 
@@ -397,21 +755,44 @@ fn main() -> Result<()> {
         // ============================================
         Commands::Usage(command) => match command {
             UsageCommands::Scan { dry_run, reset } => cmd_usage_scan(&db, dry_run, reset),
-            UsageCommands::Show { limit } => cmd_usage_show(&db, limit),
-            UsageCommands::Tool { name } => cmd_usage_tool(&db, &name),
-            UsageCommands::Log { command } => cmd_usage_log(&db, &command),
-            UsageCommands::Init { shell } => {
+            UsageCommands::Show { limit } => cmd_usage_show(&db, limit, json),
+            UsageCommands::Tool { name } => cmd_usage_tool(&db, &name, json),
+            UsageCommands::Log { command, .. } => cmd_usage_log(&db, &command),
+            UsageCommands::Init { shell, aliases } => {
                 let config = HoardConfig::load()?;
-                cmd_usage_init(&config, shell)
+                if aliases {
+                    cmd_alias_snippet(&db, &config, shell)
+                } else {
+                    cmd_usage_init(&config, shell)
+                }
             }
-            UsageCommands::Config { mode } => {
+            UsageCommands::Config { mode, history } => {
                 let mut config = HoardConfig::load()?;
-                cmd_usage_config(&mut config, mode)
+                cmd_usage_config(&mut config, mode, history)
             }
             UsageCommands::Reset { force } => cmd_usage_reset(&db, force),
+            UsageCommands::Export {
+                format,
+                since,
+                output,
+            } => cmd_usage_export(&db, &format, since.as_deref(), output.as_deref()),
             _ => unreachable!("all UsageCommands variants covered"),
         },
 
+        // ============================================
+        // ALIASES
+        // ============================================
+        Commands::Alias(command) => match command {
+            AliasCommands::Add {
+                name,
+                command,
+                tool,
+            } => cmd_alias_add(&db, &name, command, tool.as_deref()),
+            AliasCommands::List => cmd_alias_list(&db, json),
+            AliasCommands::Remove { names } => cmd_alias_remove(&db, names),
+            _ => unreachable!("all AliasCommands variants covered"),
+        },
+
         // ============================================
         // CONFIG (dotfiles management)
         // ============================================
@@ -421,22 +802,73 @@ fn main() -> Result<()> {
                 target,
                 source,
                 tool,
-            } => cmd_config_link(&db, &name, &target, &source, tool),
+                profile,
+                mode,
+                validate,
+            } => cmd_config_link(
+                &db,
+                &name,
+                &target,
+                &source,
+                hoards::LinkOptions {
+                    tool,
+                    profile,
+                    mode: mode.as_str().into(),
+                    validate,
+                },
+            ),
             ConfigCommands::Unlink {
                 name,
                 remove_symlink,
                 force,
-            } => cmd_config_unlink(&db, &name, remove_symlink, force),
+                profile,
+            } => cmd_config_unlink(&db, &name, remove_symlink, force, profile.as_deref()),
             ConfigCommands::List { broken, format } => cmd_config_list(&db, broken, &format),
             ConfigCommands::Show { name } => cmd_config_show(&db, &name),
             ConfigCommands::Sync { dry_run, force } => cmd_config_sync(&db, dry_run, force),
             ConfigCommands::Status => cmd_config_status(&db),
+            ConfigCommands::Watch { repair } => cmd_config_watch(&db, repair),
+            ConfigCommands::Diff { name, adopt } => cmd_config_diff(&db, name.as_deref(), adopt),
+            ConfigCommands::Import {
+                stow,
+                chezmoi,
+                target,
+                dry_run,
+            } => cmd_config_import(
+                &db,
+                stow.as_deref(),
+                chezmoi.as_deref(),
+                target.as_deref(),
+                dry_run,
+            ),
+            ConfigCommands::Repo(repo_cmd) => match repo_cmd {
+                ConfigRepoCommands::Init { path } => cmd_config_repo_init(&db, path.as_deref()),
+                ConfigRepoCommands::Clone { url, path } => {
+                    cmd_config_repo_clone(&db, &url, path.as_deref())
+                }
+                ConfigRepoCommands::Push => cmd_config_repo_push(&db),
+                ConfigRepoCommands::Pull => cmd_config_repo_pull(&db),
+                _ => unreachable!("all ConfigRepoCommands variants covered"),
+            },
+            ConfigCommands::Restore { name, list, force } => {
+                cmd_config_restore(&db, &name, list, force)
+            }
             ConfigCommands::Edit {
                 name,
                 target,
                 source,
                 tool,
-            } => cmd_config_edit(&db, &name, target, source, tool),
+                mode,
+                validate,
+            } => cmd_config_edit(
+                &db,
+                &name,
+                target,
+                source,
+                tool,
+                mode.as_deref().map(ConfigMode::from),
+                validate,
+            ),
             _ => unreachable!("all ConfigCommands variants covered"),
         },
 
@@ -447,12 +879,39 @@ fn main() -> Result<()> {
             output,
             format,
             installed,
-        } => cmd_export(&db, output, &format, installed),
+            bundle,
+            schema,
+        } => {
+            if schema {
+                cmd_export_schema(output)
+            } else {
+                cmd_export(&db, output, &format, installed, bundle.as_deref())
+            }
+        }
         Commands::Import {
             file,
+            db: other,
+            conflict,
             skip_existing,
             dry_run,
-        } => cmd_import(&db, &file, skip_existing, dry_run),
+        } => match (file, other) {
+            (_, Some(other)) => {
+                let strategy = conflict.as_deref().map(MergeStrategy::from);
+                cmd_import_db(&db, &other, strategy.unwrap_or_default(), dry_run)
+            }
+            (Some(file), None) => cmd_import(&db, &file, skip_existing, dry_run),
+            (None, None) => unreachable!("clap requires a file or --db"),
+        },
+        Commands::Compare {
+            other,
+            install_missing,
+            force,
+        } => cmd_compare(&db, &other, install_missing, force, json),
+
+        // ============================================
+        // SERVE
+        // ============================================
+        Commands::Serve { listen, token } => cmd_serve(&listen, token),
 
         // ============================================
         // COMPLETIONS
@@ -464,7 +923,10 @@ fn main() -> Result<()> {
                 generate(shell, &mut cmd, name, &mut std::io::stdout());
                 Ok(())
             }
-            CompletionsCommands::Install { shell, force } => cmd_completions_install(shell, force),
+            CompletionsCommands::Install { shell, force, man } => {
+                cmd_completions_install(shell, force, man)
+            }
+            CompletionsCommands::Man { dir } => cmd_completions_man(dir.as_deref()),
             CompletionsCommands::Uninstall { shell } => cmd_completions_uninstall(shell),
             CompletionsCommands::Status => cmd_completions_status(),
             _ => unreachable!("all CompletionsCommands variants covered"),
@@ -477,21 +939,35 @@ fn main() -> Result<()> {
             installed,
             category,
             label,
+            libraries,
             format,
-        } => cmd_list(&db, installed, category, label, &format),
+        } => cmd_list(
+            &db,
+            installed,
+            category,
+            label,
+            libraries,
+            if json { "json" } else { &format },
+        ),
 
-        Commands::Search { query } => cmd_search(&db, &query),
-        Commands::Scan { dry_run } => cmd_scan(&db, dry_run),
+        Commands::Search { query } => cmd_search(&db, &query, json),
+        Commands::Scan { dry_run, full } => cmd_scan(&db, dry_run, full),
         Commands::FetchDescriptions { dry_run } => cmd_fetch_descriptions(&db, dry_run),
         Commands::Suggest { category } => cmd_suggest(category),
-        Commands::Stats => cmd_stats(&db),
+        Commands::Stats => cmd_stats(&db, json),
         Commands::Info => cmd_info(),
-        Commands::Categories => cmd_categories(&db),
+        Commands::Categories => cmd_categories(&db, json),
         Commands::Labels => cmd_labels(&db),
         Commands::Unused => cmd_unused(&db),
         Commands::Recommend { count } => cmd_recommend(&db, count),
-        Commands::Doctor { fix } => cmd_doctor(&db, fix),
+        Commands::Doctor { fix } => cmd_doctor(&db, fix, json),
 
         _ => unreachable!("all variants covered"),
+    };
+
+    // Keep failures in the log file next to what led up to them
+    if let Err(e) = &result {
+        tracing::debug!("failed: {:#}", e);
     }
+    result
 }
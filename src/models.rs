@@ -1,5 +1,7 @@
+use anyhow::{Result, bail};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Installation source for a tool
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -11,8 +13,14 @@ pub enum InstallSource {
     Npm,
     Pip,
     Brew,
+    /// Prebuilt binary from a forge release
+    GitHub,
+    /// Shell plugin or prompt (oh-my-zsh, zinit, fisher, oh-my-posh)
+    Shell,
     Manual,
     Unknown,
+    /// Source plugin (`hoards-source-<name>` on PATH)
+    Plugin(String),
 }
 
 impl std::fmt::Display for InstallSource {
@@ -25,15 +33,19 @@ impl std::fmt::Display for InstallSource {
             Self::Npm => write!(f, "npm"),
             Self::Pip => write!(f, "pip"),
             Self::Brew => write!(f, "brew"),
+            Self::GitHub => write!(f, "github"),
+            Self::Shell => write!(f, "shell"),
             Self::Manual => write!(f, "manual"),
             Self::Unknown => write!(f, "unknown"),
+            Self::Plugin(name) => write!(f, "{}", name),
         }
     }
 }
 
-impl From<&str> for InstallSource {
-    fn from(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
+impl InstallSource {
+    /// The built-in source of a name
+    pub fn builtin(s: &str) -> Option<Self> {
+        let source = match s.to_lowercase().as_str() {
             "cargo" => Self::Cargo,
             "apt" => Self::Apt,
             "snap" => Self::Snap,
@@ -41,8 +53,71 @@ impl From<&str> for InstallSource {
             "npm" => Self::Npm,
             "pip" => Self::Pip,
             "brew" => Self::Brew,
+            "github" => Self::GitHub,
+            "shell" => Self::Shell,
             "manual" => Self::Manual,
-            _ => Self::Unknown,
+            "unknown" => Self::Unknown,
+            _ => return None,
+        };
+        Some(source)
+    }
+
+    /// The system package manager of the platform hoards runs on: brew on
+    /// macOS, apt elsewhere on Unix, and none on Windows
+    pub fn platform_default() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::Brew
+        } else if cfg!(unix) {
+            Self::Apt
+        } else {
+            Self::Manual
+        }
+    }
+}
+
+impl From<&str> for InstallSource {
+    fn from(s: &str) -> Self {
+        Self::builtin(s).unwrap_or_else(|| {
+            let name = s.to_lowercase();
+            match crate::sources::plugin(&name) {
+                Some(_) => Self::Plugin(name),
+                None => Self::Unknown,
+            }
+        })
+    }
+}
+
+/// What kind of thing a tracked tool is
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolKind {
+    /// A command-line or desktop application
+    #[default]
+    Application,
+    /// A language runtime, toolchain or dev environment (rustup toolchains,
+    /// pyenv pythons, JDKs, docker, ...)
+    Runtime,
+    /// A pip or npm package that installs no commands, pulled in as a
+    /// dependency or for import
+    Library,
+}
+
+impl std::fmt::Display for ToolKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Application => write!(f, "application"),
+            Self::Runtime => write!(f, "runtime"),
+            Self::Library => write!(f, "library"),
+        }
+    }
+}
+
+impl From<&str> for ToolKind {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "runtime" => Self::Runtime,
+            "library" => Self::Library,
+            _ => Self::Application,
         }
     }
 }
@@ -60,6 +135,14 @@ pub struct Tool {
     pub is_installed: bool,
     pub is_favorite: bool,
     pub notes: Option<String>,
+    /// SPDX identifier (or name) of the upstream license
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Version found installed by the last scan
+    #[serde(default)]
+    pub installed_version: Option<String>,
+    #[serde(default)]
+    pub kind: ToolKind,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -78,6 +161,9 @@ impl Tool {
             is_installed: false,
             is_favorite: false,
             notes: None,
+            license: None,
+            installed_version: None,
+            kind: ToolKind::default(),
             created_at: now,
             updated_at: now,
         }
@@ -108,12 +194,101 @@ impl Tool {
         self
     }
 
+    pub fn with_installed_version(mut self, version: impl Into<String>) -> Self {
+        self.installed_version = Some(version.into());
+        self
+    }
+
+    pub fn with_kind(mut self, kind: ToolKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
     pub fn installed(mut self) -> Self {
         self.is_installed = true;
         self
     }
 }
 
+/// How restrictive a license is, for compliance reporting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LicenseKind {
+    Permissive,
+    /// File- or library-level copyleft (LGPL, MPL, EPL, ...)
+    WeakCopyleft,
+    /// Copyleft that extends to derived works (GPL, AGPL, ...)
+    Copyleft,
+    Unknown,
+}
+
+impl LicenseKind {
+    /// Classify an SPDX expression or license name
+    ///
+    /// For `OR` choices the least restrictive option counts; for `AND`
+    /// combinations the most restrictive one does.
+    pub fn of(license: &str) -> Self {
+        let license = license.trim().trim_matches(|c| c == '(' || c == ')');
+        if license.contains(" OR ") || license.contains('/') {
+            return license
+                .split(" OR ")
+                .flat_map(|part| part.split('/'))
+                .map(Self::of)
+                .min()
+                .unwrap_or(Self::Unknown);
+        }
+        if license.contains(" AND ") {
+            return license
+                .split(" AND ")
+                .map(Self::of)
+                .max()
+                .unwrap_or(Self::Unknown);
+        }
+
+        let id = license.to_uppercase();
+        if id.contains("LGPL") || id.contains("LESSER") {
+            Self::WeakCopyleft
+        } else if id.contains("GPL") || id.contains("SSPL") || id.contains("GENERAL PUBLIC") {
+            Self::Copyleft
+        } else if ["MPL", "MOZILLA", "EPL", "ECLIPSE", "CDDL", "EUPL"]
+            .iter()
+            .any(|p| id.contains(p))
+        {
+            Self::WeakCopyleft
+        } else if [
+            "MIT",
+            "APACHE",
+            "BSD",
+            "ISC",
+            "ZLIB",
+            "UNLICENSE",
+            "CC0",
+            "BSL-1.0",
+            "BOOST",
+            "WTFPL",
+            "PSF",
+            "PYTHON",
+            "ARTISTIC",
+            "0BSD",
+        ]
+        .iter()
+        .any(|p| id.contains(p))
+        {
+            Self::Permissive
+        } else {
+            Self::Unknown
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Permissive => "permissive",
+            Self::WeakCopyleft => "weak copyleft",
+            Self::Copyleft => "copyleft",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 /// An interest category for AI-assisted discovery
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Interest {
@@ -136,6 +311,43 @@ impl Interest {
     }
 }
 
+/// How a config is put in place at its target
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigMode {
+    /// Symlink to the source
+    #[default]
+    Link,
+    /// Copy of the source, for tools that break on symlinked configs
+    Copy,
+}
+
+impl ConfigMode {
+    pub fn is_link(&self) -> bool {
+        *self == Self::Link
+    }
+}
+
+impl std::fmt::Display for ConfigMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Link => write!(f, "link"),
+            Self::Copy => write!(f, "copy"),
+        }
+    }
+}
+
+impl From<&str> for ConfigMode {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "copy" => Self::Copy,
+            _ => Self::Link,
+        }
+    }
+}
+
 /// A config file tracked by hoard (links to dotfiles)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -145,6 +357,14 @@ pub struct Config {
     pub target_path: String,
     pub tool_id: Option<i64>,
     pub is_symlinked: bool,
+    #[serde(default)]
+    pub mode: ConfigMode,
+    /// Hash of what was last written to the target, for templates and copies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rendered_hash: Option<String>,
+    /// Command that checks the config after it is put in place
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validate_command: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -163,6 +383,9 @@ impl Config {
             target_path: target.into(),
             tool_id: None,
             is_symlinked: false,
+            mode: ConfigMode::Link,
+            rendered_hash: None,
+            validate_command: None,
             created_at: now,
             updated_at: now,
         }
@@ -176,6 +399,12 @@ pub struct Bundle {
     pub name: String,
     pub description: Option<String>,
     pub tools: Vec<String>,
+    /// Members that only apply to some machines, by tool name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub conditions: BTreeMap<String, MemberCondition>,
+    /// Names of bundles included in this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -186,6 +415,8 @@ impl Bundle {
             name: name.into(),
             description: None,
             tools,
+            conditions: BTreeMap::new(),
+            includes: Vec::new(),
             created_at: Utc::now(),
         }
     }
@@ -194,6 +425,265 @@ impl Bundle {
         self.description = Some(desc.into());
         self
     }
+
+    /// Condition attached to a member, if any
+    pub fn condition(&self, tool: &str) -> Option<&MemberCondition> {
+        self.conditions.get(tool)
+    }
+
+    /// Whether a member applies to `machine` (unconditional members always do)
+    pub fn applies(&self, tool: &str, machine: &Machine) -> bool {
+        self.condition(tool).is_none_or(|c| c.matches(machine))
+    }
+}
+
+/// A bundle with the bundles it includes, resolved recursively
+#[derive(Debug, Clone)]
+pub struct BundleTree {
+    pub bundle: Bundle,
+    pub children: Vec<BundleTree>,
+}
+
+impl BundleTree {
+    /// All tools of the tree as one bundle, each listed once
+    ///
+    /// A tool listed in several bundles keeps the condition of its first
+    /// occurrence, parents before the bundles they include.
+    pub fn flatten(&self) -> Bundle {
+        let mut flat = Bundle {
+            includes: Vec::new(),
+            tools: Vec::new(),
+            conditions: BTreeMap::new(),
+            ..self.bundle.clone()
+        };
+        self.collect(&mut flat);
+        flat
+    }
+
+    fn collect(&self, flat: &mut Bundle) {
+        for tool in &self.bundle.tools {
+            if flat.tools.contains(tool) {
+                continue;
+            }
+            if let Some(condition) = self.bundle.condition(tool) {
+                flat.conditions.insert(tool.clone(), condition.clone());
+            }
+            flat.tools.push(tool.clone());
+        }
+        for child in &self.children {
+            child.collect(flat);
+        }
+    }
+}
+
+/// A bundle as shared with others (`bundle publish`, `bundle install --from-url`)
+///
+/// Included bundles are flattened into the member list, and each member
+/// carries its source and, when the bundle is locked, its version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedBundle {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub tools: Vec<SharedTool>,
+}
+
+/// A member of a shared bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedTool {
+    pub name: String,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Machines the member applies to, as in `--when`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl SharedBundle {
+    /// Parse a shared bundle from TOML, or JSON when it looks like JSON
+    pub fn parse(text: &str) -> Result<Self> {
+        let shared: Self = if text.trim_start().starts_with('{') {
+            serde_json::from_str(text)?
+        } else {
+            toml::from_str(text)?
+        };
+        if shared.name.trim().is_empty() {
+            bail!("Shared bundle has no name");
+        }
+        if shared.tools.is_empty() {
+            bail!("Shared bundle '{}' has no tools", shared.name);
+        }
+        for tool in &shared.tools {
+            if let Some(when) = &tool.when {
+                MemberCondition::parse(when)?;
+            }
+        }
+        Ok(shared)
+    }
+
+    /// Serialize as TOML
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    /// The bundle to store locally
+    pub fn to_bundle(&self) -> Result<Bundle> {
+        let mut bundle = Bundle::new(
+            &self.name,
+            self.tools.iter().map(|t| t.name.clone()).collect(),
+        );
+        bundle.description = self.description.clone();
+        for tool in &self.tools {
+            if let Some(when) = &tool.when {
+                bundle
+                    .conditions
+                    .insert(tool.name.clone(), MemberCondition::parse(when)?);
+            }
+        }
+        Ok(bundle)
+    }
+}
+
+/// The machine bundle conditions are evaluated against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    /// As in `std::env::consts::OS` ("linux", "macos", ...)
+    pub os: String,
+    /// As in `std::env::consts::ARCH` ("x86_64", "aarch64", ...)
+    pub arch: String,
+    pub hostname: String,
+}
+
+impl Machine {
+    /// The machine hoards is running on (looked up once per process)
+    pub fn current() -> Self {
+        static CURRENT: std::sync::LazyLock<Machine> = std::sync::LazyLock::new(|| Machine {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            hostname: current_hostname(),
+        });
+        CURRENT.clone()
+    }
+}
+
+impl std::fmt::Display for Machine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{} ({})", self.os, self.arch, self.hostname)
+    }
+}
+
+/// Short host name, or an empty string if it can't be determined
+fn current_hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| {
+            let output = std::process::Command::new("hostname").output().ok()?;
+            Some(String::from_utf8_lossy(&output.stdout).into_owned())
+        })
+        .map(|name| name.trim().split('.').next().unwrap_or("").to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Machines a bundle member is meant for
+///
+/// Written as comma-separated `key=value` pairs, e.g. `os=linux,arch=aarch64`
+/// or `host=work-*`. Every given key must match; `host` accepts `*`
+/// wildcards.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberCondition {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+}
+
+impl MemberCondition {
+    /// Parse `os=linux,arch=x86_64,host=pattern`
+    pub fn parse(s: &str) -> Result<Self> {
+        let mut condition = Self::default();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let Some((key, value)) = part.split_once('=') else {
+                bail!("Invalid condition '{}': expected key=value", part);
+            };
+            let value = value.trim().to_lowercase();
+            if value.is_empty() {
+                bail!("Invalid condition '{}': empty value", part);
+            }
+            match key.trim().to_lowercase().as_str() {
+                "os" => condition.os = Some(normalize_os(&value).to_string()),
+                "arch" => condition.arch = Some(normalize_arch(&value).to_string()),
+                "host" | "hostname" => condition.host = Some(value),
+                other => bail!("Unknown condition key '{}' (use os, arch or host)", other),
+            }
+        }
+        if condition == Self::default() {
+            bail!("Empty condition (use e.g. os=linux, arch=aarch64 or host=work-*)");
+        }
+        Ok(condition)
+    }
+
+    /// Whether every part of the condition holds on `machine`
+    pub fn matches(&self, machine: &Machine) -> bool {
+        self.os.as_deref().is_none_or(|os| os == machine.os)
+            && self.arch.as_deref().is_none_or(|arch| arch == machine.arch)
+            && self
+                .host
+                .as_deref()
+                .is_none_or(|host| glob_match(host, &machine.hostname))
+    }
+}
+
+impl std::fmt::Display for MemberCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = [("os", &self.os), ("arch", &self.arch), ("host", &self.host)]
+            .into_iter()
+            .filter_map(|(key, value)| value.as_ref().map(|v| format!("{}={}", key, v)))
+            .collect();
+        f.write_str(&parts.join(","))
+    }
+}
+
+/// Map common OS spellings to `std::env::consts::OS` names
+fn normalize_os(os: &str) -> &str {
+    match os {
+        "darwin" | "mac" | "osx" => "macos",
+        "win" | "win32" | "win64" => "windows",
+        other => other,
+    }
+}
+
+/// Map common architecture spellings to `std::env::consts::ARCH` names
+fn normalize_arch(arch: &str) -> &str {
+    match arch {
+        "amd64" | "x64" => "x86_64",
+        "arm64" => "aarch64",
+        "i386" | "i686" => "x86",
+        other => other,
+    }
+}
+
+/// Match `text` against a pattern where `*` stands for any run of characters
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            if rest.is_empty() {
+                return true;
+            }
+            (0..=text.len())
+                .filter(|&i| text.is_char_boundary(i))
+                .any(|i| glob_match(rest, &text[i..]))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -211,6 +701,7 @@ mod tests {
         assert_eq!(InstallSource::Npm.to_string(), "npm");
         assert_eq!(InstallSource::Pip.to_string(), "pip");
         assert_eq!(InstallSource::Brew.to_string(), "brew");
+        assert_eq!(InstallSource::GitHub.to_string(), "github");
         assert_eq!(InstallSource::Manual.to_string(), "manual");
         assert_eq!(InstallSource::Unknown.to_string(), "unknown");
     }
@@ -227,6 +718,7 @@ mod tests {
         assert_eq!(InstallSource::from("npm"), InstallSource::Npm);
         assert_eq!(InstallSource::from("pip"), InstallSource::Pip);
         assert_eq!(InstallSource::from("brew"), InstallSource::Brew);
+        assert_eq!(InstallSource::from("github"), InstallSource::GitHub);
         assert_eq!(InstallSource::from("manual"), InstallSource::Manual);
         assert_eq!(InstallSource::from("unknown"), InstallSource::Unknown);
         assert_eq!(InstallSource::from("garbage"), InstallSource::Unknown);
@@ -244,6 +736,7 @@ mod tests {
             InstallSource::Npm,
             InstallSource::Pip,
             InstallSource::Brew,
+            InstallSource::GitHub,
             InstallSource::Manual,
             InstallSource::Unknown,
         ];
@@ -367,6 +860,147 @@ mod tests {
         assert!(bundle.tools.is_empty());
     }
 
+    #[test]
+    fn test_member_condition() {
+        let machine = Machine {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            hostname: "work-laptop".to_string(),
+        };
+
+        let linux = MemberCondition::parse("os=Linux").unwrap();
+        assert!(linux.matches(&machine));
+        assert_eq!(linux.to_string(), "os=linux");
+
+        let mac = MemberCondition::parse("os=darwin, arch=arm64").unwrap();
+        assert_eq!(mac.to_string(), "os=macos,arch=aarch64");
+        assert!(!mac.matches(&machine));
+
+        assert!(
+            MemberCondition::parse("host=work-*")
+                .unwrap()
+                .matches(&machine)
+        );
+        assert!(
+            MemberCondition::parse("host=*-laptop")
+                .unwrap()
+                .matches(&machine)
+        );
+        assert!(
+            !MemberCondition::parse("host=home-*")
+                .unwrap()
+                .matches(&machine)
+        );
+
+        assert!(MemberCondition::parse("").is_err());
+        assert!(MemberCondition::parse("distro=arch").is_err());
+        assert!(MemberCondition::parse("os").is_err());
+
+        let mut bundle = Bundle::new("shell", vec!["fd".to_string(), "mas".to_string()]);
+        bundle.conditions.insert(
+            "mas".to_string(),
+            MemberCondition::parse("os=macos").unwrap(),
+        );
+        assert!(bundle.applies("fd", &machine));
+        assert!(!bundle.applies("mas", &machine));
+    }
+
+    #[test]
+    fn test_bundle_tree_flatten() {
+        let mut rust = Bundle::new("rust-dev", vec!["bacon".to_string(), "fd".to_string()]);
+        rust.conditions.insert(
+            "bacon".to_string(),
+            MemberCondition::parse("os=linux").unwrap(),
+        );
+        let media = Bundle::new("media", vec!["ffmpeg".to_string()]);
+        let mut workstation = Bundle::new("workstation", vec!["fd".to_string()]);
+        workstation.includes = vec!["rust-dev".to_string(), "media".to_string()];
+
+        let tree = BundleTree {
+            bundle: workstation,
+            children: vec![
+                BundleTree {
+                    bundle: rust,
+                    children: Vec::new(),
+                },
+                BundleTree {
+                    bundle: media,
+                    children: Vec::new(),
+                },
+            ],
+        };
+
+        let flat = tree.flatten();
+        assert_eq!(flat.name, "workstation");
+        assert_eq!(flat.tools, vec!["fd", "bacon", "ffmpeg"]);
+        assert!(flat.condition("bacon").is_some());
+        assert!(flat.includes.is_empty());
+    }
+
+    // ==================== License Tests ====================
+
+    #[test]
+    fn test_license_kind() {
+        assert_eq!(LicenseKind::of("MIT"), LicenseKind::Permissive);
+        assert_eq!(LicenseKind::of("Apache-2.0"), LicenseKind::Permissive);
+        assert_eq!(LicenseKind::of("GPL-3.0-only"), LicenseKind::Copyleft);
+        assert_eq!(LicenseKind::of("AGPL-3.0"), LicenseKind::Copyleft);
+        assert_eq!(LicenseKind::of("LGPL-2.1"), LicenseKind::WeakCopyleft);
+        assert_eq!(LicenseKind::of("MPL-2.0"), LicenseKind::WeakCopyleft);
+        assert_eq!(LicenseKind::of("Other"), LicenseKind::Unknown);
+
+        // Choices take the least restrictive option, combinations the most
+        assert_eq!(LicenseKind::of("Unlicense OR MIT"), LicenseKind::Permissive);
+        assert_eq!(LicenseKind::of("MIT/Apache-2.0"), LicenseKind::Permissive);
+        assert_eq!(
+            LicenseKind::of("GPL-2.0-or-later OR MIT"),
+            LicenseKind::Permissive
+        );
+        assert_eq!(LicenseKind::of("MIT AND GPL-3.0"), LicenseKind::Copyleft);
+    }
+
+    #[test]
+    fn test_shared_bundle() {
+        let text = r#"
+            name = "shell"
+            description = "Shell essentials"
+
+            [[tools]]
+            name = "ripgrep"
+            source = "cargo"
+            version = "14.1.0"
+
+            [[tools]]
+            name = "mas"
+            source = "brew"
+            when = "os=macos"
+        "#;
+        let shared = SharedBundle::parse(text).unwrap();
+        assert_eq!(shared.tools.len(), 2);
+        assert_eq!(shared.tools[0].version.as_deref(), Some("14.1.0"));
+
+        let bundle = shared.to_bundle().unwrap();
+        assert_eq!(bundle.tools, vec!["ripgrep", "mas"]);
+        assert_eq!(
+            bundle.condition("mas").unwrap().os.as_deref(),
+            Some("macos")
+        );
+
+        // TOML and JSON round-trip
+        let toml = shared.to_toml().unwrap();
+        assert_eq!(SharedBundle::parse(&toml).unwrap(), shared);
+        let json = serde_json::to_string(&shared).unwrap();
+        assert_eq!(SharedBundle::parse(&json).unwrap(), shared);
+
+        assert!(SharedBundle::parse("name = \"empty\"\ntools = []").is_err());
+        assert!(
+            SharedBundle::parse(
+                "name = \"x\"\n[[tools]]\nname = \"a\"\nsource = \"cargo\"\nwhen = \"cpu=fast\""
+            )
+            .is_err()
+        );
+    }
+
     // ==================== Serialization Tests ====================
 
     #[test]
@@ -0,0 +1,71 @@
+//! Running external commands with a deadline
+
+use std::io::{self, Read};
+use std::process::{Command, Output, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Run a command to completion, or kill it once `timeout` has passed
+///
+/// stdout and stderr are read on their own threads while waiting, so a
+/// child with a lot to say can't fill a pipe and stall. Returns None when
+/// the command timed out.
+pub fn output_with_timeout(cmd: &mut Command, timeout: Duration) -> io::Result<Option<Output>> {
+    let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_with_timeout() {
+        // More output than a pipe buffer holds
+        let output = output_with_timeout(
+            Command::new("seq").args(["1", "200000"]),
+            Duration::from_secs(10),
+        )
+        .unwrap()
+        .unwrap();
+        assert!(output.status.success());
+        assert!(output.stdout.ends_with(b"200000\n"));
+
+        let slow = output_with_timeout(Command::new("sleep").arg("5"), Duration::from_millis(100))
+            .unwrap();
+        assert!(slow.is_none());
+
+        let mut missing = Command::new("hoards-no-such-program");
+        assert!(output_with_timeout(&mut missing, Duration::from_secs(1)).is_err());
+    }
+}
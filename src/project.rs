@@ -0,0 +1,447 @@
+//! Infer the CLI tools a project needs from the files in its directory
+//!
+//! Looks at build manifests (Cargo.toml, package.json and its scripts,
+//! pyproject.toml), task runners (Makefile, justfile), version files
+//! (.tool-versions, .nvmrc) and CI configs. Install commands found in
+//! scripts name tools directly; otherwise known tools invoked at the start
+//! of a command are picked up.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::InstallSource;
+use crate::scanner::{KnownTool, known_tools};
+
+/// A tool a project appears to need
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectTool {
+    pub name: String,
+    /// Best guess at where to install it from
+    pub source: Option<InstallSource>,
+    /// What gave it away, e.g. "Cargo.toml" or "Makefile: cargo install cargo-deny"
+    pub reason: String,
+}
+
+/// Files whose mere presence implies a tool
+const MARKER_FILES: &[(&str, &str, InstallSource)] = &[
+    ("Cargo.toml", "rustup", InstallSource::Manual),
+    ("rust-toolchain.toml", "rustup", InstallSource::Manual),
+    ("rust-toolchain", "rustup", InstallSource::Manual),
+    ("deny.toml", "cargo-deny", InstallSource::Cargo),
+    (
+        ".config/nextest.toml",
+        "cargo-nextest",
+        InstallSource::Cargo,
+    ),
+    ("bacon.toml", "bacon", InstallSource::Cargo),
+    ("Cross.toml", "cross", InstallSource::Cargo),
+    ("release.toml", "cargo-release", InstallSource::Cargo),
+    ("package.json", "fnm", InstallSource::Cargo),
+    (".nvmrc", "fnm", InstallSource::Cargo),
+    (".node-version", "fnm", InstallSource::Cargo),
+    ("pnpm-lock.yaml", "pnpm", InstallSource::Npm),
+    ("yarn.lock", "yarn", InstallSource::Npm),
+    (".python-version", "pyenv", InstallSource::Manual),
+    ("uv.lock", "uv", InstallSource::Pip),
+    ("poetry.lock", "poetry", InstallSource::Pip),
+    ("ruff.toml", "ruff", InstallSource::Pip),
+    (".pre-commit-config.yaml", "pre-commit", InstallSource::Pip),
+    ("Makefile", "make", InstallSource::Apt),
+    ("GNUmakefile", "make", InstallSource::Apt),
+    ("justfile", "just", InstallSource::Cargo),
+    ("Justfile", "just", InstallSource::Cargo),
+    (".justfile", "just", InstallSource::Cargo),
+    ("Taskfile.yml", "task", InstallSource::Brew),
+    ("Dockerfile", "docker", InstallSource::Apt),
+    ("compose.yaml", "docker", InstallSource::Apt),
+    ("docker-compose.yml", "docker", InstallSource::Apt),
+    ("Chart.yaml", "helm", InstallSource::Manual),
+    ("skaffold.yaml", "skaffold", InstallSource::Manual),
+    ("flake.nix", "nix", InstallSource::Manual),
+    ("shell.nix", "nix", InstallSource::Manual),
+    (".envrc", "direnv", InstallSource::Apt),
+    (".goreleaser.yml", "goreleaser", InstallSource::Manual),
+    ("go.mod", "go", InstallSource::Manual),
+];
+
+/// Scripts scanned for install commands and tool invocations
+const SCRIPT_FILES: &[&str] = &[
+    "Makefile",
+    "GNUmakefile",
+    "justfile",
+    "Justfile",
+    ".justfile",
+    "Taskfile.yml",
+    ".gitlab-ci.yml",
+    ".circleci/config.yml",
+];
+
+/// Directories whose YAML files are CI workflows
+const CI_DIRS: &[&str] = &[
+    ".github/workflows",
+    ".forgejo/workflows",
+    ".gitea/workflows",
+];
+
+/// Collected tools, keyed by name so the first reason wins
+#[derive(Default)]
+struct Found(BTreeMap<String, ProjectTool>);
+
+impl Found {
+    fn add(&mut self, name: &str, source: Option<InstallSource>, reason: impl Into<String>) {
+        let name = name.trim();
+        if name.is_empty() || name.starts_with('-') || name.contains(['$', '{', '/']) {
+            return;
+        }
+        self.0.entry(name.to_string()).or_insert_with(|| {
+            let known = KnownTool::find(name);
+            ProjectTool {
+                name: name.to_string(),
+                source: known.map(|kt| kt.source.clone()).or(source),
+                reason: reason.into(),
+            }
+        });
+    }
+}
+
+/// The project a directory belongs to: the nearest ancestor holding a `.git`,
+/// or the directory itself
+pub fn project_root(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .find(|d| d.join(".git").exists())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Detect the tools a project directory needs
+pub fn detect_project_tools(dir: &Path) -> Result<Vec<ProjectTool>> {
+    let mut found = Found::default();
+    let read = |file: &str| std::fs::read_to_string(dir.join(file)).ok();
+
+    for (file, tool, source) in MARKER_FILES {
+        if dir.join(file).exists() {
+            found.add(tool, Some(source.clone()), *file);
+        }
+    }
+
+    if let Some(content) = read("Cargo.toml") {
+        scan_cargo_toml(&content, &mut found);
+    }
+    if let Some(content) = read("package.json") {
+        scan_package_json(&content, &mut found);
+    }
+    if let Some(content) = read("pyproject.toml") {
+        scan_pyproject(&content, &mut found);
+    }
+    if let Some(content) = read(".tool-versions") {
+        scan_tool_versions(&content, &mut found);
+    }
+
+    for file in SCRIPT_FILES {
+        if let Some(content) = read(file) {
+            scan_script(&content, file, &mut found);
+        }
+    }
+    for ci_dir in CI_DIRS {
+        let Ok(entries) = std::fs::read_dir(dir.join(ci_dir)) else {
+            continue;
+        };
+        let mut files: Vec<_> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext == "yml" || ext == "yaml")
+            })
+            .collect();
+        files.sort();
+        for path in files {
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                let origin = format!(
+                    "{}/{}",
+                    ci_dir,
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                );
+                scan_script(&content, &origin, &mut found);
+            }
+        }
+    }
+
+    Ok(found.0.into_values().collect())
+}
+
+/// Build dependencies that shell out to a tool at build time
+const BUILD_DEPENDENCY_TOOLS: &[(&str, &str)] = &[
+    ("prost-build", "protoc"),
+    ("tonic-build", "protoc"),
+    ("protobuf-codegen", "protoc"),
+    ("bindgen", "clang"),
+    ("cmake", "cmake"),
+    ("pkg-config", "pkg-config"),
+];
+
+/// `[build-dependencies]` that need an external tool
+fn scan_cargo_toml(content: &str, found: &mut Found) {
+    let Ok(doc) = toml::from_str::<toml::Table>(content) else {
+        return;
+    };
+    let Some(deps) = doc.get("build-dependencies").and_then(|d| d.as_table()) else {
+        return;
+    };
+    for (dependency, tool) in BUILD_DEPENDENCY_TOOLS {
+        if deps.contains_key(*dependency) {
+            found.add(
+                tool,
+                Some(InstallSource::platform_default()),
+                format!("Cargo.toml: build-dependency {}", dependency),
+            );
+        }
+    }
+}
+
+/// `packageManager` names pnpm or yarn; `scripts` run like a task runner's
+fn scan_package_json(content: &str, found: &mut Found) {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return;
+    };
+    if let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) {
+        for script in scripts.values().filter_map(|s| s.as_str()) {
+            scan_script(script, "package.json: scripts", found);
+        }
+    }
+    if let Some(manager) = json.get("packageManager").and_then(|m| m.as_str()) {
+        let name = manager.split('@').next().unwrap_or_default();
+        if matches!(name, "pnpm" | "yarn") {
+            found.add(
+                name,
+                Some(InstallSource::Npm),
+                "package.json: packageManager",
+            );
+        }
+    }
+}
+
+/// `[tool.*]` tables of Python tooling
+fn scan_pyproject(content: &str, found: &mut Found) {
+    let Ok(doc) = toml::from_str::<toml::Table>(content) else {
+        return;
+    };
+    let Some(tools) = doc.get("tool").and_then(|t| t.as_table()) else {
+        return;
+    };
+    for (table, tool) in [
+        ("uv", "uv"),
+        ("poetry", "poetry"),
+        ("ruff", "ruff"),
+        ("black", "black"),
+        ("mypy", "mypy"),
+        ("pytest", "pytest"),
+        ("hatch", "hatch"),
+    ] {
+        if tools.contains_key(table) {
+            found.add(
+                tool,
+                Some(InstallSource::Pip),
+                format!("pyproject.toml: [tool.{}]", table),
+            );
+        }
+    }
+}
+
+/// asdf/mise `.tool-versions`: one `name version` per line
+fn scan_tool_versions(content: &str, found: &mut Found) {
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some(name) = line.split_whitespace().next() else {
+            continue;
+        };
+        let name = match name {
+            "nodejs" => "node",
+            "golang" => "go",
+            other => other,
+        };
+        found.add(name, None, ".tool-versions");
+    }
+}
+
+/// Install commands and known-tool invocations in a script or CI config
+fn scan_script(content: &str, origin: &str, found: &mut Found) {
+    for line in content.lines() {
+        for command in line.split(['&', '|', ';']) {
+            let words: Vec<&str> = command
+                .split_whitespace()
+                .map(|w| w.trim_matches(['"', '\'', '`', '(', ')']))
+                .skip_while(|w| matches!(*w, "-" | "run:" | "$" | "@" | "sudo" | "exec"))
+                .map(|w| w.trim_start_matches('@'))
+                .collect();
+            scan_command(&words, origin, found);
+        }
+
+        // taiki-e/install-action: `tool: cargo-nextest,cargo-deny`
+        if let Some(list) = line.trim().strip_prefix("tool:") {
+            for tool in list.split(',') {
+                let tool = tool.trim().split('@').next().unwrap_or_default();
+                found.add(
+                    tool,
+                    Some(InstallSource::Cargo),
+                    format!("{}: install-action", origin),
+                );
+            }
+        }
+    }
+}
+
+/// Look at one command (already split into words)
+fn scan_command(words: &[&str], origin: &str, found: &mut Found) {
+    let Some(&program) = words.first() else {
+        return;
+    };
+    let packages = |skip: usize| {
+        words
+            .iter()
+            .skip(skip)
+            .filter(|w| !w.starts_with('-'))
+            .map(|w| w.split(['@', '=', '<', '>']).next().unwrap_or(w))
+    };
+    let reason = |w: &[&str]| format!("{}: {}", origin, w.join(" "));
+
+    let installs: Option<(InstallSource, usize)> = match words {
+        ["cargo", "install" | "binstall", ..] => Some((InstallSource::Cargo, 2)),
+        ["pip" | "pip3" | "pipx", "install", ..] => Some((InstallSource::Pip, 2)),
+        ["uv", "tool", "install", ..] => Some((InstallSource::Pip, 3)),
+        ["npm", "install" | "i", ..] if words.contains(&"-g") || words.contains(&"--global") => {
+            Some((InstallSource::Npm, 2))
+        }
+        ["brew", "install", ..] => Some((InstallSource::Brew, 2)),
+        ["apt" | "apt-get", "install", ..] => Some((InstallSource::Apt, 2)),
+        _ => None,
+    };
+    if let Some((source, skip)) = installs {
+        let packages: Vec<&str> = packages(skip).collect();
+        for package in packages {
+            found.add(package, Some(source.clone()), reason(words));
+        }
+        return;
+    }
+
+    if let ["go", "install", target, ..] = words {
+        let path = target.split('@').next().unwrap_or_default();
+        if let Some(name) = path.rsplit('/').next() {
+            found.add(name, Some(InstallSource::Manual), reason(words));
+        }
+        return;
+    }
+
+    // A known tool run directly
+    if let Some(kt) = known_tools().iter().find(|kt| kt.binary == program) {
+        found.add(kt.name, None, format!("{}: {}", origin, program));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(found: &Found) -> Vec<&str> {
+        found.0.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_scan_script() {
+        let mut found = Found::default();
+        let ci = r#"
+      - run: cargo install cargo-deny --locked && cargo deny check
+      - run: sudo apt-get install -y libssl-dev
+      - uses: taiki-e/install-action@v2
+        with:
+          tool: cargo-nextest,cargo-llvm-cov
+      - run: npm install -g @mermaid-js/mermaid-cli
+      - run: go install github.com/golangci/golangci-lint/cmd/golangci-lint@v1.59.0
+      - run: hyperfine --warmup 3 'target/release/app'
+"#;
+        scan_script(ci, ".github/workflows/ci.yml", &mut found);
+        assert_eq!(
+            names(&found),
+            vec![
+                "cargo-deny",
+                "cargo-llvm-cov",
+                "cargo-nextest",
+                "golangci-lint",
+                "hyperfine",
+                "libssl-dev"
+            ]
+        );
+        assert_eq!(found.0["cargo-deny"].source, Some(InstallSource::Cargo));
+        assert_eq!(
+            found.0["cargo-deny"].reason,
+            ".github/workflows/ci.yml: cargo install cargo-deny --locked"
+        );
+    }
+
+    #[test]
+    fn test_scan_manifests() {
+        let mut found = Found::default();
+        scan_pyproject(
+            "[tool.ruff]\nline-length = 100\n[tool.poetry]\n",
+            &mut found,
+        );
+        scan_package_json(
+            r#"{"packageManager": "pnpm@9.1.0", "scripts": {"lint": "shellcheck scripts/*.sh"}}"#,
+            &mut found,
+        );
+        scan_tool_versions("nodejs 20.11.0\n# comment\nterraform 1.7.0\n", &mut found);
+        scan_cargo_toml("[build-dependencies]\ntonic-build = \"0.11\"\n", &mut found);
+        assert_eq!(
+            names(&found),
+            vec![
+                "node",
+                "pnpm",
+                "poetry",
+                "protoc",
+                "ruff",
+                "shellcheck",
+                "terraform"
+            ]
+        );
+        assert_eq!(
+            found.0["protoc"].reason,
+            "Cargo.toml: build-dependency tonic-build"
+        );
+        assert_eq!(found.0["pnpm"].source, Some(InstallSource::Npm));
+        assert_eq!(found.0["terraform"].source, None);
+    }
+
+    #[test]
+    fn test_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("repo/src/bin");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(project_root(&nested), nested);
+
+        std::fs::create_dir(dir.path().join("repo/.git")).unwrap();
+        assert_eq!(project_root(&nested), dir.path().join("repo"));
+    }
+
+    #[test]
+    fn test_detect_project_tools() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        std::fs::write(
+            dir.path().join("justfile"),
+            "test:\n    cargo nextest run\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join(".github/workflows")).unwrap();
+        std::fs::write(
+            dir.path().join(".github/workflows/ci.yml"),
+            "    - run: cargo install bacon\n",
+        )
+        .unwrap();
+
+        let tools = detect_project_tools(dir.path()).unwrap();
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["bacon", "just", "rustup"]);
+        assert_eq!(tools[2].reason, "Cargo.toml");
+    }
+}
@@ -0,0 +1,258 @@
+//! Static HTML report of the hoard
+//!
+//! The report is one self-contained file (inline CSS, no scripts or external
+//! assets), so it can be shared or archived as a snapshot of a setup.
+
+use chrono::{DateTime, Utc};
+
+/// Tools shown in the most-used chart
+const TOP_USED: usize = 15;
+
+/// One tracked tool in the report
+#[derive(Debug, Clone, Default)]
+pub struct ReportTool {
+    pub name: String,
+    pub source: String,
+    pub category: Option<String>,
+    pub version: Option<String>,
+    pub installed: bool,
+    pub uses: i64,
+    /// RFC 3339
+    pub last_used: Option<String>,
+    /// Newer version available, when updates were checked
+    pub update: Option<String>,
+}
+
+/// Everything the report shows
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub generated_at: DateTime<Utc>,
+    pub tools: Vec<ReportTool>,
+    /// Whether package managers were asked for updates
+    pub updates_checked: bool,
+}
+
+/// Escape text for HTML content and attribute values
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const STYLE: &str = "
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 70rem;
+       padding: 0 1rem; color: #222; background: #fafafa; }
+h1 { margin-bottom: 0; }
+.meta { color: #777; margin-top: .2rem; }
+.cards { display: flex; gap: 1rem; flex-wrap: wrap; margin: 1.5rem 0; }
+.card { background: #fff; border: 1px solid #ddd; border-radius: 8px; padding: 1rem 1.4rem; }
+.card b { display: block; font-size: 1.8rem; }
+section { margin: 2rem 0; }
+.bar { display: grid; grid-template-columns: 12rem 1fr 5rem; gap: .6rem;
+       align-items: center; margin: .2rem 0; }
+.bar span:nth-child(2) { display: block; height: .9rem; background: #2a9d8f; border-radius: 3px; }
+.bar span:last-child { text-align: right; color: #555; }
+table { border-collapse: collapse; width: 100%; background: #fff; }
+th, td { text-align: left; padding: .35rem .6rem; border-bottom: 1px solid #eee; }
+th { background: #f0f0f0; }
+td.num { text-align: right; }
+.muted { color: #999; }
+.update { color: #e76f51; font-weight: 600; }
+";
+
+/// Horizontal bars, longest first as given
+fn bar_chart(rows: &[(String, i64)]) -> String {
+    let max = rows.iter().map(|(_, n)| *n).max().unwrap_or(0).max(1);
+    rows.iter()
+        .map(|(label, n)| {
+            format!(
+                "<div class=\"bar\"><span>{}</span><span style=\"width: {:.1}%\"></span><span>{}</span></div>\n",
+                escape(label),
+                *n as f64 * 100.0 / max as f64,
+                n
+            )
+        })
+        .collect()
+}
+
+impl Report {
+    /// Render the report as a complete HTML document
+    pub fn to_html(&self) -> String {
+        let installed = self.tools.iter().filter(|t| t.installed).count();
+        let used = self.tools.iter().filter(|t| t.uses > 0).count();
+        let updates: Vec<&ReportTool> = self.tools.iter().filter(|t| t.update.is_some()).collect();
+
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        html.push_str("<title>hoards report</title>\n");
+        html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+        html.push_str("<h1>hoards report</h1>\n");
+        html.push_str(&format!(
+            "<p class=\"meta\">Generated {} by hoards {}</p>\n",
+            self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+            env!("CARGO_PKG_VERSION")
+        ));
+
+        // Summary
+        let updates_card = if self.updates_checked {
+            updates.len().to_string()
+        } else {
+            "-".to_string()
+        };
+        html.push_str("<div class=\"cards\">\n");
+        for (value, label) in [
+            (self.tools.len().to_string(), "tracked"),
+            (installed.to_string(), "installed"),
+            (used.to_string(), "used"),
+            (updates_card, "updates available"),
+        ] {
+            html.push_str(&format!(
+                "<div class=\"card\"><b>{}</b>{}</div>\n",
+                value, label
+            ));
+        }
+        html.push_str("</div>\n");
+
+        // Usage
+        let mut by_use: Vec<(String, i64)> = self
+            .tools
+            .iter()
+            .filter(|t| t.uses > 0)
+            .map(|t| (t.name.clone(), t.uses))
+            .collect();
+        by_use.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        by_use.truncate(TOP_USED);
+        html.push_str("<section>\n<h2>Most used</h2>\n");
+        if by_use.is_empty() {
+            html.push_str("<p class=\"muted\">No usage recorded.</p>\n");
+        } else {
+            html.push_str(&bar_chart(&by_use));
+        }
+        html.push_str("</section>\n");
+
+        // Categories
+        let mut categories: Vec<(String, i64)> = Vec::new();
+        for tool in self.tools.iter().filter(|t| t.installed) {
+            let category = tool.category.as_deref().unwrap_or("uncategorized");
+            match categories.iter_mut().find(|(c, _)| c == category) {
+                Some((_, n)) => *n += 1,
+                None => categories.push((category.to_string(), 1)),
+            }
+        }
+        categories.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        html.push_str("<section>\n<h2>Categories</h2>\n");
+        html.push_str(&bar_chart(&categories));
+        html.push_str("</section>\n");
+
+        // Updates
+        html.push_str("<section>\n<h2>Updates</h2>\n");
+        if !self.updates_checked {
+            html.push_str("<p class=\"muted\">Not checked.</p>\n");
+        } else if updates.is_empty() {
+            html.push_str("<p>Everything is up to date.</p>\n");
+        } else {
+            html.push_str(
+                "<table>\n<tr><th>Tool</th><th>Source</th><th>Installed</th><th>Available</th></tr>\n",
+            );
+            for tool in &updates {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"update\">{}</td></tr>\n",
+                    escape(&tool.name),
+                    escape(&tool.source),
+                    escape(tool.version.as_deref().unwrap_or("")),
+                    escape(tool.update.as_deref().unwrap_or(""))
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</section>\n");
+
+        // Inventory
+        html.push_str("<section>\n<h2>Inventory</h2>\n<table>\n");
+        html.push_str("<tr><th>Tool</th><th>Source</th><th>Category</th><th>Version</th><th>Uses</th><th>Last used</th></tr>\n");
+        for tool in &self.tools {
+            let class = if tool.installed {
+                ""
+            } else {
+                " class=\"muted\""
+            };
+            let version = match (&tool.version, &tool.update) {
+                (Some(version), Some(latest)) => format!(
+                    "{} <span class=\"update\">&rarr; {}</span>",
+                    escape(version),
+                    escape(latest)
+                ),
+                (Some(version), None) => escape(version),
+                (None, _) => String::new(),
+            };
+            html.push_str(&format!(
+                "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td></tr>\n",
+                class,
+                escape(&tool.name),
+                escape(&tool.source),
+                escape(tool.category.as_deref().unwrap_or("")),
+                version,
+                tool.uses,
+                tool.last_used.as_deref().map(|at| at.get(..10).unwrap_or(at)).unwrap_or("")
+            ));
+        }
+        html.push_str("</table>\n</section>\n</body>\n</html>\n");
+        html
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("<a href=\"x\">R&D's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;R&amp;D&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_to_html() {
+        let report = Report {
+            generated_at: Utc::now(),
+            tools: vec![
+                ReportTool {
+                    name: "ripgrep".to_string(),
+                    source: "cargo".to_string(),
+                    category: Some("search".to_string()),
+                    version: Some("14.0.0".to_string()),
+                    installed: true,
+                    uses: 42,
+                    update: Some("14.1.0".to_string()),
+                    ..Default::default()
+                },
+                ReportTool {
+                    name: "<script>".to_string(),
+                    source: "npm".to_string(),
+                    ..Default::default()
+                },
+            ],
+            updates_checked: true,
+        };
+        let html = report.to_html();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<div class=\"card\"><b>2</b>tracked</div>"));
+        assert!(html.contains("<div class=\"card\"><b>1</b>updates available</div>"));
+        assert!(html.contains("<span>ripgrep</span><span style=\"width: 100.0%\"></span>"));
+        assert!(html.contains("14.0.0 <span class=\"update\">&rarr; 14.1.0</span>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+    }
+}
@@ -0,0 +1,369 @@
+//! Language runtimes, toolchains and dev environments
+//!
+//! Rustup toolchains, pyenv pythons, nvm nodes, JDKs, container engines and
+//! kubectl are tracked as tools of kind `runtime` in the `runtime` category.
+//! They belong to their own version managers, so hoards gives upgrade
+//! guidance for them instead of upgrading them itself.
+
+use std::path::{Path, PathBuf};
+
+use crate::models::{InstallSource, Tool, ToolKind};
+use crate::scanner::{is_installed, subdirs};
+
+/// Category runtimes are filed under
+pub const RUNTIME_CATEGORY: &str = "runtime";
+
+/// Architectures that start the host triple of a rustup toolchain name
+const RUST_ARCHES: &[&str] = &[
+    "x86_64",
+    "aarch64",
+    "i686",
+    "i586",
+    "arm",
+    "armv7",
+    "riscv64gc",
+    "powerpc64le",
+    "s390x",
+    "loongarch64",
+];
+
+/// A runtime found on this machine
+#[derive(Debug, Clone, PartialEq)]
+pub struct Runtime {
+    pub name: String,
+    /// Main binary, by full path for versions kept off PATH
+    pub binary: String,
+    pub version: Option<String>,
+    pub description: String,
+    /// How to upgrade it
+    pub upgrade: String,
+}
+
+impl Runtime {
+    /// The tool hoards tracks for this runtime
+    pub fn to_tool(&self) -> Tool {
+        let mut tool = Tool::new(&self.name)
+            .with_source(InstallSource::Manual)
+            .with_binary(&self.binary)
+            .with_category(RUNTIME_CATEGORY)
+            .with_description(&self.description)
+            .with_kind(ToolKind::Runtime)
+            .installed();
+        tool.installed_version = self.version.clone();
+        tool
+    }
+}
+
+/// Every runtime found on this machine
+pub fn detect_runtimes() -> Vec<Runtime> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let env_dir = |var: &str, default: &str| {
+        std::env::var_os(var)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(default))
+    };
+
+    let mut runtimes = rustup_toolchains(&env_dir("RUSTUP_HOME", ".rustup"));
+    runtimes.extend(pyenv_versions(&env_dir("PYENV_ROOT", ".pyenv")));
+    runtimes.extend(nvm_versions(&env_dir("NVM_DIR", ".nvm")));
+    runtimes.extend(jdks(
+        &[
+            PathBuf::from("/usr/lib/jvm"),
+            PathBuf::from("/Library/Java/JavaVirtualMachines"),
+        ],
+        &env_dir("SDKMAN_DIR", ".sdkman").join("candidates/java"),
+    ));
+    runtimes.extend(container_engines());
+
+    if is_installed("kubectl") {
+        let configs: Vec<PathBuf> = match std::env::var_os("KUBECONFIG") {
+            Some(paths) => std::env::split_paths(&paths).collect(),
+            None => vec![home.join(".kube/config")],
+        };
+        let contexts: Vec<String> = configs
+            .iter()
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .flat_map(|yaml| kube_contexts(&yaml))
+            .collect();
+        runtimes.push(kubectl(&contexts));
+    }
+
+    runtimes
+}
+
+/// Upgrade guidance for a tracked runtime, if it's still on this machine
+pub fn upgrade_guidance(tool: &Tool) -> Option<String> {
+    if tool.kind != ToolKind::Runtime {
+        return None;
+    }
+    detect_runtimes()
+        .into_iter()
+        .find(|r| r.name == tool.name)
+        .map(|r| r.upgrade)
+}
+
+/// "x.y" of an "x.y.z" version
+fn minor_series(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let (major, minor) = (parts.next()?, parts.next()?);
+    [major, minor]
+        .iter()
+        .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+        .then(|| format!("{}.{}", major, minor))
+}
+
+/// Toolchains in a rustup home ("stable-x86_64-unknown-linux-gnu" ->
+/// "rust-stable")
+fn rustup_toolchains(rustup_home: &Path) -> Vec<Runtime> {
+    subdirs(&rustup_home.join("toolchains"))
+        .into_iter()
+        .map(|(dir, path)| {
+            let parts: Vec<&str> = dir.split('-').collect();
+            let host = parts
+                .iter()
+                .position(|p| RUST_ARCHES.contains(p))
+                .unwrap_or(parts.len());
+            let channel = if host == 0 {
+                dir.clone()
+            } else {
+                parts[..host].join("-")
+            };
+            let upgrade = match channel.as_str() {
+                "stable" | "beta" | "nightly" => format!("rustup update {}", channel),
+                _ => format!(
+                    "rustup toolchain install <newer>, then rustup toolchain uninstall {} \
+                     (pinned toolchain)",
+                    channel
+                ),
+            };
+            Runtime {
+                name: format!("rust-{}", channel),
+                binary: path.join("bin/rustc").display().to_string(),
+                version: None,
+                description: format!("Rust {} toolchain (rustup)", channel),
+                upgrade,
+            }
+        })
+        .collect()
+}
+
+/// Pythons installed by pyenv
+fn pyenv_versions(pyenv_root: &Path) -> Vec<Runtime> {
+    subdirs(&pyenv_root.join("versions"))
+        .into_iter()
+        .map(|(version, path)| {
+            let upgrade = match minor_series(&version) {
+                Some(series) => format!(
+                    "pyenv install {} (latest {}.x), then pyenv uninstall {}",
+                    series, series, version
+                ),
+                None => "pyenv install --list to find a newer release".to_string(),
+            };
+            Runtime {
+                name: format!("python-{}", version),
+                binary: path.join("bin/python").display().to_string(),
+                description: format!("Python {} (pyenv)", version),
+                version: Some(version),
+                upgrade,
+            }
+        })
+        .collect()
+}
+
+/// Nodes installed by nvm
+fn nvm_versions(nvm_dir: &Path) -> Vec<Runtime> {
+    subdirs(&nvm_dir.join("versions/node"))
+        .into_iter()
+        .map(|(dir, path)| {
+            let version = dir.trim_start_matches('v').to_string();
+            let major = version.split('.').next().unwrap_or(&version);
+            Runtime {
+                name: format!("node-{}", version),
+                binary: path.join("bin/node").display().to_string(),
+                description: format!("Node.js {} (nvm)", version),
+                upgrade: format!(
+                    "nvm install {} --reinstall-packages-from={}",
+                    major, version
+                ),
+                version: Some(version),
+            }
+        })
+        .collect()
+}
+
+/// JDKs in the system JVM directories and SDKMAN!
+fn jdks(system_dirs: &[PathBuf], sdkman_java: &Path) -> Vec<Runtime> {
+    let mut runtimes = Vec::new();
+
+    for dir in system_dirs {
+        for (entry, path) in subdirs(dir) {
+            // Debian keeps "default-java" and versioned aliases as symlinks
+            if path.is_symlink() {
+                continue;
+            }
+            let (id, home) = match entry.strip_suffix(".jdk") {
+                Some(id) => (id.to_string(), path.join("Contents/Home")),
+                None => (entry.clone(), path.clone()),
+            };
+            // Skip source-only and other partial installs
+            if !home.join("bin/java").exists() {
+                continue;
+            }
+            let id = id
+                .strip_prefix("java-")
+                .or_else(|| id.strip_prefix("jdk-"))
+                .unwrap_or(&id);
+            runtimes.push(Runtime {
+                name: format!("jdk-{}", id),
+                binary: home.join("bin/java").display().to_string(),
+                version: None,
+                description: format!("Java {} JDK", id),
+                upgrade: "Upgrade through the package manager or installer it came from"
+                    .to_string(),
+            });
+        }
+    }
+
+    for (version, path) in subdirs(sdkman_java) {
+        if version == "current" {
+            continue;
+        }
+        runtimes.push(Runtime {
+            name: format!("jdk-{}", version),
+            binary: path.join("bin/java").display().to_string(),
+            description: format!("Java {} JDK (SDKMAN!)", version),
+            version: Some(version),
+            upgrade: "sdk upgrade java".to_string(),
+        });
+    }
+
+    runtimes
+}
+
+/// Docker and Podman, when on PATH
+fn container_engines() -> Vec<Runtime> {
+    [
+        (
+            "docker",
+            "Docker container engine",
+            "Docker Desktop updates itself",
+        ),
+        (
+            "podman",
+            "Podman container engine",
+            "Podman Desktop updates itself",
+        ),
+    ]
+    .into_iter()
+    .filter(|(binary, ..)| is_installed(binary))
+    .map(|(binary, description, desktop)| Runtime {
+        name: binary.to_string(),
+        binary: binary.to_string(),
+        version: None,
+        description: description.to_string(),
+        upgrade: format!(
+            "Upgrade through the package manager that installed it ({})",
+            desktop
+        ),
+    })
+    .collect()
+}
+
+/// kubectl, described with the contexts it can reach
+fn kubectl(contexts: &[String]) -> Runtime {
+    let description = if contexts.is_empty() {
+        "Kubernetes CLI".to_string()
+    } else {
+        format!("Kubernetes CLI (contexts: {})", contexts.join(", "))
+    };
+    Runtime {
+        name: "kubectl".to_string(),
+        binary: "kubectl".to_string(),
+        version: None,
+        description,
+        upgrade: "Upgrade through the package manager that installed it, staying within \
+                  one minor version of your clusters"
+            .to_string(),
+    }
+}
+
+/// Context names in a kubeconfig, the current one marked with `*`
+fn kube_contexts(yaml: &str) -> Vec<String> {
+    let Ok(config) = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(yaml) else {
+        return Vec::new();
+    };
+    let current = config.get("current-context").and_then(|c| c.as_str());
+    config
+        .get("contexts")
+        .and_then(|c| c.as_sequence())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.get("name")?.as_str())
+        .map(|name| match Some(name) == current {
+            true => format!("*{}", name),
+            false => name.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_version_managed_runtimes() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for sub in [
+            "rustup/toolchains/stable-x86_64-unknown-linux-gnu",
+            "rustup/toolchains/nightly-2024-01-01-aarch64-apple-darwin",
+            "pyenv/versions/3.11.7",
+            "nvm/versions/node/v20.11.0",
+            "jvm/java-17-openjdk-amd64/bin",
+            "jvm/openjdk-17",
+            "sdkman/17.0.9-tem",
+            "sdkman/current",
+        ] {
+            std::fs::create_dir_all(root.join(sub)).unwrap();
+        }
+        std::fs::write(root.join("jvm/java-17-openjdk-amd64/bin/java"), "").unwrap();
+
+        let rust = rustup_toolchains(&root.join("rustup"));
+        let names: Vec<_> = rust.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["rust-nightly-2024-01-01", "rust-stable"]);
+        assert_eq!(rust[1].upgrade, "rustup update stable");
+        assert!(rust[1].binary.ends_with("bin/rustc"));
+
+        let python = &pyenv_versions(&root.join("pyenv"))[0];
+        assert_eq!(python.name, "python-3.11.7");
+        assert_eq!(python.version.as_deref(), Some("3.11.7"));
+        assert!(python.upgrade.starts_with("pyenv install 3.11 "));
+
+        let node = &nvm_versions(&root.join("nvm"))[0];
+        assert_eq!(node.name, "node-20.11.0");
+        assert_eq!(
+            node.upgrade,
+            "nvm install 20 --reinstall-packages-from=20.11.0"
+        );
+
+        let java = jdks(&[root.join("jvm")], &root.join("sdkman"));
+        let names: Vec<_> = java.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["jdk-17-openjdk-amd64", "jdk-17.0.9-tem"]);
+
+        let tool = python.to_tool();
+        assert_eq!(tool.kind, ToolKind::Runtime);
+        assert_eq!(tool.category.as_deref(), Some(RUNTIME_CATEGORY));
+    }
+
+    #[test]
+    fn test_kube_contexts() {
+        let yaml = "current-context: prod\ncontexts:\n- name: dev\n  context: {cluster: dev}\n- name: prod\n  context: {cluster: prod}\n";
+        assert_eq!(kube_contexts(yaml), ["dev", "*prod"]);
+        assert!(kube_contexts("not: [yaml").is_empty());
+        assert_eq!(
+            kubectl(&kube_contexts(yaml)).description,
+            "Kubernetes CLI (contexts: dev, *prod)"
+        );
+    }
+}
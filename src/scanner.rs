@@ -1,5 +1,9 @@
 use anyhow::Result;
-use std::process::Command;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+use std::time::Duration;
 
 use crate::http::HTTP_AGENT;
 use crate::models::{InstallSource, Tool};
@@ -174,6 +178,7 @@ pub fn fetch_help_description(binary: &str) -> Option<String> {
 }
 
 /// Known tools to scan for, organized by category
+#[derive(Debug, Clone)]
 pub struct KnownTool {
     pub name: &'static str,
     pub binary: &'static str,
@@ -183,7 +188,42 @@ pub struct KnownTool {
     pub install_cmd: &'static str,
 }
 
+impl KnownTool {
+    /// Look up a known tool by name
+    pub fn find(name: &str) -> Option<&'static KnownTool> {
+        known_tools().iter().find(|kt| kt.name == name)
+    }
+
+    /// The tool to track, with the known metadata
+    pub fn to_tool(&self) -> Tool {
+        Tool::new(self.name)
+            .with_source(self.source.clone())
+            .with_description(self.description)
+            .with_category(self.category)
+            .with_install_command(self.install_cmd)
+            .with_binary(self.binary)
+    }
+}
+
 /// List of known CLI tools to scan for
+/// Built-in known tools plus the user catalog (`known_tools.d`)
+///
+/// Catalog tools replace built-in ones with the same name. Loaded once per
+/// run.
+pub fn known_tools() -> &'static [KnownTool] {
+    static CATALOG: LazyLock<Vec<KnownTool>> = LazyLock::new(|| {
+        let mut tools = crate::catalog::load_user_catalog();
+        let builtin: Vec<KnownTool> = KNOWN_TOOLS
+            .iter()
+            .filter(|kt| !tools.iter().any(|t| t.name == kt.name))
+            .cloned()
+            .collect();
+        tools.extend(builtin);
+        tools
+    });
+    &CATALOG
+}
+
 pub static KNOWN_TOOLS: &[KnownTool] = &[
     // Modern CLI replacements
     KnownTool {
@@ -550,6 +590,71 @@ pub static KNOWN_TOOLS: &[KnownTool] = &[
         source: InstallSource::Cargo,
         install_cmd: "cargo install fnm",
     },
+    // Language tooling
+    KnownTool {
+        name: "bacon",
+        binary: "bacon",
+        description: "Background Rust code checker",
+        category: "dev",
+        source: InstallSource::Cargo,
+        install_cmd: "cargo install bacon",
+    },
+    KnownTool {
+        name: "cargo-nextest",
+        binary: "cargo-nextest",
+        description: "Next-generation Rust test runner",
+        category: "dev",
+        source: InstallSource::Cargo,
+        install_cmd: "cargo install cargo-nextest",
+    },
+    KnownTool {
+        name: "cargo-watch",
+        binary: "cargo-watch",
+        description: "Run cargo commands on file changes",
+        category: "dev",
+        source: InstallSource::Cargo,
+        install_cmd: "cargo install cargo-watch",
+    },
+    KnownTool {
+        name: "uv",
+        binary: "uv",
+        description: "Fast Python package and project manager",
+        category: "lang",
+        source: InstallSource::Pip,
+        install_cmd: "pip install uv",
+    },
+    KnownTool {
+        name: "ruff",
+        binary: "ruff",
+        description: "Fast Python linter and formatter",
+        category: "dev",
+        source: InstallSource::Pip,
+        install_cmd: "pip install ruff",
+    },
+    KnownTool {
+        name: "ipython",
+        binary: "ipython",
+        description: "Enhanced interactive Python shell",
+        category: "dev",
+        source: InstallSource::Pip,
+        install_cmd: "pip install ipython",
+    },
+    KnownTool {
+        name: "shellcheck",
+        binary: "shellcheck",
+        description: "Static analysis for shell scripts",
+        category: "dev",
+        source: InstallSource::Apt,
+        install_cmd: "sudo apt install shellcheck",
+    },
+    KnownTool {
+        name: "protoc",
+        binary: "protoc",
+        description: "Protocol Buffers compiler",
+        category: "dev",
+        source: InstallSource::Apt,
+        install_cmd: "sudo apt install protobuf-compiler",
+    },
     // Container/K8s
     KnownTool {
         name: "docker",
@@ -591,6 +696,22 @@ pub static KNOWN_TOOLS: &[KnownTool] = &[
         source: InstallSource::Manual,
         install_cmd: "sudo snap install helm --classic",
     },
+    KnownTool {
+        name: "kubectx",
+        binary: "kubectx",
+        description: "Switch between Kubernetes contexts",
+        category: "container",
+        source: InstallSource::Apt,
+        install_cmd: "sudo apt install kubectx",
+    },
+    KnownTool {
+        name: "stern",
+        binary: "stern",
+        description: "Tail logs from multiple Kubernetes pods",
+        category: "container",
+        source: InstallSource::Manual,
+        install_cmd: "go install github.com/stern/stern@latest",
+    },
     // Git tools
     KnownTool {
         name: "gh",
@@ -633,43 +754,342 @@ pub static KNOWN_TOOLS: &[KnownTool] = &[
         source: InstallSource::Apt,
         install_cmd: "sudo apt install git-crypt",
     },
+    // Data wrangling
+    KnownTool {
+        name: "xsv",
+        binary: "xsv",
+        description: "Fast CSV toolkit",
+        category: "data",
+        source: InstallSource::Cargo,
+        install_cmd: "cargo install xsv",
+    },
+    KnownTool {
+        name: "miller",
+        binary: "mlr",
+        description: "Like awk and jq for CSV, TSV and JSON",
+        category: "data",
+        source: InstallSource::Apt,
+        install_cmd: "sudo apt install miller",
+    },
+    KnownTool {
+        name: "csvlens",
+        binary: "csvlens",
+        description: "CSV viewer for the terminal",
+        category: "data",
+        source: InstallSource::Cargo,
+        install_cmd: "cargo install csvlens",
+    },
+    // Dotfiles
+    KnownTool {
+        name: "stow",
+        binary: "stow",
+        description: "Symlink farm manager for dotfiles",
+        category: "dotfiles",
+        source: InstallSource::Apt,
+        install_cmd: "sudo apt install stow",
+    },
+    KnownTool {
+        name: "chezmoi",
+        binary: "chezmoi",
+        description: "Manage dotfiles across machines",
+        category: "dotfiles",
+        source: InstallSource::Manual,
+        install_cmd: "sudo snap install chezmoi --classic",
+    },
 ];
 
 /// Check if a binary is installed
+///
+/// A full path counts when it exists, since runtimes and shell plugins are
+/// tracked by their location rather than a binary on PATH. On Windows,
+/// programs registered under the registry's App Paths count too.
 pub fn is_installed(binary: &str) -> bool {
-    which::which(binary).is_ok()
+    let path = std::path::Path::new(binary);
+    if path.is_absolute() {
+        return path.exists();
+    }
+    which::which(binary).is_ok() || app_path(binary).is_some()
+}
+
+/// Extensions that make a file executable on Windows (%PATHEXT%)
+#[cfg(not(unix))]
+fn path_extensions() -> Vec<String> {
+    std::env::var("PATHEXT")
+        .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| ext.trim_start_matches('.').to_lowercase())
+        .collect()
+}
+
+/// Subdirectories of a directory (following symlinks), sorted by name
+pub fn subdirs(dir: &std::path::Path) -> Vec<(String, PathBuf)> {
+    let mut dirs: Vec<_> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| Some((e.file_name().into_string().ok()?, e.path())))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Name a file runs as if it's executable: its file name on Unix, its name
+/// without the extension (`rg.exe` -> `rg`) on Windows
+pub fn executable_name(path: &std::path::Path) -> Option<String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = path.metadata().ok()?;
+        if !metadata.is_file() || metadata.permissions().mode() & 0o111 == 0 {
+            return None;
+        }
+        path.file_name()?.to_str().map(str::to_string)
+    }
+    #[cfg(not(unix))]
+    {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if !path.is_file() || !path_extensions().contains(&extension) {
+            return None;
+        }
+        path.file_stem()?.to_str().map(str::to_string)
+    }
+}
+
+/// Programs registered under the Windows registry's App Paths, by lowercase
+/// name without extension
+static APP_PATHS: LazyLock<std::collections::HashMap<String, PathBuf>> = LazyLock::new(|| {
+    if !cfg!(windows) {
+        return Default::default();
+    }
+    const KEY: &str = r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths";
+    ["HKCU", "HKLM"]
+        .iter()
+        .filter_map(|hive| {
+            Command::new("reg")
+                .args(["query", &format!(r"{}\{}", hive, KEY), "/s", "/ve"])
+                .output()
+                .ok()
+        })
+        .flat_map(|output| parse_app_paths(&String::from_utf8_lossy(&output.stdout)))
+        .collect()
+});
+
+/// Parse `reg query "...\App Paths" /s /ve` output into (name, program)
+fn parse_app_paths(output: &str) -> Vec<(String, PathBuf)> {
+    let mut entries = Vec::new();
+    let mut name = None;
+    for line in output.lines() {
+        if line.starts_with("HKEY_") {
+            name = line
+                .rsplit('\\')
+                .next()
+                .and_then(|key| key.rsplit_once('.').map(|(stem, _)| stem.to_lowercase()));
+        } else if let Some(name) = &name
+            && let Some((_, value)) = line
+                .split_once("REG_EXPAND_SZ")
+                .or_else(|| line.split_once("REG_SZ"))
+        {
+            let value = value.trim().trim_matches('"');
+            if !value.is_empty() {
+                entries.push((name.clone(), PathBuf::from(value)));
+            }
+        }
+    }
+    entries
+}
+
+/// Program the Windows registry's App Paths registers for a binary
+pub fn app_path(binary: &str) -> Option<PathBuf> {
+    APP_PATHS.get(&binary.to_lowercase()).cloned()
+}
+
+/// How long [`binary_version`] waits for a binary to answer
+const VERSION_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Version a binary reports for `--version`, if it answers in time
+pub fn binary_version(binary: &str) -> Option<String> {
+    let output = crate::process::output_with_timeout(
+        Command::new(binary).arg("--version").stdin(Stdio::null()),
+        VERSION_TIMEOUT,
+    )
+    .ok()?;
+    let Some(output) = output else {
+        tracing::debug!("{} --version timed out", binary);
+        return None;
+    };
+    // Some tools print their version on stderr
+    let text = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    parse_version_output(&String::from_utf8_lossy(&text))
+}
+
+/// First version number in `--version` output ("ripgrep 14.1.0 (rev 1)",
+/// "jq-1.7.1", "v20.11.0")
+pub fn parse_version_output(output: &str) -> Option<String> {
+    output
+        .lines()
+        .take(3)
+        .flat_map(str::split_whitespace)
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphanumeric()))
+        .find_map(|word| {
+            let word = word.split_once('-').map_or(word, |(name, rest)| {
+                if name.starts_with(|c: char| c.is_ascii_digit()) {
+                    word
+                } else {
+                    rest
+                }
+            });
+            let word = word.strip_prefix('v').unwrap_or(word);
+            let end = word
+                .find(|c: char| !(c.is_ascii_alphanumeric() || ".-+".contains(c)))
+                .unwrap_or(word.len());
+            let word = &word[..end];
+            (word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'))
+                .then(|| word.to_string())
+        })
+}
+
+/// Every copy of a binary on PATH, in lookup order
+pub fn binary_paths(binary: &str) -> Vec<std::path::PathBuf> {
+    which::which_all(binary)
+        .map(|paths| paths.collect())
+        .unwrap_or_default()
+}
+
+/// Package manager that likely put a binary at `path`, from where it lives
+///
+/// Symlinks are followed, so an npm shim in `/usr/local/bin` still reads as
+/// npm. `~/.local/bin` is shared by pip, pipx and release installs and gives
+/// no answer.
+pub fn source_of_path(path: &std::path::Path) -> Option<InstallSource> {
+    let resolved = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    [path, resolved.as_path()].into_iter().find_map(|path| {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        if path.contains("/.cargo/bin/") {
+            Some(InstallSource::Cargo)
+        } else if path.contains("/node_modules/") || path.contains("/.npm-global/") {
+            Some(InstallSource::Npm)
+        } else if path.contains("/site-packages/") || path.contains("/pipx/venvs/") {
+            Some(InstallSource::Pip)
+        } else if path.starts_with("/snap/") {
+            Some(InstallSource::Snap)
+        } else if path.contains("/flatpak/exports/bin/") {
+            Some(InstallSource::Flatpak)
+        } else if path.contains("/Cellar/")
+            || path.contains("/linuxbrew/")
+            || path.starts_with("/opt/homebrew/")
+        {
+            Some(InstallSource::Brew)
+        } else if cfg!(target_os = "linux")
+            && matches!(dir, "/usr/bin" | "/bin" | "/usr/sbin" | "/sbin")
+        {
+            Some(InstallSource::Apt)
+        } else {
+            None
+        }
+    })
+}
+
+/// One copy of a binary on PATH
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BinaryCopy {
+    pub path: PathBuf,
+    /// Package manager that likely installed it
+    pub source: Option<InstallSource>,
+}
+
+/// A binary name found as different files in several PATH directories
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowedBinary {
+    pub binary: String,
+    /// Copies in PATH order: the first one runs, the rest are shadowed
+    pub copies: Vec<BinaryCopy>,
+}
+
+impl ShadowedBinary {
+    /// The copy that runs
+    pub fn winner(&self) -> &BinaryCopy {
+        &self.copies[0]
+    }
+
+    /// The copies that never run by name
+    pub fn shadowed(&self) -> &[BinaryCopy] {
+        &self.copies[1..]
+    }
+}
+
+/// Binaries present as different files in more than one of `dirs`
+///
+/// Links to the same file (`/bin` -> `/usr/bin`, shims) count once, so only
+/// real duplicates are reported.
+pub fn find_shadowed_binaries(dirs: &[PathBuf]) -> Vec<ShadowedBinary> {
+    use std::collections::{BTreeMap, HashSet};
+
+    let mut copies: BTreeMap<String, Vec<BinaryCopy>> = BTreeMap::new();
+    let mut targets: HashSet<(String, PathBuf)> = HashSet::new();
+    let mut seen_dirs: HashSet<PathBuf> = HashSet::new();
+
+    for dir in dirs {
+        let canonical_dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.clone());
+        if !seen_dirs.insert(canonical_dir) {
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        paths.sort();
+        for path in paths {
+            let Some(name) = executable_name(&path) else {
+                continue;
+            };
+            let target = std::fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if !targets.insert((name.clone(), target)) {
+                continue;
+            }
+            copies.entry(name).or_default().push(BinaryCopy {
+                source: source_of_path(&path),
+                path,
+            });
+        }
+    }
+
+    copies
+        .into_iter()
+        .filter(|(_, copies)| copies.len() > 1)
+        .map(|(binary, copies)| ShadowedBinary { binary, copies })
+        .collect()
+}
+
+/// Binaries shadowed by another copy earlier on `$PATH`
+pub fn scan_shadowed_binaries() -> Vec<ShadowedBinary> {
+    let dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    find_shadowed_binaries(&dirs)
 }
 
 /// Scan system for known tools and return found ones
 pub fn scan_known_tools() -> Vec<Tool> {
-    KNOWN_TOOLS
+    known_tools()
         .iter()
         .filter(|kt| is_installed(kt.binary))
-        .map(|kt| {
-            Tool::new(kt.name)
-                .with_source(kt.source.clone())
-                .with_description(kt.description)
-                .with_category(kt.category)
-                .with_install_command(kt.install_cmd)
-                .with_binary(kt.binary)
-                .installed()
-        })
+        .map(|kt| kt.to_tool().installed())
         .collect()
 }
 
 /// Scan system for known tools and return NOT installed ones (suggestions)
 pub fn scan_missing_tools() -> Vec<Tool> {
-    KNOWN_TOOLS
+    known_tools()
         .iter()
         .filter(|kt| !is_installed(kt.binary))
-        .map(|kt| {
-            Tool::new(kt.name)
-                .with_source(kt.source.clone())
-                .with_description(kt.description)
-                .with_category(kt.category)
-                .with_install_command(kt.install_cmd)
-                .with_binary(kt.binary)
-        })
+        .map(KnownTool::to_tool)
         .collect()
 }
 
@@ -694,8 +1114,8 @@ pub fn scan_cargo_tools() -> Result<Vec<Tool>> {
             // Binary line: "    rg"
             let binary = line.trim();
             if !binary.is_empty() && is_installed(binary) {
-                // Skip if already in KNOWN_TOOLS (we have better metadata there)
-                let dominated = KNOWN_TOOLS
+                // Skip if already a known tool (we have better metadata there)
+                let dominated = known_tools()
                     .iter()
                     .any(|kt| kt.name == crate_name || kt.binary == binary);
                 if !dominated {
@@ -746,8 +1166,8 @@ pub fn scan_pip_tools() -> Result<Vec<Tool>> {
             None => continue,
         };
 
-        // Skip if already in KNOWN_TOOLS
-        if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+        // Skip if already a known tool
+        if known_tools().iter().any(|kt| kt.name == package) {
             continue;
         }
 
@@ -801,8 +1221,8 @@ pub fn scan_npm_tools() -> Result<Vec<Tool>> {
                 continue;
             }
 
-            // Skip if already in KNOWN_TOOLS
-            if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+            // Skip if already a known tool
+            if known_tools().iter().any(|kt| kt.name == package) {
                 continue;
             }
 
@@ -849,8 +1269,8 @@ pub fn scan_brew_tools() -> Result<Vec<Tool>> {
             continue;
         }
 
-        // Skip if already in KNOWN_TOOLS
-        if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+        // Skip if already a known tool
+        if known_tools().iter().any(|kt| kt.name == package) {
             continue;
         }
 
@@ -872,15 +1292,59 @@ pub fn scan_brew_tools() -> Result<Vec<Tool>> {
     Ok(tools)
 }
 
-/// Directories to scan in PATH for unknown binaries
+/// Directories to scan in PATH for unknown binaries (`~` is the home
+/// directory, `*` any one entry)
 const PATH_SCAN_DIRS: &[&str] = &[
     "/usr/local/bin",
-    "/home/*/go/bin",
-    "/home/*/.local/bin",
-    "/home/*/.cargo/bin",
+    "~/go/bin",
+    "~/.local/bin",
+    "~/.cargo/bin",
     "/opt/*/bin",
 ];
 
+/// Directories to scan for unknown binaries: [`PATH_SCAN_DIRS`] on Unix; on
+/// Windows the %PATH% entries outside the Windows directory, plus the cargo
+/// and go bin directories
+fn path_scan_dirs() -> Vec<PathBuf> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let mut dirs = Vec::new();
+
+    if cfg!(windows) {
+        let system_root = std::env::var_os("SystemRoot")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(r"C:\Windows"));
+        dirs.extend(
+            std::env::var_os("PATH")
+                .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|dir| !dir.starts_with(&system_root)),
+        );
+        dirs.extend([home.join(".cargo/bin"), home.join("go/bin")]);
+    } else {
+        for pattern in PATH_SCAN_DIRS {
+            let pattern = match pattern.strip_prefix("~/") {
+                Some(rest) => home.join(rest),
+                None => PathBuf::from(pattern),
+            };
+            match pattern.to_str().and_then(|p| p.split_once("/*/")) {
+                Some((parent, rest)) => dirs.extend(
+                    std::fs::read_dir(parent)
+                        .into_iter()
+                        .flatten()
+                        .flatten()
+                        .map(|e| e.path().join(rest)),
+                ),
+                None => dirs.push(pattern),
+            }
+        }
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    dirs.retain(|dir| dir.is_dir() && seen.insert(dir.clone()));
+    dirs
+}
+
 /// Binaries to skip (system utilities, not interesting to track)
 const PATH_SKIP_BINARIES: &[&str] = &[
     ".",
@@ -903,49 +1367,19 @@ const PATH_SKIP_BINARIES: &[&str] = &[
 
 /// Scan PATH directories for binaries not tracked by other package managers
 pub fn scan_path_tools(tracked_binaries: &std::collections::HashSet<String>) -> Result<Vec<Tool>> {
-    use std::os::unix::fs::PermissionsExt;
-
-    let home = std::env::var("HOME").unwrap_or_default();
     let mut tools = Vec::new();
     let mut seen = std::collections::HashSet::new();
 
-    for pattern in PATH_SCAN_DIRS {
-        let expanded = pattern.replace('*', home.trim_start_matches("/home/"));
-        let expanded = if pattern.contains("/home/*") {
-            pattern.replace("/home/*", &home)
-        } else {
-            expanded
-        };
-
-        let dir = std::path::Path::new(&expanded);
-        if !dir.is_dir() {
-            continue;
-        }
-
-        let entries = match std::fs::read_dir(dir) {
+    for dir in path_scan_dirs() {
+        let entries = match std::fs::read_dir(&dir) {
             Ok(e) => e,
             Err(_) => continue,
         };
 
         for entry in entries.flatten() {
-            let path = entry.path();
-
-            // Must be a file
-            if !path.is_file() {
-                continue;
-            }
-
-            // Must be executable
-            let metadata = match path.metadata() {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-            if metadata.permissions().mode() & 0o111 == 0 {
-                continue;
-            }
-
-            let name = match path.file_name().and_then(|n| n.to_str()) {
-                Some(n) => n.to_string(),
+            // Must be an executable file
+            let name = match executable_name(&entry.path()) {
+                Some(n) => n,
                 None => continue,
             };
 
@@ -956,7 +1390,7 @@ pub fn scan_path_tools(tracked_binaries: &std::collections::HashSet<String>) ->
             if PATH_SKIP_BINARIES.contains(&name.as_str()) {
                 continue;
             }
-            if KNOWN_TOOLS
+            if known_tools()
                 .iter()
                 .any(|kt| kt.binary == name || kt.name == name)
             {
@@ -964,19 +1398,13 @@ pub fn scan_path_tools(tracked_binaries: &std::collections::HashSet<String>) ->
             }
 
             // Determine source hint from path
-            let source = if expanded.contains("/go/bin") {
-                InstallSource::Manual // Go binary
-            } else if expanded.contains("/.cargo/bin") {
+            let source = if dir.ends_with(".cargo/bin") {
                 InstallSource::Cargo
             } else {
                 InstallSource::Manual
             };
 
-            let category = if expanded.contains("/go/bin") {
-                "go"
-            } else {
-                "cli"
-            };
+            let category = if dir.ends_with("go/bin") { "go" } else { "cli" };
 
             seen.insert(name.clone());
             tools.push(
@@ -1114,8 +1542,8 @@ pub fn scan_apt_tools() -> Result<Vec<Tool>> {
             continue;
         }
 
-        // Skip if already in KNOWN_TOOLS
-        if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+        // Skip if already a known tool
+        if known_tools().iter().any(|kt| kt.name == package) {
             continue;
         }
 
@@ -1179,6 +1607,95 @@ mod tests {
         assert!(!is_installed("definitely_not_a_real_binary_12345"));
     }
 
+    #[test]
+    fn test_source_of_path() {
+        use std::path::Path;
+
+        let of = |path: &str| source_of_path(Path::new(path));
+        assert_eq!(of("/home/me/.cargo/bin/rg"), Some(InstallSource::Cargo));
+        assert_eq!(of("/snap/bin/nvim"), Some(InstallSource::Snap));
+        assert_eq!(of("/opt/homebrew/bin/jq"), Some(InstallSource::Brew));
+        assert_eq!(
+            of("/usr/lib/node_modules/prettier/bin/prettier.cjs"),
+            Some(InstallSource::Npm)
+        );
+        assert_eq!(of("/home/me/.local/bin/definitely-not-here"), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_find_shadowed_binaries() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = tempfile::tempdir().unwrap();
+        let dirs: Vec<PathBuf> = ["a", "b", "c"]
+            .iter()
+            .map(|d| root.path().join(d))
+            .collect();
+        for dir in &dirs {
+            std::fs::create_dir(dir).unwrap();
+        }
+        let exe = |path: &PathBuf| {
+            std::fs::write(path, "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        exe(&dirs[0].join("rg"));
+        exe(&dirs[1].join("rg"));
+        exe(&dirs[0].join("fd"));
+        std::os::unix::fs::symlink(dirs[0].join("fd"), dirs[1].join("fd")).unwrap();
+        std::fs::write(dirs[1].join("notes"), "").unwrap();
+        std::fs::write(dirs[0].join("notes"), "").unwrap();
+        // A linked directory is the same directory
+        std::os::unix::fs::symlink(&dirs[1], root.path().join("c2")).unwrap();
+
+        let mut search = dirs.clone();
+        search.push(root.path().join("c2"));
+        let shadowed = find_shadowed_binaries(&search);
+        assert_eq!(shadowed.len(), 1);
+        assert_eq!(shadowed[0].binary, "rg");
+        assert_eq!(shadowed[0].winner().path, dirs[0].join("rg"));
+        assert_eq!(shadowed[0].shadowed().len(), 1);
+        assert_eq!(shadowed[0].shadowed()[0].path, dirs[1].join("rg"));
+    }
+
+    #[test]
+    fn test_parse_app_paths() {
+        let output = "\r
+HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\App Paths\\Code.exe\r
+    (Default)    REG_SZ    \"C:\\Program Files\\Microsoft VS Code\\Code.exe\"\r
+\r
+HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\App Paths\\wt.exe\r
+    (Default)    REG_EXPAND_SZ    %LOCALAPPDATA%\\Microsoft\\WindowsApps\\wt.exe\r
+";
+        let entries = parse_app_paths(output);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "code");
+        assert_eq!(
+            entries[0].1,
+            PathBuf::from(r"C:\Program Files\Microsoft VS Code\Code.exe")
+        );
+        assert_eq!(entries[1].0, "wt");
+    }
+
+    #[test]
+    fn test_parse_version_output() {
+        let v = |output: &str| parse_version_output(output);
+        assert_eq!(
+            v("ripgrep 14.1.0 (rev e50df40a19)").as_deref(),
+            Some("14.1.0")
+        );
+        assert_eq!(v("jq-1.7.1\n").as_deref(), Some("1.7.1"));
+        assert_eq!(v("v20.11.0").as_deref(), Some("20.11.0"));
+        assert_eq!(v("git version 2.43.0").as_deref(), Some("2.43.0"));
+        assert_eq!(v("fd 10.2.0-beta.1").as_deref(), Some("10.2.0-beta.1"));
+        assert_eq!(
+            v("bash, version 5.2.21(1)-release (x86_64-pc-linux-gnu)").as_deref(),
+            Some("5.2.21")
+        );
+        assert_eq!(v("usage: tool [options]"), None);
+        assert!(binary_version("definitely_not_a_real_binary_12345").is_none());
+    }
+
     #[test]
     fn test_scan_known_tools() {
         let tools = scan_known_tools();
@@ -0,0 +1,266 @@
+//! Minimal HTTP/1.1 for `hoards serve`
+//!
+//! Just enough of the protocol for a local JSON API: one request per
+//! connection, a `Content-Length` body and no chunked encoding, keep-alive
+//! or TLS. Put a reverse proxy in front to expose it beyond the machine.
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, Read, Write};
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Longest request line or header accepted
+const MAX_LINE_BYTES: u64 = 8 * 1024;
+
+/// Most headers accepted in one request
+const MAX_HEADERS: usize = 64;
+
+/// A parsed request
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub method: String,
+    /// Decoded path, without the query string
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// Header names are lowercased
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Path split on `/`, empty segments dropped
+    pub fn segments(&self) -> Vec<&str> {
+        self.path.split('/').filter(|s| !s.is_empty()).collect()
+    }
+
+    /// The token from an `Authorization: Bearer <token>` header
+    pub fn bearer_token(&self) -> Option<&str> {
+        let value = self.headers.get("authorization")?;
+        let (scheme, token) = value.split_once(' ')?;
+        scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+    }
+
+    /// A query parameter as a flag (`1`, `true` or `yes`)
+    pub fn flag(&self, name: &str) -> bool {
+        self.query
+            .get(name)
+            .is_some_and(|v| matches!(v.as_str(), "1" | "true" | "yes"))
+    }
+}
+
+/// A response with a JSON body
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: serde_json::Value,
+}
+
+impl Response {
+    pub fn json<T: Serialize>(status: u16, body: &T) -> Self {
+        Self {
+            status,
+            body: serde_json::to_value(body).unwrap_or(serde_json::Value::Null),
+        }
+    }
+
+    pub fn ok<T: Serialize>(body: &T) -> Self {
+        Self::json(200, body)
+    }
+
+    /// An `{"error": ...}` body
+    pub fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: serde_json::json!({ "error": message.into() }),
+        }
+    }
+
+    /// Write the response, closing the connection afterwards
+    pub fn write_to(&self, out: &mut impl Write) -> Result<()> {
+        let body = serde_json::to_string_pretty(&self.body)?;
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            body.len(),
+            body
+        )?;
+        out.flush()?;
+        Ok(())
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Decode `%XX` escapes (and `+` as a space, as in query strings)
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if bytes[i + 1..].len() >= 2
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit() =>
+            {
+                let hex = |b: u8| (b as char).to_digit(16).unwrap_or(0) as u8;
+                out.push(hex(bytes[i + 1]) << 4 | hex(bytes[i + 2]));
+                i += 3;
+                continue;
+            }
+            b'+' => out.push(b' '),
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn read_line(reader: &mut impl BufRead) -> Result<String> {
+    let mut line = String::new();
+    reader.take(MAX_LINE_BYTES).read_line(&mut line)?;
+    if !line.ends_with('\n') {
+        bail!("Request line or header too long, or connection closed");
+    }
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Read one request from a connection
+pub fn read_request(reader: &mut impl BufRead) -> Result<Request> {
+    let request_line = read_line(reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        bail!("Malformed request line: {}", request_line);
+    };
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect();
+
+    let mut headers = HashMap::new();
+    for count in 0.. {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            bail!("More than {} headers", MAX_HEADERS);
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length: usize = match headers.get("content-length") {
+        Some(value) => value.parse().context("Invalid Content-Length")?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        bail!("Request body over {} bytes", MAX_BODY_BYTES);
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method: method.to_ascii_uppercase(),
+        // `+` only means a space in query strings
+        path: percent_decode(&path.replace('+', "%2B")),
+        query,
+        headers,
+        body,
+    })
+}
+
+/// Compare tokens in time independent of where they differ
+pub fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// A random 32-character hex token
+pub fn generate_token() -> Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|e| {
+        anyhow::anyhow!("No random source to generate a token ({}); pass --token", e)
+    })?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /api/tools/search%20me?q=ripgrep+fast&installed=1 HTTP/1.1\r\n\
+                   Host: localhost\r\n\
+                   Authorization: Bearer abc123\r\n\
+                   Content-Length: 13\r\n\
+                   \r\n\
+                   {\"name\":\"x\"}\n";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/tools/search me");
+        assert_eq!(request.segments(), vec!["api", "tools", "search me"]);
+        assert_eq!(request.query["q"], "ripgrep fast");
+        assert!(request.flag("installed"));
+        assert!(!request.flag("missing"));
+        assert_eq!(request.bearer_token(), Some("abc123"));
+        assert_eq!(request.body, b"{\"name\":\"x\"}\n");
+
+        assert!(read_request(&mut "GET".as_bytes()).is_err());
+        let oversized = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert!(read_request(&mut oversized.as_bytes()).is_err());
+        let crowded: String = (0..=MAX_HEADERS)
+            .map(|i| format!("X-Header-{}: {}\r\n", i, i))
+            .collect();
+        let crowded = format!("GET / HTTP/1.1\r\n{}\r\n", crowded);
+        assert!(read_request(&mut crowded.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_tokens_and_responses() {
+        assert_eq!(percent_decode("a%2Fb%zz%"), "a/b%zz%");
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secret", "secreT"));
+        assert!(!tokens_match("secret", "secrets"));
+        assert_eq!(generate_token().unwrap().len(), 32);
+
+        let mut out = Vec::new();
+        Response::error(404, "Tool 'x' not found")
+            .write_to(&mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(out.ends_with("{\n  \"error\": \"Tool 'x' not found\"\n}"));
+    }
+}
@@ -0,0 +1,198 @@
+//! systemd user units and launchd agents
+//!
+//! Scans record the program each user service runs and the tracked tool it
+//! belongs to, so `uninstall` can warn about tools running as services and
+//! `doctor` can flag services whose program is gone.
+
+use std::path::{Path, PathBuf};
+
+use crate::db::UserService;
+use crate::models::Tool;
+
+/// Directories holding systemd user units written by the user or admin
+/// (distribution units under /usr are left out)
+fn systemd_unit_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = [dirs::config_dir(), dirs::data_dir()]
+        .into_iter()
+        .flatten()
+        .map(|d| d.join("systemd/user"))
+        .collect();
+    dirs.push(PathBuf::from("/etc/systemd/user"));
+    dirs
+}
+
+/// Directories holding launchd agents
+fn launchd_agent_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = dirs::home_dir()
+        .map(|h| h.join("Library/LaunchAgents"))
+        .into_iter()
+        .collect();
+    dirs.push(PathBuf::from("/Library/LaunchAgents"));
+    dirs
+}
+
+/// Every user service on this machine, tied to the tracked tools they run
+pub fn scan_user_services(tools: &[Tool]) -> Vec<UserService> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let mut services = Vec::new();
+
+    for (manager, dirs, extension) in [
+        ("systemd", systemd_unit_dirs(), "service"),
+        ("launchd", launchd_agent_dirs(), "plist"),
+    ] {
+        for dir in dirs {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.extension().is_some_and(|e| e == extension))
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                let Ok(content) = std::fs::read_to_string(&path) else {
+                    continue;
+                };
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+                let (name, binary) = match manager {
+                    "systemd" => (file_name.to_string(), systemd_program(&content, &home)),
+                    _ => (
+                        plist_string(&content, "Label")
+                            .map(str::to_string)
+                            .unwrap_or_else(|| file_name.trim_end_matches(".plist").to_string()),
+                        launchd_program(&content),
+                    ),
+                };
+                // A unit in a later directory is overridden by an earlier one
+                if let Some(binary) = binary
+                    && !services
+                        .iter()
+                        .any(|s: &UserService| s.manager == manager && s.name == name)
+                {
+                    services.push(UserService {
+                        tool: tool_for(&binary, tools),
+                        name,
+                        manager: manager.to_string(),
+                        path: path.display().to_string(),
+                        binary,
+                    });
+                }
+            }
+        }
+    }
+
+    services
+}
+
+/// Program a systemd unit's `ExecStart=` runs, with `%h` expanded
+fn systemd_program(unit: &str, home: &Path) -> Option<String> {
+    let exec = unit
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("ExecStart="))
+        .find(|exec| !exec.trim().is_empty())?;
+    // Prefixes such as "-" (ignore failure) and "@" (argv[0]) come first
+    let exec = exec.trim().trim_start_matches(['-', '@', ':', '+', '!']);
+    let exec = exec.replace("%h", &home.display().to_string());
+    program_of(exec.split_whitespace().map(|w| w.trim_matches('"')))
+}
+
+/// Program a launchd agent runs (`Program`, else the first of
+/// `ProgramArguments`)
+fn launchd_program(plist: &str) -> Option<String> {
+    plist_string(plist, "Program")
+        .map(str::to_string)
+        .or_else(|| program_of(plist_strings(plist, "ProgramArguments").into_iter()))
+}
+
+/// First string after a key in an XML plist
+fn plist_string<'a>(plist: &'a str, key: &str) -> Option<&'a str> {
+    plist_strings(plist, key).into_iter().next()
+}
+
+/// Strings after a key in an XML plist, up to the next key
+fn plist_strings<'a>(plist: &'a str, key: &str) -> Vec<&'a str> {
+    let Some(start) = plist.find(&format!("<key>{}</key>", key)) else {
+        return Vec::new();
+    };
+    let rest = &plist[start + key.len() + 11..];
+    let rest = &rest[..rest.find("<key>").unwrap_or(rest.len())];
+    rest.split("<string>")
+        .skip(1)
+        .filter_map(|s| s.split_once("</string>"))
+        .map(|(value, _)| value.trim())
+        .collect()
+}
+
+/// The program of a command line, looking through `env [VAR=value]...`
+fn program_of<'a>(mut args: impl Iterator<Item = &'a str>) -> Option<String> {
+    let program = args.next()?;
+    if Path::new(program).file_name().is_some_and(|f| f == "env") {
+        return args
+            .find(|a| !a.starts_with('-') && !a.contains('='))
+            .map(str::to_string);
+    }
+    Some(program.to_string())
+}
+
+/// Tracked tool whose binary a program is
+fn tool_for(program: &str, tools: &[Tool]) -> Option<String> {
+    let file_name = Path::new(program).file_name()?.to_str()?;
+    tools
+        .iter()
+        .find(|t| {
+            let binary = t.binary_name.as_deref().unwrap_or(&t.name);
+            binary == program || binary == file_name
+        })
+        .map(|t| t.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_systemd_program() {
+        let home = Path::new("/home/me");
+        let unit =
+            "[Unit]\nDescription=Sync\n\n[Service]\nExecStart=-%h/.cargo/bin/syncthing serve\n";
+        assert_eq!(
+            systemd_program(unit, home).as_deref(),
+            Some("/home/me/.cargo/bin/syncthing")
+        );
+        let unit = "[Service]\nExecStart=/usr/bin/env RUST_LOG=info atuin daemon\n";
+        assert_eq!(systemd_program(unit, home).as_deref(), Some("atuin"));
+        assert_eq!(systemd_program("[Service]\nType=oneshot\n", home), None);
+    }
+
+    #[test]
+    fn test_launchd_program() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>com.example.atuin</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>/opt/homebrew/bin/atuin</string>
+        <string>daemon</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>"#;
+        assert_eq!(plist_string(plist, "Label"), Some("com.example.atuin"));
+        assert_eq!(
+            launchd_program(plist).as_deref(),
+            Some("/opt/homebrew/bin/atuin")
+        );
+
+        let tools = vec![Tool::new("atuin").with_binary("atuin")];
+        assert_eq!(
+            tool_for("/opt/homebrew/bin/atuin", &tools).as_deref(),
+            Some("atuin")
+        );
+        assert_eq!(tool_for("/usr/bin/other", &tools), None);
+    }
+}
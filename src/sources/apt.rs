@@ -2,8 +2,9 @@
 
 use super::PackageSource;
 use crate::models::{InstallSource, Tool};
-use crate::scanner::{KNOWN_TOOLS, is_installed};
+use crate::scanner::{is_installed, known_tools};
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub struct AptSource;
@@ -113,9 +114,13 @@ impl PackageSource for AptSource {
     }
 
     fn scan(&self) -> Result<Vec<Tool>> {
-        // Get list of installed packages with their sections
+        // Get list of installed packages with their sections and versions
         let output = Command::new("dpkg-query")
-            .args(["-W", "-f", "${Package}\t${Section}\t${binary:Summary}\n"])
+            .args([
+                "-W",
+                "-f",
+                "${Package}\t${Section}\t${Version}\t${binary:Summary}\n",
+            ])
             .output()?;
 
         if !output.status.success() {
@@ -126,14 +131,15 @@ impl PackageSource for AptSource {
         let mut tools = Vec::new();
 
         for line in stdout.lines() {
-            let parts: Vec<&str> = line.splitn(3, '\t').collect();
+            let parts: Vec<&str> = line.splitn(4, '\t').collect();
             if parts.len() < 2 {
                 continue;
             }
 
             let package = parts[0];
             let section = parts.get(1).unwrap_or(&"");
-            let description = parts.get(2).map(|s| s.to_string());
+            let version = parts.get(2).filter(|v| !v.is_empty());
+            let description = parts.get(3).map(|s| s.to_string());
 
             // Skip GUI sections
             if GUI_SECTIONS.iter().any(|s| section.contains(s)) {
@@ -156,8 +162,8 @@ impl PackageSource for AptSource {
                 continue;
             }
 
-            // Skip if already in KNOWN_TOOLS
-            if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+            // Skip if already a known tool
+            if known_tools().iter().any(|kt| kt.name == package) {
                 continue;
             }
 
@@ -185,6 +191,9 @@ impl PackageSource for AptSource {
             {
                 tool = tool.with_description(desc);
             }
+            if let Some(version) = version {
+                tool = tool.with_installed_version(*version);
+            }
 
             tools.push(tool);
         }
@@ -192,6 +201,10 @@ impl PackageSource for AptSource {
         Ok(tools)
     }
 
+    fn state_paths(&self) -> Vec<PathBuf> {
+        vec![PathBuf::from("/var/lib/dpkg/status")]
+    }
+
     fn fetch_description(&self, package: &str) -> Option<String> {
         // Use dpkg-query for local description (no remote API)
         let output = Command::new("dpkg-query")
@@ -207,6 +220,13 @@ impl PackageSource for AptSource {
         if desc.is_empty() { None } else { Some(desc) }
     }
 
+    fn registry_url(&self, package: &str) -> Option<String> {
+        Some(format!(
+            "https://packages.debian.org/search?keywords={}",
+            package
+        ))
+    }
+
     fn install_command(&self, package: &str) -> String {
         format!("sudo apt install {}", package)
     }
@@ -3,8 +3,9 @@
 use super::PackageSource;
 use crate::http::HTTP_AGENT;
 use crate::models::{InstallSource, Tool};
-use crate::scanner::{KNOWN_TOOLS, is_installed};
+use crate::scanner::{is_installed, known_tools};
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub struct BrewSource;
@@ -20,7 +21,7 @@ impl PackageSource for BrewSource {
 
     fn scan(&self) -> Result<Vec<Tool>> {
         let output = Command::new("brew")
-            .args(["list", "--formula", "-1"])
+            .args(["list", "--formula", "--versions"])
             .output()?;
 
         if !output.status.success() {
@@ -30,14 +31,16 @@ impl PackageSource for BrewSource {
         let stdout = String::from_utf8_lossy(&output.stdout);
         let mut tools = Vec::new();
 
-        for package in stdout.lines() {
-            let package = package.trim();
-            if package.is_empty() {
+        for line in stdout.lines() {
+            // Format: "package version [older versions...]"
+            let mut words = line.split_whitespace();
+            let Some(package) = words.next() else {
                 continue;
-            }
+            };
+            let version = words.next_back();
 
-            // Skip if already in KNOWN_TOOLS
-            if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+            // Skip if already a known tool
+            if known_tools().iter().any(|kt| kt.name == package) {
                 continue;
             }
 
@@ -46,12 +49,13 @@ impl PackageSource for BrewSource {
                 continue;
             }
 
-            let tool = Tool::new(package)
+            let mut tool = Tool::new(package)
                 .with_source(InstallSource::Brew)
                 .with_binary(package)
                 .with_category("cli")
                 .with_install_command(self.install_command(package))
                 .installed();
+            tool.installed_version = version.map(str::to_string);
             // Description fetched in parallel by cmd_scan
 
             tools.push(tool);
@@ -60,6 +64,22 @@ impl PackageSource for BrewSource {
         Ok(tools)
     }
 
+    fn state_paths(&self) -> Vec<PathBuf> {
+        let mut cellars: Vec<PathBuf> = std::env::var_os("HOMEBREW_CELLAR")
+            .map(PathBuf::from)
+            .into_iter()
+            .collect();
+        cellars.extend(
+            [
+                "/opt/homebrew/Cellar",
+                "/usr/local/Cellar",
+                "/home/linuxbrew/.linuxbrew/Cellar",
+            ]
+            .map(PathBuf::from),
+        );
+        cellars
+    }
+
     fn fetch_description(&self, package: &str) -> Option<String> {
         let url = format!("https://formulae.brew.sh/api/formula/{}.json", package);
         let mut response = HTTP_AGENT.get(&url).call().ok()?;
@@ -71,6 +91,21 @@ impl PackageSource for BrewSource {
             .map(|s| s.to_string())
     }
 
+    fn fetch_license(&self, package: &str) -> Option<String> {
+        let url = format!("https://formulae.brew.sh/api/formula/{}.json", package);
+        let mut response = HTTP_AGENT.get(&url).call().ok()?;
+        let json: serde_json::Value = response.body_mut().read_json().ok()?;
+
+        json.get("license")?
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    fn registry_url(&self, package: &str) -> Option<String> {
+        Some(format!("https://formulae.brew.sh/formula/{}", package))
+    }
+
     fn install_command(&self, package: &str) -> String {
         format!("brew install {}", package)
     }
@@ -3,8 +3,9 @@
 use super::PackageSource;
 use crate::http::HTTP_AGENT;
 use crate::models::{InstallSource, Tool};
-use crate::scanner::{KNOWN_TOOLS, is_installed};
+use crate::scanner::{is_installed, known_tools};
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub struct CargoSource;
@@ -27,27 +28,34 @@ impl PackageSource for CargoSource {
 
         let stdout = String::from_utf8_lossy(&output.stdout);
         let mut tools = Vec::new();
-        let mut current_crate: Option<String> = None;
+        let mut current_crate: Option<(String, Option<String>)> = None;
 
         for line in stdout.lines() {
             if !line.starts_with(' ') {
                 // Crate name line: "ripgrep v14.1.0:"
-                current_crate = line.split_whitespace().next().map(|s| s.to_string());
-            } else if let Some(ref crate_name) = current_crate {
+                let mut words = line.split_whitespace();
+                current_crate = words.next().map(|name| {
+                    let version = words
+                        .next()
+                        .map(|v| v.trim_start_matches('v').trim_end_matches(':').to_string());
+                    (name.to_string(), version)
+                });
+            } else if let Some((ref crate_name, ref version)) = current_crate {
                 // Binary line: "    rg"
                 let binary = line.trim();
                 if !binary.is_empty() && is_installed(binary) {
-                    // Skip if already in KNOWN_TOOLS (we have better metadata there)
-                    let dominated = KNOWN_TOOLS
+                    // Skip if already a known tool (we have better metadata there)
+                    let dominated = known_tools()
                         .iter()
                         .any(|kt| kt.name == crate_name || kt.binary == binary);
                     if !dominated {
-                        let tool = Tool::new(crate_name)
+                        let mut tool = Tool::new(crate_name)
                             .with_source(InstallSource::Cargo)
                             .with_binary(binary)
                             .with_category("cli")
                             .with_install_command(self.install_command(crate_name))
                             .installed();
+                        tool.installed_version = version.clone();
                         // Description fetched in parallel by cmd_scan
 
                         tools.push(tool);
@@ -59,6 +67,14 @@ impl PackageSource for CargoSource {
         Ok(tools)
     }
 
+    fn state_paths(&self) -> Vec<PathBuf> {
+        let home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".cargo")));
+        home.map(|h| vec![h.join(".crates.toml"), h.join(".crates2.json")])
+            .unwrap_or_default()
+    }
+
     fn fetch_description(&self, package: &str) -> Option<String> {
         let url = format!("https://crates.io/api/v1/crates/{}", package);
         let mut response = HTTP_AGENT.get(&url).call().ok()?;
@@ -71,6 +87,24 @@ impl PackageSource for CargoSource {
             .map(|s| s.to_string())
     }
 
+    fn fetch_license(&self, package: &str) -> Option<String> {
+        let url = format!("https://crates.io/api/v1/crates/{}", package);
+        let mut response = HTTP_AGENT.get(&url).call().ok()?;
+        let json: serde_json::Value = response.body_mut().read_json().ok()?;
+
+        // Newest version first
+        json.get("versions")?
+            .get(0)?
+            .get("license")?
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    fn registry_url(&self, package: &str) -> Option<String> {
+        Some(format!("https://crates.io/crates/{}", package))
+    }
+
     fn install_command(&self, package: &str) -> String {
         format!("cargo install {}", package)
     }
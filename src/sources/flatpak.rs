@@ -4,6 +4,7 @@ use super::PackageSource;
 use crate::models::{InstallSource, Tool};
 use crate::scanner::is_installed;
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub struct FlatpakSource;
@@ -79,10 +80,7 @@ impl PackageSource for FlatpakSource {
                 .with_install_command(self.install_command(app_id))
                 .installed();
 
-            // Add version to notes if available
-            if let Some(ver) = version {
-                tool.notes = Some(format!("Version: {}", ver));
-            }
+            tool.installed_version = version.map(str::to_string);
             // Description fetched in parallel by cmd_scan
 
             tools.push(tool);
@@ -91,6 +89,12 @@ impl PackageSource for FlatpakSource {
         Ok(tools)
     }
 
+    fn state_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![PathBuf::from("/var/lib/flatpak/app")];
+        paths.extend(dirs::data_dir().map(|d| d.join("flatpak/app")));
+        paths
+    }
+
     fn fetch_description(&self, package: &str) -> Option<String> {
         // Use flatpak info to get application metadata
         let output = Command::new("flatpak")
@@ -123,6 +127,10 @@ impl PackageSource for FlatpakSource {
         None
     }
 
+    fn registry_url(&self, package: &str) -> Option<String> {
+        Some(format!("https://flathub.org/apps/{}", package))
+    }
+
     fn install_command(&self, package: &str) -> String {
         format!("flatpak install -y {}", package)
     }
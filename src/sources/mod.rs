@@ -10,6 +10,8 @@ mod flatpak;
 mod manual;
 mod npm;
 mod pip;
+mod plugin;
+mod shell;
 
 pub use apt::AptSource;
 pub use brew::BrewSource;
@@ -17,10 +19,15 @@ pub use cargo::CargoSource;
 pub use flatpak::FlatpakSource;
 pub use manual::ManualSource;
 pub use npm::NpmSource;
+pub(crate) use npm::global_root as npm_global_root;
 pub use pip::PipSource;
+pub(crate) use pip::{distribution_requirements, distribution_sizes};
+pub use plugin::{PLUGIN_PREFIX, PluginPackage, PluginSource, plugin, plugins};
+pub use shell::{ShellPlugin, ShellSource};
 
-use crate::models::{InstallSource, Tool};
+use crate::models::{InstallSource, Tool, ToolKind};
 use anyhow::Result;
+use std::path::{Path, PathBuf};
 
 /// Trait for package managers/sources
 ///
@@ -39,10 +46,28 @@ pub trait PackageSource: Send + Sync {
     /// Scan system for installed packages from this source
     fn scan(&self) -> Result<Vec<Tool>>;
 
+    /// Files or directories that change whenever packages of this source are
+    /// installed or removed, so scans can skip the source while they don't
+    /// (empty: always rescan)
+    fn state_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
     /// Fetch description from package registry
     /// Returns None if not available or request fails
     fn fetch_description(&self, package: &str) -> Option<String>;
 
+    /// Fetch the license (SPDX expression) from the package registry
+    /// Returns None if not available or request fails
+    fn fetch_license(&self, _package: &str) -> Option<String> {
+        None
+    }
+
+    /// Web page of a package in the registry, if the source has one
+    fn registry_url(&self, _package: &str) -> Option<String> {
+        None
+    }
+
     /// Generate install command for a package
     fn install_command(&self, package: &str) -> String;
 
@@ -60,17 +85,22 @@ pub trait PackageSource: Send + Sync {
     }
 }
 
-/// Get all available package sources
+/// Get all available package sources, including source plugins on PATH
 pub fn all_sources() -> Vec<Box<dyn PackageSource>> {
-    vec![
+    let mut sources: Vec<Box<dyn PackageSource>> = vec![
         Box::new(CargoSource),
         Box::new(PipSource),
         Box::new(NpmSource),
         Box::new(BrewSource),
         Box::new(AptSource),
         Box::new(FlatpakSource),
+        Box::new(ShellSource),
         Box::new(ManualSource),
-    ]
+    ];
+    for plugin in plugins() {
+        sources.push(Box::new(plugin.clone()));
+    }
+    sources
 }
 
 /// Get a specific source by name
@@ -82,8 +112,9 @@ pub fn get_source(name: &str) -> Option<Box<dyn PackageSource>> {
         "brew" => Some(Box::new(BrewSource)),
         "apt" => Some(Box::new(AptSource)),
         "flatpak" => Some(Box::new(FlatpakSource)),
+        "shell" => Some(Box::new(ShellSource)),
         "manual" => Some(Box::new(ManualSource)),
-        _ => None,
+        name => plugin(name).map(|p| Box::new(p.clone()) as Box<dyn PackageSource>),
     }
 }
 
@@ -96,21 +127,64 @@ pub fn source_for(install_source: &InstallSource) -> Option<Box<dyn PackageSourc
         InstallSource::Brew => Some(Box::new(BrewSource)),
         InstallSource::Apt => Some(Box::new(AptSource)),
         InstallSource::Flatpak => Some(Box::new(FlatpakSource)),
+        InstallSource::Shell => Some(Box::new(ShellSource)),
         InstallSource::Manual => Some(Box::new(ManualSource)),
+        InstallSource::Plugin(name) => {
+            plugin(name).map(|p| Box::new(p.clone()) as Box<dyn PackageSource>)
+        }
         _ => None,
     }
 }
 
+/// Classify a pip or npm package by the commands it installs
+///
+/// `commands` are its console scripts or bin entries found on PATH. A package
+/// with one is an application tracked by that command (preferring one named
+/// after the package); otherwise it's a library tracked by its install
+/// location, so installed checks still work.
+pub(crate) fn classify_package(
+    package: &str,
+    commands: &[String],
+    location: &Path,
+) -> (ToolKind, String) {
+    match commands.iter().find(|c| *c == package).or(commands.first()) {
+        Some(command) => (ToolKind::Application, command.clone()),
+        None => (ToolKind::Library, location.display().to_string()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_classify_package() {
+        let location = Path::new("/usr/lib/node_modules/typescript");
+        let commands = vec!["tsserver".to_string(), "tsc".to_string()];
+        assert_eq!(
+            classify_package("typescript", &commands, location),
+            (ToolKind::Application, "tsserver".to_string())
+        );
+        let commands = vec!["black-primer".to_string(), "black".to_string()];
+        assert_eq!(
+            classify_package("black", &commands, location),
+            (ToolKind::Application, "black".to_string())
+        );
+        assert_eq!(
+            classify_package("lodash", &[], Path::new("/usr/lib/node_modules/lodash")),
+            (
+                ToolKind::Library,
+                "/usr/lib/node_modules/lodash".to_string()
+            )
+        );
+    }
+
     // ==================== all_sources Tests ====================
 
     #[test]
     fn test_all_sources_returns_expected_count() {
         let sources = all_sources();
-        assert_eq!(sources.len(), 7);
+        assert_eq!(sources.len(), 8);
     }
 
     #[test]
@@ -136,6 +210,7 @@ mod tests {
         assert!(names.contains(&"brew"));
         assert!(names.contains(&"apt"));
         assert!(names.contains(&"flatpak"));
+        assert!(names.contains(&"shell"));
         assert!(names.contains(&"manual"));
     }
 
@@ -149,6 +224,7 @@ mod tests {
         assert!(get_source("brew").is_some());
         assert!(get_source("apt").is_some());
         assert!(get_source("flatpak").is_some());
+        assert!(get_source("shell").is_some());
         assert!(get_source("manual").is_some());
     }
 
@@ -294,4 +370,17 @@ mod tests {
         let source = ManualSource;
         assert!(source.check_update("tool", "1.0.0").is_none());
     }
+
+    #[test]
+    fn test_registry_url() {
+        assert_eq!(
+            CargoSource.registry_url("ripgrep").as_deref(),
+            Some("https://crates.io/crates/ripgrep")
+        );
+        assert_eq!(
+            NpmSource.registry_url("prettier").as_deref(),
+            Some("https://www.npmjs.com/package/prettier")
+        );
+        assert!(ManualSource.registry_url("tool").is_none());
+    }
 }
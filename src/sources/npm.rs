@@ -1,14 +1,34 @@
 //! Npm (Node.js) package source
 
-use super::PackageSource;
+use super::{PackageSource, classify_package};
 use crate::http::HTTP_AGENT;
-use crate::models::{InstallSource, Tool};
-use crate::scanner::{KNOWN_TOOLS, is_installed};
+use crate::models::{InstallSource, Tool, ToolKind};
+use crate::scanner::{is_installed, known_tools};
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub struct NpmSource;
 
+/// Global node_modules directory
+pub(crate) fn global_root() -> Option<PathBuf> {
+    let output = Command::new("npm").args(["root", "-g"]).output().ok()?;
+    let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !root.is_empty()).then(|| PathBuf::from(root))
+}
+
+/// Commands a package.json `bin` field installs
+fn bin_commands(package: &str, manifest: &serde_json::Value) -> Vec<String> {
+    match manifest.get("bin") {
+        // A single command is named after the package, without its scope
+        Some(serde_json::Value::String(_)) => {
+            vec![package.rsplit('/').next().unwrap_or(package).to_string()]
+        }
+        Some(serde_json::Value::Object(bins)) => bins.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
 impl PackageSource for NpmSource {
     fn name(&self) -> &'static str {
         "npm"
@@ -36,30 +56,54 @@ impl PackageSource for NpmSource {
         };
 
         let mut tools = Vec::new();
+        let root = global_root();
 
         if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
-            for (package, _) in deps {
+            for (package, info) in deps {
                 // Skip npm itself
                 if package == "npm" {
                     continue;
                 }
 
-                // Skip if already in KNOWN_TOOLS
-                if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+                // Skip if already a known tool
+                if known_tools().iter().any(|kt| kt.name == package) {
                     continue;
                 }
 
-                // Check if package has a binary in PATH
-                if !is_installed(package) {
-                    continue;
-                }
-
-                let tool = Tool::new(package)
+                // Classify by the package's bin entries; without the global
+                // root, only track packages with a same-name binary
+                let (kind, binary) = match &root {
+                    Some(root) => {
+                        let location = root.join(package);
+                        let manifest = std::fs::read_to_string(location.join("package.json"))
+                            .ok()
+                            .and_then(|s| serde_json::from_str(&s).ok())
+                            .unwrap_or_default();
+                        let mut commands: Vec<String> = bin_commands(package, &manifest)
+                            .into_iter()
+                            .filter(|c| is_installed(c))
+                            .collect();
+                        if commands.is_empty() && is_installed(package) {
+                            commands.push(package.clone());
+                        }
+                        classify_package(package, &commands, &location)
+                    }
+                    None if is_installed(package) => (ToolKind::Application, package.clone()),
+                    None => continue,
+                };
+
+                let mut tool = Tool::new(package)
                     .with_source(InstallSource::Npm)
-                    .with_binary(package)
-                    .with_category("cli")
+                    .with_binary(&binary)
+                    .with_category(if kind == ToolKind::Library {
+                        "library"
+                    } else {
+                        "cli"
+                    })
+                    .with_kind(kind)
                     .with_install_command(self.install_command(package))
                     .installed();
+                tool.installed_version = info["version"].as_str().map(str::to_string);
                 // Description fetched in parallel by cmd_scan
 
                 tools.push(tool);
@@ -80,6 +124,21 @@ impl PackageSource for NpmSource {
             .map(|s| s.to_string())
     }
 
+    fn fetch_license(&self, package: &str) -> Option<String> {
+        let url = format!("https://registry.npmjs.org/{}", package);
+        let mut response = HTTP_AGENT.get(&url).call().ok()?;
+        let json: serde_json::Value = response.body_mut().read_json().ok()?;
+
+        json.get("license")?
+            .as_str()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    }
+
+    fn registry_url(&self, package: &str) -> Option<String> {
+        Some(format!("https://www.npmjs.com/package/{}", package))
+    }
+
     fn install_command(&self, package: &str) -> String {
         format!("npm install -g {}", package)
     }
@@ -1,14 +1,120 @@
 //! Pip (Python) package source
 
-use super::PackageSource;
+use super::{PackageSource, classify_package};
 use crate::http::HTTP_AGENT;
-use crate::models::{InstallSource, Tool};
-use crate::scanner::{KNOWN_TOOLS, is_installed};
+use crate::models::{InstallSource, Tool, ToolKind};
+use crate::scanner::{is_installed, known_tools};
 use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
 use std::process::Command;
 
+/// Lists installed distributions as `[name, version, console scripts, dist-info path]`
+const DISTRIBUTIONS_SCRIPT: &str = "import importlib.metadata as m, json
+print(json.dumps([[d.metadata['Name'], d.version,
+    [e.name for e in d.entry_points if e.group == 'console_scripts'],
+    str(getattr(d, '_path', ''))] for d in m.distributions() if d.metadata['Name']]))";
+
+/// Maps each installed distribution to the bytes of the files it installed
+const SIZES_SCRIPT: &str = "import importlib.metadata as m, json, os
+sizes = {}
+for d in m.distributions():
+    name = (d.metadata['Name'] or '').lower().replace('_', '-')
+    if name and name not in sizes:
+        files = [str(f.locate()) for f in d.files or []]
+        sizes[name] = sum(os.path.getsize(f) for f in files if os.path.isfile(f))
+print(json.dumps(sizes))";
+
+/// Maps each installed distribution to the distributions it requires
+/// (extras left out)
+const REQUIREMENTS_SCRIPT: &str = "import importlib.metadata as m, json, re
+norm = lambda n: n.lower().replace('_', '-')
+deps = {}
+for d in m.distributions():
+    name = norm(d.metadata['Name'] or '')
+    if name and name not in deps:
+        deps[name] = [norm(re.split(r'[ ;<>=!~\\[(]', r, maxsplit=1)[0])
+            for r in d.requires or [] if 'extra ==' not in r]
+print(json.dumps(deps))";
+
 pub struct PipSource;
 
+/// Output of a Python script run by the first interpreter that succeeds
+fn run_python(script: &str) -> Option<Vec<u8>> {
+    ["python3", "python"].iter().find_map(|python| {
+        Command::new(python)
+            .args(["-c", script])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| o.stdout)
+    })
+}
+
+/// Distributions each installed distribution requires
+pub(crate) fn distribution_requirements() -> HashMap<String, Vec<String>> {
+    run_python(REQUIREMENTS_SCRIPT)
+        .and_then(|stdout| serde_json::from_slice(&stdout).ok())
+        .unwrap_or_default()
+}
+
+/// Bytes each installed distribution occupies, console scripts included
+pub(crate) fn distribution_sizes() -> HashMap<String, u64> {
+    run_python(SIZES_SCRIPT)
+        .and_then(|stdout| serde_json::from_slice(&stdout).ok())
+        .unwrap_or_default()
+}
+
+impl PipSource {
+    /// Installed distributions classified as applications or libraries by
+    /// their console scripts; None when importlib.metadata isn't available
+    fn scan_distributions(&self) -> Option<Vec<Tool>> {
+        let distributions: Vec<(String, String, Vec<String>, String)> =
+            serde_json::from_slice(&run_python(DISTRIBUTIONS_SCRIPT)?).ok()?;
+
+        let mut tools: Vec<Tool> = Vec::new();
+        for (name, version, scripts, path) in distributions {
+            let package = name.to_lowercase().replace('_', "-");
+
+            // Skip known tools, and copies shadowed by an earlier sys.path entry
+            if known_tools().iter().any(|kt| kt.name == package)
+                || tools.iter().any(|t| t.name == package)
+            {
+                continue;
+            }
+
+            let mut commands: Vec<String> =
+                scripts.into_iter().filter(|s| is_installed(s)).collect();
+            // Older packages ship plain scripts rather than entry points
+            if commands.is_empty() && is_installed(&package) {
+                commands.push(package.clone());
+            }
+            if commands.is_empty() && path.is_empty() {
+                continue;
+            }
+
+            let (kind, binary) = classify_package(&package, &commands, Path::new(&path));
+            let mut tool = Tool::new(&package)
+                .with_source(InstallSource::Pip)
+                .with_binary(&binary)
+                .with_category(if kind == ToolKind::Library {
+                    "library"
+                } else {
+                    "cli"
+                })
+                .with_kind(kind)
+                .with_install_command(self.install_command(&package))
+                .installed();
+            tool.installed_version = Some(version);
+            // Description fetched in parallel by cmd_scan
+
+            tools.push(tool);
+        }
+
+        Some(tools)
+    }
+}
+
 impl PackageSource for PipSource {
     fn name(&self) -> &'static str {
         "pip"
@@ -19,7 +125,12 @@ impl PackageSource for PipSource {
     }
 
     fn scan(&self) -> Result<Vec<Tool>> {
-        // Try pip3 first, then pip
+        if let Some(tools) = self.scan_distributions() {
+            return Ok(tools);
+        }
+
+        // Without importlib.metadata, only track packages with a same-name
+        // binary; try pip3 first, then pip
         let output = Command::new("pip3")
             .args(["list", "--format=freeze"])
             .output()
@@ -38,13 +149,13 @@ impl PackageSource for PipSource {
 
         for line in stdout.lines() {
             // Format: "package==version"
-            let package = match line.split("==").next() {
-                Some(p) => p.to_lowercase().replace('_', "-"),
-                None => continue,
+            let (package, version) = match line.split_once("==") {
+                Some((p, v)) => (p.to_lowercase().replace('_', "-"), Some(v.trim())),
+                None => (line.to_lowercase().replace('_', "-"), None),
             };
 
-            // Skip if already in KNOWN_TOOLS
-            if KNOWN_TOOLS.iter().any(|kt| kt.name == package) {
+            // Skip if already a known tool
+            if known_tools().iter().any(|kt| kt.name == package) {
                 continue;
             }
 
@@ -53,12 +164,13 @@ impl PackageSource for PipSource {
                 continue;
             }
 
-            let tool = Tool::new(&package)
+            let mut tool = Tool::new(&package)
                 .with_source(InstallSource::Pip)
                 .with_binary(&package)
                 .with_category("cli")
                 .with_install_command(self.install_command(&package))
                 .installed();
+            tool.installed_version = version.map(str::to_string);
             // Description fetched in parallel by cmd_scan
 
             tools.push(tool);
@@ -81,6 +193,36 @@ impl PackageSource for PipSource {
         }
     }
 
+    fn fetch_license(&self, package: &str) -> Option<String> {
+        let url = format!("https://pypi.org/pypi/{}/json", package);
+        let mut response = HTTP_AGENT.get(&url).call().ok()?;
+        let json: serde_json::Value = response.body_mut().read_json().ok()?;
+        let info = json.get("info")?;
+
+        // Prefer the SPDX expression; the free-form field often holds the full license text
+        let field = |key: &str| {
+            info.get(key)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|s| !s.is_empty() && *s != "UNKNOWN" && !s.contains('\n') && s.len() <= 40)
+                .map(|s| s.to_string())
+        };
+        field("license_expression")
+            .or_else(|| field("license"))
+            .or_else(|| {
+                info.get("classifiers")?
+                    .as_array()?
+                    .iter()
+                    .filter_map(|c| c.as_str())
+                    .find_map(|c| c.strip_prefix("License :: OSI Approved :: "))
+                    .map(|s| s.to_string())
+            })
+    }
+
+    fn registry_url(&self, package: &str) -> Option<String> {
+        Some(format!("https://pypi.org/project/{}/", package))
+    }
+
     fn install_command(&self, package: &str) -> String {
         format!("pip install {}", package)
     }
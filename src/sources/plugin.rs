@@ -0,0 +1,376 @@
+//! Executable source plugins
+//!
+//! An executable named `hoards-source-<name>` on PATH is exposed as the
+//! package source `<name>`, so niche or company-internal package managers
+//! can be added without changing hoards. hoards runs the plugin with a
+//! command and reads JSON from its stdout:
+//!
+//! | Command | Output |
+//! |---------|--------|
+//! | `scan` | `[{"name", "binary"?, "version"?, "description"?, "category"?}]` |
+//! | `describe <package>` | `{"description"?, "license"?, "url"?}` or `null` |
+//! | `install-cmd <package> [version]` | `{"install": [argv], "uninstall"?: [argv]}` |
+//! | `latest-version <package>` | `{"version": "1.2.3"}` or `null` |
+//!
+//! A non-zero exit status means the plugin can't answer; hoards then
+//! treats the information as unavailable.
+
+use super::PackageSource;
+use crate::commands::{SafeCommand, validate_binary_name, validate_package_name};
+use crate::models::{InstallSource, Tool};
+use crate::scanner::{executable_name, is_installed};
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{LazyLock, Mutex};
+
+/// File name prefix of source plugins
+pub const PLUGIN_PREFIX: &str = "hoards-source-";
+
+/// An installed package reported by `scan`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginPackage {
+    pub name: String,
+    pub binary: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+}
+
+/// Registry details reported by `describe`
+#[derive(Debug, Default, Deserialize)]
+struct PluginDescription {
+    description: Option<String>,
+    license: Option<String>,
+    url: Option<String>,
+}
+
+/// Commands reported by `install-cmd`, as argument vectors
+#[derive(Debug, Deserialize)]
+struct PluginCommands {
+    install: Vec<String>,
+    #[serde(default)]
+    uninstall: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginVersion {
+    version: String,
+}
+
+/// A package source backed by a `hoards-source-<name>` executable
+#[derive(Debug, Clone)]
+pub struct PluginSource {
+    name: &'static str,
+    path: PathBuf,
+}
+
+/// Source plugins on PATH, found once per run; the first of a name wins
+static PLUGINS: LazyLock<Vec<PluginSource>> = LazyLock::new(discover);
+
+/// `scan` output per plugin, so version lookups don't rescan
+static SCANS: LazyLock<Mutex<HashMap<&'static str, Vec<PluginPackage>>>> =
+    LazyLock::new(Default::default);
+
+/// Whether a plugin name is usable as a source name
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Find source plugins on PATH, skipping names of built-in sources
+fn discover() -> Vec<PluginSource> {
+    let Some(path) = std::env::var_os("PATH") else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<PluginSource> = Vec::new();
+    for dir in std::env::split_paths(&path) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found: Vec<(String, PathBuf)> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with(PLUGIN_PREFIX))
+            .filter_map(|e| {
+                let name = executable_name(&e.path())?;
+                let name = name.strip_prefix(PLUGIN_PREFIX)?.to_string();
+                Some((name, e.path()))
+            })
+            .filter(|(name, _)| valid_name(name))
+            .collect();
+        found.sort();
+        for (name, path) in found {
+            if InstallSource::builtin(&name).is_some() || plugins.iter().any(|p| p.name == name) {
+                continue;
+            }
+            tracing::debug!("source plugin {} at {}", name, path.display());
+            plugins.push(PluginSource {
+                // Plugins are found once and live for the whole run
+                name: Box::leak(name.into_boxed_str()),
+                path,
+            });
+        }
+    }
+    plugins
+}
+
+/// Source plugins found on PATH
+pub fn plugins() -> &'static [PluginSource] {
+    &PLUGINS
+}
+
+/// The source plugin of a name
+pub fn plugin(name: &str) -> Option<&'static PluginSource> {
+    PLUGINS.iter().find(|p| p.name == name)
+}
+
+impl PluginSource {
+    /// Path of the plugin executable
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Run a protocol command and parse its JSON output
+    fn call<T: DeserializeOwned>(&self, args: &[&str]) -> Result<T> {
+        tracing::debug!("running {} {}", self.path.display(), args.join(" "));
+        let output = Command::new(&self.path)
+            .args(args)
+            .output()
+            .with_context(|| format!("Failed to run {}", self.path.display()))?;
+        if !output.status.success() {
+            bail!(
+                "{}{} {} exited with {}",
+                PLUGIN_PREFIX,
+                self.name,
+                args[0],
+                output.status
+            );
+        }
+        serde_json::from_slice(&output.stdout).with_context(|| {
+            format!(
+                "{}{} {} printed invalid JSON",
+                PLUGIN_PREFIX, self.name, args[0]
+            )
+        })
+    }
+
+    /// Packages reported by `scan`, run at most once per plugin
+    pub fn packages(&self) -> Result<Vec<PluginPackage>> {
+        let mut scans = SCANS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(packages) = scans.get(self.name) {
+            return Ok(packages.clone());
+        }
+        let packages: Vec<PluginPackage> = self.call(&["scan"])?;
+        scans.insert(self.name, packages.clone());
+        Ok(packages)
+    }
+
+    fn describe(&self, package: &str) -> PluginDescription {
+        self.call::<Option<PluginDescription>>(&["describe", package])
+            .inspect_err(|e| tracing::debug!("{:#}", e))
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    fn commands(&self, package: &str, version: Option<&str>) -> Result<PluginCommands> {
+        let mut args = vec!["install-cmd", package];
+        args.extend(version);
+        let commands: PluginCommands = self.call(&args)?;
+        if commands.install.is_empty() {
+            bail!(
+                "{}{} gave an empty install command",
+                PLUGIN_PREFIX,
+                self.name
+            );
+        }
+        Ok(commands)
+    }
+
+    /// Installed version of a package, from the plugin's scan
+    pub fn installed_version(&self, package: &str) -> Option<String> {
+        self.packages()
+            .ok()?
+            .into_iter()
+            .find(|p| p.name == package)
+            .and_then(|p| p.version)
+    }
+
+    /// Latest version of a package
+    pub fn latest_version(&self, package: &str) -> Option<String> {
+        self.call::<Option<PluginVersion>>(&["latest-version", package])
+            .inspect_err(|e| tracing::debug!("{:#}", e))
+            .ok()
+            .flatten()
+            .map(|v| v.version)
+    }
+
+    /// Install command for a package, run without a shell
+    pub fn install_safe_command(
+        &self,
+        package: &str,
+        version: Option<&str>,
+    ) -> Result<SafeCommand> {
+        let commands = self.commands(package, version)?;
+        Ok(safe_command(commands.install))
+    }
+
+    /// Uninstall command for a package, if the plugin has one
+    pub fn uninstall_safe_command(&self, package: &str) -> Result<Option<SafeCommand>> {
+        let commands = self.commands(package, None)?;
+        Ok((!commands.uninstall.is_empty()).then(|| safe_command(commands.uninstall)))
+    }
+}
+
+/// A command from an argument vector
+fn safe_command(mut argv: Vec<String>) -> SafeCommand {
+    let program = argv.remove(0);
+    let display = std::iter::once(program.as_str())
+        .chain(argv.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+    SafeCommand {
+        program,
+        args: argv,
+        display,
+    }
+}
+
+impl PackageSource for PluginSource {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn install_source(&self) -> InstallSource {
+        InstallSource::Plugin(self.name.to_string())
+    }
+
+    fn scan(&self) -> Result<Vec<Tool>> {
+        let mut tools = Vec::new();
+        for package in self.packages()? {
+            let binary = package.binary.unwrap_or_else(|| package.name.clone());
+            if validate_package_name(&package.name).is_err()
+                || validate_binary_name(&binary).is_err()
+            {
+                tracing::warn!(
+                    "{}{} reported an invalid package '{}'",
+                    PLUGIN_PREFIX,
+                    self.name,
+                    package.name
+                );
+                continue;
+            }
+            if !is_installed(&binary) {
+                continue;
+            }
+            let mut tool = Tool::new(&package.name)
+                .with_source(self.install_source())
+                .with_binary(binary)
+                .with_category(package.category.as_deref().unwrap_or("cli"))
+                .installed();
+            tool.installed_version = package.version;
+            if let Some(description) = package.description {
+                tool = tool.with_description(description);
+            }
+            if let Ok(commands) = self.commands(&package.name, None) {
+                tool = tool.with_install_command(commands.install.join(" "));
+            }
+            tools.push(tool);
+        }
+        Ok(tools)
+    }
+
+    fn fetch_description(&self, package: &str) -> Option<String> {
+        self.describe(package).description
+    }
+
+    fn fetch_license(&self, package: &str) -> Option<String> {
+        self.describe(package).license
+    }
+
+    fn registry_url(&self, package: &str) -> Option<String> {
+        self.describe(package).url
+    }
+
+    fn install_command(&self, package: &str) -> String {
+        self.commands(package, None)
+            .map(|c| c.install.join(" "))
+            .unwrap_or_default()
+    }
+
+    fn uninstall_command(&self, package: &str) -> String {
+        self.commands(package, None)
+            .map(|c| c.uninstall.join(" "))
+            .unwrap_or_default()
+    }
+
+    fn supports_updates(&self) -> bool {
+        true
+    }
+
+    fn check_update(&self, package: &str, current_version: &str) -> Option<String> {
+        self.latest_version(package)
+            .filter(|latest| latest != current_version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_name() {
+        assert!(valid_name("portage"));
+        assert!(valid_name("acme-internal"));
+        assert!(!valid_name(""));
+        assert!(!valid_name("Bad Name"));
+        assert!(!valid_name("../x"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_protocol() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hoards-source-test");
+        std::fs::write(
+            &path,
+            r#"#!/bin/sh
+case "$1" in
+  scan) echo '[{"name": "sh", "version": "1.0"}, {"name": "bad;name"}]' ;;
+  describe) echo '{"description": "A shell", "license": "MIT"}' ;;
+  install-cmd) echo "{\"install\": [\"tpm\", \"add\", \"$2\"], \"uninstall\": [\"tpm\", \"rm\", \"$2\"]}" ;;
+  latest-version) echo '{"version": "1.1"}' ;;
+  *) exit 1 ;;
+esac
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let plugin = PluginSource { name: "test", path };
+
+        let tools = plugin.scan().unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].source, InstallSource::Plugin("test".to_string()));
+        assert_eq!(tools[0].install_command.as_deref(), Some("tpm add sh"));
+        assert_eq!(plugin.installed_version("sh").as_deref(), Some("1.0"));
+
+        assert_eq!(plugin.fetch_description("sh").as_deref(), Some("A shell"));
+        assert_eq!(plugin.fetch_license("sh").as_deref(), Some("MIT"));
+        assert_eq!(plugin.registry_url("sh"), None);
+        assert_eq!(plugin.check_update("sh", "1.0").as_deref(), Some("1.1"));
+        assert_eq!(plugin.check_update("sh", "1.1"), None);
+
+        let install = plugin.install_safe_command("sh", None).unwrap();
+        assert_eq!(install.program, "tpm");
+        assert_eq!(install.args, vec!["add", "sh"]);
+        let uninstall = plugin.uninstall_safe_command("sh").unwrap().unwrap();
+        assert_eq!(uninstall.display, "tpm rm sh");
+    }
+}
@@ -0,0 +1,370 @@
+//! Shell plugins and prompt frameworks
+//!
+//! Covers oh-my-zsh and its custom plugins and themes, zinit plugins, fisher
+//! plugins and prompt tools such as oh-my-posh. Plugins that are git
+//! checkouts record their commit as the installed version, which update
+//! checks compare against the remote.
+
+use super::PackageSource;
+use crate::models::{InstallSource, Tool};
+use crate::scanner::{binary_version, is_installed, known_tools, subdirs};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Plugins that are prompts or prompt themes
+const PROMPT_PLUGINS: &[&str] = &["powerlevel10k", "pure", "spaceship-prompt", "tide"];
+
+/// Prompt tools installed as standalone binaries
+const PROMPT_TOOLS: &[&str] = &["starship", "oh-my-posh"];
+
+/// A shell plugin, theme or prompt found on this machine
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellPlugin {
+    pub name: String,
+    /// `oh-my-zsh`, `zinit`, `fisher` or `prompt`
+    pub manager: &'static str,
+    /// The plugin's checkout, the file listing it, or its binary
+    pub path: PathBuf,
+    /// Repository ("owner/repo" or a clone URL), if known
+    pub repo: Option<String>,
+    /// A git checkout that update checks can compare with its remote
+    pub git: bool,
+}
+
+impl ShellPlugin {
+    fn category(&self) -> &'static str {
+        if self.manager == "prompt" || PROMPT_PLUGINS.contains(&self.name.as_str()) {
+            "prompt"
+        } else {
+            "shell"
+        }
+    }
+
+    fn install_command(&self) -> String {
+        let repo = self.repo.as_deref().unwrap_or(&self.name);
+        match self.manager {
+            "oh-my-zsh" if self.name == "oh-my-zsh" => {
+                "sh -c \"$(curl -fsSL https://install.ohmyz.sh)\"".to_string()
+            }
+            "oh-my-zsh" => match &self.repo {
+                Some(url) => format!("git clone {} {}", url, self.path.display()),
+                None => format!("# Clone {} into {}", self.name, self.path.display()),
+            },
+            "zinit" => format!("zinit light {}", repo),
+            "fisher" => format!("fisher install {}", repo),
+            _ => format!("# Install {} with your package manager", self.name),
+        }
+    }
+
+    fn uninstall_command(&self) -> String {
+        let repo = self.repo.as_deref().unwrap_or(&self.name);
+        match self.manager {
+            "oh-my-zsh" if self.name == "oh-my-zsh" => "uninstall_oh_my_zsh".to_string(),
+            "oh-my-zsh" => format!("rm -rf {}", self.path.display()),
+            "zinit" => format!("zinit delete {}", repo),
+            "fisher" => format!("fisher remove {}", repo),
+            _ => format!("# Uninstall {} with your package manager", self.name),
+        }
+    }
+
+    /// Commit a git checkout is at
+    pub fn head(&self) -> Option<String> {
+        git_output(&self.path, &["rev-parse", "--short", "HEAD"])
+    }
+
+    /// Commit the checkout's remote HEAD is at (asks the remote)
+    pub fn remote_head(&self) -> Option<String> {
+        let output = git_output(&self.path, &["ls-remote", "origin", "HEAD"])?;
+        let sha = output.split_whitespace().next()?;
+        Some(sha.chars().take(7).collect())
+    }
+
+    fn to_tool(&self) -> Tool {
+        let mut tool = Tool::new(&self.name)
+            .with_source(InstallSource::Shell)
+            .with_binary(self.path.display().to_string())
+            .with_category(self.category())
+            .with_install_command(self.install_command())
+            .installed();
+        tool.installed_version = if self.git {
+            self.head()
+        } else if self.manager == "prompt" {
+            binary_version(&self.name)
+        } else {
+            None
+        };
+        tool
+    }
+}
+
+/// Trimmed stdout of a git command run in a checkout
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+/// `origin` URL from a checkout's git config
+fn git_remote(dir: &Path) -> Option<String> {
+    let config = std::fs::read_to_string(dir.join(".git/config")).ok()?;
+    let mut in_origin = false;
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_origin = line == "[remote \"origin\"]";
+        } else if in_origin && let Some(url) = line.strip_prefix("url") {
+            return Some(url.trim_start_matches([' ', '=']).trim().to_string());
+        }
+    }
+    None
+}
+
+/// oh-my-zsh itself and the plugins and themes cloned into its custom dir
+fn omz_plugins(omz_dir: &Path, custom_dir: &Path) -> Vec<ShellPlugin> {
+    if !omz_dir.join("oh-my-zsh.sh").exists() {
+        return Vec::new();
+    }
+    let mut plugins = vec![ShellPlugin {
+        name: "oh-my-zsh".to_string(),
+        manager: "oh-my-zsh",
+        path: omz_dir.to_path_buf(),
+        repo: git_remote(omz_dir),
+        git: omz_dir.join(".git").exists(),
+    }];
+    for kind in ["plugins", "themes"] {
+        for (name, path) in subdirs(&custom_dir.join(kind)) {
+            // Only clones; the bundled "example" plugin has no .git
+            if !path.join(".git").exists() {
+                continue;
+            }
+            plugins.push(ShellPlugin {
+                name,
+                manager: "oh-my-zsh",
+                repo: git_remote(&path),
+                path,
+                git: true,
+            });
+        }
+    }
+    plugins
+}
+
+/// Plugins zinit has cloned ("zsh-users---zsh-autosuggestions")
+fn zinit_plugins(zinit_dir: &Path) -> Vec<ShellPlugin> {
+    subdirs(&zinit_dir.join("plugins"))
+        .into_iter()
+        .filter(|(dir, _)| !dir.starts_with("_local"))
+        .filter_map(|(dir, path)| {
+            let (owner, repo) = dir.split_once("---")?;
+            Some(ShellPlugin {
+                name: repo.to_string(),
+                manager: "zinit",
+                repo: Some(format!("{}/{}", owner, repo)),
+                git: path.join(".git").exists(),
+                path,
+            })
+        })
+        .collect()
+}
+
+/// Plugins listed in fisher's `fish_plugins` file
+fn fisher_plugins(fish_plugins: &Path) -> Vec<ShellPlugin> {
+    let Ok(content) = std::fs::read_to_string(fish_plugins) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|entry| {
+            let name = entry
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .unwrap_or(entry)
+                .split('@')
+                .next()
+                .unwrap_or(entry);
+            ShellPlugin {
+                name: name.to_string(),
+                manager: "fisher",
+                path: fish_plugins.to_path_buf(),
+                repo: Some(entry.to_string()),
+                git: false,
+            }
+        })
+        .collect()
+}
+
+pub struct ShellSource;
+
+impl ShellSource {
+    fn omz_dirs() -> (PathBuf, PathBuf) {
+        let omz = std::env::var_os("ZSH")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".oh-my-zsh")))
+            .unwrap_or_default();
+        let custom = std::env::var_os("ZSH_CUSTOM")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| omz.join("custom"));
+        (omz, custom)
+    }
+
+    fn zinit_dir() -> PathBuf {
+        dirs::data_dir().unwrap_or_default().join("zinit")
+    }
+
+    fn fish_plugins_file() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_default()
+            .join("fish/fish_plugins")
+    }
+
+    /// Every shell plugin and prompt on this machine
+    pub fn plugins(&self) -> Vec<ShellPlugin> {
+        let (omz, custom) = Self::omz_dirs();
+        let mut plugins = omz_plugins(&omz, &custom);
+        plugins.extend(zinit_plugins(&Self::zinit_dir()));
+        plugins.extend(fisher_plugins(&Self::fish_plugins_file()));
+        plugins.extend(
+            PROMPT_TOOLS
+                .iter()
+                .filter(|name| is_installed(name))
+                .map(|name| ShellPlugin {
+                    name: name.to_string(),
+                    manager: "prompt",
+                    path: PathBuf::from(name),
+                    repo: None,
+                    git: false,
+                }),
+        );
+        plugins
+    }
+
+    fn plugin(&self, name: &str) -> Option<ShellPlugin> {
+        self.plugins().into_iter().find(|p| p.name == name)
+    }
+}
+
+impl PackageSource for ShellSource {
+    fn name(&self) -> &'static str {
+        "shell"
+    }
+
+    fn install_source(&self) -> InstallSource {
+        InstallSource::Shell
+    }
+
+    fn scan(&self) -> Result<Vec<Tool>> {
+        Ok(self
+            .plugins()
+            .iter()
+            // Known tools (starship, ...) are tracked with better metadata
+            .filter(|p| !known_tools().iter().any(|kt| kt.name == p.name))
+            .map(ShellPlugin::to_tool)
+            .collect())
+    }
+
+    fn state_paths(&self) -> Vec<PathBuf> {
+        let (omz, custom) = Self::omz_dirs();
+        vec![
+            omz,
+            custom.join("plugins"),
+            custom.join("themes"),
+            Self::zinit_dir().join("plugins"),
+            Self::fish_plugins_file(),
+        ]
+    }
+
+    fn fetch_description(&self, _package: &str) -> Option<String> {
+        None
+    }
+
+    fn install_command(&self, package: &str) -> String {
+        match self.plugin(package) {
+            Some(plugin) => plugin.install_command(),
+            None => format!("# Install {} with your shell plugin manager", package),
+        }
+    }
+
+    fn uninstall_command(&self, package: &str) -> String {
+        match self.plugin(package) {
+            Some(plugin) => plugin.uninstall_command(),
+            None => format!("# Uninstall {} with your shell plugin manager", package),
+        }
+    }
+
+    fn supports_updates(&self) -> bool {
+        true
+    }
+
+    fn check_update(&self, package: &str, current_version: &str) -> Option<String> {
+        let plugin = self.plugin(package).filter(|p| p.git)?;
+        let latest = plugin.remote_head()?;
+        (!latest.starts_with(current_version) && !current_version.starts_with(&latest))
+            .then_some(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_shell_plugins() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        for sub in [
+            "omz/custom/plugins/zsh-autosuggestions/.git",
+            "omz/custom/plugins/example",
+            "omz/custom/themes/powerlevel10k/.git",
+            "zinit/plugins/zsh-users---zsh-syntax-highlighting",
+            "zinit/plugins/_local---zinit",
+        ] {
+            std::fs::create_dir_all(root.join(sub)).unwrap();
+        }
+        std::fs::write(root.join("omz/oh-my-zsh.sh"), "").unwrap();
+        std::fs::write(
+            root.join("omz/custom/plugins/zsh-autosuggestions/.git/config"),
+            "[core]\n\tbare = false\n[remote \"origin\"]\n\turl = https://github.com/zsh-users/zsh-autosuggestions\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("fish_plugins"),
+            "jorgebucaran/fisher\nIlanCosman/tide@v6\n",
+        )
+        .unwrap();
+
+        let omz = omz_plugins(&root.join("omz"), &root.join("omz/custom"));
+        let names: Vec<_> = omz.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["oh-my-zsh", "zsh-autosuggestions", "powerlevel10k"]);
+        assert_eq!(
+            omz[1].repo.as_deref(),
+            Some("https://github.com/zsh-users/zsh-autosuggestions")
+        );
+        assert_eq!(omz[2].category(), "prompt");
+
+        let zinit = zinit_plugins(&root.join("zinit"));
+        assert_eq!(zinit.len(), 1);
+        assert_eq!(zinit[0].name, "zsh-syntax-highlighting");
+        assert_eq!(
+            zinit[0].install_command(),
+            "zinit light zsh-users/zsh-syntax-highlighting"
+        );
+
+        let fisher = fisher_plugins(&root.join("fish_plugins"));
+        let names: Vec<_> = fisher.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["fisher", "tide"]);
+        assert_eq!(
+            fisher[1].uninstall_command(),
+            "fisher remove IlanCosman/tide@v6"
+        );
+        assert_eq!(fisher[1].category(), "prompt");
+    }
+}
@@ -0,0 +1,97 @@
+//! Built-in starter bundles
+//!
+//! Templates give new users a ready-made bundle for a common setup. Their
+//! members are all in [`KNOWN_TOOLS`](crate::scanner::KNOWN_TOOLS), which
+//! supplies the source, description and install command of each tool.
+
+use crate::scanner::KnownTool;
+
+/// A starter bundle shipped with hoards
+#[derive(Debug)]
+pub struct BundleTemplate {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub tools: &'static [&'static str],
+}
+
+impl BundleTemplate {
+    /// Look up a template by name
+    pub fn find(name: &str) -> Option<&'static BundleTemplate> {
+        BUNDLE_TEMPLATES.iter().find(|t| t.name == name)
+    }
+
+    /// Known-tool data of the members
+    pub fn known_tools(&self) -> impl Iterator<Item = &'static KnownTool> + '_ {
+        self.tools.iter().filter_map(|name| KnownTool::find(name))
+    }
+}
+
+/// Templates offered by `hoards bundle template` and the TUI picker
+pub static BUNDLE_TEMPLATES: &[BundleTemplate] = &[
+    BundleTemplate {
+        name: "rust-dev",
+        description: "Rust toolchain, test runners and code stats",
+        tools: &[
+            "rustup",
+            "bacon",
+            "cargo-nextest",
+            "cargo-watch",
+            "tokei",
+            "hyperfine",
+            "just",
+            "delta",
+        ],
+    },
+    BundleTemplate {
+        name: "python-dev",
+        description: "Python versions, packaging, linting and a better REPL",
+        tools: &["pyenv", "uv", "ruff", "ipython", "httpie", "jq", "just"],
+    },
+    BundleTemplate {
+        name: "kubernetes",
+        description: "Cluster access, dashboards, charts and log tailing",
+        tools: &["kubectl", "kubectx", "k9s", "helm", "stern", "docker", "yq"],
+    },
+    BundleTemplate {
+        name: "data-wrangling",
+        description: "Slice and reshape JSON, YAML and CSV from the shell",
+        tools: &["jq", "yq", "xsv", "miller", "csvlens", "choose", "sd"],
+    },
+    BundleTemplate {
+        name: "dotfiles-basics",
+        description: "Manage dotfiles and a comfortable shell",
+        tools: &[
+            "stow",
+            "chezmoi",
+            "age",
+            "git-crypt",
+            "starship",
+            "zoxide",
+            "fzf",
+            "bat",
+            "eza",
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_members_are_known() {
+        for template in BUNDLE_TEMPLATES {
+            for tool in template.tools {
+                assert!(
+                    KnownTool::find(tool).is_some(),
+                    "{} in template {} is not a known tool",
+                    tool,
+                    template.name
+                );
+            }
+            assert_eq!(template.known_tools().count(), template.tools.len());
+        }
+        assert!(BundleTemplate::find("rust-dev").is_some());
+        assert!(BundleTemplate::find("cobol-dev").is_none());
+    }
+}
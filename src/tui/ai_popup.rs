@@ -0,0 +1,309 @@
+//! Streaming AI popup
+//!
+//! AI requests run on a worker thread so the TUI keeps drawing while the
+//! provider generates. Partial output arrives over a channel and is shown as
+//! it streams in; closing the popup early sets a cancel flag that aborts the
+//! request.
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::ai::changelog::{PendingUpdate, UpdateNote, parse_summary, sort_notes, summary_prompt};
+use crate::ai::{
+    Cheatsheet, DiscoveryResponse, cheatsheet_prompt, discovery_prompt, explain_error_prompt,
+    get_help_output, invoke_ai_streaming, parse_cheatsheet_response, parse_discovery_response,
+};
+
+/// An AI request started from the TUI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AiTask {
+    /// Generate a cheatsheet from a tool's --help output
+    Cheatsheet {
+        tool: String,
+        binary: String,
+        description: Option<String>,
+    },
+    /// Recommend tools for a free-form query
+    Discover {
+        query: String,
+        installed: Vec<String>,
+    },
+    /// Explain an error message
+    ExplainError { error: String },
+    /// Digest release notes of pending updates
+    ///
+    /// `cached` notes are merged into the result without being re-sent.
+    UpdatesSummary {
+        pending: Vec<PendingUpdate>,
+        cached: Vec<UpdateNote>,
+    },
+}
+
+impl AiTask {
+    /// Popup title for this task
+    pub fn title(&self) -> String {
+        match self {
+            AiTask::Cheatsheet { tool, .. } => format!("Cheatsheet: {}", tool),
+            AiTask::Discover { query, .. } => format!("AI Discover: {}", query),
+            AiTask::ExplainError { .. } => "Explain Error".to_string(),
+            AiTask::UpdatesSummary { .. } => "Updates Summary".to_string(),
+        }
+    }
+
+    /// Feature name recorded in AI usage accounting
+    fn feature(&self) -> &'static str {
+        match self {
+            AiTask::Cheatsheet { .. } => "cheatsheet",
+            AiTask::Discover { .. } => "discovery",
+            AiTask::ExplainError { .. } => "explain_error",
+            AiTask::UpdatesSummary { .. } => "updates_summary",
+        }
+    }
+
+    /// Build the prompt (runs on the worker, since --help may be slow)
+    fn prompt(&self) -> Result<String> {
+        Ok(match self {
+            AiTask::Cheatsheet {
+                tool,
+                binary,
+                description,
+            } => {
+                let help = get_help_output(binary)?;
+                cheatsheet_prompt(tool, description.as_deref(), &help)
+            }
+            AiTask::Discover { query, installed } => discovery_prompt(query, installed),
+            AiTask::ExplainError { error } => explain_error_prompt(error),
+            AiTask::UpdatesSummary { pending, .. } => summary_prompt(pending),
+        })
+    }
+
+    /// Turn the full response into the task's structured result
+    fn parse(&self, response: &str) -> Result<AiOutcome> {
+        Ok(match self {
+            AiTask::Cheatsheet { .. } => {
+                AiOutcome::Cheatsheet(parse_cheatsheet_response(response)?)
+            }
+            AiTask::Discover { .. } => AiOutcome::Discovery(parse_discovery_response(response)?),
+            AiTask::ExplainError { .. } => AiOutcome::Text(response.to_string()),
+            AiTask::UpdatesSummary { cached, .. } => {
+                let mut notes = cached.clone();
+                notes.extend(parse_summary(response)?);
+                sort_notes(&mut notes);
+                AiOutcome::Updates(notes)
+            }
+        })
+    }
+}
+
+/// Final result of a completed AI task
+#[derive(Debug, Clone)]
+pub enum AiOutcome {
+    Cheatsheet(Cheatsheet),
+    Discovery(DiscoveryResponse),
+    Updates(Vec<UpdateNote>),
+    Text(String),
+}
+
+/// Messages sent from the worker thread
+enum AiEvent {
+    Chunk(String),
+    Done(Result<String>),
+}
+
+/// State of the AI popup
+pub struct AiPopup {
+    pub task: AiTask,
+    /// Raw text streamed so far
+    pub streamed: String,
+    /// Set once the request finishes (error text on failure)
+    pub outcome: Option<Result<AiOutcome, String>>,
+    /// Whether the result was loaded from cache instead of generated
+    pub cached: bool,
+    /// Scroll offset in lines
+    pub scroll: u16,
+    /// Keep the view pinned to the newest output while streaming
+    pub follow: bool,
+    receiver: Option<Receiver<AiEvent>>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl AiPopup {
+    /// Start a task on a worker thread
+    pub fn start(task: AiTask) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let worker_task = task.clone();
+        let worker_cancel = Arc::clone(&cancel);
+        thread::spawn(move || {
+            let result = worker_task.prompt().and_then(|prompt| {
+                invoke_ai_streaming(worker_task.feature(), &prompt, &mut |chunk| {
+                    // A closed channel means the popup is gone, so stop too
+                    !worker_cancel.load(Ordering::Relaxed)
+                        && sender.send(AiEvent::Chunk(chunk.to_string())).is_ok()
+                })
+            });
+            let _ = sender.send(AiEvent::Done(result));
+        });
+
+        Self {
+            task,
+            streamed: String::new(),
+            outcome: None,
+            cached: false,
+            scroll: 0,
+            follow: true,
+            receiver: Some(receiver),
+            cancel,
+        }
+    }
+
+    /// Show an already available result without contacting the provider
+    pub fn finished(task: AiTask, outcome: AiOutcome) -> Self {
+        Self {
+            task,
+            streamed: String::new(),
+            outcome: Some(Ok(outcome)),
+            cached: true,
+            scroll: 0,
+            follow: false,
+            receiver: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Whether the request is still running
+    pub fn is_streaming(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Drain pending output from the worker
+    ///
+    /// Returns the parsed outcome once, when the request completes
+    /// successfully, so the caller can cache or apply it.
+    pub fn poll(&mut self) -> Option<AiOutcome> {
+        let receiver = self.receiver.as_ref()?;
+        loop {
+            match receiver.try_recv() {
+                Ok(AiEvent::Chunk(chunk)) => self.streamed.push_str(&chunk),
+                Ok(AiEvent::Done(result)) => {
+                    self.receiver = None;
+                    let outcome = result.and_then(|response| self.task.parse(&response));
+                    self.follow = false;
+                    self.scroll = 0;
+                    return match outcome {
+                        Ok(outcome) => {
+                            self.outcome = Some(Ok(outcome.clone()));
+                            Some(outcome)
+                        }
+                        Err(e) => {
+                            self.outcome = Some(Err(e.to_string()));
+                            None
+                        }
+                    };
+                }
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => {
+                    self.receiver = None;
+                    self.outcome = Some(Err("AI worker stopped unexpectedly".to_string()));
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Abort the running request
+    pub fn cancel(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        if self.receiver.take().is_some() {
+            self.outcome = Some(Err(crate::ai::CANCELLED_MESSAGE.to_string()));
+        }
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.follow = false;
+        self.scroll = self.scroll.saturating_add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.follow = false;
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+impl Drop for AiPopup {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A streaming popup fed by the returned sender instead of a worker
+    fn streaming_popup(task: AiTask) -> (AiPopup, mpsc::Sender<AiEvent>) {
+        let (sender, receiver) = mpsc::channel();
+        let mut popup = AiPopup::finished(task, AiOutcome::Text(String::new()));
+        popup.outcome = None;
+        popup.cached = false;
+        popup.receiver = Some(receiver);
+        (popup, sender)
+    }
+
+    #[test]
+    fn test_poll_accumulates_chunks_and_parses() {
+        let task = AiTask::ExplainError {
+            error: "boom".to_string(),
+        };
+        let (mut popup, sender) = streaming_popup(task);
+        sender.send(AiEvent::Chunk("Try ".to_string())).unwrap();
+        sender.send(AiEvent::Chunk("again".to_string())).unwrap();
+
+        assert!(popup.poll().is_none());
+        assert_eq!(popup.streamed, "Try again");
+        assert!(popup.is_streaming());
+
+        sender
+            .send(AiEvent::Done(Ok("Try again".to_string())))
+            .unwrap();
+        assert!(matches!(popup.poll(), Some(AiOutcome::Text(text)) if text == "Try again"));
+        assert!(!popup.is_streaming());
+    }
+
+    #[test]
+    fn test_poll_reports_parse_errors() {
+        let task = AiTask::Discover {
+            query: "k8s".to_string(),
+            installed: Vec::new(),
+        };
+        let (mut popup, sender) = streaming_popup(task);
+        sender
+            .send(AiEvent::Done(Ok("not json".to_string())))
+            .unwrap();
+
+        assert!(popup.poll().is_none());
+        assert!(matches!(popup.outcome, Some(Err(_))));
+    }
+
+    #[test]
+    fn test_cancel_stops_streaming() {
+        let task = AiTask::ExplainError {
+            error: "boom".to_string(),
+        };
+        let (mut popup, sender) = streaming_popup(task);
+        sender.send(AiEvent::Chunk("partial".to_string())).unwrap();
+        popup.poll();
+        popup.cancel();
+
+        assert!(!popup.is_streaming());
+        assert!(popup.cancel.load(Ordering::Relaxed));
+        assert_eq!(
+            popup.outcome.as_ref().unwrap().as_ref().unwrap_err(),
+            crate::ai::CANCELLED_MESSAGE
+        );
+    }
+}
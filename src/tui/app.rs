@@ -1,13 +1,16 @@
 //! Application state for the TUI
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use anyhow::Result;
 
+use super::ai_popup::{AiOutcome, AiPopup, AiTask};
+use super::external::ExternalCommand;
 use crate::Update;
-use crate::config::{AiProvider, HoardConfig, SourcesConfig, TuiTheme, UsageMode};
-use crate::db::{Database, GitHubInfo, ToolUsage};
-use crate::models::{Bundle, InstallSource, Tool};
+use crate::config::{AiProvider, HoardConfig, OpenAction, SourcesConfig, TuiTheme, UsageMode};
+use crate::db::{Database, GitHubInfo, ToolUsage, UsageRhythm};
+use crate::health::ToolHealth;
+use crate::models::{Bundle, InstallSource, Tool, ToolKind};
 
 /// A search result from the Discover tab
 #[derive(Debug, Clone)]
@@ -17,6 +20,8 @@ pub struct DiscoverResult {
     pub source: DiscoverSource,
     pub stars: Option<u64>,
     pub url: Option<String>,
+    /// Tracked tool for this result, with whether it is installed
+    pub in_hoard: Option<(String, bool)>,
 }
 
 /// Source of a discover result
@@ -34,7 +39,7 @@ pub enum DiscoverSource {
 impl DiscoverSource {
     pub fn to_install_source(&self) -> InstallSource {
         match self {
-            DiscoverSource::GitHub => InstallSource::Unknown,
+            DiscoverSource::GitHub => InstallSource::GitHub,
             DiscoverSource::CratesIo => InstallSource::Cargo,
             DiscoverSource::PyPI => InstallSource::Pip,
             DiscoverSource::Npm => InstallSource::Npm,
@@ -45,6 +50,17 @@ impl DiscoverSource {
     }
 
     pub fn icon(&self) -> &'static str {
+        if crate::icons::ascii_mode() {
+            return match self {
+                DiscoverSource::GitHub => "[gh]",
+                DiscoverSource::CratesIo => "[rs]",
+                DiscoverSource::PyPI => "[py]",
+                DiscoverSource::Npm => "[js]",
+                DiscoverSource::Apt => "[ap]",
+                DiscoverSource::Homebrew => "[br]",
+                DiscoverSource::AI => "[ai]",
+            };
+        }
         match self {
             DiscoverSource::GitHub => "\u{f09b}", //
             DiscoverSource::CratesIo => "🦀",
@@ -102,24 +118,25 @@ impl ConfigSection {
     /// Get the starting line number for this section in the config menu.
     /// Used for click detection and auto-scroll.
     ///
-    /// Layout (without custom theme description):
-    /// - Lines 0-5: AI Provider (header + 5 options)
-    /// - Line 6: empty
-    /// - Lines 7-14: Theme (header + 7 options)
-    /// - Line 15: empty
-    /// - Lines 16-23: Sources (header + 7 options)
-    /// - Line 24: empty
-    /// - Lines 25-27: Usage (header + 2 options)
-    /// - Line 28: empty
-    /// - Line 29: Buttons
+    /// Layout (without custom theme description, N = number of AI providers):
+    /// - Lines 0..=N: AI Provider (header + N options)
+    /// - Line N+1: empty
+    /// - Lines N+2..=N+9: Theme (header + 7 options)
+    /// - Line N+10: empty
+    /// - Lines N+11..=N+18: Sources (header + 7 options)
+    /// - Line N+19: empty
+    /// - Lines N+20..=N+22: Usage (header + 2 options)
+    /// - Line N+23: empty
+    /// - Line N+24: Buttons
     pub fn start_line(&self, custom_theme_selected: bool) -> usize {
         let theme_extra = if custom_theme_selected { 1 } else { 0 };
+        let ai = AiProvider::all().len();
         match self {
             Self::AiProvider => 0,
-            Self::Theme => 7,
-            Self::Sources => 16 + theme_extra,
-            Self::UsageMode => 25 + theme_extra,
-            Self::Buttons => 29 + theme_extra,
+            Self::Theme => ai + 2,
+            Self::Sources => ai + 11 + theme_extra,
+            Self::UsageMode => ai + 20 + theme_extra,
+            Self::Buttons => ai + 24 + theme_extra,
         }
     }
 
@@ -127,31 +144,35 @@ impl ConfigSection {
     /// Returns (first_item_line, last_item_line) inclusive.
     pub fn item_lines(&self, custom_theme_selected: bool) -> (usize, usize) {
         let theme_extra = if custom_theme_selected { 1 } else { 0 };
+        let ai = AiProvider::all().len();
         match self {
-            Self::AiProvider => (1, 5),                              // 5 AI providers
-            Self::Theme => (8, 14),                                  // 7 themes (indices 0-6)
-            Self::Sources => (17 + theme_extra, 23 + theme_extra),   // 7 sources
-            Self::UsageMode => (26 + theme_extra, 27 + theme_extra), // 2 modes
-            Self::Buttons => (29 + theme_extra, 29 + theme_extra),   // 1 line
+            Self::AiProvider => (1, ai),     // one line per AI provider
+            Self::Theme => (ai + 3, ai + 9), // 7 themes (indices 0-6)
+            Self::Sources => (ai + 12 + theme_extra, ai + 18 + theme_extra), // 7 sources
+            Self::UsageMode => (ai + 21 + theme_extra, ai + 22 + theme_extra), // 2 modes
+            Self::Buttons => (ai + 24 + theme_extra, ai + 24 + theme_extra), // 1 line
         }
     }
 
     /// Number of selectable items in this section
     pub fn item_count(&self) -> usize {
         match self {
-            Self::AiProvider => 5, // None, Claude, Gemini, Codex, Opencode
-            Self::Theme => 7,      // 6 built-in + Custom
-            Self::Sources => 7,    // cargo, apt, pip, npm, brew, flatpak, manual
-            Self::UsageMode => 2,  // Scan, Hook
-            Self::Buttons => 2,    // Save, Cancel
+            Self::AiProvider => AiProvider::all().len(),
+            Self::Theme => 7,     // 6 built-in + Custom
+            Self::Sources => 7,   // cargo, apt, pip, npm, brew, flatpak, manual
+            Self::UsageMode => 2, // Scan, Hook
+            Self::Buttons => 2,   // Save, Cancel
         }
     }
 }
 
 /// Config menu layout constants
 pub mod config_menu_layout {
-    /// Base number of lines in config menu (without custom theme description)
-    pub const TOTAL_LINES_BASE: usize = 30;
+    use crate::config::AiProvider;
+
+    /// Number of lines in config menu besides the AI provider options
+    /// (without custom theme description)
+    pub const FIXED_LINES: usize = 26;
     /// Extra line when custom theme is selected (for file path hint)
     pub const CUSTOM_THEME_EXTRA_LINES: usize = 1;
     /// Index of custom theme
@@ -159,10 +180,11 @@ pub mod config_menu_layout {
 
     /// Calculate total lines based on whether custom theme is selected
     pub fn total_lines(custom_theme_selected: bool) -> usize {
+        let base = FIXED_LINES + AiProvider::all().len();
         if custom_theme_selected {
-            TOTAL_LINES_BASE + CUSTOM_THEME_EXTRA_LINES
+            base + CUSTOM_THEME_EXTRA_LINES
         } else {
-            TOTAL_LINES_BASE
+            base
         }
     }
 }
@@ -184,6 +206,10 @@ pub struct ConfigMenuState {
     pub button_focused: usize,
     /// Scroll offset for the config menu content
     pub scroll_offset: usize,
+    /// Result of the Ollama connectivity check (run when Ollama is selected)
+    pub ollama_status: Option<Result<usize, String>>,
+    /// AI calls and estimated tokens over the last 30 days
+    pub ai_usage: Option<(i64, i64)>,
 }
 
 impl Default for ConfigMenuState {
@@ -197,6 +223,8 @@ impl Default for ConfigMenuState {
             source_focused: 0,
             button_focused: 0, // Save
             scroll_offset: 0,
+            ollama_status: None,
+            ai_usage: None,
         }
     }
 }
@@ -219,12 +247,33 @@ impl ConfigMenuState {
             source_focused: 0,
             button_focused: 0,
             scroll_offset: 0,
+            ollama_status: None,
+            ai_usage: None,
         }
     }
 
+    /// Load the 30-day AI usage totals shown under the provider list
+    pub fn load_ai_usage(&mut self, db: &Database) {
+        let since = chrono::Utc::now() - chrono::Duration::days(30);
+        self.ai_usage = db.ai_usage_summary(Some(since)).ok().map(|summary| {
+            (
+                summary.iter().map(|s| s.calls).sum(),
+                summary.iter().map(|s| s.total_tokens()).sum(),
+            )
+        });
+    }
+
     /// Build config from current state
     pub fn to_config(&self) -> HoardConfig {
         let mut config = HoardConfig::default();
+        self.apply_to(&mut config);
+        config
+    }
+
+    /// Overlay the menu selections onto an existing config
+    ///
+    /// Settings the menu does not expose are left untouched.
+    pub fn apply_to(&self, config: &mut HoardConfig) {
         config.ai.provider = AiProvider::all()[self.ai_selected];
         config.tui.theme = TuiTheme::from_index(self.theme_selected);
         config.usage.mode = if self.usage_selected == 0 {
@@ -233,7 +282,6 @@ impl ConfigMenuState {
             UsageMode::Hook
         };
         config.sources = self.sources.clone();
-        config
     }
 
     /// Move to next item in current section
@@ -323,6 +371,11 @@ impl ConfigMenuState {
     }
 }
 
+/// Cache key of an AI discovery request (same as `hoards ai discover`)
+fn discovery_cache_key(query: &str, installed: &[String]) -> String {
+    crate::ai::cache::DISCOVERY.prompt_key(&crate::ai::discovery_prompt(query, installed))
+}
+
 /// Fuzzy match a query against a target string (fzf-style)
 /// Returns Some(score) if matches, None if no match
 /// Higher scores = better matches
@@ -499,6 +552,27 @@ impl Tab {
             _ => None,
         }
     }
+
+    /// Parse a tab from its name (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Tab> {
+        Tab::all()
+            .iter()
+            .find(|t| t.title().eq_ignore_ascii_case(name))
+            .copied()
+    }
+}
+
+/// Initial state requested on the command line (`hoards tui --tab ...`)
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    /// Tab to open
+    pub tab: Option<Tab>,
+    /// Search query to pre-fill
+    pub query: Option<String>,
+    /// Source filter to apply
+    pub filter: Option<String>,
+    /// Bundle to select (opens the Bundles tab)
+    pub bundle: Option<String>,
 }
 
 /// Input mode for the application
@@ -525,6 +599,25 @@ impl BackgroundOp {
     }
 }
 
+/// README of the selected tool, shown in a scrollable popup
+#[derive(Debug, Clone)]
+pub struct ReadmePopup {
+    pub tool: String,
+    /// Repository shown in the title, e.g. "owner/repo"
+    pub repo: String,
+    pub content: String,
+    /// Scroll offset in lines
+    pub scroll: u16,
+}
+
+/// Installs per month, shown as a chart with the selected month's tools
+#[derive(Debug, Clone)]
+pub struct TimelinePopup {
+    pub months: Vec<crate::db::TimelineMonth>,
+    /// Index of the month whose tools are listed
+    pub selected: usize,
+}
+
 /// Progress information for loading overlay
 #[derive(Debug, Clone, Default)]
 pub struct LoadingProgress {
@@ -549,6 +642,8 @@ pub enum PendingAction {
     Install(Vec<String>),   // Tool names to install
     Uninstall(Vec<String>), // Tool names to uninstall
     Update(Vec<String>),    // Tool names to update
+    /// Install the release binary of a repository (owner/repo)
+    InstallRelease(String),
 }
 
 /// Undoable action for history
@@ -643,12 +738,16 @@ impl PendingAction {
                     format!("Update {} tools?", tools.len())
                 }
             }
+            PendingAction::InstallRelease(repo) => {
+                format!("Install {} from its latest release?", repo)
+            }
         }
     }
 
     pub fn tools(&self) -> &[String] {
         match self {
             PendingAction::Install(t) | PendingAction::Uninstall(t) | PendingAction::Update(t) => t,
+            PendingAction::InstallRelease(repo) => std::slice::from_ref(repo),
         }
     }
 }
@@ -660,6 +759,30 @@ pub struct StatusMessage {
     pub is_error: bool,
 }
 
+/// Severity of a recorded notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Info,
+    Error,
+}
+
+impl NotificationLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationLevel::Info => "info",
+            NotificationLevel::Error => "error",
+        }
+    }
+}
+
+/// A status message kept in the notification history
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub text: String,
+    pub level: NotificationLevel,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+}
+
 /// Sort options for tool list
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SortBy {
@@ -712,6 +835,12 @@ pub const COMMANDS: &[(&str, &str)] = &[
     ("fav", "fav - toggle favorites filter"),
     ("favorites", "favorites - toggle favorites filter"),
     ("starred", "starred - toggle favorites filter"),
+    ("libs", "libs - show/hide library packages"),
+    ("libraries", "libraries - show/hide library packages"),
+    (
+        "project",
+        "project [path] - tools used in a project (toggle)",
+    ),
     ("1", "go to Installed tab"),
     ("installed", "go to Installed tab"),
     ("2", "go to Available tab"),
@@ -734,6 +863,35 @@ pub const COMMANDS: &[(&str, &str)] = &[
     ("z", "undo last action"),
     ("redo", "redo undone action"),
     ("y", "redo undone action"),
+    ("notifications", "show notification history"),
+    ("log", "show notification history"),
+    (
+        "open",
+        "open [action] - run an open-with action on selected tool",
+    ),
+    ("readme", "readme [refresh] - show README of selected tool"),
+    ("timeline", "timeline - installs per month"),
+    ("cheatsheet", "AI cheatsheet for selected tool"),
+    ("cs", "AI cheatsheet for selected tool"),
+    (
+        "ai-discover",
+        "ai-discover <query> - AI tool recommendations",
+    ),
+    ("topic", "topic <name> - discover GitHub repos by topic"),
+    ("explain", "ask AI to explain the last error"),
+    ("summarize", "AI digest of pending updates' release notes"),
+    (
+        "updates-summary",
+        "AI digest of pending updates' release notes",
+    ),
+    (
+        "a11y",
+        "toggle accessibility mode (ASCII icons, high contrast)",
+    ),
+    (
+        "ascii",
+        "toggle accessibility mode (ASCII icons, high contrast)",
+    ),
     ("c", "config - open configuration menu"),
     ("config", "open configuration menu"),
     ("settings", "open configuration menu"),
@@ -747,6 +905,9 @@ pub const COMMANDS: &[(&str, &str)] = &[
 // Extracted Components (reducing App god object)
 // ============================================================================
 
+/// Number of related tools shown in the details popup
+const RELATED_TOOLS_LIMIT: usize = 5;
+
 /// Manages cached data for the TUI (usage, GitHub info, labels)
 #[derive(Debug, Default)]
 pub struct CacheManager {
@@ -754,10 +915,20 @@ pub struct CacheManager {
     pub usage_data: HashMap<String, ToolUsage>,
     /// 7-day daily usage counts for sparklines
     pub daily_usage: HashMap<String, Vec<i64>>,
+    /// Weekday/hour usage buckets for the details heatmap
+    pub rhythms: HashMap<String, UsageRhythm>,
+    /// Most used subcommands and flags per tool
+    pub patterns: HashMap<String, Vec<(String, i64)>>,
     /// GitHub info cache (stars, description, etc.)
     pub github_cache: HashMap<String, GitHubInfo>,
     /// Labels/tags per tool
     pub labels_cache: HashMap<String, Vec<String>>,
+    /// Pinned tools with their pinned version, if any
+    pub pins_cache: HashMap<String, Option<String>>,
+    /// Most similar tools per tool (None = not in the embedding index)
+    pub related_cache: HashMap<String, Option<Vec<(String, f32)>>>,
+    /// Last computed health score per tool
+    pub health_cache: HashMap<String, ToolHealth>,
 }
 
 impl CacheManager {
@@ -765,18 +936,27 @@ impl CacheManager {
     pub fn new(db: &Database) -> Self {
         let usage_data = db.get_all_usage().unwrap_or_default().into_iter().collect();
         let daily_usage = db.get_all_daily_usage(7).unwrap_or_default();
+        let rhythms = db.get_all_usage_rhythms().unwrap_or_default();
+        let patterns = db.get_all_usage_patterns(5).unwrap_or_default();
         let github_cache = db
             .get_all_github_info()
             .unwrap_or_default()
             .into_iter()
             .collect();
         let labels_cache = db.get_all_tool_labels().unwrap_or_default();
+        let pins_cache = db.get_pins().unwrap_or_default();
+        let health_cache = db.get_tool_health().unwrap_or_default();
 
         Self {
             usage_data,
             daily_usage,
+            rhythms,
+            patterns,
             github_cache,
             labels_cache,
+            pins_cache,
+            related_cache: HashMap::new(),
+            health_cache,
         }
     }
 
@@ -795,12 +975,109 @@ impl CacheManager {
         self.github_cache.get(tool_name)
     }
 
+    /// Get related tools from the embedding index, computing on first use
+    ///
+    /// Only stored vectors are read, so this never calls an AI provider.
+    pub fn get_related(&mut self, tool_name: &str, db: &Database) -> Option<&[(String, f32)]> {
+        self.related_cache
+            .entry(tool_name.to_string())
+            .or_insert_with(|| {
+                crate::ai::embeddings::similar_tools(db, tool_name, RELATED_TOOLS_LIMIT)
+                    .unwrap_or_default()
+            })
+            .as_deref()
+    }
+
     /// Reload labels cache from database
     pub fn reload_labels(&mut self, db: &Database) {
         self.labels_cache = db.get_all_tool_labels().unwrap_or_default();
     }
 }
 
+/// Bounded history of status notifications for the notifications drawer
+#[derive(Debug, Default)]
+pub struct NotificationLog {
+    /// Recorded notifications, oldest first
+    entries: VecDeque<Notification>,
+    /// Maximum number of notifications kept
+    max_size: usize,
+    /// Scroll offset within the drawer (0 = newest)
+    pub scroll: usize,
+}
+
+impl NotificationLog {
+    /// Create a log that keeps at most `max_size` notifications
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_size,
+            scroll: 0,
+        }
+    }
+
+    /// Record a notification, dropping the oldest one when full
+    pub fn push(&mut self, text: impl Into<String>, level: NotificationLevel) {
+        if self.entries.len() >= self.max_size {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Notification {
+            text: text.into(),
+            level,
+            timestamp: chrono::Local::now(),
+        });
+    }
+
+    /// Iterate over notifications, newest first
+    pub fn newest_first(&self) -> impl Iterator<Item = &Notification> {
+        self.entries.iter().rev()
+    }
+
+    /// Number of recorded notifications
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no notifications were recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of recorded errors
+    pub fn error_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|n| n.level == NotificationLevel::Error)
+            .count()
+    }
+
+    /// Most recent error message, if any
+    pub fn last_error(&self) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|n| n.level == NotificationLevel::Error)
+            .map(|n| n.text.as_str())
+    }
+
+    /// Scroll towards older notifications
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.entries.len() {
+            self.scroll += 1;
+        }
+    }
+
+    /// Scroll towards newer notifications
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    /// Remove all notifications
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.scroll = 0;
+    }
+}
+
 /// Manages bundle list state and navigation
 #[derive(Debug, Default)]
 pub struct BundleState {
@@ -980,6 +1257,20 @@ impl CommandPalette {
     }
 }
 
+/// Tools used within one project, for the `:project` filter
+#[derive(Debug, Clone)]
+pub struct ProjectFilter {
+    pub root: String,
+    pub tools: HashSet<String>,
+}
+
+impl ProjectFilter {
+    /// Short name shown in the footer (the project directory's name)
+    pub fn name(&self) -> &str {
+        self.root.rsplit('/').next().unwrap_or(&self.root)
+    }
+}
+
 /// Main application state
 pub struct App {
     pub running: bool,
@@ -988,6 +1279,8 @@ pub struct App {
     pub search_query: String,
     pub source_filter: Option<String>, // Filter by source (cargo, apt, etc.)
     pub favorites_only: bool,          // Filter to show only favorites
+    pub include_libraries: bool,       // Also show pip/npm library packages
+    pub project_filter: Option<ProjectFilter>, // Tools used in a project (hook mode)
 
     // Tool list state
     pub all_tools: Vec<Tool>, // All tools for current tab (unfiltered)
@@ -1010,6 +1303,7 @@ pub struct App {
     pub show_details_popup: bool,
     pub sort_by: SortBy,
     pub theme_variant: super::theme::ThemeVariant,
+    pub accessible: bool, // ASCII icons + high-contrast colors
 
     // Multi-selection
     pub selected_tools: HashSet<String>,
@@ -1017,6 +1311,8 @@ pub struct App {
     // Actions
     pub pending_action: Option<PendingAction>,
     pub status_message: Option<StatusMessage>,
+    pub notifications: NotificationLog, // History of status messages
+    pub show_notifications: bool,
 
     // Background operations (executed by main loop with loading indicator)
     pub background_op: Option<BackgroundOp>,
@@ -1032,7 +1328,7 @@ pub struct App {
 
     // Feature availability status (for footer display)
     pub ai_available: bool, // AI provider is configured
-    pub gh_available: bool, // GitHub CLI is installed
+    pub gh_available: bool, // GitHub token is configured
 
     // Last sync timestamp
     pub last_sync: Option<chrono::DateTime<chrono::Utc>>,
@@ -1046,6 +1342,23 @@ pub struct App {
     // Config menu state
     pub show_config_menu: bool,
     pub config_menu: ConfigMenuState,
+
+    // External "open with" actions
+    pub open_actions: Vec<OpenAction>,
+    pub pending_external: Option<ExternalCommand>,
+
+    // Streaming AI popup (cheatsheet, discover, explain error)
+    pub ai_popup: Option<AiPopup>,
+
+    // README popup (served from the local cache when fresh)
+    pub readme_popup: Option<ReadmePopup>,
+
+    // Install timeline chart
+    pub timeline_popup: Option<TimelinePopup>,
+
+    // Bundle template picker (selected template index)
+    pub template_picker: Option<usize>,
+    pub templates_offered: bool, // Picker already opened on an empty Bundles tab
 }
 
 impl App {
@@ -1058,7 +1371,7 @@ impl App {
         let config_exists = HoardConfig::exists();
         let config = HoardConfig::load().unwrap_or_default();
         let ai_available = config.ai.provider != AiProvider::None;
-        let gh_available = which::which("gh").is_ok();
+        let gh_available = crate::forge::github::is_gh_available();
 
         // Get theme from config
         let theme_variant = super::theme::ThemeVariant::from_config_theme(config.tui.theme);
@@ -1066,7 +1379,9 @@ impl App {
         // Auto-show config menu if no config file exists
         let show_config_menu = !config_exists;
         let config_menu = if show_config_menu {
-            ConfigMenuState::from_config(&config)
+            let mut menu = ConfigMenuState::from_config(&config);
+            menu.load_ai_usage(db);
+            menu
         } else {
             ConfigMenuState::default()
         };
@@ -1078,6 +1393,8 @@ impl App {
             search_query: String::new(),
             source_filter: None,
             favorites_only: false,
+            include_libraries: false,
+            project_filter: None,
             all_tools,
             tools,
             selected_index: 0,
@@ -1092,9 +1409,12 @@ impl App {
             show_details_popup: false,
             sort_by: SortBy::default(),
             theme_variant,
+            accessible: crate::icons::ascii_mode(),
             selected_tools: HashSet::new(),
             pending_action: None,
             status_message: None,
+            notifications: NotificationLog::new(100), // Keep 100 notifications max
+            show_notifications: false,
             background_op: None,
             loading_progress: LoadingProgress::default(),
             history: ActionHistory::new(50), // Keep 50 actions max
@@ -1110,9 +1430,50 @@ impl App {
             discover_loading: false,
             show_config_menu,
             config_menu,
+            open_actions: config.tui.open_with.clone(),
+            pending_external: None,
+            ai_popup: None,
+            readme_popup: None,
+            timeline_popup: None,
+            template_picker: None,
+            templates_offered: false,
         })
     }
 
+    /// Apply command-line launch options (tab, query, filter, bundle)
+    pub fn apply_launch_options(&mut self, options: &LaunchOptions, db: &Database) {
+        if let Some(tab) = options.tab {
+            self.switch_tab(tab, db);
+        }
+
+        if let Some(ref name) = options.bundle {
+            self.switch_tab(Tab::Bundles, db);
+            let index = self.bundles.iter().position(|b| &b.name == name);
+            match index {
+                Some(index) => self.bundles.select(index),
+                None => self.set_status(format!("Bundle not found: {}", name), true),
+            }
+        }
+
+        if let Some(ref source) = options.filter {
+            self.set_source_filter(Some(source));
+        }
+
+        if let Some(ref query) = options.query {
+            if self.tab == Tab::Discover {
+                self.discover_query = query.clone();
+            } else {
+                self.search_query = query.clone();
+                self.apply_filter_and_sort();
+            }
+        }
+
+        // Deep-linking to Updates is only useful once updates have been checked
+        if self.tab == Tab::Updates && !self.updates_checked {
+            self.schedule_op(BackgroundOp::CheckUpdates { step: 0 });
+        }
+    }
+
     /// Quit the application
     pub fn quit(&mut self) {
         self.running = false;
@@ -1129,7 +1490,20 @@ impl App {
 
     /// Get the current theme
     pub fn theme(&self) -> super::theme::Theme {
-        self.theme_variant.theme()
+        let theme = self.theme_variant.theme();
+        if self.accessible {
+            theme.high_contrast()
+        } else {
+            theme
+        }
+    }
+
+    /// Toggle accessibility mode (ASCII icons and high-contrast colors)
+    pub fn toggle_accessible(&mut self) {
+        self.accessible = !self.accessible;
+        crate::icons::set_ascii_mode(self.accessible);
+        let state = if self.accessible { "on" } else { "off" };
+        self.set_status(format!("Accessibility mode: {}", state), false);
     }
 
     /// Switch to a specific tab
@@ -1141,6 +1515,11 @@ impl App {
             self.search_query.clear();
             self.refresh_tools(db);
         }
+
+        // First visit without bundles: offer the starter templates
+        if tab == Tab::Bundles && self.bundles.is_empty() && !self.templates_offered {
+            self.open_template_picker();
+        }
     }
 
     /// Go to next tab
@@ -1222,6 +1601,16 @@ impl App {
                 if self.favorites_only && !t.is_favorite {
                     return false;
                 }
+                // Hide libraries unless asked for
+                if !self.include_libraries && t.kind == ToolKind::Library {
+                    return false;
+                }
+                // Filter to tools used in a project
+                if let Some(ref project) = self.project_filter
+                    && !project.tools.contains(&t.name)
+                {
+                    return false;
+                }
                 true
             })
             .collect();
@@ -1266,21 +1655,20 @@ impl App {
         // Sort by fuzzy score when searching, otherwise by user preference
         if !self.search_query.is_empty() {
             // Sort by score descending (best matches first)
-            filtered.sort_by(|a, b| b.1.cmp(&a.1));
+            filtered.sort_by_key(|t| std::cmp::Reverse(t.1));
         } else {
             // Sort by user preference
             match self.sort_by {
                 SortBy::Name => filtered.sort_by(|a, b| a.0.name.cmp(&b.0.name)),
                 SortBy::Usage => {
                     let usage = &self.cache.usage_data;
-                    filtered.sort_by(|a, b| {
-                        let a_usage = usage.get(&a.0.name).map(|u| u.use_count).unwrap_or(0);
-                        let b_usage = usage.get(&b.0.name).map(|u| u.use_count).unwrap_or(0);
-                        b_usage.cmp(&a_usage) // Descending
+                    filtered.sort_by_key(|t| {
+                        // Descending
+                        std::cmp::Reverse(usage.get(&t.0.name).map(|u| u.use_count).unwrap_or(0))
                     });
                 }
                 SortBy::Recent => {
-                    filtered.sort_by(|a, b| b.0.updated_at.cmp(&a.0.updated_at));
+                    filtered.sort_by_key(|t| std::cmp::Reverse(t.0.updated_at));
                 }
             }
         }
@@ -1434,6 +1822,24 @@ impl App {
         self.bundles.last();
     }
 
+    /// Move Discover result selection down
+    pub fn select_next_discover(&mut self) {
+        if !self.discover_results.is_empty() {
+            self.discover_selected =
+                (self.discover_selected + 1).min(self.discover_results.len() - 1);
+        }
+    }
+
+    /// Move Discover result selection up
+    pub fn select_prev_discover(&mut self) {
+        self.discover_selected = self.discover_selected.saturating_sub(1);
+    }
+
+    /// Get the currently selected Discover result
+    pub fn selected_discover_result(&self) -> Option<&DiscoverResult> {
+        self.discover_results.get(self.discover_selected)
+    }
+
     /// Get the currently selected bundle
     pub fn selected_bundle(&self) -> Option<&Bundle> {
         self.bundles.selected_bundle()
@@ -1459,20 +1865,422 @@ impl App {
         self.cache.github_cache.get(tool_name)
     }
 
+    /// Find the configured open action bound to a key
+    pub fn open_action_for_key(&self, key: char) -> Option<OpenAction> {
+        self.open_actions
+            .iter()
+            .find(|action| action.key == Some(key))
+            .cloned()
+    }
+
+    /// Prepare an open action for the selected tool
+    ///
+    /// The command itself is run by the main loop, which suspends the
+    /// terminal first for interactive actions.
+    pub fn request_open_action(&mut self, action: &OpenAction, db: &Database) {
+        if matches!(self.tab, Tab::Bundles | Tab::Discover) {
+            self.set_status("Open actions apply to tools".to_string(), true);
+            return;
+        }
+        let Some(tool) = self.selected_tool().cloned() else {
+            self.set_status("No tool selected".to_string(), true);
+            return;
+        };
+
+        let github = self.get_github_info(&tool.name, db).cloned();
+        match ExternalCommand::from_action(action, &tool, github.as_ref()) {
+            Ok(command) => self.pending_external = Some(command),
+            Err(e) => self.set_status(e.to_string(), true),
+        }
+    }
+
+    /// Take the pending external command (called by the main loop)
+    pub fn take_external_command(&mut self) -> Option<ExternalCommand> {
+        self.pending_external.take()
+    }
+
+    // ==================== AI Popup ====================
+
+    /// Start an AI task in the streaming popup
+    fn start_ai_task(&mut self, task: AiTask) {
+        if !self.ai_available {
+            self.set_status(
+                "No AI provider configured (run 'hoards ai set <provider>')".to_string(),
+                true,
+            );
+            return;
+        }
+        self.ai_popup = Some(AiPopup::start(task));
+    }
+
+    /// Show a cheatsheet for the selected tool, generating it if not cached
+    pub fn open_cheatsheet(&mut self, db: &Database) {
+        if matches!(self.tab, Tab::Bundles | Tab::Discover) {
+            self.set_status("Cheatsheets apply to tools".to_string(), true);
+            return;
+        }
+        let Some(tool) = self.selected_tool() else {
+            self.set_status("No tool selected".to_string(), true);
+            return;
+        };
+
+        let binary = tool
+            .binary_name
+            .clone()
+            .unwrap_or_else(|| tool.name.clone());
+        let task = AiTask::Cheatsheet {
+            tool: tool.name.clone(),
+            binary,
+            description: tool.description.clone(),
+        };
+        if let AiTask::Cheatsheet { tool, binary, .. } = &task
+            && let Ok(Some(cheatsheet)) =
+                crate::commands::ai::get_cached_cheatsheet(db, tool, binary)
+        {
+            self.ai_popup = Some(AiPopup::finished(task, AiOutcome::Cheatsheet(cheatsheet)));
+            return;
+        }
+        self.start_ai_task(task);
+    }
+
+    /// Ask the AI for tool recommendations, reusing a fresh cached answer
+    pub fn start_ai_discover(&mut self, query: &str, db: &Database) {
+        let installed: Vec<String> = db
+            .list_tools(true, None)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        let key = discovery_cache_key(query, &installed);
+        let task = AiTask::Discover {
+            query: query.to_string(),
+            installed,
+        };
+        if let Some(discovery) = crate::ai::cache::DISCOVERY
+            .get(db, &key)
+            .and_then(|cached| crate::ai::parse_discovery_response(&cached).ok())
+        {
+            self.load_ai_discovery(query, discovery.clone());
+            self.ai_popup = Some(AiPopup::finished(task, AiOutcome::Discovery(discovery)));
+            return;
+        }
+        self.start_ai_task(task);
+    }
+
+    /// Show AI recommendations in the Discover tab
+    fn load_ai_discovery(&mut self, query: &str, discovery: crate::ai::DiscoveryResponse) {
+        self.discover_query = query.to_string();
+        self.discover_selected = 0;
+        self.discover_results = discovery
+            .tools
+            .into_iter()
+            .map(|t| DiscoverResult {
+                name: t.name,
+                description: Some(t.description),
+                source: DiscoverSource::AI,
+                stars: None,
+                url: t.github.map(|repo| format!("https://github.com/{}", repo)),
+                in_hoard: None,
+            })
+            .collect();
+    }
+
+    /// Show GitHub repositories with a topic in the Discover tab
+    pub fn discover_topic(&mut self, topic: &str, db: &Database) {
+        self.switch_tab(Tab::Discover, db);
+        self.discover_query = format!("topic:{}", topic);
+        self.discover_selected = 0;
+
+        match crate::commands::discover::search_topic(db, topic, 30) {
+            Ok(matches) => {
+                let in_hoard = matches.iter().filter(|m| m.tool.is_some()).count();
+                self.discover_results = matches
+                    .into_iter()
+                    .map(|m| DiscoverResult {
+                        url: Some(format!("https://github.com/{}", m.repo.full_name)),
+                        name: m.repo.full_name,
+                        description: m.repo.description,
+                        source: DiscoverSource::GitHub,
+                        stars: u64::try_from(m.repo.stars).ok(),
+                        in_hoard: m.tool.map(|t| (t.name, t.is_installed)),
+                    })
+                    .collect();
+                self.set_status(
+                    format!(
+                        "{} repos with topic '{}', {} already in your hoard",
+                        self.discover_results.len(),
+                        topic,
+                        in_hoard
+                    ),
+                    false,
+                );
+            }
+            Err(e) => {
+                self.discover_results.clear();
+                self.set_status(format!("Topic search failed: {}", e), true);
+            }
+        }
+    }
+
+    /// Ask the AI to explain the most recent error notification
+    pub fn explain_last_error(&mut self) {
+        match self.notifications.last_error() {
+            Some(error) => {
+                let error = error.to_string();
+                self.start_ai_task(AiTask::ExplainError { error });
+            }
+            None => self.set_status("No errors to explain".to_string(), false),
+        }
+    }
+
+    /// Summarize release notes of the pending updates
+    pub fn summarize_updates(&mut self, db: &Database) {
+        use crate::ai::changelog::{cached_notes, pending_updates, sort_notes};
+
+        if !self.updates_checked {
+            self.set_status("Check for updates first (r)".to_string(), true);
+            return;
+        }
+        if self.available_updates.is_empty() {
+            self.set_status("All tools are up to date".to_string(), false);
+            return;
+        }
+
+        let pending = match pending_updates(db, self.available_updates.values()) {
+            Ok(pending) => pending,
+            Err(e) => {
+                self.set_status(format!("Failed to load updates: {}", e), true);
+                return;
+            }
+        };
+        let (mut cached, pending) = cached_notes(db, pending);
+        if pending.is_empty() {
+            sort_notes(&mut cached);
+            let task = AiTask::UpdatesSummary {
+                pending,
+                cached: Vec::new(),
+            };
+            self.ai_popup = Some(AiPopup::finished(task, AiOutcome::Updates(cached)));
+            return;
+        }
+        self.start_ai_task(AiTask::UpdatesSummary { pending, cached });
+    }
+
+    /// Pull streamed output into the popup (called by the main loop)
+    ///
+    /// Completed cheatsheets, update summaries and discovery responses are
+    /// cached, and discovery results are loaded into the Discover tab.
+    pub fn poll_ai(&mut self, db: &Database) {
+        let Some(popup) = self.ai_popup.as_mut() else {
+            return;
+        };
+        let Some(outcome) = popup.poll() else {
+            return;
+        };
+
+        match (&popup.task, outcome) {
+            (AiTask::Cheatsheet { tool, binary, .. }, AiOutcome::Cheatsheet(cheatsheet)) => {
+                let _ = crate::commands::ai::cache_cheatsheet(db, tool, binary, &cheatsheet);
+            }
+            (AiTask::UpdatesSummary { pending, .. }, AiOutcome::Updates(notes)) => {
+                let _ = crate::ai::changelog::cache_notes(db, pending, &notes);
+            }
+            (AiTask::Discover { query, installed }, AiOutcome::Discovery(discovery)) => {
+                let query = query.clone();
+                let _ = crate::ai::cache::DISCOVERY.set(
+                    db,
+                    &discovery_cache_key(&query, installed),
+                    &popup.streamed,
+                );
+                self.load_ai_discovery(&query, discovery);
+            }
+            _ => {}
+        }
+    }
+
+    /// Cancel a running AI request, or close the popup once finished
+    pub fn dismiss_ai_popup(&mut self) {
+        match self.ai_popup.as_mut() {
+            Some(popup) if popup.is_streaming() => popup.cancel(),
+            _ => self.ai_popup = None,
+        }
+    }
+
+    // ==================== Timeline ====================
+
+    /// Show installs per month, starting on the latest month
+    pub fn open_timeline(&mut self, db: &Database) {
+        let categories: HashMap<String, String> = db
+            .list_tools(false, None)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|t| Some((t.name, t.category?)))
+            .collect();
+        match db.get_timeline_events() {
+            Ok(events) => {
+                let months = crate::db::monthly_timeline(&events, &categories);
+                if months.is_empty() {
+                    self.set_status("No installs recorded yet", false);
+                    return;
+                }
+                self.timeline_popup = Some(TimelinePopup {
+                    selected: months.len() - 1,
+                    months,
+                });
+            }
+            Err(e) => self.set_status(format!("Failed to load timeline: {}", e), true),
+        }
+    }
+
+    /// Close the timeline chart
+    pub fn close_timeline(&mut self) {
+        self.timeline_popup = None;
+    }
+
+    /// Move the selected month by `delta` months
+    pub fn move_timeline_selection(&mut self, delta: isize) {
+        if let Some(popup) = self.timeline_popup.as_mut() {
+            let last = popup.months.len().saturating_sub(1);
+            popup.selected = popup.selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    // ==================== Template Picker ====================
+
+    /// Show the bundle template picker
+    pub fn open_template_picker(&mut self) {
+        self.templates_offered = true;
+        self.template_picker = Some(0);
+    }
+
+    /// Close the template picker
+    pub fn close_template_picker(&mut self) {
+        self.template_picker = None;
+    }
+
+    /// Move the picker selection by `delta` templates
+    pub fn move_template_selection(&mut self, delta: isize) {
+        if let Some(selected) = self.template_picker.as_mut() {
+            let last = crate::BUNDLE_TEMPLATES.len().saturating_sub(1);
+            *selected = selected.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    /// Create a bundle from the selected template and select it
+    pub fn apply_selected_template(&mut self, db: &Database) {
+        let Some(template) = self
+            .template_picker
+            .take()
+            .and_then(|i| crate::BUNDLE_TEMPLATES.get(i))
+        else {
+            return;
+        };
+
+        match crate::commands::create_bundle_from_template(db, template, template.name) {
+            Ok(added) => {
+                let _ = self.bundles.reload(db);
+                let index = self.bundles.iter().position(|b| b.name == template.name);
+                if let Some(index) = index {
+                    self.bundles.select(index);
+                }
+                let tracked = if added > 0 {
+                    format!(", tracking {} new tool(s)", added)
+                } else {
+                    String::new()
+                };
+                self.set_status(
+                    format!(
+                        "Created bundle '{}'{} - press i to install",
+                        template.name, tracked
+                    ),
+                    false,
+                );
+            }
+            Err(e) => self.set_status(format!("Template failed: {}", e), true),
+        }
+    }
+
+    // ==================== README Popup ====================
+
+    /// Show the README of the selected tool
+    ///
+    /// Uses the cached copy while fresh; `refresh` always asks the forge.
+    pub fn open_readme(&mut self, db: &Database, refresh: bool) {
+        if matches!(self.tab, Tab::Bundles | Tab::Discover) {
+            self.set_status("READMEs apply to tools".to_string(), true);
+            return;
+        }
+        let Some(tool) = self.selected_tool().map(|t| t.name.clone()) else {
+            self.set_status("No tool selected".to_string(), true);
+            return;
+        };
+        let Some(info) = db.get_github_info(&tool).ok().flatten() else {
+            self.set_status(
+                format!("No repository known for {} (run 'hoards gh fetch')", tool),
+                true,
+            );
+            return;
+        };
+
+        let repo = info.repo_ref();
+        match crate::commands::github::load_readme(db, &repo, refresh) {
+            Ok(content) => {
+                let scroll = match &self.readme_popup {
+                    Some(popup) if refresh && popup.tool == tool => popup.scroll,
+                    _ => 0,
+                };
+                self.readme_popup = Some(ReadmePopup {
+                    tool,
+                    repo: format!("{}/{}", repo.owner, repo.repo),
+                    content,
+                    scroll,
+                });
+            }
+            Err(e) => self.set_status(format!("Failed to load README: {}", e), true),
+        }
+    }
+
+    /// Close the README popup
+    pub fn close_readme(&mut self) {
+        self.readme_popup = None;
+    }
+
     /// Toggle help overlay
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
 
     /// Open config menu
-    pub fn open_config_menu(&mut self) {
+    pub fn open_config_menu(&mut self, db: &Database) {
         // Load current config and initialize menu state
         if let Ok(config) = HoardConfig::load() {
             self.config_menu = ConfigMenuState::from_config(&config);
         } else {
             self.config_menu = ConfigMenuState::default();
         }
+        self.config_menu.load_ai_usage(db);
         self.show_config_menu = true;
+        self.check_ollama_if_selected();
+    }
+
+    /// Live preview of the AI provider selected in the config menu
+    pub fn preview_ai_provider(&mut self) {
+        self.ai_available = AiProvider::all()[self.config_menu.ai_selected] != AiProvider::None;
+        self.check_ollama_if_selected();
+    }
+
+    /// Run a one-off Ollama connectivity check when Ollama is selected,
+    /// so the menu can show whether the local server is reachable
+    fn check_ollama_if_selected(&mut self) {
+        let provider = AiProvider::all()[self.config_menu.ai_selected];
+        if provider == AiProvider::Ollama && self.config_menu.ollama_status.is_none() {
+            let ollama = HoardConfig::load().unwrap_or_default().ai.ollama;
+            self.config_menu.ollama_status = Some(
+                crate::ai::ollama::list_models(&ollama)
+                    .map(|models| models.len())
+                    .map_err(|_| format!("not reachable at {}", ollama.host)),
+            );
+        }
     }
 
     /// Close config menu without saving (reverts any preview changes)
@@ -1487,7 +2295,8 @@ impl App {
 
     /// Save config from menu and close
     pub fn save_config_menu(&mut self) {
-        let config = self.config_menu.to_config();
+        let mut config = HoardConfig::load().unwrap_or_default();
+        self.config_menu.apply_to(&mut config);
 
         // Apply theme immediately
         self.theme_variant = super::theme::ThemeVariant::from_config_theme(config.tui.theme);
@@ -1740,6 +2549,24 @@ impl App {
                 self.exit_command();
             }
 
+            // Library packages
+            "libs" | "libraries" => {
+                self.toggle_libraries_filter();
+                self.exit_command();
+            }
+
+            // Project filter (path keeps its case)
+            "project" | "proj" => {
+                let path = self
+                    .command
+                    .input
+                    .split_whitespace()
+                    .nth(1)
+                    .map(String::from);
+                self.set_project_filter(db, path.as_deref());
+                self.exit_command();
+            }
+
             // Tab navigation
             "installed" | "1" => {
                 self.switch_tab(Tab::Installed, db);
@@ -1790,9 +2617,91 @@ impl App {
                 self.exit_command();
             }
 
+            // Notification history
+            "notifications" | "log" => {
+                self.show_notifications = true;
+                self.notifications.scroll = 0;
+                self.exit_command();
+            }
+
+            // External open-with actions
+            "open" => {
+                match parts.get(1) {
+                    Some(name) => {
+                        let action = self
+                            .open_actions
+                            .iter()
+                            .find(|a| a.name.eq_ignore_ascii_case(name))
+                            .cloned();
+                        match action {
+                            Some(action) => self.request_open_action(&action, db),
+                            None => self.set_status(format!("Unknown open action: {}", name), true),
+                        }
+                    }
+                    None => {
+                        let names: Vec<String> = self
+                            .open_actions
+                            .iter()
+                            .map(|a| match a.key {
+                                Some(key) => format!("{} ({})", a.name, key),
+                                None => a.name.clone(),
+                            })
+                            .collect();
+                        self.set_status(format!("Open actions: {}", names.join(", ")), false);
+                    }
+                }
+                self.exit_command();
+            }
+
+            "readme" => {
+                self.open_readme(db, parts.get(1) == Some(&"refresh"));
+                self.exit_command();
+            }
+
+            "timeline" => {
+                self.open_timeline(db);
+                self.exit_command();
+            }
+
+            // AI popups
+            "cheatsheet" | "cs" => {
+                self.open_cheatsheet(db);
+                self.exit_command();
+            }
+            "ai-discover" => {
+                if parts.len() > 1 {
+                    let query = parts[1..].join(" ");
+                    self.start_ai_discover(&query, db);
+                } else {
+                    self.set_status("Usage: ai-discover <query>".to_string(), true);
+                }
+                self.exit_command();
+            }
+            "topic" => {
+                match parts.get(1) {
+                    Some(topic) => self.discover_topic(topic, db),
+                    None => self.set_status("Usage: topic <name>".to_string(), true),
+                }
+                self.exit_command();
+            }
+            "explain" => {
+                self.explain_last_error();
+                self.exit_command();
+            }
+            "summarize" | "updates-summary" => {
+                self.summarize_updates(db);
+                self.exit_command();
+            }
+
+            // Accessibility
+            "a11y" | "ascii" => {
+                self.toggle_accessible();
+                self.exit_command();
+            }
+
             // Config
             "c" | "config" | "settings" | "cfg" => {
-                self.open_config_menu();
+                self.open_config_menu(db);
                 self.exit_command();
             }
 
@@ -1932,6 +2841,55 @@ impl App {
         self.apply_filter_and_sort();
     }
 
+    /// Show only tools used in a project, or clear the filter
+    ///
+    /// Without a path, clears an active filter or uses the current directory.
+    pub fn set_project_filter(&mut self, db: &Database, path: Option<&str>) {
+        if path.is_none() && self.project_filter.take().is_some() {
+            self.set_status("Project filter cleared".to_string(), false);
+            self.apply_filter_and_sort();
+            return;
+        }
+
+        let dir = match path {
+            Some(p) => std::path::Path::new(p).canonicalize(),
+            None => std::env::current_dir(),
+        };
+        let dir = match dir {
+            Ok(d) => d,
+            Err(e) => {
+                self.set_status(format!("Cannot open {}: {}", path.unwrap_or("."), e), true);
+                return;
+            }
+        };
+        let root = crate::project::project_root(&dir)
+            .to_string_lossy()
+            .to_string();
+
+        let tools: HashSet<String> = db
+            .get_project_usage(&root)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|u| u.tool)
+            .collect();
+        if tools.is_empty() {
+            self.set_status(format!("No usage recorded in {} (hook mode)", root), true);
+            return;
+        }
+
+        let filter = ProjectFilter { root, tools };
+        self.set_status(
+            format!(
+                "Filter: project={} ({} tools)",
+                filter.name(),
+                filter.tools.len()
+            ),
+            false,
+        );
+        self.project_filter = Some(filter);
+        self.apply_filter_and_sort();
+    }
+
     /// Toggle favorites-only filter
     pub fn toggle_favorites_filter(&mut self) {
         self.favorites_only = !self.favorites_only;
@@ -1944,6 +2902,18 @@ impl App {
         self.apply_filter_and_sort();
     }
 
+    /// Toggle showing library packages alongside applications
+    pub fn toggle_libraries_filter(&mut self) {
+        self.include_libraries = !self.include_libraries;
+        let status = if self.include_libraries {
+            "Showing libraries"
+        } else {
+            "Hiding libraries"
+        };
+        self.set_status(status.to_string(), false);
+        self.apply_filter_and_sort();
+    }
+
     // ==================== Selection ====================
 
     /// Toggle selection of current tool
@@ -2208,6 +3178,63 @@ impl App {
         }
     }
 
+    /// Request installing the selected Discover result from its release binaries
+    pub fn request_discover_install(&mut self) {
+        let Some(result) = self.selected_discover_result() else {
+            return;
+        };
+        if let Some((name, true)) = &result.in_hoard {
+            let status = format!("{} is already installed", name);
+            self.set_status(status, false);
+            return;
+        }
+        let repo = match (&result.source, &result.url) {
+            (DiscoverSource::GitHub | DiscoverSource::AI, Some(url)) => url
+                .strip_prefix("https://github.com/")
+                .map(|repo| repo.trim_end_matches('/').to_string()),
+            _ => None,
+        };
+        match repo {
+            Some(repo) => self.pending_action = Some(PendingAction::InstallRelease(repo)),
+            None => self.set_status(
+                "Only GitHub results can be installed from release binaries".to_string(),
+                true,
+            ),
+        }
+    }
+
+    /// Install a repository's release binary for this platform
+    pub fn install_release(&mut self, repo: &str, db: &Database) {
+        use crate::commands::github::{find_release_asset, install_release};
+
+        let result = crate::forge::parse_repo_url(repo).and_then(|repo_ref| {
+            let (release, asset) = find_release_asset(&repo_ref, None)?;
+            let name = repo_ref.repo.to_lowercase();
+            let path = install_release(db, &repo_ref, &release, &asset, &name, None)?;
+            Ok((name, release.tag_name, path))
+        });
+
+        match result {
+            Ok((name, tag, path)) => {
+                for result in &mut self.discover_results {
+                    if result
+                        .url
+                        .as_deref()
+                        .and_then(|u| u.strip_prefix("https://github.com/"))
+                        == Some(repo)
+                    {
+                        result.in_hoard = Some((name.clone(), true));
+                    }
+                }
+                self.set_status(
+                    format!("Installed {} {} to {}", name, tag, path.display()),
+                    false,
+                );
+            }
+            Err(e) => self.set_status(format!("Install failed: {}", e), true),
+        }
+    }
+
     /// Request uninstall action for selected tools (or current tool if none selected)
     pub fn request_uninstall(&mut self) {
         let tools = if self.selected_tools.is_empty() {
@@ -2262,10 +3289,17 @@ impl App {
             return;
         };
 
-        // Find tools that aren't installed
+        // Find tools for this machine that aren't installed, including nested bundles
+        let bundle = db
+            .get_flattened_bundle(&bundle.name)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| bundle.clone());
+        let machine = crate::models::Machine::current();
         let missing_tools: Vec<String> = bundle
             .tools
             .iter()
+            .filter(|name| bundle.applies(name, &machine))
             .filter(|name| {
                 !db.get_tool_by_name(name)
                     .ok()
@@ -2338,12 +3372,22 @@ impl App {
         self.pending_action.is_some()
     }
 
-    /// Set a status message
+    /// Set a status message (also recorded in the notification history)
     pub fn set_status(&mut self, text: impl Into<String>, is_error: bool) {
-        self.status_message = Some(StatusMessage {
-            text: text.into(),
-            is_error,
-        });
+        let text = text.into();
+        let level = if is_error {
+            NotificationLevel::Error
+        } else {
+            NotificationLevel::Info
+        };
+        self.notifications.push(text.clone(), level);
+        self.status_message = Some(StatusMessage { text, is_error });
+    }
+
+    /// Toggle the notifications drawer
+    pub fn toggle_notifications(&mut self) {
+        self.show_notifications = !self.show_notifications;
+        self.notifications.scroll = 0;
     }
 
     /// Clear status message
@@ -2582,6 +3626,123 @@ mod tests {
         assert!(app.status_message.as_ref().unwrap().is_error);
     }
 
+    // ==================== Launch Options Tests ====================
+
+    #[test]
+    fn test_tab_from_name() {
+        assert_eq!(Tab::from_name("updates"), Some(Tab::Updates));
+        assert_eq!(Tab::from_name("Bundles"), Some(Tab::Bundles));
+        assert_eq!(Tab::from_name("nope"), None);
+    }
+
+    #[test]
+    fn test_apply_launch_options_query_and_filter() {
+        let db = Database::open_in_memory().unwrap();
+        db.insert_tool(
+            &Tool::new("ripgrep")
+                .with_source(InstallSource::Cargo)
+                .installed(),
+        )
+        .unwrap();
+        db.insert_tool(&Tool::new("bat").with_source(InstallSource::Apt).installed())
+            .unwrap();
+        let mut app = App::new(&db).unwrap();
+
+        let options = LaunchOptions {
+            tab: Some(Tab::Installed),
+            query: Some("rip".to_string()),
+            filter: Some("cargo".to_string()),
+            bundle: None,
+        };
+        app.apply_launch_options(&options, &db);
+
+        assert_eq!(app.search_query, "rip");
+        assert_eq!(app.source_filter.as_deref(), Some("cargo"));
+        assert_eq!(app.tools.len(), 1);
+        assert_eq!(app.tools[0].name, "ripgrep");
+    }
+
+    #[test]
+    fn test_apply_launch_options_bundle() {
+        let db = Database::open_in_memory().unwrap();
+        db.create_bundle(&Bundle::new("alpha", vec!["a".to_string()]))
+            .unwrap();
+        db.create_bundle(&Bundle::new("devtools", vec!["b".to_string()]))
+            .unwrap();
+        let mut app = App::new(&db).unwrap();
+
+        let options = LaunchOptions {
+            bundle: Some("devtools".to_string()),
+            ..Default::default()
+        };
+        app.apply_launch_options(&options, &db);
+
+        assert_eq!(app.tab, Tab::Bundles);
+        assert_eq!(app.selected_bundle().unwrap().name, "devtools");
+
+        let missing = LaunchOptions {
+            bundle: Some("missing".to_string()),
+            ..Default::default()
+        };
+        app.apply_launch_options(&missing, &db);
+        assert!(app.status_message.as_ref().unwrap().is_error);
+    }
+
+    // ==================== Notification Tests ====================
+
+    #[test]
+    fn test_set_status_records_notification() {
+        let db = Database::open_in_memory().unwrap();
+        let mut app = App::new(&db).unwrap();
+
+        app.set_status("first", false);
+        app.set_status("second", true);
+
+        let texts: Vec<_> = app
+            .notifications
+            .newest_first()
+            .map(|n| n.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["second", "first"]);
+        assert_eq!(app.notifications.error_count(), 1);
+
+        // Clearing the status message keeps the history
+        app.clear_status();
+        assert_eq!(app.notifications.len(), 2);
+    }
+
+    #[test]
+    fn test_notification_log_max_size() {
+        let mut log = NotificationLog::new(3);
+        for i in 0..5 {
+            log.push(format!("msg {}", i), NotificationLevel::Info);
+        }
+
+        assert_eq!(log.len(), 3);
+        assert_eq!(log.newest_first().next().unwrap().text, "msg 4");
+        assert_eq!(log.newest_first().last().unwrap().text, "msg 2");
+    }
+
+    #[test]
+    fn test_notification_log_scroll_bounds() {
+        let mut log = NotificationLog::new(10);
+        log.scroll_down();
+        assert_eq!(log.scroll, 0);
+
+        log.push("a", NotificationLevel::Info);
+        log.push("b", NotificationLevel::Error);
+        log.scroll_down();
+        log.scroll_down();
+        assert_eq!(log.scroll, 1);
+
+        log.scroll_up();
+        log.scroll_up();
+        assert_eq!(log.scroll, 0);
+
+        log.clear();
+        assert!(log.is_empty());
+    }
+
     // ==================== Undo/Redo Tests ====================
 
     #[test]
@@ -2860,7 +4021,7 @@ mod tests {
         app.ai_available = true;
 
         // Open config menu
-        app.open_config_menu();
+        app.open_config_menu(&db);
         assert!(app.show_config_menu);
 
         // Verify we're on AI Provider section
@@ -2888,18 +4049,50 @@ mod tests {
         assert!(config.ai.provider != AiProvider::None);
     }
 
+    #[test]
+    fn test_config_menu_apply_preserves_other_fields() {
+        let mut config = HoardConfig::default();
+        config.tui.accessible = true;
+
+        let menu = ConfigMenuState::from_config(&config);
+        let mut updated = config.clone();
+        menu.apply_to(&mut updated);
+
+        assert!(updated.tui.accessible);
+    }
+
+    #[test]
+    fn test_accessible_theme_is_high_contrast() {
+        use ratatui::style::Color;
+
+        let db = Database::open_in_memory().unwrap();
+        let mut app = App::new(&db).unwrap();
+        app.accessible = false;
+        app.theme_variant = super::super::theme::ThemeVariant::CatppuccinMocha;
+        let normal = app.theme();
+
+        app.accessible = true;
+        let contrast = app.theme();
+        assert_eq!(contrast.base, Color::Rgb(0, 0, 0));
+        assert_eq!(contrast.text, Color::Rgb(255, 255, 255));
+        assert_ne!(normal.base, contrast.base);
+    }
+
     #[test]
     fn test_ai_provider_all_indices() {
         use crate::config::AiProvider;
 
         // Verify the indices in AiProvider::all() match expectations
         let all = AiProvider::all();
-        assert_eq!(all.len(), 5);
+        assert_eq!(all.len(), 8);
         assert_eq!(all[0], AiProvider::None);
         assert_eq!(all[1], AiProvider::Claude);
         assert_eq!(all[2], AiProvider::Gemini);
         assert_eq!(all[3], AiProvider::Codex);
         assert_eq!(all[4], AiProvider::Opencode);
+        assert_eq!(all[5], AiProvider::OpenAi);
+        assert_eq!(all[6], AiProvider::Ollama);
+        assert_eq!(all[7], AiProvider::Anthropic);
     }
 
     #[test]
@@ -2913,7 +4106,7 @@ mod tests {
         app.ai_available = true;
 
         // Open config menu
-        app.open_config_menu();
+        app.open_config_menu(&db);
 
         // Change to None (index 0)
         app.config_menu.ai_selected = 0;
@@ -40,7 +40,7 @@ fn handle_key_event(app: &mut App, key: KeyEvent, db: &Database) {
         return;
     }
 
-    // Handle overlays (help, config menu, and details popup)
+    // Handle overlays (help, config menu, details popup, and notifications)
     if app.show_help {
         if matches!(
             key.code,
@@ -56,6 +56,62 @@ fn handle_key_event(app: &mut App, key: KeyEvent, db: &Database) {
         return;
     }
 
+    if let Some(popup) = app.ai_popup.as_mut() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.dismiss_ai_popup(),
+            KeyCode::Enter if !popup.is_streaming() => app.dismiss_ai_popup(),
+            KeyCode::Char('j') | KeyCode::Down => popup.scroll_down(),
+            KeyCode::Char('k') | KeyCode::Up => popup.scroll_up(),
+            KeyCode::Char('g') => {
+                popup.follow = false;
+                popup.scroll = 0;
+            }
+            KeyCode::Char('G') => popup.follow = true,
+            _ => {}
+        }
+        return;
+    }
+
+    if let Some(popup) = app.readme_popup.as_mut() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.close_readme(),
+            KeyCode::Char('j') | KeyCode::Down => popup.scroll = popup.scroll.saturating_add(1),
+            KeyCode::Char('k') | KeyCode::Up => popup.scroll = popup.scroll.saturating_sub(1),
+            KeyCode::PageDown | KeyCode::Char(' ') => {
+                popup.scroll = popup.scroll.saturating_add(10)
+            }
+            KeyCode::PageUp => popup.scroll = popup.scroll.saturating_sub(10),
+            KeyCode::Char('g') => popup.scroll = 0,
+            KeyCode::Char('G') => popup.scroll = u16::MAX,
+            KeyCode::Char('r') => app.open_readme(db, true),
+            _ => {}
+        }
+        return;
+    }
+
+    if app.timeline_popup.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => app.close_timeline(),
+            KeyCode::Char('j') | KeyCode::Down => app.move_timeline_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => app.move_timeline_selection(-1),
+            KeyCode::Char('g') => app.move_timeline_selection(isize::MIN),
+            KeyCode::Char('G') => app.move_timeline_selection(isize::MAX),
+            _ => {}
+        }
+        return;
+    }
+
+    if app.template_picker.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_template_picker(),
+            KeyCode::Enter => app.apply_selected_template(db),
+            KeyCode::Char('j') | KeyCode::Down => app.move_template_selection(1),
+            KeyCode::Char('k') | KeyCode::Up => app.move_template_selection(-1),
+            _ => {}
+        }
+        return;
+    }
+
     if app.show_details_popup {
         if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
             app.close_details_popup();
@@ -63,6 +119,11 @@ fn handle_key_event(app: &mut App, key: KeyEvent, db: &Database) {
         return;
     }
 
+    if app.show_notifications {
+        handle_notifications_drawer(app, key);
+        return;
+    }
+
     // Clear status message on any key press
     app.clear_status();
 
@@ -82,6 +143,16 @@ fn handle_jump_mode(app: &mut App, key: KeyEvent) {
     }
 }
 
+fn handle_notifications_drawer(app: &mut App, key: KeyEvent) {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => app.toggle_notifications(),
+        KeyCode::Char('j') | KeyCode::Down => app.notifications.scroll_down(),
+        KeyCode::Char('k') | KeyCode::Up => app.notifications.scroll_up(),
+        KeyCode::Char('c') => app.notifications.clear(),
+        _ => {}
+    }
+}
+
 fn handle_config_menu(app: &mut App, key: KeyEvent) {
     use super::app::ConfigSection;
     use crate::config::TuiTheme;
@@ -103,10 +174,7 @@ fn handle_config_menu(app: &mut App, key: KeyEvent) {
                     let theme = TuiTheme::from_index(app.config_menu.theme_selected);
                     app.theme_variant = super::theme::ThemeVariant::from_config_theme(theme);
                 }
-                ConfigSection::AiProvider => {
-                    // Live preview AI provider status indicator
-                    app.ai_available = app.config_menu.ai_selected != 0; // 0 = None
-                }
+                ConfigSection::AiProvider => app.preview_ai_provider(),
                 _ => {}
             }
         }
@@ -118,24 +186,19 @@ fn handle_config_menu(app: &mut App, key: KeyEvent) {
                     let theme = TuiTheme::from_index(app.config_menu.theme_selected);
                     app.theme_variant = super::theme::ThemeVariant::from_config_theme(theme);
                 }
-                ConfigSection::AiProvider => {
-                    // Live preview AI provider status indicator
-                    app.ai_available = app.config_menu.ai_selected != 0; // 0 = None
-                }
+                ConfigSection::AiProvider => app.preview_ai_provider(),
                 _ => {}
             }
         }
 
         // Left/right navigation for buttons
-        KeyCode::Char('h') | KeyCode::Left => {
-            if app.config_menu.section == ConfigSection::Buttons {
-                app.config_menu.button_focused = 0; // Save
-            }
+        KeyCode::Char('h') | KeyCode::Left if app.config_menu.section == ConfigSection::Buttons => {
+            app.config_menu.button_focused = 0; // Save
         }
-        KeyCode::Char('l') | KeyCode::Right => {
-            if app.config_menu.section == ConfigSection::Buttons {
-                app.config_menu.button_focused = 1; // Cancel
-            }
+        KeyCode::Char('l') | KeyCode::Right
+            if app.config_menu.section == ConfigSection::Buttons =>
+        {
+            app.config_menu.button_focused = 1; // Cancel
         }
 
         // Toggle checkbox / select radio / activate button
@@ -167,6 +230,8 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
         KeyCode::Char('j') | KeyCode::Down => {
             if app.tab == Tab::Bundles {
                 app.select_next_bundle();
+            } else if app.tab == Tab::Discover {
+                app.select_next_discover();
             } else {
                 app.select_next();
             }
@@ -174,6 +239,8 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
         KeyCode::Char('k') | KeyCode::Up => {
             if app.tab == Tab::Bundles {
                 app.select_prev_bundle();
+            } else if app.tab == Tab::Discover {
+                app.select_prev_discover();
             } else {
                 app.select_prev();
             }
@@ -218,6 +285,8 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
         KeyCode::Char('/') => app.enter_search(),
 
         // Search navigation (n/N for next/prev match with wrapping)
+        // Without an active filter, n opens the notification history instead
+        KeyCode::Char('n') if app.search_query.is_empty() => app.toggle_notifications(),
         KeyCode::Char('n') => app.search_next(),
         KeyCode::Char('N') => app.search_prev(),
 
@@ -251,19 +320,26 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
         KeyCode::Char('i') => {
             if app.tab == Tab::Bundles {
                 app.request_bundle_install(db);
+            } else if app.tab == Tab::Discover {
+                app.request_discover_install();
             } else {
                 app.request_install();
             }
         }
+        KeyCode::Char('T') if app.tab == Tab::Bundles => app.open_template_picker(),
         KeyCode::Char('a') if app.tab == Tab::Bundles => {
             app.track_bundle_tools(db); // Add missing bundle tools to Available
         }
         KeyCode::Char('D') => app.request_uninstall(), // Shift+d for uninstall (safer)
         KeyCode::Char('u') => app.request_update(),    // Update tools with available updates
+        KeyCode::Char('S') if app.tab == Tab::Updates => app.summarize_updates(db),
 
         // Details popup (for narrow terminals or quick view)
         KeyCode::Enter => app.toggle_details_popup(),
 
+        // README of the selected tool
+        KeyCode::Char('R') => app.open_readme(db, false),
+
         // Help
         KeyCode::Char('?') => app.toggle_help(),
 
@@ -271,7 +347,7 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
         KeyCode::Char('t') => app.cycle_theme(),
 
         // Config menu
-        KeyCode::Char('c') => app.open_config_menu(),
+        KeyCode::Char('c') => app.open_config_menu(db),
 
         // Undo/redo
         KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => app.undo(),
@@ -287,6 +363,13 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
             }
         }
 
+        // User-defined open-with actions (only keys not bound above)
+        KeyCode::Char(c) if key.modifiers.difference(KeyModifiers::SHIFT).is_empty() => {
+            if let Some(action) = app.open_action_for_key(c) {
+                app.request_open_action(&action, db);
+            }
+        }
+
         _ => {}
     }
 }
@@ -331,7 +414,12 @@ fn handle_mouse_event(app: &mut App, mouse: crossterm::event::MouseEvent, db: &D
     }
 
     // Don't handle mouse during overlays or special modes
-    if app.show_help || app.show_details_popup || app.has_pending_action() {
+    if app.show_help
+        || app.show_details_popup
+        || app.show_notifications
+        || app.ai_popup.is_some()
+        || app.has_pending_action()
+    {
         return;
     }
 
@@ -441,6 +529,7 @@ fn handle_config_menu_mouse(app: &mut App, mouse: crossterm::event::MouseEvent)
                     let item = clicked_line - ai_start;
                     if item < ConfigSection::AiProvider.item_count() {
                         app.config_menu.ai_selected = item;
+                        app.preview_ai_provider();
                     }
                 } else if clicked_line >= theme_start && clicked_line <= theme_end {
                     // Theme item clicked
@@ -535,6 +624,7 @@ fn execute_action(app: &mut App, action: &PendingAction, db: &Database) {
             }
             app.clear_selection();
         }
+        PendingAction::InstallRelease(repo) => app.install_release(repo, db),
     }
     // Refresh tools list after action
     app.refresh_tools(db);
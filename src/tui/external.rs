@@ -0,0 +1,175 @@
+//! External "open with" commands launched from the TUI
+//!
+//! Commands come from `tui.open_with` in the config. They are split on
+//! whitespace and run directly (never through a shell), with placeholders
+//! expanded per argument so tool data can't inject extra arguments.
+
+use anyhow::{Context, Result, bail};
+use std::process::{Command, Stdio};
+
+use crate::config::OpenAction;
+use crate::db::GitHubInfo;
+use crate::models::Tool;
+
+/// A fully expanded command ready to run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalCommand {
+    pub label: String,
+    pub program: String,
+    pub args: Vec<String>,
+    pub interactive: bool,
+}
+
+impl ExternalCommand {
+    /// Expand an action's placeholders for the given tool
+    pub fn from_action(
+        action: &OpenAction,
+        tool: &Tool,
+        github: Option<&GitHubInfo>,
+    ) -> Result<Self> {
+        let mut parts = action.command.split_whitespace();
+        let Some(program) = parts.next() else {
+            bail!("Action '{}' has an empty command", action.name);
+        };
+
+        let repo = github.map(|gh| format!("{}/{}", gh.repo_owner, gh.repo_name));
+        let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+        let source = tool.source.to_string();
+
+        let args = parts
+            .map(|arg| {
+                if arg.contains("{repo}") && repo.is_none() {
+                    bail!(
+                        "No GitHub repository known for {} (run 'hoards gh sync')",
+                        tool.name
+                    );
+                }
+                Ok(arg
+                    .replace("{name}", &tool.name)
+                    .replace("{binary}", binary)
+                    .replace("{source}", &source)
+                    .replace("{repo}", repo.as_deref().unwrap_or_default()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            label: format!("{} {}", action.name, tool.name),
+            program: program.to_string(),
+            args,
+            interactive: action.interactive,
+        })
+    }
+
+    /// Open a URL with the desktop's default handler
+    pub fn browser(url: &str) -> Self {
+        let (program, mut args) = if cfg!(target_os = "macos") {
+            ("open", Vec::new())
+        } else if cfg!(windows) {
+            (
+                "cmd",
+                vec!["/C".to_string(), "start".to_string(), String::new()],
+            )
+        } else {
+            ("xdg-open", Vec::new())
+        };
+        args.push(url.to_string());
+        Self {
+            label: format!("open {}", url),
+            program: program.to_string(),
+            args,
+            interactive: false,
+        }
+    }
+
+    /// Run the command
+    ///
+    /// Interactive commands inherit the terminal (the caller must suspend the
+    /// TUI first); others run with captured output so they can't draw over it.
+    pub fn run(&self) -> Result<()> {
+        which::which(&self.program)
+            .with_context(|| format!("Command not found: {}", self.program))?;
+
+        let mut command = Command::new(&self.program);
+        command.args(&self.args);
+
+        if self.interactive {
+            let status = command
+                .status()
+                .with_context(|| format!("Failed to run {}", self.program))?;
+            if !status.success() {
+                bail!("{} exited with {}", self.program, status);
+            }
+        } else {
+            let output = command
+                .stdin(Stdio::null())
+                .output()
+                .with_context(|| format!("Failed to run {}", self.program))?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let reason = stderr.lines().next().unwrap_or("unknown error");
+                bail!("{} failed: {}", self.program, reason);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::InstallSource;
+
+    fn github() -> GitHubInfo {
+        GitHubInfo {
+            provider: crate::forge::ForgeProvider::GitHub,
+            host: "github.com".to_string(),
+            repo_owner: "BurntSushi".to_string(),
+            repo_name: "ripgrep".to_string(),
+            description: None,
+            stars: 0,
+            language: None,
+            homepage: None,
+            pushed_at: None,
+            open_issues: None,
+            archived: false,
+            contributors: None,
+        }
+    }
+
+    #[test]
+    fn test_expand_placeholders() {
+        let tool = Tool::new("ripgrep")
+            .with_binary("rg")
+            .with_source(InstallSource::Cargo);
+        let action = OpenAction::new("gh", "gh repo view --web {repo}");
+        let cmd = ExternalCommand::from_action(&action, &tool, Some(&github())).unwrap();
+        assert_eq!(cmd.program, "gh");
+        assert_eq!(
+            cmd.args,
+            vec!["repo", "view", "--web", "BurntSushi/ripgrep"]
+        );
+
+        let action = OpenAction::new("man", "man {binary}").interactive();
+        let cmd = ExternalCommand::from_action(&action, &tool, None).unwrap();
+        assert_eq!(cmd.args, vec!["rg"]);
+        assert!(cmd.interactive);
+    }
+
+    #[test]
+    fn test_expand_keeps_values_in_one_argument() {
+        let tool = Tool::new("evil; rm -rf ~");
+        let action = OpenAction::new("echo", "echo {name}");
+        let cmd = ExternalCommand::from_action(&action, &tool, None).unwrap();
+        assert_eq!(cmd.args, vec!["evil; rm -rf ~"]);
+    }
+
+    #[test]
+    fn test_expand_missing_repo() {
+        let tool = Tool::new("ripgrep");
+        let action = OpenAction::new("gh", "gh repo view --web {repo}");
+        assert!(ExternalCommand::from_action(&action, &tool, None).is_err());
+
+        let empty = OpenAction::new("empty", "   ");
+        assert!(ExternalCommand::from_action(&empty, &tool, None).is_err());
+    }
+}
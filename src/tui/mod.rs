@@ -2,12 +2,15 @@
 //!
 //! This module provides a full-featured TUI built with Ratatui.
 
+mod ai_popup;
 mod app;
 mod event;
+mod external;
 pub mod theme;
 mod ui;
 
-pub use app::App;
+pub use app::{App, LaunchOptions, Tab};
+pub use external::ExternalCommand;
 pub use theme::{Theme, ThemeVariant};
 
 use anyhow::Result;
@@ -45,10 +48,21 @@ fn restore_terminal(terminal: &mut Tui) -> Result<()> {
     Ok(())
 }
 
-/// Run the TUI application
-pub fn run(db: &Database) -> Result<()> {
-    let mut terminal = init_terminal()?;
+/// Run the TUI application, pre-navigated according to `options`
+pub fn run(db: &Database, options: &LaunchOptions) -> Result<()> {
+    // Never freeze the UI waiting for a GitHub quota to reset
+    crate::forge::scheduler::set_max_wait(std::time::Duration::ZERO);
+
+    // Usage logged by the shell hook since the last flush; a failed flush
+    // leaves the log for next time and shouldn't keep the TUI from starting
+    if let Err(e) = crate::flush_usage_log(db) {
+        tracing::warn!("Failed to flush usage log: {:#}", e);
+    }
+
     let mut app = App::new(db)?;
+    app.apply_launch_options(options, db);
+
+    let mut terminal = init_terminal()?;
 
     let result = run_app(&mut terminal, &mut app, db);
 
@@ -58,10 +72,41 @@ pub fn run(db: &Database) -> Result<()> {
     result
 }
 
+/// Run an external command, suspending the TUI around interactive ones
+fn run_external(terminal: &mut Tui, app: &mut App, command: &ExternalCommand) -> Result<()> {
+    if command.interactive {
+        restore_terminal(terminal)?;
+    }
+
+    let result = command.run();
+
+    if command.interactive {
+        enable_raw_mode()?;
+        execute!(
+            terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableMouseCapture
+        )?;
+        terminal.clear()?;
+    }
+
+    match result {
+        Ok(()) => app.set_status(format!("Ran {}", command.label), false),
+        Err(e) => app.set_status(e.to_string(), true),
+    }
+    Ok(())
+}
+
 fn run_app(terminal: &mut Tui, app: &mut App, db: &Database) -> Result<()> {
     while app.running {
         terminal.draw(|frame| ui::render(frame, app, db))?;
         event::handle_events(app, db)?;
+        app.poll_ai(db);
+
+        // Run a pending open-with command, handing over the terminal if needed
+        if let Some(command) = app.take_external_command() {
+            run_external(terminal, app, &command)?;
+        }
 
         // Execute background operations step by step with loading indicator
         while app.has_background_op() {
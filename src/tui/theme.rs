@@ -30,6 +30,57 @@ pub struct Theme {
     pub teal: Color,   // Sparklines, metrics
 }
 
+impl Theme {
+    /// Derive a high-contrast version of this theme (accessibility mode)
+    ///
+    /// Backgrounds are pushed to pure black (or white for light themes) and
+    /// text and accents are pushed the opposite way, so every variant stays
+    /// readable on low-quality displays.
+    pub fn high_contrast(self) -> Theme {
+        let dark = luminance(self.base) < 128;
+        let (bg, fg) = if dark {
+            ((0, 0, 0), (255, 255, 255))
+        } else {
+            ((255, 255, 255), (0, 0, 0))
+        };
+
+        Theme {
+            name: self.name,
+            base: mix(self.base, bg, 1.0),
+            surface0: mix(self.surface0, bg, 0.6),
+            surface1: mix(self.surface1, fg, 0.3),
+            text: mix(self.text, fg, 1.0),
+            subtext0: mix(self.subtext0, fg, 0.5),
+            blue: mix(self.blue, fg, 0.3),
+            green: mix(self.green, fg, 0.3),
+            yellow: mix(self.yellow, fg, 0.3),
+            red: mix(self.red, fg, 0.3),
+            mauve: mix(self.mauve, fg, 0.3),
+            peach: mix(self.peach, fg, 0.3),
+            teal: mix(self.teal, fg, 0.3),
+        }
+    }
+}
+
+/// Approximate perceived brightness (0-255) of an RGB color
+fn luminance(color: Color) -> u8 {
+    match color {
+        Color::Rgb(r, g, b) => ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8,
+        _ => 0,
+    }
+}
+
+/// Blend an RGB color toward a target by `amount` (0.0-1.0)
+fn mix(color: Color, target: (u8, u8, u8), amount: f32) -> Color {
+    let blend = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount).round() as u8;
+    match color {
+        Color::Rgb(r, g, b) => {
+            Color::Rgb(blend(r, target.0), blend(g, target.1), blend(b, target.2))
+        }
+        other => other,
+    }
+}
+
 /// RGB color for JSON serialization
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct RgbColor {
@@ -30,6 +30,18 @@ fn label_color(label: &str, theme: &Theme) -> Color {
     colors[hash % colors.len()]
 }
 
+/// Color for a license by how restrictive it is
+fn license_color(license: &str, theme: &Theme) -> Color {
+    use crate::models::LicenseKind;
+
+    match LicenseKind::of(license) {
+        LicenseKind::Permissive => theme.green,
+        LicenseKind::WeakCopyleft => theme.yellow,
+        LicenseKind::Copyleft => theme.peach,
+        LicenseKind::Unknown => theme.subtext0,
+    }
+}
+
 /// Format an RFC3339 datetime string to a friendly local time format
 /// e.g., "Today at 3:45 PM", "Yesterday at 10:30 AM", "Jan 15 at 2:00 PM", "Jan 15, 2025"
 fn format_friendly_datetime(rfc3339: &str) -> String {
@@ -127,17 +139,24 @@ fn highlight_matches(
     }
 }
 use crate::db::Database;
-use crate::icons::source_icon;
+use crate::health::{HealthGrade, ToolHealth};
+use crate::icons::{self, UsageHealth, source_icon};
 
 /// Generate a sparkline string from usage data
-/// Uses Unicode block elements: ▁▂▃▄▅▆▇█
+/// Uses Unicode block elements: ▁▂▃▄▅▆▇█ (ASCII ramp in accessible mode)
 fn sparkline(data: &[i64]) -> String {
     if data.is_empty() || data.iter().all(|&x| x == 0) {
-        return "·······".to_string(); // No data indicator
+        // No data indicator
+        return if icons::ascii_mode() {
+            "......"
+        } else {
+            "·······"
+        }
+        .to_string();
     }
 
     let max = *data.iter().max().unwrap_or(&1).max(&1);
-    let blocks = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let blocks = icons::sparkline_blocks();
 
     data.iter()
         .map(|&value| {
@@ -152,6 +171,28 @@ fn sparkline(data: &[i64]) -> String {
         .collect()
 }
 
+/// Weekday × hour heatmap of a tool's usage (one cell per hour)
+fn rhythm_heatmap(rhythm: &crate::db::UsageRhythm, theme: &Theme) -> Vec<Line<'static>> {
+    const DAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+    let max = rhythm.counts.iter().flatten().copied().max().unwrap_or(0);
+    let mut lines = vec![Line::from(vec![
+        Span::styled("  When:   ", Style::default().fg(theme.subtext0)),
+        Span::styled("0     6     12    18", Style::default().fg(theme.surface1)),
+    ])];
+    for (day, counts) in DAYS.iter().zip(&rhythm.counts) {
+        let cells: String = counts.iter().map(|&c| icons::heat_shade(c, max)).collect();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("    {}    ", day),
+                Style::default().fg(theme.subtext0),
+            ),
+            Span::styled(cells, Style::default().fg(theme.teal)),
+        ]));
+    }
+    lines
+}
+
 /// Determine health status based on usage recency
 /// Returns (indicator, color) tuple
 fn health_indicator(
@@ -168,17 +209,27 @@ fn health_indicator(
             (now - used).num_days()
         });
 
-    match (use_count, days_since_use) {
+    let (health, color) = match (use_count, days_since_use) {
         // Never used - red
-        (0, _) => ("●", theme.red),
+        (0, _) => (UsageHealth::Unused, theme.red),
         // Used within last 7 days - green
-        (_, Some(days)) if days < 7 => ("●", theme.green),
+        (_, Some(days)) if days < 7 => (UsageHealth::Active, theme.green),
         // Used within last 30 days - yellow
-        (_, Some(days)) if days < 30 => ("●", theme.yellow),
+        (_, Some(days)) if days < 30 => (UsageHealth::Fading, theme.yellow),
         // Used but more than 30 days ago - red
-        (_, Some(_)) => ("●", theme.red),
+        (_, Some(_)) => (UsageHealth::Stale, theme.red),
         // Has usage but no timestamp (legacy data) - green
-        (_, None) => ("●", theme.green),
+        (_, None) => (UsageHealth::Active, theme.green),
+    };
+    (icons::health_icon(health), color)
+}
+
+/// Color of a health score badge
+fn health_color(health: &ToolHealth, theme: &Theme) -> Color {
+    match health.grade() {
+        HealthGrade::Good => theme.green,
+        HealthGrade::Fair => theme.yellow,
+        HealthGrade::Poor => theme.red,
     }
 }
 
@@ -217,6 +268,26 @@ pub fn render(frame: &mut Frame, app: &mut App, db: &Database) {
         render_details_popup(frame, app, db, &theme, area);
     }
 
+    if app.show_notifications {
+        render_notifications_drawer(frame, app, &theme, chunks[1]);
+    }
+
+    if app.ai_popup.is_some() {
+        render_ai_popup(frame, app, &theme, area);
+    }
+
+    if app.readme_popup.is_some() {
+        render_readme_popup(frame, app, &theme, area);
+    }
+
+    if app.timeline_popup.is_some() {
+        render_timeline_popup(frame, app, &theme, area);
+    }
+
+    if app.template_picker.is_some() {
+        render_template_picker(frame, app, &theme, area);
+    }
+
     // Confirmation dialog takes highest priority
     if app.has_pending_action() {
         render_confirmation_dialog(frame, app, &theme, area);
@@ -357,9 +428,9 @@ fn get_tool_status_indicator(
     theme: &Theme,
 ) -> (&'static str, Color) {
     if app.tab == super::app::Tab::Updates {
-        ("↑", theme.yellow)
+        (icons::update_icon(), theme.yellow)
     } else if !tool.is_installed {
-        ("○", theme.subtext0)
+        (icons::not_installed_icon(), theme.subtext0)
     } else {
         let usage = app.get_usage(&tool.name);
         let use_count = usage.as_ref().map(|u| u.use_count).unwrap_or(0);
@@ -377,7 +448,7 @@ fn build_tool_list_item(
 ) -> ListItem<'static> {
     // Selection checkbox
     let selected = app.is_selected(&tool.name);
-    let checkbox = if selected { "☑" } else { "☐" };
+    let checkbox = icons::checkbox(selected);
     let checkbox_color = if selected { theme.blue } else { theme.surface1 };
 
     // Source icon
@@ -407,7 +478,7 @@ fn build_tool_list_item(
         .filter(|gh| gh.stars > 0)
         .map(|gh| {
             Span::styled(
-                format!(" ★ {}", format_stars(gh.stars)),
+                format!(" {} {}", icons::star(), format_stars(gh.stars)),
                 Style::default().fg(theme.yellow),
             )
         })
@@ -425,6 +496,18 @@ fn build_tool_list_item(
         theme.text,
         theme.yellow,
     ));
+    if app.cache.pins_cache.contains_key(&tool.name) {
+        spans.push(Span::styled(
+            format!(" {}", icons::pin()),
+            Style::default().fg(theme.yellow),
+        ));
+    }
+    if let Some(health) = app.cache.health_cache.get(&tool.name) {
+        spans.push(Span::styled(
+            format!(" {}{}", icons::health_score(), health.score),
+            Style::default().fg(health_color(health, theme)),
+        ));
+    }
     spans.push(stars_span);
     spans.push(Span::styled(extra_info, Style::default().fg(extra_color)));
     spans.push(spark_span);
@@ -579,6 +662,16 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
             ]));
         }
 
+        if let Some(license) = &tool.license {
+            lines.push(Line::from(vec![
+                Span::styled("License: ", Style::default().fg(theme.subtext0)),
+                Span::styled(
+                    license.clone(),
+                    Style::default().fg(license_color(license, theme)),
+                ),
+            ]));
+        }
+
         // Labels (as colored pills)
         if let Some(labels) = app.cache.labels_cache.get(&tool.name)
             && !labels.is_empty()
@@ -600,6 +693,21 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
             lines.push(Line::from(spans));
         }
 
+        // Notes ('hoards note')
+        if let Some(notes) = tool.notes.as_deref().filter(|n| !n.trim().is_empty()) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Notes:",
+                Style::default().fg(theme.subtext0),
+            )));
+            for note in notes.lines() {
+                lines.push(Line::from(Span::styled(
+                    note.to_string(),
+                    Style::default().fg(theme.text),
+                )));
+            }
+        }
+
         lines.push(Line::from(""));
 
         // Usage statistics
@@ -626,13 +734,42 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
                     ),
                 ]));
             }
+            if let Some(patterns) = app.cache.patterns.get(&tool.name)
+                && !patterns.is_empty()
+            {
+                let mut spans = vec![Span::styled("  Top: ", Style::default().fg(theme.subtext0))];
+                for (i, (pattern, count)) in patterns.iter().enumerate() {
+                    if i > 0 {
+                        spans.push(Span::styled(" · ", Style::default().fg(theme.surface1)));
+                    }
+                    spans.push(Span::styled(
+                        pattern.clone(),
+                        Style::default().fg(theme.text),
+                    ));
+                    spans.push(Span::styled(
+                        format!(" {}", count),
+                        Style::default().fg(theme.subtext0),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+            if let Some(rhythm) = app.cache.rhythms.get(&tool.name)
+                && rhythm.total() > 0
+            {
+                lines.extend(rhythm_heatmap(rhythm, theme));
+            }
             lines.push(Line::from(""));
         }
 
-        // GitHub info (already fetched above)
+        // Repository info (already fetched above)
         if let Some(gh) = app.cache.github_cache.get(&tool.name) {
+            let heading = if gh.is_github() {
+                "GitHub:".to_string()
+            } else {
+                format!("{}:", gh.host)
+            };
             lines.push(Line::from(Span::styled(
-                "GitHub:",
+                heading,
                 Style::default()
                     .fg(theme.subtext0)
                     .add_modifier(Modifier::BOLD),
@@ -654,6 +791,21 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
                     Style::default().fg(theme.blue),
                 ),
             ]));
+            if let Some(pushed) = &gh.pushed_at {
+                lines.push(Line::from(vec![
+                    Span::styled("  Last commit: ", Style::default().fg(theme.subtext0)),
+                    Span::styled(
+                        format_friendly_datetime(pushed),
+                        Style::default().fg(theme.text),
+                    ),
+                ]));
+            }
+            if let Some(reason) = gh.abandonment() {
+                lines.push(Line::from(Span::styled(
+                    format!("  ⚠ Looks abandoned: {}", reason),
+                    Style::default().fg(theme.peach),
+                )));
+            }
             lines.push(Line::from(""));
         }
 
@@ -697,6 +849,29 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
             )));
         }
 
+        if let Some(health) = app.cache.health_cache.get(&tool.name) {
+            let signals: Vec<String> = [
+                ("usage", health.usage),
+                ("updates", health.updates),
+                ("upstream", health.upstream),
+                ("configs", health.configs),
+            ]
+            .into_iter()
+            .filter_map(|(name, score)| Some(format!("{} {}", name, score?)))
+            .collect();
+            lines.push(Line::from(vec![
+                Span::styled("Health: ", Style::default().fg(theme.subtext0)),
+                Span::styled(
+                    format!("{}/100", health.score),
+                    Style::default().fg(health_color(health, theme)),
+                ),
+                Span::styled(
+                    format!(" ({})", signals.join(", ")),
+                    Style::default().fg(theme.subtext0),
+                ),
+            ]));
+        }
+
         if tool.is_favorite {
             lines.push(Line::from(Span::styled(
                 "★ Favorite",
@@ -735,8 +910,7 @@ fn format_stars(stars: i64) -> String {
 
 fn render_bundle_list(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     if app.bundles.is_empty() {
-        let message =
-            "No bundles yet. Create one with: hoards bundle create <name> --tools tool1,tool2";
+        let message = "No bundles yet. Press T to start from a template, or create one with: hoards bundle create <name> <tools...>";
         let paragraph = Paragraph::new(message)
             .style(Style::default().fg(theme.subtext0))
             .alignment(Alignment::Center)
@@ -756,11 +930,16 @@ fn render_bundle_list(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
         .enumerate()
         .map(|(i, bundle)| {
             let tool_count = bundle.tools.len();
-            let count_str = if tool_count == 1 {
+            let mut count_str = if tool_count == 1 {
                 "1 tool".to_string()
             } else {
                 format!("{} tools", tool_count)
             };
+            match bundle.includes.len() {
+                0 => {}
+                1 => count_str.push_str(" + 1 bundle"),
+                n => count_str.push_str(&format!(" + {} bundles", n)),
+            }
 
             let content = Line::from(vec![
                 Span::styled("📦 ", Style::default()),
@@ -817,6 +996,65 @@ fn render_bundle_list(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     }
 }
 
+/// Append included bundles and their tools as tree lines
+fn push_bundle_tree<'a>(
+    lines: &mut Vec<Line<'a>>,
+    children: &[crate::models::BundleTree],
+    prefix: &str,
+    db: &Database,
+    theme: &Theme,
+    machine: &crate::models::Machine,
+) {
+    for (i, child) in children.iter().enumerate() {
+        let (branch, indent) = if i + 1 == children.len() {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {}{}", prefix, branch),
+                Style::default().fg(theme.surface1),
+            ),
+            Span::styled(
+                format!("📦 {}", child.bundle.name),
+                Style::default().fg(theme.blue).bold(),
+            ),
+        ]));
+
+        let child_prefix = format!("{}{}", prefix, indent);
+        let entries = child.bundle.tools.len() + child.children.len();
+        for (j, tool) in child.bundle.tools.iter().enumerate() {
+            let branch = if j + 1 == entries {
+                "└─ "
+            } else {
+                "├─ "
+            };
+            let (status, color) = if !child.bundle.applies(tool, machine) {
+                ("-", theme.surface1)
+            } else if db
+                .get_tool_by_name(tool)
+                .ok()
+                .flatten()
+                .is_some_and(|t| t.is_installed)
+            {
+                (icons::installed_icon(), theme.green)
+            } else {
+                (icons::not_installed_icon(), theme.subtext0)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}{}", child_prefix, branch),
+                    Style::default().fg(theme.surface1),
+                ),
+                Span::styled(format!("{} ", status), Style::default().fg(color)),
+                Span::styled(tool.clone(), Style::default().fg(theme.text)),
+            ]));
+        }
+        push_bundle_tree(lines, &child.children, &child_prefix, db, theme, machine);
+    }
+}
+
 fn render_bundle_details(frame: &mut Frame, app: &App, db: &Database, theme: &Theme, area: Rect) {
     let content = if let Some(bundle) = app.bundles.get(app.bundles.selected) {
         let mut lines = vec![
@@ -839,14 +1077,28 @@ fn render_bundle_details(frame: &mut Frame, app: &App, db: &Database, theme: &Th
             lines.push(Line::from(""));
         }
 
+        // Included bundles contribute their tools too
+        let tree = db.get_bundle_tree(&bundle.name).ok().flatten();
+        let flat = tree
+            .as_ref()
+            .map(|t| t.flatten())
+            .unwrap_or_else(|| bundle.clone());
+
         // Tool count
-        lines.push(Line::from(vec![
+        let mut count = vec![
             Span::styled("Tools: ", Style::default().fg(theme.subtext0)),
             Span::styled(
-                format!("{}", bundle.tools.len()),
+                format!("{}", flat.tools.len()),
                 Style::default().fg(theme.teal),
             ),
-        ]));
+        ];
+        if !bundle.includes.is_empty() {
+            count.push(Span::styled(
+                format!(" ({} direct)", bundle.tools.len()),
+                Style::default().fg(theme.subtext0),
+            ));
+        }
+        lines.push(Line::from(count));
         lines.push(Line::from(""));
 
         // List tools with installation status
@@ -855,7 +1107,23 @@ fn render_bundle_details(frame: &mut Frame, app: &App, db: &Database, theme: &Th
             Style::default().fg(theme.surface1),
         )));
 
+        let machine = crate::models::Machine::current();
         for tool_name in &bundle.tools {
+            // Members for other machines are listed with their condition
+            if let Some(condition) = bundle.condition(tool_name)
+                && !condition.matches(&machine)
+            {
+                lines.push(Line::from(vec![
+                    Span::styled("  - ", Style::default().fg(theme.surface1)),
+                    Span::styled(tool_name.clone(), Style::default().fg(theme.surface1)),
+                    Span::styled(
+                        format!("  not here ({})", condition),
+                        Style::default().fg(theme.surface1).italic(),
+                    ),
+                ]));
+                continue;
+            }
+
             // Check if tool is installed
             let is_installed = db
                 .get_tool_by_name(tool_name)
@@ -865,15 +1133,31 @@ fn render_bundle_details(frame: &mut Frame, app: &App, db: &Database, theme: &Th
                 .unwrap_or(false);
 
             let (status, status_color) = if is_installed {
-                ("●", theme.green)
+                (icons::installed_icon(), theme.green)
             } else {
-                ("○", theme.subtext0)
+                (icons::not_installed_icon(), theme.subtext0)
             };
 
-            lines.push(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(format!("  {} ", status), Style::default().fg(status_color)),
                 Span::styled(tool_name.clone(), Style::default().fg(theme.text)),
-            ]));
+            ];
+            if let Some(condition) = bundle.condition(tool_name) {
+                spans.push(Span::styled(
+                    format!("  {}", condition),
+                    Style::default().fg(theme.subtext0),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        if let Some(tree) = tree.as_ref().filter(|t| !t.children.is_empty()) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "─── Includes ───",
+                Style::default().fg(theme.surface1),
+            )));
+            push_bundle_tree(&mut lines, &tree.children, "", db, theme, &machine);
         }
 
         lines.push(Line::from(""));
@@ -882,7 +1166,7 @@ fn render_bundle_details(frame: &mut Frame, app: &App, db: &Database, theme: &Th
         let mut untracked = 0;
         let mut not_installed = 0;
 
-        for name in &bundle.tools {
+        for name in flat.tools.iter().filter(|t| flat.applies(t, &machine)) {
             match db.get_tool_by_name(name).ok().flatten() {
                 None => untracked += 1,
                 Some(t) if !t.is_installed => not_installed += 1,
@@ -1012,6 +1296,10 @@ fn render_discover(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
                     Span::styled("- Node.js packages", Style::default().fg(theme.subtext0)),
                 ]),
                 Line::from(""),
+                Line::from(Span::styled(
+                    "Browse by GitHub topic with :topic <name>",
+                    Style::default().fg(theme.subtext0),
+                )),
                 Line::from(Span::styled(
                     "Or use AI suggestions with :ai <description>",
                     Style::default().fg(theme.subtext0),
@@ -1052,7 +1340,7 @@ fn render_discover(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
                 let icon = result.source.icon();
                 let stars_str = result
                     .stars
-                    .map(|s| format!(" ★ {}", format_stars(s as i64)))
+                    .map(|s| format!(" {} {}", icons::star(), format_stars(s as i64)))
                     .unwrap_or_default();
 
                 let desc = result
@@ -1068,7 +1356,20 @@ fn render_discover(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
                     })
                     .unwrap_or_default();
 
+                let marker = match &result.in_hoard {
+                    Some((_, true)) => Span::styled(
+                        format!("{} ", icons::installed_icon()),
+                        Style::default().fg(theme.green),
+                    ),
+                    Some((_, false)) => Span::styled(
+                        format!("{} ", icons::not_installed_icon()),
+                        Style::default().fg(theme.yellow),
+                    ),
+                    None => Span::raw("  "),
+                };
+
                 let content = Line::from(vec![
+                    marker,
                     Span::styled(format!("{} ", icon), Style::default()),
                     Span::styled(&result.name, Style::default().fg(theme.text)),
                     Span::styled(stars_str, Style::default().fg(theme.yellow)),
@@ -1122,18 +1423,28 @@ fn build_footer_right_status(app: &App, theme: &Theme) -> (Vec<Span<'static>>, u
     let sync_str = app
         .last_sync
         .as_ref()
-        .map(|dt| format!("⟳ {}", format_relative_time(dt)))
+        .map(|dt| format!("{} {}", icons::sync_icon(), format_relative_time(dt)))
         .unwrap_or_default();
-    let sync_len = if sync_str.is_empty() {
-        0
-    } else {
-        sync_str.chars().count() + 1
+
+    // In ASCII mode spell out availability instead of relying on color
+    let availability = |label: &str, available: bool| {
+        if icons::ascii_mode() {
+            format!("{}{}", label, if available { "+" } else { "-" })
+        } else {
+            label.to_string()
+        }
     };
 
     let mut spans = vec![
-        Span::styled("🤖", Style::default().fg(ai_color)),
+        Span::styled(
+            availability(icons::ai_icon(), app.ai_available),
+            Style::default().fg(ai_color),
+        ),
         Span::styled(" ", Style::default()),
-        Span::styled("\u{f09b}", Style::default().fg(gh_color)),
+        Span::styled(
+            availability(icons::github_icon(), app.gh_available),
+            Style::default().fg(gh_color),
+        ),
         Span::styled("  ", Style::default()),
     ];
 
@@ -1151,7 +1462,7 @@ fn build_footer_right_status(app: &App, theme: &Theme) -> (Vec<Span<'static>>, u
     ));
     spans.push(Span::styled(" ", Style::default()));
 
-    let width = 2 + 1 + 1 + 2 + sync_len + 1 + version.len() + 1;
+    let width = spans.iter().map(|span| span.width()).sum();
     (spans, width)
 }
 
@@ -1178,7 +1489,12 @@ fn build_normal_mode_footer(app: &App, theme: &Theme) -> Vec<Span<'static>> {
             format!("{} selected", app.selection_count()),
             Style::default().fg(theme.blue),
         ));
-    } else if !app.search_query.is_empty() || app.source_filter.is_some() || app.favorites_only {
+    } else if !app.search_query.is_empty()
+        || app.source_filter.is_some()
+        || app.favorites_only
+        || app.include_libraries
+        || app.project_filter.is_some()
+    {
         spans.extend(build_filter_status(app, theme));
     }
 
@@ -1196,8 +1512,27 @@ fn build_filter_status(app: &App, theme: &Theme) -> Vec<Span<'static>> {
         Style::default().fg(theme.blue),
     ));
 
+    if app.include_libraries {
+        spans.push(Span::styled("+libs ", Style::default().fg(theme.teal)));
+    }
     if app.favorites_only {
-        spans.push(Span::styled("★", Style::default().fg(theme.yellow)));
+        spans.push(Span::styled(
+            icons::star(),
+            Style::default().fg(theme.yellow),
+        ));
+        if app.project_filter.is_some()
+            || app.source_filter.is_some()
+            || !app.search_query.is_empty()
+        {
+            spans.push(Span::styled(" ", Style::default()));
+        }
+    }
+    if let Some(ref project) = app.project_filter {
+        spans.push(Span::styled("proj:", Style::default().fg(theme.green)));
+        spans.push(Span::styled(
+            project.name().to_string(),
+            Style::default().fg(theme.text),
+        ));
         if app.source_filter.is_some() || !app.search_query.is_empty() {
             spans.push(Span::styled(" ", Style::default()));
         }
@@ -1385,6 +1720,31 @@ fn render_help_overlay(frame: &mut Frame, theme: &Theme, area: Rect) {
             Span::styled("  n/N      ", Style::default().fg(theme.yellow)),
             Span::styled("Next/prev match (wrap)", Style::default().fg(theme.text)),
         ]),
+        Line::from(vec![
+            Span::styled("  n        ", Style::default().fg(theme.yellow)),
+            Span::styled(
+                "Notification history (no filter)",
+                Style::default().fg(theme.text),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  o / M    ", Style::default().fg(theme.yellow)),
+            Span::styled(
+                "Open on GitHub / man page (configurable)",
+                Style::default().fg(theme.text),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  R        ", Style::default().fg(theme.yellow)),
+            Span::styled("Show README (cached)", Style::default().fg(theme.text)),
+        ]),
+        Line::from(vec![
+            Span::styled("  :cs      ", Style::default().fg(theme.yellow)),
+            Span::styled(
+                "AI cheatsheet (also :ai-discover, :explain)",
+                Style::default().fg(theme.text),
+            ),
+        ]),
         Line::from(vec![
             Span::styled("  f<char>  ", Style::default().fg(theme.peach)),
             Span::styled("Jump to letter", Style::default().fg(theme.text)),
@@ -1456,6 +1816,20 @@ fn render_help_overlay(frame: &mut Frame, theme: &Theme, area: Rect) {
             Span::styled("  u        ", Style::default().fg(theme.yellow)),
             Span::styled("Update tool(s)", Style::default().fg(theme.text)),
         ]),
+        Line::from(vec![
+            Span::styled("  S        ", Style::default().fg(theme.yellow)),
+            Span::styled(
+                "AI summary of pending updates (Updates tab)",
+                Style::default().fg(theme.text),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("  T        ", Style::default().fg(theme.yellow)),
+            Span::styled(
+                "New bundle from a template (Bundles tab)",
+                Style::default().fg(theme.text),
+            ),
+        ]),
         Line::from(vec![
             Span::styled("  Enter    ", Style::default().fg(theme.yellow)),
             Span::styled("Show details popup", Style::default().fg(theme.text)),
@@ -1553,7 +1927,7 @@ fn render_help_overlay(frame: &mut Frame, theme: &Theme, area: Rect) {
 
 /// Create a radio button line for config menu
 fn make_radio_line<'a>(selected: bool, focused: bool, label: String, theme: &Theme) -> Line<'a> {
-    let bullet = if selected { "●" } else { "○" };
+    let bullet = icons::radio(selected);
     let style = if focused {
         Style::default().fg(theme.blue).bold()
     } else if selected {
@@ -1569,7 +1943,7 @@ fn make_radio_line<'a>(selected: bool, focused: bool, label: String, theme: &The
 
 /// Create a checkbox line for config menu
 fn make_checkbox_line<'a>(checked: bool, focused: bool, label: String, theme: &Theme) -> Line<'a> {
-    let mark = if checked { "☑" } else { "☐" };
+    let mark = icons::checkbox(checked);
     let style = if focused {
         Style::default().fg(theme.blue).bold()
     } else if checked {
@@ -1613,12 +1987,32 @@ fn render_config_ai_section(
             AiProvider::Gemini => "Gemini",
             AiProvider::Codex => "Codex",
             AiProvider::Opencode => "Opencode",
+            AiProvider::OpenAi => "OpenAI-compatible API",
+            AiProvider::Ollama => "Ollama (local)",
+            AiProvider::Anthropic => "Anthropic API",
         };
         let selected = i == state.ai_selected;
         let focused = ai_focused && selected;
-        lines.push(make_radio_line(selected, focused, label.to_string(), theme));
+        let label = match (provider, &state.ollama_status) {
+            (AiProvider::Ollama, Some(Ok(count))) => format!("{} - {} model(s)", label, count),
+            (AiProvider::Ollama, Some(Err(e))) => format!("{} - {}", label, e),
+            _ => label.to_string(),
+        };
+        lines.push(make_radio_line(selected, focused, label, theme));
     }
 
+    let usage = match state.ai_usage {
+        Some((0, _)) | None => "No AI calls in the last 30 days".to_string(),
+        Some((calls, tokens)) => format!(
+            "Last 30 days: {} call(s), ~{} tokens ('hoards ai usage' for details)",
+            calls, tokens
+        ),
+    };
+    lines.push(Line::from(Span::styled(
+        format!("    {}", usage),
+        Style::default().fg(theme.subtext0).italic(),
+    )));
+
     lines.push(Line::from(""));
     lines
 }
@@ -1847,8 +2241,9 @@ fn render_details_popup(
     let popup_area = centered_rect(70, 80, area);
 
     let content = if let Some(tool) = app.selected_tool().cloned() {
-        // Pre-fetch GitHub info
+        // Pre-fetch GitHub info and related tools
         let _ = app.get_github_info(&tool.name, db);
+        let related = app.cache.get_related(&tool.name, db).map(<[_]>::to_vec);
 
         let mut lines = vec![
             Line::from(Span::styled(
@@ -1901,6 +2296,16 @@ fn render_details_popup(
             ]));
         }
 
+        if let Some(license) = &tool.license {
+            lines.push(Line::from(vec![
+                Span::styled("License: ", Style::default().fg(theme.subtext0)),
+                Span::styled(
+                    license.clone(),
+                    Style::default().fg(license_color(license, theme)),
+                ),
+            ]));
+        }
+
         // Labels (as colored pills)
         if let Some(labels) = app.cache.labels_cache.get(&tool.name)
             && !labels.is_empty()
@@ -1954,6 +2359,40 @@ fn render_details_popup(
                 Span::styled("/", Style::default().fg(theme.subtext0)),
                 Span::styled(&gh.repo_name, Style::default().fg(theme.blue)),
             ]));
+            if let Some(reason) = gh.abandonment() {
+                lines.push(Line::from(Span::styled(
+                    format!("⚠ Looks abandoned: {}", reason),
+                    Style::default().fg(theme.peach),
+                )));
+            }
+        }
+
+        // Related tools (embedding similarity)
+        lines.push(Line::from(""));
+        match related {
+            Some(related) if !related.is_empty() => {
+                lines.push(Line::from(Span::styled(
+                    "Related tools:",
+                    Style::default().fg(theme.subtext0),
+                )));
+                for (name, score) in related {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {:>3.0}% ", score * 100.0),
+                            Style::default().fg(theme.teal),
+                        ),
+                        Span::styled(name, Style::default().fg(theme.text)),
+                    ]));
+                }
+            }
+            Some(_) => {}
+            None => lines.push(Line::from(Span::styled(
+                format!(
+                    "Related tools: run 'hoards discover similar {}' to index",
+                    tool.name
+                ),
+                Style::default().fg(theme.subtext0),
+            ))),
         }
 
         lines.push(Line::from(""));
@@ -1984,6 +2423,469 @@ fn render_details_popup(
     frame.render_widget(popup, popup_area);
 }
 
+/// Render the notification history as a drawer on the right side of the body
+fn render_notifications_drawer(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
+    use super::app::NotificationLevel;
+
+    let width = (area.width / 2).max(40).min(area.width);
+    let drawer_area = Rect {
+        x: area.x + area.width - width,
+        y: area.y,
+        width,
+        height: area.height,
+    };
+
+    let log = &app.notifications;
+    let mut lines: Vec<Line> = if log.is_empty() {
+        vec![Line::from(Span::styled(
+            "No notifications yet",
+            Style::default().fg(theme.subtext0),
+        ))]
+    } else {
+        log.newest_first()
+            .skip(log.scroll)
+            .map(|n| {
+                let color = match n.level {
+                    NotificationLevel::Info => theme.green,
+                    NotificationLevel::Error => theme.red,
+                };
+                Line::from(vec![
+                    Span::styled(
+                        n.timestamp.format("%H:%M:%S ").to_string(),
+                        Style::default().fg(theme.subtext0),
+                    ),
+                    Span::styled(
+                        format!("{:<6}", n.level.label()),
+                        Style::default().fg(color).bold(),
+                    ),
+                    Span::styled(n.text.clone(), Style::default().fg(theme.text)),
+                ])
+            })
+            .collect()
+    };
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("j/k", Style::default().fg(theme.blue)),
+        Span::styled(" scroll  ", Style::default().fg(theme.subtext0)),
+        Span::styled("c", Style::default().fg(theme.blue)),
+        Span::styled(" clear  ", Style::default().fg(theme.subtext0)),
+        Span::styled("n/Esc", Style::default().fg(theme.blue)),
+        Span::styled(" close", Style::default().fg(theme.subtext0)),
+    ]));
+
+    let title = format!(
+        " Notifications ({}, {} error(s)) ",
+        log.len(),
+        log.error_count()
+    );
+    let drawer = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.blue))
+                .title(Span::styled(title, Style::default().fg(theme.blue).bold()))
+                .style(Style::default().bg(theme.base)),
+        )
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, drawer_area);
+    frame.render_widget(drawer, drawer_area);
+}
+
+fn render_ai_popup(frame: &mut Frame, app: &mut App, theme: &Theme, area: Rect) {
+    use super::ai_popup::AiOutcome;
+
+    let Some(popup) = app.ai_popup.as_mut() else {
+        return;
+    };
+    let popup_area = centered_rect(70, 75, area);
+    let dim = Style::default().fg(theme.subtext0);
+    let text = Style::default().fg(theme.text);
+
+    let mut lines: Vec<Line> = Vec::new();
+    match &popup.outcome {
+        Some(Ok(AiOutcome::Cheatsheet(cheatsheet))) => {
+            lines.push(Line::from(Span::styled(
+                cheatsheet.title.clone(),
+                Style::default().fg(theme.text).bold(),
+            )));
+            for section in &cheatsheet.sections {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    section.name.clone(),
+                    Style::default().fg(theme.blue).bold(),
+                )));
+                let width = section
+                    .commands
+                    .iter()
+                    .map(|c| c.cmd.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                for command in &section.commands {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {:<width$}  ", command.cmd, width = width),
+                            Style::default().fg(theme.green),
+                        ),
+                        Span::styled(command.desc.clone(), dim),
+                    ]));
+                }
+            }
+        }
+        Some(Ok(AiOutcome::Discovery(discovery))) => {
+            lines.push(Line::from(Span::styled(discovery.summary.clone(), text)));
+            for tool in &discovery.tools {
+                lines.push(Line::from(""));
+                let color = if tool.category == "essential" {
+                    theme.green
+                } else {
+                    theme.blue
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(tool.name.clone(), Style::default().fg(color).bold()),
+                    Span::styled(format!("  [{}]", tool.category), dim),
+                ]));
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", tool.description),
+                    text,
+                )));
+                lines.push(Line::from(Span::styled(format!("  {}", tool.reason), dim)));
+                lines.push(Line::from(Span::styled(
+                    format!("  $ {}", tool.install_cmd),
+                    Style::default().fg(theme.peach),
+                )));
+            }
+        }
+        Some(Ok(AiOutcome::Updates(notes))) => {
+            use crate::ai::changelog::UpdatePriority;
+
+            if notes.is_empty() {
+                lines.push(Line::from(Span::styled("No summaries returned", dim)));
+            }
+            for note in notes {
+                let color = match note.priority {
+                    UpdatePriority::Security => theme.red,
+                    UpdatePriority::Breaking => theme.peach,
+                    UpdatePriority::Recommended => theme.green,
+                    UpdatePriority::Optional => theme.blue,
+                    UpdatePriority::Skip => theme.subtext0,
+                };
+                let versions = app
+                    .available_updates
+                    .get(&note.tool)
+                    .map(|u| format!("  {} -> {}", u.current, u.latest))
+                    .unwrap_or_default();
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("{:<12}", note.priority.label()),
+                        Style::default().fg(color).bold(),
+                    ),
+                    Span::styled(note.tool.clone(), Style::default().fg(theme.text).bold()),
+                    Span::styled(versions, dim),
+                ]));
+                lines.push(Line::from(Span::styled(
+                    format!("{:<12}{}", "", note.summary),
+                    text,
+                )));
+            }
+        }
+        Some(Ok(AiOutcome::Text(response))) => {
+            lines.extend(
+                response
+                    .lines()
+                    .map(|l| Line::from(Span::styled(l.to_string(), text))),
+            );
+        }
+        Some(Err(error)) => {
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(theme.red).bold(),
+            )));
+            if !popup.streamed.is_empty() {
+                lines.push(Line::from(""));
+                lines.extend(
+                    popup
+                        .streamed
+                        .lines()
+                        .map(|l| Line::from(Span::styled(l.to_string(), dim))),
+                );
+            }
+        }
+        None if popup.streamed.is_empty() => {
+            lines.push(Line::from(Span::styled("Waiting for response...", dim)));
+        }
+        None => {
+            lines.extend(
+                popup
+                    .streamed
+                    .lines()
+                    .map(|l| Line::from(Span::styled(l.to_string(), text))),
+            );
+        }
+    }
+
+    // Keep the newest output in view while streaming
+    let visible = popup_area.height.saturating_sub(3);
+    let max_scroll = (lines.len() as u16).saturating_sub(visible);
+    if popup.follow {
+        popup.scroll = max_scroll;
+    }
+    popup.scroll = popup.scroll.min(max_scroll);
+
+    let status = if popup.is_streaming() {
+        " generating... "
+    } else if popup.cached {
+        " cached "
+    } else {
+        ""
+    };
+    let hint = if popup.is_streaming() {
+        " j/k scroll  G follow  Esc cancel "
+    } else {
+        " j/k scroll  Esc close "
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.mauve))
+                .title(Span::styled(
+                    format!(" {} ", popup.task.title()),
+                    Style::default().fg(theme.mauve).bold(),
+                ))
+                .title(Line::from(Span::styled(status, dim)).right_aligned())
+                .title_bottom(Line::from(Span::styled(hint, dim)).right_aligned())
+                .style(Style::default().bg(theme.base)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((popup.scroll, 0));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_readme_popup(frame: &mut Frame, app: &mut App, theme: &Theme, area: Rect) {
+    let Some(popup) = app.readme_popup.as_mut() else {
+        return;
+    };
+    let popup_area = centered_rect(80, 85, area);
+    let dim = Style::default().fg(theme.subtext0);
+
+    let lines: Vec<Line> = popup
+        .content
+        .lines()
+        .map(|l| {
+            let style = if l.starts_with('#') {
+                Style::default().fg(theme.blue).bold()
+            } else if l.starts_with("```") || l.starts_with("    ") {
+                Style::default().fg(theme.green)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            Line::from(Span::styled(l.to_string(), style))
+        })
+        .collect();
+
+    let visible = popup_area.height.saturating_sub(2);
+    let max_scroll = (lines.len() as u16).saturating_sub(visible);
+    popup.scroll = popup.scroll.min(max_scroll);
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.blue))
+                .title(Span::styled(
+                    format!(" README: {} ", popup.repo),
+                    Style::default().fg(theme.blue).bold(),
+                ))
+                .title_bottom(
+                    Line::from(Span::styled(" j/k scroll  r refresh  Esc close ", dim))
+                        .right_aligned(),
+                )
+                .style(Style::default().bg(theme.base)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((popup.scroll, 0));
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_timeline_popup(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
+    let Some(popup) = &app.timeline_popup else {
+        return;
+    };
+    let popup_area = centered_rect(70, 80, area);
+    let dim = Style::default().fg(theme.subtext0);
+
+    // Chart the months that fit above the selected month's tools, keeping
+    // the selection in view
+    let chart_height = (popup_area.height.saturating_sub(2) as usize / 2).max(3);
+    let start = (popup.selected + 1).saturating_sub(chart_height);
+    let bar_width = (popup_area.width.saturating_sub(30) as usize).max(5);
+    let max = popup
+        .months
+        .iter()
+        .map(|m| m.installed.len())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, month) in popup
+        .months
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(chart_height)
+    {
+        let selected = i == popup.selected;
+        let installs = month.installed.len();
+        let mut spans = vec![
+            Span::styled(
+                format!("{} {} ", if selected { ">" } else { " " }, month.month),
+                if selected {
+                    Style::default().fg(theme.blue).bold()
+                } else {
+                    Style::default().fg(theme.text)
+                },
+            ),
+            Span::styled(
+                "█".repeat((installs * bar_width).div_ceil(max)),
+                Style::default().fg(if month.burst.is_some() {
+                    theme.yellow
+                } else {
+                    theme.teal
+                }),
+            ),
+            Span::styled(format!(" {}", installs), Style::default().fg(theme.text)),
+        ];
+        if !month.removed.is_empty() {
+            spans.push(Span::styled(
+                format!(" -{}", month.removed.len()),
+                Style::default().fg(theme.red),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(month) = popup.months.get(popup.selected) {
+        lines.push(Line::from(""));
+        let mut header = vec![Span::styled(
+            month.month.clone(),
+            Style::default().fg(theme.text).bold(),
+        )];
+        if let Some(burst) = &month.burst {
+            header.push(Span::styled(
+                format!("  burst: {}", burst),
+                Style::default().fg(theme.yellow),
+            ));
+        }
+        lines.push(Line::from(header));
+        if month.installed.is_empty() && month.removed.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "Nothing installed or removed",
+                dim,
+            )));
+        }
+        if !month.installed.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("+ ", Style::default().fg(theme.green)),
+                Span::styled(month.installed.join(", "), Style::default().fg(theme.text)),
+            ]));
+        }
+        if !month.removed.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("- ", Style::default().fg(theme.red)),
+                Span::styled(month.removed.join(", "), Style::default().fg(theme.text)),
+            ]));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.blue))
+                .title(Span::styled(
+                    " Install timeline ",
+                    Style::default().fg(theme.blue).bold(),
+                ))
+                .title_bottom(
+                    Line::from(Span::styled(" j/k month  g/G first/last  Esc close ", dim))
+                        .right_aligned(),
+                )
+                .style(Style::default().bg(theme.base)),
+        )
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn render_template_picker(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
+    let Some(selected) = app.template_picker else {
+        return;
+    };
+    let popup_area = centered_rect(70, 70, area);
+    let dim = Style::default().fg(theme.subtext0);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            "Start with a ready-made bundle of well-known tools:",
+            Style::default().fg(theme.text),
+        )),
+        Line::from(""),
+    ];
+    for (i, template) in crate::BUNDLE_TEMPLATES.iter().enumerate() {
+        let exists = app.bundles.iter().any(|b| b.name == template.name);
+        let (marker, name_style) = if i == selected {
+            ("▸ ", Style::default().fg(theme.blue).bold())
+        } else {
+            ("  ", Style::default().fg(theme.text).bold())
+        };
+        let mut header = vec![
+            Span::styled(marker, Style::default().fg(theme.blue)),
+            Span::styled(template.name, name_style),
+            Span::styled(format!("  {}", template.description), dim),
+        ];
+        if exists {
+            header.push(Span::styled(
+                "  (already a bundle)",
+                Style::default().fg(theme.yellow),
+            ));
+        }
+        lines.push(Line::from(header));
+        lines.push(Line::from(Span::styled(
+            format!("    {}", template.tools.join(", ")),
+            Style::default().fg(theme.surface1),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.blue))
+                .title(Span::styled(
+                    " Bundle Templates ",
+                    Style::default().fg(theme.blue).bold(),
+                ))
+                .title_bottom(
+                    Line::from(Span::styled(" j/k select  Enter create  Esc close ", dim))
+                        .right_aligned(),
+                )
+                .style(Style::default().bg(theme.base)),
+        )
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(paragraph, popup_area);
+}
+
 fn render_loading_overlay(frame: &mut Frame, app: &App, theme: &Theme, area: Rect) {
     let popup_area = centered_rect(50, 30, area);
 
@@ -1997,11 +2899,9 @@ fn render_loading_overlay(frame: &mut Frame, app: &App, theme: &Theme, area: Rec
 
     // Build progress bar
     let bar_width = 30;
-    let filled = if progress.total_steps > 0 {
-        (progress.current_step * bar_width) / progress.total_steps
-    } else {
-        0
-    };
+    let filled = (progress.current_step * bar_width)
+        .checked_div(progress.total_steps)
+        .unwrap_or(0);
     let empty = bar_width - filled;
     let progress_bar = format!(
         "[{}{}] {}/{}",
@@ -2117,6 +3017,15 @@ fn render_confirmation_dialog(frame: &mut Frame, app: &App, theme: &Theme, area:
                     theme.yellow,
                 )
             }
+            super::app::PendingAction::InstallRelease(_) => (
+                " Install ",
+                format!(
+                    "{}\n\nBinary for {} into the install directory",
+                    action.description(),
+                    crate::forge::assets::platform()
+                ),
+                theme.green,
+            ),
         }
     } else {
         return;
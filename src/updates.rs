@@ -1,8 +1,11 @@
 use anyhow::Result;
+use serde::Serialize;
 use std::process::Command;
 
+use crate::sources::{PackageSource, PluginSource, ShellSource};
+
 /// An available update
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct Update {
     pub name: String,
     pub current: String,
@@ -148,6 +151,58 @@ pub fn check_npm_updates() -> Result<Vec<Update>> {
     Ok(updates)
 }
 
+/// Check for updates of the packages a source plugin reports
+pub fn check_plugin_updates(plugin: &PluginSource) -> Result<Vec<Update>> {
+    let mut updates = Vec::new();
+    for package in plugin.packages()? {
+        let Some(current) = package.version else {
+            continue;
+        };
+        if let Some(latest) = plugin.latest_version(&package.name)
+            && version_is_newer(&latest, &current)
+        {
+            updates.push(Update {
+                name: package.name,
+                current,
+                latest,
+                source: plugin.name().to_string(),
+            });
+        }
+    }
+    Ok(updates)
+}
+
+/// Check shell plugins that are git checkouts against their remotes
+pub fn check_shell_updates() -> Result<Vec<Update>> {
+    let plugins: Vec<_> = ShellSource
+        .plugins()
+        .into_iter()
+        .filter(|p| p.git)
+        .collect();
+    let updates = std::thread::scope(|s| {
+        let handles: Vec<_> = plugins
+            .iter()
+            .map(|plugin| {
+                s.spawn(move || {
+                    let current = plugin.head()?;
+                    let latest = plugin.remote_head()?;
+                    (!current.starts_with(&latest)).then(|| Update {
+                        name: plugin.name.clone(),
+                        current,
+                        latest,
+                        source: "shell".to_string(),
+                    })
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().ok().flatten())
+            .collect()
+    });
+    Ok(updates)
+}
+
 /// Check for apt updates using `apt list --upgradable`
 pub fn check_apt_updates() -> Result<Vec<Update>> {
     let output = Command::new("apt")
@@ -220,7 +275,7 @@ pub fn check_brew_updates() -> Result<Vec<Update>> {
 }
 
 /// A potential upgrade by switching sources
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct CrossSourceUpgrade {
     pub name: String,
     pub current_version: String,
@@ -318,7 +373,7 @@ pub fn get_installed_version(name: &str, source: &str) -> Option<String> {
         "pip" => get_pip_version(name),
         "npm" => get_npm_version(name),
         "apt" => get_apt_version(name),
-        _ => None,
+        source => crate::sources::plugin(source)?.installed_version(name),
     }
 }
 
@@ -328,7 +383,11 @@ pub fn get_available_versions(name: &str, source: &str, current: &str) -> Vec<St
         "cargo" => get_crates_io_versions(name, current),
         "pip" => get_pypi_versions(name, current),
         "npm" => get_npm_versions(name, current),
-        _ => Vec::new(),
+        source => crate::sources::plugin(source)
+            .and_then(|p| p.latest_version(name))
+            .filter(|latest| latest != current)
+            .into_iter()
+            .collect(),
     }
 }
 
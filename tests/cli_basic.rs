@@ -3,7 +3,7 @@
 mod common;
 
 use chrono::Utc;
-use hoards::{Bundle, InstallSource, Tool};
+use hoards::{Bundle, Config, InstallSource, Tool, cmd_export, cmd_import};
 
 // ==================== Database Workflow Tests ====================
 
@@ -23,6 +23,9 @@ fn test_add_and_retrieve_tool() {
         is_installed: true,
         is_favorite: false,
         notes: None,
+        license: None,
+        installed_version: None,
+        kind: Default::default(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
     };
@@ -58,6 +61,9 @@ fn test_search_tools() {
             is_installed: true,
             is_favorite: false,
             notes: None,
+            license: None,
+            installed_version: None,
+            kind: Default::default(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -96,6 +102,9 @@ fn test_bundle_operations() {
             is_installed: false,
             is_favorite: false,
             notes: None,
+            license: None,
+            installed_version: None,
+            kind: Default::default(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -108,6 +117,8 @@ fn test_bundle_operations() {
         name: "test-bundle".to_string(),
         description: Some("A test bundle".to_string()),
         tools: vec!["tool1".to_string(), "tool2".to_string()],
+        conditions: Default::default(),
+        includes: Vec::new(),
         created_at: Utc::now(),
     };
 
@@ -168,6 +179,9 @@ fn test_labels() {
         is_installed: false,
         is_favorite: false,
         notes: None,
+        license: None,
+        installed_version: None,
+        kind: Default::default(),
         created_at: Utc::now(),
         updated_at: Utc::now(),
     };
@@ -213,6 +227,8 @@ fn test_bundle_creation_atomic() {
             "tool-b".to_string(),
             "tool-c".to_string(),
         ],
+        conditions: Default::default(),
+        includes: Vec::new(),
         created_at: Utc::now(),
     };
 
@@ -229,3 +245,39 @@ fn test_bundle_creation_atomic() {
 
     assert_eq!(retrieved.tools.len(), 3);
 }
+
+// ==================== Export/Import Tests ====================
+
+#[test]
+fn test_export_import_round_trip() {
+    let ctx = common::TestContext::new();
+    ctx.db
+        .insert_tool(&Tool::new("ripgrep").with_source(InstallSource::Cargo))
+        .unwrap();
+    ctx.db
+        .add_labels("ripgrep", &["search".to_string()])
+        .unwrap();
+    ctx.db
+        .create_bundle(&Bundle::new("search", vec!["ripgrep".to_string()]))
+        .unwrap();
+    ctx.db
+        .insert_config(&Config::new("rg", "~/dotfiles/ripgreprc", "~/.ripgreprc"))
+        .unwrap();
+
+    let temp = tempfile::TempDir::new().unwrap();
+    for format in ["yaml", "toml", "json"] {
+        let path = temp.path().join(format!("hoard.{}", format));
+        let path = path.to_string_lossy().into_owned();
+        cmd_export(&ctx.db, Some(path.clone()), format, false, None).unwrap();
+
+        let other = common::TestContext::new();
+        cmd_import(&other.db, &path, false, false).unwrap();
+        assert_eq!(other.db.get_labels("ripgrep").unwrap(), vec!["search"]);
+        assert_eq!(
+            other.db.get_bundle("search").unwrap().unwrap().tools,
+            vec!["ripgrep"]
+        );
+        let config = other.db.get_config_by_name("rg").unwrap().unwrap();
+        assert_eq!(config.target_path, "~/.ripgreprc");
+    }
+}
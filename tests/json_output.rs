@@ -0,0 +1,102 @@
+//! Global `--json` output, run through the binary
+
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
+
+/// Run hoards with its home, data and config directories inside `home`
+fn hoards(home: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_hoards"))
+        .args(args)
+        .env("HOME", home)
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("XDG_CONFIG_HOME", home.join("config"))
+        .env("XDG_CACHE_HOME", home.join("cache"))
+        .env("XDG_STATE_HOME", home.join("state"))
+        .output()
+        .expect("Failed to run hoards")
+}
+
+/// Run hoards with `--json` and parse what it prints
+fn hoards_json(home: &Path, args: &[&str]) -> Value {
+    let output = hoards(home, &[&["--json"], args].concat());
+    assert!(
+        output.status.success(),
+        "hoards {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("Output is not JSON")
+}
+
+fn keys(value: &Value) -> Vec<&str> {
+    let mut keys: Vec<_> = value
+        .as_object()
+        .expect("Output is not a JSON object")
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn test_json_show_stats_and_bundle_show() {
+    let home = tempfile::tempdir().unwrap();
+    let home = home.path();
+    for args in [
+        &["add", "ripgrep", "--source", "cargo", "--binary", "rg"][..],
+        &["bundle", "create", "search", "ripgrep"],
+    ] {
+        assert!(hoards(home, args).status.success());
+    }
+
+    let show = hoards_json(home, &["show", "ripgrep"]);
+    assert_eq!(
+        keys(&show),
+        vec![
+            "labels",
+            "relations",
+            "release",
+            "repository",
+            "tool",
+            "usage"
+        ]
+    );
+    assert_eq!(show["tool"]["name"], "ripgrep");
+    assert_eq!(show["tool"]["binary_name"], "rg");
+
+    let stats = hoards_json(home, &["stats"]);
+    assert_eq!(
+        keys(&stats),
+        vec![
+            "categories",
+            "favorites",
+            "installed",
+            "known_tools",
+            "missing",
+            "total"
+        ]
+    );
+    assert_eq!(stats["total"], 1);
+
+    let bundle = hoards_json(home, &["bundle", "show", "search"]);
+    assert_eq!(keys(&bundle), vec!["bundle", "members"]);
+    assert_eq!(bundle["bundle"]["name"], "search");
+    assert_eq!(bundle["members"][0]["name"], "ripgrep");
+    assert_eq!(bundle["members"][0]["tracked"], true);
+}
+
+#[test]
+fn test_json_not_found() {
+    let home = tempfile::tempdir().unwrap();
+
+    // Missing items print `null` and exit with the not-found code
+    let output = hoards(home.path(), &["--json", "show", "nothing"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        serde_json::from_slice::<Value>(&output.stdout).unwrap(),
+        Value::Null
+    );
+}